@@ -0,0 +1,192 @@
+//! 二阶 IIR 滤波节(转置直接 II 型)及常用设计式。
+//!
+//! 离线响度的 K-weighting([`crate::envelope`])按模拟原型现场推导系数;均衡器
+//! ([`crate::eq`])用 RBJ Audio EQ Cookbook 的峰值 / 搁架 / 高低通设计式。两者共用同一
+//! 滤波节:系数与状态都用 f64——低频滤波在高采样率下极点紧贴单位圆,f32 精度会让
+//! 状态漂移、低频衰减失真。
+
+/// 二阶 IIR 滤波节。系数已按 a0 归一。
+#[derive(Clone, Debug)]
+pub(crate) struct Biquad {
+    /// 分子系数 z^0。
+    pub(crate) b0: f64,
+    /// 分子系数 z^-1。
+    pub(crate) b1: f64,
+    /// 分子系数 z^-2。
+    pub(crate) b2: f64,
+    /// 分母系数 z^-1。
+    pub(crate) a1: f64,
+    /// 分母系数 z^-2。
+    pub(crate) a2: f64,
+    /// 延迟状态 1。
+    z1: f64,
+    /// 延迟状态 2。
+    z2: f64,
+}
+
+/// RBJ 设计式的公共中间量。
+struct Rbj {
+    /// `cos(w0)`。
+    cos: f64,
+    /// `sin(w0) / 2Q`。
+    alpha: f64,
+    /// 幅度 `10^(dB/40)`(峰值 / 搁架用)。
+    amp: f64,
+}
+
+impl Rbj {
+    /// 按采样率推导;频率钳在 (1Hz, 0.49 × 采样率),Q 至少 0.01,保证滤波稳定。
+    fn new(sample_rate: f64, freq_hz: f64, gain_db: f64, q: f64) -> Self {
+        let freq = freq_hz.clamp(1.0, sample_rate * 0.49);
+        let w0 = 2.0 * std::f64::consts::PI * freq / sample_rate;
+        Self {
+            cos: w0.cos(),
+            alpha: w0.sin() / (2.0 * q.max(0.01)),
+            amp: 10.0f64.powf(gain_db / 40.0),
+        }
+    }
+}
+
+impl Biquad {
+    /// 由未归一系数构造(`b = [b0, b1, b2]`,`a = [a0, a1, a2]`),状态清零。
+    pub(crate) fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        let [b0, b1, b2] = b;
+        let [a0, a1, a2] = a;
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// 峰值(钟形)滤波:中心频率处增益 `gain_db`,带宽由 `q` 决定。
+    pub(crate) fn peaking(sample_rate: f64, freq_hz: f64, gain_db: f64, q: f64) -> Self {
+        let Rbj { cos, alpha, amp } = Rbj::new(sample_rate, freq_hz, gain_db, q);
+        Self::new(
+            [1.0 + alpha * amp, -2.0 * cos, 1.0 - alpha * amp],
+            [1.0 + alpha / amp, -2.0 * cos, 1.0 - alpha / amp],
+        )
+    }
+
+    /// 低频搁架:转折频率以下整体增益 `gain_db`。
+    pub(crate) fn low_shelf(sample_rate: f64, freq_hz: f64, gain_db: f64, q: f64) -> Self {
+        let Rbj { cos, alpha, amp } = Rbj::new(sample_rate, freq_hz, gain_db, q);
+        let k = 2.0 * amp.sqrt() * alpha;
+        Self::new(
+            [
+                amp * ((amp + 1.0) - (amp - 1.0) * cos + k),
+                2.0 * amp * ((amp - 1.0) - (amp + 1.0) * cos),
+                amp * ((amp + 1.0) - (amp - 1.0) * cos - k),
+            ],
+            [
+                (amp + 1.0) + (amp - 1.0) * cos + k,
+                -2.0 * ((amp - 1.0) + (amp + 1.0) * cos),
+                (amp + 1.0) + (amp - 1.0) * cos - k,
+            ],
+        )
+    }
+
+    /// 高频搁架:转折频率以上整体增益 `gain_db`。
+    pub(crate) fn high_shelf(sample_rate: f64, freq_hz: f64, gain_db: f64, q: f64) -> Self {
+        let Rbj { cos, alpha, amp } = Rbj::new(sample_rate, freq_hz, gain_db, q);
+        let k = 2.0 * amp.sqrt() * alpha;
+        Self::new(
+            [
+                amp * ((amp + 1.0) + (amp - 1.0) * cos + k),
+                -2.0 * amp * ((amp - 1.0) + (amp + 1.0) * cos),
+                amp * ((amp + 1.0) + (amp - 1.0) * cos - k),
+            ],
+            [
+                (amp + 1.0) - (amp - 1.0) * cos + k,
+                2.0 * ((amp - 1.0) - (amp + 1.0) * cos),
+                (amp + 1.0) - (amp - 1.0) * cos - k,
+            ],
+        )
+    }
+
+    /// 二阶高通:截止频率以下衰减(`q` = 0.707 为 Butterworth)。
+    pub(crate) fn high_pass(sample_rate: f64, freq_hz: f64, q: f64) -> Self {
+        let Rbj { cos, alpha, .. } = Rbj::new(sample_rate, freq_hz, 0.0, q);
+        Self::new(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// 二阶低通:截止频率以上衰减(`q` = 0.707 为 Butterworth)。
+    pub(crate) fn low_pass(sample_rate: f64, freq_hz: f64, q: f64) -> Self {
+        let Rbj { cos, alpha, .. } = Rbj::new(sample_rate, freq_hz, 0.0, q);
+        Self::new(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+
+    /// 换成 `other` 的系数,保留延迟状态(播中调参不从零起振)。
+    pub(crate) fn retune(&mut self, other: &Self) {
+        self.b0 = other.b0;
+        self.b1 = other.b1;
+        self.b2 = other.b2;
+        self.a1 = other.a1;
+        self.a2 = other.a2;
+    }
+
+    /// 处理一个样本,推进内部状态。
+    pub(crate) fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Biquad;
+
+    /// 正弦过滤波后的稳态峰值幅度(跳过前半段暂态)。
+    fn steady_peak(mut filter: Biquad, freq_hz: f64) -> f64 {
+        let rate = 48_000.0;
+        (0..48_000u32)
+            .map(|i| {
+                let t = f64::from(i) / rate;
+                filter.process((2.0 * std::f64::consts::PI * freq_hz * t).sin())
+            })
+            .skip(24_000)
+            .fold(0.0, |m: f64, y| m.max(y.abs()))
+    }
+
+    /// dB → 线性幅度。
+    fn linear(db: f64) -> f64 {
+        10.0f64.powf(db / 20.0)
+    }
+
+    /// 峰值滤波在中心频率处给出设定增益,远离中心处近乎直通。
+    #[test]
+    fn peaking_hits_gain_at_center() {
+        let at_center = steady_peak(Biquad::peaking(48_000.0, 1_000.0, 6.0, 1.0), 1_000.0);
+        let far = steady_peak(Biquad::peaking(48_000.0, 1_000.0, 6.0, 1.0), 50.0);
+        assert!(
+            (at_center - linear(6.0)).abs() < 0.02,
+            "中心增益:{at_center}"
+        );
+        assert!((far - 1.0).abs() < 0.05, "远端应直通:{far}");
+    }
+
+    /// 搁架在通带一侧整体抬升,另一侧直通;高低通在阻带明显衰减。
+    #[test]
+    fn shelves_and_passes_shape_the_right_side() {
+        let low = steady_peak(Biquad::low_shelf(48_000.0, 200.0, -6.0, 0.707), 40.0);
+        let high = steady_peak(Biquad::high_shelf(48_000.0, 4_000.0, 6.0, 0.707), 15_000.0);
+        assert!((low - linear(-6.0)).abs() < 0.03, "低搁架:{low}");
+        assert!((high - linear(6.0)).abs() < 0.05, "高搁架:{high}");
+        let hp = steady_peak(Biquad::high_pass(48_000.0, 1_000.0, 0.707), 100.0);
+        let lp = steady_peak(Biquad::low_pass(48_000.0, 1_000.0, 0.707), 10_000.0);
+        assert!(hp < 0.02, "高通阻带:{hp}");
+        assert!(lp < 0.02, "低通阻带:{lp}");
+    }
+}
@@ -4,6 +4,11 @@ use std::path::PathBuf;
 
 use mineral_model::{MediaUrl, StreamLayout};
 
+use crate::crossfade::Transition;
+use crate::eq::EqParams;
+use crate::normalize::GainSlot;
+use crate::tempo::SpeedMode;
+
 /// 投递给 engine 主循环的一条指令。
 pub(crate) enum AudioCommand {
     /// 切到这个 URL,从头播。
@@ -40,6 +45,9 @@ pub(crate) enum AudioCommand {
 
         /// 流的容器布局:决定解码器 seekable / 流式打开(分片远端流流式,避免 open 预扫全片)。
         layout: StreamLayout,
+
+        /// 与当前曲的衔接方式(无缝硬接 / 交叉淡化)。
+        transition: Transition,
     },
     /// 撤销「尚未 append 进队列」的待建下一曲(缓冲不及预期时的回退;已 append 则无效)。
     ClearNext,
@@ -51,6 +59,26 @@ pub(crate) enum AudioCommand {
     Stop,
     /// 设置音量(0..=100)。
     SetVolume(u8),
+    /// 改写某槽曲目的归一化增益(dB);播中改写平滑过渡。
+    SetGain {
+        /// 作用目标(当前曲 / 下一曲)。
+        slot: GainSlot,
+
+        /// 增益(dB,0 = 原样)。
+        gain_db: f64,
+    },
+    /// 替换均衡参数(`None` = 旁路);段数不变时保留滤波状态。
+    SetEq(Option<EqParams>),
+    /// 钉住输出设备并热切换(`None` = 跟随默认设备);不丢播放位置。
+    SetDevice(Option<String>),
+    /// 设置播放倍速(已钳进 0.5..=3);时间伸缩保持音高,变调变速交给 rodio。
+    SetSpeed {
+        /// 倍速(1 = 原速)。
+        speed: f32,
+
+        /// 变速方式。
+        mode: SpeedMode,
+    },
     // seek 不走 channel,走 [`crate::handle::AudioHandle`] 的 `Arc<Mutex<Option<Duration>>>`
     // mailbox(latest-wins),engine 主循环每 tick `take()` 一次 —— 长按 ←/→ 时合并。
 }
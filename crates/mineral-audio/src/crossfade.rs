@@ -0,0 +1,512 @@
+//! 交叉淡化:当前曲尾段与已预排下一曲的开头叠混,替代 gapless 的首尾硬接。
+//!
+//! rodio 队列只会顺序播放,故不另起混音路:每首曲的队列条目([`TrackSource`])共享一份
+//! 曲目状态([`Track`]),当前曲进入尾段窗口时**直接从下一曲的共享状态里拉样本**,按曲线
+//! 混进自身输出;当前曲耗尽后队列自然切到下一曲的条目,从淡入已拉到的位置接着播。引擎的
+//! 2-slot 记账([`crate::queue_slots::PlayHead`])与 gapless 完全一致——边界仍是当前曲耗尽
+//! 那一刻,只是此时下一曲已经响了一个淡化时长。
+//!
+//! 叠混只在两曲声道数 / 采样率一致、当前曲总长已知时发生;进窗口时下一曲尚未就绪(预排
+//! 晚了)则本次放弃淡化,退回 gapless。rodio 的播放位置只统计经过队列条目的样本,被前一首
+//! 「预先拉走」的部分由 [`TrackLink::preroll`] 补回。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rodio::source::SeekError;
+use rodio::{ChannelCount, SampleRate, Source};
+
+/// 淡化曲线形状。
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossfadeCurve {
+    /// 线性:两曲增益线性交换;中点合成响度略凹。
+    Linear,
+
+    /// 等功率(正弦 / 余弦):中点合成功率不变,不相关素材听感最平。
+    EqualPower,
+
+    /// S 形(smoothstep):首尾变化缓、中段快,适合节拍型过渡。
+    SCurve,
+}
+
+/// 交叉淡化参数(配置 `audio.crossfade` 的切片)。
+#[non_exhaustive]
+#[derive(Clone, Debug, typed_builder::TypedBuilder, derive_getters::Getters)]
+pub struct CrossfadeParams {
+    /// 是否启用;关闭时一律 gapless。
+    enabled: bool,
+
+    /// 淡化时长(毫秒);为 0 等同关闭。
+    duration_ms: u32,
+
+    /// 淡化曲线。
+    curve: CrossfadeCurve,
+}
+
+/// 一次预排的衔接方式(由 daemon 逐曲决定,同专辑连播保持 gapless)。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transition {
+    /// 首尾无缝硬接。
+    #[default]
+    Gapless,
+
+    /// 按 [`CrossfadeParams`] 叠混(引擎未启用淡化时等同 gapless)。
+    Crossfade,
+}
+
+/// 某曲线在淡化进度 `t`(0..=1)处的 `(淡出增益, 淡入增益)`。
+fn curve_gains(curve: CrossfadeCurve, t: f32) -> (f32, f32) {
+    let t = t.clamp(0.0, 1.0);
+    match curve {
+        CrossfadeCurve::Linear => (1.0 - t, t),
+        CrossfadeCurve::EqualPower => {
+            let a = t * std::f32::consts::FRAC_PI_2;
+            (a.cos(), a.sin())
+        }
+        CrossfadeCurve::SCurve => {
+            let s = t * t * (3.0 - 2.0 * t);
+            (1.0 - s, s)
+        }
+    }
+}
+
+/// 时长折算成交错样本数(帧数 × 声道数)。
+fn duration_to_samples(d: Duration, rate: SampleRate, channels: ChannelCount) -> u64 {
+    let frames = d.as_micros() * u128::from(rate.get()) / 1_000_000;
+    u64::try_from(frames * u128::from(channels.get())).unwrap_or(u64::MAX)
+}
+
+/// 交错样本数折算成时长。
+fn samples_to_duration(samples: u64, rate: SampleRate, channels: ChannelCount) -> Duration {
+    let per_sec = u64::from(rate.get()) * u64::from(channels.get());
+    if per_sec == 0 {
+        return Duration::ZERO;
+    }
+    let micros = u128::from(samples) * 1_000_000 / u128::from(per_sec);
+    Duration::from_micros(u64::try_from(micros).unwrap_or(u64::MAX))
+}
+
+/// 一首曲的共享状态:队列条目与前一首的淡化尾段都从这里拉样本。
+struct Track<S> {
+    /// 内层音频源(已包好归一化)。
+    source: S,
+
+    /// 已拉出的交错样本数(无论经谁拉)。
+    pulled: u64,
+
+    /// 队列条目首次自己拉样本时,前一首已代拉的样本数(rodio 位置的补偿量)。
+    preroll: u64,
+
+    /// 队列条目是否已开始自己拉样本。
+    started: bool,
+}
+
+/// 共享曲目状态句柄。
+type SharedTrack<S> = Arc<Mutex<Track<S>>>;
+
+/// 当前曲尾段淡化的进度。
+enum Fade<S> {
+    /// 尚未进入尾段窗口。
+    Idle,
+
+    /// 正在叠混下一曲。
+    Mixing {
+        /// 下一曲的共享状态。
+        next: SharedTrack<S>,
+
+        /// 本次淡化的总样本数。
+        len: u64,
+
+        /// 已叠混的样本数。
+        done: u64,
+    },
+
+    /// 进窗口时不具备叠混条件(下一曲未就绪 / 格式不一致),本曲不再尝试。
+    Declined,
+}
+
+/// 引擎持有的曲目句柄:给它挂下一曲、读位置补偿。
+pub(crate) struct TrackLink<S> {
+    /// 本曲共享状态。
+    track: SharedTrack<S>,
+
+    /// 本曲尾段要叠混的下一曲(引擎在预排就绪时挂上)。
+    successor: Arc<Mutex<Option<SharedTrack<S>>>>,
+
+    /// 本曲声道数。
+    channels: ChannelCount,
+
+    /// 本曲采样率。
+    rate: SampleRate,
+}
+
+impl<S> TrackLink<S> {
+    /// 把 `next` 挂为本曲尾段的叠混对象;声道 / 采样率不一致时不挂(该次衔接退回 gapless)。
+    ///
+    /// # Return:
+    ///   是否挂上。
+    pub(crate) fn link(&self, next: &Self) -> bool {
+        if self.channels != next.channels || self.rate != next.rate {
+            return false;
+        }
+        *self.successor.lock() = Some(Arc::clone(&next.track));
+        true
+    }
+
+    /// rodio 播放位置之外,本曲已被前一首淡化代拉的时长(叠加到 `get_pos` 上才是真实位置)。
+    pub(crate) fn preroll(&self) -> Duration {
+        let pre = self.track.lock().preroll;
+        samples_to_duration(pre, self.rate, self.channels)
+    }
+}
+
+/// 引擎两槽(当前 / 下一曲)的淡化记账,与 [`crate::queue_slots::PlayHead`] 同步轮转。
+pub(crate) struct Fades<S> {
+    /// 当前曲句柄。
+    cur: Option<TrackLink<S>>,
+
+    /// 已预排下一曲句柄。
+    next: Option<TrackLink<S>>,
+
+    /// 待建 / 已排下一曲的衔接方式(预排发起时记下,就绪挂接时用)。
+    transition: Transition,
+}
+
+impl<S> Default for Fades<S> {
+    fn default() -> Self {
+        Self {
+            cur: None,
+            next: None,
+            transition: Transition::Gapless,
+        }
+    }
+}
+
+impl<S> Fades<S> {
+    /// cut-over 起播:当前槽换成新曲,清掉预排。
+    pub(crate) fn start(&mut self, link: TrackLink<S>) {
+        self.cur = Some(link);
+        self.next = None;
+    }
+
+    /// 发起预排:记下本次衔接方式。
+    pub(crate) fn arm_next(&mut self, transition: Transition) {
+        self.transition = transition;
+        self.next = None;
+    }
+
+    /// 预排曲已 append:按衔接方式挂到当前曲尾段,并占下一曲槽。
+    ///
+    /// # Return:
+    ///   是否将以交叉淡化衔接。
+    pub(crate) fn attach_next(&mut self, link: TrackLink<S>) -> bool {
+        let crossfade = self.transition == Transition::Crossfade
+            && self.cur.as_ref().is_some_and(|cur| cur.link(&link));
+        self.next = Some(link);
+        crossfade
+    }
+
+    /// 边界:下一曲成为当前曲。
+    pub(crate) fn rotate(&mut self) {
+        self.cur = self.next.take();
+    }
+
+    /// 停止:两槽清空。
+    pub(crate) fn clear(&mut self) {
+        self.cur = None;
+        self.next = None;
+    }
+
+    /// 当前曲的位置补偿(见 [`TrackLink::preroll`])。
+    pub(crate) fn preroll(&self) -> Duration {
+        self.cur.as_ref().map_or(Duration::ZERO, TrackLink::preroll)
+    }
+}
+
+/// 队列条目:从共享状态拉本曲样本,尾段窗口内叠混下一曲。
+pub(crate) struct TrackSource<S> {
+    /// 本曲共享状态。
+    track: SharedTrack<S>,
+
+    /// 尾段要叠混的下一曲(引擎经 [`TrackLink::link`] 挂上)。
+    successor: Arc<Mutex<Option<SharedTrack<S>>>>,
+
+    /// 淡化窗口(交错样本数;0 = 本曲不淡化)。
+    window: u64,
+
+    /// 本曲总样本数(探不出总长为 `None`,不淡化)。
+    total: Option<u64>,
+
+    /// 淡化曲线。
+    curve: CrossfadeCurve,
+
+    /// 本曲声道数。
+    channels: ChannelCount,
+
+    /// 本曲采样率。
+    rate: SampleRate,
+
+    /// 尾段淡化进度。
+    fade: Fade<S>,
+}
+
+/// 把一首曲包成队列条目 + 引擎句柄。
+///
+/// # Params:
+///   - `source`: 本曲音频源
+///   - `params`: 交叉淡化参数(未启用时窗口为 0,条目只是透传)
+///
+/// # Return:
+///   `(队列条目, 引擎句柄)`。
+pub(crate) fn track<S>(source: S, params: &CrossfadeParams) -> (TrackSource<S>, TrackLink<S>)
+where
+    S: Source<Item = f32>,
+{
+    let channels = source.channels();
+    let rate = source.sample_rate();
+    let total = source
+        .total_duration()
+        .map(|d| duration_to_samples(d, rate, channels));
+    let window = if params.enabled {
+        let want = duration_to_samples(
+            Duration::from_millis(u64::from(params.duration_ms)),
+            rate,
+            channels,
+        );
+        // 短曲不让淡化吃掉一半以上。
+        total.map_or(0, |t| want.min(t / 2))
+    } else {
+        0
+    };
+    let shared = Arc::new(Mutex::new(Track {
+        source,
+        pulled: 0,
+        preroll: 0,
+        started: false,
+    }));
+    let successor = Arc::new(Mutex::new(None));
+    let entry = TrackSource {
+        track: Arc::clone(&shared),
+        successor: Arc::clone(&successor),
+        window,
+        total,
+        curve: params.curve,
+        channels,
+        rate,
+        fade: Fade::Idle,
+    };
+    let link = TrackLink {
+        track: shared,
+        successor,
+        channels,
+        rate,
+    };
+    (entry, link)
+}
+
+impl<S> TrackSource<S>
+where
+    S: Source<Item = f32>,
+{
+    /// 在帧边界上判断是否进入尾段窗口;进窗口即定夺叠混与否。
+    ///
+    /// # Params:
+    ///   - `pulled`: 本样本拉出前的已拉样本数
+    fn maybe_enter_window(&mut self, pulled: u64) {
+        let Fade::Idle = self.fade else {
+            return;
+        };
+        let Some(total) = self.total else {
+            return;
+        };
+        if self.window == 0 || !pulled.is_multiple_of(u64::from(self.channels.get())) {
+            return;
+        }
+        let remaining = total.saturating_sub(pulled);
+        if remaining > self.window {
+            return;
+        }
+        self.fade = match self.successor.lock().clone() {
+            Some(next) if remaining > 0 => Fade::Mixing {
+                next,
+                len: remaining,
+                done: 0,
+            },
+            _ => Fade::Declined,
+        };
+    }
+}
+
+impl<S> Iterator for TrackSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (sample, pulled) = {
+            let mut t = self.track.lock();
+            if !t.started {
+                t.started = true;
+                t.preroll = t.pulled;
+            }
+            let pulled = t.pulled;
+            let sample = t.source.next()?;
+            t.pulled += 1;
+            (sample, pulled)
+        };
+        self.maybe_enter_window(pulled);
+        let Fade::Mixing { next, len, done } = &mut self.fade else {
+            return Some(sample);
+        };
+        #[allow(clippy::as_conversions)] // reason: 淡化进度比例,精度损失无碍
+        let t = *done as f32 / *len as f32;
+        *done += 1;
+        let (out_gain, in_gain) = curve_gains(self.curve, t);
+        let incoming = {
+            let mut n = next.lock();
+            let s = n.source.next();
+            if s.is_some() {
+                n.pulled += 1;
+            }
+            s.unwrap_or(0.0)
+        };
+        Some(sample * out_gain + incoming * in_gain)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+impl<S> Source for TrackSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        self.track.lock().source.current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total
+            .map(|t| samples_to_duration(t, self.rate, self.channels))
+    }
+
+    /// 透传给本曲 decoder 并校正已拉样本数;淡化中被 seek 走时把下一曲倒回开头、重新等窗口。
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        {
+            let mut t = self.track.lock();
+            t.source.try_seek(pos)?;
+            t.pulled = duration_to_samples(pos, self.rate, self.channels);
+            // seek 后 rodio 位置即为目标位置,不再需要补偿。
+            t.preroll = 0;
+        }
+        if let Fade::Mixing { next, .. } = std::mem::replace(&mut self.fade, Fade::Idle) {
+            let mut n = next.lock();
+            if let Err(e) = n.source.try_seek(Duration::ZERO) {
+                mineral_log::debug!(target: "audio", error = %e, "淡化中 seek:下一曲倒回开头失败");
+            }
+            n.pulled = 0;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32};
+    use std::time::Duration;
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{CrossfadeCurve, CrossfadeParams, curve_gains, track};
+
+    /// 单声道 1kHz 的常值缓冲(1 样本 = 1ms,便于按毫秒推算)。
+    fn buffer(value: f32, len: usize) -> SamplesBuffer {
+        SamplesBuffer::new(
+            NonZeroU16::MIN,
+            NonZeroU32::new(1_000).unwrap_or(NonZeroU32::MIN),
+            vec![value; len],
+        )
+    }
+
+    /// 测试用淡化参数。
+    fn params(enabled: bool, duration_ms: u32) -> CrossfadeParams {
+        CrossfadeParams::builder()
+            .enabled(enabled)
+            .duration_ms(duration_ms)
+            .curve(CrossfadeCurve::Linear)
+            .build()
+    }
+
+    /// 三种曲线首尾一致(1,0)→(0,1);等功率中点功率守恒。
+    #[test]
+    fn curves_swap_gains_end_to_end() {
+        for curve in [
+            CrossfadeCurve::Linear,
+            CrossfadeCurve::EqualPower,
+            CrossfadeCurve::SCurve,
+        ] {
+            let (o0, i0) = curve_gains(curve, 0.0);
+            let (o1, i1) = curve_gains(curve, 1.0);
+            assert!((o0 - 1.0).abs() < 1e-6 && i0.abs() < 1e-6, "{curve:?} 起点");
+            assert!(o1.abs() < 1e-6 && (i1 - 1.0).abs() < 1e-6, "{curve:?} 终点");
+        }
+        let (o, i) = curve_gains(CrossfadeCurve::EqualPower, 0.5);
+        assert!((o * o + i * i - 1.0).abs() < 1e-5, "等功率中点功率应守恒");
+    }
+
+    /// 挂上下一曲:当前曲最后 100ms 叠混,下一曲条目从淡入拉到的位置接着播,位置补偿 100ms。
+    #[test]
+    fn tail_mixes_next_head_and_hands_over() {
+        let (cur, cur_link) = track(buffer(1.0, 1_000), &params(true, 100));
+        let (next, next_link) = track(buffer(0.5, 1_000), &params(true, 100));
+        assert!(cur_link.link(&next_link));
+        let out: Vec<f32> = cur.collect();
+        assert_eq!(out.len(), 1_000, "当前曲长度不变");
+        assert!(
+            out.iter().take(900).all(|s| (s - 1.0).abs() < 1e-6),
+            "窗口前原样"
+        );
+        let last = out.last().copied().unwrap_or_default();
+        assert!((last - 0.5).abs() < 0.02, "淡化末尾应几乎全是下一曲:{last}");
+        let rest: Vec<f32> = next.collect();
+        assert_eq!(rest.len(), 900, "下一曲应从已拉走的 100 样本之后接续");
+        assert_eq!(next_link.preroll(), Duration::from_millis(100));
+    }
+
+    /// 未挂下一曲(预排晚了)或未启用:原样输出,不叠混。
+    #[test]
+    fn declines_without_successor_or_when_disabled() {
+        let (cur, _link) = track(buffer(1.0, 500), &params(true, 100));
+        assert!(cur.into_iter().all(|s| (s - 1.0).abs() < 1e-6));
+
+        let (cur, cur_link) = track(buffer(1.0, 500), &params(false, 100));
+        let (_next, next_link) = track(buffer(0.5, 500), &params(false, 100));
+        assert!(cur_link.link(&next_link));
+        assert!(cur.into_iter().all(|s| (s - 1.0).abs() < 1e-6));
+    }
+
+    /// 声道 / 采样率不一致不挂(退回 gapless)。
+    #[test]
+    fn mismatched_formats_do_not_link() {
+        let (_cur, cur_link) = track(buffer(1.0, 500), &params(true, 100));
+        let stereo = SamplesBuffer::new(
+            NonZeroU16::new(2).unwrap_or(NonZeroU16::MIN),
+            NonZeroU32::new(1_000).unwrap_or(NonZeroU32::MIN),
+            vec![0.5; 1_000],
+        );
+        let (_next, next_link) = track(stereo, &params(true, 100));
+        assert!(!cur_link.link(&next_link));
+    }
+}
@@ -0,0 +1,45 @@
+//! 解码入口:本地文件 / 远端流 reader 统一装箱,按已知字节长度构造 seekable decoder。
+//!
+//! 播放引擎与离线分析(包络 / 响度)共用同一套打开与解码路径。
+
+use std::io::{BufReader, Read, Seek};
+
+use color_eyre::eyre::eyre;
+use rodio::decoder::DecoderBuilder;
+
+/// `Read + Seek + Send + Sync` 的对象安全别名:把不同 `StorageProvider` 的 reader
+/// (远端流 / 本地文件)装箱成同一类型,链下建好的下一曲 reader 经统一通道交回引擎线程。
+///
+/// `Box<dyn ReadSeek>` 经 std 的 `impl<R: Read+?Sized> Read for Box<R>`(Seek 同理)自动
+/// 获得 Read/Seek(`dyn ReadSeek` 含超 trait),无需手写转发 impl。
+pub(crate) trait ReadSeek: Read + Seek + Send + Sync {}
+impl<T: Read + Seek + Send + Sync> ReadSeek for T {}
+
+/// 打开本地文件成装箱 reader(+ 已知字节长度,供 decoder seekable)。
+pub(crate) fn open_local(
+    p: &std::path::Path,
+) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)> {
+    let file = std::fs::File::open(p).map_err(|e| eyre!("open {}: {e}", p.display()))?;
+    let byte_len = file.metadata().ok().map(|m| m.len());
+    Ok((Box::new(BufReader::new(file)), byte_len))
+}
+
+/// 用 [`DecoderBuilder`] 构造 decoder,**`byte_len` 已知时一并塞进**。
+///
+/// 关键:rodio `Decoder::new()` 默认 `is_seekable=false`,Symphonia 在源不可
+/// 随机访问时只能向前 seek(后退会返 `ForwardOnly` → `RandomAccessNotSupported`)
+/// —— 表现就是按 ← 没反应。`with_byte_len` 会一并把 `is_seekable` 置 true。
+/// `byte_len` 未知时退化到默认行为(只能向前 seek),至少不比之前差。
+pub(crate) fn build_decoder<R>(
+    reader: R,
+    byte_len: Option<u64>,
+) -> color_eyre::Result<rodio::Decoder<R>>
+where
+    R: Read + Seek + Send + Sync + 'static,
+{
+    let mut builder = DecoderBuilder::new().with_data(reader);
+    if let Some(len) = byte_len {
+        builder = builder.with_byte_len(len);
+    }
+    builder.build().map_err(|e| eyre!("decode: {e}"))
+}
@@ -1,14 +1,17 @@
 //! 引擎线程主体:owns rodio device sink + Player + 内嵌 tokio runtime。
 //!
-//! 命令通道处理 play/append_next/clear_next/pause/resume/stop/set_volume(语义不可合并)。
+//! 命令通道处理 play/append_next/clear_next/pause/resume/stop/set_volume/set_eq/set_device/
+//! set_speed(语义不可合并)。变速后位置按曲内媒体时间报告([`crate::tempo`])。
+//! 输出设备经 [`crate::output`] 中继,可运行期热切换、拔出自动回落;
+//! [`AudioMode::File`] 下换成 [`crate::file_sink`],每 tick 把队列输出渲染进文件。
 //! seek 单独走 [`crate::handle::AudioHandle`] → mailbox(latest-wins),engine 每个 tick
 //! `take()` 一次实际打 demuxer ——抗住长按 ←/→ 的 30Hz key-repeat。
 //!
 //! gapless:除「当前曲」外可多排一首「下一曲」decoder 进 rodio 队列([`crate::queue_slots`]
 //! 的 [`PlayHead`] 记账),当前曲自然耗尽时 rodio 零静音接续。预排远端曲的建流 / 预缓冲在
 //! runtime 上**链下**进行,就绪后经通道交回引擎线程 build decoder + `append`,不阻塞命令线程。
+//! 预排时 daemon 指定衔接方式:交叉淡化由当前曲尾段直接叠混下一曲([`crate::crossfade`])。
 
-use std::io::{BufReader, Read, Seek};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -19,25 +22,22 @@ use color_eyre::eyre::eyre;
 use mineral_model::{MediaUrl, StreamLayout};
 use parking_lot::Mutex;
 use rodio::Source;
-use rodio::decoder::DecoderBuilder;
-use stream_download::Settings;
-use stream_download::StreamDownload;
-use stream_download::StreamPhase;
-use stream_download::StreamState;
-use stream_download::http::HttpStream;
-use stream_download::http::reqwest::Client;
-use stream_download::source::SourceStream;
-use stream_download::storage::StorageProvider;
-use stream_download::storage::temp::TempStorageProvider;
 
 use crate::bps::Bps;
 use crate::command::AudioCommand;
-use crate::file_storage::FileStorageProvider;
+use crate::crossfade::{self, CrossfadeParams, Fades, Transition};
+use crate::decode::{ReadSeek, build_decoder, open_local};
+use crate::eq::{EqChain, EqSource, SharedEq};
+use crate::file_sink::FileSink;
 use crate::handle::{AudioMode, EngineParams};
-use crate::policy::{download_reached_full, effective_byte_len};
+use crate::normalize::{LimiterParams, NormalizeSource, TrackGains};
+use crate::output::Output;
+use crate::policy::effective_byte_len;
 use crate::queue_slots::{Boundary, PlayHead, SharedProgress, Slot};
 use crate::snapshot::{AudioBackend, AudioSnapshot};
+use crate::stream::{StreamTarget, create_stream};
 use crate::tap::{SharedProd, TapSource};
+use crate::tempo::{SharedStretch, SpeedMode, TempoSource, TrackClocks, shared_stretch};
 
 /// 把 0..=100 的 pct 映射成 rodio 的线性 gain(0.0..=1.0),走 cubic 感知曲线。
 ///
@@ -49,13 +49,8 @@ fn pct_to_gain(pct: u8) -> f32 {
     p * p * p
 }
 
-/// `Read + Seek + Send + Sync` 的对象安全别名:把不同 `StorageProvider` 的 reader
-/// (远端流 / 本地文件)装箱成同一类型,链下建好的下一曲 reader 经统一通道交回引擎线程。
-///
-/// `Box<dyn ReadSeek>` 经 std 的 `impl<R: Read+?Sized> Read for Box<R>`(Seek 同理)自动
-/// 获得 Read/Seek(`dyn ReadSeek` 含超 trait),无需手写转发 impl。
-pub(crate) trait ReadSeek: Read + Seek + Send + Sync {}
-impl<T: Read + Seek + Send + Sync> ReadSeek for T {}
+/// 引擎里一首曲的音频源(decoder 外包归一化),交叉淡化在它外层叠混。
+type Decoded = NormalizeSource<rodio::Decoder<Box<dyn ReadSeek>>>;
 
 /// 链下建好的下一曲:reader 已就绪(预缓冲完成),交回引擎线程 build decoder + append。
 struct NextBuilt {
@@ -92,6 +87,9 @@ pub(crate) struct EngineIo {
 
     /// 当前出声曲目的采样率原子(UI spectrum 读)。
     pub(crate) sr_atomic: Arc<AtomicU32>,
+
+    /// 当前输出设备名(null 模式为 `None`)。
+    pub(crate) device: Arc<Mutex<Option<String>>>,
 }
 
 /// 引擎线程入口。
@@ -109,6 +107,7 @@ pub(crate) fn run(
 /// 引擎主循环:初始化 sink/runtime,失败时通过 `ready_tx` 上报,然后循环 recv 命令 +
 /// drain 链下建好的下一曲 + drain seek + 刷 snapshot。
 ///
+/// [`AudioMode::File`] 的文件建不出来算真错(配置写错路径),经 `ready_tx` 上报。
 /// 无音频设备(或 [`AudioMode::ForceNull`])不算错:置 [`AudioBackend::Null`]、报 ready、进
 /// [`run_null_mode`] 空跑——daemon 照常 bind / serve / graceful shutdown,client 据 snapshot 提示降级。
 fn engine_main(
@@ -117,29 +116,42 @@ fn engine_main(
     mode: AudioMode,
     params: &EngineParams,
 ) -> color_eyre::Result<()> {
-    let sink = match mode {
+    let (player, queue) = rodio::Player::new();
+    let output = match mode {
         AudioMode::ForceNull => None,
-        AudioMode::Auto => match rodio::DeviceSinkBuilder::open_default_sink() {
-            Ok(s) => Some(s),
+        AudioMode::File { path, realtime } => match FileSink::create(queue, &path, realtime) {
+            Ok(f) => Some(Sink::File(f)),
+            Err(e) => {
+                let msg = format!("{e:#}");
+                let _ = io.ready_tx.send(Err(e));
+                return Err(eyre!(msg));
+            }
+        },
+        AudioMode::Auto => match Output::open(queue, params.device().clone()) {
+            Ok(o) => Some(Sink::Device(o)),
             Err(e) => {
                 mineral_log::warn!(
                     target: "audio",
-                    error = mineral_log::chain(&eyre!("rodio device sink: {e}")),
+                    error = mineral_log::chain(&e),
                     "no audio device; running in null mode (no sound)"
                 );
                 None
             }
         },
     };
-    let Some(mut stream_handle) = sink else {
+    let Some(output) = output else {
         io.snapshot.lock().backend = AudioBackend::Null;
         let _ = io.ready_tx.send(Ok(()));
         return run_null_mode(cmd_rx);
     };
-    // 默认 drop 时会向 stderr 打一行 "Audio playback has finished",TUI 退出后会污染终端,关掉。
-    stream_handle.log_on_drop(false);
+    match &output {
+        Sink::Device(o) => *io.device.lock() = Some(o.device().to_owned()),
+        Sink::File(f) => {
+            mineral_log::info!(target: "audio", path = %f.path().display(), "rendering to file (no sound)");
+            io.snapshot.lock().backend = AudioBackend::File;
+        }
+    }
 
-    let player = rodio::Player::connect_new(stream_handle.mixer());
     player.set_volume(pct_to_gain(*params.initial_volume()));
 
     // multi_thread:stream-download 后台下载 task 必须在独立 worker 上持续被 poll,
@@ -162,13 +174,7 @@ fn engine_main(
     let _ = io.ready_tx.send(Ok(()));
 
     let tick = Duration::from_millis(*params.tick_ms());
-    let mut engine = Engine::new(
-        &player,
-        &rt,
-        &io.tap_producer,
-        &io.sr_atomic,
-        *params.prefetch_bytes(),
-    );
+    let mut engine = Engine::new(&player, &rt, output, io, params);
 
     loop {
         match cmd_rx.recv_timeout(tick) {
@@ -177,6 +183,18 @@ fn engine_main(
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         }
         engine.drain_next_built();
+        match &mut engine.output {
+            Sink::Device(o) => {
+                if o.maintain() {
+                    *io.device.lock() = Some(o.device().to_owned());
+                }
+            }
+            Sink::File(f) => {
+                if let Err(e) = f.render(&player) {
+                    mineral_log::warn!(target: "audio", error = mineral_log::chain(&e), "写音频文件失败");
+                }
+            }
+        }
         drain_seek(&io.seek_mailbox, &player);
         engine.update_snapshot(&io.snapshot);
     }
@@ -193,6 +211,15 @@ fn run_null_mode(cmd_rx: &mpsc::Receiver<AudioCommand>) -> color_eyre::Result<()
     Ok(())
 }
 
+/// 引擎的出口:声卡(可热切换)或文件。
+enum Sink {
+    /// 声卡输出。
+    Device(Output),
+
+    /// 文件出口(不发声)。
+    File(FileSink),
+}
+
 /// 引擎跨 tick 的可变状态 + 不可变依赖(player / rt / tap / 进度载体 / 链下结果通道)。
 struct Engine<'a> {
     /// rodio 播放器(队列)。
@@ -233,6 +260,33 @@ struct Engine<'a> {
 
     /// 流式播放起播前预拉的字节数(配置 `audio.prefetch_bytes`)。
     prefetch_bytes: u64,
+
+    /// 峰值限幅器参数(每首曲包 [`NormalizeSource`] 时用)。
+    limiter: LimiterParams,
+
+    /// 当前 / 下一曲的归一化增益,与 `head` 同步轮转。
+    gains: TrackGains,
+
+    /// 交叉淡化参数(每首曲包 [`crossfade::TrackSource`] 时用)。
+    crossfade: CrossfadeParams,
+
+    /// 当前 / 下一曲的淡化句柄,与 `head` 同步轮转。
+    fades: Fades<Decoded>,
+
+    /// 跨曲共享的均衡器(每首曲包 [`EqSource`] 时用)。
+    eq: SharedEq,
+
+    /// 跨曲共享的时间伸缩倍速(每首曲包 [`TempoSource`] 时用)。
+    stretch: SharedStretch,
+
+    /// 当前 / 下一曲的媒体时钟,与 `head` 同步轮转。
+    clocks: TrackClocks,
+
+    /// 输出端(当前设备 + 播放队列中继,或文件出口)。
+    output: Sink,
+
+    /// 当前输出设备名的共享槽(切换后写回,handle 读)。
+    device: Arc<Mutex<Option<String>>>,
 }
 
 impl<'a> Engine<'a> {
@@ -240,16 +294,16 @@ impl<'a> Engine<'a> {
     fn new(
         player: &'a rodio::Player,
         rt: &'a tokio::runtime::Runtime,
-        tap_producer: &SharedProd,
-        sr_atomic: &Arc<AtomicU32>,
-        prefetch_bytes: u64,
+        output: Sink,
+        io: &EngineIo,
+        params: &EngineParams,
     ) -> Self {
         let (next_built_tx, next_built_rx) = mpsc::channel();
         Self {
             player,
             rt,
-            tap_producer: Arc::clone(tap_producer),
-            sr_atomic: Arc::clone(sr_atomic),
+            tap_producer: Arc::clone(&io.tap_producer),
+            sr_atomic: Arc::clone(&io.sr_atomic),
             progress: Arc::new(SharedProgress::default()),
             next_built_tx,
             next_built_rx,
@@ -258,7 +312,16 @@ impl<'a> Engine<'a> {
             pending_next_gen: 0,
             cur_sample_rate: 0,
             next_sample_rate: 0,
-            prefetch_bytes,
+            prefetch_bytes: *params.prefetch_bytes(),
+            limiter: params.limiter().clone(),
+            gains: TrackGains::default(),
+            crossfade: params.crossfade().clone(),
+            fades: Fades::default(),
+            eq: EqChain::shared(params.eq().clone()),
+            stretch: shared_stretch(),
+            clocks: TrackClocks::default(),
+            output,
+            device: Arc::clone(&io.device),
         }
     }
 
@@ -286,12 +349,33 @@ impl<'a> Engine<'a> {
                 headers,
                 capture,
                 layout,
-            } => self.append_next(url, headers, capture, layout),
+                transition,
+            } => self.append_next(url, headers, capture, layout, transition),
             AudioCommand::ClearNext => self.clear_next(),
             AudioCommand::Pause => self.player.pause(),
             AudioCommand::Resume => self.player.play(),
             AudioCommand::Stop => self.stop(),
             AudioCommand::SetVolume(pct) => self.player.set_volume(pct_to_gain(pct)),
+            AudioCommand::SetGain { slot, gain_db } => self.gains.set(slot, gain_db),
+            AudioCommand::SetEq(params) => self.eq.lock().set(params),
+            AudioCommand::SetDevice(name) => {
+                let Sink::Device(output) = &mut self.output else {
+                    mineral_log::warn!(target: "audio", "文件出口下忽略切换输出设备");
+                    return;
+                };
+                if let Err(e) = output.switch(name) {
+                    mineral_log::warn!(target: "audio", error = mineral_log::chain(&e), "切换输出设备失败");
+                }
+                *self.device.lock() = Some(output.device().to_owned());
+            }
+            AudioCommand::SetSpeed { speed, mode } => {
+                let (stretch, resample) = match mode {
+                    SpeedMode::PitchShift => (1.0, speed),
+                    SpeedMode::TimeStretch => (speed, 1.0),
+                };
+                self.stretch.store(f32::to_bits(stretch), Ordering::Relaxed);
+                self.player.set_speed(resample);
+            }
         }
     }
 
@@ -341,13 +425,14 @@ impl<'a> Engine<'a> {
     }
 
     /// 预排下一曲:占用另一进度槽,远端走链下建流(就绪后 drain 才 append),本地立即排进通道。
-    /// 当前无曲在播时忽略(上层不该在停止态预排)。
+    /// 当前无曲在播时忽略(上层不该在停止态预排)。`transition` 在就绪 append 时生效。
     fn append_next(
         &mut self,
         url: MediaUrl,
         headers: Vec<(String, String)>,
         capture: Option<PathBuf>,
         layout: StreamLayout,
+        transition: Transition,
     ) {
         if !self.head.cur.occupied {
             return;
@@ -364,7 +449,9 @@ impl<'a> Engine<'a> {
         };
         self.reset_progress(idx, track_gen);
         self.pending_next_gen = track_gen;
-        mineral_log::debug!(target: "audio", url = %url, stream_gen = track_gen, "append next (prefetch)");
+        self.gains.arm_next();
+        self.fades.arm_next(transition);
+        mineral_log::debug!(target: "audio", url = %url, stream_gen = track_gen, ?transition, "append next (prefetch)");
         match url {
             MediaUrl::Remote(u) => {
                 let tx = self.next_built_tx.clone();
@@ -425,6 +512,8 @@ impl<'a> Engine<'a> {
         self.head.cur.occupied = false;
         self.head.next.occupied = false;
         self.player.stop();
+        self.fades.clear();
+        self.clocks = TrackClocks::default();
         self.pending_next_gen = 0;
     }
 
@@ -441,7 +530,7 @@ impl<'a> Engine<'a> {
     ///   `(duration_ms, sample_rate, is_local)`;`duration_ms` 为 `None` = decoder 探不出总长
     ///   (分片容器流式打开)。
     fn build_and_append_blocking(
-        &self,
+        &mut self,
         url: MediaUrl,
         headers: Vec<(String, String)>,
         capture: Option<PathBuf>,
@@ -478,8 +567,15 @@ impl<'a> Engine<'a> {
             target: "audio", slot = "cur", sample_rate = sr, dur_ms = ?dur_ms,
             byte_len_known = byte_len.is_some(), "decoder ready"
         );
-        self.player
-            .append(TapSource::new(decoder, Arc::clone(&self.tap_producer)));
+        let gain = self.gains.arm_current();
+        let source = NormalizeSource::new(decoder, gain, &self.limiter);
+        let (entry, link) = crossfade::track(source, &self.crossfade);
+        self.fades.start(link);
+        let entry = TempoSource::new(entry, Arc::clone(&self.stretch), self.clocks.arm_current());
+        self.player.append(TapSource::new(
+            EqSource::new(entry, Arc::clone(&self.eq)),
+            Arc::clone(&self.tap_producer),
+        ));
         Ok((dur_ms, sr, local))
     }
 
@@ -503,8 +599,16 @@ impl<'a> Engine<'a> {
                         target: "audio", slot = "next", sample_rate = self.next_sample_rate,
                         dur_ms = ?dur_ms, byte_len_known, "decoder ready (prefetch)"
                     );
-                    self.player
-                        .append(TapSource::new(decoder, Arc::clone(&self.tap_producer)));
+                    let source = NormalizeSource::new(decoder, self.gains.next(), &self.limiter);
+                    let (entry, link) = crossfade::track(source, &self.crossfade);
+                    let crossfade = self.fades.attach_next(link);
+                    mineral_log::debug!(target: "audio", crossfade, "next armed");
+                    let entry =
+                        TempoSource::new(entry, Arc::clone(&self.stretch), self.clocks.arm_next());
+                    self.player.append(TapSource::new(
+                        EqSource::new(entry, Arc::clone(&self.eq)),
+                        Arc::clone(&self.tap_producer),
+                    ));
                     if built.local_full {
                         self.progress
                             .slot(built.progress_idx)
@@ -529,15 +633,20 @@ impl<'a> Engine<'a> {
 
     /// 把 player 当前播放状态拍进共享 snapshot,顺带观测 `len()` 推进 [`PlayHead`] 边界。
     fn update_snapshot(&mut self, snapshot: &Arc<Mutex<AudioSnapshot>>) {
-        let pos_ms = duration_to_ms(self.player.get_pos());
         let is_paused = self.player.is_paused();
         let boundary = self.head.observe(self.player.len());
         if boundary == Boundary::Gapless {
-            // 下一曲已轮转成当前曲:此刻才把采样率切过去,频谱不提前跳。
+            // 下一曲已轮转成当前曲:此刻才把采样率切过去,频谱不提前跳;增益 / 淡化槽同步轮转。
+            self.gains.rotate();
+            self.fades.rotate();
+            self.clocks.rotate();
             self.cur_sample_rate = self.next_sample_rate;
             self.sr_atomic
                 .store(self.cur_sample_rate, Ordering::Relaxed);
         }
+        // 位置取本曲媒体时钟(变速下 rodio 数的是墙钟);交叉淡化接上的曲已被前一首代拉了
+        // 一段,时钟不含这段,补上才是真实位置。
+        let pos_ms = duration_to_ms(self.clocks.position() + self.fades.preroll());
         let playing = !is_paused && self.head.cur.occupied;
         let f = self.head.snapshot_fields(&self.progress);
 
@@ -564,288 +673,19 @@ fn drain_seek(seek_mailbox: &Arc<Mutex<Option<Duration>>>, player: &rodio::Playe
     let Some(target) = seek_mailbox.lock().take() else {
         return;
     };
-    if let Err(e) = player.try_seek(target) {
-        mineral_log::warn!(target: "audio", seek_to = ?target, error = mineral_log::chain(&e), "seek failed");
-    }
-}
-
-/// 打开本地文件成装箱 reader(+ 已知字节长度,供 decoder seekable)。
-pub(crate) fn open_local(
-    p: &std::path::Path,
-) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)> {
-    let file = std::fs::File::open(p).map_err(|e| eyre!("open {}: {e}", p.display()))?;
-    let byte_len = file.metadata().ok().map(|m| m.len());
-    Ok((Box::new(BufReader::new(file)), byte_len))
-}
-
-/// 取流目标:远端音频 URL + 附加请求头(如 B站 baseUrl 需 `Referer`)。二者是「怎么取这条流」
-/// 的一体两面,合并成一个参数,避免建流函数参数膨胀。
-struct StreamTarget {
-    /// 远端音频 URL。
-    url: url::Url,
-
-    /// 取流附加请求头;空 = 无附加头,走默认无头 client。
-    headers: Vec<(String, String)>,
-}
-
-/// 起 stream-download(远端):建 HTTP 流、装好缓冲进度回调(写指定进度槽,代号门控挡旧流)、
-/// capture(非空)时 spawn 完成 waiter store 下完代号,返回装箱 reader + 字节长度。
-///
-/// # Params:
-///   - `target`: 取流目标(远端 URL + 取流头)
-///   - `capture`: 落盘路径(`Some` = 持久 capture 供入缓存,`None` = 会自删的 temp)
-///   - `stream_gen`: 本流代号
-///   - `progress_idx`: 写入的进度槽下标
-///   - `progress`: 双槽共享进度
-///   - `prefetch_bytes`: 起播前预拉的字节数(配置 `audio.prefetch_bytes`)
-///
-/// # Return:
-///   `(装箱 reader, 字节长度)`;字节长度 `None` 表示无 `Content-Length`。
-async fn create_stream(
-    target: StreamTarget,
-    capture: Option<PathBuf>,
-    stream_gen: u64,
-    progress_idx: usize,
-    progress: Arc<SharedProgress>,
-    prefetch_bytes: u64,
-) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)> {
-    match capture {
-        Some(path) => {
-            stream_with_provider(
-                target,
-                FileStorageProvider::new(path.clone()),
-                stream_gen,
-                progress_idx,
-                progress,
-                // 下完 waiter 核对该落盘文件字节数达 content_length 才标下完(挡截断)。
-                /*verify_path*/
-                Some(path),
-                prefetch_bytes,
-            )
-            .await
-        }
-        None => {
-            stream_with_provider(
-                target,
-                TempStorageProvider::new(),
-                stream_gen,
-                progress_idx,
-                progress,
-                /*verify_path*/ None,
-                prefetch_bytes,
-            )
-            .await
-        }
-    }
-}
-
-/// 把 `(name, value)` 头烤进一个 reqwest client 的 `default_headers`,供取流请求带上。
-///
-/// 用 `append`(非 `insert`):保序、允许重复同名头。非法头(名/值不合 HTTP 规范)跳过并 warn,
-/// 不掀掉整条请求;client build 失败(TLS 初始化等真错)冒泡。
-///
-/// # Params:
-///   - `headers`: 待注入的请求头(调用方已保证非空)
-///
-/// # Return:
-///   预配置好 `default_headers` 的 reqwest client
-fn client_with_headers(headers: &[(String, String)]) -> color_eyre::Result<Client> {
-    use stream_download::http::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-
-    let mut map = HeaderMap::new();
-    for (name, value) in headers {
-        match (
-            HeaderName::from_bytes(name.as_bytes()),
-            HeaderValue::from_str(value),
-        ) {
-            (Ok(n), Ok(v)) => {
-                map.append(n, v);
-            }
-            _ => mineral_log::warn!(target: "audio", header = %name, "跳过非法取流头"),
-        }
-    }
-    Client::builder()
-        .default_headers(map)
-        .build()
-        .map_err(|e| eyre!("build headed http client: {e}"))
-}
-
-/// 用给定 `StorageProvider` 起 stream-download(两种 provider 走同一泛型路径,差别只在 `provider`)。
-///
-/// # Params:
-///   - `verify_path`: capture 落盘路径(`Some` = capture 播放,spawn waiter 等下完并**核对字节数**;
-///     `None` = 非 capture,不 spawn waiter)
-///   - `prefetch_bytes`: 起播前预拉的字节数(配置 `audio.prefetch_bytes`)
-async fn stream_with_provider<P>(
-    target: StreamTarget,
-    provider: P,
-    stream_gen: u64,
-    progress_idx: usize,
-    progress: Arc<SharedProgress>,
-    verify_path: Option<PathBuf>,
-    prefetch_bytes: u64,
-) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)>
-where
-    P: StorageProvider + 'static,
-    P::Reader: Read + Seek + Send + Sync + 'static,
-{
-    let StreamTarget { url, headers } = target;
-    // 空头走默认 client(零行为变化,netease/local 保持原路径);带头则建一个把这些头烤进
-    // `default_headers` 的 reqwest client——B站 baseUrl 取流必须带 `Referer`,否则 403。
-    let stream = if headers.is_empty() {
-        HttpStream::<Client>::create(url)
-            .await
-            .map_err(|e| eyre!("http stream: {e}"))?
+    // 变调模式下 rodio 的变速层会把目标乘上倍速再下传;先除掉,落点才是曲内媒体时间。
+    let speed = player.speed();
+    let target = if speed > 0.0 {
+        target.div_f32(speed)
     } else {
-        let client = client_with_headers(&headers)?;
-        HttpStream::new(client, url)
-            .await
-            .map_err(|e| eyre!("http stream (headed): {e}"))?
+        target
     };
-    let len = stream.content_length();
-    let total = len.unwrap_or(0);
-    let prog = Arc::clone(&progress);
-    let settings = Settings::default()
-        .prefetch_bytes(prefetch_bytes)
-        .on_progress(
-            move |_stream: &HttpStream<Client>, state: StreamState, _cancel| {
-                // 切歌 / 换预排后旧流的迟到回调(代号不匹配)直接忽略,不污染当前缓冲。
-                let slot = prog.slot(progress_idx);
-                if slot.buffer_gen.load(Ordering::Acquire) != stream_gen {
-                    return;
-                }
-                let bps = match state.phase {
-                    // 长度未知(无 Content-Length)时比例恒零,下完瞬间补满。
-                    StreamPhase::Complete => Bps::FULL,
-                    _ => Bps::ratio(state.current_position, total),
-                };
-                slot.buffer_bps.store(bps.get(), Ordering::Release);
-            },
-        );
-    let reader = StreamDownload::from_stream(stream, provider, settings)
-        .await
-        .map_err(|e| eyre!("stream-download init: {e}"))?;
-    // capture 播放:拿 download handle,spawn 一个 waiter 等整段下完后 store 本曲代号。
-    // 必须在 reader 被 decoder 消费前取 handle。
-    if let Some(vpath) = verify_path {
-        let handle = reader.handle();
-        let done = Arc::clone(&progress);
-        tokio::spawn(async move {
-            handle.wait_for_completion().await;
-            // stream_download 在下载出错/断连时也 signal complete;核对落盘字节数达 content_length
-            // 才标下完,否则截断文件会被 harvest 进缓存、之后播放解码 IO 错。
-            let file_len = tokio::fs::metadata(&vpath)
-                .await
-                .map(|m| m.len())
-                .unwrap_or(0);
-            if download_reached_full(file_len, len) {
-                done.slot(progress_idx)
-                    .done_gen
-                    .store(stream_gen, Ordering::Release);
-            } else {
-                mineral_log::warn!(
-                    target: "audio",
-                    stream_gen,
-                    file_len,
-                    expected = len.unwrap_or(0),
-                    "capture 未下完整段(截断),不标下完、不入缓存"
-                );
-            }
-        });
-    }
-    Ok((Box::new(reader), len))
-}
-
-/// 用 [`DecoderBuilder`] 构造 decoder,**`byte_len` 已知时一并塞进**。
-///
-/// 关键:rodio `Decoder::new()` 默认 `is_seekable=false`,Symphonia 在源不可
-/// 随机访问时只能向前 seek(后退会返 `ForwardOnly` → `RandomAccessNotSupported`)
-/// —— 表现就是按 ← 没反应。`with_byte_len` 会一并把 `is_seekable` 置 true。
-/// `byte_len` 未知时退化到默认行为(只能向前 seek),至少不比之前差。
-pub(crate) fn build_decoder<R>(
-    reader: R,
-    byte_len: Option<u64>,
-) -> color_eyre::Result<rodio::Decoder<R>>
-where
-    R: Read + Seek + Send + Sync + 'static,
-{
-    let mut builder = DecoderBuilder::new().with_data(reader);
-    if let Some(len) = byte_len {
-        builder = builder.with_byte_len(len);
+    if let Err(e) = player.try_seek(target) {
+        mineral_log::warn!(target: "audio", seek_to = ?target, error = mineral_log::chain(&e), "seek failed");
     }
-    builder.build().map_err(|e| eyre!("decode: {e}"))
 }
 
 /// `Duration` → ms,超过 `u64::MAX` 时饱和(实际曲长不会触达)。
 fn duration_to_ms(d: Duration) -> u64 {
     u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
 }
-
-#[cfg(test)]
-mod tests {
-    use std::sync::Arc;
-
-    use parking_lot::Mutex;
-    use tokio::io::{AsyncReadExt, AsyncWriteExt};
-
-    use crate::engine::{StreamTarget, create_stream};
-    use crate::queue_slots::SharedProgress;
-
-    /// `create_stream` 把 `headers` 注入 HTTP 取流请求:B站 baseUrl 播放必须带 `Referer`,否则
-    /// 403。起一个记录请求头的本地 server,用带 Referer 的 headers 建流,断言 server 收到该头。
-    ///
-    /// 直接测 `create_stream`(而非经 `AudioHandle::play`):`ForceNull` 模式走 `run_null_mode`
-    /// 短路建流、`Auto` 又需真声卡,都测不到取流路径;`create_stream` 是取流链的真实汇聚点。
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn create_stream_injects_headers_into_http_request() -> color_eyre::Result<()> {
-        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
-        let addr = listener.local_addr()?;
-        let recorded = Arc::new(Mutex::new(None::<String>));
-        let rec = Arc::clone(&recorded);
-        tokio::spawn(async move {
-            if let Ok((mut sock, _)) = listener.accept().await {
-                let mut buf = [0u8; 2048];
-                let n = sock.read(&mut buf).await.unwrap_or(0);
-                let req = buf
-                    .get(..n)
-                    .map(|b| String::from_utf8_lossy(b).into_owned())
-                    .unwrap_or_default();
-                *rec.lock() = Some(req);
-                let body = b"FAKEDATA";
-                let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
-                drop(sock.write_all(head.as_bytes()).await);
-                drop(sock.write_all(body).await);
-                drop(sock.shutdown().await);
-            }
-        });
-
-        let url = url::Url::parse(&format!("http://{addr}/a.mp3"))?;
-        let progress = Arc::new(SharedProgress::default());
-        let target = StreamTarget {
-            url,
-            headers: vec![("Referer".to_owned(), "https://www.bilibili.com".to_owned())],
-        };
-        // body 非有效音频,建流本身不解码,故忽略返回;只验证请求已带上 header。
-        let _ = create_stream(
-            target, /*capture*/ None, /*stream_gen*/ 1, /*progress_idx*/ 0,
-            progress, /*prefetch_bytes*/ 0,
-        )
-        .await;
-
-        let raw = recorded.lock().clone().unwrap_or_default();
-        // HTTP header 名大小写不敏感(hyper 发小写),按名匹配、value 保原样。
-        let referer = raw.lines().find_map(|line| {
-            line.split_once(':').and_then(|(name, value)| {
-                name.trim()
-                    .eq_ignore_ascii_case("referer")
-                    .then(|| value.trim().to_owned())
-            })
-        });
-        assert_eq!(
-            referer.as_deref(),
-            Some("https://www.bilibili.com"),
-            "create_stream 应把 headers 注入 HTTP 请求;实际收到:\n{raw}"
-        );
-        Ok(())
-    }
-}
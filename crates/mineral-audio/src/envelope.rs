@@ -18,6 +18,8 @@ use color_eyre::eyre::eyre;
 use mineral_model::Envelope;
 use rodio::Source;
 
+use crate::biquad::Biquad;
+
 /// 包络算法版本:滤波 / 分块统计 / 归一 / 量化的**结构**变更时 bump;读取方版本
 /// 不符视同缺失、触发重算。v3 = RMS 换 K-weighting + momentary 时窗(块粒度也从
 /// 固定帧数改为固定时长,消除时间粒度随采样率漂移)。
@@ -78,43 +80,13 @@ pub struct HighpassParams {
     q: f64,
 }
 
-/// 二阶 IIR 滤波节(转置直接 II 型)。
-///
-/// 系数与状态都用 f64:38Hz 高通在高采样率下极点紧贴单位圆,f32 精度会让
-/// 状态漂移、低频衰减失真。
-struct Biquad {
-    /// 分子系数 z^0。
-    b0: f64,
-    /// 分子系数 z^-1。
-    b1: f64,
-    /// 分子系数 z^-2。
-    b2: f64,
-    /// 分母系数 z^-1(a0 已归一进其余系数)。
-    a1: f64,
-    /// 分母系数 z^-2。
-    a2: f64,
-    /// 延迟状态 1。
-    z1: f64,
-    /// 延迟状态 2。
-    z2: f64,
-}
-
-impl Biquad {
-    /// 处理一个样本,推进内部状态。
-    fn process(&mut self, x: f64) -> f64 {
-        let y = self.b0 * x + self.z1;
-        self.z1 = self.b1 * x - self.a1 * y + self.z2;
-        self.z2 = self.b2 * x - self.a2 * y;
-        y
-    }
-}
-
 /// BS.1770 K-weighting 预滤波:高频搁架(头部声学,~2kHz 以上 +4dB)+ RLB 高通
 /// (38Hz,人耳低频不敏感)两级级联。
 ///
 /// 规范只给出 48kHz 的系数表;任意采样率按模拟原型参数(f0 / G / Q)经双线性变换
 /// 现场推导。参数与推导式对照 libebur128,48kHz 下与规范系数表逐位一致(见测试)。
-struct KWeighting {
+/// 整曲响度([`crate::loudness`])逐声道复用同一滤波。
+pub(crate) struct KWeighting {
     /// 高频搁架级。
     shelf: Biquad,
     /// RLB 高通级。
@@ -123,39 +95,33 @@ struct KWeighting {
 
 impl KWeighting {
     /// 按采样率与模拟原型参数推导两级系数。
-    fn new(sample_rate: f64, shelf: &ShelfParams, highpass: &HighpassParams) -> Self {
+    pub(crate) fn new(sample_rate: f64, shelf: &ShelfParams, highpass: &HighpassParams) -> Self {
         let q = *shelf.q();
         let k = (std::f64::consts::PI * shelf.f0_hz() / sample_rate).tan();
         let vh = 10.0f64.powf(shelf.gain_db() / 20.0);
         let vb = vh.powf(*shelf.band_exponent());
         let a0 = 1.0 + k / q + k * k;
-        let shelf = Biquad {
-            b0: (vh + vb * k / q + k * k) / a0,
-            b1: 2.0 * (k * k - vh) / a0,
-            b2: (vh - vb * k / q + k * k) / a0,
-            a1: 2.0 * (k * k - 1.0) / a0,
-            a2: (1.0 - k / q + k * k) / a0,
-            z1: 0.0,
-            z2: 0.0,
-        };
+        let shelf = Biquad::new(
+            [
+                vh + vb * k / q + k * k,
+                2.0 * (k * k - vh),
+                vh - vb * k / q + k * k,
+            ],
+            [a0, 2.0 * (k * k - 1.0), 1.0 - k / q + k * k],
+        );
         let q = *highpass.q();
         let k = (std::f64::consts::PI * highpass.f0_hz() / sample_rate).tan();
         let a0 = 1.0 + k / q + k * k;
         // 分子固定 [1, -2, 1] 不按 a0 归一——规范原文如此,通带增益仍 ≈ 0dB。
-        let highpass = Biquad {
-            b0: 1.0,
-            b1: -2.0,
-            b2: 1.0,
-            a1: 2.0 * (k * k - 1.0) / a0,
-            a2: (1.0 - k / q + k * k) / a0,
-            z1: 0.0,
-            z2: 0.0,
-        };
+        let highpass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
         Self { shelf, highpass }
     }
 
-    /// 处理一个 mono 样本(两级级联)。
-    fn process(&mut self, x: f64) -> f64 {
+    /// 处理一个样本(两级级联)。
+    pub(crate) fn process(&mut self, x: f64) -> f64 {
         self.highpass.process(self.shelf.process(x))
     }
 }
@@ -336,8 +302,8 @@ pub fn envelope_from_file(
     path: &std::path::Path,
     params: &EnvelopeParams,
 ) -> color_eyre::Result<Envelope> {
-    let (reader, byte_len) = crate::engine::open_local(path)?;
-    let decoder = crate::engine::build_decoder(reader, byte_len)?;
+    let (reader, byte_len) = crate::decode::open_local(path)?;
+    let decoder = crate::decode::build_decoder(reader, byte_len)?;
     let channels = decoder.channels();
     let sample_rate = decoder.sample_rate();
     let points = envelope_from_samples(decoder, channels, sample_rate, params)
@@ -0,0 +1,316 @@
+//! 参数均衡器:多段 biquad(峰值 / 搁架 / 高低通)级联 + 前级增益,插在归一化与交叉淡化
+//! 之后、[`crate::tap::TapSource`] 之前——频谱看到的就是耳朵听到的。
+//!
+//! 预设怎么选、叫什么名字是 daemon 的事;引擎只认一组 [`EqParams`],经
+//! [`crate::AudioHandle::set_eq`] 随时替换。滤波状态放在跨曲共享的 [`EqChain`] 里:gapless
+//! 边界换 decoder 时不清零(清零会在无缝衔接处留一个咔哒);采样率 / 声道数变了才重建。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rodio::source::SeekError;
+use rodio::{ChannelCount, SampleRate, Source};
+
+use crate::biquad::Biquad;
+
+/// 均衡段的滤波类型。
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EqBandKind {
+    /// 峰值(钟形):中心频率处增减 `gain_db`。
+    Peaking,
+
+    /// 低频搁架:转折频率以下整体增减。
+    LowShelf,
+
+    /// 高频搁架:转折频率以上整体增减。
+    HighShelf,
+
+    /// 高通:截止频率以下衰减(忽略 `gain_db`)。
+    HighPass,
+
+    /// 低通:截止频率以上衰减(忽略 `gain_db`)。
+    LowPass,
+}
+
+/// 一段均衡。
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, typed_builder::TypedBuilder, derive_getters::Getters)]
+pub struct EqBand {
+    /// 滤波类型。
+    kind: EqBandKind,
+
+    /// 中心 / 转折 / 截止频率(Hz)。
+    freq_hz: f64,
+
+    /// 增益(dB;高低通忽略)。
+    gain_db: f64,
+
+    /// 品质因数(峰值段的带宽;搁架 / 高低通 0.707 为平坦过渡)。
+    q: f64,
+}
+
+/// 一组均衡参数(配置 `audio.eq` 某个预设的切片)。
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, typed_builder::TypedBuilder, derive_getters::Getters)]
+pub struct EqParams {
+    /// 前级增益(dB),给提升段留余量。
+    preamp_db: f64,
+
+    /// 各段,按顺序级联。
+    bands: Vec<EqBand>,
+}
+
+/// 按采样率设计一段的滤波节。
+fn design(band: &EqBand, sample_rate: f64) -> Biquad {
+    match band.kind {
+        EqBandKind::Peaking => Biquad::peaking(sample_rate, band.freq_hz, band.gain_db, band.q),
+        EqBandKind::LowShelf => Biquad::low_shelf(sample_rate, band.freq_hz, band.gain_db, band.q),
+        EqBandKind::HighShelf => {
+            Biquad::high_shelf(sample_rate, band.freq_hz, band.gain_db, band.q)
+        }
+        EqBandKind::HighPass => Biquad::high_pass(sample_rate, band.freq_hz, band.q),
+        EqBandKind::LowPass => Biquad::low_pass(sample_rate, band.freq_hz, band.q),
+    }
+}
+
+/// dB → 线性幅度。
+#[allow(clippy::as_conversions)] // reason: 前级增益量级有限,f64 → f32 只损精度
+fn db_to_linear(db: f64) -> f32 {
+    10.0f64.powf(db / 20.0) as f32
+}
+
+/// 均衡器运行态:参数 + 逐声道滤波状态,引擎与各曲 [`EqSource`] 共享。
+pub(crate) struct EqChain {
+    /// 当前参数;`None` = 旁路。
+    params: Option<EqParams>,
+
+    /// 滤波节按哪个 `(采样率, 声道数)` 设计;`None` = 尚未设计。
+    format: Option<(SampleRate, ChannelCount)>,
+
+    /// 前级线性增益。
+    preamp: f32,
+
+    /// 逐声道的各段滤波节。
+    filters: Vec<Vec<Biquad>>,
+}
+
+/// 跨曲共享的均衡器句柄。
+pub(crate) type SharedEq = Arc<Mutex<EqChain>>;
+
+impl EqChain {
+    /// 以初始参数建共享句柄(滤波节等首个样本到来时按其格式设计)。
+    pub(crate) fn shared(params: Option<EqParams>) -> SharedEq {
+        Arc::new(Mutex::new(Self {
+            preamp: params.as_ref().map_or(1.0, |p| db_to_linear(p.preamp_db)),
+            params,
+            format: None,
+            filters: Vec::new(),
+        }))
+    }
+
+    /// 替换参数。段数不变时只换系数、保留状态(播中切预设不从零起振);否则下个样本重建。
+    pub(crate) fn set(&mut self, params: Option<EqParams>) {
+        self.preamp = params.as_ref().map_or(1.0, |p| db_to_linear(p.preamp_db));
+        let same_shape = match (&self.params, &params) {
+            (Some(old), Some(new)) => old.bands.len() == new.bands.len(),
+            _ => false,
+        };
+        self.params = params;
+        match (same_shape, self.format, &self.params) {
+            (true, Some((rate, _)), Some(p)) => {
+                let rate = f64::from(rate.get());
+                for channel in &mut self.filters {
+                    for (filter, band) in channel.iter_mut().zip(&p.bands) {
+                        filter.retune(&design(band, rate));
+                    }
+                }
+            }
+            _ => self.format = None,
+        }
+    }
+
+    /// 处理一个样本。
+    ///
+    /// # Params:
+    ///   - `x`: 输入样本
+    ///   - `channel`: 该样本所属声道(交错序号)
+    ///   - `rate`: 来源采样率
+    ///   - `channels`: 来源声道数
+    ///
+    /// # Return:
+    ///   均衡后的样本;旁路时原样返回。
+    #[allow(clippy::as_conversions)] // reason: 滤波在 f64 里算,落回 f32 样本只损精度
+    fn process(&mut self, x: f32, channel: usize, rate: SampleRate, channels: ChannelCount) -> f32 {
+        let Some(params) = self.params.as_ref() else {
+            return x;
+        };
+        if self.format != Some((rate, channels)) {
+            let sr = f64::from(rate.get());
+            self.filters = (0..channels.get())
+                .map(|_| params.bands.iter().map(|b| design(b, sr)).collect())
+                .collect();
+            self.format = Some((rate, channels));
+        }
+        let Some(filters) = self.filters.get_mut(channel) else {
+            return x;
+        };
+        let y = filters
+            .iter_mut()
+            .fold(f64::from(x * self.preamp), |acc, f| f.process(acc));
+        y as f32
+    }
+}
+
+/// 包装 `Source<Item = f32>`:逐样本过共享均衡器。
+pub(crate) struct EqSource<S> {
+    /// 内层音频源(交叉淡化队列项)。
+    inner: S,
+
+    /// 跨曲共享的均衡器。
+    chain: SharedEq,
+
+    /// 下一个样本的声道序号。
+    channel: u16,
+}
+
+impl<S> EqSource<S> {
+    /// 包装 `inner`(队列项总从帧边界开始,声道序号从 0 起)。
+    pub(crate) fn new(inner: S, chain: SharedEq) -> Self {
+        Self {
+            inner,
+            chain,
+            channel: 0,
+        }
+    }
+}
+
+impl<S> Iterator for EqSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.inner.next()?;
+        let channels = self.inner.channels();
+        let y = self.chain.lock().process(
+            s,
+            usize::from(self.channel),
+            self.inner.sample_rate(),
+            channels,
+        );
+        self.channel = match self.channel.checked_add(1) {
+            Some(n) if n < channels.get() => n,
+            _ => 0,
+        };
+        Some(y)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S> Source for EqSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+
+    /// 透传给 inner(不透传会回落到默认 `NotSupported`,seek 全失效)。
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.channel = 0;
+        self.inner.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32};
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{EqBand, EqBandKind, EqChain, EqParams, EqSource};
+
+    /// 单段均衡参数。
+    fn params(kind: EqBandKind, preamp_db: f64) -> EqParams {
+        EqParams::builder()
+            .preamp_db(preamp_db)
+            .bands(vec![
+                EqBand::builder()
+                    .kind(kind)
+                    .freq_hz(1_000.0)
+                    .gain_db(6.0)
+                    .q(1.0)
+                    .build(),
+            ])
+            .build()
+    }
+
+    /// 双声道 8kHz 缓冲。
+    fn stereo(samples: Vec<f32>) -> SamplesBuffer {
+        SamplesBuffer::new(
+            NonZeroU16::new(2).unwrap_or(NonZeroU16::MIN),
+            NonZeroU32::new(8_000).unwrap_or(NonZeroU32::MIN),
+            samples,
+        )
+    }
+
+    /// 旁路时样本原样通过;前级增益对直流生效(峰值段对直流近乎直通)。
+    #[test]
+    fn bypass_and_preamp() {
+        let bypass: Vec<f32> = EqSource::new(stereo(vec![0.5; 8]), EqChain::shared(None)).collect();
+        assert_eq!(bypass, vec![0.5; 8]);
+        let chain = EqChain::shared(Some(params(EqBandKind::Peaking, -6.0)));
+        let out: Vec<f32> = EqSource::new(stereo(vec![0.5; 4_000]), chain).collect();
+        let last = out.last().copied().unwrap_or_default();
+        assert!(
+            (last - 0.5 * 10.0f32.powf(-6.0 / 20.0)).abs() < 1e-3,
+            "前级:{last}"
+        );
+    }
+
+    /// 声道各自滤波:左声道直流、右声道静音,高通后左声道衰减到零、右声道保持静音。
+    #[test]
+    fn channels_are_filtered_independently() {
+        let input: Vec<f32> = (0..4_000)
+            .map(|i| if i % 2 == 0 { 0.5 } else { 0.0 })
+            .collect();
+        let chain = EqChain::shared(Some(params(EqBandKind::HighPass, 0.0)));
+        let out: Vec<f32> = EqSource::new(stereo(input), chain).collect();
+        let tail = out.get(3_000..).unwrap_or_default();
+        assert!(
+            tail.iter().all(|s| s.abs() < 1e-3),
+            "高通应滤掉直流:{tail:?}"
+        );
+    }
+
+    /// 共享状态跨曲保留:同一链上第二首接着第一首的滤波状态,旁路后再设参数也立即生效。
+    #[test]
+    fn set_switches_live() {
+        let chain = EqChain::shared(None);
+        let first: Vec<f32> =
+            EqSource::new(stereo(vec![0.5; 16]), std::sync::Arc::clone(&chain)).collect();
+        assert_eq!(first, vec![0.5; 16]);
+        chain.lock().set(Some(params(EqBandKind::Peaking, -6.0)));
+        let second: Vec<f32> = EqSource::new(stereo(vec![0.5; 4_000]), chain).collect();
+        let last = second.last().copied().unwrap_or_default();
+        assert!(last < 0.3, "设参数后应即时生效:{last}");
+    }
+}
@@ -0,0 +1,524 @@
+//! 文件出口:把混音后的 PCM 流渲染进 WAV / FLAC 文件,代替声卡。
+//!
+//! 给无头 CI 用:[`crate::AudioMode::ForceNull`] 把样本丢了,断言不了引擎的实际产出(无缝
+//! 接缝、音量斜坡、DSP)。文件出口直接消费播放队列输出,统一转成 [`RATE`] / [`CHANNELS`]
+//! (同格式直通,样本不变),按扩展名写 32-bit 浮点 WAV(逐样本无损)或 24-bit FLAC。
+//!
+//! 节拍:`realtime` 按墙钟推进(与真声卡同速);否则每 tick 渲染 [`FAST_CHUNK_FRAMES`]
+//! 帧,远快于实时。队列空 / 暂停时不写(文件里只有「会被听到」的流,不灌静音)。WAV 每
+//! tick 回填头部长度,进程在跑时文件也随时可读;FLAC 用可变块长,每 tick 落整块。
+//!
+//! rodio 队列空时会续一段段保活静音;队列一空就丢掉格式转换器、把剩余静音段吃掉,下一曲
+//! 进队时按它的真实格式重建转换器——接缝处既不漏样本也不多出静音。
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::num::{NonZeroU16, NonZeroU32};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::WrapErr;
+use parking_lot::Mutex;
+use rodio::queue::SourcesQueueOutput;
+use rodio::source::{SeekError, UniformSourceIterator};
+use rodio::{ChannelCount, SampleRate, Source};
+
+/// 输出采样率(Hz)。
+pub(crate) const RATE: u32 = 44_100;
+
+/// 输出声道数。
+pub(crate) const CHANNELS: u16 = 2;
+
+/// 非实时模式下每 tick 渲染的帧数(1 秒音频;20ms tick 下约 50 倍速)。
+const FAST_CHUNK_FRAMES: u64 = 44_100;
+
+/// FLAC 最大块长(帧)。
+const FLAC_MAX_BLOCK: usize = 4_096;
+
+/// FLAC 最小块长(帧);末块之外不得更短。
+const FLAC_MIN_BLOCK: usize = 16;
+
+/// FLAC 位深。
+const FLAC_BITS: u32 = 24;
+
+/// 编码器:按扩展名二选一。
+enum Encoder {
+    /// 32-bit 浮点 WAV。
+    Wav(WavWriter),
+
+    /// 24-bit FLAC(verbatim 子帧,不压缩)。
+    Flac(FlacWriter),
+}
+
+impl Encoder {
+    /// 写一帧(交错样本)。
+    fn write_frame(&mut self, frame: &[f32]) -> std::io::Result<()> {
+        match self {
+            Self::Wav(w) => w.write_frame(frame),
+            Self::Flac(f) => f.write_frame(frame),
+        }
+    }
+
+    /// 让文件在当前位置自洽(回填长度 / 落整块)。
+    fn sync(&mut self, last: bool) -> std::io::Result<()> {
+        match self {
+            Self::Wav(w) => w.sync(),
+            Self::Flac(f) => f.sync(last),
+        }
+    }
+}
+
+/// 共享播放队列的读端:格式转换器可随时丢弃重建,队列本身留在 [`FileSink`] 手里。
+#[derive(Clone)]
+struct QueueReader(Arc<Mutex<SourcesQueueOutput>>);
+
+impl Iterator for QueueReader {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.lock().next()
+    }
+}
+
+impl Source for QueueReader {
+    fn current_span_len(&self) -> Option<usize> {
+        self.0.lock().current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.0.lock().channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.0.lock().sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    fn try_seek(&mut self, _pos: Duration) -> Result<(), SeekError> {
+        Err(SeekError::NotSupported {
+            underlying_source: std::any::type_name::<Self>(),
+        })
+    }
+}
+
+/// 引擎的文件出口。
+pub(crate) struct FileSink {
+    /// 播放队列输出。
+    queue: QueueReader,
+
+    /// 统一到 [`RATE`] / [`CHANNELS`] 的转换器;队列空闲时丢弃,下一曲进队再按其格式重建。
+    source: Option<UniformSourceIterator<QueueReader>>,
+
+    /// 编码器。
+    encoder: Encoder,
+
+    /// 目标路径(日志用)。
+    path: PathBuf,
+
+    /// 是否按墙钟推进。
+    realtime: bool,
+
+    /// 实时模式的节拍基准:`(时刻, 当时已写帧数)`;空闲后重置。
+    anchor: Option<(Instant, u64)>,
+
+    /// 已写帧数。
+    frames: u64,
+}
+
+impl FileSink {
+    /// 创建输出文件(`.flac` 写 FLAC,其余写 WAV)并接上播放队列。
+    ///
+    /// # Params:
+    ///   - `queue`: `rodio::Player::new()` 给出的队列输出
+    ///   - `path`: 目标文件(已存在则覆盖)
+    ///   - `realtime`: 按墙钟推进(否则尽快渲染)
+    ///
+    /// # Return:
+    ///   文件建不出来时报错。
+    pub(crate) fn create(
+        queue: SourcesQueueOutput,
+        path: &Path,
+        realtime: bool,
+    ) -> color_eyre::Result<Self> {
+        let file = File::create(path)
+            .wrap_err_with(|| format!("create audio sink file {}", path.display()))?;
+        let flac = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("flac"));
+        let out = BufWriter::new(file);
+        let encoder = if flac {
+            Encoder::Flac(FlacWriter::new(out)?)
+        } else {
+            Encoder::Wav(WavWriter::new(out)?)
+        };
+        Ok(Self {
+            queue: QueueReader(Arc::new(Mutex::new(queue))),
+            source: None,
+            encoder,
+            path: path.to_owned(),
+            realtime,
+            anchor: None,
+            frames: 0,
+        })
+    }
+
+    /// 目标路径。
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 每 tick 调:按节拍渲染一段并让文件自洽。队列空 / 暂停时不写。
+    pub(crate) fn render(&mut self, player: &rodio::Player) -> color_eyre::Result<()> {
+        if player.empty() || player.is_paused() {
+            self.anchor = None;
+            return Ok(());
+        }
+        let budget = if self.realtime {
+            let (t0, f0) = *self.anchor.get_or_insert((Instant::now(), self.frames));
+            let due = f0.saturating_add(
+                u64::try_from(t0.elapsed().as_millis())
+                    .unwrap_or(u64::MAX)
+                    .saturating_mul(u64::from(RATE))
+                    / 1_000,
+            );
+            due.saturating_sub(self.frames)
+        } else {
+            FAST_CHUNK_FRAMES
+        };
+        let queue = &self.queue;
+        let source = self.source.get_or_insert_with(|| {
+            UniformSourceIterator::new(
+                queue.clone(),
+                NonZeroU16::new(CHANNELS).unwrap_or(NonZeroU16::MIN),
+                NonZeroU32::new(RATE).unwrap_or(NonZeroU32::MIN),
+            )
+        });
+        let mut frame = vec![0.0f32; usize::from(CHANNELS)];
+        for _ in 0..budget {
+            for s in &mut frame {
+                *s = source.next().unwrap_or(0.0);
+            }
+            // 末曲在这一帧的第一次拉取时耗尽:这一帧起都是队列的保活静音,不写。
+            if player.empty() {
+                self.go_idle();
+                break;
+            }
+            self.encoder.write_frame(&frame)?;
+            self.frames += 1;
+        }
+        self.encoder.sync(false)?;
+        Ok(())
+    }
+
+    /// 队列空了:丢掉转换器,吃掉当前保活静音段的剩余样本,让下一曲从头对齐。
+    fn go_idle(&mut self) {
+        self.source = None;
+        self.anchor = None;
+        let mut queue = self.queue.0.lock();
+        let (left, _) = queue.size_hint();
+        for _ in 0..left {
+            let _ = queue.next();
+        }
+    }
+}
+
+impl Drop for FileSink {
+    fn drop(&mut self) {
+        if let Err(e) = self.encoder.sync(true) {
+            mineral_log::warn!(target: "audio", path = %self.path.display(), error = %e, "收尾音频文件失败");
+        }
+    }
+}
+
+/// 32-bit 浮点 WAV 写端:头部先占位,[`Self::sync`] 回填长度。
+struct WavWriter {
+    /// 目标文件。
+    out: BufWriter<File>,
+
+    /// 已写样本数据字节数。
+    data_len: u32,
+}
+
+impl WavWriter {
+    /// 写 RIFF / fmt / data 头(长度占位)。
+    fn new(mut out: BufWriter<File>) -> std::io::Result<Self> {
+        let block_align = CHANNELS * 4;
+        out.write_all(b"RIFF")?;
+        out.write_all(&0u32.to_le_bytes())?;
+        out.write_all(b"WAVE")?;
+        out.write_all(b"fmt ")?;
+        out.write_all(&18u32.to_le_bytes())?; // fmt 块长(含 cbSize)
+        out.write_all(&3u16.to_le_bytes())?; // IEEE float
+        out.write_all(&CHANNELS.to_le_bytes())?;
+        out.write_all(&RATE.to_le_bytes())?;
+        out.write_all(&(RATE * u32::from(block_align)).to_le_bytes())?;
+        out.write_all(&block_align.to_le_bytes())?;
+        out.write_all(&32u16.to_le_bytes())?; // 位深
+        out.write_all(&0u16.to_le_bytes())?; // cbSize
+        out.write_all(b"data")?;
+        out.write_all(&0u32.to_le_bytes())?;
+        Ok(Self { out, data_len: 0 })
+    }
+
+    /// 写一帧。超过 WAV 4GiB 上限后静默截断(约 3.4 小时)。
+    fn write_frame(&mut self, frame: &[f32]) -> std::io::Result<()> {
+        let Some(len) = self.data_len.checked_add(u32::from(CHANNELS) * 4) else {
+            return Ok(());
+        };
+        for s in frame {
+            self.out.write_all(&s.to_le_bytes())?;
+        }
+        self.data_len = len;
+        Ok(())
+    }
+
+    /// 回填 RIFF / data 长度并刷盘。
+    fn sync(&mut self) -> std::io::Result<()> {
+        self.out.seek(SeekFrom::Start(4))?;
+        self.out
+            .write_all(&(38u32.saturating_add(self.data_len)).to_le_bytes())?;
+        self.out.seek(SeekFrom::Start(42))?;
+        self.out.write_all(&self.data_len.to_le_bytes())?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()
+    }
+}
+
+/// 24-bit FLAC 写端:可变块长,verbatim 子帧;STREAMINFO 的总样本数在 [`Self::sync`] 回填。
+struct FlacWriter {
+    /// 目标文件。
+    out: BufWriter<File>,
+
+    /// 未成块的帧(交错,已量化)。
+    pending: Vec<i32>,
+
+    /// 已落块的帧数(即下一块首帧序号)。
+    written: u64,
+}
+
+/// STREAMINFO 里「采样率 / 声道 / 位深 / 总样本数」打包字段的文件偏移。
+const FLAC_INFO_OFFSET: u64 = 18;
+
+impl FlacWriter {
+    /// 写 `fLaC` 标记与 STREAMINFO(总样本数占位)。
+    fn new(mut out: BufWriter<File>) -> std::io::Result<Self> {
+        out.write_all(b"fLaC")?;
+        // 最后一个元数据块(0x80)+ 类型 STREAMINFO(0),长 34。
+        out.write_all(&[0x80, 0, 0, 34])?;
+        out.write_all(&u16::try_from(FLAC_MIN_BLOCK).unwrap_or(16).to_be_bytes())?;
+        out.write_all(
+            &u16::try_from(FLAC_MAX_BLOCK)
+                .unwrap_or(u16::MAX)
+                .to_be_bytes(),
+        )?;
+        // 最小 / 最大帧字节数未知(0)。
+        out.write_all(&[0; 6])?;
+        out.write_all(&stream_info_word(0).to_be_bytes())?;
+        // MD5 未知(全 0)。
+        out.write_all(&[0; 16])?;
+        Ok(Self {
+            out,
+            pending: Vec::new(),
+            written: 0,
+        })
+    }
+
+    /// 量化并缓存一帧,攒满最大块长即落块。
+    fn write_frame(&mut self, frame: &[f32]) -> std::io::Result<()> {
+        self.pending.extend(frame.iter().map(|s| quantize(*s)));
+        if self.pending.len() >= FLAC_MAX_BLOCK * usize::from(CHANNELS) {
+            self.flush_block()?;
+        }
+        Ok(())
+    }
+
+    /// 把缓存的帧写成一个 FLAC 帧。
+    fn flush_block(&mut self) -> std::io::Result<()> {
+        let frames = self.pending.len() / usize::from(CHANNELS);
+        if frames == 0 {
+            return Ok(());
+        }
+        let block = encode_frame(&self.pending, frames, self.written);
+        self.out.write_all(&block)?;
+        self.pending.clear();
+        self.written += u64::try_from(frames).unwrap_or(0);
+        Ok(())
+    }
+
+    /// 落块(末块之外不足最小块长的留到下次)并回填总样本数。
+    fn sync(&mut self, last: bool) -> std::io::Result<()> {
+        if last || self.pending.len() >= FLAC_MIN_BLOCK * usize::from(CHANNELS) {
+            self.flush_block()?;
+        }
+        self.out.seek(SeekFrom::Start(FLAC_INFO_OFFSET))?;
+        self.out
+            .write_all(&stream_info_word(self.written).to_be_bytes())?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()
+    }
+}
+
+/// STREAMINFO 的 64 位打包字段:采样率(20)| 声道数-1(3)| 位深-1(5)| 总样本数(36)。
+fn stream_info_word(total: u64) -> u64 {
+    (u64::from(RATE) << 44)
+        | (u64::from(CHANNELS - 1) << 41)
+        | (u64::from(FLAC_BITS - 1) << 36)
+        | (total & 0xF_FFFF_FFFF)
+}
+
+/// f32 样本 → 24-bit 有符号整数。
+fn quantize(s: f32) -> i32 {
+    #[allow(clippy::as_conversions)] // reason: 钳进 [-1, 1] 后 ×(2^23-1) 落在 i32 内
+    let q = (f64::from(s.clamp(-1.0, 1.0)) * 8_388_607.0).round() as i32;
+    q
+}
+
+/// 编码一个可变块长 FLAC 帧(各声道独立、verbatim 子帧)。
+///
+/// # Params:
+///   - `samples`: 交错样本(24-bit)
+///   - `frames`: 帧数(1..=[`FLAC_MAX_BLOCK`])
+///   - `first`: 本块首帧在流里的序号
+fn encode_frame(samples: &[i32], frames: usize, first: u64) -> Vec<u8> {
+    let ch = usize::from(CHANNELS);
+    let mut out = vec![0xFF, 0xF9];
+    // 块长码 0111 = 头尾附 16 位(块长-1);采样率码 0000 = 取 STREAMINFO。
+    out.push(0x70);
+    // 声道分配(独立声道 = 声道数-1)| 位深码 110 = 24 bit。
+    out.push(((u8::try_from(CHANNELS - 1).unwrap_or(1)) << 4) | (0b110 << 1));
+    push_utf8_number(&mut out, first);
+    out.extend_from_slice(&u16::try_from(frames - 1).unwrap_or(u16::MAX).to_be_bytes());
+    out.push(crc8(&out));
+    for c in 0..ch {
+        // 子帧头:verbatim(000001),无 wasted bits。
+        out.push(0x02);
+        for s in samples.iter().skip(c).step_by(ch) {
+            let [_, b1, b2, b3] = s.to_be_bytes();
+            out.extend_from_slice(&[b1, b2, b3]);
+        }
+    }
+    let crc = crc16(&out);
+    out.extend_from_slice(&crc.to_be_bytes());
+    out
+}
+
+/// FLAC 帧头里的「扩展 UTF-8」编码数(可变块长流里是首样本序号,至多 36 位)。
+fn push_utf8_number(out: &mut Vec<u8>, v: u64) {
+    if v < 0x80 {
+        out.push(u8::try_from(v).unwrap_or(0));
+        return;
+    }
+    let continuation = match v {
+        0..0x800 => 1,
+        0x800..0x1_0000 => 2,
+        0x1_0000..0x20_0000 => 3,
+        0x20_0000..0x400_0000 => 4,
+        0x400_0000..0x8000_0000 => 5,
+        _ => 6,
+    };
+    let lead_mask: u8 = !(0xFFu8 >> (continuation + 1));
+    let lead_bits = u8::try_from(v >> (6 * continuation)).unwrap_or(0);
+    out.push(lead_mask | lead_bits);
+    for i in (0..continuation).rev() {
+        out.push(0x80 | u8::try_from((v >> (6 * i)) & 0x3F).unwrap_or(0));
+    }
+}
+
+/// CRC-8(多项式 0x07,初值 0),FLAC 帧头校验。
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, b| {
+        crc ^= b;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// CRC-16(多项式 0x8005,初值 0),FLAC 整帧校验。
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, b| {
+        crc ^= u16::from(*b) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use std::num::{NonZeroU16, NonZeroU32};
+
+    use rodio::Source;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{CHANNELS, FileSink, RATE};
+
+    /// 立体声 44.1kHz 的已知波形(两声道不同,便于发现声道错位)。
+    fn ramp(frames: u16) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let x = f32::from(i) / f32::from(frames);
+                [x * 0.5, -x * 0.25]
+            })
+            .collect()
+    }
+
+    /// 用 rodio 播放队列经文件出口渲染一段样本,再用 rodio 解码回来。
+    fn render_and_decode(ext: &str, samples: &[f32]) -> color_eyre::Result<Vec<f32>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(format!("out.{ext}"));
+        let (player, queue) = rodio::Player::new();
+        let mut sink = FileSink::create(queue, &path, /*realtime*/ false)?;
+        player.append(SamplesBuffer::new(
+            NonZeroU16::new(CHANNELS).unwrap_or(NonZeroU16::MIN),
+            NonZeroU32::new(RATE).unwrap_or(NonZeroU32::MIN),
+            samples.to_vec(),
+        ));
+        while !player.empty() {
+            sink.render(&player)?;
+        }
+        drop(sink);
+        let decoder = rodio::Decoder::new(BufReader::new(std::fs::File::open(&path)?))?;
+        assert_eq!(decoder.channels().get(), CHANNELS);
+        assert_eq!(decoder.sample_rate().get(), RATE);
+        Ok(decoder.collect())
+    }
+
+    /// WAV 逐样本无损,且队列耗尽后不再追加静音。
+    #[test]
+    fn wav_is_sample_exact() -> color_eyre::Result<()> {
+        let input = ramp(5_000);
+        let output = render_and_decode("wav", &input)?;
+        assert_eq!(output, input);
+        Ok(())
+    }
+
+    /// FLAC 可被解码,样本在 24-bit 量化误差内,长度一致。
+    #[test]
+    fn flac_round_trips_within_quantization() -> color_eyre::Result<()> {
+        let input = ramp(10_000);
+        let output = render_and_decode("flac", &input)?;
+        assert_eq!(output.len(), input.len());
+        let worst = input
+            .iter()
+            .zip(&output)
+            .fold(0.0f32, |m, (a, b)| m.max((a - b).abs()));
+        assert!(worst < 1e-6, "量化误差过大:{worst}");
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! UI 持有的 audio handle:线程安全、可 clone,所有方法都是非阻塞。
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::mpsc;
@@ -13,9 +14,14 @@ use ringbuf::traits::{Consumer, Split};
 use ringbuf::{HeapCons, HeapRb};
 
 use crate::command::AudioCommand;
+use crate::crossfade::{CrossfadeParams, Transition};
 use crate::engine;
+use crate::eq::EqParams;
+use crate::normalize::{GainSlot, LimiterParams};
+use crate::output::{self, OutputDevice};
 use crate::snapshot::AudioSnapshot;
 use crate::tap::SharedProd;
+use crate::tempo::{SpeedMode, clamp_speed, speed_to_pct};
 
 /// 音频引擎启动参数(来自用户配置 `audio` 段;生产构造方为 daemon 启动链)。
 #[non_exhaustive]
@@ -33,10 +39,22 @@ pub struct EngineParams {
     /// PCM tap ringbuf 容量(f32 样本)。**外键**:须 ≥ 2 × 频谱 FFT 窗大小
     /// (配置 `tui.spectrum.fft_size`)——双窗余量,UI 卡一帧不丢样本。
     tap_capacity: usize,
+
+    /// 响度归一化的峰值限幅器(配置 `audio.normalization.limiter`)。
+    limiter: LimiterParams,
+
+    /// 交叉淡化(配置 `audio.crossfade`)。
+    crossfade: CrossfadeParams,
+
+    /// 起始均衡参数(配置 `audio.eq.preset` 选中的预设;`None` = 旁路)。
+    eq: Option<EqParams>,
+
+    /// 钉住的输出设备名(配置 `audio.device`;`None` = 默认设备,找不到也回落默认)。
+    device: Option<String>,
 }
 
 /// 引擎启动时的音频后端选择。
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum AudioMode {
     /// 自动:尝试打开默认输出设备,失败则降级到 null(无声但引擎仍活)。
     #[default]
@@ -44,6 +62,15 @@ pub enum AudioMode {
 
     /// 强制 null:不碰设备,直接空跑。用于无音频环境 / e2e 测试确定性复现降级。
     ForceNull,
+
+    /// 文件出口:不碰设备,把混音后的 PCM 渲染进文件,测试可逐样本断言引擎产出。
+    File {
+        /// 目标文件;`.flac` 写 24-bit FLAC,其余写 32-bit 浮点 WAV。
+        path: PathBuf,
+
+        /// 按墙钟实时渲染;`false` = 尽快渲染。
+        realtime: bool,
+    },
 }
 
 /// 共享内部状态:命令通道 + snapshot + seek mailbox(latest-wins)。
@@ -56,6 +83,9 @@ struct Inner {
 
     /// 最新待执行的 seek 目标位置;engine 每 tick `take()` 一次实际打 demuxer,长按 ←/→ 时只生效最后一次。
     seek_mailbox: Arc<Mutex<Option<Duration>>>,
+
+    /// 当前输出设备名(engine 打开 / 切换设备时写;null 模式为 `None`)。
+    device: Arc<Mutex<Option<String>>>,
 }
 
 /// PCM tap:UI 端独占,持有 ringbuf 读端 + 当前轨道 sample_rate。
@@ -93,7 +123,8 @@ impl AudioHandle {
     /// 启动 engine 线程并返回 (handle, spectrum tap)。
     ///
     /// # Params:
-    ///   - `mode`: [`AudioMode::Auto`] 拿不到设备时降级 null;[`AudioMode::ForceNull`] 直接空跑。
+    ///   - `mode`: [`AudioMode::Auto`] 拿不到设备时降级 null;[`AudioMode::ForceNull`] 直接空跑;
+    ///     [`AudioMode::File`] 渲染进文件(文件建不出来算真错)。
     ///   - `params`: 引擎启动参数(初始音量 / tick / prefetch / tap 容量),来自配置。
     ///
     /// # Return:
@@ -102,6 +133,7 @@ impl AudioHandle {
         let (cmd_tx, cmd_rx) = mpsc::channel::<AudioCommand>();
         let snapshot = Arc::new(Mutex::new(AudioSnapshot {
             volume_pct: (*params.initial_volume()).min(100),
+            speed_pct: 100,
             ..AudioSnapshot::default()
         }));
 
@@ -112,6 +144,8 @@ impl AudioHandle {
         let shared_prod: SharedProd = Arc::new(Mutex::new(producer));
         let sr_atomic = Arc::new(AtomicU32::new(0));
 
+        let device = Arc::new(Mutex::new(None::<String>));
+
         let (ready_tx, ready_rx) = mpsc::sync_channel::<color_eyre::Result<()>>(1);
         let io = engine::EngineIo {
             snapshot: Arc::clone(&snapshot),
@@ -119,6 +153,7 @@ impl AudioHandle {
             ready_tx,
             tap_producer: Arc::clone(&shared_prod),
             sr_atomic: Arc::clone(&sr_atomic),
+            device: Arc::clone(&device),
         };
         thread::Builder::new()
             .name(String::from("mineral-audio"))
@@ -138,6 +173,7 @@ impl AudioHandle {
                 cmd_tx,
                 snapshot,
                 seek_mailbox,
+                device,
             }),
         };
         let tap = SpectrumTap {
@@ -194,12 +230,20 @@ impl AudioHandle {
     ///   - `url`: 下一曲播放源
     ///   - `headers`: 取流附加请求头(如 B站 baseUrl 需 `Referer`);空 = 无附加头
     ///   - `layout`: 流的容器布局(分片远端流以流式打开)
-    pub fn append_next(&self, url: MediaUrl, headers: Vec<(String, String)>, layout: StreamLayout) {
+    ///   - `transition`: 与当前曲的衔接方式(无缝硬接 / 交叉淡化)
+    pub fn append_next(
+        &self,
+        url: MediaUrl,
+        headers: Vec<(String, String)>,
+        layout: StreamLayout,
+        transition: Transition,
+    ) {
         self.send(AudioCommand::AppendNext {
             url,
             headers,
             capture: None,
             layout,
+            transition,
         });
     }
 
@@ -210,18 +254,21 @@ impl AudioHandle {
     ///   - `headers`: 取流附加请求头(如 B站 baseUrl 需 `Referer`);空 = 无附加头
     ///   - `capture`: 捕获落盘路径
     ///   - `layout`: 流的容器布局(分片远端流以流式打开)
+    ///   - `transition`: 与当前曲的衔接方式(无缝硬接 / 交叉淡化)
     pub fn append_next_capturing(
         &self,
         url: MediaUrl,
         headers: Vec<(String, String)>,
         capture: std::path::PathBuf,
         layout: StreamLayout,
+        transition: Transition,
     ) {
         self.send(AudioCommand::AppendNext {
             url,
             headers,
             capture: Some(capture),
             layout,
+            transition,
         });
     }
 
@@ -259,6 +306,65 @@ impl AudioHandle {
         self.send(AudioCommand::SetVolume(clamped));
     }
 
+    /// 改写某槽曲目的响度归一化增益(daemon 按标签 / 分析算好后调)。新曲起播 / 预排时
+    /// 该槽回到 0dB,故须在 `play` / `append_next` **之后**调;播中改写平滑过渡。
+    ///
+    /// # Params:
+    ///   - `slot`: 当前曲 / 下一曲
+    ///   - `gain_db`: 增益(dB,0 = 原样)
+    pub fn set_track_gain(&self, slot: GainSlot, gain_db: f64) {
+        self.send(AudioCommand::SetGain { slot, gain_db });
+    }
+
+    /// 替换均衡参数,即时作用于正在播放的曲目。
+    ///
+    /// # Params:
+    ///   - `params`: 新参数;`None` = 旁路
+    pub fn set_eq(&self, params: Option<EqParams>) {
+        self.send(AudioCommand::SetEq(params));
+    }
+
+    /// 设置播放倍速,跨曲保持;快照里的位置 / 时长始终是曲内媒体时间。本地立刻更新 snapshot。
+    ///
+    /// # Params:
+    ///   - `speed`: 倍速(1 = 原速),钳进 [`crate::MIN_SPEED`]..=[`crate::MAX_SPEED`]
+    ///   - `mode`: 时间伸缩(保持音高)/ 变调变速
+    pub fn set_speed(&self, speed: f32, mode: SpeedMode) {
+        let speed = clamp_speed(speed);
+        {
+            let mut g = self.inner.snapshot.lock();
+            g.speed_pct = speed_to_pct(speed);
+            g.speed_mode = mode;
+        }
+        self.send(AudioCommand::SetSpeed { speed, mode });
+    }
+
+    /// 钉住输出设备并热切换,播放位置不变。
+    ///
+    /// # Params:
+    ///   - `name`: 设备名;`None` = 跟随默认设备
+    pub fn set_output_device(&self, name: Option<String>) {
+        self.send(AudioCommand::SetDevice(name));
+    }
+
+    /// 当前输出设备名;null 模式(无设备)为 `None`。
+    pub fn output_device(&self) -> Option<String> {
+        self.inner.device.lock().clone()
+    }
+
+    /// 枚举可用输出设备,标注引擎当前所用的一个。
+    ///
+    /// # Return:
+    ///   设备列表;音频子系统不可用时报错。
+    pub fn output_devices(&self) -> color_eyre::Result<Vec<OutputDevice>> {
+        let active = self.output_device();
+        let mut devices = output::output_devices()?;
+        for d in &mut devices {
+            d.active = active.as_deref() == Some(d.name.as_str());
+        }
+        Ok(devices)
+    }
+
     /// UI tick 拉一次:engine 已经更新过的最新状态。
     pub fn snapshot(&self) -> AudioSnapshot {
         *self.inner.snapshot.lock()
@@ -280,7 +386,8 @@ mod tests {
     use crate::handle::AudioMode;
     use crate::snapshot::AudioBackend;
 
-    use super::{AudioHandle, EngineParams};
+    use super::{AudioHandle, CrossfadeParams, EngineParams, GainSlot, LimiterParams, Transition};
+    use crate::crossfade::CrossfadeCurve;
 
     /// 测试基线参数(任意合理值;生产默认的唯一真相源是 mineral-config 的 default.lua)。
     fn params(initial_volume: u8) -> EngineParams {
@@ -289,6 +396,22 @@ mod tests {
             .tick_ms(20)
             .prefetch_bytes(256 * 1024)
             .tap_capacity(8192)
+            .limiter(
+                LimiterParams::builder()
+                    .enabled(true)
+                    .threshold_db(-1.0)
+                    .release_ms(100)
+                    .build(),
+            )
+            .crossfade(
+                CrossfadeParams::builder()
+                    .enabled(true)
+                    .duration_ms(5_000)
+                    .curve(CrossfadeCurve::EqualPower)
+                    .build(),
+            )
+            .eq(None)
+            .device(None)
             .build()
     }
 
@@ -325,8 +448,10 @@ mod tests {
             MediaUrl::remote("https://example.com/next.mp3")?,
             Vec::new(),
             StreamLayout::Contiguous,
+            Transition::Crossfade,
         );
         handle.clear_next();
+        handle.set_track_gain(GainSlot::Next, -3.0);
         handle.stop();
         assert_eq!(
             handle.snapshot().volume_pct,
@@ -339,4 +464,65 @@ mod tests {
         assert_eq!(handle.snapshot().backend, AudioBackend::Null);
         Ok(())
     }
+
+    /// 交错立体声锯齿:左声道上行、右声道下行,幅度压在限幅阈值以下。
+    fn stereo_saw(frames: u16, offset: u16) -> Vec<i16> {
+        (0..frames)
+            .map(|i| i16::try_from((i % 7_000).saturating_add(offset)).unwrap_or_default())
+            .flat_map(|v| [v, -v])
+            .collect()
+    }
+
+    /// `File` 出口逐样本复现引擎产出:两首本地曲无缝接续,文件 == 两段输入首尾相连。
+    ///
+    /// 首曲长于一个快速渲染块,预排命令到达时首曲仍在播(真正走 gapless 接续路径)。
+    #[test]
+    fn file_sink_renders_gapless_pair_sample_exact() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (a, b) = (stereo_saw(60_000, 0), stereo_saw(2_000, 1_000));
+        let (a_path, b_path) = (dir.path().join("a.wav"), dir.path().join("b.wav"));
+        mineral_test::write_wav(&a_path, &a, 2, 44_100)?;
+        mineral_test::write_wav(&b_path, &b, 2, 44_100)?;
+        let out = dir.path().join("out.wav");
+
+        let mode = AudioMode::File {
+            path: out.clone(),
+            realtime: false,
+        };
+        let (handle, _tap) = AudioHandle::spawn(mode, params(100))?;
+        assert_eq!(handle.snapshot().backend, AudioBackend::File);
+        handle.play(
+            MediaUrl::local(a_path),
+            Vec::new(),
+            StreamLayout::Contiguous,
+        );
+        handle.append_next(
+            MediaUrl::local(b_path),
+            Vec::new(),
+            StreamLayout::Contiguous,
+            Transition::Gapless,
+        );
+
+        let want: Vec<f32> = a
+            .iter()
+            .chain(&b)
+            .map(|s| f32::from(*s) / 32_768.0)
+            .collect();
+        let mut got = Vec::new();
+        for _ in 0..250 {
+            std::thread::sleep(Duration::from_millis(20));
+            got = mineral_test::read_wav(&out)?.0;
+            if got.len() >= want.len() {
+                break;
+            }
+        }
+        assert_eq!(got.len(), want.len(), "文件应恰含两曲全部样本,不多不少");
+        let worst = got
+            .iter()
+            .zip(&want)
+            .map(|(g, w)| (g - w).abs())
+            .fold(0.0f32, f32::max);
+        assert!(worst < 1e-6, "逐样本应与输入一致,最大偏差 {worst}");
+        Ok(())
+    }
 }
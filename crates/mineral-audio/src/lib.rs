@@ -3,21 +3,37 @@
 //! 引擎跑在专属 OS 线程,owns rodio `OutputStream` 与 `Sink`;mpsc 命令通道把 UI 操作
 //! 转给 worker。snapshot 用 `Arc<Mutex<_>>` 共享给 UI 周期 polling。
 
+mod biquad;
 mod bps;
 mod command;
+mod crossfade;
+mod decode;
 mod engine;
 mod envelope;
+mod eq;
+mod file_sink;
 mod file_storage;
 mod handle;
+mod loudness;
+mod normalize;
+mod output;
 mod policy;
 mod queue_slots;
 mod snapshot;
+mod stream;
 mod tap;
+mod tempo;
 
 pub use bps::Bps;
+pub use crossfade::{CrossfadeCurve, CrossfadeParams, Transition};
 pub use envelope::{
     ENVELOPE_VERSION, EnvelopeParams, HighpassParams, ShelfParams, envelope_from_file,
     envelope_from_samples,
 };
+pub use eq::{EqBand, EqBandKind, EqParams};
 pub use handle::{AudioHandle, AudioMode, EngineParams, SpectrumTap};
+pub use loudness::{LOUDNESS_VERSION, analyze_file, loudness_from_samples};
+pub use normalize::{GainSlot, LimiterParams};
+pub use output::{OutputDevice, output_devices};
 pub use snapshot::{AudioBackend, AudioSnapshot};
+pub use tempo::{MAX_SPEED, MIN_SPEED, SpeedMode};
@@ -0,0 +1,360 @@
+//! 整曲积分响度(EBU R128 / ITU-R BS.1770):逐声道 K-weighting → 100ms 子块均方 →
+//! 400ms 门限块(75% 重叠)→ 绝对 −70 LUFS + 相对 −10 LU 双门限 → 门限内均值取对数。
+//!
+//! 与 [`crate::envelope`] 的刻意偏离相反,这里**按规范**算绝对响度值(归一化增益要跨曲
+//! 可比):逐声道滤波后求和而非 mono 下混,带 gating。仅两处简化:各声道权重一律 1.0
+//! (规范的环绕声道 1.41 / LFE 排除——音乐源几乎全是双声道),峰值取采样峰值而非
+//! 4× 过采样的 true peak(由播放端限幅器兜底)。
+//!
+//! 同为**离线一次性**计算:[`analyze_file`] 一遍解码同时喂包络与响度,两者同行落库。
+
+use std::num::{NonZeroU16, NonZeroU32};
+
+use color_eyre::eyre::eyre;
+use mineral_model::{Envelope, Loudness};
+use rodio::Source;
+
+use crate::envelope::{ENVELOPE_VERSION, EnvelopeParams, KWeighting, envelope_from_samples};
+
+/// 响度算法版本:门限 / 分块 / 声道合成的**结构**变更时 bump;读取方版本不符视同缺失、
+/// 触发重算。滤波参数沿用 `audio.envelope`,其数值变更同样不反映到版本里。
+pub const LOUDNESS_VERSION: u16 = 1;
+
+/// 门限块内的子块数:400ms 门限块 = 4 × 100ms 子块,逐子块滑动即规范的 75% 重叠。
+const SUBBLOCKS_PER_GATE: usize = 4;
+
+/// 子块时长(毫秒)。门限参数是规范定义的一部分,不随 `audio.envelope.block_ms` 走。
+const SUBBLOCK_MS: u64 = 100;
+
+/// 绝对门限(LUFS):低于它的门限块视为静音,不参与积分。全曲都被挡掉时即为结果。
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// 相对门限(LU):相对「过绝对门限的块的均值响度」再低这么多的块不参与积分。
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// 均方功率 → 响度(LUFS);−0.691 抵消 K-weighting 在 1kHz 的 +0.691dB。
+///
+/// # Params:
+///   - `power`: 声道求和后的均方功率
+///
+/// # Return:
+///   响度(LUFS);功率为 0 时为 `-inf`(自然落在任何门限之下)。
+fn power_to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// 流式响度计量:逐样本喂入交错样本,结束时给出门限积分响度与采样峰值。
+struct LoudnessMeter {
+    /// 每声道一套 K-weighting 滤波(声道间状态互不干扰)。
+    filters: Vec<KWeighting>,
+
+    /// 当前样本在帧内的声道下标。
+    channel: usize,
+
+    /// 子块帧数(按采样率折算的 100ms)。
+    frames_per_block: u32,
+
+    /// 当前子块已累积的帧数。
+    frames_in_block: u32,
+
+    /// 当前子块各声道加权平方和之和。
+    square_sum: f64,
+
+    /// 已完成子块的声道求和均方功率。
+    blocks: Vec<f64>,
+
+    /// 迄今最大采样绝对值。
+    peak: f64,
+}
+
+impl LoudnessMeter {
+    /// 按声道数 / 采样率建计量器。
+    ///
+    /// # Params:
+    ///   - `channels`: 声道数
+    ///   - `sample_rate`: 采样率(Hz,定滤波系数与子块帧数)
+    ///   - `params`: 包络参数(只取 K-weighting 两级滤波原型)
+    fn new(channels: NonZeroU16, sample_rate: NonZeroU32, params: &EnvelopeParams) -> Self {
+        let rate = f64::from(sample_rate.get());
+        let frames_per_block =
+            u32::try_from((u64::from(sample_rate.get()) * SUBBLOCK_MS / 1_000).max(1))
+                .unwrap_or(u32::MAX);
+        Self {
+            filters: (0..channels.get())
+                .map(|_| KWeighting::new(rate, params.shelf(), params.highpass()))
+                .collect(),
+            channel: 0,
+            frames_per_block,
+            frames_in_block: 0,
+            square_sum: 0.0,
+            blocks: Vec::new(),
+            peak: 0.0,
+        }
+    }
+
+    /// 喂入一个交错样本(帧内按声道顺序)。
+    fn push(&mut self, sample: f32) {
+        let x = f64::from(sample);
+        self.peak = self.peak.max(x.abs());
+        if let Some(filter) = self.filters.get_mut(self.channel) {
+            let y = filter.process(x);
+            self.square_sum += y * y;
+        }
+        self.channel += 1;
+        if self.channel < self.filters.len() {
+            return;
+        }
+        self.channel = 0;
+        self.frames_in_block = self.frames_in_block.saturating_add(1);
+        if self.frames_in_block >= self.frames_per_block {
+            self.blocks
+                .push(self.square_sum / f64::from(self.frames_in_block));
+            self.square_sum = 0.0;
+            self.frames_in_block = 0;
+        }
+    }
+
+    /// 收尾:残子块入账,按双门限积分。
+    ///
+    /// 不满一个门限块的极短输入按全部子块合成一块(规范未定义,取最接近的近似)。
+    ///
+    /// # Return:
+    ///   响度结果;输入不足一帧时 `None`。
+    fn finish(mut self) -> Option<Loudness> {
+        if self.frames_in_block > 0 {
+            self.blocks
+                .push(self.square_sum / f64::from(self.frames_in_block));
+        }
+        if self.blocks.is_empty() {
+            return None;
+        }
+        let gates: Vec<f64> = if self.blocks.len() < SUBBLOCKS_PER_GATE {
+            vec![mean(&self.blocks)]
+        } else {
+            self.blocks.windows(SUBBLOCKS_PER_GATE).map(mean).collect()
+        };
+        let audible: Vec<f64> = gates
+            .into_iter()
+            .filter(|p| power_to_lufs(*p) > ABSOLUTE_GATE_LUFS)
+            .collect();
+        let integrated_lufs = if audible.is_empty() {
+            ABSOLUTE_GATE_LUFS
+        } else {
+            let relative = power_to_lufs(mean(&audible)) + RELATIVE_GATE_LU;
+            let gated: Vec<f64> = audible
+                .iter()
+                .copied()
+                .filter(|p| power_to_lufs(*p) > relative)
+                .collect();
+            power_to_lufs(mean(&gated))
+        };
+        Some(Loudness {
+            integrated_lufs,
+            peak: self.peak,
+            version: LOUDNESS_VERSION,
+        })
+    }
+}
+
+/// 算术平均;空切片为 0。
+fn mean(values: &[f64]) -> f64 {
+    let count = u32::try_from(values.len()).unwrap_or(u32::MAX).max(1);
+    values.iter().sum::<f64>() / f64::from(count)
+}
+
+/// 交错多声道样本流 → 整曲积分响度 + 采样峰值。
+///
+/// # Params:
+///   - `interleaved`: 交错样本(帧内按声道顺序)
+///   - `channels`: 声道数
+///   - `sample_rate`: 采样率(Hz)
+///   - `params`: 包络参数(只取 K-weighting 滤波原型)
+///
+/// # Return:
+///   响度结果;输入解不出任何完整帧时 `None`。
+pub fn loudness_from_samples(
+    interleaved: impl Iterator<Item = f32>,
+    channels: NonZeroU16,
+    sample_rate: NonZeroU32,
+    params: &EnvelopeParams,
+) -> Option<Loudness> {
+    let mut meter = LoudnessMeter::new(channels, sample_rate, params);
+    interleaved.for_each(|s| meter.push(s));
+    meter.finish()
+}
+
+/// 离线解码整曲,一遍同时算出包络与积分响度。阻塞且 CPU 密集,调用方放 `spawn_blocking`。
+///
+/// 输入约束同 [`crate::envelope_from_file`]:必须是可完整读取的本地音频文件。
+///
+/// # Params:
+///   - `path`: 本地音频文件路径
+///   - `params`: 计算参数(配置 `audio.envelope` 切片)
+///
+/// # Return:
+///   `(包络, 响度)`,版本分别为 [`ENVELOPE_VERSION`] / [`LOUDNESS_VERSION`];
+///   打开 / 解码失败或解不出任何完整帧时报错。
+pub fn analyze_file(
+    path: &std::path::Path,
+    params: &EnvelopeParams,
+) -> color_eyre::Result<(Envelope, Loudness)> {
+    let (reader, byte_len) = crate::decode::open_local(path)?;
+    let decoder = crate::decode::build_decoder(reader, byte_len)?;
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let mut meter = LoudnessMeter::new(channels, sample_rate, params);
+    let points = envelope_from_samples(
+        decoder.inspect(|s| meter.push(*s)),
+        channels,
+        sample_rate,
+        params,
+    )
+    .ok_or_else(|| eyre!("解不出任何完整帧: {}", path.display()))?;
+    let loudness = meter
+        .finish()
+        .ok_or_else(|| eyre!("解不出任何完整帧: {}", path.display()))?;
+    Ok((
+        Envelope {
+            points,
+            version: ENVELOPE_VERSION,
+        },
+        loudness,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
+
+    use super::{LOUDNESS_VERSION, analyze_file, loudness_from_samples};
+    use crate::envelope::{EnvelopeParams, HighpassParams, ShelfParams};
+
+    /// 测试用参数(default.lua 同款 BS.1770 滤波原型)。
+    fn params() -> EnvelopeParams {
+        EnvelopeParams::builder()
+            .point_count(NonZeroUsize::new(8).unwrap_or(NonZeroUsize::MIN))
+            .block_ms(NonZeroU32::new(100).unwrap_or(NonZeroU32::MIN))
+            .window_ms(NonZeroU32::new(400).unwrap_or(NonZeroU32::MIN))
+            .shelf(
+                ShelfParams::builder()
+                    .f0_hz(1_681.974_450_955_533)
+                    .gain_db(3.999_843_853_973_347)
+                    .q(0.707_175_236_955_419_6)
+                    .band_exponent(0.499_666_774_154_541_6)
+                    .build(),
+            )
+            .highpass(
+                HighpassParams::builder()
+                    .f0_hz(38.135_470_876_024_44)
+                    .q(0.500_327_037_323_877_3)
+                    .build(),
+            )
+            .build()
+    }
+
+    /// 48kHz 采样率。
+    fn rate() -> NonZeroU32 {
+        NonZeroU32::new(48_000).unwrap_or(NonZeroU32::MIN)
+    }
+
+    /// 1kHz 正弦(48kHz),相位逐样本累加(`as` 全禁)。
+    fn tone(amplitude: f32, frames: usize) -> Vec<f32> {
+        let step = std::f32::consts::TAU * 1_000.0 / 48_000.0;
+        let mut phase = 0.0f32;
+        (0..frames)
+            .map(|_| {
+                let s = amplitude * phase.sin();
+                phase += step;
+                s
+            })
+            .collect()
+    }
+
+    /// 规范锚点:1kHz 正弦幅度 0.5(−6.02 dBFS)单声道 ≈ −9.03 LUFS;同信号双声道
+    /// 声道功率相加再高 3.01 LU。峰值即幅度。
+    #[test]
+    fn sine_reads_reference_loudness() -> color_eyre::Result<()> {
+        let mono = tone(0.5, 144_000);
+        let got = loudness_from_samples(mono.iter().copied(), NonZeroU16::MIN, rate(), &params())
+            .ok_or_else(|| color_eyre::eyre::eyre!("非空输入必须产出响度"))?;
+        assert!(
+            (got.integrated_lufs + 9.03).abs() < 0.1,
+            "单声道 −6dBFS 正弦应 ≈ −9.03 LUFS:{got:?}"
+        );
+        assert!((got.peak - 0.5).abs() < 1e-3, "峰值即幅度:{got:?}");
+        assert_eq!(got.version, LOUDNESS_VERSION);
+
+        let stereo = mono.iter().flat_map(|s| [*s, *s]);
+        let channels = NonZeroU16::new(2).unwrap_or(NonZeroU16::MIN);
+        let both = loudness_from_samples(stereo, channels, rate(), &params())
+            .ok_or_else(|| color_eyre::eyre::eyre!("非空输入必须产出响度"))?;
+        assert!(
+            (both.integrated_lufs - got.integrated_lufs - 3.01).abs() < 0.1,
+            "双声道同信号应高 3.01 LU:{both:?} vs {got:?}"
+        );
+        Ok(())
+    }
+
+    /// 双门限:静音段被绝对门限挡掉、−30dB 的轻声段被相对门限挡掉,积分值只由
+    /// 主体段决定——安静前奏不该把整曲拉低、让归一化过度提升。
+    #[test]
+    fn gating_ignores_silence_and_quiet_sections() -> color_eyre::Result<()> {
+        let body = tone(0.5, 288_000);
+        let alone = loudness_from_samples(body.iter().copied(), NonZeroU16::MIN, rate(), &params())
+            .ok_or_else(|| color_eyre::eyre::eyre!("非空输入必须产出响度"))?;
+        let padded = std::iter::repeat_n(0.0f32, 96_000)
+            .chain(tone(0.016, 96_000))
+            .chain(body.iter().copied());
+        let gated = loudness_from_samples(padded, NonZeroU16::MIN, rate(), &params())
+            .ok_or_else(|| color_eyre::eyre::eyre!("非空输入必须产出响度"))?;
+        assert!(
+            (gated.integrated_lufs - alone.integrated_lufs).abs() < 0.2,
+            "静音 / 轻声段应被门限挡掉:{gated:?} vs {alone:?}"
+        );
+        Ok(())
+    }
+
+    /// 全曲静音落绝对门限、峰值 0;空输入 `None`(不发明哨兵)。
+    #[test]
+    fn silence_and_empty_input() -> color_eyre::Result<()> {
+        let silent = loudness_from_samples(
+            std::iter::repeat_n(0.0f32, 48_000),
+            NonZeroU16::MIN,
+            rate(),
+            &params(),
+        )
+        .ok_or_else(|| color_eyre::eyre::eyre!("非空输入必须产出响度"))?;
+        assert!((silent.integrated_lufs - super::ABSOLUTE_GATE_LUFS).abs() < f64::EPSILON);
+        assert!(silent.peak.abs() < f64::EPSILON);
+        assert_eq!(
+            loudness_from_samples(
+                std::iter::empty::<f32>(),
+                NonZeroU16::MIN,
+                rate(),
+                &params()
+            ),
+            None
+        );
+        Ok(())
+    }
+
+    /// 整曲入口一遍解码同出两份结果:包络定长、响度有限且峰值对得上 WAV 满幅的一半。
+    #[test]
+    fn analyze_file_yields_envelope_and_loudness() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("square.wav");
+        // 1 秒 8kHz、幅度 16384(≈ 0.5 满幅)的 500Hz 方波。
+        let samples: Vec<i16> = (0..8_000i32)
+            .map(|i| if (i / 8) % 2 == 0 { 16_384 } else { -16_384 })
+            .collect();
+        mineral_test::write_wav(
+            &path, &samples, /*channels*/ 1, /*sample_rate*/ 8_000,
+        )?;
+        let (envelope, loudness) = analyze_file(&path, &params())?;
+        assert_eq!(envelope.points.len(), 8);
+        assert_eq!(envelope.version, crate::envelope::ENVELOPE_VERSION);
+        assert!(loudness.integrated_lufs.is_finite() && loudness.integrated_lufs > -20.0);
+        assert!((loudness.peak - 0.5).abs() < 1e-3, "{loudness:?}");
+        Ok(())
+    }
+}
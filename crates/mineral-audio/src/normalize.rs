@@ -0,0 +1,339 @@
+//! 响度归一化的播放端:逐曲增益 + 峰值限幅器,包在 decoder 外、[`crate::tap::TapSource`] 内。
+//!
+//! 增益**怎么算**(ReplayGain 标签 / R128 分析 / 目标响度 / 防削波)是 daemon 的事,引擎只认
+//! 「这首曲该乘多少 dB」。每首曲一个共享原子([`TrackGain`]),daemon 经
+//! [`crate::AudioHandle::set_track_gain`] 随时改写:起播前到达即时生效,播中到达(首播时
+//! 离线分析才算完)则按 [`GAIN_RAMP_MS`] 平滑过渡,不出咔哒声。
+//!
+//! 限幅器是最后一道保险:提升增益后仍可能超过满幅的采样(无峰值信息的流、采样间峰值)
+//! 瞬时压到门限,再按 release 时间常数指数回升。无 lookahead——峰值本身被精确限住,
+//! 代价是极端过载时的轻微失真,对「补几 dB」的归一化场景足够。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use rodio::source::SeekError;
+use rodio::{ChannelCount, SampleRate, Source};
+
+/// 增益变更的平滑时间常数(毫秒)。
+const GAIN_RAMP_MS: f64 = 50.0;
+
+/// 峰值限幅器参数(配置 `audio.normalization.limiter` 的切片)。
+#[non_exhaustive]
+#[derive(Clone, Debug, typed_builder::TypedBuilder, derive_getters::Getters)]
+pub struct LimiterParams {
+    /// 是否启用(归一化关闭时 daemon 一并关掉,不改动原始母带)。
+    enabled: bool,
+
+    /// 限幅门限(dBFS,≤ 0)。
+    threshold_db: f64,
+
+    /// 增益回升的时间常数(毫秒)。
+    release_ms: u32,
+}
+
+/// [`crate::AudioHandle::set_track_gain`] 的作用目标。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GainSlot {
+    /// 当前出声曲。
+    Current,
+
+    /// 已预排(或正在链下预排)的下一曲;gapless 边界轮转后自动成为当前曲的增益。
+    Next,
+}
+
+/// 一首曲的线性增益,跨线程共享(f32 位模式存原子)。新建即 0dB。
+#[derive(Clone, Debug)]
+pub(crate) struct TrackGain(Arc<AtomicU32>);
+
+impl Default for TrackGain {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU32::new(1.0f32.to_bits())))
+    }
+}
+
+impl TrackGain {
+    /// 按 dB 改写目标增益(非有限值按 0dB)。
+    pub(crate) fn set_db(&self, gain_db: f64) {
+        let linear = if gain_db.is_finite() {
+            db_to_linear(gain_db)
+        } else {
+            1.0
+        };
+        self.0.store(linear.to_bits(), Ordering::Relaxed);
+    }
+
+    /// 当前目标线性增益。
+    fn linear(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// 引擎两槽(当前 / 下一曲)的增益记账,与 [`crate::queue_slots::PlayHead`] 同步轮转。
+#[derive(Default)]
+pub(crate) struct TrackGains {
+    /// 当前曲增益。
+    cur: TrackGain,
+
+    /// 下一曲增益。
+    next: TrackGain,
+}
+
+impl TrackGains {
+    /// cut-over 起播:当前槽换成新的 0dB 增益并返回其句柄(包进新 decoder)。
+    pub(crate) fn arm_current(&mut self) -> TrackGain {
+        self.cur = TrackGain::default();
+        self.cur.clone()
+    }
+
+    /// 发起预排:下一曲槽换成新的 0dB 增益(其后的 `SetGain(Next)` 落到这里)。
+    pub(crate) fn arm_next(&mut self) {
+        self.next = TrackGain::default();
+    }
+
+    /// 下一曲槽的句柄(预排 decoder 建好时包进去)。
+    pub(crate) fn next(&self) -> TrackGain {
+        self.next.clone()
+    }
+
+    /// gapless 边界:下一曲成为当前曲。
+    pub(crate) fn rotate(&mut self) {
+        self.cur = std::mem::take(&mut self.next);
+    }
+
+    /// 改写某槽的目标增益。
+    pub(crate) fn set(&self, slot: GainSlot, gain_db: f64) {
+        match slot {
+            GainSlot::Current => self.cur.set_db(gain_db),
+            GainSlot::Next => self.next.set_db(gain_db),
+        }
+    }
+}
+
+/// 单极点平滑系数:时间常数 `ms` 折算成按交错样本推进的每步逼近比例。
+///
+/// # Params:
+///   - `ms`: 时间常数(毫秒)
+///   - `sample_rate`: 采样率
+///   - `channels`: 声道数(交错样本每帧推进 `channels` 步)
+///
+/// # Return:
+///   `(0, 1]` 的系数;时间常数为 0 时为 1(即时)。
+#[allow(clippy::as_conversions)] // reason: f64 系数落 f32 存储,值域 (0, 1] 无溢出
+fn smoothing(ms: f64, sample_rate: SampleRate, channels: ChannelCount) -> f32 {
+    let steps = ms / 1_000.0 * f64::from(sample_rate.get()) * f64::from(channels.get());
+    if steps <= 1.0 {
+        return 1.0;
+    }
+    (1.0 - (-1.0 / steps).exp()) as f32
+}
+
+/// dB → 线性幅度。
+#[allow(clippy::as_conversions)] // reason: 归一化增益 / 门限量级有限,f64 → f32 只损精度
+fn db_to_linear(db: f64) -> f32 {
+    10.0f64.powf(db / 20.0) as f32
+}
+
+/// 限幅器运行态。
+struct Limiter {
+    /// 门限(线性幅度)。
+    ceiling: f32,
+
+    /// release 每步回升比例。
+    release: f32,
+
+    /// 当前增益衰减(1.0 = 不衰减)。
+    reduction: f32,
+}
+
+impl Limiter {
+    /// 处理一个(已乘增益的)样本:超门限瞬时压住,其后指数回升。
+    fn process(&mut self, y: f32) -> f32 {
+        let level = y.abs();
+        let needed = if level > self.ceiling {
+            self.ceiling / level
+        } else {
+            1.0
+        };
+        let released = self.reduction + (1.0 - self.reduction) * self.release;
+        self.reduction = needed.min(released);
+        y * self.reduction
+    }
+}
+
+/// 包装 `Source<Item = f32>`:样本乘逐曲增益(平滑跟随目标),再过可选限幅器。
+pub(crate) struct NormalizeSource<S> {
+    /// 内层音频源(decoder)。
+    inner: S,
+
+    /// 本曲目标增益(daemon 可随时改写)。
+    gain: TrackGain,
+
+    /// 平滑后的实际增益。
+    current: f32,
+
+    /// 增益平滑每步逼近比例。
+    ramp: f32,
+
+    /// 限幅器(未启用为 `None`)。
+    limiter: Option<Limiter>,
+}
+
+impl<S> NormalizeSource<S>
+where
+    S: Source<Item = f32>,
+{
+    /// 包装 `inner`;起始增益直接取目标值(起播前已设好的增益不经过渡)。
+    pub(crate) fn new(inner: S, gain: TrackGain, limiter: &LimiterParams) -> Self {
+        let rate = inner.sample_rate();
+        let channels = inner.channels();
+        let limiter = limiter.enabled.then(|| Limiter {
+            ceiling: db_to_linear(limiter.threshold_db.min(0.0)),
+            release: smoothing(f64::from(limiter.release_ms), rate, channels),
+            reduction: 1.0,
+        });
+        Self {
+            current: gain.linear(),
+            inner,
+            gain,
+            ramp: smoothing(GAIN_RAMP_MS, rate, channels),
+            limiter,
+        }
+    }
+}
+
+impl<S> Iterator for NormalizeSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.inner.next()?;
+        self.current += (self.gain.linear() - self.current) * self.ramp;
+        let y = s * self.current;
+        Some(match self.limiter.as_mut() {
+            Some(limiter) => limiter.process(y),
+            None => y,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S> Source for NormalizeSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+
+    /// 透传给 inner decoder(不透传会回落到默认 `NotSupported`,seek 全失效)。
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32};
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{GainSlot, LimiterParams, NormalizeSource, TrackGain, TrackGains};
+
+    /// 测试用限幅参数。
+    fn limiter(enabled: bool) -> LimiterParams {
+        LimiterParams::builder()
+            .enabled(enabled)
+            .threshold_db(-6.0)
+            .release_ms(100)
+            .build()
+    }
+
+    /// 单声道 8kHz 样本缓冲。
+    fn buffer(samples: Vec<f32>) -> SamplesBuffer {
+        SamplesBuffer::new(
+            NonZeroU16::MIN,
+            NonZeroU32::new(8_000).unwrap_or(NonZeroU32::MIN),
+            samples,
+        )
+    }
+
+    /// 起播前设好的增益即时生效(−6dB ≈ ×0.501),不从 0dB 慢慢滑下来。
+    #[test]
+    fn preset_gain_applies_from_first_sample() {
+        let gain = TrackGain::default();
+        gain.set_db(-6.0);
+        let out: Vec<f32> =
+            NormalizeSource::new(buffer(vec![0.5; 4]), gain, &limiter(false)).collect();
+        assert!(
+            out.iter().all(|s| (s - 0.25).abs() < 1e-3),
+            "预设增益应从首样本生效:{out:?}"
+        );
+    }
+
+    /// 播中改写增益平滑过渡:改写后首样本不跳变,约 5 个时间常数后贴近目标。
+    #[test]
+    fn live_gain_change_ramps() {
+        let gain = TrackGain::default();
+        let mut source =
+            NormalizeSource::new(buffer(vec![0.5; 4_000]), gain.clone(), &limiter(false));
+        assert_eq!(source.next(), Some(0.5));
+        gain.set_db(-6.0);
+        let first = source.next().unwrap_or_default();
+        assert!(first > 0.45, "改写后首样本不得跳变:{first}");
+        let settled = source.nth(2_000).unwrap_or_default();
+        assert!((settled - 0.25).abs() < 1e-2, "应平滑贴近目标:{settled}");
+    }
+
+    /// 限幅器:提升后超门限的样本一律压在门限内;未启用时原样放大。
+    #[test]
+    fn limiter_caps_boosted_peaks() {
+        let gain = TrackGain::default();
+        gain.set_db(6.0);
+        let input = vec![0.9, -0.9, 0.1, 0.9];
+        let ceiling = 10.0f32.powf(-6.0 / 20.0);
+        let limited: Vec<f32> =
+            NormalizeSource::new(buffer(input.clone()), gain.clone(), &limiter(true)).collect();
+        assert!(
+            limited.iter().all(|s| s.abs() <= ceiling + 1e-6),
+            "限幅后不得超门限:{limited:?}"
+        );
+        let raw: Vec<f32> = NormalizeSource::new(buffer(input), gain, &limiter(false)).collect();
+        assert!(
+            raw.first().is_some_and(|s| *s > 1.0),
+            "未启用不限幅:{raw:?}"
+        );
+    }
+
+    /// 两槽记账:预排槽的增益在 gapless 轮转后成为当前槽;新预排槽回到 0dB。
+    #[test]
+    fn gains_rotate_with_the_play_head() {
+        let mut gains = TrackGains::default();
+        let _cur = gains.arm_current();
+        gains.arm_next();
+        gains.set(GainSlot::Next, -6.0);
+        let next = gains.next();
+        gains.rotate();
+        gains.set(GainSlot::Current, -12.0);
+        assert!((next.linear() - 10.0f32.powf(-12.0 / 20.0)).abs() < 1e-6);
+        assert!((gains.next().linear() - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,307 @@
+//! 输出设备:枚举、按名打开(找不到回落默认设备)、运行期热切换、拔出检测。
+//!
+//! rodio `Player` 不直接连设备:`Player::new()` 拿到的队列输出放进共享的 [`Relay`],设备
+//! mixer 只挂一个 relay。换设备 = 旧 relay 断开 + 新设备挂新 relay,队列本身(解码器、播放
+//! 位置、预排的下一曲)原封不动,所以切换不丢位置、不重新取流。
+//!
+//! 钉住的设备消失(USB DAC 拔出,cpal 报 `DeviceNotAvailable`)时回落默认设备;之后定期探测,
+//! 钉住的设备重新出现就切回去。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::eyre;
+use parking_lot::Mutex;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::queue::SourcesQueueOutput;
+use rodio::source::SeekError;
+use rodio::{ChannelCount, DeviceSinkBuilder, MixerDeviceSink, SampleRate, Source};
+use serde::{Deserialize, Serialize};
+
+/// 回落 / 失联期间探测设备的间隔。
+const PROBE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 一个可用的输出设备。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputDevice {
+    /// 设备名(配置 `audio.device` / 切换请求按它匹配)。
+    pub name: String,
+
+    /// 是否系统默认输出设备。
+    pub is_default: bool,
+
+    /// 是否引擎当前正在用的设备。
+    pub active: bool,
+}
+
+/// 设备的显示名;取不到时为空串。
+fn device_name(device: &rodio::Device) -> String {
+    device
+        .description()
+        .map(|d| d.name().to_owned())
+        .unwrap_or_default()
+}
+
+/// 枚举当前可用的输出设备(`active` 恒 false,由持有引擎的一侧标注)。
+///
+/// # Return:
+///   设备列表(按系统枚举序);音频子系统不可用时报错。
+pub fn output_devices() -> color_eyre::Result<Vec<OutputDevice>> {
+    let host = rodio::cpal::default_host();
+    let default = host.default_output_device().map(|d| device_name(&d));
+    let devices = host
+        .output_devices()
+        .map_err(|e| eyre!("枚举输出设备: {e}"))?;
+    Ok(devices
+        .map(|d| {
+            let name = device_name(&d);
+            OutputDevice {
+                is_default: default.as_deref() == Some(name.as_str()),
+                name,
+                active: false,
+            }
+        })
+        .collect())
+}
+
+/// 设备 mixer 上挂的中继:从共享队列拉样本;断开后返回 `None`,被设备 mixer 丢弃。
+struct Relay {
+    /// 共享的播放队列输出。
+    queue: Arc<Mutex<SourcesQueueOutput>>,
+
+    /// 本中继是否仍挂着(换设备时置 false)。
+    attached: Arc<AtomicBool>,
+}
+
+impl Iterator for Relay {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.attached.load(Ordering::Acquire) {
+            return None;
+        }
+        self.queue.lock().next()
+    }
+}
+
+impl Source for Relay {
+    fn current_span_len(&self) -> Option<usize> {
+        self.queue.lock().current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.queue.lock().channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.queue.lock().sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    fn try_seek(&mut self, _pos: Duration) -> Result<(), SeekError> {
+        Err(SeekError::NotSupported {
+            underlying_source: std::any::type_name::<Self>(),
+        })
+    }
+}
+
+/// 已打开的设备 sink。
+struct Opened {
+    /// 设备 sink(drop 即关流)。
+    sink: MixerDeviceSink,
+
+    /// 设备名。
+    name: String,
+
+    /// cpal 报告设备不可用时置位。
+    lost: Arc<AtomicBool>,
+}
+
+/// 按名打开输出设备;`pinned` 找不到(或为 `None`)时用默认设备。
+fn open_device(pinned: Option<&str>) -> color_eyre::Result<Opened> {
+    let host = rodio::cpal::default_host();
+    let found = pinned.and_then(|want| {
+        let hit = host
+            .output_devices()
+            .ok()
+            .and_then(|mut all| all.find(|d| device_name(d) == want));
+        if hit.is_none() {
+            mineral_log::warn!(target: "audio", device = want, "指定的输出设备不存在,回落默认设备");
+        }
+        hit
+    });
+    let device = found
+        .or_else(|| host.default_output_device())
+        .ok_or_else(|| eyre!("没有可用的输出设备"))?;
+    let name = device_name(&device);
+    let lost = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&lost);
+    let mut sink = DeviceSinkBuilder::from_device(device)
+        .map_err(|e| eyre!("rodio device sink: {e}"))?
+        .with_error_callback(move |e: rodio::cpal::StreamError| match e {
+            rodio::cpal::StreamError::DeviceNotAvailable
+            | rodio::cpal::StreamError::StreamInvalidated => flag.store(true, Ordering::Release),
+            other => mineral_log::debug!(target: "audio", error = %other, "输出流错误"),
+        })
+        .open_sink_or_fallback()
+        .map_err(|e| eyre!("rodio device sink: {e}"))?;
+    // 默认 drop 时会向 stderr 打一行 "Audio playback has finished",TUI 退出后会污染终端,关掉。
+    sink.log_on_drop(false);
+    Ok(Opened { sink, name, lost })
+}
+
+/// 引擎的输出端:共享队列 + 当前设备 sink + 钉住的设备名。
+pub(crate) struct Output {
+    /// 共享的播放队列输出(各代 relay 共用)。
+    queue: Arc<Mutex<SourcesQueueOutput>>,
+
+    /// 当前 relay 的挂载标志。
+    attached: Arc<AtomicBool>,
+
+    /// 当前设备。
+    opened: Opened,
+
+    /// 钉住的设备名(配置 `audio.device` / 运行期切换);`None` = 跟随默认设备。
+    pinned: Option<String>,
+
+    /// 失联后重开失败时,下次重试的时刻。
+    retry_at: Option<Instant>,
+
+    /// 回落期间下次探测钉住设备是否回来的时刻。
+    probe_at: Instant,
+}
+
+impl Output {
+    /// 打开输出设备并挂上播放队列。
+    ///
+    /// # Params:
+    ///   - `queue`: `rodio::Player::new()` 给出的队列输出
+    ///   - `pinned`: 钉住的设备名(找不到回落默认)
+    ///
+    /// # Return:
+    ///   连默认设备都打不开时报错(调用方降级 null)。
+    pub(crate) fn open(
+        queue: SourcesQueueOutput,
+        pinned: Option<String>,
+    ) -> color_eyre::Result<Self> {
+        let opened = open_device(pinned.as_deref())?;
+        let queue = Arc::new(Mutex::new(queue));
+        let attached = Arc::new(AtomicBool::new(true));
+        opened.sink.mixer().add(Relay {
+            queue: Arc::clone(&queue),
+            attached: Arc::clone(&attached),
+        });
+        Ok(Self {
+            queue,
+            attached,
+            opened,
+            pinned,
+            retry_at: None,
+            probe_at: Instant::now(),
+        })
+    }
+
+    /// 当前设备名。
+    pub(crate) fn device(&self) -> &str {
+        &self.opened.name
+    }
+
+    /// 钉住新设备并切过去(`None` = 跟随默认)。打不开时保留原设备。
+    pub(crate) fn switch(&mut self, pinned: Option<String>) -> color_eyre::Result<()> {
+        self.pinned = pinned;
+        self.reattach()
+    }
+
+    /// 每 tick 调:当前设备失联 → 重开(钉住的不在就回落默认);回落中探到钉住的设备回来 → 切回。
+    ///
+    /// # Return:
+    ///   设备有变化时 `true`。
+    pub(crate) fn maintain(&mut self) -> bool {
+        let now = Instant::now();
+        if self.opened.lost.load(Ordering::Acquire) {
+            // 拔出瞬间立即回落;重开失败才按探测间隔重试。
+            if self.retry_at.is_some_and(|t| now < t) {
+                return false;
+            }
+            return match self.reattach() {
+                Ok(()) => {
+                    self.retry_at = None;
+                    true
+                }
+                Err(e) => {
+                    mineral_log::warn!(target: "audio", error = mineral_log::chain(&e), "重开输出设备失败,稍后重试");
+                    self.retry_at = Some(now + PROBE_INTERVAL);
+                    false
+                }
+            };
+        }
+        let Some(want) = self.pinned.as_deref() else {
+            return false;
+        };
+        if want == self.opened.name || now < self.probe_at {
+            return false;
+        }
+        self.probe_at = now + PROBE_INTERVAL;
+        let back = output_devices().is_ok_and(|all| all.iter().any(|d| d.name == want));
+        back && self.reattach().is_ok()
+    }
+
+    /// 按当前 `pinned` 开新设备、挂新 relay,再断开旧 relay 并关旧设备。
+    fn reattach(&mut self) -> color_eyre::Result<()> {
+        let opened = open_device(self.pinned.as_deref())?;
+        let attached = Arc::new(AtomicBool::new(true));
+        // 先断旧再挂新:同一时刻只有一个 relay 在拉共享队列。
+        self.attached.store(false, Ordering::Release);
+        opened.sink.mixer().add(Relay {
+            queue: Arc::clone(&self.queue),
+            attached: Arc::clone(&attached),
+        });
+        mineral_log::info!(target: "audio", from = %self.opened.name, to = %opened.name, "切换输出设备");
+        self.attached = attached;
+        self.opened = opened;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use parking_lot::Mutex;
+    use rodio::buffer::SamplesBuffer;
+
+    use super::Relay;
+
+    /// 换中继不丢位置:旧 relay 断开后返回 `None`,新 relay 从同一队列接着拉后续样本。
+    #[test]
+    fn relay_handover_keeps_position() {
+        let (player, queue) = rodio::Player::new();
+        player.append(SamplesBuffer::new(
+            NonZeroU16::MIN,
+            NonZeroU32::new(8_000).unwrap_or(NonZeroU32::MIN),
+            (1u8..=8).map(f32::from).collect::<Vec<_>>(),
+        ));
+        let queue = Arc::new(Mutex::new(queue));
+        let first_flag = Arc::new(AtomicBool::new(true));
+        let mut first = Relay {
+            queue: Arc::clone(&queue),
+            attached: Arc::clone(&first_flag),
+        };
+        let head: Vec<f32> = first.by_ref().take(3).collect();
+        first_flag.store(false, Ordering::Release);
+        assert_eq!(first.next(), None, "断开的 relay 应结束");
+        let second = Relay {
+            queue,
+            attached: Arc::new(AtomicBool::new(true)),
+        };
+        let tail: Vec<f32> = second.take(5).collect();
+        assert_eq!(head, vec![1.0, 2.0, 3.0]);
+        assert_eq!(tail, vec![4.0, 5.0, 6.0, 7.0, 8.0]);
+    }
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::bps::Bps;
+use crate::tempo::SpeedMode;
 
 /// 音频输出后端的当前形态。
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +14,9 @@ pub enum AudioBackend {
 
     /// 降级:无可用音频设备,引擎空跑——命令被接受但不发声。
     Null,
+
+    /// 文件出口:不发声,混音后的 PCM 渲染进文件(配置 `audio.backend.file`)。
+    File,
 }
 
 /// 当前引擎状态的只读视图。
@@ -31,6 +35,12 @@ pub struct AudioSnapshot {
     /// 当前音量(0..=100)。
     pub volume_pct: u8,
 
+    /// 当前播放倍速(百分比,100 = 原速;50..=300)。位置 / 时长不受影响,始终是曲内媒体时间。
+    pub speed_pct: u16,
+
+    /// 当前变速方式。
+    pub speed_mode: SpeedMode,
+
     /// 单调递增的「曲终事件 latch」。每次 sink 自然播完一首歌(非 user-stop)engine
     /// 把它 +1。UI 维护 `last_seen_finished_seq`,看到增长就触发 advance —— 用 seq
     /// 而非 transient bool 是为了让 UI 在 tick 间隙也能可靠捕获边界,不会漏。
@@ -0,0 +1,272 @@
+//! 远端取流:stream-download 建 HTTP 流(可带取流头)、缓冲进度回调、capture 下完核对。
+//!
+//! 引擎线程的 cut-over 起播与链下预排都经 [`create_stream`] 取流,差别只在写哪个进度槽。
+
+use std::io::{Read, Seek};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use color_eyre::eyre::eyre;
+use stream_download::Settings;
+use stream_download::StreamDownload;
+use stream_download::StreamPhase;
+use stream_download::StreamState;
+use stream_download::http::HttpStream;
+use stream_download::http::reqwest::Client;
+use stream_download::source::SourceStream;
+use stream_download::storage::StorageProvider;
+use stream_download::storage::temp::TempStorageProvider;
+
+use crate::bps::Bps;
+use crate::decode::ReadSeek;
+use crate::file_storage::FileStorageProvider;
+use crate::policy::download_reached_full;
+use crate::queue_slots::SharedProgress;
+
+/// 取流目标:远端音频 URL + 附加请求头(如 B站 baseUrl 需 `Referer`)。二者是「怎么取这条流」
+/// 的一体两面,合并成一个参数,避免建流函数参数膨胀。
+pub(crate) struct StreamTarget {
+    /// 远端音频 URL。
+    pub(crate) url: url::Url,
+
+    /// 取流附加请求头;空 = 无附加头,走默认无头 client。
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+/// 起 stream-download(远端):建 HTTP 流、装好缓冲进度回调(写指定进度槽,代号门控挡旧流)、
+/// capture(非空)时 spawn 完成 waiter store 下完代号,返回装箱 reader + 字节长度。
+///
+/// # Params:
+///   - `target`: 取流目标(远端 URL + 取流头)
+///   - `capture`: 落盘路径(`Some` = 持久 capture 供入缓存,`None` = 会自删的 temp)
+///   - `stream_gen`: 本流代号
+///   - `progress_idx`: 写入的进度槽下标
+///   - `progress`: 双槽共享进度
+///   - `prefetch_bytes`: 起播前预拉的字节数(配置 `audio.prefetch_bytes`)
+///
+/// # Return:
+///   `(装箱 reader, 字节长度)`;字节长度 `None` 表示无 `Content-Length`。
+pub(crate) async fn create_stream(
+    target: StreamTarget,
+    capture: Option<PathBuf>,
+    stream_gen: u64,
+    progress_idx: usize,
+    progress: Arc<SharedProgress>,
+    prefetch_bytes: u64,
+) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)> {
+    match capture {
+        Some(path) => {
+            stream_with_provider(
+                target,
+                FileStorageProvider::new(path.clone()),
+                stream_gen,
+                progress_idx,
+                progress,
+                // 下完 waiter 核对该落盘文件字节数达 content_length 才标下完(挡截断)。
+                /*verify_path*/
+                Some(path),
+                prefetch_bytes,
+            )
+            .await
+        }
+        None => {
+            stream_with_provider(
+                target,
+                TempStorageProvider::new(),
+                stream_gen,
+                progress_idx,
+                progress,
+                /*verify_path*/ None,
+                prefetch_bytes,
+            )
+            .await
+        }
+    }
+}
+
+/// 把 `(name, value)` 头烤进一个 reqwest client 的 `default_headers`,供取流请求带上。
+///
+/// 用 `append`(非 `insert`):保序、允许重复同名头。非法头(名/值不合 HTTP 规范)跳过并 warn,
+/// 不掀掉整条请求;client build 失败(TLS 初始化等真错)冒泡。
+///
+/// # Params:
+///   - `headers`: 待注入的请求头(调用方已保证非空)
+///
+/// # Return:
+///   预配置好 `default_headers` 的 reqwest client
+fn client_with_headers(headers: &[(String, String)]) -> color_eyre::Result<Client> {
+    use stream_download::http::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(n), Ok(v)) => {
+                map.append(n, v);
+            }
+            _ => mineral_log::warn!(target: "audio", header = %name, "跳过非法取流头"),
+        }
+    }
+    Client::builder()
+        .default_headers(map)
+        .build()
+        .map_err(|e| eyre!("build headed http client: {e}"))
+}
+
+/// 用给定 `StorageProvider` 起 stream-download(两种 provider 走同一泛型路径,差别只在 `provider`)。
+///
+/// # Params:
+///   - `verify_path`: capture 落盘路径(`Some` = capture 播放,spawn waiter 等下完并**核对字节数**;
+///     `None` = 非 capture,不 spawn waiter)
+///   - `prefetch_bytes`: 起播前预拉的字节数(配置 `audio.prefetch_bytes`)
+async fn stream_with_provider<P>(
+    target: StreamTarget,
+    provider: P,
+    stream_gen: u64,
+    progress_idx: usize,
+    progress: Arc<SharedProgress>,
+    verify_path: Option<PathBuf>,
+    prefetch_bytes: u64,
+) -> color_eyre::Result<(Box<dyn ReadSeek>, Option<u64>)>
+where
+    P: StorageProvider + 'static,
+    P::Reader: Read + Seek + Send + Sync + 'static,
+{
+    let StreamTarget { url, headers } = target;
+    // 空头走默认 client(零行为变化,netease/local 保持原路径);带头则建一个把这些头烤进
+    // `default_headers` 的 reqwest client——B站 baseUrl 取流必须带 `Referer`,否则 403。
+    let stream = if headers.is_empty() {
+        HttpStream::<Client>::create(url)
+            .await
+            .map_err(|e| eyre!("http stream: {e}"))?
+    } else {
+        let client = client_with_headers(&headers)?;
+        HttpStream::new(client, url)
+            .await
+            .map_err(|e| eyre!("http stream (headed): {e}"))?
+    };
+    let len = stream.content_length();
+    let total = len.unwrap_or(0);
+    let prog = Arc::clone(&progress);
+    let settings = Settings::default()
+        .prefetch_bytes(prefetch_bytes)
+        .on_progress(
+            move |_stream: &HttpStream<Client>, state: StreamState, _cancel| {
+                // 切歌 / 换预排后旧流的迟到回调(代号不匹配)直接忽略,不污染当前缓冲。
+                let slot = prog.slot(progress_idx);
+                if slot.buffer_gen.load(Ordering::Acquire) != stream_gen {
+                    return;
+                }
+                let bps = match state.phase {
+                    // 长度未知(无 Content-Length)时比例恒零,下完瞬间补满。
+                    StreamPhase::Complete => Bps::FULL,
+                    _ => Bps::ratio(state.current_position, total),
+                };
+                slot.buffer_bps.store(bps.get(), Ordering::Release);
+            },
+        );
+    let reader = StreamDownload::from_stream(stream, provider, settings)
+        .await
+        .map_err(|e| eyre!("stream-download init: {e}"))?;
+    // capture 播放:拿 download handle,spawn 一个 waiter 等整段下完后 store 本曲代号。
+    // 必须在 reader 被 decoder 消费前取 handle。
+    if let Some(vpath) = verify_path {
+        let handle = reader.handle();
+        let done = Arc::clone(&progress);
+        tokio::spawn(async move {
+            handle.wait_for_completion().await;
+            // stream_download 在下载出错/断连时也 signal complete;核对落盘字节数达 content_length
+            // 才标下完,否则截断文件会被 harvest 进缓存、之后播放解码 IO 错。
+            let file_len = tokio::fs::metadata(&vpath)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+            if download_reached_full(file_len, len) {
+                done.slot(progress_idx)
+                    .done_gen
+                    .store(stream_gen, Ordering::Release);
+            } else {
+                mineral_log::warn!(
+                    target: "audio",
+                    stream_gen,
+                    file_len,
+                    expected = len.unwrap_or(0),
+                    "capture 未下完整段(截断),不标下完、不入缓存"
+                );
+            }
+        });
+    }
+    Ok((Box::new(reader), len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use crate::queue_slots::SharedProgress;
+    use crate::stream::{StreamTarget, create_stream};
+
+    /// `create_stream` 把 `headers` 注入 HTTP 取流请求:B站 baseUrl 播放必须带 `Referer`,否则
+    /// 403。起一个记录请求头的本地 server,用带 Referer 的 headers 建流,断言 server 收到该头。
+    ///
+    /// 直接测 `create_stream`(而非经 `AudioHandle::play`):`ForceNull` 模式走 `run_null_mode`
+    /// 短路建流、`Auto` 又需真声卡,都测不到取流路径;`create_stream` 是取流链的真实汇聚点。
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn create_stream_injects_headers_into_http_request() -> color_eyre::Result<()> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let recorded = Arc::new(Mutex::new(None::<String>));
+        let rec = Arc::clone(&recorded);
+        tokio::spawn(async move {
+            if let Ok((mut sock, _)) = listener.accept().await {
+                let mut buf = [0u8; 2048];
+                let n = sock.read(&mut buf).await.unwrap_or(0);
+                let req = buf
+                    .get(..n)
+                    .map(|b| String::from_utf8_lossy(b).into_owned())
+                    .unwrap_or_default();
+                *rec.lock() = Some(req);
+                let body = b"FAKEDATA";
+                let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+                drop(sock.write_all(head.as_bytes()).await);
+                drop(sock.write_all(body).await);
+                drop(sock.shutdown().await);
+            }
+        });
+
+        let url = url::Url::parse(&format!("http://{addr}/a.mp3"))?;
+        let progress = Arc::new(SharedProgress::default());
+        let target = StreamTarget {
+            url,
+            headers: vec![("Referer".to_owned(), "https://www.bilibili.com".to_owned())],
+        };
+        // body 非有效音频,建流本身不解码,故忽略返回;只验证请求已带上 header。
+        let _ = create_stream(
+            target, /*capture*/ None, /*stream_gen*/ 1, /*progress_idx*/ 0,
+            progress, /*prefetch_bytes*/ 0,
+        )
+        .await;
+
+        let raw = recorded.lock().clone().unwrap_or_default();
+        // HTTP header 名大小写不敏感(hyper 发小写),按名匹配、value 保原样。
+        let referer = raw.lines().find_map(|line| {
+            line.split_once(':').and_then(|(name, value)| {
+                name.trim()
+                    .eq_ignore_ascii_case("referer")
+                    .then(|| value.trim().to_owned())
+            })
+        });
+        assert_eq!(
+            referer.as_deref(),
+            Some("https://www.bilibili.com"),
+            "create_stream 应把 headers 注入 HTTP 请求;实际收到:\n{raw}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,537 @@
+//! 变速播放:保持音高的时间伸缩(WSOLA)与变调变速(磁带式)两种模式。
+//!
+//! 变调模式直接交给 rodio `Player::set_speed`(改采样率播放,音高随速度走);时间伸缩在
+//! [`TempoSource`] 里做波形相似叠加:按倍速跳着取输入窗口,每窗在标称位置附近搜一段与上一窗
+//! 「自然延续」最相像的起点,再以 Hann 窗 50% 重叠相加——音高不变、接缝不咔哒。倍速 1 时
+//! 原样直通,不付搜索的开销。
+//!
+//! 变速后 rodio 数的是输出样本(墙钟时间),不再等于曲内位置。每曲一个 [`TrackClock`] 记
+//! **媒体时间**(每个输出样本推进「倍速」个输入样本,seek 时对齐目标),snapshot 的位置 /
+//! 进度条 / 歌词同步 / 收听时长都读它。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use rodio::source::SeekError;
+use rodio::{ChannelCount, SampleRate, Source};
+use serde::{Deserialize, Serialize};
+
+/// 最低倍速。
+pub const MIN_SPEED: f32 = 0.5;
+
+/// 最高倍速。
+pub const MAX_SPEED: f32 = 3.0;
+
+/// 半窗长(ms):窗长 40ms,对语音与音乐都是常用的折中。
+const HALF_WINDOW_MS: u32 = 20;
+
+/// 相似度搜索半径(ms)。
+const SEARCH_MS: u32 = 10;
+
+/// 相似度计算的帧步长(抽样比较,省 3/4 运算,对选点影响可忽略)。
+const CORR_STRIDE: usize = 4;
+
+/// 倍速与 1 的差在此以内视为原速(直通)。
+const UNITY_EPSILON: f32 = 1e-3;
+
+/// 变速方式。
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedMode {
+    /// 时间伸缩:只改快慢,音高不变(播客 / 讲座)。
+    #[default]
+    TimeStretch,
+
+    /// 变调变速:像磁带快放,音高随速度升降。
+    PitchShift,
+}
+
+/// 把倍速钳进 [`MIN_SPEED`]..=[`MAX_SPEED`];非有限值回落原速。
+pub(crate) fn clamp_speed(speed: f32) -> f32 {
+    if speed.is_finite() {
+        speed.clamp(MIN_SPEED, MAX_SPEED)
+    } else {
+        1.0
+    }
+}
+
+/// 倍速 → 百分比(四舍五入;入参已钳过)。
+pub(crate) fn speed_to_pct(speed: f32) -> u16 {
+    #[allow(clippy::as_conversions)] // reason: 已钳进 0.5..=3,×100 后落在 u16 内
+    let pct = (speed * 100.0).round() as u16;
+    pct
+}
+
+/// 跨曲共享的时间伸缩倍速(`f32` 位模式);变调模式下恒为 1(变速交给 rodio)。
+pub(crate) type SharedStretch = Arc<AtomicU32>;
+
+/// 以原速建共享倍速。
+pub(crate) fn shared_stretch() -> SharedStretch {
+    Arc::new(AtomicU32::new(1.0f32.to_bits()))
+}
+
+/// 单曲的媒体时钟(曲内位置,ms),由该曲的 [`TempoSource`] 逐样本写入。
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TrackClock(Arc<AtomicU64>);
+
+impl TrackClock {
+    /// 当前曲内位置。
+    pub(crate) fn position(&self) -> Duration {
+        Duration::from_millis(self.0.load(Ordering::Relaxed))
+    }
+
+    /// 写入曲内位置。
+    fn store(&self, pos: Duration) {
+        let ms = u64::try_from(pos.as_millis()).unwrap_or(u64::MAX);
+        self.0.store(ms, Ordering::Relaxed);
+    }
+}
+
+/// 当前 / 下一曲的媒体时钟,与 [`crate::queue_slots::PlayHead`] 同步轮转。
+#[derive(Default)]
+pub(crate) struct TrackClocks {
+    /// 当前曲时钟。
+    cur: TrackClock,
+
+    /// 下一曲时钟。
+    next: TrackClock,
+}
+
+impl TrackClocks {
+    /// cut-over 起播:当前槽换成归零的新时钟并返回其句柄(包进新曲)。
+    pub(crate) fn arm_current(&mut self) -> TrackClock {
+        self.cur = TrackClock::default();
+        self.cur.clone()
+    }
+
+    /// 预排曲建好:下一曲槽换成归零的新时钟并返回其句柄。
+    pub(crate) fn arm_next(&mut self) -> TrackClock {
+        self.next = TrackClock::default();
+        self.next.clone()
+    }
+
+    /// gapless 边界:下一曲成为当前曲。
+    pub(crate) fn rotate(&mut self) {
+        self.cur = std::mem::take(&mut self.next);
+    }
+
+    /// 当前曲内位置。
+    pub(crate) fn position(&self) -> Duration {
+        self.cur.position()
+    }
+}
+
+/// usize 帧数 → f64。
+#[allow(clippy::as_conversions)] // reason: 缓冲帧数远小于 2^52,转 f64 无损
+fn to_f64(n: usize) -> f64 {
+    n as f64
+}
+
+/// 非负 f64 帧位置 → usize(向下取整,负值归零)。
+#[allow(clippy::as_conversions)] // reason: 帧位置非负且远小于 usize::MAX,截断即取整
+fn to_frames(x: f64) -> usize {
+    x.max(0.0) as usize
+}
+
+/// 采样率下的毫秒数 → 帧数(至少 1)。
+fn ms_to_frames(rate: SampleRate, ms: u32) -> usize {
+    usize::try_from(u64::from(rate.get()) * u64::from(ms) / 1_000)
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// 包装一曲的 `Source<Item = f32>`:按共享倍速做时间伸缩,并维护该曲的媒体时钟。
+pub(crate) struct TempoSource<S> {
+    /// 内层音频源(交叉淡化队列项)。
+    inner: S,
+
+    /// 跨曲共享的伸缩倍速。
+    stretch: SharedStretch,
+
+    /// 本曲媒体时钟。
+    clock: TrackClock,
+
+    /// 缓冲所按的声道数。
+    channels: ChannelCount,
+
+    /// 缓冲所按的采样率。
+    rate: SampleRate,
+
+    /// 半窗长(帧)。
+    half: usize,
+
+    /// 搜索半径(帧)。
+    search: usize,
+
+    /// 上升半窗(Hann 前半);下降半窗 = `1 - rise`,两者逐帧相加恒为 1。
+    rise: Vec<f32>,
+
+    /// 已读未用的输入(交错样本)。
+    buf: Vec<f32>,
+
+    /// `buf` 里「原样接续」的起点帧:直通从这里续,伸缩时它是上一窗的自然延续。
+    cursor: usize,
+
+    /// 上一窗的后半(已乘下降半窗,交错);空 = 直通态。
+    tail: Vec<f32>,
+
+    /// 上一窗的标称起点(`buf` 帧坐标,可为负)。
+    nominal: f64,
+
+    /// 待输出样本。
+    out: Vec<f32>,
+
+    /// `out` 的读位置。
+    out_pos: usize,
+
+    /// `out` 里每个样本对应的输入样本数(= 产出时的倍速)。
+    out_factor: f64,
+
+    /// 媒体时间基准(起播为 0,seek 后为目标位置)。
+    offset: Duration,
+
+    /// 基准之后已推进的输入样本数(交错计)。
+    media_samples: f64,
+}
+
+impl<S> TempoSource<S>
+where
+    S: Source<Item = f32>,
+{
+    /// 包装 `inner`,时钟从 0 起。
+    pub(crate) fn new(inner: S, stretch: SharedStretch, clock: TrackClock) -> Self {
+        let mut this = Self {
+            channels: inner.channels(),
+            rate: inner.sample_rate(),
+            inner,
+            stretch,
+            clock,
+            half: 1,
+            search: 1,
+            rise: Vec::new(),
+            buf: Vec::new(),
+            cursor: 0,
+            tail: Vec::new(),
+            nominal: 0.0,
+            out: Vec::new(),
+            out_pos: 0,
+            out_factor: 1.0,
+            offset: Duration::ZERO,
+            media_samples: 0.0,
+        };
+        this.refresh_format();
+        this
+    }
+
+    /// 按 inner 当前格式重算窗长 / 搜索半径 / 窗函数(仅在无缓冲时调)。
+    fn refresh_format(&mut self) {
+        let (channels, rate) = (self.inner.channels(), self.inner.sample_rate());
+        if !self.rise.is_empty() && channels == self.channels && rate == self.rate {
+            return;
+        }
+        self.channels = channels;
+        self.rate = rate;
+        self.half = ms_to_frames(rate, HALF_WINDOW_MS);
+        self.search = ms_to_frames(rate, SEARCH_MS);
+        let half = to_f64(self.half);
+        self.rise = (0..self.half)
+            .map(|i| {
+                let w = 0.5 - 0.5 * (std::f64::consts::PI * to_f64(i) / half).cos();
+                #[allow(clippy::as_conversions)] // reason: 窗系数在 [0, 1],f64 → f32 只损精度
+                let w = w as f32;
+                w
+            })
+            .collect();
+    }
+
+    /// 推进媒体时钟一个输出样本。
+    fn advance(&mut self, factor: f64) {
+        self.media_samples += factor;
+        let per_sec = f64::from(self.rate.get()) * f64::from(self.channels.get());
+        self.clock
+            .store(self.offset + Duration::from_secs_f64(self.media_samples / per_sec));
+    }
+
+    /// 缓冲补到至少 `frames` 帧。
+    ///
+    /// # Return:
+    ///   inner 已耗尽、补不够时 `false`。
+    fn fill(&mut self, frames: usize) -> bool {
+        let want = frames.saturating_mul(usize::from(self.channels.get()));
+        while self.buf.len() < want {
+            let Some(s) = self.inner.next() else {
+                return false;
+            };
+            self.buf.push(s);
+        }
+        true
+    }
+
+    /// 把 `buf` 里 `cursor` 起的剩余输入原样移进 `out`(切回直通 / 曲尾冲刷)。
+    ///
+    /// # Return:
+    ///   有样本可出时 `true`。
+    fn flush_raw(&mut self) -> bool {
+        self.tail.clear();
+        let start = self.cursor.saturating_mul(usize::from(self.channels.get()));
+        let rest = self
+            .buf
+            .get(start..)
+            .map(<[f32]>::to_vec)
+            .unwrap_or_default();
+        self.buf.clear();
+        self.cursor = 0;
+        self.out = rest;
+        self.out_pos = 0;
+        self.out_factor = 1.0;
+        !self.out.is_empty()
+    }
+
+    /// 某帧的各声道和(相似度只看单声道混合)。
+    fn mono_at(&self, frame: usize) -> f32 {
+        let ch = usize::from(self.channels.get());
+        self.buf
+            .get(frame * ch..(frame + 1) * ch)
+            .map_or(0.0, |f| f.iter().sum())
+    }
+
+    /// 在 `lo..=hi` 里找与 `cursor` 起的自然延续最相像的窗起点(归一化互相关最大)。
+    fn best_offset(&self, lo: usize, hi: usize, target: usize) -> usize {
+        let reference: Vec<f32> = (0..self.half)
+            .step_by(CORR_STRIDE)
+            .map(|i| self.mono_at(self.cursor + i))
+            .collect();
+        let score = |k: usize| {
+            let (dot, energy) =
+                reference
+                    .iter()
+                    .enumerate()
+                    .fold((0.0f32, 0.0f32), |(dot, energy), (j, r)| {
+                        let x = self.mono_at(k + j * CORR_STRIDE);
+                        (dot + r * x, energy + x * x)
+                    });
+            dot / (energy.sqrt() + 1e-9)
+        };
+        // 从标称点起步、严格更优才换:静音段落在标称点上,不乱跳。
+        let start = target.clamp(lo, hi);
+        (lo..=hi)
+            .fold((start, score(start)), |best, k| {
+                let s = score(k);
+                if s > best.1 { (k, s) } else { best }
+            })
+            .0
+    }
+
+    /// 伸缩一步:产出半窗样本进 `out`。
+    ///
+    /// # Return:
+    ///   inner 耗尽、凑不齐一窗时 `false`(调用方改为原样冲刷剩余输入)。
+    fn stretch_step(&mut self, factor: f32) -> bool {
+        let ch = usize::from(self.channels.get());
+        let (half, search) = (self.half, self.search);
+        if self.tail.is_empty() {
+            // 从直通进入伸缩:把 cursor 起的半窗当作「上一窗的后半」,后续与之自然衔接。
+            if !self.fill(self.cursor + half) {
+                return false;
+            }
+            let rise = &self.rise;
+            self.tail = self
+                .buf
+                .get(self.cursor * ch..(self.cursor + half) * ch)
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+                .map(|(i, x)| x * (1.0 - rise.get(i / ch).copied().unwrap_or(1.0)))
+                .collect();
+            self.nominal = to_f64(self.cursor) - to_f64(half);
+        }
+        let factor = f64::from(factor);
+        let target = self.nominal + to_f64(half) * factor;
+        let lo = to_frames(target - to_f64(search));
+        let hi = to_frames(target) + search;
+        if !self.fill((hi + 2 * half).max(self.cursor + half)) {
+            return false;
+        }
+        let k = self.best_offset(lo, hi, to_frames(target));
+        let (rise, buf) = (&self.rise, &self.buf);
+        let head = buf.get(k * ch..(k + half) * ch).unwrap_or_default();
+        self.out.clear();
+        self.out.extend(
+            self.tail
+                .iter()
+                .zip(head)
+                .enumerate()
+                .map(|(i, (t, x))| t + x * rise.get(i / ch).copied().unwrap_or(1.0)),
+        );
+        self.tail = buf
+            .get((k + half) * ch..(k + 2 * half) * ch)
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x * (1.0 - rise.get(i / ch).copied().unwrap_or(1.0)))
+            .collect();
+        self.out_pos = 0;
+        self.out_factor = factor;
+        self.cursor = k + half;
+        self.nominal = target;
+        // 丢掉之后再也用不到的输入:下一窗最低只会搜到 `nominal - search`。
+        let drop = self.cursor.min(to_frames(self.nominal - to_f64(search)));
+        self.buf.drain(..drop * ch);
+        self.cursor -= drop;
+        self.nominal -= to_f64(drop);
+        true
+    }
+}
+
+impl<S> Iterator for TempoSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(&s) = self.out.get(self.out_pos) {
+                self.out_pos += 1;
+                self.advance(self.out_factor);
+                return Some(s);
+            }
+            let factor = f32::from_bits(self.stretch.load(Ordering::Relaxed));
+            if (factor - 1.0).abs() < UNITY_EPSILON {
+                // 原速:先把伸缩态留下的输入原样吐完(上一窗后半与 cursor 起的输入同源,
+                // 丢掉 tail 直接续上即无缝),再逐样本直通。
+                if !self.buf.is_empty() && self.flush_raw() {
+                    continue;
+                }
+                let s = self.inner.next()?;
+                self.refresh_format();
+                self.advance(1.0);
+                return Some(s);
+            }
+            if self.buf.is_empty() && self.tail.is_empty() {
+                self.refresh_format();
+            }
+            if !self.stretch_step(factor) && !self.flush_raw() {
+                return None;
+            }
+        }
+    }
+}
+
+impl<S> Source for TempoSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        // 有缓冲时输出与 inner 的 span 边界对不齐;缓冲期间格式不变,报「直到结束」。
+        if self.buf.is_empty() && self.out_pos >= self.out.len() {
+            self.inner.current_span_len()
+        } else {
+            None
+        }
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+
+    /// 透传给 inner,清空伸缩缓冲,媒体时钟对齐到目标位置。
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos)?;
+        self.buf.clear();
+        self.tail.clear();
+        self.out.clear();
+        self.out_pos = 0;
+        self.cursor = 0;
+        self.offset = pos;
+        self.media_samples = 0.0;
+        self.clock.store(pos);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU32};
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use rodio::buffer::SamplesBuffer;
+
+    use super::{TempoSource, TrackClock, clamp_speed, shared_stretch};
+
+    /// 单声道 8kHz 正弦(`secs` 秒)。
+    fn sine(freq: f32, secs: u32) -> SamplesBuffer {
+        let samples: Vec<f32> = (0..8_000 * secs)
+            .map(|i| {
+                #[allow(clippy::as_conversions)] // reason: 测试样本序号 < 2^24,转 f32 无损
+                let t = i as f32 / 8_000.0;
+                (2.0 * std::f32::consts::PI * freq * t).sin() * 0.5
+            })
+            .collect();
+        SamplesBuffer::new(
+            NonZeroU16::MIN,
+            NonZeroU32::new(8_000).unwrap_or(NonZeroU32::MIN),
+            samples,
+        )
+    }
+
+    /// 过零次数(估主频)。
+    fn crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| matches!(w, [a, b] if (*a < 0.0) != (*b < 0.0)))
+            .count()
+    }
+
+    /// 原速直通:样本原样、时钟按媒体时间走。
+    #[test]
+    fn unity_passes_through() {
+        let clock = TrackClock::default();
+        let src = TempoSource::new(sine(440.0, 1), shared_stretch(), clock.clone());
+        let out: Vec<f32> = src.collect();
+        let reference: Vec<f32> = sine(440.0, 1).collect();
+        assert_eq!(out, reference);
+        assert_eq!(clock.position(), Duration::from_secs(1));
+    }
+
+    /// 2 倍伸缩:时长减半、音高不变,媒体时钟仍走完整曲长。
+    #[test]
+    fn stretch_halves_duration_and_keeps_pitch() {
+        let stretch = shared_stretch();
+        stretch.store(2.0f32.to_bits(), Ordering::Relaxed);
+        let clock = TrackClock::default();
+        let out: Vec<f32> = TempoSource::new(sine(440.0, 2), stretch, clock.clone()).collect();
+        assert!(
+            (7_600..=8_400).contains(&out.len()),
+            "2 秒素材 2 倍速应出约 1 秒:{}",
+            out.len()
+        );
+        // 1 秒 440Hz 正弦约 880 次过零;变调的话会翻倍。
+        let zc = crossings(&out);
+        assert!((820..=940).contains(&zc), "音高应不变:{zc}");
+        let pos = clock.position();
+        assert!(
+            pos >= Duration::from_millis(1_950) && pos <= Duration::from_millis(2_050),
+            "媒体时钟:{pos:?}"
+        );
+    }
+
+    /// 倍速钳在 0.5..=3,非有限值回落原速。
+    #[test]
+    fn speed_is_clamped() {
+        assert!((clamp_speed(0.1) - 0.5).abs() < f32::EPSILON);
+        assert!((clamp_speed(5.0) - 3.0).abs() < f32::EPSILON);
+        assert!((clamp_speed(f32::NAN) - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,164 @@
+//! 收藏夹写端点(`x/v3/fav/*`,表单 POST + `csrf`;全部需登录 cookie)。
+//!
+//! 收藏的资源按视频 `aid` + 类型 `2`(视频稿件)定位,不认 BV 号;`bvid:page` → aid 的映射在
+//! channel 层做(view 一跳)。
+
+use crate::transport::Transport;
+use crate::wire::de::from_value;
+use crate::wire::fav::FavFolder;
+
+/// 单个资源改收藏状态端点(一次可同时加入 / 移出多个夹)。
+const RESOURCE_DEAL_URL: &str = "https://api.bilibili.com/x/v3/fav/resource/deal";
+
+/// 批量把资源移出某收藏夹端点。
+const RESOURCE_BATCH_DEL_URL: &str = "https://api.bilibili.com/x/v3/fav/resource/batch-del";
+
+/// 新建收藏夹端点。
+const FOLDER_ADD_URL: &str = "https://api.bilibili.com/x/v3/fav/folder/add";
+
+/// 删除收藏夹端点。
+const FOLDER_DEL_URL: &str = "https://api.bilibili.com/x/v3/fav/folder/del";
+
+/// 修改收藏夹元信息端点(标题 / 简介)。
+const FOLDER_EDIT_URL: &str = "https://api.bilibili.com/x/v3/fav/folder/edit";
+
+/// 收藏资源类型:视频稿件。
+const RESOURCE_TYPE_VIDEO: i64 = 2;
+
+/// 把一个视频加入 / 移出若干收藏夹。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `aid`: 视频 aid
+///   - `add`: 要加入的收藏夹 id
+///   - `del`: 要移出的收藏夹 id
+pub async fn deal_resource(
+    transport: &Transport,
+    aid: i64,
+    add: &[i64],
+    del: &[i64],
+) -> color_eyre::Result<()> {
+    transport
+        .post_form(
+            RESOURCE_DEAL_URL,
+            vec![
+                ("rid", aid.to_string()),
+                ("type", RESOURCE_TYPE_VIDEO.to_string()),
+                ("add_media_ids", join_ids(add)),
+                ("del_media_ids", join_ids(del)),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// 把一批视频移出某收藏夹。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `fid`: 收藏夹 id(media_id)
+///   - `aids`: 要移出的视频 aid
+pub async fn batch_delete(transport: &Transport, fid: i64, aids: &[i64]) -> color_eyre::Result<()> {
+    transport
+        .post_form(
+            RESOURCE_BATCH_DEL_URL,
+            vec![
+                ("media_id", fid.to_string()),
+                ("resources", video_resources(aids)),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// 新建一个公开收藏夹。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `title`: 收藏夹标题
+///
+/// # Return:
+///   新建收藏夹的元信息 DTO(含分配的 id)。
+pub async fn create_folder(transport: &Transport, title: &str) -> color_eyre::Result<FavFolder> {
+    let data = transport
+        .post_form(
+            FOLDER_ADD_URL,
+            vec![
+                ("title", title.to_owned()),
+                ("intro", String::new()),
+                ("privacy", "0".to_owned()),
+            ],
+        )
+        .await?;
+    from_value(data)
+}
+
+/// 删除一个收藏夹(连同其内容)。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `fid`: 收藏夹 id(media_id)
+pub async fn delete_folder(transport: &Transport, fid: &str) -> color_eyre::Result<()> {
+    transport
+        .post_form(FOLDER_DEL_URL, vec![("media_ids", fid.to_owned())])
+        .await?;
+    Ok(())
+}
+
+/// 改收藏夹标题。端点会同时覆写简介,调用方需传回原简介以免被清空。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `fid`: 收藏夹 id(media_id)
+///   - `title`: 新标题
+///   - `intro`: 原简介(原样写回)
+pub async fn edit_folder(
+    transport: &Transport,
+    fid: &str,
+    title: &str,
+    intro: &str,
+) -> color_eyre::Result<()> {
+    transport
+        .post_form(
+            FOLDER_EDIT_URL,
+            vec![
+                ("media_id", fid.to_owned()),
+                ("title", title.to_owned()),
+                ("intro", intro.to_owned()),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+/// 收藏夹 id 列表 → 逗号分隔串(空列表 → 空串,端点视为不改)。
+fn join_ids(ids: &[i64]) -> String {
+    ids.iter().map(i64::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// 视频 aid 列表 → `batch-del` 的 `resources` 串(`aid:2,aid:2`)。
+fn video_resources(aids: &[i64]) -> String {
+    aids.iter()
+        .map(|aid| format!("{aid}:{RESOURCE_TYPE_VIDEO}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join_ids, video_resources};
+
+    /// `resources` 每项带视频类型后缀、逗号分隔。
+    #[test]
+    fn video_resources_tags_each_aid_with_type() {
+        assert_eq!(video_resources(&[170001, 42]), "170001:2,42:2");
+        assert_eq!(video_resources(&[]), "");
+    }
+
+    /// 夹 id 逗号分隔;空列表给空串(deal 端点的「不改这一侧」)。
+    #[test]
+    fn join_ids_is_comma_separated() {
+        assert_eq!(join_ids(&[1, 2, 3]), "1,2,3");
+        assert_eq!(join_ids(&[]), "");
+    }
+}
@@ -1,8 +1,10 @@
 //! 端点封装:transport 请求 + DTO 反序列化。薄层,业务编排(详情聚合 / cid 定位)在 channel。
 
 pub mod fav;
+pub mod fav_edit;
 pub mod login;
 pub mod playurl;
 pub mod search;
 pub mod space;
+pub mod subtitle;
 pub mod view;
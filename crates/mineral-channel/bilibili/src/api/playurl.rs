@@ -34,3 +34,20 @@ pub async fn playurl(
         .await?;
     from_value(data)
 }
+
+/// 取音频轨的 init 段(读无损轨的位深用)。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `url`: 音频轨取流直链
+///   - `range`: init 段字节范围(`segment_base.initialization`)
+///
+/// # Return:
+///   init 段字节。
+pub async fn init_segment(
+    transport: &Transport,
+    url: &str,
+    range: &str,
+) -> color_eyre::Result<Vec<u8>> {
+    transport.get_range(url, range).await
+}
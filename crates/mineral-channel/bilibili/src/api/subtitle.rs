@@ -0,0 +1,49 @@
+//! CC / AI 字幕:播放器信息端点(`player/wbi/v2`,WBI 签名)取字幕列表,再直取 CDN 上的字幕 JSON。
+
+use crate::convert::https_url;
+use crate::transport::Transport;
+use crate::wire::de::from_value;
+use crate::wire::subtitle::{PlayerInfo, SubtitleBody, SubtitleTrack};
+
+/// 播放器信息端点(含字幕列表)。
+const PLAYER_URL: &str = "https://api.bilibili.com/x/player/wbi/v2";
+
+/// 取某分 P 的字幕轨列表(无字幕为空)。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `bvid`: 视频 BV 号
+///   - `cid`: 分 P 的 cid(由 view 端点取得)
+///
+/// # Return:
+///   各语言字幕轨。
+pub async fn subtitle_tracks(
+    transport: &Transport,
+    bvid: &str,
+    cid: i64,
+) -> color_eyre::Result<Vec<SubtitleTrack>> {
+    let data = transport
+        .get_signed(
+            PLAYER_URL,
+            vec![("bvid", bvid.to_owned()), ("cid", cid.to_string())],
+        )
+        .await?;
+    let info: PlayerInfo = from_value(data)?;
+    Ok(info.subtitle.map(|s| s.subtitles).unwrap_or_default())
+}
+
+/// 下载一条字幕轨的字幕 JSON(协议相对 URL 补 `https:`)。
+///
+/// # Params:
+///   - `transport`: HTTP 传输层
+///   - `track`: 字幕轨(取其 `subtitle_url`)
+///
+/// # Return:
+///   字幕本体 DTO。
+pub async fn subtitle_body(
+    transport: &Transport,
+    track: &SubtitleTrack,
+) -> color_eyre::Result<SubtitleBody> {
+    let value = transport.get_value(&https_url(&track.subtitle_url)).await?;
+    from_value(value)
+}
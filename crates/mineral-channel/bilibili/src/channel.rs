@@ -2,15 +2,17 @@
 //!
 //! 业务层:组合 `api/` 端点(协议 → DTO)与 `convert`(DTO → mineral-model),收敛错误为
 //! `mineral_channel_core::Error`。B站取流需先经 view 定位分 P 的 cid,再打 playurl,故
-//! `song_urls` 每首两跳(view + playurl);详情/搜索是单跳。
+//! `song_urls` 每首两跳(view + playurl);详情/搜索是单跳。`lyrics` 同理先定位 cid,再取
+//! 播放器信息里的 CC / AI 字幕当行级歌词。收藏夹写操作(红心镜像 / 加删歌 / 建删改夹)只在
+//! 登录态开放,收藏端点认视频 aid,曲目 id 经 view 一跳映射。
 
 use async_trait::async_trait;
 use mineral_channel_core::{
     ArtistSectionKind, ArtistSections, ChannelCaps, Error, MusicChannel, Page, Result, SearchHits,
 };
 use mineral_model::{
-    Album, AlbumId, Artist, ArtistId, BitRate, PlayUrl, Playlist, PlaylistId, SearchKind, Song,
-    SongId, SourceKind, UserId,
+    Album, AlbumId, Artist, ArtistId, BitRate, Lyrics, PlayUrl, Playlist, PlaylistId, SearchKind,
+    Song, SongId, SourceKind, UserId,
 };
 use rustc_hash::FxHashSet;
 
@@ -28,7 +30,7 @@ pub struct BilibiliChannel {
     /// B站请求的 HTTP 传输层(isahc + WBI 签名 + buvid3 冷启动)。
     transport: Transport,
 
-    /// 登录用户 mid;`None`(guest)时 `my_playlists` 返回 `NotSupported`。
+    /// 登录用户 mid;`None`(guest)时 `my_playlists` 与收藏夹写操作返回 `NotSupported`。
     user_id: Option<UserId>,
 }
 
@@ -69,6 +71,53 @@ impl BilibiliChannel {
     pub fn transport(&self) -> &Transport {
         &self.transport
     }
+
+    /// 写操作前置:需登录态(mid),guest → `NotSupported`。
+    fn require_login(&self) -> Result<&UserId> {
+        self.user_id.as_ref().ok_or(Error::NotSupported)
+    }
+
+    /// 把 `bvid:page` 曲目 id 映射成收藏端点认的视频 aid:按 bvid 去重(同视频多 P 只算一个
+    /// 资源),每个视频一跳 view 取 aid;格式不符的 id 跳过。
+    async fn aids_for(&self, ids: &[SongId]) -> Result<Vec<i64>> {
+        let mut bvids = Vec::<String>::new();
+        for id in ids {
+            if let Some((bvid, _)) = parse_song_ref(id)
+                && !bvids.contains(&bvid)
+            {
+                bvids.push(bvid);
+            }
+        }
+        let mut aids = Vec::<i64>::with_capacity(bvids.len());
+        for bvid in bvids {
+            let info = api::view::video_info(&self.transport, &bvid)
+                .await
+                .map_err(map_err)?;
+            aids.push(info.aid);
+        }
+        Ok(aids)
+    }
+
+    /// 读无损轨 init 段里的位深;取不到只丢位深(debug 记一笔),不影响取流本身。
+    ///
+    /// # Params:
+    ///   - `play_url`: 选中的无损轨
+    ///   - `range`: 其 init 段字节范围
+    async fn flac_bit_depth(&self, play_url: &PlayUrl, range: &str) -> Option<u8> {
+        let url = play_url.url.as_remote()?;
+        match api::playurl::init_segment(&self.transport, url.as_str(), range).await {
+            Ok(init) => convert::flac_bit_depth(&init),
+            Err(e) => {
+                mineral_log::debug!(
+                    target: "bilibili",
+                    song_id = play_url.song_id.as_str(),
+                    error = mineral_log::chain(&e),
+                    "无损轨 init 段读取失败,不报位深"
+                );
+                None
+            }
+        }
+    }
 }
 
 /// api 层 `color_eyre::Report` 收敛到 channel-core 错误。
@@ -116,6 +165,13 @@ fn expansion_should_abort(err: &Error) -> bool {
     )
 }
 
+/// 收藏夹 PlaylistId 的裸值 → 数值 fid(加删歌端点按数值夹 id 收参,非数值 id 不发请求)。
+fn parse_fid(id: &PlaylistId) -> Result<i64> {
+    id.as_str()
+        .parse::<i64>()
+        .map_err(|e| Error::Parse(format!("非法收藏夹 id {}: {e}", id.as_str())))
+}
+
 /// 在视频详情里定位某分 P 的 cid:优先 `pages` 里 `page` 匹配项;单 P(无 pages)且
 /// `page <= 1` 用顶层 cid 兜底。
 fn cid_for_page(info: &VideoInfo, page: i32) -> Option<i64> {
@@ -134,13 +190,13 @@ impl MusicChannel for BilibiliChannel {
     }
 
     fn caps(&self) -> ChannelCaps {
-        // 全库搜视频(BV → Album)+ 用户(→ Artist);写操作永不支持(只读源)。B站一个视频
+        // 全库搜视频(BV → Album)+ 用户(→ Artist);收藏夹写操作仅登录态可用(guest 只读)。B站一个视频
         // 就是一张专辑,分 P 是曲目,搜索直接产 Album、不投影成 P1 单曲(避免「显示全 BV 总长、
         // 实播单 P」的时长错位)。song 模板用位置占位拆 `bvid:page` 复合裸 id(`?p=1` 对单 P
         // 视频冗余但合法,不特判);收藏夹的稳定分享 URL 需要 mid 参与,裸 fid 拼不出,歌单模板留空。
         ChannelCaps::builder()
             .searchable(vec![SearchKind::Album, SearchKind::Artist])
-            .playlist_edit(false)
+            .playlist_edit(self.user_id.is_some())
             // UP 主详情:只有投稿专辑区,无「热门曲」区(B站无整源热门单曲概念,见 artist_detail)。
             .artist_sections(ArtistSections::new(vec![ArtistSectionKind::Albums]))
             // album = 整个视频(裸 id 即 bvid,无分 P 段),用 `{id}` 整段;artist = UP 主空间页,
@@ -224,9 +280,10 @@ impl MusicChannel for BilibiliChannel {
             .collect())
     }
 
-    async fn song_urls(&self, ids: &[SongId], _quality: BitRate) -> Result<Vec<PlayUrl>> {
-        // 每首两跳:view 定位分 P cid → playurl 取 dash.audio → convert 带上 Referer 取流头。
-        // 音质档由 B站按登录态定(guest 封顶),故 `quality` 暂不下发。
+    async fn song_urls(&self, ids: &[SongId], quality: BitRate) -> Result<Vec<PlayUrl>> {
+        // 每首两跳:view 定位分 P cid → playurl 取 dash 全部音频轨 → convert 按 `quality`
+        // 选「不高于请求的最高档」并带上 Referer 取流头。B站按登录态决定给哪些轨(guest
+        // 无 flac / 杜比),请求侧无需区分,选轨自然回落。选中无损轨时再多一跳读 init 段报位深。
         let mut out = Vec::<PlayUrl>::new();
         for id in ids {
             let Some((bvid, page)) = parse_song_ref(id) else {
@@ -241,13 +298,58 @@ impl MusicChannel for BilibiliChannel {
             let result = api::playurl::playurl(&self.transport, &bvid, cid)
                 .await
                 .map_err(map_err)?;
-            if let Some(pu) = convert::playurl_to_play(id.clone(), result) {
+            if let Some(picked) = convert::playurl_to_play(id.clone(), result, quality) {
+                let mut pu = picked.play_url;
+                if let Some(range) = picked.flac_init {
+                    pu.bit_depth = self.flac_bit_depth(&pu, &range).await;
+                }
                 out.push(pu);
             }
         }
         Ok(out)
     }
 
+    async fn lyrics(&self, id: &SongId) -> Result<Lyrics> {
+        // view 定位 cid → player/v2 取字幕轨 → 下载原文轨(+ 第二语言轨当翻译)。
+        // 无字幕(绝大多数翻唱 / 演奏视频)返回空歌词,不当错误。
+        let Some((bvid, page)) = parse_song_ref(id) else {
+            return Ok(Lyrics::default());
+        };
+        let info = api::view::video_info(&self.transport, &bvid)
+            .await
+            .map_err(map_err)?;
+        let Some(cid) = cid_for_page(&info, page) else {
+            return Ok(Lyrics::default());
+        };
+        let tracks = api::subtitle::subtitle_tracks(&self.transport, &bvid, cid)
+            .await
+            .map_err(map_err)?;
+        let Some((primary, secondary)) = convert::pick_subtitle_tracks(tracks) else {
+            return Ok(Lyrics::default());
+        };
+        let primary = api::subtitle::subtitle_body(&self.transport, &primary)
+            .await
+            .map_err(map_err)?;
+        let secondary = match secondary {
+            // 翻译轨失败只丢翻译,原文照出。
+            Some(track) => match api::subtitle::subtitle_body(&self.transport, &track).await {
+                Ok(body) => Some(body),
+                Err(e) => {
+                    mineral_log::warn!(
+                        target: "bilibili",
+                        bvid,
+                        lan = track.lan,
+                        error = mineral_log::chain(&e),
+                        "翻译字幕下载失败,只出原文"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        Ok(convert::subtitles_to_lyrics(primary, secondary))
+    }
+
     async fn playlist_detail(&self, id: &PlaylistId) -> Result<Playlist> {
         // 收藏夹内容:翻页拉全条目,单 P 直接成曲;多 P 条目逐 BV 拉 view 展开成逐 P 曲目
         // (串行:只有多 P 条目才多这一跳,音乐向收藏夹里量级很小)。view 失败分两类:该视频
@@ -310,13 +412,94 @@ impl MusicChannel for BilibiliChannel {
             .map(convert::fav_folder_to_playlist)
             .collect())
     }
+
+    async fn create_playlist(&self, name: &str) -> Result<Playlist> {
+        self.require_login()?;
+        let folder = api::fav_edit::create_folder(&self.transport, name)
+            .await
+            .map_err(map_err)?;
+        Ok(convert::fav_folder_to_playlist(folder))
+    }
+
+    async fn delete_playlist(&self, id: &PlaylistId) -> Result<()> {
+        self.require_login()?;
+        api::fav_edit::delete_folder(&self.transport, id.as_str())
+            .await
+            .map_err(map_err)
+    }
+
+    async fn playlist_add_songs(&self, id: &PlaylistId, songs: &[SongId]) -> Result<()> {
+        // 收藏单位是整个视频:同视频多 P 只收一次,逐视频 deal(无批量加入端点)。
+        self.require_login()?;
+        let fid = parse_fid(id)?;
+        for aid in self.aids_for(songs).await? {
+            api::fav_edit::deal_resource(&self.transport, aid, &[fid], &[])
+                .await
+                .map_err(map_err)?;
+        }
+        Ok(())
+    }
+
+    async fn playlist_remove_songs(&self, id: &PlaylistId, songs: &[SongId]) -> Result<()> {
+        // 移除某 P 即移出整个视频(多 P 条目在收藏夹里是一条资源)。
+        self.require_login()?;
+        let fid = parse_fid(id)?;
+        let aids = self.aids_for(songs).await?;
+        if aids.is_empty() {
+            return Ok(());
+        }
+        api::fav_edit::batch_delete(&self.transport, fid, &aids)
+            .await
+            .map_err(map_err)
+    }
+
+    async fn rename_playlist(&self, id: &PlaylistId, name: &str) -> Result<()> {
+        // edit 端点标题 / 简介一起覆写:先取原简介原样写回,免得改名把简介清空。
+        self.require_login()?;
+        let fid = id.as_str();
+        let current = api::fav::resource_list(&self.transport, fid, 1)
+            .await
+            .map_err(map_err)?;
+        let intro = current.info.and_then(|i| i.intro).unwrap_or_default();
+        api::fav_edit::edit_folder(&self.transport, fid, name, &intro)
+            .await
+            .map_err(map_err)
+    }
+
+    async fn set_loved(&self, id: &SongId, loved: bool) -> Result<()> {
+        // 红心 ↔ 默认收藏夹:喜欢即收进默认夹,取消即移出。默认夹每次现查(用户可能在别处
+        // 改过),一跳 created/list。
+        let uid = self.require_login()?;
+        let folders = api::fav::created_folders(&self.transport, uid.as_str())
+            .await
+            .map_err(map_err)?;
+        let Some(fid) = convert::default_fav_folder(&folders.list.unwrap_or_default()) else {
+            return Err(Error::Other(color_eyre::eyre::eyre!("未找到默认收藏夹")));
+        };
+        let Some(aid) = self
+            .aids_for(std::slice::from_ref(id))
+            .await?
+            .into_iter()
+            .next()
+        else {
+            return Err(Error::Parse(format!("非法 B站 song id: {}", id.as_str())));
+        };
+        let (add, del) = if loved {
+            (vec![fid], Vec::new())
+        } else {
+            (Vec::new(), vec![fid])
+        };
+        api::fav_edit::deal_resource(&self.transport, aid, &add, &del)
+            .await
+            .map_err(map_err)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use mineral_channel_core::Error;
 
-    use super::{cid_for_page, expansion_should_abort};
+    use super::{BilibiliChannel, cid_for_page, expansion_should_abort};
     use crate::wire::de::from_value;
     use crate::wire::view::VideoInfo;
 
@@ -353,6 +536,48 @@ mod tests {
         );
     }
 
+    /// 歌单写能力随登录态:guest 只读,带凭证才声明 `playlist_edit`;guest 调写操作得
+    /// `NotSupported`(不发请求),登录态给非数值收藏夹 id 得 `Parse`(同样不发请求)。
+    #[tokio::test]
+    async fn playlist_edit_caps_follow_login_state() -> color_eyre::Result<()> {
+        use mineral_channel_core::MusicChannel;
+        use mineral_model::{PlaylistId, SourceKind};
+
+        use crate::config::BilibiliConfig;
+        use crate::credential::StoredBilibiliAuth;
+
+        let cfg = BilibiliConfig::builder()
+            .max_connections(0)
+            .proxy(None)
+            .timeout_secs(30)
+            .build();
+        let guest = BilibiliChannel::new(&cfg)?;
+        assert!(!*guest.caps().playlist_edit(), "guest 只读");
+        let pid = PlaylistId::new(SourceKind::BILIBILI, "42");
+        assert!(
+            matches!(guest.delete_playlist(&pid).await, Err(Error::NotSupported)),
+            "guest 写操作应 NotSupported"
+        );
+
+        let auth = StoredBilibiliAuth {
+            sessdata: "S".to_owned(),
+            bili_jct: "J".to_owned(),
+            dede_user_id: "42".to_owned(),
+        };
+        let logged_in = BilibiliChannel::with_credential(&cfg, &auth)?;
+        assert!(*logged_in.caps().playlist_edit(), "登录态可写收藏夹");
+        let bad = PlaylistId::new(SourceKind::BILIBILI, "fav");
+        let songs = [mineral_model::SongId::new(SourceKind::BILIBILI, "BV1xx:1")];
+        assert!(
+            matches!(
+                logged_in.playlist_remove_songs(&bad, &songs).await,
+                Err(Error::Parse(_))
+            ),
+            "非数值收藏夹 id 在发请求前拒绝"
+        );
+        Ok(())
+    }
+
     /// 多 P 视频按 page 号定位 cid:命中 `pages` 里对应 page 的 cid。
     #[test]
     fn cid_for_page_locates_matching_page() -> color_eyre::Result<()> {
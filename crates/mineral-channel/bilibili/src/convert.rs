@@ -3,15 +3,19 @@
 //! 内容层级映射:分 P → [`Song`],视频(BV)→ [`Album`],单 P 视频即一首歌。SongId 用
 //! `{bvid}:{page}` 形态(全局唯一;裸值喂后端时按 `:` 拆回 bvid + 分 P 号)。
 
+use std::time::Duration;
+
 use mineral_model::{
-    Album, AlbumId, AlbumRef, Artist, ArtistId, ArtistRef, AudioFormat, BitRate, MediaUrl, PlayUrl,
-    Playlist, PlaylistId, Song, SongId, SourceKind, StreamLayout,
+    Album, AlbumId, AlbumRef, Artist, ArtistId, ArtistRef, AudioFormat, BitRate, CST_OFFSET_MS,
+    LyricLine, Lyrics, MediaUrl, PlayUrl, Playlist, PlaylistId, Song, SongId, SourceKind,
+    StreamLayout, release_year,
 };
 
 use crate::wire::fav::{FavFolder, FavInfo, FavMedia};
 use crate::wire::playurl::{DashAudio, PlayUrlResult};
 use crate::wire::search::{SearchUserItem, SearchVideoItem};
 use crate::wire::space::{ArcVideoItem, CardInfo, CardResult};
+use crate::wire::subtitle::{SubtitleBody, SubtitleTrack};
 use crate::wire::view::{VideoInfo, VideoOwner, VideoPage};
 
 /// 去掉标题里的 `<em ...>` / `</em>` 高亮标签(B站搜索给命中词裹上的 keyword 标记)。
@@ -56,16 +60,25 @@ pub(crate) fn seconds_to_ms(secs: i64) -> u64 {
     u64::try_from(secs).unwrap_or(0).saturating_mul(1000)
 }
 
+/// 浮点秒 → 毫秒(负值 / NaN 落 0,越界饱和)。字幕时间轴是浮点秒。
+fn float_seconds_to_ms(secs: f64) -> u64 {
+    Duration::try_from_secs_f64(secs)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// 协议相对 URL(`//host/...`)补 `https:`;已带协议的原样返回。
+pub(crate) fn https_url(raw: &str) -> String {
+    raw.strip_prefix("//")
+        .map(|rest| format!("https://{rest}"))
+        .unwrap_or_else(|| raw.to_owned())
+}
+
 /// B站封面 URL → [`MediaUrl::Remote`]:协议相对(`//host/...`)补 `https:`,空串 → `None`。
 fn cover_media_url(pic: &str) -> Option<MediaUrl> {
     if pic.is_empty() {
         return None;
     }
-    let full = pic
-        .strip_prefix("//")
-        .map(|rest| format!("https://{rest}"))
-        .unwrap_or_else(|| pic.to_owned());
-    MediaUrl::remote(&full).ok()
+    MediaUrl::remote(&https_url(pic)).ok()
 }
 
 /// UP 主 → [`ArtistRef`](mid 入 BILIBILI namespace)。
@@ -114,7 +127,8 @@ pub(crate) fn search_video_to_album(item: SearchVideoItem) -> Option<Album> {
 ///   - `video_title`: 视频标题(作 album 名)
 ///   - `owner`: UP 主(作 artist)
 ///   - `pic`: 视频封面 URL(协议相对会补 https)
-///   - `page`: 分 P 元信息(`part` 作曲名、`page` 拼进 SongId、`duration` 秒 → ms)
+///   - `page`: 分 P 元信息(`part` 作曲名、`page` 拼进 SongId 并作音轨号、`duration` 秒 → ms)
+///   - `pubdate`: 投稿时间(unix 秒),取年份作发行年
 ///
 /// # Return:
 ///   该 P 对应的 [`Song`],id 为 `{bvid}:{page}`。
@@ -124,6 +138,7 @@ pub(crate) fn view_page_to_song(
     owner: &VideoOwner,
     pic: Option<&str>,
     page: &VideoPage,
+    pubdate: Option<i64>,
 ) -> Song {
     let duration_ms = Some(seconds_to_ms(page.duration));
     Song::builder()
@@ -137,6 +152,10 @@ pub(crate) fn view_page_to_song(
             id: AlbumId::new(SourceKind::BILIBILI, bvid.to_owned()),
             name: video_title.to_owned(),
         }))
+        // 视频即专辑:UP 主即专辑艺人,分 P 序号即音轨号,投稿年即发行年。
+        .album_artist(Some(owner.name.clone()).filter(|n| !n.is_empty()))
+        .track_no(u32::try_from(page.page).ok().filter(|n| *n > 0))
+        .year(pubdate.and_then(|s| release_year(s.saturating_mul(1000), CST_OFFSET_MS)))
         .duration_ms(duration_ms)
         .cover_url(pic.and_then(cover_media_url))
         .build()
@@ -171,7 +190,7 @@ pub(crate) fn view_to_album(info: VideoInfo) -> Album {
     };
     let songs = pages
         .iter()
-        .map(|page| view_page_to_song(&bvid, &title, &owner, pic.as_deref(), page))
+        .map(|page| view_page_to_song(&bvid, &title, &owner, pic.as_deref(), page, pubdate))
         .collect::<Vec<Song>>();
     let track_count = u64::try_from(songs.len()).unwrap_or(0);
     Album::builder()
@@ -200,59 +219,139 @@ fn playback_stream_headers() -> Vec<(String, String)> {
     ]
 }
 
+/// 64k 音频轨音质码(guest 可取)。
+const AUDIO_64K: i64 = 30216;
+
+/// 132k 音频轨音质码(guest 可取)。
+const AUDIO_132K: i64 = 30232;
+
+/// 杜比全景声音频轨音质码(大会员,E-AC-3;播放层没有 E-AC-3 解码器,不参与选轨)。
+const AUDIO_DOLBY: i64 = 30250;
+
+/// 无损音频轨音质码(大会员,FLAC)。
+const AUDIO_FLAC: i64 = 30251;
+
+/// [`playurl_to_play`] 的选轨结果。
+pub(crate) struct PickedPlay {
+    /// 选中轨的播放链接;`bit_depth` 待调用方读 init 段补上。
+    pub(crate) play_url: PlayUrl,
+
+    /// 选中的是无损轨时,其 init 段字节范围(位深藏在里面,见 [`flac_bit_depth`])。
+    pub(crate) flac_init: Option<String>,
+}
+
 /// playurl DTO → [`PlayUrl`];无可用音频轨返回 `None`。
 ///
-/// 选轨:有无损(flac)优先取无损,否则取 `id` 最大的普通音频轨。音质/格式按 `id` + `codecs`
-/// 归一化;url 带上取流 [`playback_stream_headers`]。
+/// 选轨按音质档:普通 / 无损轨汇成一池,各轨经 [`classify_audio`] 归到 [`BitRate`] 档
+/// (`30216`/`30232`/`30280`/FLAC 依次对应 Standard → Lossless),取不高于 `quality` 的
+/// 最高档;池里全比请求高(如只要 Standard 却只给了 192k)就取最低档。guest 拿不到无损轨,
+/// 请求高档时自然落到 192k。杜比轨(E-AC-3)播放层解不了,不进池。`quality` / `format` /
+/// `bitrate_bps` 回填实际选中轨的值,url 带上取流 [`playback_stream_headers`]。
 ///
 /// # Params:
 ///   - `song_id`: 目标分 P 的 SongId(`{bvid}:{page}`)
 ///   - `result`: playurl 响应的 `data`
+///   - `quality`: 请求的目标音质(上限)
 ///
 /// # Return:
-///   可播的 [`PlayUrl`];无音频轨 / url 解析失败为 `None`。
-pub(crate) fn playurl_to_play(song_id: SongId, result: PlayUrlResult) -> Option<PlayUrl> {
+///   选中轨;无音频轨 / url 解析失败为 `None`。
+pub(crate) fn playurl_to_play(
+    song_id: SongId,
+    result: PlayUrlResult,
+    quality: BitRate,
+) -> Option<PickedPlay> {
     let dash = result.dash?;
-    let flac_audio = dash.flac.and_then(|f| f.audio);
-    let best = best_audio(flac_audio, dash.audio.unwrap_or_default())?;
-    let (quality, format) = classify_audio(best.id, best.codecs.as_deref());
+    let mut pool = dash.audio.unwrap_or_default();
+    pool.extend(dash.flac.and_then(|f| f.audio));
+    pool.retain(|a| !is_dolby(a));
+    let (best, tier, format) = pick_audio(pool, quality)?;
     let url = MediaUrl::remote(&best.base_url).ok()?;
-    Some(PlayUrl {
+    let flac_init = (format == AudioFormat::Flac)
+        .then(|| best.segment_base.as_ref().map(|s| s.initialization.clone()))
+        .flatten();
+    let play_url = PlayUrl {
         song_id,
         url,
         bitrate_bps: best.bandwidth.and_then(|b| u32::try_from(b).ok()),
-        quality,
+        quality: tier,
         // playurl 接口不给文件大小。
         size: None,
         format: Some(format),
+        // playurl 不报位深:无损轨由调用方读 init 段补,有损轨本无位深可言。
         bit_depth: None,
         stream_headers: playback_stream_headers(),
         // B站音频是分片 fMP4:告知播放层以流式打开,避免 seekable 全扫导致起播前拉整段。
         layout: StreamLayout::Chunked,
         substituted: false,
+    };
+    Some(PickedPlay {
+        play_url,
+        flac_init,
     })
 }
 
-/// 选最优音频轨:无损轨(若有)优先,否则普通轨里取 `id` 最大(质量最高)。
-fn best_audio(flac: Option<DashAudio>, mut normal: Vec<DashAudio>) -> Option<DashAudio> {
-    if let Some(f) = flac {
-        return Some(f);
+/// 是否杜比轨(码 `30250` 或 codecs 含 `ec-3`)。
+fn is_dolby(audio: &DashAudio) -> bool {
+    audio.id == AUDIO_DOLBY
+        || audio
+            .codecs
+            .as_deref()
+            .is_some_and(|c| c.to_ascii_lowercase().contains("ec-3"))
+}
+
+/// 从无损轨的 init 段读位深:`dfLa` box(FLAC-in-MP4)里首个元数据块是 STREAMINFO,
+/// 位深在其第 12~13 字节的 5 个 bit(存的是位深 - 1)。
+///
+/// # Params:
+///   - `init`: init 段字节
+///
+/// # Return:
+///   位深;找不到 `dfLa` / STREAMINFO 时为 `None`。
+pub(crate) fn flac_bit_depth(init: &[u8]) -> Option<u8> {
+    let at = init.windows(4).position(|w| w == b"dfLa")?;
+    // box 类型后:version + flags 4 字节,再是元数据块头(1 字节类型 + 3 字节长度)。
+    let block = init.get(at + 8..)?;
+    if block.first()? & 0x7f != 0 {
+        return None;
+    }
+    let info = block.get(4..)?;
+    let (hi, lo) = (info.get(12)?, info.get(13)?);
+    Some((((hi & 0x01) << 4) | (lo >> 4)) + 1)
+}
+
+/// 从候选池选轨:不高于 `quality` 的最高档;没有则退最低档。同档取 `id` 大、码率高者。
+///
+/// # Return:
+///   `(选中轨, 其音质档, 其格式)`;池空为 `None`。
+fn pick_audio(pool: Vec<DashAudio>, quality: BitRate) -> Option<(DashAudio, BitRate, AudioFormat)> {
+    let mut ranked = pool
+        .into_iter()
+        .map(|a| {
+            let (tier, format) = classify_audio(a.id, a.codecs.as_deref());
+            (a, tier, format)
+        })
+        .collect::<Vec<_>>();
+    // 升序:档 → 音质码 → 码率;末位即「最好」。
+    ranked.sort_by_key(|(a, tier, _)| (*tier, a.id, a.bandwidth.unwrap_or(0)));
+    match ranked.iter().rposition(|(_, tier, _)| *tier <= quality) {
+        Some(at) => Some(ranked.swap_remove(at)),
+        None => ranked.into_iter().next(),
     }
-    normal.sort_by_key(|a| a.id);
-    normal.pop()
 }
 
 /// 音质码 + codecs → 归一化的 (音质, 格式)。
 ///
-/// codecs 含 `flac` 判无损;否则按 `id` 映射三档(`30216`/`30232` → Standard/Higher,其余高档
-/// 归 Exhigh),格式恒 AAC(B站 dash 普通音频轨是 m4a/aac)。
+/// codecs 含 `flac`(或码 `30251`)判无损;其余按码映射三档(`30216`/`30232` →
+/// Standard/Higher,`30280` 及未知码归 Exhigh),格式恒 AAC(B站 dash 普通音频轨是 m4a/aac)。
 fn classify_audio(id: i64, codecs: Option<&str>) -> (BitRate, AudioFormat) {
-    if codecs.is_some_and(|c| c.to_ascii_lowercase().contains("flac")) {
+    let codecs = codecs.map(str::to_ascii_lowercase).unwrap_or_default();
+    if id == AUDIO_FLAC || codecs.contains("flac") {
         return (BitRate::Lossless, AudioFormat::Flac);
     }
     let quality = match id {
-        30216 => BitRate::Standard,
-        30232 => BitRate::Higher,
+        AUDIO_64K => BitRate::Standard,
+        AUDIO_132K => BitRate::Higher,
+        // `30280` 与未知码(B站新增的高档)都归 Exhigh。
         _ => BitRate::Exhigh,
     };
     (quality, AudioFormat::Aac)
@@ -271,6 +370,25 @@ pub(crate) fn fav_folder_to_playlist(folder: FavFolder) -> Playlist {
         .build()
 }
 
+/// 收藏夹 `attr` 里「非默认夹」的标志位(默认收藏夹该位为 0)。
+const FAV_ATTR_NOT_DEFAULT: i64 = 0b10;
+
+/// 在「我创建的收藏夹」里找默认收藏夹(红心镜像写入的目标):`attr` 非默认位为 0 者;
+/// 缺 `attr`(字段被裁)时退回首个——B站列表恒把默认夹排第一。
+///
+/// # Params:
+///   - `folders`: 我创建的收藏夹列表
+///
+/// # Return:
+///   默认收藏夹 id;列表为空 → `None`。
+pub(crate) fn default_fav_folder(folders: &[FavFolder]) -> Option<i64> {
+    folders
+        .iter()
+        .find(|f| f.attr & FAV_ATTR_NOT_DEFAULT == 0)
+        .or_else(|| folders.first())
+        .map(|f| f.id)
+}
+
 /// 收藏夹条目的曲目产出计划(由 [`plan_fav_entry`] 判定)。
 pub(crate) enum FavEntryPlan {
     /// 单 P 条目:该视频即一首歌,直接成曲。
@@ -314,6 +432,7 @@ fn fav_media_to_song(media: FavMedia) -> Option<Song> {
     let title = media.title.unwrap_or_default();
     let duration_ms = media.duration.map(seconds_to_ms);
     let cover = media.cover.as_deref().and_then(cover_media_url);
+    let album_artist = Some(media.upper.name.clone()).filter(|n| !n.is_empty());
     let artist = ArtistRef {
         id: ArtistId::new(SourceKind::BILIBILI, media.upper.mid.to_string()),
         name: media.upper.name,
@@ -328,6 +447,12 @@ fn fav_media_to_song(media: FavMedia) -> Option<Song> {
             .name(title)
             .artists(vec![artist])
             .album(Some(album))
+            .album_artist(album_artist)
+            .year(
+                media
+                    .pubtime
+                    .and_then(|s| release_year(s.saturating_mul(1000), CST_OFFSET_MS)),
+            )
             .duration_ms(duration_ms)
             .cover_url(cover)
             .build(),
@@ -419,6 +544,80 @@ pub(crate) fn arc_video_to_album(item: ArcVideoItem) -> Option<Album> {
     )
 }
 
+/// 字幕轨的语言主码:去掉 AI 轨的 `ai-` 前缀与地区后缀(`ai-zh` / `zh-CN` / `zh-Hans` → `zh`)。
+fn subtitle_base_lang(lan: &str) -> &str {
+    let lan = lan.strip_prefix("ai-").unwrap_or(lan);
+    lan.split_once('-').map_or(lan, |(base, _)| base)
+}
+
+/// 字幕轨的优先级(越小越优先):UP 主上传的 CC 轨先于 AI 生成轨,同类里中文先于其它语言;
+/// 再同级按列表原序。
+fn subtitle_rank(track: &SubtitleTrack) -> (bool, bool) {
+    (
+        track.lan.starts_with("ai-"),
+        subtitle_base_lang(&track.lan) != "zh",
+    )
+}
+
+/// 从字幕列表里挑「原文轨 + 可选翻译轨」。
+///
+/// 原文取优先级最高的轨(见 [`subtitle_rank`]);翻译取剩余轨里与原文**语言不同**的最优一条
+/// (同语言的 CC / AI 重复轨不当翻译)。`subtitle_url` 为空的轨(未登录时的 AI 轨)不参与。
+///
+/// # Params:
+///   - `tracks`: 播放器信息里的字幕轨列表
+///
+/// # Return:
+///   `(原文轨, 翻译轨)`;无可用轨为 `None`。
+pub(crate) fn pick_subtitle_tracks(
+    tracks: Vec<SubtitleTrack>,
+) -> Option<(SubtitleTrack, Option<SubtitleTrack>)> {
+    let mut usable = tracks
+        .into_iter()
+        .filter(|t| !t.subtitle_url.is_empty())
+        .collect::<Vec<_>>();
+    usable.sort_by_key(subtitle_rank);
+    let mut iter = usable.into_iter();
+    let primary = iter.next()?;
+    let base = subtitle_base_lang(&primary.lan).to_owned();
+    let secondary = iter.find(|t| subtitle_base_lang(&t.lan) != base);
+    Some((primary, secondary))
+}
+
+/// 字幕本体 → 行级 [`LyricLine`] 序列:`from` 秒 → `time_ms`,文本内换行并成空格,空条丢弃,
+/// 按时间升序。
+///
+/// # Params:
+///   - `body`: 字幕文件本体
+///
+/// # Return:
+///   带时间戳的 `Plain` 行。
+pub(crate) fn subtitle_to_lines(body: SubtitleBody) -> Vec<LyricLine> {
+    let mut lines = body
+        .body
+        .into_iter()
+        .filter_map(|cue| {
+            let text = cue.content.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!text.is_empty()).then(|| LyricLine::timed(float_seconds_to_ms(cue.from), text))
+        })
+        .collect::<Vec<_>>();
+    lines.sort_by_key(|l| l.time_ms);
+    lines
+}
+
+/// 原文字幕 + 可选翻译字幕 → [`Lyrics`](翻译按时间配对进各行的 `translation`)。
+///
+/// # Params:
+///   - `primary`: 原文轨字幕本体
+///   - `secondary`: 翻译轨字幕本体(无第二语言为 `None`)
+pub(crate) fn subtitles_to_lyrics(
+    primary: SubtitleBody,
+    secondary: Option<SubtitleBody>,
+) -> Lyrics {
+    let translation = secondary.map(subtitle_to_lines).unwrap_or_default();
+    Lyrics::assemble(subtitle_to_lines(primary), &translation, &[])
+}
+
 #[cfg(test)]
 mod tests {
     use mineral_model::{AlbumId, ArtistId, MediaUrl, SongId, SourceKind};
@@ -428,7 +627,7 @@ mod tests {
     use crate::wire::search::SearchVideoItem;
     use crate::wire::view::VideoInfo;
 
-    /// playurl → PlayUrl:取 id 最大的音频轨、映射 Exhigh/AAC、**带上 Referer + UA 取流头**
+    /// playurl → PlayUrl:请求 Exhigh 时取 192k 轨、映射 Exhigh/AAC、**带上 Referer + UA 取流头**
     /// (B站 baseUrl 播放两者缺一即 403),bitrate 落 bandwidth。
     ///
     /// 回归:真实响应每项**同时**带 `baseUrl` + `base_url`(值同)。DTO 只认 `baseUrl`,
@@ -445,8 +644,13 @@ mod tests {
             ] }
         });
         let dto: PlayUrlResult = from_value(raw)?;
-        let pu = playurl_to_play(SongId::new(SourceKind::BILIBILI, "BV1xx:1"), dto)
-            .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))?;
+        let pu = playurl_to_play(
+            SongId::new(SourceKind::BILIBILI, "BV1xx:1"),
+            dto,
+            mineral_model::BitRate::Exhigh,
+        )
+        .map(|p| p.play_url)
+        .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))?;
         assert_eq!(
             pu.url,
             MediaUrl::remote("https://cdn/192k.m4s")?,
@@ -471,24 +675,129 @@ mod tests {
         Ok(())
     }
 
-    /// flac 轨存在时优先取无损(Lossless/Flac)。
+    /// 大会员全档响应:按请求档取「不高于请求的最高档」——Lossless / Hires 都取 flac(杜比轨
+    /// 解不了,不参与选轨)、Higher 取 132k;`quality` / `format` / `bitrate_bps` 回填实际选中轨,
+    /// 选中无损轨时带出 init 段范围供读位深。
     #[test]
-    fn playurl_prefers_flac_when_present() -> color_eyre::Result<()> {
+    fn playurl_picks_tier_at_or_below_request() -> color_eyre::Result<()> {
+        use mineral_model::{AudioFormat, BitRate};
+
+        use super::{PickedPlay, playurl_to_play};
+        use crate::wire::playurl::PlayUrlResult;
+
+        let raw = serde_json::json!({
+            "dash": {
+                "audio": [
+                    { "id": 30216, "baseUrl": "https://cdn/64k.m4s", "bandwidth": 64000, "codecs": "mp4a.40.2" },
+                    { "id": 30232, "baseUrl": "https://cdn/132k.m4s", "bandwidth": 132000, "codecs": "mp4a.40.2" },
+                    { "id": 30280, "baseUrl": "https://cdn/192k.m4s", "bandwidth": 320000, "codecs": "mp4a.40.2" }
+                ],
+                "flac": { "audio": { "id": 30251, "baseUrl": "https://cdn/flac.m4s", "bandwidth": 900000, "codecs": "fLaC", "segment_base": { "initialization": "0-857", "index_range": "858-1233" } } },
+                "dolby": { "type": 2, "audio": [ { "id": 30250, "baseUrl": "https://cdn/dolby.m4s", "bandwidth": 448000, "codecs": "ec-3" } ] }
+            }
+        });
+        let pick = |quality: BitRate| -> color_eyre::Result<PickedPlay> {
+            let dto: PlayUrlResult = from_value(raw.clone())?;
+            playurl_to_play(SongId::new(SourceKind::BILIBILI, "BV1xx:1"), dto, quality)
+                .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))
+        };
+
+        let picked = pick(BitRate::Lossless)?;
+        assert_eq!(picked.flac_init.as_deref(), Some("0-857"));
+        let pu = picked.play_url;
+        assert_eq!(pu.url, MediaUrl::remote("https://cdn/flac.m4s")?);
+        assert_eq!(pu.quality, BitRate::Lossless);
+        assert_eq!(pu.format, Some(AudioFormat::Flac));
+        assert_eq!(pu.bitrate_bps, Some(900_000));
+
+        let pu = pick(BitRate::Hires)?.play_url;
+        assert_eq!(
+            pu.url,
+            MediaUrl::remote("https://cdn/flac.m4s")?,
+            "杜比轨不参与选轨"
+        );
+        assert_eq!(pu.quality, BitRate::Lossless);
+        assert_eq!(pu.format, Some(AudioFormat::Flac));
+
+        let picked = pick(BitRate::Higher)?;
+        assert_eq!(picked.flac_init, None, "有损轨不读位深");
+        let pu = picked.play_url;
+        assert_eq!(pu.url, MediaUrl::remote("https://cdn/132k.m4s")?);
+        assert_eq!(pu.quality, BitRate::Higher);
+        assert_eq!(pu.format, Some(AudioFormat::Aac));
+        Ok(())
+    }
+
+    /// init 段里 `dfLa` 的 STREAMINFO 按 bit 解出位深;没有 `dfLa` 为 `None`。
+    #[test]
+    fn flac_bit_depth_reads_streaminfo() {
+        use super::flac_bit_depth;
+
+        // 48kHz / 双声道 / 24bit:采样率 20bit = 0x0BB80,声道 - 1 = 1(3bit),位深 - 1 = 23(5bit)。
+        let mut streaminfo = [0_u8; 34];
+        streaminfo[10] = 0x0B;
+        streaminfo[11] = 0xB8;
+        streaminfo[12] = 0x03;
+        streaminfo[13] = 0x70;
+        let mut init = b"\0\0\0\x18ftypiso5".to_vec();
+        init.extend_from_slice(b"\0\0\0\x32dfLa\0\0\0\0\x80\0\0\x22");
+        init.extend_from_slice(&streaminfo);
+        assert_eq!(flac_bit_depth(&init), Some(24));
+
+        streaminfo[12] = 0x02;
+        streaminfo[13] = 0xF0;
+        let mut sixteen = init.clone();
+        sixteen.truncate(init.len() - streaminfo.len());
+        sixteen.extend_from_slice(&streaminfo);
+        assert_eq!(flac_bit_depth(&sixteen), Some(16));
+
+        assert_eq!(flac_bit_depth(b"\0\0\0\x18ftypiso5"), None);
+    }
+
+    /// guest 响应(无 flac / 杜比,`flac` 为 null):请求无损回落到 192k 并如实报 Exhigh;
+    /// 池里全比请求高时退最低档。
+    #[test]
+    fn playurl_guest_falls_back_cleanly() -> color_eyre::Result<()> {
+        use mineral_model::BitRate;
+
         use super::playurl_to_play;
         use crate::wire::playurl::PlayUrlResult;
 
         let raw = serde_json::json!({
             "dash": {
-                "audio": [ { "id": 30280, "baseUrl": "https://cdn/192k.m4s", "bandwidth": 320000, "codecs": "mp4a.40.2" } ],
-                "flac": { "audio": { "id": 30251, "baseUrl": "https://cdn/flac.m4s", "bandwidth": 900000, "codecs": "fLaC" } }
+                "audio": [
+                    { "id": 30280, "baseUrl": "https://cdn/192k.m4s", "bandwidth": 320000, "codecs": "mp4a.40.2" },
+                    { "id": 30232, "baseUrl": "https://cdn/132k.m4s", "bandwidth": 132000, "codecs": "mp4a.40.2" }
+                ],
+                "flac": null,
+                "dolby": { "type": 0, "audio": null }
             }
         });
+        let dto: PlayUrlResult = from_value(raw.clone())?;
+        let pu = playurl_to_play(
+            SongId::new(SourceKind::BILIBILI, "BV1xx:1"),
+            dto,
+            BitRate::Lossless,
+        )
+        .map(|p| p.play_url)
+        .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))?;
+        assert_eq!(pu.url, MediaUrl::remote("https://cdn/192k.m4s")?);
+        assert_eq!(pu.quality, BitRate::Exhigh, "报实际档,不回显请求档");
+
         let dto: PlayUrlResult = from_value(raw)?;
-        let pu = playurl_to_play(SongId::new(SourceKind::BILIBILI, "BV1xx:1"), dto)
-            .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))?;
-        assert_eq!(pu.url, MediaUrl::remote("https://cdn/flac.m4s")?);
-        assert_eq!(pu.quality, mineral_model::BitRate::Lossless);
-        assert_eq!(pu.format, Some(mineral_model::AudioFormat::Flac));
+        let pu = playurl_to_play(
+            SongId::new(SourceKind::BILIBILI, "BV1xx:1"),
+            dto,
+            BitRate::Standard,
+        )
+        .map(|p| p.play_url)
+        .ok_or_else(|| color_eyre::eyre::eyre!("应产出 PlayUrl"))?;
+        assert_eq!(
+            pu.url,
+            MediaUrl::remote("https://cdn/132k.m4s")?,
+            "无更低档时取最低"
+        );
+        assert_eq!(pu.quality, BitRate::Higher);
         Ok(())
     }
 
@@ -527,7 +836,7 @@ mod tests {
     }
 
     /// 多 P 视频 → Album:逐 P 成曲(SongId = `{bvid}:{page}`)、track_count = P 数、
-    /// pubdate 秒 → 毫秒。附完整 Debug 快照。
+    /// pubdate 秒 → 毫秒、曲目带专辑艺人与发行年。附完整 Debug 快照。
     #[test]
     fn multi_page_video_maps_each_page_to_song() -> color_eyre::Result<()> {
         let raw = serde_json::json!({
@@ -558,6 +867,12 @@ mod tests {
         assert_eq!(s0.name, "第一话");
         assert_eq!(s0.duration_ms, Some(240_000));
         assert_eq!(album.publish_time_ms, 1_600_000_000_000);
+        assert_eq!(
+            s0.album_artist.as_deref(),
+            Some("UP主甲"),
+            "UP 主即专辑艺人"
+        );
+        assert_eq!(s0.year, Some(2020), "投稿年即发行年");
         mineral_test::assert_snap_debug!(
             "多P视频详情 → Album(2P 逐 P 成曲 + owner + 封面补 https)",
             album
@@ -851,4 +1166,88 @@ mod tests {
         assert_eq!(pl.description, "夹子简介", "intro 落 description");
         Ok(())
     }
+
+    /// 默认收藏夹按 `attr` 非默认位识别(不看位置);全是非默认夹时退回首个,空列表无。
+    #[test]
+    fn default_fav_folder_picks_attr_default() -> color_eyre::Result<()> {
+        use super::default_fav_folder;
+        use crate::wire::fav::FavFolder;
+
+        let folders: Vec<FavFolder> = from_value(serde_json::json!([
+            { "id": 7, "title": "歌", "attr": 2 },
+            { "id": 3, "title": "默认收藏夹", "attr": 0 },
+            { "id": 9, "title": "私密", "attr": 3 }
+        ]))?;
+        assert_eq!(default_fav_folder(&folders), Some(3));
+        assert_eq!(
+            default_fav_folder(folders.get(..1).unwrap_or_default()),
+            Some(7),
+            "无默认位退回首个"
+        );
+        assert_eq!(default_fav_folder(&[]), None);
+        Ok(())
+    }
+
+    /// 字幕选轨:CC 先于 AI、中文先于其它语言;翻译取语言不同的次优轨,同语言 AI 重复轨
+    /// 不当翻译;无 URL 的轨跳过。
+    #[test]
+    fn picks_primary_and_translation_subtitle() -> color_eyre::Result<()> {
+        use super::pick_subtitle_tracks;
+        use crate::wire::subtitle::PlayerInfo;
+
+        let info: PlayerInfo = from_value(serde_json::json!({
+            "subtitle": { "subtitles": [
+                { "lan": "ai-zh", "subtitle_url": "//s/ai-zh.json" },
+                { "lan": "ja", "subtitle_url": "//s/ja.json" },
+                { "lan": "en-US", "subtitle_url": "" },
+                { "lan": "zh-CN", "subtitle_url": "//s/zh.json" }
+            ] }
+        }))?;
+        let tracks = info.subtitle.map(|s| s.subtitles).unwrap_or_default();
+        let (primary, secondary) =
+            pick_subtitle_tracks(tracks).ok_or_else(|| color_eyre::eyre::eyre!("应有原文轨"))?;
+        assert_eq!(primary.lan, "zh-CN");
+        assert_eq!(secondary.map(|t| t.lan).as_deref(), Some("ja"));
+
+        let only_zh: PlayerInfo = from_value(serde_json::json!({
+            "subtitle": { "subtitles": [
+                { "lan": "ai-zh", "subtitle_url": "//s/ai-zh.json" },
+                { "lan": "zh-Hans", "subtitle_url": "//s/zh.json" }
+            ] }
+        }))?;
+        let tracks = only_zh.subtitle.map(|s| s.subtitles).unwrap_or_default();
+        let picked = pick_subtitle_tracks(tracks);
+        assert!(
+            picked.is_some_and(|(p, s)| p.lan == "zh-Hans" && s.is_none()),
+            "同语言的 AI 轨不当翻译"
+        );
+        assert!(pick_subtitle_tracks(Vec::new()).is_none());
+        Ok(())
+    }
+
+    /// 字幕 → 歌词:浮点秒转毫秒、乱序重排、换行并空格、空条丢弃;第二语言按时间配进 translation。
+    #[test]
+    fn subtitles_convert_to_lyrics_with_translation() -> color_eyre::Result<()> {
+        use mineral_model::LyricLine;
+
+        use super::subtitles_to_lyrics;
+        use crate::wire::subtitle::SubtitleBody;
+
+        let primary: SubtitleBody = from_value(serde_json::json!({ "body": [
+            { "from": 4.2, "to": 6.0, "content": "第二句\n续" },
+            { "from": 1.25, "to": 4.2, "content": "第一句" },
+            { "from": 7.0, "to": 8.0, "content": "  " }
+        ] }))?;
+        let secondary: SubtitleBody = from_value(serde_json::json!({ "body": [
+            { "from": 1.25, "to": 4.2, "content": "first" },
+            { "from": 4.2, "to": 6.0, "content": "second" }
+        ] }))?;
+        let lyrics = subtitles_to_lyrics(primary, Some(secondary));
+        let mut first = LyricLine::timed(1_250, "第一句");
+        first.translation = Some("first".to_owned());
+        let mut second = LyricLine::timed(4_200, "第二句 续");
+        second.translation = Some("second".to_owned());
+        assert_eq!(lyrics.lines, vec![first, second]);
+        Ok(())
+    }
 }
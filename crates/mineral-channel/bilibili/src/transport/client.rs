@@ -33,6 +33,9 @@ const WBI_TTL: Duration = Duration::from_secs(3600);
 /// 签名失效(风控 / keys 过期)的业务 code——命中则刷新 keys 重签一次。
 const CODE_WBI_EXPIRED: i64 = -352;
 
+/// 未登录的业务 code:写操作缺 `bili_jct`(guest jar)时本地直接结构化成它,不发请求。
+const CODE_NOT_LOGGED_IN: i64 = -101;
+
 /// 一对 WBI 签名 key(从 nav 的图片 URL 文件名提取)。
 #[derive(Clone)]
 struct WbiKeys {
@@ -141,8 +144,15 @@ impl Transport {
 
     /// 发一个 GET,返回**整个信封**(不校验 `code`)。
     ///
-    /// 用于 nav——guest 请求返回 `code = -101` 但 `data.wbi_img` 仍在。
-    async fn get_value(&self, url: &str) -> Result<Value> {
+    /// 用于 nav——guest 请求返回 `code = -101` 但 `data.wbi_img` 仍在;也用于 CDN 上
+    /// 不带信封的静态 JSON(字幕文件)。
+    ///
+    /// # Params:
+    ///   - `url`: 完整请求 URL
+    ///
+    /// # Return:
+    ///   响应体 JSON。
+    pub async fn get_value(&self, url: &str) -> Result<Value> {
         let req = Request::get(url)
             .header("User-Agent", UA)
             .header("Referer", REFERER)
@@ -157,6 +167,29 @@ impl Transport {
         serde_json::from_slice(&bytes).context("parse json envelope")
     }
 
+    /// 按字节范围 GET 一段原始内容(取流 CDN 上音频轨的 init 段),带取流同款 UA / Referer。
+    ///
+    /// # Params:
+    ///   - `url`: 取流直链
+    ///   - `range`: 字节范围,`<起>-<止>`(含两端)
+    ///
+    /// # Return:
+    ///   响应体字节。
+    pub async fn get_range(&self, url: &str, range: &str) -> Result<Vec<u8>> {
+        let req = Request::get(url)
+            .header("User-Agent", UA)
+            .header("Referer", REFERER)
+            .header("Range", format!("bytes={range}"))
+            .body(())
+            .map_err(|e| eyre!("build request: {e}"))?;
+        let mut resp = self
+            .client
+            .send_async(req)
+            .await
+            .map_err(|e| eyre!("send: {e}"))?;
+        resp.bytes().await.map_err(|e| eyre!("read body: {e}"))
+    }
+
     /// 发一个 GET,解 `{code, message, data}` 信封:`code == 0` 返回 `data`,否则结构化
     /// [`ApiCodeError`](channel 边界 downcast 映射)。
     ///
@@ -169,6 +202,47 @@ impl Transport {
         decode_envelope(&self.get_value(url).await?)
     }
 
+    /// 发一个表单 POST(写端点:收藏夹增删改),自动追加 `csrf`(取自 jar 里的 `bili_jct`),
+    /// 按 [`Self::get_data`] 同款解信封。jar 无 `bili_jct`(guest)时不发请求,直接报
+    /// `-101` 的 [`ApiCodeError`](channel 边界映射成 `AuthRequired`)。
+    ///
+    /// # Params:
+    ///   - `url`: 端点 URL
+    ///   - `params`: 业务表单字段(不含 `csrf`)
+    ///
+    /// # Return:
+    ///   信封的 `data` 字段(无则 `Null`)。
+    pub async fn post_form(&self, url: &str, params: Vec<(&str, String)>) -> Result<Value> {
+        let Some(csrf) = self.csrf() else {
+            return Err(color_eyre::Report::new(ApiCodeError {
+                code: CODE_NOT_LOGGED_IN,
+                message: "缺少 bili_jct,写操作需登录".to_owned(),
+            }));
+        };
+        let req = Request::post(url)
+            .header("User-Agent", UA)
+            .header("Referer", REFERER)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(encode_form(params, &csrf))
+            .map_err(|e| eyre!("build request: {e}"))?;
+        let mut resp = self
+            .client
+            .send_async(req)
+            .await
+            .map_err(|e| eyre!("send: {e}"))?;
+        let bytes = resp.bytes().await.map_err(|e| eyre!("read body: {e}"))?;
+        let value: Value = serde_json::from_slice(&bytes).context("parse json envelope")?;
+        decode_envelope(&value)
+    }
+
+    /// 从 jar 取 CSRF token(`bili_jct` cookie 值);guest / 未登录 → `None`。
+    fn csrf(&self) -> Option<String> {
+        let uri: Uri = HOME_URL.parse().ok()?;
+        self.cookie_jar()?
+            .get_by_name(&uri, "bili_jct")
+            .map(|c| c.value().to_owned())
+    }
+
     /// WBI 签名 GET:确保 buvid3 → 取 keys 签名 → 请求;命中 `-352`(签名失效)刷新 keys 重签一次。
     ///
     /// # Params:
@@ -269,6 +343,16 @@ fn decode_envelope(v: &Value) -> Result<Value> {
     Ok(v.get("data").cloned().unwrap_or(Value::Null))
 }
 
+/// 把表单字段 + `csrf` 编成 `application/x-www-form-urlencoded` body。
+///
+/// 不复用 WBI 的 query 编码:那边会剔除 `!'()*`,收藏夹标题里的这些字符必须原样提交。
+fn encode_form(params: Vec<(&str, String)>, csrf: &str) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .append_pair("csrf", csrf)
+        .finish()
+}
+
 /// 该错误是否是 WBI 签名失效(`-352`)。
 fn is_wbi_expired(e: &color_eyre::Report) -> bool {
     e.downcast_ref::<ApiCodeError>()
@@ -285,7 +369,7 @@ fn now_secs() -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use super::decode_envelope;
+    use super::{decode_envelope, encode_form};
     use crate::error::ApiCodeError;
 
     /// from_credential 把三件套 cookie 塞进 jar,且能被 `www` **和** `api` 两个子域取回。
@@ -326,6 +410,43 @@ mod tests {
         Ok(())
     }
 
+    /// 表单 body:字段按序编码、末尾追加 csrf;标题里的 `!()` / 空格 / 中文原样保留(转义而非剔除)。
+    #[test]
+    fn encode_form_appends_csrf_and_keeps_punctuation() {
+        let body = encode_form(
+            vec![
+                ("title", "我的 (精选)!".to_owned()),
+                ("privacy", "0".to_owned()),
+            ],
+            "JCT",
+        );
+        assert_eq!(
+            body,
+            "title=%E6%88%91%E7%9A%84+%28%E7%B2%BE%E9%80%89%29%21&privacy=0&csrf=JCT"
+        );
+    }
+
+    /// guest transport 的 jar 里没有 `bili_jct`,写操作本地直接报 -101,不发请求。
+    #[tokio::test]
+    async fn post_form_without_credential_is_not_logged_in() -> color_eyre::Result<()> {
+        use crate::config::BilibiliConfig;
+
+        let cfg = BilibiliConfig::builder()
+            .max_connections(0)
+            .proxy(None)
+            .timeout_secs(30)
+            .build();
+        let t = super::Transport::new(&cfg)?;
+        let Err(err) = t.post_form("http://127.0.0.1:9/unused", Vec::new()).await else {
+            return Err(color_eyre::eyre::eyre!("guest 写操作应报错"));
+        };
+        let api = err
+            .downcast_ref::<ApiCodeError>()
+            .ok_or_else(|| color_eyre::eyre::eyre!("应结构化为 ApiCodeError"))?;
+        assert_eq!(api.code, -101);
+        Ok(())
+    }
+
     /// `code == 0` 时解出 `data`。
     #[test]
     fn envelope_ok_returns_data() -> color_eyre::Result<()> {
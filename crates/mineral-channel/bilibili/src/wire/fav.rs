@@ -1,8 +1,9 @@
 //! 收藏夹端点 DTO。
 //!
-//! 两个端点:`x/v3/fav/folder/created/list`(我的收藏夹列表,分页、每项带封面)、
+//! 两个读端点:`x/v3/fav/folder/created/list`(我的收藏夹列表,分页、每项带封面)、
 //! `x/v3/fav/resource/list`(某收藏夹内容)。都是明文 GET(无 WBI),私密夹 /「我的」列表需登录
 //! cookie。注:另有 `created/list-all`(全量不分页)但每项**不返 cover**,故列表走分页版取封面。
+//! 写端点(`folder/add` 等)里只有新建夹返回 data,形同 [`FavFolder`],复用之。
 
 use serde::Deserialize;
 
@@ -37,6 +38,10 @@ pub struct FavFolder {
     /// 收藏夹简介。
     #[serde(default)]
     pub intro: Option<String>,
+
+    /// 属性位:bit0 = 私密,bit1 = 非默认夹(默认收藏夹该位为 0)。
+    #[serde(default)]
+    pub attr: i64,
 }
 
 /// 「收藏夹内容」响应的 `data`。
@@ -97,6 +102,10 @@ pub struct FavMedia {
 
     /// 视频 UP 主(收藏夹条目里叫 `upper`)。
     pub upper: FavUpper,
+
+    /// 投稿时间(unix 秒)。
+    #[serde(default)]
+    pub pubtime: Option<i64>,
 }
 
 /// 收藏条目的 UP 主。
@@ -12,4 +12,5 @@ pub mod nav;
 pub mod playurl;
 pub mod search;
 pub mod space;
+pub mod subtitle;
 pub mod view;
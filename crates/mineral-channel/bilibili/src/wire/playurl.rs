@@ -1,7 +1,7 @@
 //! 播放地址端点(`x/player/wbi/playurl`)的 DTO。
 //!
 //! `fnval=4048` 请求 DASH 格式;音频流在 `data.dash.audio[]`(每档一个 `id` 音质码 + `baseUrl`),
-//! 大会员无损在 `data.dash.flac.audio`。取流走 `baseUrl`——**必须带 `Referer`**,否则 403。
+//! 大会员无损在 `data.dash.flac.audio`,杜比全景声在 `data.dash.dolby.audio[]`。取流走 `baseUrl`——**必须带 `Referer`**,否则 403。
 
 use serde::Deserialize;
 
@@ -20,6 +20,9 @@ pub struct Dash {
 
     /// 无损(FLAC)音频轨(需大会员;未开通为 `None`)。
     pub flac: Option<DashFlac>,
+
+    /// 杜比全景声音频轨(仅部分视频提供,需大会员;没有为 `None`)。
+    pub dolby: Option<DashDolby>,
 }
 
 /// 无损音频轨容器。
@@ -29,6 +32,13 @@ pub struct DashFlac {
     pub audio: Option<DashAudio>,
 }
 
+/// 杜比音频轨容器。
+#[derive(Debug, Clone, Deserialize)]
+pub struct DashDolby {
+    /// 杜比音频轨(E-AC-3,`codecs` 为 `ec-3`);无杜比时为 `None` 或空数组。
+    pub audio: Option<Vec<DashAudio>>,
+}
+
 /// 一条音频轨。
 #[derive(Debug, Clone, Deserialize)]
 pub struct DashAudio {
@@ -46,4 +56,15 @@ pub struct DashAudio {
 
     /// 编解码器串(如 `mp4a.40.2` = AAC;`fLaC` = 无损),用于判 format。
     pub codecs: Option<String>,
+
+    /// fMP4 分段索引(init 段字节范围);缺失为 `None`。
+    pub segment_base: Option<SegmentBase>,
+}
+
+/// 一条音频轨的 fMP4 分段索引。B站同一项里 `segment_base` 与 `SegmentBase` 两种写法并存,
+/// 只认前者。
+#[derive(Debug, Clone, Deserialize)]
+pub struct SegmentBase {
+    /// init 段(`moov`,含解码参数)的字节范围,如 `0-857`。
+    pub initialization: String,
 }
@@ -0,0 +1,111 @@
+//! 播放器信息端点(`x/player/wbi/v2?bvid=&cid=`)的字幕列表 + 字幕文件本体的 DTO。
+//!
+//! 字幕列表在 `data.subtitle.subtitles[]`(每条一个语言轨:`lan` 语言码 + `subtitle_url`);
+//! 字幕本体是 CDN 上的静态 JSON(无 `{code, data}` 信封),时间轴在 `body[]`,单位**秒(浮点)**。
+
+use serde::Deserialize;
+
+/// 播放器信息响应的 `data` 块(只取字幕段)。
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlayerInfo {
+    /// 字幕段(无字幕 / 风控降级时可缺)。
+    #[serde(default)]
+    pub subtitle: Option<PlayerSubtitle>,
+}
+
+/// 播放器信息里的字幕段。
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlayerSubtitle {
+    /// 各语言字幕轨(UP 主上传 CC + AI 生成混排;可缺 / 为空)。
+    #[serde(default)]
+    pub subtitles: Vec<SubtitleTrack>,
+}
+
+/// 一条语言字幕轨。
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubtitleTrack {
+    /// 语言码(`zh-CN` / `en-US` / `ja`;AI 生成的带 `ai-` 前缀,如 `ai-zh`)。
+    pub lan: String,
+
+    /// 语言显示名(如「中文(中国)」「中文(自动生成)」)。
+    #[serde(default)]
+    pub lan_doc: String,
+
+    /// 字幕文件 URL(常为协议相对 `//aisubtitle.hdslb.com/...`;未登录时 AI 轨可能为空串)。
+    #[serde(default)]
+    pub subtitle_url: String,
+}
+
+/// 字幕文件本体。
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubtitleBody {
+    /// 时间轴上的字幕条(按 `from` 升序,偶有乱序)。
+    #[serde(default)]
+    pub body: Vec<SubtitleCue>,
+}
+
+/// 一条字幕。
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubtitleCue {
+    /// 起始时间(秒,浮点)。
+    pub from: f64,
+
+    /// 结束时间(秒,浮点)。
+    #[serde(default)]
+    pub to: f64,
+
+    /// 字幕文本(可能含 `\n` 换行)。
+    pub content: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlayerInfo, SubtitleBody};
+    use crate::wire::de::from_value;
+
+    /// 字幕列表:CC + AI 混排逐条解析;`lan_doc` / `subtitle_url` 缺失落空串。
+    #[test]
+    fn parses_subtitle_tracks() -> color_eyre::Result<()> {
+        let raw = serde_json::json!({
+            "aid": 800, "bvid": "BV1xx", "cid": 1001,
+            "subtitle": {
+                "allow_submit": false,
+                "subtitles": [
+                    { "id": 1, "lan": "zh-CN", "lan_doc": "中文(中国)",
+                      "subtitle_url": "//aisubtitle.hdslb.com/bfs/subtitle/a.json" },
+                    { "id": 2, "lan": "ai-ja" }
+                ]
+            }
+        });
+        let info: PlayerInfo = from_value(raw)?;
+        let tracks = info.subtitle.map(|s| s.subtitles).unwrap_or_default();
+        let lans = tracks.iter().map(|t| t.lan.as_str()).collect::<Vec<_>>();
+        assert_eq!(lans, vec!["zh-CN", "ai-ja"]);
+        assert!(tracks.get(1).is_some_and(|t| t.subtitle_url.is_empty()));
+        Ok(())
+    }
+
+    /// 无字幕视频:`subtitle` 缺失落 `None`,不报错。
+    #[test]
+    fn tolerates_missing_subtitle() -> color_eyre::Result<()> {
+        let info: PlayerInfo = from_value(serde_json::json!({ "bvid": "BV1yy" }))?;
+        assert!(info.subtitle.is_none());
+        Ok(())
+    }
+
+    /// 字幕本体:秒级浮点时间轴。
+    #[test]
+    fn parses_subtitle_body() -> color_eyre::Result<()> {
+        let raw = serde_json::json!({
+            "font_size": 0.4, "stroke": "none",
+            "body": [
+                { "from": 1.25, "to": 3.5, "sid": 1, "location": 2, "content": "第一句" },
+                { "from": 3.5, "to": 6.0, "sid": 2, "location": 2, "content": "第二句" }
+            ]
+        });
+        let body: SubtitleBody = from_value(raw)?;
+        assert_eq!(body.body.len(), 2);
+        assert!(body.body.first().is_some_and(|c| c.content == "第一句"));
+        Ok(())
+    }
+}
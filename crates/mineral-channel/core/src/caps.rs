@@ -2,6 +2,8 @@
 
 use derive_getters::Getters;
 use mineral_model::SearchKind;
+
+use crate::discover::DiscoverKind;
 use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
@@ -65,6 +67,11 @@ pub struct ChannelCaps {
     /// artist 详情的分区能力(见 [`ArtistSections`];每个源显式声明,无默认)。
     artist_sections: ArtistSections,
 
+    /// 支持的发现面(推荐 / 排行榜,见 [`DiscoverKind`]);声明顺序即歌单库里虚拟歌单的
+    /// 排列顺序。空 = 无发现面(多数源)。
+    #[builder(default)]
+    discover: Vec<DiscoverKind>,
+
     /// 歌曲网页(分享链接)模板。占位语义(渲染统一走 [`render_web_url`]):
     /// `{id}` 填**整段裸** id(如 `"https://music.163.com/song?id={id}"`);裸 id 是
     /// `:` 分段的复合值时可用 `{0}`/`{1}`… 位置占位取各段(如 B 站裸 id `bvid:page` 配
@@ -106,9 +113,75 @@ pub fn render_web_url(template: &str, raw_id: &str) -> String {
     out
 }
 
+/// 按网页模板反解裸 id([`render_web_url`] 的逆;CLI `queue add` 与 daemon 的外部入口
+/// 认分享链接共用)。`{id}` 取整段,`{0}`/`{1}`… 取各段后按序以 `:` 拼回;末尾占位截到
+/// `&` / `#` 前(分享链接常带追踪参数)。
+///
+/// # Params:
+///   - `template`: 源声明的网页模板
+///   - `url`: 用户给的链接
+///
+/// # Return:
+///   对得上模板时为裸 id。
+pub fn match_web_url(template: &str, url: &str) -> Option<String> {
+    let mut whole = None;
+    let mut segments = Vec::<(usize, String)>::new();
+    let mut tpl = template;
+    let mut rest = url;
+    loop {
+        let Some(open) = tpl.find('{') else {
+            // 模板尾部的字面量须整段对上。
+            if rest != tpl {
+                return None;
+            }
+            return assemble(whole, segments);
+        };
+        let (literal, after) = tpl.split_at(open);
+        rest = rest.strip_prefix(literal)?;
+        let close = after.find('}')?;
+        let name = after.get(1..close)?;
+        tpl = after.get(close.saturating_add(1)..)?;
+        let next_literal = tpl.split('{').next().unwrap_or_default();
+        let end = match (next_literal.is_empty(), tpl.is_empty()) {
+            (false, _) => rest.find(next_literal)?,
+            (true, true) => rest.find(['&', '#']).unwrap_or(rest.len()),
+            // 两个占位紧挨着,切分点无从判断。
+            (true, false) => return None,
+        };
+        let (value, remainder) = rest.split_at(end);
+        if value.is_empty() {
+            return None;
+        }
+        if name == "id" {
+            whole = Some(value.to_owned());
+        } else {
+            segments.push((name.parse().ok()?, value.to_owned()));
+        }
+        rest = if tpl.is_empty() { "" } else { remainder };
+    }
+}
+
+/// 反解出的占位值拼回裸 id:`{id}` 优先,否则位置段按序号以 `:` 连接。
+fn assemble(whole: Option<String>, mut segments: Vec<(usize, String)>) -> Option<String> {
+    if whole.is_some() {
+        return whole;
+    }
+    if segments.is_empty() {
+        return None;
+    }
+    segments.sort_by_key(|(i, _)| *i);
+    Some(
+        segments
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<String>>()
+            .join(":"),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ArtistSectionKind, ArtistSections, ChannelCaps, render_web_url};
+    use super::{ArtistSectionKind, ArtistSections, ChannelCaps, match_web_url, render_web_url};
     use mineral_model::SearchKind;
 
     /// 两区皆有的 artist 分区(音乐源形态测试夹具)。
@@ -143,6 +216,40 @@ mod tests {
         );
     }
 
+    /// `{id}` 整段反解;末尾的追踪参数截掉。
+    #[test]
+    fn web_url_whole_id() {
+        let tpl = "https://music.163.com/song?id={id}";
+        assert_eq!(
+            match_web_url(tpl, "https://music.163.com/song?id=123"),
+            Some("123".to_owned())
+        );
+        assert_eq!(
+            match_web_url(tpl, "https://music.163.com/song?id=123&userid=9"),
+            Some("123".to_owned())
+        );
+        assert_eq!(match_web_url(tpl, "https://example.com/song?id=123"), None);
+        assert_eq!(match_web_url(tpl, "https://music.163.com/song?id="), None);
+    }
+
+    /// 位置占位反解后以 `:` 拼回复合裸 id;与渲染互逆。
+    #[test]
+    fn web_url_positional_segments() {
+        let tpl = "https://www.bilibili.com/video/{0}?p={1}";
+        assert_eq!(
+            match_web_url(tpl, "https://www.bilibili.com/video/BV1xx411c7mD?p=2"),
+            Some("BV1xx411c7mD:2".to_owned())
+        );
+        assert_eq!(
+            match_web_url(tpl, "https://www.bilibili.com/video/BV1xx411c7mD"),
+            None
+        );
+        assert_eq!(
+            match_web_url(tpl, &render_web_url(tpl, "BV1xx:3")),
+            Some("BV1xx:3".to_owned())
+        );
+    }
+
     #[test]
     fn builder_and_getters_roundtrip() {
         let caps = ChannelCaps::builder()
@@ -0,0 +1,418 @@
+//! 发现面(个性化推荐 / 排行榜)的能力枚举与歌单库虚拟歌单约定。
+//!
+//! 每日推荐 / 私人 FM / 相似歌曲 / 相似艺人没有真实歌单 id,在歌单库里以**虚拟歌单**出现:
+//! id 落在源自己的 namespace,裸值带 `discover:` 前缀(真实歌单 id 不会以它开头)。相似艺人
+//! 的虚拟歌单每位艺人出一首热门曲,简介列出艺人名。取详情统一走
+//! [`resolve_playlist`]:认出虚拟 id 改调对应的 trait 方法,否则透传 `playlist_detail`。
+//! 排行榜的榜单本身就是源的真实歌单,由上层直接并进歌单列表,不经虚拟 id。
+
+use mineral_model::{Artist, ArtistId, Playlist, PlaylistId, Song, SongId, SourceKind};
+use serde::{Deserialize, Serialize};
+
+use crate::MusicChannel;
+use crate::caps::ChannelCaps;
+use crate::error::{Error, Result};
+
+/// 虚拟歌单裸值前缀。
+const VIRTUAL_PREFIX: &str = "discover:";
+
+/// 相似艺人虚拟歌单最多收几位艺人(每位一次详情请求)。
+const SIMILAR_ARTISTS_LIMIT: usize = 12;
+
+/// 发现面种类。源在 [`ChannelCaps::discover`] 里列出自己支持的种类,UI 据此决定出哪些入口。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoverKind {
+    /// 每日推荐歌曲([`MusicChannel::daily_songs`])。
+    DailySongs,
+
+    /// 私人 FM([`MusicChannel::personal_fm`]),每次拉取给新的一批。
+    PersonalFm,
+
+    /// 相似歌曲([`MusicChannel::similar_songs`]);虚拟歌单以当前播放曲为种子。
+    SimilarSongs,
+
+    /// 相似艺人([`MusicChannel::similar_artists`]);虚拟歌单以当前播放曲的主艺人为种子,
+    /// 每位相似艺人出一首热门曲。
+    SimilarArtists,
+
+    /// 排行榜([`MusicChannel::toplists`]);榜单是真实歌单,直接并进歌单库。
+    Toplists,
+}
+
+impl DiscoverKind {
+    /// 虚拟歌单裸值里的种类段;无虚拟歌单形态(排行榜)→ `None`。
+    fn slug(self) -> Option<&'static str> {
+        match self {
+            Self::DailySongs => Some("daily"),
+            Self::PersonalFm => Some("fm"),
+            Self::SimilarSongs => Some("similar"),
+            Self::SimilarArtists => Some("similar-artists"),
+            Self::Toplists => None,
+        }
+    }
+
+    /// 种类段 → 种类(只认有虚拟歌单形态的四种)。
+    fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "daily" => Some(Self::DailySongs),
+            "fm" => Some(Self::PersonalFm),
+            "similar" => Some(Self::SimilarSongs),
+            "similar-artists" => Some(Self::SimilarArtists),
+            _ => None,
+        }
+    }
+
+    /// 虚拟歌单是否以当前播放曲为种子(相似歌曲 / 相似艺人)。
+    fn seeded(self) -> bool {
+        matches!(self, Self::SimilarSongs | Self::SimilarArtists)
+    }
+
+    /// 虚拟歌单的展示名与简介。
+    fn label(self) -> (&'static str, &'static str) {
+        match self {
+            Self::DailySongs => ("Daily Recommendations", "Refreshed every day"),
+            Self::PersonalFm => ("Personal FM", "A fresh batch on every load"),
+            Self::SimilarSongs => ("Similar to Now Playing", "Seeded by the playing track"),
+            Self::SimilarArtists => ("Artists Like Now Playing", "Seeded by the playing artist"),
+            Self::Toplists => ("Charts", ""),
+        }
+    }
+
+    /// 该种类在某源下的虚拟歌单 id(不带种子)。
+    ///
+    /// # Params:
+    ///   - `source`: 所属源
+    ///
+    /// # Return:
+    ///   虚拟歌单 id;无虚拟歌单形态的种类 → `None`。
+    pub fn playlist_id(self, source: SourceKind) -> Option<PlaylistId> {
+        self.slug()
+            .map(|slug| PlaylistId::new(source, format!("{VIRTUAL_PREFIX}{slug}")))
+    }
+}
+
+/// 从虚拟歌单 id 解出的发现面引用。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoverRef {
+    /// 发现面种类。
+    pub kind: DiscoverKind,
+
+    /// 相似歌曲 / 相似艺人的种子曲(与歌单同源);未绑种子时为 `None`。
+    pub seed: Option<SongId>,
+}
+
+/// 该歌单 id 是否是发现面虚拟歌单(写操作据此拒绝)。
+pub fn is_virtual(id: &PlaylistId) -> bool {
+    id.value().starts_with(VIRTUAL_PREFIX)
+}
+
+/// 解析虚拟歌单 id;真实歌单 / 未知种类段 → `None`。
+///
+/// # Params:
+///   - `id`: 歌单 id
+///
+/// # Return:
+///   发现面引用(相似歌曲 / 相似艺人带可选种子)。
+pub fn parse_playlist_id(id: &PlaylistId) -> Option<DiscoverRef> {
+    let rest = id.value().strip_prefix(VIRTUAL_PREFIX)?;
+    let (slug, seed) = match rest.split_once(':') {
+        Some((slug, seed)) => (slug, Some(seed)),
+        None => (rest, None),
+    };
+    let kind = DiscoverKind::from_slug(slug)?;
+    let seed = match (kind, seed) {
+        (kind, Some(seed)) if kind.seeded() => Some(SongId::new(id.namespace(), seed)),
+        (_, Some(_)) => return None,
+        (_, None) => None,
+    };
+    Some(DiscoverRef { kind, seed })
+}
+
+/// 给未绑种子的「相似歌曲 / 相似艺人」虚拟歌单 id 绑上种子曲(server 按当前播放曲在提交
+/// 取数时调用)。
+///
+/// # Params:
+///   - `id`: 待绑种子的歌单 id
+///   - `seed`: 种子曲
+///
+/// # Return:
+///   绑好种子的 id;`id` 不是未绑种子的相似类歌单、或种子与歌单不同源 → `None`(原样使用)。
+pub fn seed_similar(id: &PlaylistId, seed: &SongId) -> Option<PlaylistId> {
+    let parsed = parse_playlist_id(id)?;
+    let slug = parsed.kind.slug()?;
+    if !parsed.kind.seeded() || parsed.seed.is_some() || seed.namespace() != id.namespace() {
+        return None;
+    }
+    Some(PlaylistId::new(
+        id.namespace(),
+        format!("{VIRTUAL_PREFIX}{slug}:{}", seed.value()),
+    ))
+}
+
+/// 某源 caps 声明的虚拟歌单条目(按声明序;只有元信息,曲目按需经 [`resolve_playlist`])。
+///
+/// # Params:
+///   - `source`: 所属源
+///   - `caps`: 该源能力声明
+///
+/// # Return:
+///   虚拟歌单列表(未声明发现面 → 空)。
+pub fn virtual_playlists(source: SourceKind, caps: &ChannelCaps) -> Vec<Playlist> {
+    caps.discover()
+        .iter()
+        .filter_map(|kind| virtual_playlist(source, *kind, Vec::new()))
+        .collect()
+}
+
+/// 取歌单详情的统一入口:虚拟 id 改调发现面方法,否则透传 [`MusicChannel::playlist_detail`]。
+///
+/// 虚拟歌单返回的 id 去掉种子,与歌单库里的条目对得上。相似类未绑种子(没在播 / 在播曲
+/// 不是本源的)给空歌单而非报错,条目照常可进。
+///
+/// # Params:
+///   - `channel`: 歌单所属源的 channel
+///   - `id`: 歌单 id(真实或虚拟)
+///
+/// # Return:
+///   歌单详情(元信息 + 曲目)。
+pub async fn resolve_playlist(channel: &dyn MusicChannel, id: &PlaylistId) -> Result<Playlist> {
+    let Some(discover) = parse_playlist_id(id) else {
+        return channel.playlist_detail(id).await;
+    };
+    let songs = match (discover.kind, discover.seed) {
+        (DiscoverKind::DailySongs, _) => channel.daily_songs().await?,
+        (DiscoverKind::PersonalFm, _) => channel.personal_fm().await?,
+        (DiscoverKind::SimilarSongs, Some(seed)) => channel.similar_songs(&seed).await?,
+        (DiscoverKind::SimilarArtists, Some(seed)) => {
+            let artists = match seed_artist(channel, &seed).await? {
+                Some(artist) => channel.similar_artists(&artist).await?,
+                None => Vec::new(),
+            };
+            let mut playlist = virtual_playlist(
+                id.namespace(),
+                discover.kind,
+                artist_top_songs(channel, &artists).await,
+            )
+            .ok_or(Error::NotSupported)?;
+            if !artists.is_empty() {
+                playlist.description = artists
+                    .iter()
+                    .take(SIMILAR_ARTISTS_LIMIT)
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" · ");
+            }
+            return Ok(playlist);
+        }
+        (DiscoverKind::SimilarSongs | DiscoverKind::SimilarArtists, None) => Vec::new(),
+        (DiscoverKind::Toplists, _) => {
+            return Err(Error::NotSupported);
+        }
+    };
+    virtual_playlist(id.namespace(), discover.kind, songs).ok_or(Error::NotSupported)
+}
+
+/// 种子曲的主艺人(相似艺人的查询起点)。
+///
+/// # Params:
+///   - `channel`: 种子曲所属源的 channel
+///   - `seed`: 种子曲
+///
+/// # Return:
+///   主艺人 id;详情里查不到该曲或其无艺人 → `None`。
+async fn seed_artist(channel: &dyn MusicChannel, seed: &SongId) -> Result<Option<ArtistId>> {
+    let songs = channel.songs_detail(std::slice::from_ref(seed)).await?;
+    Ok(songs
+        .into_iter()
+        .next()
+        .and_then(|song| song.artists.into_iter().next())
+        .map(|artist| artist.id))
+}
+
+/// 每位相似艺人取一首热门曲(至多 [`SIMILAR_ARTISTS_LIMIT`] 位)。单个艺人详情失败只少这一首,
+/// 不拖垮整张歌单。
+///
+/// # Params:
+///   - `channel`: 艺人所属源的 channel
+///   - `artists`: 相似艺人(源给的相似度序)
+///
+/// # Return:
+///   按艺人序排列的曲目。
+async fn artist_top_songs(channel: &dyn MusicChannel, artists: &[Artist]) -> Vec<Song> {
+    let mut songs = Vec::new();
+    for artist in artists.iter().take(SIMILAR_ARTISTS_LIMIT) {
+        if let Ok(detail) = channel.artist_detail(&artist.id).await
+            && let Some(top) = detail.songs.into_iter().next()
+        {
+            songs.push(top);
+        }
+    }
+    songs
+}
+
+/// 组一个虚拟歌单;无虚拟歌单形态的种类 → `None`。
+fn virtual_playlist(source: SourceKind, kind: DiscoverKind, songs: Vec<Song>) -> Option<Playlist> {
+    let id = kind.playlist_id(source)?;
+    let (name, description) = kind.label();
+    Some(
+        Playlist::builder()
+            .id(id)
+            .name(name.to_owned())
+            .description(description.to_owned())
+            .track_count(u64::try_from(songs.len()).unwrap_or(u64::MAX))
+            .songs(songs)
+            .build(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{PlaylistId, SongId, SourceKind};
+
+    use super::{DiscoverKind, is_virtual, parse_playlist_id, seed_similar};
+
+    /// 虚拟 id 往返:种类段解回种类;相似歌曲的种子按同源 SongId 解出。
+    #[test]
+    fn virtual_ids_roundtrip() -> color_eyre::Result<()> {
+        let daily = DiscoverKind::DailySongs
+            .playlist_id(SourceKind::NETEASE)
+            .ok_or_else(|| color_eyre::eyre::eyre!("每日推荐应有虚拟歌单"))?;
+        assert!(is_virtual(&daily));
+        let parsed =
+            parse_playlist_id(&daily).ok_or_else(|| color_eyre::eyre::eyre!("应能解回"))?;
+        assert_eq!(parsed.kind, DiscoverKind::DailySongs);
+        assert_eq!(parsed.seed, None);
+
+        let similar = DiscoverKind::SimilarSongs
+            .playlist_id(SourceKind::NETEASE)
+            .ok_or_else(|| color_eyre::eyre::eyre!("相似歌曲应有虚拟歌单"))?;
+        let seed = SongId::new(SourceKind::NETEASE, "186016");
+        let seeded = seed_similar(&similar, &seed)
+            .ok_or_else(|| color_eyre::eyre::eyre!("同源种子应绑上"))?;
+        let parsed =
+            parse_playlist_id(&seeded).ok_or_else(|| color_eyre::eyre::eyre!("应能解回"))?;
+        assert_eq!(parsed.kind, DiscoverKind::SimilarSongs);
+        assert_eq!(parsed.seed, Some(seed));
+        Ok(())
+    }
+
+    /// 真实歌单 id 不认;只有相似歌曲能带种子;跨源种子 / 已绑种子不再绑。
+    #[test]
+    fn rejects_real_and_malformed_ids() -> color_eyre::Result<()> {
+        let real = PlaylistId::new(SourceKind::NETEASE, "3778678");
+        assert!(!is_virtual(&real));
+        assert_eq!(parse_playlist_id(&real), None);
+        assert_eq!(
+            parse_playlist_id(&PlaylistId::new(SourceKind::NETEASE, "discover:fm:1")),
+            None,
+            "FM 不带种子"
+        );
+        assert_eq!(
+            DiscoverKind::Toplists.playlist_id(SourceKind::NETEASE),
+            None
+        );
+
+        let similar = DiscoverKind::SimilarSongs
+            .playlist_id(SourceKind::NETEASE)
+            .ok_or_else(|| color_eyre::eyre::eyre!("相似歌曲应有虚拟歌单"))?;
+        let foreign = SongId::new(SourceKind::BILIBILI, "BV1xx:1");
+        assert_eq!(seed_similar(&similar, &foreign), None, "跨源种子不绑");
+        let own = SongId::new(SourceKind::NETEASE, "1");
+        let seeded = seed_similar(&similar, &own)
+            .ok_or_else(|| color_eyre::eyre::eyre!("同源种子应绑上"))?;
+        assert_eq!(seed_similar(&seeded, &own), None, "已绑种子不再绑");
+        Ok(())
+    }
+
+    /// 相似艺人:种子曲 → 主艺人 → 相似艺人,每位出一首热门曲、简介列艺人名;回报 id 去掉种子;
+    /// 未绑种子给空歌单。
+    #[tokio::test]
+    async fn similar_artists_resolve_to_top_songs() -> color_eyre::Result<()> {
+        use async_trait::async_trait;
+        use mineral_model::{Artist, ArtistId, ArtistRef, BitRate, PlayUrl, Song};
+
+        use super::resolve_playlist;
+        use crate::{ChannelCaps, MusicChannel, Result};
+
+        /// 只答种子曲详情、相似艺人与艺人热门曲的桩 channel。
+        struct Stub;
+
+        /// 造一首歌:id 与名字同值,艺人一位。
+        fn song(id: &str, artist: &str) -> Song {
+            Song::builder()
+                .id(SongId::new(SourceKind::NETEASE, id))
+                .name(id.to_owned())
+                .artists(vec![ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, artist),
+                    name: artist.to_owned(),
+                }])
+                .build()
+        }
+
+        #[async_trait]
+        impl MusicChannel for Stub {
+            fn source(&self) -> SourceKind {
+                SourceKind::NETEASE
+            }
+
+            fn caps(&self) -> ChannelCaps {
+                ChannelCaps::builder()
+                    .searchable(Vec::new())
+                    .playlist_edit(false)
+                    .artist_sections(crate::ArtistSections::new(Vec::new()))
+                    .build()
+            }
+
+            async fn songs_detail(&self, ids: &[SongId]) -> Result<Vec<Song>> {
+                Ok(ids
+                    .iter()
+                    .map(|id| song(id.value(), "seed-artist"))
+                    .collect())
+            }
+
+            async fn similar_artists(&self, id: &ArtistId) -> Result<Vec<Artist>> {
+                assert_eq!(id.value(), "seed-artist", "以种子曲主艺人查相似");
+                Ok(["a", "b"]
+                    .into_iter()
+                    .map(|name| {
+                        Artist::builder()
+                            .id(ArtistId::new(SourceKind::NETEASE, name))
+                            .name(name.to_owned())
+                            .build()
+                    })
+                    .collect())
+            }
+
+            async fn artist_detail(&self, id: &ArtistId) -> Result<Artist> {
+                Ok(Artist::builder()
+                    .id(id.clone())
+                    .name(id.value().to_owned())
+                    .songs(vec![song(&format!("{}-top", id.value()), id.value())])
+                    .build())
+            }
+
+            async fn song_urls(&self, _ids: &[SongId], _quality: BitRate) -> Result<Vec<PlayUrl>> {
+                Ok(Vec::new())
+            }
+        }
+
+        let unseeded = DiscoverKind::SimilarArtists
+            .playlist_id(SourceKind::NETEASE)
+            .ok_or_else(|| color_eyre::eyre::eyre!("相似艺人应有虚拟歌单"))?;
+        assert!(resolve_playlist(&Stub, &unseeded).await?.songs.is_empty());
+
+        let seeded = seed_similar(&unseeded, &SongId::new(SourceKind::NETEASE, "186016"))
+            .ok_or_else(|| color_eyre::eyre::eyre!("同源种子应绑上"))?;
+        let playlist = resolve_playlist(&Stub, &seeded).await?;
+        assert_eq!(playlist.id, unseeded);
+        assert_eq!(playlist.description, "a · b");
+        let ids = playlist
+            .songs
+            .iter()
+            .map(|s| s.id.value().to_owned())
+            .collect::<Vec<String>>();
+        assert_eq!(ids, ["a-top", "b-top"]);
+        Ok(())
+    }
+}
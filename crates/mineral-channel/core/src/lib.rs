@@ -7,18 +7,24 @@
 pub mod caps;
 /// 登录凭证类型。
 pub mod credential;
+/// 发现面(推荐 / 排行榜)种类与歌单库虚拟歌单约定。
+pub mod discover;
 /// channel 公共错误类型与 `Result` 别名。
 pub mod error;
 /// 搜索命中页(含显式翻页信号)。
 pub mod hits;
 /// 列表分页参数。
 pub mod page;
+/// 曲库重扫回执。
+pub mod scan;
 
-pub use caps::{ArtistSectionKind, ArtistSections, ChannelCaps, render_web_url};
+pub use caps::{ArtistSectionKind, ArtistSections, ChannelCaps, match_web_url, render_web_url};
 pub use credential::Credential;
+pub use discover::DiscoverKind;
 pub use error::{Error, Result};
 pub use hits::SearchHits;
 pub use page::Page;
+pub use scan::LibraryScanReport;
 
 use rustc_hash::FxHashSet;
 
@@ -102,7 +108,8 @@ pub trait MusicChannel: Send + Sync {
     // ---------- 歌单管理(可选写操作) ----------
     // 前置条件(调用方保证,server 在边界校验):涉及的 SongId 必须与歌单
     // PlaylistId 同 namespace——远程歌单装不下别源的歌,channel 实现不做
-    // 防御性检查。写操作失败语义见 [`Error`];实现方不得把远端的"已存在"
+    // 防御性检查。唯一例外是聚合源(`mineral`)自有的混源歌单,它只存引用,
+    // 任何源的歌都收。写操作失败语义见 [`Error`];实现方不得把远端的"已存在"
     // 等业务态伪装成成功。
 
     /// 创建歌单(可选)。
@@ -142,6 +149,16 @@ pub trait MusicChannel: Send + Sync {
         Err(Error::NotSupported)
     }
 
+    /// 把歌单内一首歌挪到指定位置(可选)。
+    ///
+    /// # Params:
+    ///   - `id`: 目标歌单
+    ///   - `song`: 要挪的歌(须已在歌单中)
+    ///   - `to`: 目标位置(0-based,按挪动后的列表计;越界即放末尾)
+    async fn playlist_move_song(&self, _id: &PlaylistId, _song: &SongId, _to: usize) -> Result<()> {
+        Err(Error::NotSupported)
+    }
+
     /// 歌单改名(可选)。
     async fn rename_playlist(&self, _id: &PlaylistId, _name: &str) -> Result<()> {
         Err(Error::NotSupported)
@@ -152,6 +169,55 @@ pub trait MusicChannel: Send + Sync {
         Err(Error::NotSupported)
     }
 
+    /// 创建智能歌单(可选;只有聚合源认)。
+    ///
+    /// # Params:
+    ///   - `name`: 歌单名(同时作稳定键)
+    ///   - `query`: 筛选表达式原文(空串 = 空歌单)
+    ///
+    /// # Return:
+    ///   新建的歌单;同名已存在为冲突错误,不覆盖原定义。
+    async fn create_smart_playlist(&self, _name: &str, _query: &str) -> Result<Playlist> {
+        Err(Error::NotSupported)
+    }
+
+    // ---------- 发现 / 推荐(可选) ----------
+    // 源在 caps 的 `discover` 里声明支持哪几种;每日推荐 / 私人 FM / 相似歌曲在歌单库里以
+    // 虚拟歌单出现,取详情经 [`discover::resolve_playlist`] 路由到这里。
+
+    /// 每日推荐歌曲(可选;个性化面,通常需登录)。
+    async fn daily_songs(&self) -> Result<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    /// 私人 FM 的下一批歌曲(可选;通常需登录)。每次调用都给**新的一批**,上层可反复
+    /// 拉取当无尽电台用。
+    async fn personal_fm(&self) -> Result<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    /// 与某首歌相似的歌曲(可选)。
+    ///
+    /// # Params:
+    ///   - `id`: 种子曲(与本源同 namespace)
+    async fn similar_songs(&self, _id: &SongId) -> Result<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    /// 与某艺人相似的艺人(可选)。
+    ///
+    /// # Params:
+    ///   - `id`: 种子艺人(与本源同 namespace)
+    async fn similar_artists(&self, _id: &ArtistId) -> Result<Vec<Artist>> {
+        Err(Error::NotSupported)
+    }
+
+    /// 公开排行榜(可选)。榜单即该源的真实歌单,只给元信息,曲目按需走
+    /// [`Self::playlist_detail`]。
+    async fn toplists(&self) -> Result<Vec<Playlist>> {
+        Err(Error::NotSupported)
+    }
+
     // ---------- 播放 ----------
     /// 解析若干歌曲在指定音质下的播放 URL。
     async fn song_urls(&self, ids: &[SongId], quality: BitRate) -> Result<Vec<PlayUrl>>;
@@ -185,6 +251,28 @@ pub trait MusicChannel: Send + Sync {
         Err(Error::NotSupported)
     }
 
+    /// 重扫该 channel 背后的文件曲库(可选;只有本地类源有意义)。
+    ///
+    /// # Params:
+    ///   - `full`: `true` 忽略索引指纹、全部重读标签;`false` 只处理新增 / 变动 / 消失的文件
+    ///
+    /// # Return:
+    ///   本次扫描的计数回执;无文件曲库的源返回 [`Error::NotSupported`]。
+    async fn rescan_library(&self, _full: bool) -> Result<LibraryScanReport> {
+        Err(Error::NotSupported)
+    }
+
+    /// 文件曲库里的全部歌曲(可选;只有本地类源有意义)。
+    ///
+    /// 供按目录浏览 / 按标签列举这类要看见整个曲库的入口(如 MPD 的 `lsinfo` / `list`);
+    /// 未扫描过的曲库先扫描一次。
+    ///
+    /// # Return:
+    ///   按文件路径排序的全部歌曲;无文件曲库的源返回 [`Error::NotSupported`]。
+    async fn library_songs(&self) -> Result<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
     // ---------- 用户数据 / 装饰(可选) ----------
     // 这一组方法都是「同一登录用户视角下,跨歌曲的元信息」,bulk 一次拉满,
     // 上层用来 decorate `SongView`。沿用 default `NotSupported` 模式。
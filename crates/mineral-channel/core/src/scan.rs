@@ -0,0 +1,25 @@
+//! 曲库重扫回执。
+
+use serde::{Deserialize, Serialize};
+
+/// 一次 [`crate::MusicChannel::rescan_library`] 的结果计数。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryScanReport {
+    /// 扫描后曲库总曲数。
+    pub tracks: u64,
+
+    /// 新增文件数。
+    pub added: u64,
+
+    /// 指纹(mtime / size)变动而重读标签的文件数。
+    pub updated: u64,
+
+    /// 已消失而移出索引的文件数。
+    pub removed: u64,
+
+    /// 指纹未变、直接复用索引的文件数。
+    pub unchanged: u64,
+
+    /// 扫描耗时(ms)。
+    pub elapsed_ms: u64,
+}
@@ -116,6 +116,14 @@ async fn playlist_writes_default_to_not_supported() -> color_eyre::Result<()> {
         chan.set_playlist_description(&pl, "新描述").await,
         Err(Error::NotSupported)
     ));
+    assert!(matches!(
+        chan.create_smart_playlist("智能", "loved").await,
+        Err(Error::NotSupported)
+    ));
+    assert!(matches!(
+        chan.library_songs().await,
+        Err(Error::NotSupported)
+    ));
     Ok(())
 }
 
@@ -0,0 +1,381 @@
+//! 本地曲库 channel 实现:数据全部来自扫描根下的文件,无网络后端。
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use mineral_channel_core::{
+    ArtistSectionKind, ArtistSections, ChannelCaps, Error, LibraryScanReport, MusicChannel, Page,
+    Result, SearchHits,
+};
+use mineral_model::{
+    Album, AlbumId, Artist, ArtistId, BitRate, MediaUrl, PlayUrl, Playlist, PlaylistId, SearchKind,
+    Song, SongId, SourceKind,
+};
+use mineral_persist::{LocalLibraryStore, LocalScanRecord, LocalTrackRow, ServerStore};
+use tokio::sync::Mutex;
+
+use crate::config::LocalConfig;
+use crate::index::{self, Reconciled};
+use crate::library::Library;
+use crate::m3u::M3u;
+use crate::{m3u, scan, tags};
+
+/// 本地曲库 channel:source 为 [`SourceKind::LOCAL`]。
+///
+/// 曲库**首次被查询时**才扫描(启动不付扫描代价,没打开本地源的会话完全不碰磁盘),
+/// 之后复用内存快照。扫描对照持久索引做增量:`(mtime, size)` 未变的文件直接复用
+/// 索引里的标签,只 probe 新增 / 变动的文件;文件变动后走 [`LocalChannel::rescan`]。
+pub struct LocalChannel {
+    /// 扫描配置(根目录 / 封面抽取目录)。
+    config: LocalConfig,
+
+    /// 持久索引(降级 store 时每次扫描都是全量 probe)。
+    index: LocalLibraryStore,
+
+    /// 当前曲库快照;`None` = 尚未扫描。只护换快照,查询拿到 `Arc` 即放锁。
+    library: parking_lot::Mutex<Option<Arc<Library>>>,
+
+    /// 扫描互斥:同一时刻只有一次扫描在跑,扫描期间查询照常读旧快照。
+    scan_lock: Mutex<()>,
+}
+
+impl LocalChannel {
+    /// 新建本地 channel(不扫描,见类型文档)。
+    ///
+    /// # Params:
+    ///   - `config`: 扫描配置
+    ///   - `store`: 持久层句柄(取本地曲库索引)
+    ///
+    /// # Return:
+    ///   本地 channel 实例。
+    pub fn new(config: LocalConfig, store: &ServerStore) -> Self {
+        Self {
+            config,
+            index: store.local_library(),
+            library: parking_lot::Mutex::new(None),
+            scan_lock: Mutex::new(()),
+        }
+    }
+
+    /// 重新扫描全部根目录、对账索引并替换曲库快照。
+    ///
+    /// # Params:
+    ///   - `full`: `true` 忽略索引指纹、全部重读标签
+    ///
+    /// # Return:
+    ///   扫描计数回执;扫描任务 panic / 索引读写失败为 `Err`。
+    pub async fn rescan(&self, full: bool) -> Result<LibraryScanReport> {
+        let _scanning = self.scan_lock.lock().await;
+        self.scan(full).await
+    }
+
+    /// 取当前曲库快照,未扫描过则先做一次增量扫描。
+    async fn library(&self) -> Result<Arc<Library>> {
+        if let Some(lib) = self.snapshot() {
+            return Ok(lib);
+        }
+        let _scanning = self.scan_lock.lock().await;
+        // 等锁期间别的调用可能已建好
+        if let Some(lib) = self.snapshot() {
+            return Ok(lib);
+        }
+        self.scan(false).await?;
+        self.snapshot()
+            .ok_or_else(|| Error::Other(eyre!("本地曲库扫描后快照缺失")))
+    }
+
+    /// 当前快照(不触发扫描)。
+    fn snapshot(&self) -> Option<Arc<Library>> {
+        self.library.lock().clone()
+    }
+
+    /// 一次扫描:读索引 → 阻塞线程池里遍历 + 对账 + 建库 → 增量落库 → 换快照。
+    /// 调用方须持有 `scan_lock`。
+    ///
+    /// # Params:
+    ///   - `full`: 是否全量
+    ///
+    /// # Return:
+    ///   扫描计数回执。
+    async fn scan(&self, full: bool) -> Result<LibraryScanReport> {
+        let started = Instant::now();
+        let prev = self.index.load_all().await.map_err(Error::Other)?;
+        let config = self.config.clone();
+        let (lib, diff) = tokio::task::spawn_blocking(move || scan_library(&config, prev, full))
+            .await
+            .map_err(|e| Error::Other(eyre!("本地曲库扫描任务失败: {e}")))?;
+        self.index
+            .apply(&diff.upserts, &diff.removed)
+            .await
+            .map_err(Error::Other)?;
+        let report = LibraryScanReport {
+            tracks: u64::try_from(lib.len()).unwrap_or(u64::MAX),
+            added: diff.added,
+            updated: diff.updated,
+            removed: u64::try_from(diff.removed.len()).unwrap_or(u64::MAX),
+            unchanged: diff.unchanged,
+            elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        };
+        let record = LocalScanRecord {
+            finished_at_ms: unix_ms(),
+            full,
+            tracks: report.tracks,
+            added: report.added,
+            updated: report.updated,
+            removed: report.removed,
+            elapsed_ms: report.elapsed_ms,
+        };
+        if let Err(e) = self.index.record_scan(&record).await {
+            mineral_log::warn!(target: "local", error = mineral_log::chain(&e), "记录扫描回执失败");
+        }
+        *self.library.lock() = Some(Arc::new(lib));
+        mineral_log::info!(
+            target: "local",
+            full,
+            tracks = report.tracks,
+            added = report.added,
+            updated = report.updated,
+            removed = report.removed,
+            elapsed_ms = report.elapsed_ms,
+            "本地曲库扫描完成"
+        );
+        Ok(report)
+    }
+}
+
+/// 同步扫描:遍历根 → 解析 m3u → 对账索引(只读变动文件的标签)→ 建库。
+///
+/// m3u 引用的根外音频文件与根内文件一同对账,同样享受增量。
+///
+/// # Params:
+///   - `config`: 扫描配置
+///   - `prev`: 上次索引
+///   - `full`: 是否全量
+///
+/// # Return:
+///   建好的曲库与对账结果(`tracks` 已移交曲库)。
+fn scan_library(
+    config: &LocalConfig,
+    prev: Vec<LocalTrackRow>,
+    full: bool,
+) -> (Library, Reconciled) {
+    let cover_dir = config.cover_dir().as_deref();
+    let found = scan::walk(config.roots());
+    let m3us = found
+        .m3u
+        .into_iter()
+        .filter_map(|p| match m3u::read(&p) {
+            Ok(list) => Some((p, list)),
+            Err(e) => {
+                mineral_log::warn!(target: "local", path = %p.display(), error = mineral_log::chain(&e), "读 m3u 失败,跳过");
+                None
+            }
+        })
+        .collect::<Vec<(PathBuf, M3u)>>();
+    let mut paths = found.audio;
+    for (_, list) in &m3us {
+        paths.extend(
+            list.entries
+                .iter()
+                .filter(|e| e.is_file() && scan::is_audio(e))
+                .cloned(),
+        );
+    }
+    let mut diff = index::reconcile(prev, &paths, full, |p| tags::read_track(p, cover_dir));
+    let tracks = std::mem::take(&mut diff.tracks);
+    let lib = Library::build(tracks, config.roots(), m3us, |p| {
+        tags::read_track(p, cover_dir)
+    });
+    (lib, diff)
+}
+
+/// 当前 unix 毫秒(扫描回执时间戳)。
+fn unix_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .and_then(|d| i64::try_from(d.as_millis()).ok())
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl MusicChannel for LocalChannel {
+    fn source(&self) -> SourceKind {
+        SourceKind::LOCAL
+    }
+
+    fn caps(&self) -> ChannelCaps {
+        // 歌单是文件夹 / m3u 的只读投影,不开写操作;本地文件无网页形态,不给 web url。
+        ChannelCaps::builder()
+            .searchable(vec![
+                SearchKind::Song,
+                SearchKind::Album,
+                SearchKind::Artist,
+                SearchKind::Playlist,
+            ])
+            .playlist_edit(false)
+            .artist_sections(ArtistSections::new(vec![
+                ArtistSectionKind::TopSongs,
+                ArtistSectionKind::Albums,
+            ]))
+            .build()
+    }
+
+    async fn search_songs(&self, query: &str, page: Page) -> Result<SearchHits<Song>> {
+        Ok(self.library().await?.search_songs(query, page))
+    }
+
+    async fn search_albums(&self, query: &str, page: Page) -> Result<SearchHits<Album>> {
+        Ok(self.library().await?.search_albums(query, page))
+    }
+
+    async fn search_playlists(&self, query: &str, page: Page) -> Result<SearchHits<Playlist>> {
+        Ok(self.library().await?.search_playlists(query, page))
+    }
+
+    async fn search_artists(&self, query: &str, page: Page) -> Result<SearchHits<Artist>> {
+        Ok(self.library().await?.search_artists(query, page))
+    }
+
+    async fn songs_detail(&self, ids: &[SongId]) -> Result<Vec<Song>> {
+        Ok(self.library().await?.songs_detail(ids))
+    }
+
+    async fn album_detail(&self, id: &AlbumId) -> Result<Album> {
+        self.library()
+            .await?
+            .album(id)
+            .ok_or_else(|| not_found("album", id.value()))
+    }
+
+    async fn playlist_detail(&self, id: &PlaylistId) -> Result<Playlist> {
+        self.library()
+            .await?
+            .playlist(id)
+            .ok_or_else(|| not_found("playlist", id.value()))
+    }
+
+    async fn artist_detail(&self, id: &ArtistId) -> Result<Artist> {
+        self.library()
+            .await?
+            .artist(id)
+            .ok_or_else(|| not_found("artist", id.value()))
+    }
+
+    async fn artist_albums(&self, id: &ArtistId, page: Page) -> Result<Vec<Album>> {
+        Ok(self.library().await?.artist_albums(id, page))
+    }
+
+    /// 本地文件只有一份:忽略请求音质,按文件实际属性报档(见 `Track::quality`)。
+    /// 库里没有的 id(文件已删 / 不在扫描根下)略过,调用方按缺项处理。
+    async fn song_urls(&self, ids: &[SongId], _quality: BitRate) -> Result<Vec<PlayUrl>> {
+        let lib = self.library().await?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| {
+                let t = lib.track(id)?;
+                let size = std::fs::metadata(&t.path).ok().map(|m| m.len());
+                Some(PlayUrl {
+                    song_id: id.clone(),
+                    url: MediaUrl::local(&t.path),
+                    bitrate_bps: t.bitrate_kbps.and_then(|k| k.checked_mul(1000)),
+                    quality: t.quality(),
+                    size,
+                    format: t.format.clone(),
+                    bit_depth: t.bit_depth,
+                    stream_headers: Vec::new(),
+                    layout: Default::default(),
+                    substituted: false,
+                })
+            })
+            .collect())
+    }
+
+    async fn my_playlists(&self) -> Result<Vec<Playlist>> {
+        Ok(self.library().await?.playlists())
+    }
+
+    async fn rescan_library(&self, full: bool) -> Result<LibraryScanReport> {
+        self.rescan(full).await
+    }
+
+    async fn library_songs(&self) -> Result<Vec<Song>> {
+        Ok(self.library().await?.songs())
+    }
+}
+
+/// 库里查不到的实体 → 业务错误(本地源没有远端 code,统一用 404)。
+fn not_found(kind: &str, id: &str) -> Error {
+    Error::Api {
+        code: 404,
+        message: format!("local {kind} not found: {id}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_channel_core::{MusicChannel, Page};
+    use mineral_model::{BitRate, MediaUrl};
+    use mineral_persist::ServerStore;
+
+    use super::LocalChannel;
+    use crate::config::LocalConfig;
+    use crate::library::song_id;
+
+    /// 端到端:扫描临时目录 → 搜得到 → 取流给本地路径与文件实际音质;新文件经 rescan 入库。
+    #[tokio::test]
+    async fn scan_search_and_song_urls() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let album = dir.path().join("Shore");
+        std::fs::create_dir_all(&album)?;
+        let wav = album.join("Palisade.wav");
+        mineral_test::write_wav(&wav, &[0_i16; 800], 1, 8_000)?;
+        let config = LocalConfig::builder()
+            .roots(vec![dir.path().to_path_buf()])
+            .build();
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let ch = LocalChannel::new(config.clone(), &store);
+
+        let hits = ch
+            .search_songs(
+                "palisade",
+                Page {
+                    offset: 0,
+                    limit: 10,
+                },
+            )
+            .await?;
+        assert_eq!(hits.items.len(), 1, "无标签文件按文件名可搜");
+        let urls = ch.song_urls(&[song_id(&wav)], BitRate::Standard).await?;
+        let pu = urls
+            .first()
+            .ok_or_else(|| color_eyre::eyre::eyre!("缺 PlayUrl"))?;
+        assert_eq!(pu.url, MediaUrl::local(&wav));
+        assert_eq!(pu.quality, BitRate::Lossless, "忽略请求档,按文件报无损");
+
+        mineral_test::write_wav(&album.join("Tide.wav"), &[0_i16; 800], 1, 8_000)?;
+        let report = ch.rescan(false).await?;
+        assert_eq!((report.tracks, report.added, report.unchanged), (2, 1, 1));
+        let all = ch.library_songs().await?;
+        assert_eq!(
+            all.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["Palisade", "Tide"],
+            "全库按路径序"
+        );
+        let lists = ch.my_playlists().await?;
+        assert_eq!(
+            lists.first().map(|p| p.track_count),
+            Some(2),
+            "文件夹歌单随重扫更新"
+        );
+
+        // 新实例(= 重启)沿用持久索引:全部命中指纹,不重读标签
+        let reopened = LocalChannel::new(config, &store);
+        let report = reopened.rescan_library(false).await?;
+        assert_eq!((report.tracks, report.unchanged, report.added), (2, 2, 0));
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! `LocalChannel` 的构造参数([`LocalConfig`])。
+
+use std::path::PathBuf;
+
+/// `LocalChannel` 的构造参数。私有字段 + builder 构造 + getter 读取。
+///
+/// 同其他源:默认值的唯一真相源是 mineral-config 的 `default.lua`(`sources.local` 段),
+/// 本类型不带默认,由消费侧映射传入(`~` 展开也在消费侧做完)。
+#[non_exhaustive]
+#[derive(Clone, Debug, typed_builder::TypedBuilder, derive_getters::Getters)]
+pub struct LocalConfig {
+    /// 扫描根目录(已展开的绝对路径)。根不存在 / 不可读只 warn 跳过,不阻断其他根。
+    roots: Vec<PathBuf>,
+
+    /// 内嵌封面的抽取目录;`None` = 不抽内嵌封面(只认同目录的 `cover.jpg` 等图片)。
+    #[builder(default)]
+    cover_dir: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::LocalConfig;
+
+    /// builder 逐字段生效。
+    #[test]
+    fn builder_sets_fields() {
+        let c = LocalConfig::builder()
+            .roots(vec![PathBuf::from("/srv/music")])
+            .cover_dir(Some(PathBuf::from("/tmp/cover")))
+            .build();
+        assert_eq!(c.roots().as_slice(), &[PathBuf::from("/srv/music")]);
+        assert_eq!(
+            c.cover_dir().as_deref(),
+            Some(std::path::Path::new("/tmp/cover"))
+        );
+    }
+}
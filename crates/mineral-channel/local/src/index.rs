@@ -0,0 +1,248 @@
+//! 扫描结果与持久索引的对账:按 `(mtime, size)` 指纹决定复用索引行还是重读标签。
+//!
+//! 纯同步逻辑(stat + lofty),由 channel 放到阻塞线程池里跑;落库与建库在外层。
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use mineral_model::AudioFormat;
+use mineral_persist::LocalTrackRow;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::tags::Track;
+
+/// 一个文件的变动指纹。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fingerprint {
+    /// 修改时间(unix ms);取不到为 0。
+    mtime_ms: i64,
+
+    /// 文件大小(bytes)。
+    size: u64,
+}
+
+/// 一次对账的产出。
+#[derive(Debug, Default)]
+pub(crate) struct Reconciled {
+    /// 扫描后的全部曲目(建库用)。
+    pub(crate) tracks: Vec<Track>,
+
+    /// 需写回索引的行(新增 + 重读)。
+    pub(crate) upserts: Vec<LocalTrackRow>,
+
+    /// 需移出索引的路径(文件已消失)。
+    pub(crate) removed: Vec<String>,
+
+    /// 新增文件数。
+    pub(crate) added: u64,
+
+    /// 重读文件数。
+    pub(crate) updated: u64,
+
+    /// 复用索引的文件数。
+    pub(crate) unchanged: u64,
+}
+
+/// 对账:当前文件集 vs 上次索引。
+///
+/// 指纹一致且非全量时复用索引行(不碰 lofty);否则经 `read` 重读标签。stat 失败的文件
+/// (扫描与对账之间被删)按消失处理。
+///
+/// # Params:
+///   - `prev`: 上次索引的全部行
+///   - `paths`: 本次扫到的音频文件(含 m3u 引用的根外文件)
+///   - `full`: 是否忽略指纹全部重读
+///   - `read`: 读标签函数(一般即 [`crate::tags::read_track`])
+///
+/// # Return:
+///   对账结果。
+pub(crate) fn reconcile(
+    prev: Vec<LocalTrackRow>,
+    paths: &[PathBuf],
+    full: bool,
+    read: impl Fn(&Path) -> Track,
+) -> Reconciled {
+    let mut prev = prev
+        .into_iter()
+        .map(|r| (r.path.clone(), r))
+        .collect::<FxHashMap<String, LocalTrackRow>>();
+    let mut seen = FxHashSet::<String>::default();
+    let mut out = Reconciled::default();
+    for path in paths {
+        let key = path_key(path);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        let old = prev.remove(&key);
+        let Some(fp) = fingerprint(path) else {
+            if old.is_some() {
+                out.removed.push(key);
+            }
+            continue;
+        };
+        match old {
+            Some(row) if !full && row.mtime_ms == fp.mtime_ms && row.size == fp.size => {
+                out.unchanged = out.unchanged.saturating_add(1);
+                out.tracks.push(from_row(path, row));
+            }
+            old => {
+                if old.is_some() {
+                    out.updated = out.updated.saturating_add(1);
+                } else {
+                    out.added = out.added.saturating_add(1);
+                }
+                let track = read(path);
+                out.upserts.push(to_row(&track, key, fp));
+                out.tracks.push(track);
+            }
+        }
+    }
+    // 剩下的旧行本次没扫到:文件已消失 / 已移出扫描根
+    out.removed.extend(prev.into_keys());
+    out.removed.sort();
+    out
+}
+
+/// 路径 → 索引主键(lossy UTF-8;非 UTF-8 路径每次都算变动,能播但不享受增量)。
+///
+/// # Params:
+///   - `path`: 文件路径
+///
+/// # Return:
+///   索引主键。
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// stat 取指纹。
+///
+/// # Params:
+///   - `path`: 文件路径
+///
+/// # Return:
+///   指纹;文件不存在 / 无权限为 `None`。
+fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime_ms = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .and_then(|d| i64::try_from(d.as_millis()).ok())
+        .unwrap_or(0);
+    Some(Fingerprint {
+        mtime_ms,
+        size: meta.len(),
+    })
+}
+
+/// 曲目 → 索引行。
+///
+/// # Params:
+///   - `t`: 曲目
+///   - `key`: 索引主键
+///   - `fp`: 文件指纹
+///
+/// # Return:
+///   索引行。
+fn to_row(t: &Track, key: String, fp: Fingerprint) -> LocalTrackRow {
+    LocalTrackRow {
+        path: key,
+        mtime_ms: fp.mtime_ms,
+        size: fp.size,
+        title: t.title.clone(),
+        artists: t.artists.clone(),
+        album: t.album.clone(),
+        album_artist: t.album_artist.clone(),
+        year: t.year,
+        disc: t.disc,
+        track: t.track,
+        duration_ms: t.duration_ms,
+        format: t.format.as_ref().map(|f| f.as_str().to_owned()),
+        bitrate_kbps: t.bitrate_kbps,
+        bit_depth: t.bit_depth,
+        sample_rate: t.sample_rate,
+        cover: t.cover.as_ref().map(|c| c.to_string_lossy().into_owned()),
+    }
+}
+
+/// 索引行 → 曲目(路径取本次扫到的真实路径,不用 lossy 主键)。
+///
+/// # Params:
+///   - `path`: 文件真实路径
+///   - `row`: 索引行
+///
+/// # Return:
+///   曲目。
+fn from_row(path: &Path, row: LocalTrackRow) -> Track {
+    Track {
+        path: path.to_path_buf(),
+        title: row.title,
+        artists: row.artists,
+        album: row.album,
+        album_artist: row.album_artist,
+        year: row.year,
+        disc: row.disc,
+        track: row.track,
+        duration_ms: row.duration_ms,
+        format: row.format.map(AudioFormat::from),
+        bitrate_kbps: row.bitrate_kbps,
+        bit_depth: row.bit_depth,
+        sample_rate: row.sample_rate,
+        cover: row.cover.map(PathBuf::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::path::Path;
+
+    use super::reconcile;
+    use crate::tags::read_track;
+
+    /// 增量:未变文件复用、变动文件重读、新文件入库、消失文件移出;全量则全部重读。
+    #[test]
+    fn reconcile_by_fingerprint() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let a = dir.path().join("a.wav");
+        let b = dir.path().join("b.wav");
+        let gone = dir.path().join("gone.wav");
+        for p in [&a, &b, &gone] {
+            mineral_test::write_wav(p, &[0_i16; 800], 1, 8_000)?;
+        }
+        let reads = Cell::new(0_u32);
+        let read = |p: &Path| {
+            reads.set(reads.get().saturating_add(1));
+            read_track(p, None)
+        };
+
+        let first = reconcile(
+            Vec::new(),
+            &[a.clone(), b.clone(), gone.clone()],
+            false,
+            read,
+        );
+        assert_eq!((first.added, first.upserts.len()), (3, 3));
+        std::fs::remove_file(&gone)?;
+        mineral_test::write_wav(&b, &[0_i16; 1600], 1, 8_000)?;
+        let c = dir.path().join("c.wav");
+        mineral_test::write_wav(&c, &[0_i16; 800], 1, 8_000)?;
+
+        reads.set(0);
+        let paths = [a.clone(), b.clone(), c.clone()];
+        let second = reconcile(first.upserts.clone(), &paths, false, read);
+        assert_eq!(
+            (second.unchanged, second.updated, second.added),
+            (1, 1, 1),
+            "a 复用、b 重读、c 新增"
+        );
+        assert_eq!(reads.get(), 2, "复用的文件不碰标签");
+        assert_eq!(second.removed, vec![gone.to_string_lossy().into_owned()]);
+        assert_eq!(second.tracks.len(), 3);
+
+        let rows = reconcile(Vec::new(), &paths, false, read).upserts;
+        let full = reconcile(rows, &paths, true, read);
+        assert_eq!((full.unchanged, full.updated), (0, 3), "全量忽略指纹");
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+//! 本地曲库 channel(source = `local`):把配置的扫描根下的音频文件投影成统一模型。
+//!
+//! 模块自底向上:
+//! - [`scan`] —— 遍历扫描根,收集音频文件与 `.m3u` 歌单文件
+//! - [`tags`] —— lofty 读单个文件的标签 / 时长 / 封面
+//! - [`m3u`] —— `.m3u` / `.m3u8` 解析
+//! - [`index`] —— 与持久索引按 `(mtime, size)` 对账,只重读变动文件
+//! - [`library`] —— 内存索引(歌曲 / 专辑 / 艺人 / 歌单 + 搜索)
+//! - [`channel`] —— 把索引绑到 `MusicChannel` trait
+//!
+//! 没有网络,也没有「用户」:`my_playlists` 就是扫描根的直接子目录 + m3u 文件,取流直接
+//! 给 `MediaUrl::Local`。
+
+mod channel;
+mod config;
+mod index;
+mod library;
+mod m3u;
+mod scan;
+mod tags;
+
+pub use channel::LocalChannel;
+pub use config::LocalConfig;
@@ -0,0 +1,641 @@
+//! 内存曲库:扫描产出的 [`Track`] 集合 + 派生的专辑 / 艺人 / 歌单视图与搜索。
+//!
+//! 身份约定(全部挂 [`SourceKind::LOCAL`] namespace):
+//! - 歌曲 id = 文件绝对路径;
+//! - 艺人 id = 艺名;
+//! - 专辑 id = `<专辑艺人>/<专辑名>`(专辑艺人缺失回落主艺人;都缺则只有专辑名)——同名专辑
+//!   按艺人区分,不把两张 `Greatest Hits` 并成一张;
+//! - 歌单 id = `dir:<目录路径>`(扫描根的直接子目录,含其下全部子树)或 `m3u:<文件路径>`。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use mineral_channel_core::{Page, SearchHits};
+use mineral_model::{
+    Album, AlbumId, AlbumRef, Artist, ArtistId, ArtistRef, MediaUrl, Playlist, PlaylistId, Song,
+    SongId, SourceKind,
+};
+use rustc_hash::FxHashMap;
+
+use crate::m3u::M3u;
+use crate::tags::Track;
+
+/// 文件夹歌单 id 前缀。
+const DIR_PREFIX: &str = "dir:";
+
+/// m3u 歌单 id 前缀。
+const M3U_PREFIX: &str = "m3u:";
+
+/// 派生视图里的一组曲目(专辑 / 艺人 / 歌单共用):名字 + 成员下标。
+#[derive(Debug)]
+struct Group {
+    /// 展示名。
+    name: String,
+
+    /// 归属艺人(专辑用:专辑艺人;其他视图为 `None`)。
+    owner: Option<String>,
+
+    /// 描述(歌单用:来源路径)。
+    description: String,
+
+    /// 成员在 [`Library::tracks`] 里的下标(按视图自身的顺序排好)。
+    members: Vec<usize>,
+}
+
+/// 一次扫描得到的完整曲库快照(只读;重扫即整体替换)。
+#[derive(Debug, Default)]
+pub(crate) struct Library {
+    /// 全部曲目,按路径排序。
+    tracks: Vec<Track>,
+
+    /// 与 `tracks` 同序的歌曲投影(预先建好,查询时直接 clone)。
+    songs: Vec<Song>,
+
+    /// 歌曲 id → 下标。
+    by_id: FxHashMap<SongId, usize>,
+
+    /// 专辑,键为专辑 id 的值(id 类型无序,按值字符串排出稳定序)。
+    albums: BTreeMap<String, Group>,
+
+    /// 艺人,键为艺名(= 艺人 id 的值),按艺名排序。
+    artists: BTreeMap<String, Group>,
+
+    /// 歌单:先文件夹、后 m3u,各自按路径排序。
+    playlists: Vec<(PlaylistId, Group)>,
+}
+
+impl Library {
+    /// 由曲目与歌单文件建库。
+    ///
+    /// m3u 里引用了、但不在 `tracks` 里的音频文件(扫描根之外的路径)在此补读入库,
+    /// 歌单才不缺曲;引用的文件不存在则静默略过该条目。
+    ///
+    /// # Params:
+    ///   - `tracks`: 扫描根下读出的曲目(顺序不限)
+    ///   - `roots`: 扫描根(派生文件夹歌单)
+    ///   - `m3us`: 已解析的 m3u 歌单及其文件路径
+    ///   - `read_extra`: 补读 m3u 外部条目的函数(一般即 [`crate::tags::read_track`])
+    ///
+    /// # Return:
+    ///   建好的曲库。
+    pub(crate) fn build(
+        mut tracks: Vec<Track>,
+        roots: &[PathBuf],
+        m3us: Vec<(PathBuf, M3u)>,
+        read_extra: impl Fn(&Path) -> Track,
+    ) -> Self {
+        let mut known = tracks
+            .iter()
+            .map(|t| t.path.clone())
+            .collect::<rustc_hash::FxHashSet<PathBuf>>();
+        for (_, m3u) in &m3us {
+            for entry in &m3u.entries {
+                if !known.contains(entry) && entry.is_file() && crate::scan::is_audio(entry) {
+                    known.insert(entry.clone());
+                    tracks.push(read_extra(entry));
+                }
+            }
+        }
+        tracks.sort_by(|a, b| a.path.cmp(&b.path));
+        tracks.dedup_by(|a, b| a.path == b.path);
+
+        let songs = tracks.iter().map(to_song).collect::<Vec<Song>>();
+        let by_id = songs
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.id.clone(), i))
+            .collect::<FxHashMap<SongId, usize>>();
+        let mut lib = Self {
+            tracks,
+            songs,
+            by_id,
+            ..Self::default()
+        };
+        lib.derive_albums_and_artists();
+        lib.derive_folder_playlists(roots);
+        lib.derive_m3u_playlists(m3us);
+        lib
+    }
+
+    /// 曲库曲目总数。
+    pub(crate) fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// 全部歌曲(与曲目同序,即按路径)。
+    pub(crate) fn songs(&self) -> Vec<Song> {
+        self.songs.clone()
+    }
+
+    /// 按歌曲 id 取曲目。
+    pub(crate) fn track(&self, id: &SongId) -> Option<&Track> {
+        self.by_id.get(id).and_then(|&i| self.tracks.get(i))
+    }
+
+    /// 批量取歌曲;未知 id 略过(保持入参顺序)。
+    pub(crate) fn songs_detail(&self, ids: &[SongId]) -> Vec<Song> {
+        ids.iter()
+            .filter_map(|id| self.by_id.get(id))
+            .filter_map(|&i| self.songs.get(i).cloned())
+            .collect()
+    }
+
+    /// 专辑详情(含曲目,按碟号 / 曲序 / 路径排)。
+    pub(crate) fn album(&self, id: &AlbumId) -> Option<Album> {
+        self.albums
+            .get(id.value())
+            .map(|g| self.album_of(id.value(), g, /*with_songs*/ true))
+    }
+
+    /// 艺人详情:曲目为该艺人全部歌曲(按专辑归属再按路径),附专辑数 / 曲数。
+    pub(crate) fn artist(&self, id: &ArtistId) -> Option<Artist> {
+        let g = self.artists.get(id.value())?;
+        let album_count = u64::try_from(self.albums_of_artist(&g.name).count()).ok();
+        Some(
+            Artist::builder()
+                .id(id.clone())
+                .name(g.name.clone())
+                .album_count(album_count)
+                .song_count(u64::try_from(g.members.len()).ok())
+                .avatar_url(self.cover_of(&g.members))
+                .songs(self.songs_at(&g.members))
+                .build(),
+        )
+    }
+
+    /// 艺人参与的专辑(分页;不含曲目)。专辑艺人是他,或其任一曲目署了他的名,都算。
+    pub(crate) fn artist_albums(&self, id: &ArtistId, page: Page) -> Vec<Album> {
+        let Some(g) = self.artists.get(id.value()) else {
+            return Vec::new();
+        };
+        let all = self
+            .albums_of_artist(&g.name)
+            .map(|(key, ag)| self.album_of(key, ag, /*with_songs*/ false))
+            .collect::<Vec<Album>>();
+        paginate(all, page).items
+    }
+
+    /// 全部歌单(不含曲目,只带计数)。
+    pub(crate) fn playlists(&self) -> Vec<Playlist> {
+        self.playlists
+            .iter()
+            .map(|(id, g)| self.playlist_of(id, g, /*with_songs*/ false))
+            .collect()
+    }
+
+    /// 歌单详情(含曲目)。
+    pub(crate) fn playlist(&self, id: &PlaylistId) -> Option<Playlist> {
+        self.playlists
+            .iter()
+            .find(|(pid, _)| pid == id)
+            .map(|(pid, g)| self.playlist_of(pid, g, /*with_songs*/ true))
+    }
+
+    /// 搜单曲:每个词都须出现在歌名 / 艺人 / 专辑之一(大小写不敏感)。
+    pub(crate) fn search_songs(&self, query: &str, page: Page) -> SearchHits<Song> {
+        let terms = terms(query);
+        let hits = self
+            .tracks
+            .iter()
+            .zip(&self.songs)
+            .filter(|(t, _)| {
+                let hay = [
+                    t.title.as_str(),
+                    &t.artists.join(" "),
+                    t.album.as_deref().unwrap_or_default(),
+                ]
+                .join(" ");
+                matches(&terms, &hay)
+            })
+            .map(|(_, s)| s.clone())
+            .collect::<Vec<Song>>();
+        paginate(hits, page)
+    }
+
+    /// 搜专辑:词匹配专辑名或专辑艺人。
+    pub(crate) fn search_albums(&self, query: &str, page: Page) -> SearchHits<Album> {
+        let terms = terms(query);
+        let hits = self
+            .albums
+            .iter()
+            .filter(|(_, g)| {
+                let hay = format!("{} {}", g.name, g.owner.as_deref().unwrap_or_default());
+                matches(&terms, &hay)
+            })
+            .map(|(key, g)| self.album_of(key, g, /*with_songs*/ false))
+            .collect::<Vec<Album>>();
+        paginate(hits, page)
+    }
+
+    /// 搜艺人:词匹配艺名。
+    pub(crate) fn search_artists(&self, query: &str, page: Page) -> SearchHits<Artist> {
+        let terms = terms(query);
+        let hits = self
+            .artists
+            .iter()
+            .filter(|(_, g)| matches(&terms, &g.name))
+            .map(|(name, g)| {
+                Artist::builder()
+                    .id(ArtistId::new(SourceKind::LOCAL, name.as_str()))
+                    .name(g.name.clone())
+                    .song_count(u64::try_from(g.members.len()).ok())
+                    .avatar_url(self.cover_of(&g.members))
+                    .build()
+            })
+            .collect::<Vec<Artist>>();
+        paginate(hits, page)
+    }
+
+    /// 搜歌单:词匹配歌单名。
+    pub(crate) fn search_playlists(&self, query: &str, page: Page) -> SearchHits<Playlist> {
+        let terms = terms(query);
+        let hits = self
+            .playlists
+            .iter()
+            .filter(|(_, g)| matches(&terms, &g.name))
+            .map(|(id, g)| self.playlist_of(id, g, /*with_songs*/ false))
+            .collect::<Vec<Playlist>>();
+        paginate(hits, page)
+    }
+
+    /// 由曲目派生专辑与艺人分组。
+    fn derive_albums_and_artists(&mut self) {
+        for (i, t) in self.tracks.iter().enumerate() {
+            if let Some(album) = &t.album {
+                let owner = t.album_owner().map(str::to_owned);
+                self.albums
+                    .entry(album_key(owner.as_deref(), album))
+                    .or_insert_with(|| Group {
+                        name: album.clone(),
+                        owner,
+                        description: String::new(),
+                        members: Vec::new(),
+                    })
+                    .members
+                    .push(i);
+            }
+            for name in &t.artists {
+                self.artists
+                    .entry(name.clone())
+                    .or_insert_with(|| Group {
+                        name: name.clone(),
+                        owner: None,
+                        description: String::new(),
+                        members: Vec::new(),
+                    })
+                    .members
+                    .push(i);
+            }
+        }
+        let tracks = &self.tracks;
+        for g in self.albums.values_mut() {
+            g.members.sort_by_key(|&i| {
+                tracks
+                    .get(i)
+                    .map(|t| (t.disc.unwrap_or(1), t.track.unwrap_or(u32::MAX)))
+            });
+        }
+    }
+
+    /// 扫描根的每个直接子目录 → 一张文件夹歌单(成员 = 其子树下全部曲目,按路径)。
+    ///
+    /// 直接放在根下的散曲不成歌单(它们照样在曲库里、可搜)。
+    fn derive_folder_playlists(&mut self, roots: &[PathBuf]) {
+        let mut dirs = BTreeMap::<PathBuf, Vec<usize>>::new();
+        for (i, t) in self.tracks.iter().enumerate() {
+            let Some(root) = roots.iter().find(|r| t.path.starts_with(r)) else {
+                continue;
+            };
+            let Ok(rel) = t.path.strip_prefix(root) else {
+                continue;
+            };
+            let mut comps = rel.components();
+            if let (Some(first), Some(_)) = (comps.next(), comps.next()) {
+                dirs.entry(root.join(first)).or_default().push(i);
+            }
+        }
+        for (dir, members) in dirs {
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            self.playlists.push((
+                PlaylistId::new(SourceKind::LOCAL, format!("{DIR_PREFIX}{}", dir.display())),
+                Group {
+                    name,
+                    owner: None,
+                    description: dir.display().to_string(),
+                    members,
+                },
+            ));
+        }
+    }
+
+    /// 每个 m3u 文件 → 一张歌单(成员保持文件内顺序;库里没有的条目略过)。
+    fn derive_m3u_playlists(&mut self, mut m3us: Vec<(PathBuf, M3u)>) {
+        m3us.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, m3u) in m3us {
+            let members = m3u
+                .entries
+                .iter()
+                .filter_map(|p| self.by_id.get(&song_id(p)).copied())
+                .collect::<Vec<usize>>();
+            self.playlists.push((
+                PlaylistId::new(SourceKind::LOCAL, format!("{M3U_PREFIX}{}", path.display())),
+                Group {
+                    name: m3u.name,
+                    owner: None,
+                    description: path.display().to_string(),
+                    members,
+                },
+            ));
+        }
+    }
+
+    /// 名下有该艺人的专辑:专辑艺人即他,或任一曲目署名含他。
+    fn albums_of_artist<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a Group)> + 'a {
+        self.albums.iter().filter(move |(_, g)| {
+            g.owner.as_deref() == Some(name)
+                || g.members.iter().any(|&i| {
+                    self.tracks
+                        .get(i)
+                        .is_some_and(|t| t.artists.iter().any(|a| a == name))
+                })
+        })
+    }
+
+    /// 组 → [`Album`]。
+    fn album_of(&self, key: &str, g: &Group, with_songs: bool) -> Album {
+        let artists = g
+            .owner
+            .iter()
+            .map(|o| artist_ref(o))
+            .collect::<Vec<ArtistRef>>();
+        Album::builder()
+            .id(AlbumId::new(SourceKind::LOCAL, key))
+            .name(g.name.clone())
+            .artists(artists)
+            .track_count(u64::try_from(g.members.len()).ok())
+            .cover_url(self.cover_of(&g.members))
+            .songs(if with_songs {
+                self.songs_at(&g.members)
+            } else {
+                Vec::new()
+            })
+            .build()
+    }
+
+    /// 组 → [`Playlist`]。
+    fn playlist_of(&self, id: &PlaylistId, g: &Group, with_songs: bool) -> Playlist {
+        Playlist::builder()
+            .id(id.clone())
+            .name(g.name.clone())
+            .description(g.description.clone())
+            .cover_url(self.cover_of(&g.members))
+            .track_count(u64::try_from(g.members.len()).unwrap_or(u64::MAX))
+            .songs(if with_songs {
+                self.songs_at(&g.members)
+            } else {
+                Vec::new()
+            })
+            .build()
+    }
+
+    /// 按下标取歌曲(越界略过)。
+    fn songs_at(&self, members: &[usize]) -> Vec<Song> {
+        members
+            .iter()
+            .filter_map(|&i| self.songs.get(i).cloned())
+            .collect()
+    }
+
+    /// 组封面:第一首带封面的曲目的封面。
+    fn cover_of(&self, members: &[usize]) -> Option<MediaUrl> {
+        members
+            .iter()
+            .filter_map(|&i| self.tracks.get(i))
+            .find_map(|t| t.cover.as_ref())
+            .map(MediaUrl::local)
+    }
+}
+
+/// 文件路径 → 歌曲 id。
+///
+/// # Params:
+///   - `path`: 音频文件绝对路径
+///
+/// # Return:
+///   `local` namespace 下以路径为值的 [`SongId`]。
+pub(crate) fn song_id(path: &Path) -> SongId {
+    SongId::new(SourceKind::LOCAL, path.to_string_lossy())
+}
+
+/// 专辑 id 的值:`<专辑艺人>/<专辑名>`,艺人缺失时只有专辑名。
+fn album_key(owner: Option<&str>, album: &str) -> String {
+    match owner {
+        Some(o) => format!("{o}/{album}"),
+        None => album.to_owned(),
+    }
+}
+
+/// 艺名 → 艺人引用。
+fn artist_ref(name: &str) -> ArtistRef {
+    ArtistRef {
+        id: ArtistId::new(SourceKind::LOCAL, name),
+        name: name.to_owned(),
+    }
+}
+
+/// 曲目 → 歌曲投影。
+fn to_song(t: &Track) -> Song {
+    let album = t.album.as_ref().map(|name| AlbumRef {
+        id: AlbumId::new(SourceKind::LOCAL, album_key(t.album_owner(), name)),
+        name: name.clone(),
+    });
+    Song::builder()
+        .id(song_id(&t.path))
+        .name(t.title.clone())
+        .artists(t.artists.iter().map(|a| artist_ref(a)).collect())
+        .album(album)
+        .album_artist(t.album_artist.clone())
+        .year(t.year)
+        .track_no(t.track)
+        .duration_ms(t.duration_ms)
+        .cover_url(t.cover.as_ref().map(MediaUrl::local))
+        .source_url(Some(MediaUrl::local(&t.path)))
+        .build()
+}
+
+/// 查询串 → 小写词表(按空白切)。
+fn terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(str::to_lowercase).collect()
+}
+
+/// 全部词都出现在 `hay` 里(大小写不敏感);空词表不命中任何东西。
+fn matches(terms: &[String], hay: &str) -> bool {
+    if terms.is_empty() {
+        return false;
+    }
+    let hay = hay.to_lowercase();
+    terms.iter().all(|t| hay.contains(t.as_str()))
+}
+
+/// 对全量命中做内存分页;`has_more` 如实给出(本地全量已知,不靠条数推断)。
+fn paginate<T>(all: Vec<T>, page: Page) -> SearchHits<T> {
+    let offset = usize::try_from(page.offset).unwrap_or(usize::MAX);
+    let limit = usize::try_from(page.limit).unwrap_or(usize::MAX);
+    let total = all.len();
+    let items = all.into_iter().skip(offset).take(limit).collect::<Vec<T>>();
+    let has_more = offset.saturating_add(limit) < total;
+    SearchHits::new(items, has_more)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use mineral_channel_core::Page;
+    use mineral_model::{AlbumId, ArtistId, PlaylistId, SourceKind};
+
+    use super::{Library, song_id};
+    use crate::m3u::M3u;
+    use crate::tags::Track;
+
+    /// 构造一条测试曲目。
+    fn track(path: &str, title: &str, artist: &str, album: &str, no: u32) -> Track {
+        Track {
+            path: PathBuf::from(path),
+            title: title.to_owned(),
+            artists: vec![artist.to_owned()],
+            album: Some(album.to_owned()),
+            album_artist: None,
+            year: None,
+            disc: None,
+            track: Some(no),
+            duration_ms: Some(1_000),
+            format: None,
+            bitrate_kbps: None,
+            bit_depth: None,
+            sample_rate: None,
+            cover: None,
+        }
+    }
+
+    /// 固定的小曲库:两张专辑在根的两个子目录,一首散曲在根下。
+    fn fixture(m3us: Vec<(PathBuf, M3u)>) -> Library {
+        let tracks = vec![
+            track("/m/Shore/02.flac", "Tide", "Ada", "Shore", 2),
+            track("/m/Shore/01.flac", "Palisade", "Ada", "Shore", 1),
+            track("/m/Night/01.mp3", "Lantern", "Bo", "Night", 1),
+            track("/m/loose.mp3", "Loose", "Ada", "Singles", 1),
+        ];
+        Library::build(tracks, &[PathBuf::from("/m")], m3us, |p: &Path| {
+            track(&p.to_string_lossy(), "extra", "Cy", "X", 1)
+        })
+    }
+
+    /// 专辑按曲序排、艺人专辑覆盖署名曲、文件夹歌单只取根的直接子目录。
+    #[test]
+    fn derives_albums_artists_and_folders() -> color_eyre::Result<()> {
+        let lib = fixture(Vec::new());
+        let album = lib
+            .album(&AlbumId::new(SourceKind::LOCAL, "Ada/Shore"))
+            .ok_or_else(|| color_eyre::eyre::eyre!("缺专辑"))?;
+        let names = album
+            .songs
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Palisade", "Tide"], "按曲序而非路径");
+
+        let ada = ArtistId::new(SourceKind::LOCAL, "Ada");
+        let albums = lib.artist_albums(
+            &ada,
+            Page {
+                offset: 0,
+                limit: 10,
+            },
+        );
+        assert_eq!(albums.len(), 2, "Shore + Singles");
+
+        let lists = lib.playlists();
+        let names = lists.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["Night", "Shore"], "根下散曲不成歌单");
+        assert!(lists.iter().all(|p| p.songs.is_empty()), "列表不带曲目");
+        Ok(())
+    }
+
+    /// 搜索:多词全部命中才算;分页如实给 has_more。
+    #[test]
+    fn search_terms_and_paging() {
+        let lib = fixture(Vec::new());
+        let hits = lib.search_songs(
+            "ada shore",
+            Page {
+                offset: 0,
+                limit: 1,
+            },
+        );
+        assert_eq!(hits.items.len(), 1);
+        assert_eq!(hits.has_more, Some(true));
+        let hits = lib.search_songs(
+            "ADA shore",
+            Page {
+                offset: 1,
+                limit: 5,
+            },
+        );
+        assert_eq!(hits.items.len(), 1);
+        assert_eq!(hits.has_more, Some(false));
+        assert!(
+            lib.search_songs(
+                "  ",
+                Page {
+                    offset: 0,
+                    limit: 5
+                }
+            )
+            .items
+            .is_empty()
+        );
+    }
+
+    /// m3u 歌单保序;根外条目补读入库,不存在的条目略过。
+    #[test]
+    fn m3u_playlist_keeps_order_and_reads_outside_entries() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let outside = dir.path().join("out.mp3");
+        std::fs::write(&outside, b"")?;
+        let m3u = M3u {
+            name: "mix".to_owned(),
+            entries: vec![
+                PathBuf::from("/m/Night/01.mp3"),
+                outside.clone(),
+                PathBuf::from("/m/gone.mp3"),
+                PathBuf::from("/m/Shore/01.flac"),
+            ],
+        };
+        let path = PathBuf::from("/m/mix.m3u");
+        let lib = fixture(vec![(path.clone(), m3u)]);
+        assert_eq!(lib.len(), 5, "根外条目入库");
+        let pl = lib
+            .playlist(&PlaylistId::new(
+                SourceKind::LOCAL,
+                format!("m3u:{}", path.display()),
+            ))
+            .ok_or_else(|| color_eyre::eyre::eyre!("缺 m3u 歌单"))?;
+        let ids = pl.songs.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                song_id(Path::new("/m/Night/01.mp3")),
+                song_id(&outside),
+                song_id(Path::new("/m/Shore/01.flac")),
+            ]
+        );
+        Ok(())
+    }
+}
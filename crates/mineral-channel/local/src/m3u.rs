@@ -0,0 +1,105 @@
+//! m3u / m3u8 歌单文件解析。
+//!
+//! 只认本地路径条目:`#` 开头的行(含 `#EXTM3U` / `#EXTINF`)跳过,`#PLAYLIST:` 取作
+//! 歌单名;`http(s)://` 等远端条目忽略;`file://` 去前缀;相对路径按 m3u 所在目录解析。
+
+use std::path::{Path, PathBuf};
+
+/// 一份解析后的 m3u 歌单。
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct M3u {
+    /// 歌单名:`#PLAYLIST:` 指令优先,缺失回落文件名(去扩展名)。
+    pub(crate) name: String,
+
+    /// 条目路径(绝对路径,保持文件内顺序;不校验存在性)。
+    pub(crate) entries: Vec<PathBuf>,
+}
+
+/// 读并解析一个 m3u 文件。
+///
+/// # Params:
+///   - `path`: m3u 文件路径
+///
+/// # Return:
+///   解析结果;文件读不了为 `Err`(调用方 warn 后跳过该歌单)。
+pub(crate) fn read(path: &Path) -> std::io::Result<M3u> {
+    let bytes = std::fs::read(path)?;
+    Ok(parse(path, &String::from_utf8_lossy(&bytes)))
+}
+
+/// 解析 m3u 文本。
+///
+/// # Params:
+///   - `path`: m3u 文件路径(取默认名与相对路径基准)
+///   - `text`: 文件内容
+///
+/// # Return:
+///   解析结果。
+fn parse(path: &Path, text: &str) -> M3u {
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut name = None;
+    let mut entries = Vec::new();
+    for line in text.lines() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('#') {
+            if let Some(title) = rest.strip_prefix("PLAYLIST:") {
+                let title = title.trim();
+                if !title.is_empty() {
+                    name = Some(title.to_owned());
+                }
+            }
+            continue;
+        }
+        let raw = match line.strip_prefix("file://") {
+            Some(p) => p,
+            None if line.contains("://") => continue,
+            None => line,
+        };
+        let entry = Path::new(raw);
+        entries.push(if entry.is_absolute() {
+            entry.to_path_buf()
+        } else {
+            base.join(entry)
+        });
+    }
+    let name = name.unwrap_or_else(|| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    M3u { name, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse;
+
+    /// 指令行跳过、`#PLAYLIST:` 取名、远端条目忽略、`file://` 去前缀、相对路径按 m3u 目录解析。
+    #[test]
+    fn parses_paths_and_name() {
+        let text = "\u{feff}#EXTM3U\n#PLAYLIST: 深夜\n#EXTINF:123,Ada - Palisade\n\
+                    a/01.flac\n\nhttps://example.com/x.mp3\nfile:///music/b.mp3\n/abs/c.ogg\n";
+        let m = parse(Path::new("/lists/night.m3u8"), text);
+        assert_eq!(m.name, "深夜");
+        assert_eq!(
+            m.entries,
+            vec![
+                Path::new("/lists/a/01.flac").to_path_buf(),
+                Path::new("/music/b.mp3").to_path_buf(),
+                Path::new("/abs/c.ogg").to_path_buf(),
+            ]
+        );
+    }
+
+    /// 无 `#PLAYLIST:` 时歌单名回落文件名。
+    #[test]
+    fn name_falls_back_to_file_stem() {
+        let m = parse(Path::new("/lists/road trip.m3u"), "x.mp3\n");
+        assert_eq!(m.name, "road trip");
+    }
+}
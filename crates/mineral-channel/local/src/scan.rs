@@ -0,0 +1,170 @@
+//! 扫描根遍历:收集音频文件与 m3u 歌单文件。
+//!
+//! 纯文件系统遍历,不读标签(标签见 [`crate::tags`])。隐藏目录(`.` 开头)跳过;
+//! 目录符号链接**不跟随**(防环),文件符号链接照常收。
+
+use std::path::{Path, PathBuf};
+
+/// 一次遍历的产出。两个列表都按路径排序,保证索引顺序稳定(同一目录树两次扫描同序)。
+#[derive(Debug, Default)]
+pub(crate) struct ScanOutput {
+    /// 音频文件绝对路径。
+    pub(crate) audio: Vec<PathBuf>,
+
+    /// `.m3u` / `.m3u8` 歌单文件绝对路径。
+    pub(crate) m3u: Vec<PathBuf>,
+}
+
+/// 遍历全部扫描根。
+///
+/// 根不存在 / 不可读只 warn 跳过;子目录读失败同样跳过该子树,不中断整次扫描。
+///
+/// # Params:
+///   - `roots`: 扫描根
+///
+/// # Return:
+///   全部根下的音频与 m3u 文件(各自按路径排序、去重)。
+pub(crate) fn walk(roots: &[PathBuf]) -> ScanOutput {
+    let mut out = ScanOutput::default();
+    for root in roots {
+        if !root.is_dir() {
+            mineral_log::warn!(target: "local", root = %root.display(), "扫描根不存在或不是目录,跳过");
+            continue;
+        }
+        walk_dir(root, &mut out);
+    }
+    out.audio.sort();
+    out.audio.dedup();
+    out.m3u.sort();
+    out.m3u.dedup();
+    out
+}
+
+/// 深度优先遍历单个目录(显式栈,不递归,深目录树不爆栈)。
+///
+/// # Params:
+///   - `root`: 起点目录
+///   - `out`: 累积产出
+fn walk_dir(root: &Path, out: &mut ScanOutput) {
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                mineral_log::warn!(target: "local", dir = %dir.display(), error = mineral_log::chain(&e), "读目录失败,跳过该子树");
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(ft) = entry.file_type() else {
+                continue;
+            };
+            if ft.is_dir() {
+                if !is_hidden(&path) {
+                    stack.push(path);
+                }
+                continue;
+            }
+            // 文件或指向文件的符号链接;指向目录的链接 is_file() 为 false,自然跳过。
+            if !path.is_file() {
+                continue;
+            }
+            match classify(&path) {
+                Some(FileKind::Audio) => out.audio.push(path),
+                Some(FileKind::M3u) => out.m3u.push(path),
+                None => {}
+            }
+        }
+    }
+}
+
+/// 扫描关心的文件种类。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileKind {
+    /// 音频文件。
+    Audio,
+
+    /// m3u 歌单。
+    M3u,
+}
+
+/// 按扩展名(大小写不敏感)归类;不关心的文件返回 `None`。
+///
+/// 扩展名只用来**筛候选**:真实容器类型由 lofty 按内容判(见 [`crate::tags`]),
+/// 改了扩展名的文件读标签失败时仍以文件名入库。
+///
+/// # Params:
+///   - `path`: 文件路径
+///
+/// # Return:
+///   文件种类;非音频 / 非 m3u 为 `None`。
+fn classify(path: &Path) -> Option<FileKind> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "mp3" | "flac" | "aac" | "m4a" | "ogg" | "oga" | "opus" | "wav" | "ape" | "alac" | "wv"
+        | "aiff" | "aif" => Some(FileKind::Audio),
+        "m3u" | "m3u8" => Some(FileKind::M3u),
+        _ => None,
+    }
+}
+
+/// 是否隐藏目录(名字以 `.` 开头)。
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
+
+/// 路径是否音频文件(按扩展名),供 m3u 条目等零散路径复用同一判据。
+///
+/// # Params:
+///   - `path`: 文件路径
+///
+/// # Return:
+///   扩展名是已知音频格式返回 `true`。
+pub(crate) fn is_audio(path: &Path) -> bool {
+    classify(path) == Some(FileKind::Audio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::walk;
+
+    /// 递归收音频与 m3u、忽略其他文件与隐藏目录;结果按路径排序。
+    #[test]
+    fn walk_collects_audio_and_m3u() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Album B"))?;
+        std::fs::create_dir_all(root.join("Album A/CD1"))?;
+        std::fs::create_dir_all(root.join(".trash"))?;
+        std::fs::write(root.join("Album B/01.flac"), b"")?;
+        std::fs::write(root.join("Album A/CD1/02.MP3"), b"")?;
+        std::fs::write(root.join("Album A/cover.jpg"), b"")?;
+        std::fs::write(root.join(".trash/gone.mp3"), b"")?;
+        std::fs::write(root.join("mix.m3u8"), b"")?;
+
+        let out = walk(&[root.to_path_buf()]);
+        assert_eq!(
+            out.audio,
+            vec![
+                root.join("Album A/CD1/02.MP3"),
+                root.join("Album B/01.flac")
+            ],
+            "大小写不敏感扩展名、隐藏目录跳过、按路径排序"
+        );
+        assert_eq!(out.m3u, vec![root.join("mix.m3u8")]);
+        Ok(())
+    }
+
+    /// 不存在的根跳过,不影响其他根。
+    #[test]
+    fn missing_root_is_skipped() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.ogg"), b"")?;
+        let out = walk(&[dir.path().join("nope"), dir.path().to_path_buf()]);
+        assert_eq!(out.audio, vec![dir.path().join("a.ogg")]);
+        Ok(())
+    }
+}
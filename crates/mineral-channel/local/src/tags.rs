@@ -0,0 +1,385 @@
+//! 单个音频文件的标签读取(lofty):标题 / 艺人 / 专辑 / 年份 / 曲序 / 时长 / 音频属性 / 封面。
+//!
+//! 读取**永不失败**:标签缺失或文件解析不了时退回文件名当标题、其余留空——本地目录里
+//! 总有没打标签的文件,它们也该出现在曲库里、能播。
+
+use std::path::{Path, PathBuf};
+
+use lofty::file::{AudioFile, FileType, TaggedFile, TaggedFileExt};
+use lofty::picture::{Picture, PictureType};
+use lofty::prelude::{Accessor, ItemKey};
+use lofty::tag::Tag;
+use md5::{Digest, Md5};
+use mineral_model::{AudioFormat, BitRate};
+
+/// 同目录封面图的候选文件名(小写比较),按优先级排列。
+const FOLDER_COVER_NAMES: [&str; 6] = [
+    "cover.jpg",
+    "cover.png",
+    "folder.jpg",
+    "folder.png",
+    "front.jpg",
+    "front.png",
+];
+
+/// 一个音频文件读出的全部元信息(索引的原子单位)。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Track {
+    /// 文件绝对路径(身份)。
+    pub(crate) path: PathBuf,
+
+    /// 标题;标签缺失时为文件名(去扩展名)。
+    pub(crate) title: String,
+
+    /// 艺人,主艺人在前;可能为空。
+    pub(crate) artists: Vec<String>,
+
+    /// 专辑名。
+    pub(crate) album: Option<String>,
+
+    /// 专辑艺人(`ALBUMARTIST`);缺失时专辑归属回落主艺人。
+    pub(crate) album_artist: Option<String>,
+
+    /// 发行年(`YEAR` / `DATE` 取年份)。
+    pub(crate) year: Option<u32>,
+
+    /// 碟号。
+    pub(crate) disc: Option<u32>,
+
+    /// 曲序。
+    pub(crate) track: Option<u32>,
+
+    /// 时长(ms);解析不了为 `None`。
+    pub(crate) duration_ms: Option<u64>,
+
+    /// 容器格式(按内容判)。
+    pub(crate) format: Option<AudioFormat>,
+
+    /// 音频码率(kbps)。
+    pub(crate) bitrate_kbps: Option<u32>,
+
+    /// 位深(仅无损容器有值)。
+    pub(crate) bit_depth: Option<u8>,
+
+    /// 采样率(Hz)。
+    pub(crate) sample_rate: Option<u32>,
+
+    /// 封面图文件(内嵌封面抽出的文件,或同目录的 `cover.jpg` 等)。
+    pub(crate) cover: Option<PathBuf>,
+}
+
+impl Track {
+    /// 只有文件名可用时的兜底:标题 = 文件名去扩展名,其余留空。
+    ///
+    /// # Params:
+    ///   - `path`: 文件路径
+    ///
+    /// # Return:
+    ///   仅含标题的 `Track`。
+    fn bare(path: &Path) -> Self {
+        let title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            path: path.to_path_buf(),
+            title,
+            artists: Vec::new(),
+            album: None,
+            album_artist: None,
+            year: None,
+            disc: None,
+            track: None,
+            duration_ms: None,
+            format: None,
+            bitrate_kbps: None,
+            bit_depth: None,
+            sample_rate: None,
+            cover: None,
+        }
+    }
+
+    /// 专辑归属艺人:`ALBUMARTIST` 优先,缺失回落主艺人。
+    pub(crate) fn album_owner(&self) -> Option<&str> {
+        self.album_artist
+            .as_deref()
+            .or_else(|| self.artists.first().map(String::as_str))
+    }
+
+    /// 按文件实际属性归一化的音质档。
+    ///
+    /// 本地文件只有一份,没有「按请求音质取档」的余地:无损容器按位深 / 采样率分
+    /// Lossless / Hires,有损按码率就近落档;属性全缺时给 Standard(保守,不虚标)。
+    ///
+    /// # Return:
+    ///   该文件的音质档。
+    pub(crate) fn quality(&self) -> BitRate {
+        if self.format.as_ref().is_some_and(AudioFormat::is_lossless) {
+            let hi_depth = self.bit_depth.is_some_and(|d| d > 16);
+            let hi_rate = self.sample_rate.is_some_and(|r| r > 48_000);
+            return if hi_depth || hi_rate {
+                BitRate::Hires
+            } else {
+                BitRate::Lossless
+            };
+        }
+        match self.bitrate_kbps {
+            Some(k) if k >= 300 => BitRate::Exhigh,
+            Some(k) if k >= 180 => BitRate::Higher,
+            _ => BitRate::Standard,
+        }
+    }
+}
+
+/// 读一个音频文件的元信息。
+///
+/// # Params:
+///   - `path`: 音频文件绝对路径
+///   - `cover_dir`: 内嵌封面抽取目录;`None` = 不抽内嵌封面
+///
+/// # Return:
+///   读出的 `Track`;解析失败时为只含文件名标题的兜底值(见模块文档)。
+pub(crate) fn read_track(path: &Path, cover_dir: Option<&Path>) -> Track {
+    let mut track = match lofty::read_from_path(path) {
+        Ok(tagged) => from_tagged(path, &tagged, cover_dir),
+        Err(e) => {
+            mineral_log::debug!(target: "local", path = %path.display(), error = mineral_log::chain(&e), "读标签失败,按文件名入库");
+            Track::bare(path)
+        }
+    };
+    if track.cover.is_none() {
+        track.cover = folder_cover(path);
+    }
+    track
+}
+
+/// 已解析文件 → `Track`。
+///
+/// # Params:
+///   - `path`: 文件路径
+///   - `tagged`: lofty 解析结果
+///   - `cover_dir`: 内嵌封面抽取目录
+///
+/// # Return:
+///   填好标签与属性的 `Track`。
+fn from_tagged(path: &Path, tagged: &TaggedFile, cover_dir: Option<&Path>) -> Track {
+    let mut track = Track::bare(path);
+    let props = tagged.properties();
+    track.duration_ms = u64::try_from(props.duration().as_millis())
+        .ok()
+        .filter(|ms| *ms > 0);
+    track.bitrate_kbps = props.audio_bitrate();
+    track.bit_depth = props.bit_depth();
+    track.sample_rate = props.sample_rate();
+    track.format = file_type_to_format(tagged.file_type());
+
+    let Some(tag) = tagged.primary_tag().or_else(|| tagged.first_tag()) else {
+        return track;
+    };
+    if let Some(title) = non_empty(tag.title().as_deref()) {
+        track.title = title;
+    }
+    track.artists = artists_of(tag);
+    track.album = non_empty(tag.album().as_deref());
+    track.album_artist = non_empty(tag.get_string(&ItemKey::AlbumArtist));
+    track.year = tag.year().filter(|y| *y > 0);
+    track.disc = tag.disk();
+    track.track = tag.track();
+    track.cover = cover_dir.and_then(|dir| extract_cover(tag, dir));
+    track
+}
+
+/// 取艺人列表:多值标签(多条 `ARTIST`)逐条取;单值按 `;` 拆(常见的多艺人写法)。
+///
+/// 不按 `/` 拆——`AC/DC` 之类艺名本身带斜杠,拆了会伤名字。
+///
+/// # Params:
+///   - `tag`: 标签
+///
+/// # Return:
+///   去空白、去空项后的艺人列表。
+fn artists_of(tag: &Tag) -> Vec<String> {
+    let raw = tag
+        .get_strings(&ItemKey::TrackArtist)
+        .map(str::to_owned)
+        .collect::<Vec<String>>();
+    let split = if raw.len() == 1 {
+        raw.iter()
+            .flat_map(|s| s.split(';'))
+            .map(str::to_owned)
+            .collect::<Vec<String>>()
+    } else {
+        raw
+    };
+    split.iter().filter_map(|s| non_empty(Some(s))).collect()
+}
+
+/// 去首尾空白;空串视为缺失。
+fn non_empty(s: Option<&str>) -> Option<String> {
+    s.map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+}
+
+/// 把内嵌封面(优先 front cover)按内容 md5 命名写进抽取目录。
+///
+/// 同专辑多首共享一张图时文件名相同,只写一次;已存在直接复用。写失败只 debug 记录、
+/// 返回 `None`(回落同目录图片)。
+///
+/// # Params:
+///   - `tag`: 标签
+///   - `dir`: 抽取目录
+///
+/// # Return:
+///   抽出的封面文件路径;无内嵌封面或写失败为 `None`。
+fn extract_cover(tag: &Tag, dir: &Path) -> Option<PathBuf> {
+    let pic = tag
+        .get_picture_type(PictureType::CoverFront)
+        .or_else(|| tag.pictures().first())?;
+    let path = dir.join(cover_file_name(pic));
+    if path.is_file() {
+        return Some(path);
+    }
+    let written = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&path, pic.data()));
+    match written {
+        Ok(()) => Some(path),
+        Err(e) => {
+            mineral_log::debug!(target: "local", path = %path.display(), error = mineral_log::chain(&e), "写内嵌封面失败");
+            None
+        }
+    }
+}
+
+/// 封面文件名:`<内容 md5>.<扩展名>`(扩展名取 MIME,未知落 `img`)。
+fn cover_file_name(pic: &Picture) -> String {
+    let digest = hex::encode(Md5::digest(pic.data()));
+    let ext = pic.mime_type().and_then(|m| m.ext()).unwrap_or("img");
+    format!("{digest}.{ext}")
+}
+
+/// 同目录的封面图片(`cover.jpg` / `folder.png` 等,文件名大小写不敏感)。
+///
+/// # Params:
+///   - `audio`: 音频文件路径
+///
+/// # Return:
+///   按 [`FOLDER_COVER_NAMES`] 优先级命中的第一张图;没有为 `None`。
+fn folder_cover(audio: &Path) -> Option<PathBuf> {
+    let dir = audio.parent()?;
+    let files = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect::<Vec<PathBuf>>();
+    FOLDER_COVER_NAMES.iter().find_map(|want| {
+        files.iter().find_map(|p| {
+            let name = p.file_name()?.to_str()?.to_ascii_lowercase();
+            (name == *want).then(|| p.clone())
+        })
+    })
+}
+
+/// lofty 容器类型 → model 的 [`AudioFormat`];未覆盖类型为 `None`。
+fn file_type_to_format(ft: FileType) -> Option<AudioFormat> {
+    match ft {
+        FileType::Mpeg => Some(AudioFormat::Mp3),
+        FileType::Flac => Some(AudioFormat::Flac),
+        FileType::Mp4 | FileType::Aac => Some(AudioFormat::Aac),
+        FileType::Vorbis => Some(AudioFormat::Ogg),
+        FileType::Wav => Some(AudioFormat::Wav),
+        FileType::Ape => Some(AudioFormat::Ape),
+        FileType::Opus => Some(AudioFormat::Other("opus".to_owned())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::config::WriteOptions;
+    use lofty::picture::{MimeType, Picture, PictureType};
+    use lofty::prelude::{Accessor, ItemKey, TagExt};
+    use lofty::tag::{Tag, TagType};
+    use mineral_model::{AudioFormat, BitRate};
+
+    use super::read_track;
+
+    /// 写一个 0.5s 静音 WAV 并挂上给定标签。
+    fn tagged_wav(path: &std::path::Path, tag: &Tag) -> color_eyre::Result<()> {
+        mineral_test::write_wav(path, &[0_i16; 44_100], 2, 44_100)?;
+        tag.save_to_path(path, WriteOptions::default())?;
+        Ok(())
+    }
+
+    /// 标签齐全:标题 / 多艺人(`;` 拆分)/ 专辑 / 专辑艺人 / 年份 / 曲序 / 时长 / 格式都读出。
+    #[test]
+    fn reads_full_tags() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("a.wav");
+        let mut tag = Tag::new(TagType::Id3v2);
+        tag.set_title("Palisade".to_owned());
+        tag.set_artist("Ada; Bo".to_owned());
+        tag.set_album("Shore".to_owned());
+        tag.insert_text(ItemKey::AlbumArtist, "Various".to_owned());
+        tag.set_track(3);
+        tag.set_year(2003);
+        tagged_wav(&path, &tag)?;
+
+        let t = read_track(&path, None);
+        assert_eq!(t.title, "Palisade");
+        assert_eq!(t.artists, vec!["Ada".to_owned(), "Bo".to_owned()]);
+        assert_eq!(t.album.as_deref(), Some("Shore"));
+        assert_eq!(t.album_owner(), Some("Various"), "ALBUMARTIST 优先于主艺人");
+        assert_eq!(t.track, Some(3));
+        assert_eq!(t.year, Some(2003));
+        assert_eq!(t.duration_ms, Some(500));
+        assert_eq!(t.format, Some(AudioFormat::Wav));
+        assert_eq!(t.quality(), BitRate::Lossless, "16bit/44.1k 的无损");
+        Ok(())
+    }
+
+    /// 解析不了的文件(假 mp3)按文件名入库,不报错。
+    #[test]
+    fn unreadable_falls_back_to_file_stem() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("07 - 夜間飛行.mp3");
+        std::fs::write(&path, b"not audio")?;
+        let t = read_track(&path, None);
+        assert_eq!(t.title, "07 - 夜間飛行");
+        assert!(t.artists.is_empty() && t.duration_ms.is_none());
+        assert_eq!(t.quality(), BitRate::Standard, "属性全缺不虚标");
+        Ok(())
+    }
+
+    /// 内嵌封面按内容 md5 抽到 cover_dir;无内嵌封面时回落同目录 `Cover.JPG`。
+    #[test]
+    fn cover_embedded_then_folder() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let covers = dir.path().join("covers");
+        let embedded = dir.path().join("e.wav");
+        let mut tag = Tag::new(TagType::Id3v2);
+        tag.set_title("e".to_owned());
+        tag.push_picture(Picture::new_unchecked(
+            PictureType::CoverFront,
+            Some(MimeType::Png),
+            None,
+            b"png-bytes".to_vec(),
+        ));
+        tagged_wav(&embedded, &tag)?;
+        let t = read_track(&embedded, Some(&covers));
+        let cover = t
+            .cover
+            .ok_or_else(|| color_eyre::eyre::eyre!("应抽出内嵌封面"))?;
+        assert_eq!(cover.parent(), Some(covers.as_path()));
+        assert_eq!(std::fs::read(&cover)?, b"png-bytes");
+
+        let album = dir.path().join("album");
+        std::fs::create_dir_all(&album)?;
+        let plain = album.join("p.wav");
+        mineral_test::write_wav(&plain, &[0_i16; 100], 1, 8_000)?;
+        std::fs::write(album.join("Cover.JPG"), b"jpg")?;
+        let t = read_track(&plain, Some(&covers));
+        assert_eq!(t.cover, Some(album.join("Cover.JPG")), "大小写不敏感命中");
+        Ok(())
+    }
+}
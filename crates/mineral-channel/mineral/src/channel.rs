@@ -1,4 +1,4 @@
-//! 聚合 channel 实现:数据全部来自 persist,无网络后端。
+//! 聚合 channel 实现:数据全部来自 persist / stats,无网络后端。
 
 use async_trait::async_trait;
 use mineral_channel_core::{
@@ -6,8 +6,16 @@ use mineral_channel_core::{
 };
 use mineral_model::{BitRate, PlayUrl, Playlist, PlaylistId, Song, SongId, SourceKind};
 use mineral_persist::ServerStore;
+use mineral_stats::{ReportOptions, StatsStore};
+use rustc_hash::{FxHashMap, FxHashSet};
 
-/// `mineral:favorites` 歌单 id——本 channel 唯一一张 synthetic 歌单。
+use crate::mix::{self, api_error, mix_key};
+use crate::smart::{Facts, SmartPlaylistDef, SmartQuery};
+
+/// 智能歌单 id 裸值的前缀(`mineral:smart:<key>`)。
+const SMART_PREFIX: &str = "smart:";
+
+/// `mineral:favorites` 歌单 id——聚合收藏 synthetic 歌单。
 ///
 /// # Return:
 ///   聚合收藏歌单的 [`PlaylistId`]。
@@ -15,19 +23,57 @@ pub fn favorites_playlist_id() -> PlaylistId {
     PlaylistId::new(SourceKind::MINERAL, "favorites")
 }
 
+/// 智能歌单 id:`mineral:smart:<key>`。
+///
+/// config 声明的歌单 key 即其名字;用户自建的 key 为建单时的名字(改名不变)。
+///
+/// # Params:
+///   - `key`: 稳定键
+///
+/// # Return:
+///   智能歌单的 [`PlaylistId`]。
+pub fn smart_playlist_id(key: &str) -> PlaylistId {
+    PlaylistId::new(SourceKind::MINERAL, format!("{SMART_PREFIX}{key}"))
+}
+
+/// 一张智能歌单的定义(config 声明或 persist 自建,二者合流后的形态)。
+struct SmartEntry {
+    /// 稳定键(id 裸值段)。
+    key: String,
+
+    /// 展示名。
+    name: String,
+
+    /// 表达式原文(空串 = 尚未设置)。
+    query: String,
+}
+
 /// 跨源聚合 channel:source 为 [`SourceKind::MINERAL`],把 persist 的全源收藏
-/// 投影成一张 `Favorites` 歌单。
+/// 投影成一张 `Favorites` 歌单,外加用户的混源歌单与若干按表达式对 persist / stats
+/// 现算的智能歌单。
 ///
 /// 搜索 / 详情 / 取流一律 `NotSupported`——歌单里每首歌的 id 保留**原源** namespace,
 /// 播放与详情由调度层按 id 路由回真实 channel,本 channel 不会收到这些调用。
-/// 收藏的写入也不经它(favorites 编排直写 persist),它是纯只读投影。
+/// 收藏的写入也不经它(favorites 编排直写 persist)。歌单写操作按 id 分流:
+/// - 混源歌单(`mix:`):建单 / 删 / 改名 / 改描述 / 加歌 / 删歌 / 挪动,歌可来自任意源;
+/// - 自建智能歌单(`smart:`):专门的建单入口(名字撞已有歌单报 409);描述即表达式,
+///   改描述只认已存在的;改名 / 删除;config 声明的只读,曲目不可直接增删。
 pub struct MineralChannel {
-    /// persist 句柄(loved + meta 的事实来源)。
+    /// persist 句柄(loved + meta 的事实来源,兼存混源歌单与自建智能歌单定义)。
     store: ServerStore,
+
+    /// stats 句柄(智能歌单的播放 / 跳过 / 下载事实来源)。
+    stats: StatsStore,
+
+    /// stats 查询口径(有效播放阈值)。
+    options: ReportOptions,
+
+    /// config 声明的智能歌单(只读,排在自建之前)。
+    smart: Vec<SmartPlaylistDef>,
 }
 
 impl MineralChannel {
-    /// 新建聚合 channel。
+    /// 新建聚合 channel(无 stats、无 config 智能歌单;用 [`Self::with_smart`] 接上)。
     ///
     /// # Params:
     ///   - `store`: server 拥有的 persist 句柄
@@ -35,7 +81,37 @@ impl MineralChannel {
     /// # Return:
     ///   聚合 channel 实例。
     pub fn new(store: ServerStore) -> Self {
-        Self { store }
+        Self {
+            store,
+            stats: StatsStore::disabled(),
+            options: ReportOptions::builder()
+                .min_listen_ms(0)
+                .top_limit(0)
+                .build(),
+            smart: Vec::new(),
+        }
+    }
+
+    /// 接上智能歌单的事实来源与 config 声明。
+    ///
+    /// # Params:
+    ///   - `stats`: server 共享的 stats 句柄(单连接池,须与埋点写入共用同一个)
+    ///   - `options`: stats 查询口径(同 `stats report`)
+    ///   - `smart`: config 声明的智能歌单
+    ///
+    /// # Return:
+    ///   接好智能歌单的 channel。
+    #[must_use]
+    pub fn with_smart(
+        mut self,
+        stats: StatsStore,
+        options: ReportOptions,
+        smart: Vec<SmartPlaylistDef>,
+    ) -> Self {
+        self.stats = stats;
+        self.options = options;
+        self.smart = smart;
+        self
     }
 
     /// 从 persist 重建聚合收藏歌单(name `Favorites`,曲目按收藏时间降序)。
@@ -68,6 +144,198 @@ impl MineralChannel {
             .songs(songs)
             .build())
     }
+
+    /// 全部智能歌单定义:config 声明在前,persist 自建在后(与 config 同键 / 同名的自建被遮蔽)。
+    ///
+    /// # Return:
+    ///   合流后的定义列表。
+    async fn smart_entries(&self) -> Result<Vec<SmartEntry>> {
+        let mut entries = self
+            .smart
+            .iter()
+            .map(|d| SmartEntry {
+                key: d.name.clone(),
+                name: d.name.clone(),
+                query: d.query.clone(),
+            })
+            .collect::<Vec<SmartEntry>>();
+        let rows = self
+            .store
+            .smart_playlists()
+            .list()
+            .await
+            .map_err(Error::Other)?;
+        for row in rows {
+            if self.is_config(&row.key) || self.is_config(&row.name) {
+                continue;
+            }
+            entries.push(SmartEntry {
+                key: row.key,
+                name: row.name,
+                query: row.query,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// 某键 / 名是否被 config 声明占用。
+    fn is_config(&self, name: &str) -> bool {
+        self.smart.iter().any(|d| d.name == name)
+    }
+
+    /// 解析一张定义的表达式;空串为 `None`(空歌单)。非法表达式记 warn 后同样按空歌单处理
+    /// (自建的写入前已校验,走到这里的只会是 config 手写错)。
+    fn parse_entry(entry: &SmartEntry) -> Option<SmartQuery> {
+        if entry.query.trim().is_empty() {
+            return None;
+        }
+        match SmartQuery::parse(&entry.query) {
+            Ok(q) => Some(q),
+            Err(e) => {
+                mineral_log::warn!(
+                    target: "mineral",
+                    name = %entry.name,
+                    error = mineral_log::chain(&e),
+                    "智能歌单表达式非法,按空歌单处理",
+                );
+                None
+            }
+        }
+    }
+
+    /// 拉一次求值快照:库全集(收藏在前,stats 维表里播过 / 下载过的补在后)+ 各回看窗的逐曲活跃度。
+    ///
+    /// # Params:
+    ///   - `lookbacks`: 要查的回看窗(已去重;`None` = 全部历史)
+    ///
+    /// # Return:
+    ///   库快照。
+    async fn load_facts(&self, lookbacks: &[Option<i64>]) -> Result<Facts> {
+        let mut songs = self.store.loved_songs().await.map_err(Error::Other)?;
+        let loved = songs
+            .iter()
+            .map(|s| s.id.clone())
+            .collect::<FxHashSet<SongId>>();
+        let known = self.stats.known_songs().await.map_err(Error::Other)?;
+        songs.extend(known.into_iter().filter(|s| !loved.contains(&s.id)));
+        let now = now_ms();
+        let mut activity = FxHashMap::default();
+        for lookback in lookbacks {
+            let start = lookback.map_or(0, |ms| now.saturating_sub(ms));
+            let rows = self
+                .stats
+                .song_activity(start..i64::MAX, &self.options)
+                .await
+                .map_err(Error::Other)?;
+            activity.insert(
+                *lookback,
+                rows.into_iter()
+                    .map(|a| (a.song.clone(), a))
+                    .collect::<FxHashMap<_, _>>(),
+            );
+        }
+        Ok(Facts {
+            songs,
+            loved,
+            activity,
+        })
+    }
+
+    /// 对一批定义求值并出歌单(快照只拉一次)。
+    ///
+    /// # Params:
+    ///   - `entries`: 待求值的定义
+    ///   - `with_songs`: `false` 只出计数,`true` 带全曲目
+    ///
+    /// # Return:
+    ///   与 `entries` 同序的歌单。
+    async fn build_smart(
+        &self,
+        entries: Vec<SmartEntry>,
+        with_songs: bool,
+    ) -> Result<Vec<Playlist>> {
+        let queries = entries.iter().map(Self::parse_entry).collect::<Vec<_>>();
+        let mut lookbacks = Vec::new();
+        for lb in queries.iter().flatten().flat_map(SmartQuery::lookbacks) {
+            if !lookbacks.contains(&lb) {
+                lookbacks.push(lb);
+            }
+        }
+        let facts = if lookbacks.is_empty() {
+            Facts::default()
+        } else {
+            self.load_facts(&lookbacks).await?
+        };
+        entries
+            .into_iter()
+            .zip(queries)
+            .map(|(entry, query)| {
+                let songs = query.map(|q| q.evaluate(&facts)).unwrap_or_default();
+                let track_count = u64::try_from(songs.len())
+                    .map_err(|e| Error::Other(color_eyre::Report::new(e)))?;
+                Ok(Playlist::builder()
+                    .id(smart_playlist_id(&entry.key))
+                    .name(entry.name)
+                    .description(entry.query)
+                    .track_count(track_count)
+                    .songs(if with_songs { songs } else { Vec::new() })
+                    .build())
+            })
+            .collect()
+    }
+
+    /// 智能歌单写操作的键校验:须是 `smart:` id 且不被 config 声明占用。
+    ///
+    /// # Params:
+    ///   - `id`: 目标歌单
+    ///
+    /// # Return:
+    ///   稳定键;收藏歌单 / 非智能 id 为 `NotSupported`,空键为 400,config 声明为 403。
+    fn smart_key<'a>(&self, id: &'a PlaylistId) -> Result<&'a str> {
+        let Some(key) = id.value().strip_prefix(SMART_PREFIX) else {
+            return Err(Error::NotSupported);
+        };
+        if key.trim().is_empty() {
+            return Err(api_error(400, "智能歌单名不能为空".to_owned()));
+        }
+        if self.is_config(key) {
+            return Err(api_error(403, format!("config 声明的智能歌单只读: {key}")));
+        }
+        Ok(key)
+    }
+
+    /// 表达式校验:空串放行(空歌单),非法为 400。
+    ///
+    /// # Params:
+    ///   - `query`: 表达式原文(已 trim)
+    fn check_query(query: &str) -> Result<()> {
+        if !query.is_empty() {
+            SmartQuery::parse(query)
+                .map_err(|e| api_error(400, format!("智能歌单表达式非法: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// 写操作前置校验:目标须是已存在的自建智能歌单。
+    ///
+    /// # Params:
+    ///   - `id`: 目标歌单
+    ///
+    /// # Return:
+    ///   自建歌单的稳定键;错误同 [`Self::smart_key`],另加不存在为 404。
+    async fn writable_key<'a>(&self, id: &'a PlaylistId) -> Result<&'a str> {
+        let key = self.smart_key(id)?;
+        let row = self
+            .store
+            .smart_playlists()
+            .get(key)
+            .await
+            .map_err(Error::Other)?;
+        if row.is_none() {
+            return Err(api_error(404, format!("智能歌单不存在: {key}")));
+        }
+        Ok(key)
+    }
 }
 
 #[async_trait]
@@ -79,7 +347,8 @@ impl MusicChannel for MineralChannel {
     fn caps(&self) -> ChannelCaps {
         ChannelCaps::builder()
             .searchable(Vec::new())
-            .playlist_edit(false)
+            // 混源歌单全套写操作;自建智能歌单只编辑定义(描述即表达式),曲目不可直接增删。
+            .playlist_edit(true)
             // 聚合源:artist 详情沿用音乐源形态(热门曲 + 专辑)。
             .artist_sections(ArtistSections::new(vec![
                 ArtistSectionKind::TopSongs,
@@ -101,14 +370,150 @@ impl MusicChannel for MineralChannel {
     }
 
     async fn my_playlists(&self) -> Result<Vec<Playlist>> {
-        Ok(vec![self.build_favorites(/*with_songs*/ false).await?])
+        let mut lists = vec![self.build_favorites(/*with_songs*/ false).await?];
+        lists.extend(mix::list(&self.store.mix_playlists()).await?);
+        let entries = self.smart_entries().await?;
+        lists.extend(self.build_smart(entries, /*with_songs*/ false).await?);
+        Ok(lists)
     }
 
     async fn playlist_detail(&self, id: &PlaylistId) -> Result<Playlist> {
-        if *id != favorites_playlist_id() {
+        if *id == favorites_playlist_id() {
+            return self.build_favorites(/*with_songs*/ true).await;
+        }
+        if let Some(key) = mix_key(id) {
+            return mix::detail(&self.store.mix_playlists(), key).await;
+        }
+        let Some(key) = id.value().strip_prefix(SMART_PREFIX) else {
             return Err(Error::NotSupported);
+        };
+        let entry = self
+            .smart_entries()
+            .await?
+            .into_iter()
+            .find(|e| e.key == key)
+            .ok_or_else(|| api_error(404, format!("智能歌单不存在: {key}")))?;
+        self.build_smart(vec![entry], /*with_songs*/ true)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Other(color_eyre::eyre::eyre!("智能歌单求值结果为空")))
+    }
+
+    async fn create_playlist(&self, name: &str) -> Result<Playlist> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(api_error(400, "歌单名不能为空".to_owned()));
+        }
+        mix::create(&self.store.mix_playlists(), name).await
+    }
+
+    async fn delete_playlist(&self, id: &PlaylistId) -> Result<()> {
+        if let Some(key) = mix_key(id) {
+            return mix::delete(&self.store.mix_playlists(), key).await;
+        }
+        let key = self.writable_key(id).await?;
+        self.store
+            .smart_playlists()
+            .delete(key)
+            .await
+            .map_err(Error::Other)
+    }
+
+    async fn rename_playlist(&self, id: &PlaylistId, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(api_error(400, "歌单名不能为空".to_owned()));
+        }
+        if let Some(key) = mix_key(id) {
+            return mix::rename(&self.store.mix_playlists(), key, name).await;
+        }
+        let key = self.writable_key(id).await?;
+        self.store
+            .smart_playlists()
+            .rename(key, name)
+            .await
+            .map_err(Error::Other)?;
+        Ok(())
+    }
+
+    async fn set_playlist_description(&self, id: &PlaylistId, desc: &str) -> Result<()> {
+        if let Some(key) = mix_key(id) {
+            return mix::set_description(&self.store.mix_playlists(), key, desc.trim()).await;
+        }
+        let key = self.writable_key(id).await?;
+        let query = desc.trim();
+        Self::check_query(query)?;
+        self.store
+            .smart_playlists()
+            .set_query(key, query)
+            .await
+            .map_err(Error::Other)?;
+        Ok(())
+    }
+
+    async fn create_smart_playlist(&self, name: &str, query: &str) -> Result<Playlist> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(api_error(400, "智能歌单名不能为空".to_owned()));
         }
-        self.build_favorites(/*with_songs*/ true).await
+        let query = query.trim();
+        Self::check_query(query)?;
+        // 名字即键:撞上 config 声明、已有的键或改名后的展示名都算重名,不覆盖原定义。
+        let taken = self
+            .smart_entries()
+            .await?
+            .iter()
+            .any(|e| e.key == name || e.name == name);
+        if taken
+            || !self
+                .store
+                .smart_playlists()
+                .create(name)
+                .await
+                .map_err(Error::Other)?
+        {
+            return Err(api_error(409, format!("智能歌单已存在: {name}")));
+        }
+        self.store
+            .smart_playlists()
+            .set_query(name, query)
+            .await
+            .map_err(Error::Other)?;
+        let entry = SmartEntry {
+            key: name.to_owned(),
+            name: name.to_owned(),
+            query: query.to_owned(),
+        };
+        self.build_smart(vec![entry], /*with_songs*/ false)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Other(color_eyre::eyre::eyre!("智能歌单求值结果为空")))
+    }
+
+    async fn playlist_add_songs(&self, id: &PlaylistId, songs: &[SongId]) -> Result<()> {
+        let key = mix_key(id).ok_or(Error::NotSupported)?;
+        mix::add_songs(&self.store.mix_playlists(), key, songs).await
+    }
+
+    async fn playlist_remove_songs(&self, id: &PlaylistId, songs: &[SongId]) -> Result<()> {
+        let key = mix_key(id).ok_or(Error::NotSupported)?;
+        mix::remove_songs(&self.store.mix_playlists(), key, songs).await
+    }
+
+    async fn playlist_move_song(&self, id: &PlaylistId, song: &SongId, to: usize) -> Result<()> {
+        let key = mix_key(id).ok_or(Error::NotSupported)?;
+        mix::move_song(&self.store.mix_playlists(), key, song, to).await
+    }
+}
+
+/// 当前 unix 毫秒(系统时间倒退给 0,不可表示给 `i64::MAX`)。
+fn now_ms() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_millis()).unwrap_or(i64::MAX),
+        Err(_) => 0,
     }
 }
 
@@ -117,9 +522,12 @@ mod tests {
     use mineral_channel_core::{Error, MusicChannel};
     use mineral_model::{SongId, SourceKind};
     use mineral_persist::ServerStore;
+    use mineral_stats::{ReportOptions, StatsStore};
     use mineral_test::{song, with_name};
 
-    use super::{MineralChannel, favorites_playlist_id};
+    use super::{MineralChannel, favorites_playlist_id, smart_playlist_id};
+    use crate::SmartPlaylistDef;
+    use crate::mix_playlist_id;
 
     /// 造一个含两源收藏的 store:netease「Palisade」+ bilibili「夜間飛行」,
     /// 外加一条 loved 但无 meta 的幽灵行(应被跳过)。TempDir 须由调用方持有到测试尾。
@@ -202,4 +610,230 @@ mod tests {
         let ch = MineralChannel::new(ServerStore::disabled());
         assert_eq!(ch.source(), SourceKind::MINERAL);
     }
+
+    /// 查询口径:阈值 0(测试不关心有效播放过滤)。
+    fn options() -> ReportOptions {
+        ReportOptions::builder()
+            .min_listen_ms(0)
+            .top_limit(10)
+            .build()
+    }
+
+    /// 歌单的 (name, track_count) 列表(断言用)。
+    fn summary(lists: &[mineral_model::Playlist]) -> Vec<(&str, u64)> {
+        lists
+            .iter()
+            .map(|p| (p.name.as_str(), p.track_count))
+            .collect()
+    }
+
+    /// config 声明在前、自建在后;建单即落表达式,曲目按表达式现算。
+    #[tokio::test]
+    async fn smart_playlists_listed_and_evaluated() -> color_eyre::Result<()> {
+        let (_dir, store) = store_with_favorites().await?;
+        let ch = MineralChannel::new(store).with_smart(
+            StatsStore::disabled(),
+            options(),
+            vec![SmartPlaylistDef {
+                name: "B 站收藏".to_owned(),
+                query: "loved source:bilibili".to_owned(),
+            }],
+        );
+        let created = ch
+            .create_smart_playlist(" 全部收藏 ", "loved sort:name")
+            .await?;
+        let id = smart_playlist_id("全部收藏");
+        assert_eq!(created.id, id);
+        assert_eq!(created.track_count, 2);
+
+        let lists = ch.my_playlists().await?;
+        assert_eq!(
+            summary(&lists),
+            vec![("Favorites", 2), ("B 站收藏", 1), ("全部收藏", 2)]
+        );
+        let detail = ch.playlist_detail(&id).await?;
+        assert_eq!(detail.description, "loved sort:name");
+        let names = detail
+            .songs
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Palisade", "夜間飛行"]);
+        Ok(())
+    }
+
+    /// 写操作边界:非法表达式 400、config 声明只读 403、不存在 404(改描述不隐式建单)、
+    /// 重名建单 409、收藏歌单不可写、智能歌单不收歌。
+    #[tokio::test]
+    async fn smart_writes_are_validated() -> color_eyre::Result<()> {
+        let (_dir, store) = store_with_favorites().await?;
+        let ch = MineralChannel::new(store).with_smart(
+            StatsStore::disabled(),
+            options(),
+            vec![SmartPlaylistDef {
+                name: "声明".to_owned(),
+                query: "loved".to_owned(),
+            }],
+        );
+        let code = |r: mineral_channel_core::Result<()>| match r {
+            Err(Error::Api { code, .. }) => Some(code),
+            _ => None,
+        };
+        let create = |name: &'static str, query: &'static str| {
+            let ch = &ch;
+            async move { ch.create_smart_playlist(name, query).await.map(|_| ()) }
+        };
+        let mine = smart_playlist_id("mine");
+        assert_eq!(
+            code(ch.set_playlist_description(&mine, "loved").await),
+            Some(404),
+            "改描述不隐式建单"
+        );
+        assert_eq!(code(create("mine", "plays>>1").await), Some(400));
+        assert_eq!(code(create("  ", "loved").await), Some(400));
+        create("mine", "loved").await?;
+        assert_eq!(code(create("mine", "plays>1").await), Some(409));
+        assert_eq!(code(create("声明", "plays>1").await), Some(409));
+        assert_eq!(
+            code(ch.set_playlist_description(&mine, "plays>>1").await),
+            Some(400)
+        );
+        assert_eq!(
+            code(
+                ch.set_playlist_description(&smart_playlist_id("声明"), "plays>1")
+                    .await
+            ),
+            Some(403)
+        );
+        assert_eq!(
+            code(ch.rename_playlist(&smart_playlist_id("声明"), "x").await),
+            Some(403)
+        );
+        assert_eq!(
+            code(ch.delete_playlist(&smart_playlist_id("nope")).await),
+            Some(404)
+        );
+        assert!(matches!(
+            ch.rename_playlist(&favorites_playlist_id(), "x").await,
+            Err(Error::NotSupported)
+        ));
+        assert!(matches!(
+            ch.playlist_add_songs(&mine, &[SongId::new(SourceKind::NETEASE, "n1")])
+                .await,
+            Err(Error::NotSupported)
+        ));
+
+        ch.rename_playlist(&mine, "改名").await?;
+        assert_eq!(
+            code(create("改名", "loved").await),
+            Some(409),
+            "撞改名后的展示名"
+        );
+        ch.set_playlist_description(&mine, "loved sort:name")
+            .await?;
+        let lists = ch.my_playlists().await?;
+        assert!(
+            lists.iter().any(|p| p.id == mine && p.name == "改名"),
+            "改名不改 id,再设表达式不回退名字"
+        );
+        ch.delete_playlist(&mine).await?;
+        assert_eq!(ch.my_playlists().await?.len(), 2);
+        Ok(())
+    }
+
+    /// 混源歌单:建单进列表,收任意源的歌、可挪可删;重复加 409、不存在 404。
+    #[tokio::test]
+    async fn mix_playlists_hold_any_source() -> color_eyre::Result<()> {
+        let (_dir, store) = store_with_favorites().await?;
+        let ch = MineralChannel::new(store);
+        let code = |r: mineral_channel_core::Result<()>| match r {
+            Err(Error::Api { code, .. }) => Some(code),
+            _ => None,
+        };
+        let created = ch.create_playlist("  混听 ").await?;
+        assert_eq!(created.id, mix_playlist_id(1));
+        assert_eq!(created.name, "混听");
+
+        let n1 = SongId::new(SourceKind::NETEASE, "n1");
+        let b1 = SongId::new(SourceKind::BILIBILI, "b1");
+        ch.playlist_add_songs(&created.id, &[n1.clone(), b1.clone()])
+            .await?;
+        assert_eq!(
+            code(
+                ch.playlist_add_songs(&created.id, std::slice::from_ref(&n1))
+                    .await
+            ),
+            Some(409)
+        );
+        ch.playlist_move_song(&created.id, &b1, 0).await?;
+        let detail = ch.playlist_detail(&created.id).await?;
+        let ids = detail
+            .songs
+            .iter()
+            .map(|s| s.id.qualified())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["bilibili:b1", "netease:n1"]);
+
+        ch.playlist_remove_songs(&created.id, &[b1]).await?;
+        ch.set_playlist_description(&created.id, "通勤").await?;
+        let lists = ch.my_playlists().await?;
+        assert_eq!(summary(&lists), vec![("Favorites", 2), ("混听", 1)]);
+        assert_eq!(lists.get(1).map(|p| p.description.as_str()), Some("通勤"));
+
+        let gone = mix_playlist_id(99);
+        assert_eq!(code(ch.rename_playlist(&gone, "x").await), Some(404));
+        assert_eq!(code(ch.playlist_add_songs(&gone, &[n1]).await), Some(404));
+        ch.delete_playlist(&created.id).await?;
+        assert_eq!(ch.my_playlists().await?.len(), 1);
+        Ok(())
+    }
+
+    /// 下载事实来自 stats:`downloaded:<dur>` 只选窗内真正下过的歌,没播过也没收藏的下载
+    /// 经 songs 维表进全集。
+    #[tokio::test]
+    async fn downloaded_query_reads_stats() -> color_eyre::Result<()> {
+        use mineral_stats::{Actor, BehaviorEvent, DownloadHook, DownloadOutcome, StatsEvent};
+
+        let (dir, store) = store_with_favorites().await?;
+        let stats = StatsStore::open(&dir.path().join("stats.db")).await?;
+        let event = StatsEvent::Behavior {
+            actor: Actor::User,
+            event: BehaviorEvent::Download {
+                song: SongId::new(SourceKind::NETEASE, "n1"),
+                quality: "lossless".to_owned(),
+                format: None,
+                outcome: DownloadOutcome::Downloaded,
+                hooked: DownloadHook::None,
+                path: None,
+            },
+        };
+        stats.record_event(super::now_ms(), None, &event).await?;
+        // 从未播放、未收藏的下载:下载完成时只补写了维表行。
+        let fresh = with_name(song("d1"), "新歌");
+        stats.upsert_song(&fresh).await?;
+        let download = StatsEvent::Behavior {
+            actor: Actor::System,
+            event: BehaviorEvent::Download {
+                song: fresh.id.clone(),
+                quality: "lossless".to_owned(),
+                format: None,
+                outcome: DownloadOutcome::Downloaded,
+                hooked: DownloadHook::None,
+                path: None,
+            },
+        };
+        stats.record_event(super::now_ms(), None, &download).await?;
+        let ch = MineralChannel::new(store).with_smart(
+            stats,
+            options(),
+            vec![SmartPlaylistDef {
+                name: "新下载".to_owned(),
+                query: "downloaded:7d".to_owned(),
+            }],
+        );
+        let p = ch.playlist_detail(&smart_playlist_id("新下载")).await?;
+        let ids = p.songs.iter().map(|s| s.id.value()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["n1", "d1"]);
+        Ok(())
+    }
 }
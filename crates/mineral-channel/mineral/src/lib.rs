@@ -1,6 +1,11 @@
 //! 跨源聚合 channel(source = `mineral`):把 persist 里的全源收藏投影成一张
-//! synthetic 歌单,供上层与普通歌单同等浏览 / 下钻。
+//! synthetic 歌单,外加用户自有的混源歌单(曲目可跨源)与按表达式对 persist / stats
+//! 现算的智能歌单,供上层与普通歌单同等浏览 / 下钻。
 
 mod channel;
+mod mix;
+mod smart;
 
-pub use channel::{MineralChannel, favorites_playlist_id};
+pub use channel::{MineralChannel, favorites_playlist_id, smart_playlist_id};
+pub use mix::{is_mix_playlist, mix_playlist_id};
+pub use smart::{SmartPlaylistDef, check_smart_query};
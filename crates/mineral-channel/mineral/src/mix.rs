@@ -0,0 +1,232 @@
+//! 聚合源自有的混源歌单:persist 里的 `mix_playlists` 投影成 `mineral:mix:<n>` 歌单。
+//!
+//! 远端歌单只装得下本源的歌;混源歌单只存 `(namespace, song_value)` 引用,netease /
+//! bilibili / 本地的歌可以并排。增删改挪全在本地,写完由 server 重推聚合面。
+
+use mineral_channel_core::{Error, Result};
+use mineral_model::{Playlist, PlaylistId, SongId, SourceKind};
+use mineral_persist::{MixPlaylistRow, MixPlaylistStore};
+
+/// 混源歌单 id 裸值的前缀(`mineral:mix:<n>`)。
+const MIX_PREFIX: &str = "mix:";
+
+/// 混源歌单 id:`mineral:mix:<n>`(`n` 为 persist 自增主键,改名不变)。
+///
+/// # Params:
+///   - `key`: persist 主键
+///
+/// # Return:
+///   混源歌单的 [`PlaylistId`]。
+pub fn mix_playlist_id(key: i64) -> PlaylistId {
+    PlaylistId::new(SourceKind::MINERAL, format!("{MIX_PREFIX}{key}"))
+}
+
+/// 是否混源歌单 id(上层据此决定挂不挂改名 / 增删曲目等管理项)。
+///
+/// # Params:
+///   - `id`: 歌单 id
+///
+/// # Return:
+///   聚合源且裸值形如 `mix:<n>` 时为 `true`。
+pub fn is_mix_playlist(id: &PlaylistId) -> bool {
+    id.namespace() == SourceKind::MINERAL && mix_key(id).is_some()
+}
+
+/// 从歌单 id 还原 persist 主键;非混源 id 为 `None`。
+///
+/// # Params:
+///   - `id`: 歌单 id
+///
+/// # Return:
+///   混源歌单的主键。
+pub(crate) fn mix_key(id: &PlaylistId) -> Option<i64> {
+    id.value().strip_prefix(MIX_PREFIX)?.parse().ok()
+}
+
+/// 一行元信息 → 歌单(曲目由调用方按需填)。
+fn to_playlist(row: MixPlaylistRow) -> Result<Playlist> {
+    let track_count =
+        u64::try_from(row.track_count).map_err(|e| Error::Other(color_eyre::Report::new(e)))?;
+    Ok(Playlist::builder()
+        .id(mix_playlist_id(row.id))
+        .name(row.name)
+        .description(row.description)
+        .track_count(track_count)
+        .build())
+}
+
+/// 全部混源歌单(只带计数,不带曲目)。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///
+/// # Return:
+///   按创建顺序的歌单列表。
+pub(crate) async fn list(store: &MixPlaylistStore) -> Result<Vec<Playlist>> {
+    store
+        .list()
+        .await
+        .map_err(Error::Other)?
+        .into_iter()
+        .map(to_playlist)
+        .collect()
+}
+
+/// 一张混源歌单的详情(带全曲目,曲目保留原源 namespace)。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///
+/// # Return:
+///   歌单详情;不存在为 404。
+pub(crate) async fn detail(store: &MixPlaylistStore, key: i64) -> Result<Playlist> {
+    let row = existing(store, key).await?;
+    let songs = store.songs(key).await.map_err(Error::Other)?;
+    let mut playlist = to_playlist(row)?;
+    playlist.songs = songs;
+    Ok(playlist)
+}
+
+/// 新建一张空歌单。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `name`: 歌单名(已 trim、非空)
+///
+/// # Return:
+///   新歌单;persist 降级无处落库时报错。
+pub(crate) async fn create(store: &MixPlaylistStore, name: &str) -> Result<Playlist> {
+    let key = store
+        .create(name)
+        .await
+        .map_err(Error::Other)?
+        .ok_or_else(|| Error::Other(color_eyre::eyre::eyre!("persist 不可用,无法新建歌单")))?;
+    Ok(Playlist::builder()
+        .id(mix_playlist_id(key))
+        .name(name.to_owned())
+        .build())
+}
+
+/// 删除歌单;不存在为 404。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+pub(crate) async fn delete(store: &MixPlaylistStore, key: i64) -> Result<()> {
+    existing(store, key).await?;
+    store.delete(key).await.map_err(Error::Other)
+}
+
+/// 改名;不存在为 404。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///   - `name`: 新名(已 trim、非空)
+pub(crate) async fn rename(store: &MixPlaylistStore, key: i64, name: &str) -> Result<()> {
+    hit(key, store.rename(key, name).await.map_err(Error::Other)?)
+}
+
+/// 改描述;不存在为 404。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///   - `desc`: 新描述
+pub(crate) async fn set_description(store: &MixPlaylistStore, key: i64, desc: &str) -> Result<()> {
+    hit(
+        key,
+        store
+            .set_description(key, desc)
+            .await
+            .map_err(Error::Other)?,
+    )
+}
+
+/// 追加歌曲。全部已在单里时按「已存在」报 409,不伪装成功;部分新增算成功。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///   - `songs`: 待追加歌曲(任意 namespace)
+pub(crate) async fn add_songs(store: &MixPlaylistStore, key: i64, songs: &[SongId]) -> Result<()> {
+    existing(store, key).await?;
+    let added = store.add_songs(key, songs).await.map_err(Error::Other)?;
+    if added == 0 && !songs.is_empty() {
+        return Err(api_error(409, "歌曲已在歌单中".to_owned()));
+    }
+    Ok(())
+}
+
+/// 移除歌曲(不在单里的忽略)。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///   - `songs`: 待移除歌曲
+pub(crate) async fn remove_songs(
+    store: &MixPlaylistStore,
+    key: i64,
+    songs: &[SongId],
+) -> Result<()> {
+    existing(store, key).await?;
+    store
+        .remove_songs(key, songs)
+        .await
+        .map_err(Error::Other)
+        .map(|_removed| ())
+}
+
+/// 挪动一首歌;歌不在单里为 404。
+///
+/// # Params:
+///   - `store`: 混源歌单存储
+///   - `key`: persist 主键
+///   - `song`: 要挪的歌
+///   - `to`: 目标位置
+pub(crate) async fn move_song(
+    store: &MixPlaylistStore,
+    key: i64,
+    song: &SongId,
+    to: usize,
+) -> Result<()> {
+    existing(store, key).await?;
+    if !store.move_song(key, song, to).await.map_err(Error::Other)? {
+        return Err(api_error(
+            404,
+            format!("歌曲不在歌单中: {}", song.qualified()),
+        ));
+    }
+    Ok(())
+}
+
+/// 取已存在歌单的元信息;不存在为 404。
+async fn existing(store: &MixPlaylistStore, key: i64) -> Result<MixPlaylistRow> {
+    store
+        .get(key)
+        .await
+        .map_err(Error::Other)?
+        .ok_or_else(|| api_error(404, format!("歌单不存在: {MIX_PREFIX}{key}")))
+}
+
+/// 单行更新的命中结果 → 未命中为 404。
+fn hit(key: i64, hit: bool) -> Result<()> {
+    if hit {
+        Ok(())
+    } else {
+        Err(api_error(404, format!("歌单不存在: {MIX_PREFIX}{key}")))
+    }
+}
+
+/// 聚合源业务错误(本 channel 没有远端 code,沿用 HTTP 语义:400 / 403 / 404 / 409)。
+///
+/// # Params:
+///   - `code`: HTTP 语义的错误码
+///   - `message`: 错误描述
+///
+/// # Return:
+///   [`Error::Api`]。
+pub(crate) fn api_error(code: i64, message: String) -> Error {
+    Error::Api { code, message }
+}
@@ -0,0 +1,538 @@
+//! 智能歌单:筛选表达式的解析与求值。
+//!
+//! 表达式是空白分隔的词项,筛选项全部成立(AND)才入选,`!` 前缀对单个筛选项取反:
+//!
+//! - `loved`:收藏过
+//! - `source:<name>`:来自某源(`netease` / `bilibili` / `local` …)
+//! - `artist:<name>`:任一艺人名包含该串(不分大小写;含空格用引号,`artist:"Sigur Rós"`)
+//! - `plays<op><n>` / `skips<op><n>`:统计窗内有效播放 / 跳过次数比较(`<` `<=` `>` `>=` `=`)
+//! - `unplayed:<dur>`:最近 dur 内没有有效播放
+//! - `downloaded:<dur>`:最近 dur 内下载过
+//!
+//! 以及不参与筛选的修饰项:`within:<dur>`(`plays` / `skips` / `top` / `sort` 的统计窗,缺省
+//! 全部历史)、`top:<n>`(按窗内播放次数取前 n,只留播过的)、`sort:<key>`(`plays` / `recent`
+//! / `downloaded` / `name`)、`limit:<n>`(截断)。时长写 `<n>h` / `<n>d` / `<n>w`。
+//!
+//! 例:`loved unplayed:90d`、`top:50 within:30d`、`skips<2 artist:"Mineral"`、
+//! `downloaded:7d sort:downloaded`。
+//!
+//! 求值对象是「库」:persist 里有 meta 的收藏 + stats 维表里播过的歌(前者优先、按收藏时间
+//! 降序在前)。不带排序修饰时结果保持这个顺序。
+
+use color_eyre::eyre::{bail, eyre};
+use mineral_model::{Song, SongId};
+use mineral_stats::SongActivity;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// 一小时的毫秒数。
+const HOUR_MS: i64 = 3_600_000;
+
+/// 数值比较符。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cmp {
+    /// `<`。
+    Lt,
+
+    /// `<=`。
+    Le,
+
+    /// `>`。
+    Gt,
+
+    /// `>=`。
+    Ge,
+
+    /// `=`。
+    Eq,
+}
+
+impl Cmp {
+    /// `lhs <op> rhs` 是否成立。
+    fn holds(self, lhs: i64, rhs: i64) -> bool {
+        match self {
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+            Self::Eq => lhs == rhs,
+        }
+    }
+}
+
+/// 单个筛选项。
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Filter {
+    /// 收藏过。
+    Loved,
+
+    /// 来源 name 相等。
+    Source(String),
+
+    /// 任一艺人名包含(已转小写)。
+    Artist(String),
+
+    /// 统计窗内有效播放次数比较。
+    Plays(Cmp, i64),
+
+    /// 统计窗内跳过次数比较。
+    Skips(Cmp, i64),
+
+    /// 最近若干 ms 内无有效播放。
+    Unplayed(i64),
+
+    /// 最近若干 ms 内下载过。
+    Downloaded(i64),
+}
+
+/// 结果排序键。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortKey {
+    /// 窗内播放次数降序。
+    Plays,
+
+    /// 窗内最近播放降序。
+    Recent,
+
+    /// 窗内最近下载降序。
+    Downloaded,
+
+    /// 歌名升序。
+    Name,
+}
+
+/// 一张声明式智能歌单(config `sources.mineral.smart_playlists` 的一项)。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmartPlaylistDef {
+    /// 歌单名(同时作稳定键)。
+    pub name: String,
+
+    /// 筛选表达式原文(语法见模块文档)。
+    pub query: String,
+}
+
+/// 校验智能歌单表达式(client 建单前先行提示,省一次写往返)。
+///
+/// # Params:
+///   - `text`: 表达式原文
+///
+/// # Return:
+///   合法返回 `Ok(())`;否则 `Err` 的消息指明哪一项不对。
+pub fn check_smart_query(text: &str) -> color_eyre::Result<()> {
+    SmartQuery::parse(text).map(|_| ())
+}
+
+/// 解析后的智能歌单表达式。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SmartQuery {
+    /// 筛选项(`true` = 取反)。
+    filters: Vec<(bool, Filter)>,
+
+    /// 统计窗回看 ms;`None` = 全部历史。
+    window: Option<i64>,
+
+    /// 按播放次数取前 n。
+    top: Option<usize>,
+
+    /// 排序键。
+    sort: Option<SortKey>,
+
+    /// 截断上限。
+    limit: Option<usize>,
+}
+
+/// 求值所需的库快照:歌曲全集 + 收藏集 + 按回看窗分组的逐曲活跃度。
+#[derive(Default)]
+pub(crate) struct Facts {
+    /// 求值全集(已去重,顺序即缺省结果序)。
+    pub(crate) songs: Vec<Song>,
+
+    /// 收藏过的歌。
+    pub(crate) loved: FxHashSet<SongId>,
+
+    /// 回看窗(`None` = 全部历史)→ 该窗内的逐曲活跃度。
+    pub(crate) activity: FxHashMap<Option<i64>, FxHashMap<SongId, SongActivity>>,
+}
+
+impl Facts {
+    /// 某窗内某首歌的活跃度;窗内无痕迹为 `None`。
+    fn get(&self, window: Option<i64>, id: &SongId) -> Option<&SongActivity> {
+        self.activity.get(&window).and_then(|m| m.get(id))
+    }
+}
+
+impl SmartQuery {
+    /// 解析表达式原文。
+    ///
+    /// # Params:
+    ///   - `text`: 表达式原文
+    ///
+    /// # Return:
+    ///   解析结果;空表达式、未知词项、非法数值 / 时长、未闭合引号返回 `Err`(消息指明哪一项)。
+    pub(crate) fn parse(text: &str) -> color_eyre::Result<Self> {
+        let mut query = Self {
+            filters: Vec::new(),
+            window: None,
+            top: None,
+            sort: None,
+            limit: None,
+        };
+        let tokens = tokenize(text)?;
+        if tokens.is_empty() {
+            bail!("表达式为空");
+        }
+        for token in tokens {
+            let (negate, term) = match token.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, token.as_str()),
+            };
+            if let Some(filter) = parse_filter(term)? {
+                query.filters.push((negate, filter));
+                continue;
+            }
+            if negate {
+                bail!("`{term}` 不是筛选项,不能取反");
+            }
+            let (key, value) = term
+                .split_once(':')
+                .ok_or_else(|| eyre!("未知词项 `{term}`"))?;
+            match key {
+                "within" => query.window = Some(parse_duration(value)?),
+                "top" => query.top = Some(parse_count(value)?),
+                "limit" => query.limit = Some(parse_count(value)?),
+                "sort" => {
+                    query.sort = Some(match value {
+                        "plays" => SortKey::Plays,
+                        "recent" => SortKey::Recent,
+                        "downloaded" => SortKey::Downloaded,
+                        "name" => SortKey::Name,
+                        other => {
+                            bail!("未知排序键 `{other}`(可选 plays / recent / downloaded / name)")
+                        }
+                    });
+                }
+                _ => bail!("未知词项 `{term}`"),
+            }
+        }
+        Ok(query)
+    }
+
+    /// 求值要查的回看窗集合(统计窗 + 各 `unplayed` / `downloaded` 的窗),已去重。
+    ///
+    /// # Return:
+    ///   回看 ms 列表;`None` = 全部历史。
+    pub(crate) fn lookbacks(&self) -> Vec<Option<i64>> {
+        let mut out = vec![self.window];
+        for (_, filter) in &self.filters {
+            if let Filter::Unplayed(ms) | Filter::Downloaded(ms) = filter
+                && !out.contains(&Some(*ms))
+            {
+                out.push(Some(*ms));
+            }
+        }
+        out
+    }
+
+    /// 对库快照求值。
+    ///
+    /// # Params:
+    ///   - `facts`: 库快照(须含 [`Self::lookbacks`] 列出的每个窗)
+    ///
+    /// # Return:
+    ///   入选歌曲,按修饰项排序 / 截断。
+    pub(crate) fn evaluate(&self, facts: &Facts) -> Vec<Song> {
+        let window = self.window;
+        let plays = |s: &Song| facts.get(window, &s.id).map_or(0, |a| a.plays);
+        let mut songs = facts
+            .songs
+            .iter()
+            .filter(|s| {
+                self.filters
+                    .iter()
+                    .all(|(negate, f)| self.matches(f, s, facts) != *negate)
+            })
+            .cloned()
+            .collect::<Vec<Song>>();
+        if let Some(n) = self.top {
+            songs.retain(|s| plays(s) > 0);
+            songs.sort_by_key(|s| std::cmp::Reverse(plays(s)));
+            songs.truncate(n);
+        }
+        match self.sort {
+            Some(SortKey::Plays) => songs.sort_by_key(|s| std::cmp::Reverse(plays(s))),
+            Some(SortKey::Recent) => songs.sort_by_key(|s| {
+                std::cmp::Reverse(facts.get(window, &s.id).and_then(|a| a.last_played_at))
+            }),
+            Some(SortKey::Downloaded) => songs.sort_by_key(|s| {
+                std::cmp::Reverse(facts.get(window, &s.id).and_then(|a| a.downloaded_at))
+            }),
+            Some(SortKey::Name) => songs.sort_by(|a, b| a.name.cmp(&b.name)),
+            None => {}
+        }
+        if let Some(n) = self.limit {
+            songs.truncate(n);
+        }
+        songs
+    }
+
+    /// 单个筛选项对一首歌是否成立(未取反的原义)。
+    fn matches(&self, filter: &Filter, song: &Song, facts: &Facts) -> bool {
+        match filter {
+            Filter::Loved => facts.loved.contains(&song.id),
+            Filter::Source(name) => song.source().name() == name,
+            Filter::Artist(needle) => song
+                .artists
+                .iter()
+                .any(|a| a.name.to_lowercase().contains(needle.as_str())),
+            Filter::Plays(cmp, n) => {
+                cmp.holds(facts.get(self.window, &song.id).map_or(0, |a| a.plays), *n)
+            }
+            Filter::Skips(cmp, n) => {
+                cmp.holds(facts.get(self.window, &song.id).map_or(0, |a| a.skips), *n)
+            }
+            Filter::Unplayed(ms) => facts.get(Some(*ms), &song.id).is_none_or(|a| a.plays == 0),
+            Filter::Downloaded(ms) => facts
+                .get(Some(*ms), &song.id)
+                .is_some_and(|a| a.downloaded_at.is_some()),
+        }
+    }
+}
+
+/// 按空白切词,双引号内的空白不切(引号本身去掉)。
+fn tokenize(text: &str) -> color_eyre::Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        bail!("引号未闭合");
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// 试把一个词项解析成筛选项;不是筛选项返回 `Ok(None)`(交给修饰项分支)。
+fn parse_filter(term: &str) -> color_eyre::Result<Option<Filter>> {
+    if term == "loved" {
+        return Ok(Some(Filter::Loved));
+    }
+    if let Some(rest) = term.strip_prefix("plays")
+        && let Some((cmp, n)) = parse_comparison(rest)?
+    {
+        return Ok(Some(Filter::Plays(cmp, n)));
+    }
+    if let Some(rest) = term.strip_prefix("skips")
+        && let Some((cmp, n)) = parse_comparison(rest)?
+    {
+        return Ok(Some(Filter::Skips(cmp, n)));
+    }
+    let Some((key, value)) = term.split_once(':') else {
+        return Ok(None);
+    };
+    Ok(match key {
+        "source" => Some(Filter::Source(value.to_owned())),
+        "artist" => Some(Filter::Artist(value.to_lowercase())),
+        "unplayed" => Some(Filter::Unplayed(parse_duration(value)?)),
+        "downloaded" => Some(Filter::Downloaded(parse_duration(value)?)),
+        _ => None,
+    })
+}
+
+/// 解析 `<op><n>`;不以比较符开头返回 `Ok(None)`。
+fn parse_comparison(text: &str) -> color_eyre::Result<Option<(Cmp, i64)>> {
+    let (cmp, rest) = if let Some(rest) = text.strip_prefix("<=") {
+        (Cmp::Le, rest)
+    } else if let Some(rest) = text.strip_prefix(">=") {
+        (Cmp::Ge, rest)
+    } else if let Some(rest) = text.strip_prefix('<') {
+        (Cmp::Lt, rest)
+    } else if let Some(rest) = text.strip_prefix('>') {
+        (Cmp::Gt, rest)
+    } else if let Some(rest) = text.strip_prefix('=') {
+        (Cmp::Eq, rest)
+    } else {
+        return Ok(None);
+    };
+    let n = rest
+        .parse::<i64>()
+        .map_err(|e| eyre!("`{rest}` 不是整数: {e}"))?;
+    Ok(Some((cmp, n)))
+}
+
+/// 解析非负计数。
+fn parse_count(text: &str) -> color_eyre::Result<usize> {
+    text.parse::<usize>()
+        .map_err(|e| eyre!("`{text}` 不是非负整数: {e}"))
+}
+
+/// 解析时长 `<n>h` / `<n>d` / `<n>w` → ms。
+fn parse_duration(text: &str) -> color_eyre::Result<i64> {
+    let unit_at = text
+        .char_indices()
+        .last()
+        .map(|(i, _)| i)
+        .ok_or_else(|| eyre!("时长为空"))?;
+    let (digits, unit) = text.split_at(unit_at);
+    let hours = match unit {
+        "h" => 1,
+        "d" => 24,
+        "w" => 24 * 7,
+        _ => bail!("时长 `{text}` 缺单位(h / d / w)"),
+    };
+    let n = digits
+        .parse::<i64>()
+        .map_err(|e| eyre!("时长 `{text}` 不是整数: {e}"))?;
+    n.checked_mul(hours)
+        .and_then(|h| h.checked_mul(HOUR_MS))
+        .ok_or_else(|| eyre!("时长 `{text}` 过大"))
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{SongId, SourceKind};
+    use mineral_stats::SongActivity;
+    use mineral_test::{song, with_artist};
+    use rustc_hash::FxHashMap;
+
+    use super::{Cmp, Facts, Filter, HOUR_MS, SmartQuery};
+
+    /// 造一条活跃度。
+    fn activity(value: &str, plays: i64, skips: i64, downloaded_at: Option<i64>) -> SongActivity {
+        SongActivity {
+            song: SongId::new(SourceKind::NETEASE, value),
+            plays,
+            skips,
+            last_played_at: (plays > 0).then_some(plays),
+            downloaded_at,
+        }
+    }
+
+    /// 歌 id 列表(断言用)。
+    fn ids(songs: &[mineral_model::Song]) -> Vec<&str> {
+        songs.iter().map(|s| s.id.value()).collect()
+    }
+
+    /// 词项、取反、引号、比较符与时长单位都按语法落型。
+    #[test]
+    fn parse_recognises_terms() -> color_eyre::Result<()> {
+        let q = SmartQuery::parse(
+            r#"loved !source:local artist:"Sigur Rós" skips<2 plays>=3 unplayed:2w within:30d top:50 sort:recent limit:10"#,
+        )?;
+        assert_eq!(
+            q.filters,
+            vec![
+                (false, Filter::Loved),
+                (true, Filter::Source("local".to_owned())),
+                (false, Filter::Artist("sigur rós".to_owned())),
+                (false, Filter::Skips(Cmp::Lt, 2)),
+                (false, Filter::Plays(Cmp::Ge, 3)),
+                (false, Filter::Unplayed(14 * 24 * HOUR_MS)),
+            ]
+        );
+        assert_eq!(q.window, Some(30 * 24 * HOUR_MS));
+        assert_eq!(q.top, Some(50));
+        assert_eq!(q.limit, Some(10));
+        assert_eq!(
+            q.lookbacks(),
+            vec![Some(30 * 24 * HOUR_MS), Some(14 * 24 * HOUR_MS)]
+        );
+        Ok(())
+    }
+
+    /// 空表达式、未知词项、缺单位、未闭合引号、取反修饰项一律拒绝。
+    #[test]
+    fn parse_rejects_malformed() {
+        for bad in [
+            "",
+            "   ",
+            "bogus",
+            "unplayed:90",
+            "top:x",
+            r#"artist:"open"#,
+            "!top:5",
+            "sort:random",
+        ] {
+            assert!(SmartQuery::parse(bad).is_err(), "{bad:?} 应被拒绝");
+        }
+    }
+
+    /// 「收藏且 90 天没听」:窗内有有效播放的收藏被滤掉,没收藏的不入选。
+    #[test]
+    fn loved_unplayed_filters_recent_plays() -> color_eyre::Result<()> {
+        let q = SmartQuery::parse("loved unplayed:90d")?;
+        let mut facts = Facts {
+            songs: vec![song("a"), song("b"), song("c")],
+            loved: [song("a").id, song("b").id].into_iter().collect(),
+            ..Facts::default()
+        };
+        let recent = [(song("b").id, activity("b", 1, 0, None))]
+            .into_iter()
+            .collect::<FxHashMap<_, _>>();
+        facts.activity.insert(Some(90 * 24 * HOUR_MS), recent);
+        facts.activity.insert(None, FxHashMap::default());
+        assert_eq!(ids(&q.evaluate(&facts)), ["a"]);
+        Ok(())
+    }
+
+    /// top:n 只留播过的、按次数降序截断;artist 不分大小写;skips 缺行按 0。
+    #[test]
+    fn top_and_artist_and_skips() -> color_eyre::Result<()> {
+        let window = [
+            (song("a").id, activity("a", 1, 5, None)),
+            (song("b").id, activity("b", 7, 0, None)),
+            (song("c").id, activity("c", 3, 1, None)),
+        ]
+        .into_iter()
+        .collect::<FxHashMap<_, _>>();
+        let mut facts = Facts {
+            songs: vec![
+                with_artist(song("a"), "Mineral"),
+                with_artist(song("b"), "Mineral"),
+                with_artist(song("c"), "Other"),
+                with_artist(song("d"), "MINERAL"),
+            ],
+            ..Facts::default()
+        };
+        facts.activity.insert(None, window);
+
+        let top = SmartQuery::parse("top:2")?;
+        assert_eq!(ids(&top.evaluate(&facts)), ["b", "c"]);
+
+        let artist = SmartQuery::parse("skips<2 artist:mineral")?;
+        assert_eq!(ids(&artist.evaluate(&facts)), ["b", "d"]);
+        Ok(())
+    }
+
+    /// 「最近下载」:只留窗内下载过的,按下载时刻降序。
+    #[test]
+    fn downloaded_sorted_by_time() -> color_eyre::Result<()> {
+        let q = SmartQuery::parse("downloaded:7d sort:downloaded")?;
+        let week = [
+            (song("a").id, activity("a", 0, 0, Some(10))),
+            (song("b").id, activity("b", 2, 0, None)),
+            (song("c").id, activity("c", 0, 0, Some(20))),
+        ]
+        .into_iter()
+        .collect::<FxHashMap<_, _>>();
+        let all = week.clone();
+        let mut facts = Facts {
+            songs: vec![song("a"), song("b"), song("c")],
+            ..Facts::default()
+        };
+        facts.activity.insert(Some(7 * 24 * HOUR_MS), week);
+        facts.activity.insert(None, all);
+        assert_eq!(ids(&q.evaluate(&facts)), ["c", "a"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! 发现面端点:每日推荐、私人 FM、相似歌曲 / 艺人、排行榜(纯协议:参数 → 类型化 wire DTO)。
+//!
+//! 每日推荐与私人 FM 是个性化端点,需登录 cookie;相似与排行榜是公开端点。FM / 相似歌曲
+//! 只回 id(见 [`SongRef`](crate::wire::discover::SongRef)),补全详情在 channel 层走 song/detail。
+
+use mineral_model::{ArtistId, SongId, SourceKind};
+use serde_json::json;
+
+/// 本模块内部统一的 result 别名,屏蔽 color-eyre 全名。
+type Result<T> = color_eyre::Result<T>;
+
+use crate::transport::client::{RequestSpec, Transport};
+use crate::transport::headers::UaKind;
+use crate::transport::url::Crypto;
+use crate::wire::discover::{
+    DailySongsResult, PersonalFmResult, SimilarArtistsResult, SimilarSongsResult, ToplistResult,
+};
+use crate::wire::playlist::PlaylistInfo;
+use crate::wire::search::SearchArtist;
+use crate::wire::song::AlbumSong;
+
+/// 相似歌曲一次取的条数(端点上限约 50)。
+const SIMILAR_SONGS_LIMIT: u32 = 50;
+
+/// 每日推荐歌曲:`/weapi/v3/discovery/recommend/songs`。需登录。
+pub async fn daily_songs(transport: &Transport) -> Result<Vec<AlbumSong>> {
+    let v = transport
+        .request(RequestSpec {
+            path: "/weapi/v3/discovery/recommend/songs",
+            crypto: Crypto::Weapi,
+            params: serde_json::Map::new(),
+            ua: UaKind::Pc,
+        })
+        .await?;
+    let r: DailySongsResult = crate::wire::de::from_value(v)?;
+    Ok(r.data.map(|d| d.daily_songs).unwrap_or_default())
+}
+
+/// 私人 FM 的下一批:`/weapi/v1/radio/get`。需登录;每次调用给新的一批(通常 3 首)。
+pub async fn personal_fm(transport: &Transport) -> Result<Vec<SongId>> {
+    let v = transport
+        .request(RequestSpec {
+            path: "/weapi/v1/radio/get",
+            crypto: Crypto::Weapi,
+            params: serde_json::Map::new(),
+            ua: UaKind::Pc,
+        })
+        .await?;
+    let r: PersonalFmResult = crate::wire::de::from_value(v)?;
+    Ok(r.data
+        .into_iter()
+        .map(|s| SongId::new(SourceKind::NETEASE, s.id.to_string()))
+        .collect())
+}
+
+/// 相似歌曲:`/weapi/v1/discovery/simiSong`。
+pub async fn similar_songs(transport: &Transport, id: &SongId) -> Result<Vec<SongId>> {
+    let mut p = serde_json::Map::new();
+    p.insert("songid".into(), json!(id.as_str()));
+    p.insert("limit".into(), json!(SIMILAR_SONGS_LIMIT));
+    p.insert("offset".into(), json!(0));
+
+    let v = transport
+        .request(RequestSpec {
+            path: "/weapi/v1/discovery/simiSong",
+            crypto: Crypto::Weapi,
+            params: p,
+            ua: UaKind::Any,
+        })
+        .await?;
+    let r: SimilarSongsResult = crate::wire::de::from_value(v)?;
+    Ok(r.songs
+        .into_iter()
+        .map(|s| SongId::new(SourceKind::NETEASE, s.id.to_string()))
+        .collect())
+}
+
+/// 相似艺人:`/weapi/discovery/simiArtist`。
+pub async fn similar_artists(transport: &Transport, id: &ArtistId) -> Result<Vec<SearchArtist>> {
+    let mut p = serde_json::Map::new();
+    p.insert("artistid".into(), json!(id.as_str()));
+
+    let v = transport
+        .request(RequestSpec {
+            path: "/weapi/discovery/simiArtist",
+            crypto: Crypto::Weapi,
+            params: p,
+            ua: UaKind::Any,
+        })
+        .await?;
+    let r: SimilarArtistsResult = crate::wire::de::from_value(v)?;
+    Ok(r.artists)
+}
+
+/// 全部排行榜(榜单元信息):`/weapi/toplist`。
+pub async fn toplists(transport: &Transport) -> Result<Vec<PlaylistInfo>> {
+    let v = transport
+        .request(RequestSpec {
+            path: "/weapi/toplist",
+            crypto: Crypto::Weapi,
+            params: serde_json::Map::new(),
+            ua: UaKind::Any,
+        })
+        .await?;
+    let r: ToplistResult = crate::wire::de::from_value(v)?;
+    Ok(r.list)
+}
@@ -2,6 +2,7 @@
 
 pub mod album;
 pub mod artist;
+pub mod discover;
 pub mod login;
 pub mod lyric;
 pub mod playlist;
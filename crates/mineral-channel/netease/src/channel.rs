@@ -8,15 +8,15 @@ use async_trait::async_trait;
 use color_eyre::eyre::eyre;
 use isahc::cookies::{Cookie, CookieJar};
 use mineral_channel_core::{
-    ArtistSectionKind, ArtistSections, ChannelCaps, Credential, Error, MusicChannel, Page, Result,
-    SearchHits,
+    ArtistSectionKind, ArtistSections, ChannelCaps, Credential, DiscoverKind, Error, MusicChannel,
+    Page, Result, SearchHits,
 };
 use mineral_model::{
     Album, AlbumId, Artist, ArtistId, BitRate, Lyrics, PlayUrl, Playlist, PlaylistId, SearchKind,
     Song, SongId, SourceKind, UserId,
 };
 use mineral_persist::ServerStore;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::error::ApiCodeError;
 
@@ -123,6 +123,21 @@ impl NeteaseChannel {
     pub fn transport(&self) -> &Transport {
         &self.transport
     }
+
+    /// 只有 id 的发现面结果(FM / 相似歌曲)经 song/detail 补全,并按端点给的推荐序排回
+    /// (detail 不保证回序);空列表不发请求。
+    async fn detail_in_order(&self, ids: &[SongId]) -> Result<Vec<Song>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut by_id = self
+            .songs_detail(ids)
+            .await?
+            .into_iter()
+            .map(|s| (s.id.clone(), s))
+            .collect::<FxHashMap<SongId, Song>>();
+        Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+    }
 }
 
 /// 把 api 层的 `color_eyre::Report` 收敛到 channel-core 错误。
@@ -152,6 +167,16 @@ impl MusicChannel for NeteaseChannel {
     }
 
     fn caps(&self) -> ChannelCaps {
+        // 发现面:每日推荐 / 私人 FM 是个性化端点,登录(有 uid)才声明;相似与排行榜公开。
+        let mut discover = Vec::<DiscoverKind>::new();
+        if self.user_id.is_some() {
+            discover.extend([DiscoverKind::DailySongs, DiscoverKind::PersonalFm]);
+        }
+        discover.extend([
+            DiscoverKind::SimilarSongs,
+            DiscoverKind::SimilarArtists,
+            DiscoverKind::Toplists,
+        ]);
         ChannelCaps::builder()
             .searchable(vec![
                 SearchKind::Song,
@@ -169,6 +194,7 @@ impl MusicChannel for NeteaseChannel {
             .playlist_web_url(Some("https://music.163.com/playlist?id={id}".to_owned()))
             .album_web_url(Some("https://music.163.com/album?id={id}".to_owned()))
             .artist_web_url(Some("https://music.163.com/artist?id={id}".to_owned()))
+            .discover(discover)
             .build()
     }
 
@@ -489,6 +515,53 @@ impl MusicChannel for NeteaseChannel {
             .map_err(Error::Other)
     }
 
+    async fn daily_songs(&self) -> Result<Vec<Song>> {
+        if self.user_id.is_none() {
+            return Err(Error::NotSupported);
+        }
+        let dtos = api::discover::daily_songs(&self.transport)
+            .await
+            .map_err(map_err)?;
+        Ok(dtos.into_iter().map(convert::album_song_to_model).collect())
+    }
+
+    async fn personal_fm(&self) -> Result<Vec<Song>> {
+        if self.user_id.is_none() {
+            return Err(Error::NotSupported);
+        }
+        let ids = api::discover::personal_fm(&self.transport)
+            .await
+            .map_err(map_err)?;
+        self.detail_in_order(&ids).await
+    }
+
+    async fn similar_songs(&self, id: &SongId) -> Result<Vec<Song>> {
+        let ids = api::discover::similar_songs(&self.transport, id)
+            .await
+            .map_err(map_err)?;
+        self.detail_in_order(&ids).await
+    }
+
+    async fn similar_artists(&self, id: &ArtistId) -> Result<Vec<Artist>> {
+        let dtos = api::discover::similar_artists(&self.transport, id)
+            .await
+            .map_err(map_err)?;
+        Ok(dtos
+            .into_iter()
+            .map(convert::search_artist_to_model)
+            .collect())
+    }
+
+    async fn toplists(&self) -> Result<Vec<Playlist>> {
+        let lists = api::discover::toplists(&self.transport)
+            .await
+            .map_err(map_err)?;
+        Ok(lists
+            .iter()
+            .map(|info| convert::playlist_info_to_model(info, Vec::new()))
+            .collect())
+    }
+
     // on_played 打点职责移交 daemon 的 StatsRecorder(见 mineral-server::stats):channel
     // 不再本地落库,回到 trait 默认空实现(其他源不实现也不丢数据)。
 }
@@ -540,6 +613,37 @@ mod tests {
         Ok(())
     }
 
+    /// 发现面随登录态:匿名只声明公开的相似 / 排行榜,个性化的每日推荐 / FM 调用即
+    /// NotSupported(不发请求)。
+    #[tokio::test]
+    async fn personalised_discover_requires_login() -> color_eyre::Result<()> {
+        use mineral_channel_core::DiscoverKind;
+
+        let config = NeteaseConfig::builder()
+            .max_connections(0)
+            .proxy(None)
+            .timeout_secs(100)
+            .build();
+        let channel = NeteaseChannel::new(&config, ServerStore::disabled())?;
+        assert_eq!(
+            channel.caps().discover().as_slice(),
+            &[
+                DiscoverKind::SimilarSongs,
+                DiscoverKind::SimilarArtists,
+                DiscoverKind::Toplists
+            ]
+        );
+        assert!(matches!(
+            channel.daily_songs().await,
+            Err(Error::NotSupported)
+        ));
+        assert!(matches!(
+            channel.personal_fm().await,
+            Err(Error::NotSupported)
+        ));
+        Ok(())
+    }
+
     /// favorite 方法收窄为**纯远端**:匿名 channel(未登录)无远端可查/可打,
     /// `liked_song_ids` 与 `set_loved` 都返回 [`Error::NotSupported`]。
     ///
@@ -1,8 +1,8 @@
 //! 网易原生 DTO → `mineral_model` 类型的转换 helper。
 
 use mineral_model::{
-    Album, AlbumId, AlbumRef, Artist, ArtistId, ArtistRef, AudioFormat, BitRate, MediaUrl, PlayUrl,
-    Playlist, PlaylistId, Song, SongId, SourceKind,
+    Album, AlbumId, AlbumRef, Artist, ArtistId, ArtistRef, AudioFormat, BitRate, CST_OFFSET_MS,
+    MediaUrl, PlayUrl, Playlist, PlaylistId, Song, SongId, SourceKind, release_year,
 };
 
 use crate::wire::artist::{ArtistAlbum, ArtistDetailResult};
@@ -62,11 +62,24 @@ pub(crate) fn album_dto_to_model(a: SearchAlbum, songs: Vec<Song>) -> Album {
 }
 
 /// 专辑详情响应(元信息 + 曲目)→ 统一 [`Album`]。
+///
+/// 曲目的专辑艺人取专辑主艺人:单曲详情不带专辑艺人,只有专辑详情里才有真值。
 pub(crate) fn album_detail_to_model(r: AlbumDetailResult) -> Album {
+    let album_artist = r
+        .album
+        .artists
+        .first()
+        .or(r.album.artist.as_ref())
+        .map(|a| a.name.clone())
+        .filter(|n| !n.is_empty());
     let songs = r
         .songs
         .into_iter()
-        .map(album_song_to_model)
+        .map(|s| {
+            let mut song = album_song_to_model(s);
+            song.album_artist.clone_from(&album_artist);
+            song
+        })
         .collect::<Vec<Song>>();
     album_dto_to_model(r.album, songs)
 }
@@ -115,6 +128,8 @@ pub(crate) fn album_song_to_model(s: AlbumSong) -> Song {
         }))
         // 接口用 dt=0 表示时长未知,在 channel 边界转成 None,0 哨兵不进模型。
         .duration_ms((s.dt > 0).then_some(s.dt))
+        .track_no((s.no > 0).then_some(s.no))
+        .year(release_year(s.publish_time, CST_OFFSET_MS))
         .cover_url(s.al.pic_url.as_deref().and_then(parse_remote))
         .unavailable(s.privilege.as_ref().is_some_and(|p| p.st < 0))
         .build()
@@ -400,7 +415,8 @@ mod tests {
         Ok(())
     }
 
-    /// 专辑详情(顶层元信息 + 曲目)→ model:简介 / track_count / 曲目 / 封面 / id 都到位。
+    /// 专辑详情(顶层元信息 + 曲目)→ model:简介 / track_count / 曲目 / 封面 / id 都到位,
+    /// 曲目带上专辑艺人与发行年。
     /// 锁住"详情端点独家给的 description 不再被丢"这一重构要点。
     #[test]
     fn album_detail_maps_meta_and_songs() -> color_eyre::Result<()> {
@@ -415,11 +431,22 @@ mod tests {
             "songs": [
                 { "id": 1, "name": "电动少女",
                   "ar": [{ "id": 1_081_839, "name": "Chinese Football" }],
-                  "al": { "id": 3_314_467, "name": "Chinese Football" }, "dt": 310_000 }
+                  "al": { "id": 3_314_467, "name": "Chinese Football" }, "dt": 310_000,
+                  "publishTime": 1_443_196_800_000_i64 }
             ]
         });
         let dto: AlbumDetailResult = from_value(raw)?;
         let album = album_detail_to_model(dto);
+        let song = album
+            .songs
+            .first()
+            .ok_or_else(|| color_eyre::eyre::eyre!("应有一首"))?;
+        assert_eq!(
+            song.album_artist.as_deref(),
+            Some("Chinese Football"),
+            "专辑艺人取专辑主艺人"
+        );
+        assert_eq!(song.year, Some(2015), "publishTime 按北京时间取年");
         assert_eq!(album.name, "Chinese Football");
         assert_eq!(album.description, "成军四年的首张全长专辑。");
         assert_eq!(album.track_count, Some(13));
@@ -0,0 +1,125 @@
+//! 发现面端点(每日推荐 / 私人 FM / 相似 / 排行榜)的响应结构。
+
+use serde::Deserialize;
+
+use super::de::null_or_vec_skip_null;
+use super::playlist::PlaylistInfo;
+use super::search::SearchArtist;
+use super::song::AlbumSong;
+
+/// `/weapi/v3/discovery/recommend/songs` 的响应:曲目在 `data.dailySongs`。
+#[derive(Debug, Deserialize)]
+pub struct DailySongsResult {
+    /// 推荐数据块(未登录 / 异常响应缺失 → `None`)。
+    #[serde(default)]
+    pub data: Option<DailySongsData>,
+}
+
+/// 每日推荐的 `data` 块。
+#[derive(Debug, Deserialize)]
+pub struct DailySongsData {
+    /// 当日推荐曲目(`ar`/`al`/`dt` 形态,权限块内联)。
+    #[serde(
+        default,
+        rename = "dailySongs",
+        deserialize_with = "null_or_vec_skip_null"
+    )]
+    pub daily_songs: Vec<AlbumSong>,
+}
+
+/// 只取 id 的歌曲项。FM / 相似歌曲端点给的是旧版 `artists`/`album`/`duration` 形态,
+/// 与 [`AlbumSong`] 不同形,统一只取 id 再经 song/detail 重拉,映射只维护一套。
+#[derive(Debug, Deserialize)]
+pub struct SongRef {
+    /// 歌曲 ID。
+    pub id: i64,
+}
+
+/// `/weapi/v1/radio/get`(私人 FM)的响应:本批曲目在 `data`。
+#[derive(Debug, Deserialize)]
+pub struct PersonalFmResult {
+    /// 本批曲目。
+    #[serde(default, deserialize_with = "null_or_vec_skip_null")]
+    pub data: Vec<SongRef>,
+}
+
+/// `/weapi/v1/discovery/simiSong` 的响应。
+#[derive(Debug, Deserialize)]
+pub struct SimilarSongsResult {
+    /// 相似曲目。
+    #[serde(default, deserialize_with = "null_or_vec_skip_null")]
+    pub songs: Vec<SongRef>,
+}
+
+/// `/weapi/discovery/simiArtist` 的响应:艺人对象与搜索结果同形,复用 [`SearchArtist`]。
+#[derive(Debug, Deserialize)]
+pub struct SimilarArtistsResult {
+    /// 相似艺人。
+    #[serde(default, deserialize_with = "null_or_vec_skip_null")]
+    pub artists: Vec<SearchArtist>,
+}
+
+/// `/weapi/toplist` 的响应:榜单对象是 [`PlaylistInfo`] 的子集(元信息,无曲目)。
+#[derive(Debug, Deserialize)]
+pub struct ToplistResult {
+    /// 全部榜单(官方榜在前)。
+    #[serde(default)]
+    pub list: Vec<PlaylistInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DailySongsResult, PersonalFmResult, ToplistResult};
+    use crate::wire::de::from_value;
+
+    /// 每日推荐:曲目在 `data.dailySongs`,内联权限块随曲目带出。
+    #[test]
+    fn daily_songs_parse_nested_data() -> color_eyre::Result<()> {
+        let r: DailySongsResult = from_value(serde_json::json!({
+            "code": 200,
+            "data": { "dailySongs": [
+                { "id": 1, "name": "a", "ar": [{ "id": 5, "name": "ar" }],
+                  "al": { "id": 7, "name": "al" }, "dt": 1000,
+                  "privilege": { "id": 1, "st": 0 } }
+            ] }
+        }))?;
+        let songs = r.data.map(|d| d.daily_songs).unwrap_or_default();
+        assert_eq!(songs.len(), 1);
+        assert!(songs.first().is_some_and(|s| s.privilege.is_some()));
+        Ok(())
+    }
+
+    /// FM 旧形态曲目只取 id;缺 `data` → 空批,不炸。
+    #[test]
+    fn personal_fm_keeps_ids_only() -> color_eyre::Result<()> {
+        let r: PersonalFmResult = from_value(serde_json::json!({
+            "data": [
+                { "id": 11, "name": "x", "artists": [{ "id": 1, "name": "y" }],
+                  "album": { "id": 2, "name": "z" }, "duration": 1000 },
+                null
+            ]
+        }))?;
+        assert_eq!(r.data.iter().map(|s| s.id).collect::<Vec<_>>(), vec![11]);
+        let empty: PersonalFmResult = from_value(serde_json::json!({ "code": 200 }))?;
+        assert!(empty.data.is_empty());
+        Ok(())
+    }
+
+    /// 榜单列表按歌单元信息解析(无 tracks 字段)。
+    #[test]
+    fn toplist_parses_playlist_meta() -> color_eyre::Result<()> {
+        let r: ToplistResult = from_value(serde_json::json!({
+            "list": [
+                { "id": 3778678, "name": "热歌榜", "trackCount": 200,
+                  "coverImgUrl": "https://p1.music.126.net/x.jpg", "updateFrequency": "每周四更新" }
+            ]
+        }))?;
+        let first = r
+            .list
+            .first()
+            .ok_or_else(|| color_eyre::eyre::eyre!("应有一项"))?;
+        assert_eq!(first.id, 3778678);
+        assert_eq!(first.track_count, 200);
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@
 pub mod artist;
 pub mod common;
 pub mod de;
+pub mod discover;
 pub mod playlist;
 pub mod search;
 pub mod song;
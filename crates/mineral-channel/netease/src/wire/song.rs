@@ -74,6 +74,14 @@ pub struct AlbumSong {
     #[serde(default)]
     pub dt: u64,
 
+    /// 专辑内音轨号(字段名为 `no`);缺失 / `0` 表示未知。
+    #[serde(default)]
+    pub no: u32,
+
+    /// 发行时间(毫秒时间戳,北京时间零点;字段名 `publishTime`);缺失 / `0` 表示未知。
+    #[serde(default, rename = "publishTime")]
+    pub publish_time: i64,
+
     /// 权限块(cloudsearch 内联;detail/歌单端点缺,由 [`merge_privileges`] 补)。
     #[serde(default)]
     pub privilege: Option<Privilege>,
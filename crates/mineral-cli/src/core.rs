@@ -5,9 +5,14 @@ use color_eyre::eyre::{WrapErr, bail};
 use tokio::runtime::Runtime;
 
 use crate::subcommands::action;
+use crate::subcommands::audio::{self, AudioCommand};
 use crate::subcommands::cache::{self, CacheCommand};
 use crate::subcommands::channel::{self, ChannelArgs};
 use crate::subcommands::config::{self, ConfigCommand};
+use crate::subcommands::library::{self, LibraryCommand};
+use crate::subcommands::playback::{self, PlaybackCommand};
+use crate::subcommands::queue::{self, QueueCommand};
+use crate::subcommands::search::{self, SearchArgs};
 use crate::subcommands::stats::{self, StatsCommand};
 use crate::subcommands::{status, stop};
 
@@ -49,6 +54,13 @@ pub enum Command {
         args: Vec<String>,
     },
 
+    /// 音频输出设备
+    Audio {
+        /// audio 下的具体子命令
+        #[command(subcommand)]
+        cmd: AudioCommand,
+    },
+
     /// 缓存管理
     Cache {
         /// cache 下的具体子命令。
@@ -66,6 +78,27 @@ pub enum Command {
         cmd: ConfigCommand,
     },
 
+    /// 本地曲库管理
+    Library {
+        /// library 下的具体子命令
+        #[command(subcommand)]
+        cmd: LibraryCommand,
+    },
+
+    /// 播放控制(play / pause / next / seek / volume …),平铺在顶层便于快捷键绑定
+    #[command(flatten)]
+    Playback(PlaybackCommand),
+
+    /// 播放队列
+    Queue {
+        /// queue 下的具体子命令
+        #[command(subcommand)]
+        cmd: QueueCommand,
+    },
+
+    /// 搜索歌曲(`--play` 直接起播)
+    Search(SearchArgs),
+
     /// 启动后台播放 daemon
     Serve,
 
@@ -101,9 +134,14 @@ pub fn run(command: Command) -> color_eyre::Result<()> {
 async fn run_async(command: Command) -> color_eyre::Result<()> {
     match command {
         Command::Action { name, args } => action::run(&name, &args).await,
+        Command::Audio { cmd } => audio::run(cmd).await,
         Command::Cache { cmd } => cache::run(cmd).await,
         Command::Channel(args) => channel::run(args).await,
         Command::Config { cmd } => config::run(cmd).await,
+        Command::Library { cmd } => library::run(cmd).await,
+        Command::Playback(cmd) => playback::run(cmd).await,
+        Command::Queue { cmd } => queue::run(cmd).await,
+        Command::Search(args) => search::run(args).await,
         Command::Stats { cmd } => stats::run(cmd).await,
         Command::Status => status::run().await,
         Command::Stop => stop::run().await,
@@ -4,5 +4,7 @@ mod core;
 mod subcommands;
 
 pub use crate::core::{Args, Command, run};
-pub use crate::subcommands::channel::{bilibili_config_from, netease_config_from};
-pub use crate::subcommands::serve::run as serve_run;
+pub use crate::subcommands::channel::{
+    bilibili_config_from, local_config_from, netease_config_from,
+};
+pub use crate::subcommands::serve::{open_stats, run as serve_run};
@@ -0,0 +1,104 @@
+//! `mineral audio` 子命令树:`devices`(列输出设备)与 `use`(运行期热切换输出设备)。
+//!
+//! `devices` 优先问 daemon(能标出正在用的设备);daemon 没跑时进程内直接枚举。`use` 只对
+//! 在跑的 daemon 有意义(不落盘;要长期钉住写 `audio.device`)。
+
+use clap::Subcommand;
+use color_eyre::eyre::bail;
+use mineral_audio::OutputDevice;
+use mineral_protocol::{OneshotClient, Request, Response};
+
+/// 音频输出。
+#[derive(Debug, Subcommand)]
+pub enum AudioCommand {
+    /// 列出可用输出设备(`*` = 正在用)
+    Devices,
+
+    /// 切换 daemon 的输出设备,播放位置不变(不落盘)
+    Use {
+        /// 设备名(见 `mineral audio devices`);省略 = 跟随系统默认设备
+        name: Option<String>,
+    },
+}
+
+/// `mineral audio` 入口。
+///
+/// # Params:
+///   - `cmd`: 已解析的子命令
+///
+/// # Return:
+///   执行结果;`use` 在 daemon 未跑 / 设备名不存在时报错。
+pub async fn run(cmd: AudioCommand) -> color_eyre::Result<()> {
+    match cmd {
+        AudioCommand::Devices => {
+            let devices = list().await?;
+            if devices.is_empty() {
+                println!("没有可用的输出设备");
+            }
+            for line in devices.iter().map(render_device) {
+                println!("{line}");
+            }
+            Ok(())
+        }
+        AudioCommand::Use { name } => {
+            let socket_path = mineral_paths::socket_path()?;
+            let mut client = OneshotClient::connect(&socket_path).await?;
+            match client
+                .request(Request::SetAudioDevice(name.clone()))
+                .await?
+            {
+                Response::Ok => {}
+                Response::Error(msg) => bail!("daemon error: {msg}"),
+                other => bail!("unexpected response: {other:?}"),
+            }
+            println!("输出设备:{}", name.as_deref().unwrap_or("系统默认"));
+            Ok(())
+        }
+    }
+}
+
+/// 取设备列表:daemon 在跑问 daemon(带 `active` 标注),否则进程内枚举。
+async fn list() -> color_eyre::Result<Vec<OutputDevice>> {
+    let socket_path = mineral_paths::socket_path()?;
+    // 连不上 = daemon 不在跑,本地枚举即可;握手失败(版本不匹配等)照常报错。
+    let Ok(stream) = tokio::net::UnixStream::connect(&socket_path).await else {
+        return mineral_audio::output_devices();
+    };
+    let mut client = OneshotClient::from_stream(stream).await?;
+    match client.request(Request::AudioDevices).await? {
+        Response::AudioDevices(devices) => Ok(devices),
+        Response::Error(msg) => bail!("daemon error: {msg}"),
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
+
+/// 一行设备:`* USB DAC (default)`。
+fn render_device(device: &OutputDevice) -> String {
+    let mark = if device.active { '*' } else { ' ' };
+    let default = if device.is_default { " (default)" } else { "" };
+    format!("{mark} {}{default}", device.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_audio::OutputDevice;
+
+    use super::render_device;
+
+    /// 正在用的设备打 `*`,系统默认设备带 `(default)`。
+    #[test]
+    fn renders_markers() {
+        let line = render_device(&OutputDevice {
+            name: "USB DAC".to_owned(),
+            is_default: true,
+            active: true,
+        });
+        assert_eq!(line, "* USB DAC (default)");
+        let line = render_device(&OutputDevice {
+            name: "HDMI".to_owned(),
+            is_default: false,
+            active: false,
+        });
+        assert_eq!(line, "  HDMI");
+    }
+}
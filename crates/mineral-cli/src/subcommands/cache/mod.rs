@@ -2,6 +2,6 @@
 //! `clean`(清理可重建缓存并展示清理效果)。两者都直接读存储,不经 daemon。
 
 mod command;
-mod render;
+pub(crate) mod render;
 
 pub use command::{CacheCommand, run};
@@ -421,7 +421,7 @@ fn occupancy(used: u64, capacity: Option<u64>) -> Option<(u64, String)> {
 }
 
 /// 字节数 → 人读字符串(B / KiB / MiB / GiB,一位小数)。
-fn human_bytes(bytes: u64) -> String {
+pub(crate) fn human_bytes(bytes: u64) -> String {
     const KIB: u64 = 1024;
     const MIB: u64 = 1024 * KIB;
     const GIB: u64 = 1024 * MIB;
@@ -440,7 +440,7 @@ fn human_bytes(bytes: u64) -> String {
 }
 
 /// 相对时间:`刚刚` / `N 分钟前` / `N 小时前` / `N 天前`。时光倒流(now < t)记为 `刚刚`。
-fn relative_age(now: SystemTime, t: SystemTime) -> String {
+pub(crate) fn relative_age(now: SystemTime, t: SystemTime) -> String {
     let secs = now.duration_since(t).map(|d| d.as_secs()).unwrap_or(0);
     if secs < 60 {
         "刚刚".to_owned()
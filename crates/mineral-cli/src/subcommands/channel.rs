@@ -3,6 +3,7 @@
 use clap::{Args as ClapArgs, Subcommand};
 use mineral_channel_bilibili::BilibiliConfig;
 use mineral_channel_bilibili::cli::BilibiliCli;
+use mineral_channel_local::LocalConfig;
 use mineral_channel_netease::NeteaseConfig;
 use mineral_channel_netease::cli::NeteaseCli;
 
@@ -79,3 +80,27 @@ pub fn bilibili_config_from(section: &mineral_config::BilibiliSection) -> Bilibi
         .timeout_secs(*section.timeout_secs())
         .build()
 }
+
+/// 把配置的本地源段映射成构造参数(同 [`netease_config_from`],消费侧一次显式映射)。
+///
+/// `~/` 开头的根在此展开;内嵌封面抽到 `<cache>/local-cover`,缓存目录取不到时不抽
+/// (只认同目录封面图),不为封面阻断本地源。
+///
+/// # Params:
+///   - `section`: 配置的 `sources["local"]` 段
+///
+/// # Return:
+///   本地源构造参数;家目录取不到(根里有 `~`)为 `Err`。
+pub fn local_config_from(
+    section: &mineral_config::LocalSection,
+) -> color_eyre::Result<LocalConfig> {
+    let roots = section
+        .roots()
+        .iter()
+        .map(|r| mineral_paths::expand_home(r))
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    Ok(LocalConfig::builder()
+        .roots(roots)
+        .cover_dir(mineral_paths::local_cover_dir().ok())
+        .build())
+}
@@ -0,0 +1,267 @@
+//! `mineral library` 子命令:本地曲库的重扫(`scan`)与索引状态(`status`)。
+//!
+//! `scan` 优先交给在跑的 daemon(走 LocalScan lane,扫完 daemon 自己刷新本地歌单);
+//! 没有 daemon 时在本进程内直接扫,结果写进同一个索引库。`status` 只读索引库,不经 daemon。
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Subcommand;
+use color_eyre::eyre::{WrapErr, bail};
+use mineral_channel_core::LibraryScanReport;
+use mineral_channel_local::LocalChannel;
+use mineral_persist::{LocalLibraryStats, LocalScanRecord, ServerStore};
+use mineral_protocol::{OneshotClient, Request, Response};
+use mineral_task::{Lane, Priority, TaskKind};
+
+use super::cache::render::{human_bytes, relative_age};
+
+/// 轮询 daemon 扫描进度的间隔。
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 本地曲库管理。
+#[derive(Debug, Subcommand)]
+pub enum LibraryCommand {
+    /// 重扫本地曲库(默认增量:只读新增 / 变动的文件)
+    Scan {
+        /// 全量重扫:忽略索引,重读全部文件的标签
+        #[arg(long)]
+        full: bool,
+    },
+
+    /// 展示本地曲库索引状态
+    Status,
+}
+
+/// 按 [`LibraryCommand`] 分发到具体实现。
+///
+/// # Params:
+///   - `command`: 已解析的 library 子命令。
+///
+/// # Return:
+///   命令执行结果。
+pub async fn run(command: LibraryCommand) -> color_eyre::Result<()> {
+    match command {
+        LibraryCommand::Scan { full } => scan(full).await,
+        LibraryCommand::Status => status().await,
+    }
+}
+
+/// `library scan`:有 daemon 交给 daemon,否则进程内扫。
+///
+/// # Params:
+///   - `full`: 是否全量重扫。
+///
+/// # Return:
+///   扫描完成并打印回执返回 `Ok(())`。
+async fn scan(full: bool) -> color_eyre::Result<()> {
+    let persist = open_store().await?;
+    match OneshotClient::connect(&mineral_paths::socket_path()?).await {
+        Ok(client) => scan_via_daemon(client, &persist, full).await,
+        Err(e) => {
+            mineral_log::debug!(target: "cli", error = mineral_log::chain(&e), "daemon 不可达,进程内扫描");
+            scan_in_process(&persist, full).await
+        }
+    }
+}
+
+/// 提交 LocalScan 任务并轮询到 lane 空闲,再从索引库读回执。
+///
+/// daemon 没注册本地源时任务立即失败、回执不会更新——据此给出明确报错。
+///
+/// # Params:
+///   - `client`: 已握手的 daemon 连接
+///   - `persist`: 索引库(daemon 与 CLI 共用同一文件)
+///   - `full`: 是否全量重扫
+///
+/// # Return:
+///   扫描完成返回 `Ok(())`。
+async fn scan_via_daemon(
+    mut client: OneshotClient,
+    persist: &ServerStore,
+    full: bool,
+) -> color_eyre::Result<()> {
+    let before = persist.local_library().stats().await?.last_scan;
+    match client
+        .request(Request::SubmitTask(
+            TaskKind::LocalScan { full },
+            Priority::User,
+        ))
+        .await?
+    {
+        Response::TaskId(_) => {}
+        Response::Error(msg) => bail!("daemon error: {msg}"),
+        other => bail!("unexpected response: {other:?}"),
+    }
+    loop {
+        let snap = match client.request(Request::TaskSnapshot).await? {
+            Response::TaskSnapshot(snap) => snap,
+            Response::Error(msg) => bail!("daemon error: {msg}"),
+            other => bail!("unexpected response: {other:?}"),
+        };
+        if snap.by_lane.get(&Lane::LocalScan).copied().unwrap_or(0) == 0 {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    let stats = persist.local_library().stats().await?;
+    match &stats.last_scan {
+        Some(record) if stats.last_scan != before => {
+            println!("{}", render_record(record));
+            Ok(())
+        }
+        _ => bail!(
+            "daemon 未完成扫描:本地源未启用(sources.local.roots 为空)或扫描失败,详见 daemon 日志"
+        ),
+    }
+}
+
+/// 无 daemon:按配置在本进程内构造本地 channel 直接扫。
+///
+/// # Params:
+///   - `persist`: 索引库
+///   - `full`: 是否全量重扫
+///
+/// # Return:
+///   扫描完成返回 `Ok(())`。
+async fn scan_in_process(persist: &ServerStore, full: bool) -> color_eyre::Result<()> {
+    let (config, _warnings) =
+        mineral_config::load(&mineral_paths::config_dir()?.join("config.lua"))?;
+    let section = config.sources().local();
+    if section.roots().is_empty() {
+        bail!("本地源未启用:请先在 config.lua 的 sources.local.roots 里配置扫描根");
+    }
+    let lc = crate::local_config_from(section).wrap_err("解析本地源扫描根失败")?;
+    let report = LocalChannel::new(lc, persist).rescan(full).await?;
+    println!("{}", render_report(&report, full));
+    Ok(())
+}
+
+/// `library status`:展示配置的扫描根与索引总览。
+///
+/// # Return:
+///   打印成功返回 `Ok(())`。
+async fn status() -> color_eyre::Result<()> {
+    let (config, _warnings) =
+        mineral_config::load(&mineral_paths::config_dir()?.join("config.lua"))?;
+    let roots = crate::local_config_from(config.sources().local())
+        .wrap_err("解析本地源扫描根失败")?
+        .roots()
+        .iter()
+        .map(|r| r.display().to_string())
+        .collect::<Vec<String>>();
+    let stats = open_store().await?.local_library().stats().await?;
+    println!("{}", render_status(&roots, &stats, SystemTime::now()));
+    Ok(())
+}
+
+/// 打开 daemon 同一份 server 库(fresh env 下先建 data_dir,同 `cache status`)。
+async fn open_store() -> color_eyre::Result<ServerStore> {
+    let data_dir = mineral_paths::data_dir()?;
+    std::fs::create_dir_all(&data_dir)
+        .wrap_err_with(|| format!("create data dir {}", data_dir.display()))?;
+    ServerStore::open(&data_dir.join("mineral.db")).await
+}
+
+/// 进程内扫描回执 → 一行摘要。
+fn render_report(r: &LibraryScanReport, full: bool) -> String {
+    format!(
+        "{} scan: {} tracks  (+{} added, ~{} updated, -{} removed, {} unchanged)  {} ms",
+        scan_mode(full),
+        r.tracks,
+        r.added,
+        r.updated,
+        r.removed,
+        r.unchanged,
+        r.elapsed_ms
+    )
+}
+
+/// 索引库里的扫描回执 → 一行摘要(daemon 路径;回执不含 unchanged)。
+fn render_record(r: &LocalScanRecord) -> String {
+    format!(
+        "{} scan: {} tracks  (+{} added, ~{} updated, -{} removed)  {} ms",
+        scan_mode(r.full),
+        r.tracks,
+        r.added,
+        r.updated,
+        r.removed,
+        r.elapsed_ms
+    )
+}
+
+/// 扫描模式名。
+fn scan_mode(full: bool) -> &'static str {
+    if full { "full" } else { "incremental" }
+}
+
+/// 状态报告:扫描根、索引规模、最近一次扫描。
+///
+/// # Params:
+///   - `roots`: 配置的扫描根(已展开 `~`)
+///   - `stats`: 索引总览
+///   - `now`: 当前时刻(相对时间基准)
+///
+/// # Return:
+///   多行 key/value 文本。
+fn render_status(roots: &[String], stats: &LocalLibraryStats, now: SystemTime) -> String {
+    let roots = if roots.is_empty() {
+        "(none — set sources.local.roots in config.lua)".to_owned()
+    } else {
+        roots.join("\n            ")
+    };
+    let last = stats.last_scan.as_ref().map_or_else(
+        || "never".to_owned(),
+        |r| {
+            let at = u64::try_from(r.finished_at_ms)
+                .map(|ms| UNIX_EPOCH + Duration::from_millis(ms))
+                .unwrap_or(UNIX_EPOCH);
+            format!("{} — {}", relative_age(now, at), render_record(r))
+        },
+    );
+    format!(
+        "roots:      {roots}\ntracks:     {}\nsize:       {}\nlast scan:  {last}",
+        stats.tracks,
+        human_bytes(stats.bytes),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use mineral_persist::{LocalLibraryStats, LocalScanRecord};
+
+    use super::render_status;
+
+    /// 状态报告:多根换行对齐、字节人读、最近扫描带相对时间与计数;从未扫描显示 never。
+    #[test]
+    fn status_renders_roots_size_and_last_scan() {
+        let stats = LocalLibraryStats {
+            tracks: 12,
+            bytes: 3 * 1024 * 1024,
+            last_scan: Some(LocalScanRecord {
+                finished_at_ms: 1_000_000,
+                full: false,
+                tracks: 12,
+                added: 2,
+                updated: 1,
+                removed: 0,
+                elapsed_ms: 40,
+            }),
+        };
+        let now = UNIX_EPOCH + Duration::from_millis(1_000_000) + Duration::from_secs(120);
+        let out = render_status(&["/m/a".to_owned(), "/m/b".to_owned()], &stats, now);
+        assert_eq!(
+            out,
+            "roots:      /m/a\n            /m/b\ntracks:     12\nsize:       3.0 MiB\n\
+             last scan:  2 分钟前 — incremental scan: 12 tracks  (+2 added, ~1 updated, -0 removed)  40 ms"
+        );
+
+        let empty = LocalLibraryStats {
+            tracks: 0,
+            bytes: 0,
+            last_scan: None,
+        };
+        assert!(render_status(&[], &empty, now).ends_with("last scan:  never"));
+    }
+}
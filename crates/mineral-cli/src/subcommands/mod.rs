@@ -1,9 +1,15 @@
 //! 各个 CLI namespace 的子命令树。
 
 pub mod action;
+pub mod audio;
 pub mod cache;
 pub mod channel;
 pub mod config;
+pub mod library;
+pub mod playback;
+pub mod queue;
+pub mod remote;
+pub mod search;
 pub mod serve;
 pub mod stats;
 pub mod status;
@@ -0,0 +1,302 @@
+//! 播放控制子命令:`play` / `pause` / `toggle` / `next` / `prev` / `seek` / `volume` /
+//! `mode` / `love`,供 tmux / i3 等快捷键直接绑定(不必经 Lua `mineral.action` 垫一层)。
+//!
+//! 全部翻成已有请求发给 daemon;执行后回打一次播放现状(`--json` 出结构化形态)。
+//! 相对量(`seek +10` / `volume -5`)先拉快照再算绝对值——daemon 只收绝对值。
+
+use clap::Subcommand;
+use color_eyre::eyre::bail;
+use mineral_protocol::{OneshotClient, PlayMode, Request, Response};
+
+use super::remote::{NowPlaying, OutputArgs, call, call_ok, connect, song_title};
+
+/// 播放控制。
+#[derive(Debug, Subcommand)]
+pub enum PlaybackCommand {
+    /// 开始 / 恢复播放(没有在播曲时从队列当前位置起播)
+    Play(OutputArgs),
+
+    /// 暂停
+    Pause(OutputArgs),
+
+    /// 播放 / 暂停切换
+    Toggle(OutputArgs),
+
+    /// 下一首
+    Next(OutputArgs),
+
+    /// 上一首(已播过开头一段时回到本曲开头)
+    Prev(OutputArgs),
+
+    /// 跳转:`1:30` 到绝对位置,`+10` / `-0:15` 相对当前位置(秒 / 分:秒 / 时:分:秒)
+    Seek {
+        /// 目标位置;带 `+` / `-` 前缀为相对量
+        #[arg(allow_hyphen_values = true, value_parser = parse_seek)]
+        time: Adjust,
+
+        /// 输出形态
+        #[command(flatten)]
+        out: OutputArgs,
+    },
+
+    /// 音量:`60` 设为绝对值,`+5` / `-5` 相对调整(0–100)
+    Volume {
+        /// 目标音量;带 `+` / `-` 前缀为相对量
+        #[arg(allow_hyphen_values = true, value_parser = parse_volume)]
+        value: Adjust,
+
+        /// 输出形态
+        #[command(flatten)]
+        out: OutputArgs,
+    },
+
+    /// 播放模式:sequential / shuffle / repeat-all / repeat-one
+    Mode {
+        /// 目标模式
+        #[arg(value_parser = parse_mode)]
+        mode: PlayMode,
+
+        /// 输出形态
+        #[command(flatten)]
+        out: OutputArgs,
+    },
+
+    /// 收藏 / 取消收藏当前曲
+    Love(OutputArgs),
+}
+
+/// 绝对值或相对量(`seek` 单位 ms,`volume` 单位百分点)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjust {
+    /// 设为该值。
+    Set(u64),
+
+    /// 在当前值上增减。
+    By(i64),
+}
+
+impl Adjust {
+    /// 落成绝对值,钳进 `[0, max]`。
+    ///
+    /// # Params:
+    ///   - `current`: 当前值(相对量的基准)
+    ///   - `max`: 上限
+    fn resolve(self, current: u64, max: u64) -> u64 {
+        let target = match self {
+            Self::Set(v) => v,
+            Self::By(d) if d >= 0 => current.saturating_add(d.unsigned_abs()),
+            Self::By(d) => current.saturating_sub(d.unsigned_abs()),
+        };
+        target.min(max)
+    }
+}
+
+/// 按 [`PlaybackCommand`] 分发。
+///
+/// # Params:
+///   - `cmd`: 已解析的播放控制子命令
+///
+/// # Return:
+///   执行结果;daemon 没跑 / 无在播曲而命令需要它时报错。
+pub async fn run(cmd: PlaybackCommand) -> color_eyre::Result<()> {
+    let mut client = connect().await?;
+    let out = match cmd {
+        PlaybackCommand::Play(out) => {
+            play(&mut client).await?;
+            out
+        }
+        PlaybackCommand::Pause(out) => {
+            call_ok(&mut client, Request::Pause).await?;
+            out
+        }
+        PlaybackCommand::Toggle(out) => {
+            if NowPlaying::fetch(&mut client).await?.snap.playing {
+                call_ok(&mut client, Request::Pause).await?;
+            } else {
+                play(&mut client).await?;
+            }
+            out
+        }
+        PlaybackCommand::Next(out) => {
+            call_ok(&mut client, Request::NextSong).await?;
+            out
+        }
+        PlaybackCommand::Prev(out) => {
+            call_ok(&mut client, Request::PrevOrRestart).await?;
+            out
+        }
+        PlaybackCommand::Seek { time, out } => {
+            let now = NowPlaying::fetch(&mut client).await?;
+            if now.song.is_none() {
+                bail!("nothing playing");
+            }
+            let max = now.snap.duration_ms.unwrap_or(u64::MAX);
+            let target = time.resolve(now.snap.position_ms, max);
+            call_ok(&mut client, Request::Seek(target)).await?;
+            out
+        }
+        PlaybackCommand::Volume { value, out } => {
+            let now = NowPlaying::fetch(&mut client).await?;
+            let target = value.resolve(u64::from(now.snap.volume_pct), 100);
+            let target = u8::try_from(target).unwrap_or(100);
+            call_ok(&mut client, Request::SetVolume(target)).await?;
+            out
+        }
+        PlaybackCommand::Mode { mode, out } => {
+            set_mode(&mut client, mode).await?;
+            out
+        }
+        PlaybackCommand::Love(out) => return love(&mut client, &out).await,
+    };
+    NowPlaying::fetch(&mut client).await?.print(&out)
+}
+
+/// 开始 / 恢复播放:在播不动;有当前曲(暂停中)恢复;否则从队列当前位置起播。
+async fn play(client: &mut OneshotClient) -> color_eyre::Result<()> {
+    let now = NowPlaying::fetch(client).await?;
+    if now.snap.playing {
+        return Ok(());
+    }
+    if now.song.is_some() {
+        return call_ok(client, Request::Resume).await;
+    }
+    let start = now.index.unwrap_or(0);
+    let Some(song) = now.queue.get(start).or_else(|| now.queue.first()).cloned() else {
+        bail!("queue is empty");
+    };
+    call_ok(client, Request::PlaySong(Box::new(song))).await
+}
+
+/// 切到目标模式。协议只有「循环到下一档」一条请求,按当前档位算出要按几下。
+async fn set_mode(client: &mut OneshotClient, target: PlayMode) -> color_eyre::Result<()> {
+    let mut mode = NowPlaying::fetch(client).await?.mode;
+    while mode != target {
+        call_ok(client, Request::CyclePlayMode).await?;
+        mode = mode.cycle();
+    }
+    Ok(())
+}
+
+/// 翻转当前曲的收藏状态并打印结果。
+async fn love(client: &mut OneshotClient, out: &OutputArgs) -> color_eyre::Result<()> {
+    let Some(song) = NowPlaying::fetch(client).await?.song else {
+        bail!("nothing playing");
+    };
+    let loved = match call(client, Request::ToggleLove(Box::new(song.clone()))).await? {
+        Response::LoveToggled(loved) => loved,
+        other => bail!("unexpected response: {other:?}"),
+    };
+    if out.json {
+        println!(
+            "{}",
+            serde_json::json!({ "loved": loved, "song": serde_json::to_value(&song)? })
+        );
+    } else {
+        let mark = if loved { '♥' } else { '♡' };
+        println!("{mark} {}", song_title(&song));
+    }
+    Ok(())
+}
+
+/// 解析 `seek` 参数:`[+|-]` 前缀 + 秒 / `分:秒` / `时:分:秒`,产出毫秒。
+fn parse_seek(raw: &str) -> Result<Adjust, String> {
+    let (sign, body) = split_sign(raw);
+    let mut secs: u64 = 0;
+    let parts = body.split(':').collect::<Vec<&str>>();
+    if parts.len() > 3 {
+        return Err(format!(
+            "invalid time {raw:?}, expected e.g. 90 / 1:30 / +10"
+        ));
+    }
+    for part in parts {
+        let n = part
+            .parse::<u64>()
+            .map_err(|e| format!("invalid time {raw:?} ({e}), expected e.g. 90 / 1:30 / +10"))?;
+        secs = secs.saturating_mul(60).saturating_add(n);
+    }
+    signed(sign, secs.saturating_mul(1000))
+}
+
+/// 解析 `volume` 参数:`[+|-]` 前缀 + 百分点整数。
+fn parse_volume(raw: &str) -> Result<Adjust, String> {
+    let (sign, body) = split_sign(raw);
+    let n = body
+        .parse::<u64>()
+        .map_err(|e| format!("invalid volume {raw:?} ({e}), expected e.g. 60 / +5 / -5"))?;
+    if sign.is_none() && n > 100 {
+        return Err(format!("volume {n} out of range 0-100"));
+    }
+    signed(sign, n)
+}
+
+/// 解析播放模式名:脚本面的蛇形名,`-` 与 `_` 通用(`repeat-all` = `repeat_all`)。
+fn parse_mode(raw: &str) -> Result<PlayMode, String> {
+    PlayMode::from_script_name(&raw.to_ascii_lowercase().replace('-', "_")).ok_or_else(|| {
+        format!("unknown mode {raw:?}, expected sequential / shuffle / repeat-all / repeat-one")
+    })
+}
+
+/// 拆出 `+` / `-` 前缀:`Some(true)` = 加,`Some(false)` = 减,`None` = 绝对值。
+fn split_sign(raw: &str) -> (Option<bool>, &str) {
+    if let Some(rest) = raw.strip_prefix('+') {
+        (Some(true), rest)
+    } else if let Some(rest) = raw.strip_prefix('-') {
+        (Some(false), rest)
+    } else {
+        (None, raw)
+    }
+}
+
+/// 按前缀组装 [`Adjust`]。
+fn signed(sign: Option<bool>, n: u64) -> Result<Adjust, String> {
+    let delta = || i64::try_from(n).map_err(|e| format!("{n}: {e}"));
+    match sign {
+        None => Ok(Adjust::Set(n)),
+        Some(true) => Ok(Adjust::By(delta()?)),
+        Some(false) => Ok(Adjust::By(-delta()?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_protocol::PlayMode;
+
+    use super::{Adjust, parse_mode, parse_seek, parse_volume};
+
+    /// seek:绝对 / 相对,秒与冒号写法都收,非法写法报错。
+    #[test]
+    fn seek_parses_clock_and_offsets() {
+        assert_eq!(parse_seek("90"), Ok(Adjust::Set(90_000)));
+        assert_eq!(parse_seek("1:30"), Ok(Adjust::Set(90_000)));
+        assert_eq!(parse_seek("1:00:05"), Ok(Adjust::Set(3_605_000)));
+        assert_eq!(parse_seek("+10"), Ok(Adjust::By(10_000)));
+        assert_eq!(parse_seek("-0:15"), Ok(Adjust::By(-15_000)));
+        assert!(parse_seek("1:2:3:4").is_err());
+        assert!(parse_seek("abc").is_err());
+    }
+
+    /// volume:绝对值越界拒绝,相对量不限幅(落地时钳)。
+    #[test]
+    fn volume_parses_and_bounds() {
+        assert_eq!(parse_volume("60"), Ok(Adjust::Set(60)));
+        assert_eq!(parse_volume("+5"), Ok(Adjust::By(5)));
+        assert_eq!(parse_volume("-5"), Ok(Adjust::By(-5)));
+        assert!(parse_volume("101").is_err());
+    }
+
+    /// 相对量落地钳进 `[0, max]`。
+    #[test]
+    fn adjust_resolves_within_bounds() {
+        assert_eq!(Adjust::By(-20).resolve(10, 100), 0);
+        assert_eq!(Adjust::By(20).resolve(90, 100), 100);
+        assert_eq!(Adjust::Set(50).resolve(90, 100), 50);
+    }
+
+    /// 模式名 `-` / `_` 通用,大小写不敏感。
+    #[test]
+    fn mode_accepts_both_separators() {
+        assert_eq!(parse_mode("repeat-all"), Ok(PlayMode::RepeatAll));
+        assert_eq!(parse_mode("Repeat_One"), Ok(PlayMode::RepeatOne));
+        assert!(parse_mode("loop").is_err());
+    }
+}
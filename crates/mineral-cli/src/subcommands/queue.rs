@@ -0,0 +1,515 @@
+//! `mineral queue` 子命令树:`list` / `add` / `clear` / `move`,在 daemon 的播放队列上
+//! 做脚本友好的增删挪。
+//!
+//! 条目按 `list` 打出的 1-based 序号指认;编辑请求带「序号 + 歌 id」双保险
+//! ([`QueueAnchor`]),期间队列被别的 client 改过即拒绝,不会误伤另一首歌。
+//!
+//! `add` 的远端 id / 分享链接先经 daemon 按源批量取详情(`SongsDetail` 任务,结果帧
+//! 配对同 `search`)再入队;源失败 / 超时才退回只带 id 的占位歌。
+
+use std::path::Path;
+use std::time::Duration;
+
+use clap::{Subcommand, ValueEnum};
+use color_eyre::eyre::{OptionExt, bail};
+use mineral_channel_core::{ChannelCaps, match_web_url};
+use mineral_model::{MediaUrl, Song, SongId, SourceKind};
+use mineral_protocol::{
+    Event, OneshotClient, QueueAnchor, QueueContextWire, QueueEditOutcome, QueueOp, QueuePos,
+    Request, Response, Subscription,
+};
+use mineral_task::{ChannelFetchKind, Priority, TaskEvent, TaskKind};
+use rustc_hash::FxHashMap;
+
+use super::remote::{NowPlaying, OutputArgs, call, call_ok, connect, song_title};
+
+/// 等详情结果帧的上限(源失败时不会有结果帧,到点即退回占位歌)。
+const DETAIL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 播放队列。
+#[derive(Debug, Subcommand)]
+pub enum QueueCommand {
+    /// 列出队列(`>` = 在播)
+    List(OutputArgs),
+
+    /// 把歌加进队列末尾
+    Add {
+        /// 歌曲:`netease:123` 形式的 id、分享链接,或本地音频文件路径
+        #[arg(required = true)]
+        songs: Vec<String>,
+
+        /// 插到当前曲之后(下一首播),而非队尾
+        #[arg(long)]
+        next: bool,
+
+        /// 输出形态
+        #[command(flatten)]
+        out: OutputArgs,
+    },
+
+    /// 清空队列(在播曲保留,声音不断)
+    Clear(OutputArgs),
+
+    /// 挪动一个条目
+    Move {
+        /// 条目序号(1 起,见 `queue list`)
+        index: usize,
+
+        /// 目标位置
+        to: MoveTo,
+
+        /// 输出形态
+        #[command(flatten)]
+        out: OutputArgs,
+    },
+}
+
+/// `queue move` 的目标位置。
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum MoveTo {
+    /// 上移一格
+    Up,
+
+    /// 下移一格
+    Down,
+
+    /// 移到队首
+    Top,
+
+    /// 移到队尾
+    Bottom,
+
+    /// 移到当前曲之后
+    Next,
+}
+
+impl From<MoveTo> for QueuePos {
+    fn from(to: MoveTo) -> Self {
+        match to {
+            MoveTo::Up => Self::Up,
+            MoveTo::Down => Self::Down,
+            MoveTo::Top => Self::Top,
+            MoveTo::Bottom => Self::Bottom,
+            MoveTo::Next => Self::AfterCurrent,
+        }
+    }
+}
+
+/// 按 [`QueueCommand`] 分发。
+///
+/// # Params:
+///   - `cmd`: 已解析的 queue 子命令
+///
+/// # Return:
+///   执行结果;条目序号越界 / 歌曲写法认不出 / 编辑撞上并发改动时报错。
+pub async fn run(cmd: QueueCommand) -> color_eyre::Result<()> {
+    let mut client = match cmd {
+        // add 要等详情任务的结果帧,握手订上任务事件。
+        QueueCommand::Add { .. } => {
+            let socket = mineral_paths::socket_path()?;
+            OneshotClient::connect_subscribed(&socket, vec![Subscription::Task]).await?
+        }
+        _ => connect().await?,
+    };
+    match cmd {
+        QueueCommand::List(out) => {
+            let now = NowPlaying::fetch(&mut client).await?;
+            print_queue(&now, &out)
+        }
+        QueueCommand::Add { songs, next, out } => {
+            let caps = channel_caps(&mut client).await?;
+            let songs = songs
+                .iter()
+                .map(|raw| resolve_song(raw, &caps))
+                .collect::<color_eyre::Result<Vec<Song>>>()?;
+            let songs = fill_details(&mut client, songs).await?;
+            // 插播逐首插在当前曲之后,倒序发才能保住命令行上的先后。
+            let ordered: Vec<&Song> = if next {
+                songs.iter().rev().collect()
+            } else {
+                songs.iter().collect()
+            };
+            for song in ordered {
+                let song = Box::new(song.clone());
+                let context = QueueContextWire::Manual;
+                let req = if next {
+                    Request::QueueInsertNext { song, context }
+                } else {
+                    Request::QueueAppend { song, context }
+                };
+                call_ok(&mut client, req).await?;
+            }
+            if out.json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "added": serde_json::to_value(&songs)? })
+                );
+            } else {
+                for song in &songs {
+                    println!("+ {}", song_title(song));
+                }
+            }
+            Ok(())
+        }
+        QueueCommand::Clear(out) => {
+            let now = NowPlaying::fetch(&mut client).await?;
+            let outcome = clear(&mut client, &now).await?;
+            print_outcome(outcome, &out)
+        }
+        QueueCommand::Move { index, to, out } => {
+            let now = NowPlaying::fetch(&mut client).await?;
+            let at = anchor(&now.queue, index)?;
+            let outcome = edit(&mut client, QueueOp::Move { at, to: to.into() }).await?;
+            print_outcome(outcome, &out)
+        }
+    }
+}
+
+/// 清空:在播曲在队列里时剪掉它上下两段;悬空 / 没在播时从队首逐条删光。
+async fn clear(
+    client: &mut OneshotClient,
+    now: &NowPlaying,
+) -> color_eyre::Result<QueueEditOutcome> {
+    if let Some((index, song)) = now
+        .index
+        .and_then(|i| now.queue.get(i).map(|song| (i, song)))
+    {
+        let at = QueueAnchor::new(index, song.id.clone());
+        let below = edit(client, QueueOp::ClearBelow(at.clone())).await?;
+        // 剪掉下段不动上段下标,锚点仍然有效。
+        let above = edit(client, QueueOp::ClearAbove(at)).await?;
+        return Ok(merge(below, above));
+    }
+    let mut outcome = QueueEditOutcome::NoOp;
+    for song in &now.queue {
+        let step = edit(
+            client,
+            QueueOp::Remove(QueueAnchor::new(0, song.id.clone())),
+        )
+        .await?;
+        if step == QueueEditOutcome::Stale {
+            return Ok(step);
+        }
+        outcome = merge(outcome, step);
+    }
+    Ok(outcome)
+}
+
+/// 两步编辑的合并结果:任一步过期即过期,任一步生效即生效。
+fn merge(a: QueueEditOutcome, b: QueueEditOutcome) -> QueueEditOutcome {
+    match (a, b) {
+        (QueueEditOutcome::Stale, _) | (_, QueueEditOutcome::Stale) => QueueEditOutcome::Stale,
+        (QueueEditOutcome::Applied, _) | (_, QueueEditOutcome::Applied) => {
+            QueueEditOutcome::Applied
+        }
+        (QueueEditOutcome::NoOp, QueueEditOutcome::NoOp) => QueueEditOutcome::NoOp,
+    }
+}
+
+/// 发一次队列编辑。
+async fn edit(client: &mut OneshotClient, op: QueueOp) -> color_eyre::Result<QueueEditOutcome> {
+    match call(client, Request::QueueEdit { op }).await? {
+        Response::QueueEdited(outcome) => Ok(outcome),
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
+
+/// 1-based 序号 → 定位(越界报错)。
+fn anchor(queue: &[Song], index: usize) -> color_eyre::Result<QueueAnchor> {
+    let i = index.checked_sub(1).ok_or_eyre("queue index starts at 1")?;
+    let song = queue
+        .get(i)
+        .ok_or_else(|| color_eyre::eyre::eyre!("queue has {} entries, no #{index}", queue.len()))?;
+    Ok(QueueAnchor::new(i, song.id.clone()))
+}
+
+/// 拉已注册 channel 的能力表(认分享链接 / 校验 namespace 用)。
+async fn channel_caps(
+    client: &mut OneshotClient,
+) -> color_eyre::Result<Vec<(SourceKind, ChannelCaps)>> {
+    match call(client, Request::ChannelCaps).await? {
+        Response::ChannelCaps(caps) => Ok(caps),
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
+
+/// 命令行上的一首歌 → [`Song`]。依次认:本地音频文件路径、各源分享链接(按源声明的
+/// 网页模板反解)、`namespace:value` 形式的 id。
+///
+/// 远端 id 此时只带身份,歌名先以裸 id 占位,元数据由 [`fill_details`] 补上。
+///
+/// # Params:
+///   - `raw`: 命令行输入
+///   - `caps`: 已注册 channel 的能力表
+fn resolve_song(raw: &str, caps: &[(SourceKind, ChannelCaps)]) -> color_eyre::Result<Song> {
+    let path = Path::new(raw);
+    if path.is_file() {
+        let path = path.canonicalize()?;
+        let name = path
+            .file_stem()
+            .map_or_else(|| raw.to_owned(), |s| s.to_string_lossy().into_owned());
+        return Ok(Song::builder()
+            .id(SongId::new(SourceKind::LOCAL, path.to_string_lossy()))
+            .name(name)
+            .source_url(Some(MediaUrl::local(path)))
+            .build());
+    }
+    let id = if raw.starts_with("http://") || raw.starts_with("https://") {
+        caps.iter()
+            .find_map(|(source, caps)| {
+                let template = caps.song_web_url().as_deref()?;
+                Some(SongId::new(*source, match_web_url(template, raw)?))
+            })
+            .ok_or_else(|| color_eyre::eyre::eyre!("no source recognizes the link {raw:?}"))?
+    } else {
+        let (namespace, value) = raw
+            .split_once(':')
+            .filter(|(ns, v)| !ns.is_empty() && !v.is_empty())
+            .ok_or_else(|| {
+                color_eyre::eyre::eyre!(
+                    "invalid song {raw:?}, expected an id like \"netease:123\", a link or a file"
+                )
+            })?;
+        let source = SourceKind::from_name(namespace);
+        if !caps.iter().any(|(s, _)| *s == source) {
+            bail!("unknown source {namespace:?}");
+        }
+        SongId::new(source, value)
+    };
+    Ok(Song::builder().name(id.value().to_owned()).id(id).build())
+}
+
+/// 给远端占位歌补详情:按源各发一个 `SongsDetail` 任务并等结果帧。某源失败 / 超时只是
+/// 该源的歌保持占位,不挡入队。
+///
+/// # Params:
+///   - `client`: 已订任务事件的 daemon 连接
+///   - `songs`: [`resolve_song`] 的结果(保持命令行顺序)
+///
+/// # Return:
+///   同序的歌,查得到详情的已替换。
+async fn fill_details(
+    client: &mut OneshotClient,
+    songs: Vec<Song>,
+) -> color_eyre::Result<Vec<Song>> {
+    let mut by_source = FxHashMap::<SourceKind, Vec<SongId>>::default();
+    for song in songs.iter().filter(|s| s.source() != SourceKind::LOCAL) {
+        by_source
+            .entry(song.source())
+            .or_default()
+            .push(song.id.clone());
+    }
+    let mut details = Vec::new();
+    for (source, ids) in by_source {
+        let fetch = ChannelFetchKind::SongsDetail {
+            source,
+            ids: ids.clone(),
+        };
+        let submit = Request::SubmitTask(TaskKind::ChannelFetch(fetch), Priority::User);
+        match call(client, submit).await? {
+            Response::TaskId(_) => {}
+            other => bail!("unexpected response: {other:?}"),
+        }
+        if let Ok(found) =
+            tokio::time::timeout(DETAIL_TIMEOUT, wait_details(client, source, &ids)).await
+        {
+            details.extend(found?);
+        }
+    }
+    Ok(apply_details(songs, details))
+}
+
+/// 等本次详情任务的结果帧(同连接上别的任务事件跳过)。
+async fn wait_details(
+    client: &mut OneshotClient,
+    source: SourceKind,
+    ids: &[SongId],
+) -> color_eyre::Result<Vec<Song>> {
+    loop {
+        let Some(event) = client.next_event().await? else {
+            bail!("daemon closed the connection");
+        };
+        if let Event::Task(task) = event
+            && let Some(songs) = pick_details(*task, source, ids)
+        {
+            return Ok(songs);
+        }
+    }
+}
+
+/// 认出本次详情任务的结果(源与 id 表都对得上才算)。
+fn pick_details(event: TaskEvent, source: SourceKind, ids: &[SongId]) -> Option<Vec<Song>> {
+    match event {
+        TaskEvent::SongsDetailFetched {
+            source: got_source,
+            ids: got_ids,
+            songs,
+        } if got_source == source && got_ids == ids => Some(songs),
+        _ => None,
+    }
+}
+
+/// 按 id 用详情替换占位歌;查不到详情的保持原样。
+fn apply_details(songs: Vec<Song>, details: Vec<Song>) -> Vec<Song> {
+    let details = details
+        .into_iter()
+        .map(|song| (song.id.clone(), song))
+        .collect::<FxHashMap<SongId, Song>>();
+    songs
+        .into_iter()
+        .map(|song| details.get(&song.id).cloned().unwrap_or(song))
+        .collect()
+}
+
+/// 打印队列:文本一行一首(`>` 标在播),JSON 为 `{ index, songs }`(`index` 0-based)。
+fn print_queue(now: &NowPlaying, out: &OutputArgs) -> color_eyre::Result<()> {
+    if out.json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "index": now.index,
+                "songs": serde_json::to_value(&now.queue)?,
+            })
+        );
+        return Ok(());
+    }
+    if now.queue.is_empty() {
+        println!("queue is empty");
+    }
+    for (i, song) in now.queue.iter().enumerate() {
+        let mark = if now.index == Some(i) { '>' } else { ' ' };
+        println!("{mark} {:>3}  {}", i.saturating_add(1), song_title(song));
+    }
+    Ok(())
+}
+
+/// 打印一次编辑的结果;过期(被并发改动顶掉)报错退出。
+fn print_outcome(outcome: QueueEditOutcome, out: &OutputArgs) -> color_eyre::Result<()> {
+    let name = match outcome {
+        QueueEditOutcome::Applied => "applied",
+        QueueEditOutcome::NoOp => "unchanged",
+        QueueEditOutcome::Stale => bail!("queue changed meanwhile, run `mineral queue list` again"),
+    };
+    if out.json {
+        println!("{}", serde_json::json!({ "outcome": name }));
+    } else {
+        println!("queue {name}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_channel_core::{ArtistSections, ChannelCaps};
+    use mineral_model::{MediaUrl, SongId, SourceKind};
+    use mineral_protocol::QueueEditOutcome;
+    use mineral_task::TaskEvent;
+
+    use super::{anchor, apply_details, merge, pick_details, resolve_song};
+
+    /// 只声明了分享链接模板的能力表。
+    fn caps() -> Vec<(SourceKind, ChannelCaps)> {
+        let caps = ChannelCaps::builder()
+            .searchable(Vec::new())
+            .playlist_edit(false)
+            .artist_sections(ArtistSections::new(Vec::new()))
+            .song_web_url(Some("https://music.163.com/song?id={id}".to_owned()))
+            .build();
+        vec![(SourceKind::NETEASE, caps)]
+    }
+
+    /// 三种写法:本地文件给路径 + 文件名,分享链接反解,限定 id 直取;占位歌名为裸 id。
+    #[test]
+    fn resolve_song_accepts_files_links_and_ids() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("Palisade.flac");
+        std::fs::write(&file, b"")?;
+        let local = resolve_song(&file.to_string_lossy(), &caps())?;
+        let path = file.canonicalize()?;
+        assert_eq!(local.id.namespace(), SourceKind::LOCAL);
+        assert_eq!(local.name, "Palisade");
+        assert_eq!(local.source_url, Some(MediaUrl::local(path)));
+
+        let link = resolve_song("https://music.163.com/song?id=186016&uct=1", &caps())?;
+        assert_eq!(link.id, SongId::new(SourceKind::NETEASE, "186016"));
+        let id = resolve_song("netease:186016", &caps())?;
+        assert_eq!(id.id, link.id);
+        assert_eq!(id.name, "186016");
+        Ok(())
+    }
+
+    /// 未注册的源、认不出的链接、缺段的 id 都报错。
+    #[test]
+    fn resolve_song_rejects_unknown_input() {
+        assert!(resolve_song("bilibili:BV1", &caps()).is_err());
+        assert!(resolve_song("https://example.com/song/1", &caps()).is_err());
+        assert!(resolve_song("netease:", &caps()).is_err());
+        assert!(resolve_song("186016", &caps()).is_err());
+    }
+
+    /// 序号 1 起;0 与越界报错;锚点带下标与歌 id。
+    #[test]
+    fn anchors_are_one_based() -> color_eyre::Result<()> {
+        let queue = vec![mineral_test::song("1"), mineral_test::song("2")];
+        let at = anchor(&queue, 2)?;
+        assert_eq!(at.index, 1);
+        assert_eq!(Some(&at.song_id), queue.get(1).map(|s| &s.id));
+        assert!(anchor(&queue, 0).is_err());
+        assert!(anchor(&queue, 3).is_err());
+        Ok(())
+    }
+
+    /// 两步编辑:过期压过一切,生效压过无变化。
+    #[test]
+    fn merge_prefers_stale_then_applied() {
+        use QueueEditOutcome::{Applied, NoOp, Stale};
+        assert_eq!(merge(Applied, Stale), Stale);
+        assert_eq!(merge(Stale, NoOp), Stale);
+        assert_eq!(merge(NoOp, Applied), Applied);
+        assert_eq!(merge(NoOp, NoOp), NoOp);
+    }
+
+    /// 详情按 id 替换占位,查不到的保持原样、顺序不变;结果帧须源与 id 表都对得上。
+    #[test]
+    fn details_replace_placeholders() {
+        let bare = mineral_test::song;
+        let mut full = bare("1");
+        full.name = "晴天".to_owned();
+        let merged = apply_details(vec![bare("1"), bare("2"), bare("1")], vec![full]);
+        assert_eq!(
+            merged.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["晴天", "2", "晴天"]
+        );
+
+        let ids = vec![SongId::new(SourceKind::NETEASE, "1")];
+        let event = |source, ids| TaskEvent::SongsDetailFetched {
+            source,
+            ids,
+            songs: Vec::new(),
+        };
+        assert!(
+            pick_details(
+                event(SourceKind::NETEASE, ids.clone()),
+                SourceKind::NETEASE,
+                &ids
+            )
+            .is_some()
+        );
+        assert!(
+            pick_details(
+                event(SourceKind::LOCAL, ids.clone()),
+                SourceKind::NETEASE,
+                &ids
+            )
+            .is_none()
+        );
+        assert!(
+            pick_details(
+                event(SourceKind::NETEASE, Vec::new()),
+                SourceKind::NETEASE,
+                &ids
+            )
+            .is_none()
+        );
+    }
+}
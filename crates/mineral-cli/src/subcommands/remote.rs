@@ -0,0 +1,161 @@
+//! 遥控类子命令(`play` / `queue` / `search` …)的共用件:连 daemon、收应答、
+//! 取播放现状并按文本 / `--json` 打印。
+//!
+//! 现状 = 音频快照 + 一次全量 [`PlayerSync`](版本号报 0 换回全部重段),两条都是
+//! 已有请求,不为 CLI 另开协议。
+
+use clap::Args;
+use color_eyre::eyre::bail;
+use mineral_audio::AudioSnapshot;
+use mineral_model::Song;
+use mineral_protocol::{OneshotClient, PlayMode, PlayerVersions, Request, Response};
+
+use super::status::format_ms;
+
+/// 输出形态开关(各遥控子命令共用)。
+#[derive(Debug, Args)]
+pub struct OutputArgs {
+    /// 以 JSON 输出(供脚本解析)
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// 连 daemon socket(含握手,订空集)。
+///
+/// # Return:
+///   已可发请求的 client;daemon 没跑时报错提示先 `mineral serve`。
+pub(crate) async fn connect() -> color_eyre::Result<OneshotClient> {
+    OneshotClient::connect(&mineral_paths::socket_path()?).await
+}
+
+/// 发一条请求,[`Response::Error`] 转成报错。
+///
+/// # Params:
+///   - `client`: 已握手的 daemon 连接
+///   - `req`: 请求体
+///
+/// # Return:
+///   非错误的应答(具体变体由调用方按请求判)。
+pub(crate) async fn call(client: &mut OneshotClient, req: Request) -> color_eyre::Result<Response> {
+    match client.request(req).await? {
+        Response::Error(msg) => bail!("daemon error: {msg}"),
+        other => Ok(other),
+    }
+}
+
+/// 发一条只回 [`Response::Ok`] 的请求。
+///
+/// # Params:
+///   - `client`: 已握手的 daemon 连接
+///   - `req`: 请求体
+pub(crate) async fn call_ok(client: &mut OneshotClient, req: Request) -> color_eyre::Result<()> {
+    match call(client, req).await? {
+        Response::Ok => Ok(()),
+        other => bail!("unexpected response: {other:?}"),
+    }
+}
+
+/// 播放现状:音频快照 + 队列 / 当前曲 / 模式。
+pub(crate) struct NowPlaying {
+    /// 音频快照(播放 / 位置 / 音量)。
+    pub snap: AudioSnapshot,
+
+    /// 当前曲(从未播 / 已 stop 为 `None`)。
+    pub song: Option<Song>,
+
+    /// 当前队列。
+    pub queue: Vec<Song>,
+
+    /// 当前曲在队列中的下标(悬空为 `None`)。
+    pub index: Option<usize>,
+
+    /// 播放模式。
+    pub mode: PlayMode,
+}
+
+impl NowPlaying {
+    /// 拉一次现状。
+    ///
+    /// # Params:
+    ///   - `client`: 已握手的 daemon 连接
+    pub(crate) async fn fetch(client: &mut OneshotClient) -> color_eyre::Result<Self> {
+        let snap = match call(client, Request::AudioSnapshot).await? {
+            Response::AudioSnapshot(snap) => snap,
+            other => bail!("unexpected response: {other:?}"),
+        };
+        let sync = match call(client, Request::PlayerSync(PlayerVersions::default())).await? {
+            Response::PlayerSync(sync) => *sync,
+            other => bail!("unexpected response: {other:?}"),
+        };
+        Ok(Self {
+            snap,
+            song: sync.current.and_then(|c| c.current_song),
+            queue: sync.queue.map(|q| q.queue).unwrap_or_default(),
+            index: sync.cursor.queue_index(),
+            mode: sync.play_mode,
+        })
+    }
+
+    /// 单行文本:`▶ 歌名 — 艺人  1:05 / 4:31  vol 80%  seq`。
+    pub(crate) fn render(&self) -> String {
+        let glyph = if self.snap.playing { '▶' } else { '⏸' };
+        let title = self
+            .song
+            .as_ref()
+            .map_or_else(|| "(nothing playing)".to_owned(), song_title);
+        let dur = self
+            .snap
+            .duration_ms
+            .map_or_else(|| "--:--".to_owned(), format_ms);
+        format!(
+            "{glyph} {title}  {} / {dur}  vol {}%  {}",
+            format_ms(self.snap.position_ms),
+            self.snap.volume_pct,
+            self.mode.label(),
+        )
+    }
+
+    /// JSON 形态(字段稳定,供脚本解析;歌曲按 model 的 serde 形态整体输出)。
+    pub(crate) fn to_json(&self) -> color_eyre::Result<serde_json::Value> {
+        Ok(serde_json::json!({
+            "playing": self.snap.playing,
+            "position_ms": self.snap.position_ms,
+            "duration_ms": self.snap.duration_ms,
+            "volume": self.snap.volume_pct,
+            "mode": self.mode.script_name(),
+            "index": self.index,
+            "song": serde_json::to_value(&self.song)?,
+        }))
+    }
+
+    /// 按 `--json` 开关打印现状。
+    ///
+    /// # Params:
+    ///   - `out`: 输出形态
+    pub(crate) fn print(&self, out: &OutputArgs) -> color_eyre::Result<()> {
+        if out.json {
+            println!("{}", self.to_json()?);
+        } else {
+            println!("{}", self.render());
+        }
+        Ok(())
+    }
+}
+
+/// 一首歌的展示名:`歌名 — 艺人1 / 艺人2`(无艺人只出歌名)。
+///
+/// # Params:
+///   - `song`: 目标歌
+pub(crate) fn song_title(song: &Song) -> String {
+    let artists = song
+        .artists
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(" / ");
+    if artists.is_empty() {
+        song.name.clone()
+    } else {
+        format!("{} — {artists}", song.name)
+    }
+}
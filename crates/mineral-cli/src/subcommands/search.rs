@@ -0,0 +1,209 @@
+//! `mineral search`:经 daemon 搜歌,打印结果或直接起播。
+//!
+//! 搜索是任务型请求:`SubmitTask` 只回任务 id,结果随后以 [`TaskEvent::SearchResults`]
+//! 推下来。故握手订 [`Subscription::Task`],按请求四元组配对结果帧。channel 失败不推
+//! 结果事件,等待设上限,超时按失败报。
+
+use std::time::Duration;
+
+use clap::Args;
+use color_eyre::eyre::{OptionExt, bail};
+use mineral_channel_core::Page;
+use mineral_model::{SearchKind, Song, SourceKind};
+use mineral_protocol::{Event, OneshotClient, QueueContextWire, Request, Response, Subscription};
+use mineral_task::{ChannelFetchKind, Priority, SearchPayload, TaskEvent, TaskKind};
+
+use super::remote::{NowPlaying, OutputArgs, call, call_ok, song_title};
+
+/// 等搜索结果的上限(channel 取数失败时不会有结果帧)。
+const RESULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 搜索参数。
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// 关键词(多个词以空格拼接)
+    #[arg(required = true)]
+    pub query: Vec<String>,
+
+    /// 搜索的音乐源
+    #[arg(long, default_value = "netease")]
+    pub source: String,
+
+    /// 结果条数上限
+    #[arg(long, default_value_t = 10)]
+    pub limit: u32,
+
+    /// 以结果替换队列并从第一首起播
+    #[arg(long)]
+    pub play: bool,
+
+    /// 输出形态
+    #[command(flatten)]
+    pub out: OutputArgs,
+}
+
+/// 搜歌并按参数打印 / 起播。
+///
+/// # Params:
+///   - `args`: 搜索参数
+///
+/// # Return:
+///   执行结果;源未注册 / 超时没等到结果 / `--play` 而结果为空时报错。
+pub async fn run(args: SearchArgs) -> color_eyre::Result<()> {
+    let socket = mineral_paths::socket_path()?;
+    let mut client = OneshotClient::connect_subscribed(&socket, vec![Subscription::Task]).await?;
+    let source = SourceKind::from_name(&args.source);
+    let query = args.query.join(" ");
+    let page = Page::new(0, args.limit);
+    let fetch = ChannelFetchKind::Search {
+        source,
+        kind: SearchKind::Song,
+        query: query.clone(),
+        page,
+    };
+    let submit = Request::SubmitTask(TaskKind::ChannelFetch(fetch), Priority::User);
+    match call(&mut client, submit).await? {
+        Response::TaskId(_) => {}
+        other => bail!("unexpected response: {other:?}"),
+    }
+    let songs = tokio::time::timeout(
+        RESULT_TIMEOUT,
+        wait_results(&mut client, source, &query, page),
+    )
+    .await
+    .map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "{e}: no results from {} in {}s (source failed or not logged in?)",
+            args.source,
+            RESULT_TIMEOUT.as_secs()
+        )
+    })??;
+
+    if args.play {
+        let first = songs.first().cloned().ok_or_eyre("no results to play")?;
+        let req = Request::SetQueue {
+            queue: songs,
+            target_id: first.id.clone(),
+            context: QueueContextWire::Search { query },
+        };
+        call_ok(&mut client, req).await?;
+        call_ok(&mut client, Request::PlaySong(Box::new(first))).await?;
+        return NowPlaying::fetch(&mut client).await?.print(&args.out);
+    }
+    if args.out.json {
+        println!("{}", serde_json::to_value(&songs)?);
+    } else {
+        if songs.is_empty() {
+            println!("no results");
+        }
+        for (i, song) in songs.iter().enumerate() {
+            println!(
+                "{:>3}  {}  {}",
+                i.saturating_add(1),
+                song.id.qualified(),
+                song_title(song)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 等本次搜索的结果帧(四元组对得上才算;同连接上别的任务事件跳过)。
+async fn wait_results(
+    client: &mut OneshotClient,
+    source: SourceKind,
+    query: &str,
+    page: Page,
+) -> color_eyre::Result<Vec<Song>> {
+    loop {
+        let Some(event) = client.next_event().await? else {
+            bail!("daemon closed the connection");
+        };
+        if let Event::Task(task) = event
+            && let Some(songs) = pick_results(*task, source, query, page)
+        {
+            return Ok(songs);
+        }
+    }
+}
+
+/// 认出本次搜索的结果(源 / 搜歌 / 关键词 / 分页都对得上才算)。
+fn pick_results(
+    event: TaskEvent,
+    source: SourceKind,
+    query: &str,
+    page: Page,
+) -> Option<Vec<Song>> {
+    match event {
+        TaskEvent::SearchResults {
+            source: got_source,
+            kind: SearchKind::Song,
+            query: got_query,
+            page: got_page,
+            payload: SearchPayload::Songs(songs),
+            ..
+        } if got_source == source && got_query == query && got_page == page => Some(songs),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_channel_core::Page;
+    use mineral_model::{SearchKind, SourceKind};
+    use mineral_task::{SearchPayload, TaskEvent};
+
+    use super::pick_results;
+
+    /// 一页搜歌结果帧。
+    fn results(source: SourceKind, query: &str, offset: u32) -> TaskEvent {
+        TaskEvent::SearchResults {
+            source,
+            kind: SearchKind::Song,
+            query: query.to_owned(),
+            page: Page::new(offset, 10),
+            payload: SearchPayload::Songs(vec![mineral_test::song("186016")]),
+            has_more: Some(false),
+        }
+    }
+
+    /// 四元组全对得上才认;别的源 / 关键词 / 页都跳过。
+    #[test]
+    fn results_pair_by_request() {
+        let page = Page::new(0, 10);
+        let hit = pick_results(
+            results(SourceKind::NETEASE, "晴天", 0),
+            SourceKind::NETEASE,
+            "晴天",
+            page,
+        );
+        assert_eq!(hit.map(|s| s.len()), Some(1));
+        assert!(
+            pick_results(
+                results(SourceKind::LOCAL, "晴天", 0),
+                SourceKind::NETEASE,
+                "晴天",
+                page
+            )
+            .is_none()
+        );
+        assert!(
+            pick_results(
+                results(SourceKind::NETEASE, "七里香", 0),
+                SourceKind::NETEASE,
+                "晴天",
+                page
+            )
+            .is_none()
+        );
+        assert!(
+            pick_results(
+                results(SourceKind::NETEASE, "晴天", 10),
+                SourceKind::NETEASE,
+                "晴天",
+                page
+            )
+            .is_none()
+        );
+    }
+}
@@ -4,6 +4,8 @@
 //! 1. 解析 socket 路径
 //! 2. stale socket 检测(已活 daemon → bail;残留 socket 文件 → 删)
 //! 3. bind + Server::spawn + serve
+//! 4. 可选的 HTTP / WebSocket 遥控 listener(`daemon.http`),与 accept loop 并行
+//! 5. 可选的 MPD 协议兼容 listener(`daemon.mpd`),同样并行
 
 use std::sync::Arc;
 
@@ -11,7 +13,7 @@ use color_eyre::eyre::{WrapErr, bail};
 use mineral_channel_core::MusicChannel;
 use mineral_persist::ServerStore;
 use mineral_server::{Server, ServerConfig, resolve_audio_mode};
-use tokio::net::{UnixListener, UnixStream};
+use tokio::net::{TcpListener, UnixListener, UnixStream};
 use tokio::signal::unix::{Signal, SignalKind, signal};
 
 /// 停机时 await 埋点 actor join 的超时上限:actor 结算末尾一行 + 退出远快于此,超时纯是
@@ -27,6 +29,7 @@ const STATS_JOIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2
 /// # Params:
 ///   - `channels`: 已构造好的全部音乐源 handle。空 vec 也合法。
 ///   - `persist`: 持久化句柄,透传给 [`Server::spawn`] 供 PlayerCore 持有。
+///   - `stats`: stats.db 句柄([`open_stats`] 打开;与聚合 channel 共用同一个,单连接池不可重开)。
 ///   - `config`: 已加载的全局配置(audio 后端 / daemon 切片在此派生)。
 ///   - `script`: 脚本部件包(daemon 入口经 `load_with_vm` 装配;无脚本时 VM 槽为空)。
 ///   - `config_tree`: 有效配置底树(与 `config` 同一次加载的合成树,交配置宿主)。
@@ -34,6 +37,7 @@ const STATS_JOIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2
 pub async fn run(
     channels: Vec<Arc<dyn MusicChannel>>,
     persist: ServerStore,
+    stats: mineral_stats::StatsStore,
     config: mineral_config::Config,
     script: mineral_server::ScriptParts,
     config_tree: serde_json::Value,
@@ -47,6 +51,10 @@ pub async fn run(
     let mut term = signal(SignalKind::terminate()).wrap_err("install SIGTERM handler")?;
     let mut interrupt = signal(SignalKind::interrupt()).wrap_err("install SIGINT handler")?;
 
+    // HTTP / MPD 遥控先于 unix socket bind:配置不合法(对外地址没口令 / 端口被占)直接
+    // 退出,不留下刚 bind 的 socket 文件。
+    let http = bind_http(config.daemon().http()).await?;
+    let mpd = bind_mpd(config.daemon().mpd()).await?;
     let socket_path = mineral_paths::socket_path()?;
     prepare_socket(&socket_path).await?;
     let listener = UnixListener::bind(&socket_path)
@@ -58,7 +66,7 @@ pub async fn run(
     // 否则按 config 的 `audio.backend` 落(env 命中短路 config)。env 只在 binary 边缘读。
     let audio_mode = resolve_audio_mode(
         std::env::var_os("MINERAL_AUDIO_NULL").is_some(),
-        *config.audio().backend(),
+        config.audio().backend(),
     );
     // 脚本线程先于 Server 起(只需 VM + host);其投递句柄喂给 Server 的事件
     // 出口。runtime 句柄持有到本函数结束 —— Drop = 停机 + join。
@@ -74,7 +82,7 @@ pub async fn run(
     // 投递句柄是热重载间接层:daemon 恒持有(初始无脚本也可经重载升级为有)。
     let script_sender = mineral_script::ScriptSender::detached();
     let (script_runtime, pumps) = script.spawn_runtime(watchdog, &script_sender, &channels);
-    let (stats, stats_actor) = spawn_recorder(&config).await;
+    let (stats, stats_actor) = spawn_recorder(&config, stats);
     // 留一份句柄给停机路径记 app_lifecycle stop + 发 Shutdown(server 会 move 走原句柄)。
     let stats_stop = stats.clone();
     let server = Server::spawn(
@@ -97,6 +105,8 @@ pub async fn run(
     }
     let outcome = tokio::select! {
         result = server.serve(listener) => result,
+        result = serve_http(&server, http, config.daemon().http().token().clone()) => result,
+        result = serve_mpd(&server, mpd, config.daemon().mpd().password().clone()) => result,
         () = wait_for_signal(&mut term, &mut interrupt) => {
             mineral_log::info!(target: "daemon", "shutdown signal received, stopping daemon");
             Ok(())
@@ -135,6 +145,88 @@ pub async fn run(
     outcome
 }
 
+/// 按 `daemon.http` bind 遥控 listener;未启用返回 `None`。
+///
+/// # Params:
+///   - `http`: `daemon.http` 配置段
+///
+/// # Return:
+///   已 bind 的 listener;地址不合法 / 对外地址没配令牌 / bind 失败时报错。
+async fn bind_http(http: &mineral_config::HttpConfig) -> color_eyre::Result<Option<TcpListener>> {
+    if !*http.enabled() {
+        return Ok(None);
+    }
+    let addr: std::net::SocketAddr = http
+        .bind()
+        .parse()
+        .wrap_err_with(|| format!("invalid daemon.http.bind {:?}", http.bind()))?;
+    mineral_server::check_exposure(&addr, http.token().as_deref(), "daemon.http.token")?;
+    let listener = TcpListener::bind(addr)
+        .await
+        .wrap_err_with(|| format!("bind http listener {addr}"))?;
+    mineral_log::info!(target: "daemon", %addr, "http remote control bound");
+    println!("mineral http remote control on http://{addr}");
+    Ok(Some(listener))
+}
+
+/// 跑 HTTP 遥控 accept loop;未启用时永不完成(在 select 里等同缺席)。
+///
+/// # Params:
+///   - `server`: daemon server
+///   - `listener`: [`bind_http`] 的结果
+///   - `token`: 访问令牌
+async fn serve_http(
+    server: &Server,
+    listener: Option<TcpListener>,
+    token: Option<String>,
+) -> color_eyre::Result<()> {
+    match listener {
+        Some(listener) => server.serve_http(listener, token).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 按 `daemon.mpd` bind MPD listener;未启用返回 `None`。
+///
+/// # Params:
+///   - `mpd`: `daemon.mpd` 配置段
+///
+/// # Return:
+///   已 bind 的 listener;地址不合法 / 对外地址没配密码 / bind 失败时报错。
+async fn bind_mpd(mpd: &mineral_config::MpdConfig) -> color_eyre::Result<Option<TcpListener>> {
+    if !*mpd.enabled() {
+        return Ok(None);
+    }
+    let addr: std::net::SocketAddr = mpd
+        .bind()
+        .parse()
+        .wrap_err_with(|| format!("invalid daemon.mpd.bind {:?}", mpd.bind()))?;
+    mineral_server::check_exposure(&addr, mpd.password().as_deref(), "daemon.mpd.password")?;
+    let listener = TcpListener::bind(addr)
+        .await
+        .wrap_err_with(|| format!("bind mpd listener {addr}"))?;
+    mineral_log::info!(target: "daemon", %addr, "mpd server bound");
+    println!("mineral mpd server on {addr}");
+    Ok(Some(listener))
+}
+
+/// 跑 MPD accept loop;未启用时永不完成(在 select 里等同缺席)。
+///
+/// # Params:
+///   - `server`: daemon server
+///   - `listener`: [`bind_mpd`] 的结果
+///   - `password`: 连接密码
+async fn serve_mpd(
+    server: &Server,
+    listener: Option<TcpListener>,
+    password: Option<String>,
+) -> color_eyre::Result<()> {
+    match listener {
+        Some(listener) => server.serve_mpd(listener, password).await,
+        None => std::future::pending().await,
+    }
+}
+
 /// 等待第一个到达的关闭信号(SIGINT 或 SIGTERM)。
 ///
 /// handler 由 caller 在 bind socket **之前**就装好(`signal(...)` 调用时即安装),
@@ -178,18 +270,12 @@ async fn prepare_socket(path: &std::path::Path) -> color_eyre::Result<()> {
     }
 }
 
-/// 起埋点 recorder:开 stats.db(失败降级 disabled no-op)+ 折算采集参数 + spawn actor。
-/// 返回 actor 的 `JoinHandle` 供停机路径带超时 await(在播 pending 结算 stop 后退出)。
-///
-/// # Params:
-///   - `config`: 全局配置(取 `stats` 段折算;`report` 口径留给报告层现读)
+/// 打开 stats.db(数据目录下);任一步失败降级 disabled no-op(warn,不阻断 daemon)。
 ///
 /// # Return:
-///   (recorder 句柄, actor 的 `JoinHandle`);降级路径亦返回可用句柄(打点静默丢弃)
-async fn spawn_recorder(
-    config: &mineral_config::Config,
-) -> (mineral_server::StatsRecorder, tokio::task::JoinHandle<()>) {
-    let store = match mineral_paths::data_dir() {
+///   成功返回启用的句柄,失败返回 disabled 句柄。
+pub async fn open_stats() -> mineral_stats::StatsStore {
+    match mineral_paths::data_dir() {
         Ok(dir) => match std::fs::create_dir_all(&dir) {
             Ok(()) => match mineral_stats::StatsStore::open(&dir.join("stats.db")).await {
                 Ok(store) => store,
@@ -207,7 +293,22 @@ async fn spawn_recorder(
             mineral_log::warn!(target: "daemon", error = mineral_log::chain(&e), "定位数据目录失败,埋点降级");
             mineral_stats::StatsStore::disabled()
         }
-    };
+    }
+}
+
+/// 起埋点 recorder:折算采集参数 + spawn actor。
+/// 返回 actor 的 `JoinHandle` 供停机路径带超时 await(在播 pending 结算 stop 后退出)。
+///
+/// # Params:
+///   - `config`: 全局配置(取 `stats` 段折算;`report` 口径留给报告层现读)
+///   - `store`: [`open_stats`] 打开的 stats.db 句柄
+///
+/// # Return:
+///   (recorder 句柄, actor 的 `JoinHandle`);降级路径亦返回可用句柄(打点静默丢弃)
+fn spawn_recorder(
+    config: &mineral_config::Config,
+    store: mineral_stats::StatsStore,
+) -> (mineral_server::StatsRecorder, tokio::task::JoinHandle<()>) {
     let params = mineral_server::params_from_config(config.stats());
     mineral_server::StatsRecorder::spawn(store, params)
 }
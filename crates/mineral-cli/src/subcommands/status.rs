@@ -4,7 +4,7 @@
 //! (握手与配对语义在 [`OneshotClient`] 内)。
 
 use color_eyre::eyre::bail;
-use mineral_audio::{AudioBackend, AudioSnapshot};
+use mineral_audio::{AudioBackend, AudioSnapshot, SpeedMode};
 use mineral_protocol::{DownloadProgress, OneshotClient, Request, Response};
 
 /// `mineral status` 入口:连 daemon socket(含握手)→ 依次拉快照 / pid / 下载进度 → 打印。
@@ -75,15 +75,20 @@ fn render_snapshot(snap: &AudioSnapshot, pid: u32) -> String {
     let backend = match snap.backend {
         AudioBackend::Device => "device",
         AudioBackend::Null => "null (no audio device)",
+        AudioBackend::File => "file (rendering to disk)",
+    };
+    let speed_mode = match snap.speed_mode {
+        SpeedMode::PitchShift => "pitch shift",
+        SpeedMode::TimeStretch | _ => "time stretch",
     };
     format!(
-        "pid:        {pid}\nplaying:    {}\nposition:   {pos} / {dur}\nvolume:     {} %\nfinished:   {} (track_finished_seq)\nbackend:    {backend}",
-        snap.playing, snap.volume_pct, snap.track_finished_seq,
+        "pid:        {pid}\nplaying:    {}\nposition:   {pos} / {dur}\nvolume:     {} %\nspeed:      {} % ({speed_mode})\nfinished:   {} (track_finished_seq)\nbackend:    {backend}",
+        snap.playing, snap.volume_pct, snap.speed_pct, snap.track_finished_seq,
     )
 }
 
 /// 把 ms 格式化成 `mm:ss`(小时被合并进分钟)。
-fn format_ms(ms: u64) -> String {
+pub(super) fn format_ms(ms: u64) -> String {
     let s = ms / 1000;
     let m = s / 60;
     let s = s % 60;
@@ -93,10 +93,14 @@ fn dir_label(config: &Config, default_download_dir: &Path) -> String {
 ///
 /// # Return:
 ///   展示名
-fn backend_name(backend: &BackendKind) -> &'static str {
+fn backend_name(backend: &BackendKind) -> String {
     match backend {
-        BackendKind::Auto => "auto",
-        BackendKind::Null => "null",
+        BackendKind::Auto => "auto".to_owned(),
+        BackendKind::Null => "null".to_owned(),
+        BackendKind::File { path, realtime } => {
+            let pace = if *realtime { "realtime" } else { "fast" };
+            format!("file {} ({pace})", path.display())
+        }
     }
 }
 
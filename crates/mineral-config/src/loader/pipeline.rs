@@ -132,9 +132,19 @@ fn extract_lua_fns(lua: &Lua, merged: &Table) -> color_eyre::Result<()> {
     extract_copy_templates(lua, merged)?;
     extract_playlist_transforms(lua, merged)?;
     extract_queue_transforms(lua, merged)?;
+    mark_struct_arrays(lua, merged);
     Ok(())
 }
 
+/// 给不含函数的结构体数组字段挂 array metatable:原因同 [`extract_copy_templates`]
+/// (空 Lua 表默认序列化成 map `{}`,落不进 `Vec`)。含函数的数组由各自提取器顺手挂;
+/// 字符串数组走 `de::string_list` 容忍空表,不必挂。
+fn mark_struct_arrays(lua: &Lua, merged: &Table) {
+    if let Some(smart) = table_at!(merged, sources.mineral.smart_playlists) {
+        smart.set_metatable(Some(lua.array_metatable()));
+    }
+}
+
 /// 把 `queue.transforms[i].transform` 从配置表里摘出,按数组序存进 VM named registry
 /// (键 [`QUEUE_TRANSFORM_FNS`]);表上的 `transform` 字段移除,`key`/`label` 留下进常规
 /// 落型。对位方式与 `tui.copy.templates` 相同(见 [`extract_copy_templates`])。
@@ -731,6 +741,33 @@ mod tests {
         Ok(())
     }
 
+    /// 非法下载路径模板在落型时就报(带字段路径),不拖到 daemon 运行期。
+    #[test]
+    fn bad_path_template_falls_back_with_field_path() -> color_eyre::Result<()> {
+        let path = temp_config(
+            "pathtpl",
+            r#"return { download = { path_template = "{albm}/{title}" } }"#,
+        )?;
+        let (cfg, warnings) = load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(
+            cfg.download().path_template(),
+            &crate::PathTemplate::default(),
+            "回落默认"
+        );
+        match warnings.as_slice() {
+            [ConfigWarning::Deserialize { path, .. }] => {
+                assert_eq!(path, "download.path_template", "字段路径应精确");
+            }
+            other => {
+                return Err(color_eyre::eyre::eyre!(
+                    "应有一条 Deserialize warning:{other:?}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn unknown_field_rejected_with_path() -> color_eyre::Result<()> {
         let path = temp_config("unknown", "return { audio = { bogus = 1 } }")?;
@@ -947,6 +984,26 @@ mod tests {
         Ok(())
     }
 
+    /// 文件后端:`{ file = { path = ... } }` 解析成带路径的变体,`realtime` 省略为 false。
+    #[test]
+    fn audio_file_backend_parses() -> color_eyre::Result<()> {
+        let path = temp_config(
+            "audiofile",
+            r#"return { audio = { backend = { file = { path = "/tmp/out.flac" } } } }"#,
+        )?;
+        let (cfg, warnings) = load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert!(warnings.is_empty(), "实得 {warnings:?}");
+        assert_eq!(
+            cfg.audio().backend(),
+            &crate::BackendKind::File {
+                path: "/tmp/out.flac".into(),
+                realtime: false,
+            }
+        );
+        Ok(())
+    }
+
     /// 用户可完全关闭窗口标题。
     #[test]
     fn window_title_disabled() -> color_eyre::Result<()> {
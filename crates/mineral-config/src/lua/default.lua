@@ -54,6 +54,9 @@ return {
       quit = "q",
       open_help = "?",
       cycle_lyric = "t",
+      lyric_later = ".", -- 在播曲歌词推迟一步(按曲持久)
+      lyric_earlier = ",", -- 在播曲歌词提前一步
+      edit_lyrics = "e", -- 歌词编辑器(LRC 打轴)
       enter_search = "/",
       activate = { "l", "<CR>" },
       back = { "h", "<Esc>", "<BS>", "<C-h>" },
@@ -92,6 +95,7 @@ return {
       volume_step = 5, -- 单次音量增减,百分点
       seek_step_secs = 5, -- 单次 seek 步长,秒
       seek_big_step_secs = 30, -- 大步 seek(Shift),秒
+      lyric_offset_step_ms = 50, -- 歌词时间偏移单次微调,毫秒
       list_jump_rows = 7, -- 列表大步跳行数(J/K)
       scrolloff = 3, -- 光标与列表视口上下边缘的最小行距(nvim 'scrolloff');0 = 贴边才滚
       line_scroll_rows = 1, -- 单行档滚动(<C-d>/<C-u>)一次滚的行数;列表与全屏歌词共用
@@ -267,7 +271,7 @@ return {
       -- channel 搜索两个下拉的白名单:列出即暴露、顺序即下拉顺序,未列出的隐藏。
       -- source 名开放(插件源可写),没加载的名字静默跳过;空列表 = 防呆回退全量。
       channel = {
-        sources = { "netease", "bilibili" },
+        sources = { "netease", "bilibili", "local" },
         -- 封闭集合 song/album/artist/playlist/user,与各 source 可搜集合求交(保此处顺序)
         kinds = { "song", "album", "artist", "playlist", "user" },
       },
@@ -362,7 +366,8 @@ return {
   -- 以下顶层段 = daemon/共享核心
   audio = {
     volume = 100, -- 启动初始音量 % 0-100;运行期音量不落盘,每次启动回到此值
-    backend = "auto", -- "auto" | "null":auto 打不开声卡自动降级无声空跑;null 强制无声
+    backend = "auto", -- "auto" | "null" | { file = { path = "out.wav" } }:auto 打不开声卡自动降级无声空跑;null 强制无声;file 渲染进文件
+    device = nil, -- 钉住的输出设备名(`mineral audio devices` 列出);nil = 系统默认。不在时回落默认,回来后切回
     playback_quality = "exhigh", -- standard | higher | exhigh | lossless | hires
     engine_tick_ms = 20, -- 引擎主循环节拍;影响 seek/停止响应延迟,不建议动
     prefetch_bytes = 256 * KB, -- 流式起播前预拉字节;大 = 起播慢但 seek 命中缓冲概率高
@@ -384,6 +389,63 @@ return {
         q = 0.5003270373238773,
       },
     },
+    -- 响度归一化:文件自带 ReplayGain 标签优先,否则用 daemon 离线分析的 R128 积分响度
+    -- (与包络同一遍算出;首次流播的曲目要等缓存 / 下载后才有,期间保持原样)
+    normalization = {
+      mode = "track", -- "off" | "track" | "album";album 无专辑增益标签时回落逐曲
+      target_lufs = -18.0, -- 目标响度;-18 = ReplayGain 2.0 参考电平,-14 接近流媒体平台
+      preamp_db = 0.0, -- 叠加在算出增益上的前级,dB
+      prevent_clipping = true, -- 按峰值收住正增益,避免归一化后削波
+      limiter = { -- 峰值限幅(最后一道保险,兜住无峰值信息的流)
+        enabled = true,
+        threshold_db = -1.0, -- 门限 dBFS
+        release_ms = 100, -- 回升时间常数,毫秒
+      },
+    },
+    -- 交叉淡化:当前曲尾段与下一曲开头叠混(替代 gapless 硬接)。同一专辑内连播与单曲循环
+    -- 恒走 gapless;预排窗口(daemon.gapless_prefetch_ms)自动按淡化时长提前
+    crossfade = {
+      enabled = false,
+      duration_ms = 6000, -- 淡化时长,毫秒;短曲按曲长一半封顶
+      curve = "equal_power", -- "linear" | "equal_power" | "s_curve"
+    },
+    -- 参数均衡器:在频谱取样之前,频谱显示的就是均衡后的声音。preset 选启动时启用的预设
+    -- (nil = 旁路);运行期可用 mineral.player.set_eq(name) 切换。段类型:"peaking" |
+    -- "low_shelf" | "high_shelf" | "high_pass" | "low_pass";gain_db 省略 = 0,q 省略 = 0.707
+    eq = {
+      preset = nil,
+      presets = {
+        bass_boost = {
+          preamp_db = -4.0, -- 给提升段留余量,避免削波
+          bands = {
+            { kind = "low_shelf", freq_hz = 100, gain_db = 5.0 },
+            { kind = "peaking", freq_hz = 250, gain_db = -1.5, q = 1.0 },
+          },
+        },
+        treble_boost = {
+          preamp_db = -3.0,
+          bands = {
+            { kind = "high_shelf", freq_hz = 6000, gain_db = 4.0 },
+          },
+        },
+        vocal = {
+          preamp_db = -2.5,
+          bands = {
+            { kind = "high_pass", freq_hz = 80 },
+            { kind = "peaking", freq_hz = 300, gain_db = -2.0, q = 1.0 },
+            { kind = "peaking", freq_hz = 2500, gain_db = 3.0, q = 1.2 },
+          },
+        },
+        loudness = {
+          preamp_db = -5.0,
+          bands = {
+            { kind = "low_shelf", freq_hz = 80, gain_db = 5.0 },
+            { kind = "peaking", freq_hz = 2500, gain_db = -1.0, q = 0.8 },
+            { kind = "high_shelf", freq_hz = 10000, gain_db = 3.5 },
+          },
+        },
+      },
+    },
   },
   -- 缓存容量(LRU,满了自动驱逐;改小不立刻删文件,下次写入时驱逐)。封面缓存预算在 tui.cover.cache。
   cache = {
@@ -393,6 +455,20 @@ return {
   download = {
     quality = "lossless", -- standard | higher | exhigh | lossless | hires;与播放音质独立
     dir = nil, -- 下载导出目录,绝对路径;缺省走默认(~/Music/mineral)
+    -- 导出路径模板(相对 dir):{source} {quality} {album} {album_artist} {artist} {artists}
+    -- {title} {track} {year} {id} {ext};数字可补零如 {track:02};取不到的变量为空,空括号自动去掉
+    -- 例:"{album_artist}/{album} ({year})/{track:02} - {title}.{ext}"
+    path_template = "{source}/{quality}/{album}/{title}.{ext}",
+    tags = { -- 下载完成后写入元信息,导出库在其他播放器 / NAS 上也能识别
+      enabled = true, -- 标题 / 艺人 / 专辑 / 专辑艺人 / 曲序 / 年份(总开关)
+      cover = true, -- 内嵌封面
+      lyrics = true, -- 内嵌歌词(有时间轴写 LRC)
+    },
+  },
+  -- 歌词来源(daemon 侧;歌词面板观感见 tui.lyrics)。
+  lyrics = {
+    prefer_local = true, -- 先找本地:同目录 .lrc → 内嵌标签 → 用户歌词目录;都没有才向来源拉取
+    dir = nil, -- 用户歌词目录,按 <来源>/<歌曲 id>.lrc 存放;缺省走默认(~/.local/share/mineral/lyrics)
   },
   sources = {
     mineral = {
@@ -402,6 +478,10 @@ return {
         chunk_size = 40, -- 每次 songs_detail 调用处理多少 id(= 聚合面刷新粒度 + 限住单次调用时长;非"请求数",请求怎么发是 channel 内部的事)
         max_concurrent = 3, -- 并行几个 songs_detail 调用(并发上限即节流;不管单次调用内部是一个还是多个请求,最多 N 个在飞)
       },
+      -- 声明式智能歌单(只读,排在 TUI 里自建的之前;数组整体替换),如:
+      --   { name = "冷落的收藏", query = "loved unplayed:90d" },
+      --   { name = "本月常听", query = "top:50 within:30d" },
+      smart_playlists = {},
     },
     netease = {
       timeout_secs = 100, -- 单次 API 请求超时,秒
@@ -415,6 +495,11 @@ return {
       max_connections = 0, -- 到源的最大并发连接,0 = 不限
       color = "#FF8cB0", -- B站品牌粉
     },
+    -- `local` 是 Lua 关键字,表键须写成 ["local"](用户 config.lua 同理)
+    ["local"] = {
+      color = "#8a7f6c", -- 中性岩灰(本地文件无品牌色)
+      roots = {}, -- 扫描根目录,绝对路径(可写 "~/Music");空 = 不启用本地源。根的直接子目录与 .m3u 文件成歌单
+    },
   },
   -- 队列:脚本注册的具名变换,出现在队列操作菜单的脚本段。
   -- transform 收有序队列与位置上下文,返回新的有序队列;返回的每首歌必须在原队列出现过
@@ -426,6 +511,12 @@ return {
   --     end },
   queue = {
     transforms = {},
+    -- 电台续播:顺序模式播到队尾前自动追加一批歌(gapless 预排窗口内完成,不断档)。
+    -- 候选先取当前曲所属源的相似歌曲 / 私人 FM,取不到回落本地统计(常听曲 / 常听艺人 + 收藏)
+    radio = {
+      enabled = false,
+      batch = 10, -- 每次续上的曲数上限
+    },
   },
   -- daemon 后端节拍。多为内部时序参数,默认值经过调校,没有明确诉求不要动。
   daemon = {
@@ -438,6 +529,18 @@ return {
     seek_threshold_ms = 1000, -- 进度偏离线性预期超过此值判定为 seek(供 MPRIS 上报)
     download_speed_tick_ms = 150, -- 下载测速刷新节流
     channel_workers_per = 8, -- 每个音乐源的后台并发 worker;大 = 抓取快但易撞限流
+    -- HTTP / WebSocket 遥控(手机 / web 面板);与 unix socket 并存。改后重启 daemon
+    http = {
+      enabled = false,
+      bind = "127.0.0.1:6690", -- 监听地址;局域网访问改成 "0.0.0.0:6690",此时必须设 token
+      token = nil, -- 访问令牌(Bearer 头或 ?token=);nil = 不鉴权,仅允许回环地址
+    },
+    -- MPD 协议兼容层(ncmpcpp / mpc / 状态栏模块 / 手机 MPD 遥控)。改后重启 daemon
+    mpd = {
+      enabled = false,
+      bind = "127.0.0.1:6600", -- 监听地址;局域网访问改成 "0.0.0.0:6600",此时必须设 password
+      password = nil, -- 连接密码(MPD password 命令);nil = 不设防,仅允许回环地址
+    },
   },
   -- 脚本运行时(config.lua 顶层的 mineral.* 调用在 daemon 内真实生效)。
   script = {
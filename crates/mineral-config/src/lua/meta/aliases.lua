@@ -27,7 +27,7 @@
 ---@alias mineral.KeyBinding string|string[]
 
 ---来源名:内置源有补全,插件源写任意 string 也合法(没加载的名字运行时静默跳过)。
----@alias mineral.SourceName "netease"|"bilibili"|string
+---@alias mineral.SourceName "netease"|"bilibili"|"local"|string
 
 ---channel 搜索的目标类型(封闭集合,typo 加载期报错)。
 ---@alias mineral.SearchKind "song"|"album"|"artist"|"playlist"|"user"
@@ -39,6 +39,11 @@
 ---{ pattern = "..." } = 占位串 {h}{hh}{m}{mm}{s}{ss}(最细到秒)。
 ---@alias mineral.TimeFormat "clock"|"seconds"|{ pattern: string }
 
+---音频后端:"auto" = 打开声卡,失败降级无声;"null" = 强制无声;
+---{ file = { path = "...", realtime? = bool } } = 不出声,把混音后的 PCM 渲染进文件
+---(`.flac` 写 24-bit FLAC,其余写 32-bit 浮点 WAV;realtime 省略 = 尽快渲染)。
+---@alias mineral.BackendKind "auto"|"null"|{ file: { path: string, realtime?: boolean } }
+
 ---弹出菜单相对锚点行的横向对齐:关键字,或 0.0~1.0 数字精确指定比例
 ---(0 贴左 / 0.5 居中 / 1 贴右)。
 ---@alias mineral.MenuAlign "left"|"center"|"right"|number
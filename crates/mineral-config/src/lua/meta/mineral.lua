@@ -290,6 +290,20 @@ function mineral.player.seek_to(secs) end
 ---@param pct integer  0-100
 function mineral.player.set_volume(pct) end
 
+--- 按名切换均衡预设(`audio.eq.presets` 的键;nil = 旁路)。未定义的名字由 daemon 记 warn 并忽略。
+---@param name string|nil
+function mineral.player.set_eq(name) end
+
+--- 设播放倍速(越界 clamp 到 0.5-3,不报错);跨曲保持,位置 / 歌词同步仍按曲内时间走。
+---@param speed number  1 = 原速
+---@param mode? "time_stretch"|"pitch_shift"  省略 = time_stretch(保持音高);pitch_shift 音高随速度升降
+function mineral.player.set_speed(speed, mode) end
+
+--- 在播曲的歌词时间偏移增减若干毫秒(正 = 歌词推迟,负 = 提前;累计钳到 ±10s)。
+--- 按曲持久(song_kv 键 `lyric_offset_ms`),TUI 歌词与 MPRIS `xesam:asText` 同步生效。
+---@param ms integer
+function mineral.player.nudge_lyric_offset(ms) end
+
 --- 设播放模式(未知名报错)。
 ---@param mode mineral.PlayMode
 function mineral.player.set_mode(mode) end
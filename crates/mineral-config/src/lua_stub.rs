@@ -2,20 +2,22 @@
 //! 宏生成的 `---@class` / `---@alias` 常量,按主题序合成完整 LuaCATS stub。
 
 use crate::schema::{
-    AmbientConfig, AmbientTrailConfig, AnchorConfig, AnimationConfig, AudioConfig, BackendKind,
-    BackfillSection, BarsConfig, BehaviorConfig, BilibiliSection, CacheConfig, ChannelSearchConfig,
-    Config, CopyConfig, CopyContext, CopyTemplate, CoverCacheConfig, CoverConfig,
-    CoverProtocolMode, CoverStorageMode, CoverTransitionConfig, CoverTransitionStyle, DaemonConfig,
-    DeepSearchConfig, DeepWeights, DownloadConfig, DriftConfig, DynamicThemeConfig, EnvelopeConfig,
-    FsSpectrumConfig, HighpassConfig, KeysConfig, KittyTransmitConfig, KmeansConfig, LayoutConfig,
-    LyricsConfig, MarqueeBounceConfig, MarqueeConfig, MarqueeLoopConfig, MarqueeMode, MenuReveal,
-    MineralSection, NeteaseSection, PrefetchConfig, PulseConfig, PulseDepthConfig, PunchConfig,
-    QueueConfig, QueueTransform, ReportConfig, RotateConfig, ScopeConfig, ScriptConfig,
-    SearchConfig, SearchFocusTransition, SearchHitConfig, SearchQueryMode, ShelfConfig,
-    SourcesConfig, SpectrumConfig, SpectrumStyle, StatsConfig, StatsLevel, SweepStyle,
-    TerrainConfig, TextAlphaConfig, TextStyle, ThemeConfig, TitleField, TitleIcons, ToastConfig,
-    TrackPosMemory, TrailTimingConfig, TuiConfig, VignetteConfig, WaterfallConfig, WaveformConfig,
-    WindowTitleConfig, ZoomConfig,
+    AmbientConfig, AmbientTrailConfig, AnchorConfig, AnimationConfig, AudioConfig, BackfillSection,
+    BarsConfig, BehaviorConfig, BilibiliSection, CacheConfig, ChannelSearchConfig, Config,
+    CopyConfig, CopyContext, CopyTemplate, CoverCacheConfig, CoverConfig, CoverProtocolMode,
+    CoverStorageMode, CoverTransitionConfig, CoverTransitionStyle, CrossfadeConfig, CrossfadeCurve,
+    DaemonConfig, DeepSearchConfig, DeepWeights, DownloadConfig, DownloadTagsConfig, DriftConfig,
+    DynamicThemeConfig, EnvelopeConfig, EqBandConfig, EqBandKind, EqConfig, EqPresetConfig,
+    FsSpectrumConfig, HighpassConfig, HttpConfig, KeysConfig, KittyTransmitConfig, KmeansConfig,
+    LayoutConfig, LimiterConfig, LocalSection, LyricSourcesConfig, LyricsConfig,
+    MarqueeBounceConfig, MarqueeConfig, MarqueeLoopConfig, MarqueeMode, MenuReveal, MineralSection,
+    MpdConfig, NeteaseSection, NormalizationConfig, NormalizationMode, PrefetchConfig, PulseConfig,
+    PulseDepthConfig, PunchConfig, QueueConfig, QueueTransform, RadioConfig, ReportConfig,
+    RotateConfig, ScopeConfig, ScriptConfig, SearchConfig, SearchFocusTransition, SearchHitConfig,
+    SearchQueryMode, ShelfConfig, SmartPlaylistConfig, SourcesConfig, SpectrumConfig,
+    SpectrumStyle, StatsConfig, StatsLevel, SweepStyle, TerrainConfig, TextAlphaConfig, TextStyle,
+    ThemeConfig, TitleField, TitleIcons, ToastConfig, TrackPosMemory, TrailTimingConfig, TuiConfig,
+    VignetteConfig, WaterfallConfig, WaveformConfig, WindowTitleConfig, ZoomConfig,
 };
 
 /// 文件头:`---@meta` 声明 + 使用说明(手写 prose,不随 schema 变)。
@@ -35,7 +37,6 @@ const ALIASES: &str = include_str!("lua/meta/aliases.lua");
 pub(crate) fn meta_config_lua() -> String {
     let enum_aliases = [
         SpectrumStyle::LUA_ALIAS,
-        BackendKind::LUA_ALIAS,
         TrackPosMemory::LUA_ALIAS,
         CoverProtocolMode::LUA_ALIAS,
         CoverStorageMode::LUA_ALIAS,
@@ -49,6 +50,9 @@ pub(crate) fn meta_config_lua() -> String {
         TitleField::LUA_ALIAS,
         StatsLevel::LUA_ALIAS,
         SearchQueryMode::LUA_ALIAS,
+        NormalizationMode::LUA_ALIAS,
+        CrossfadeCurve::LUA_ALIAS,
+        EqBandKind::LUA_ALIAS,
     ]
     .join("\n\n");
     let classes = [
@@ -103,16 +107,29 @@ pub(crate) fn meta_config_lua() -> String {
         EnvelopeConfig::LUA_STUB,
         ShelfConfig::LUA_STUB,
         HighpassConfig::LUA_STUB,
+        NormalizationConfig::LUA_STUB,
+        LimiterConfig::LUA_STUB,
+        CrossfadeConfig::LUA_STUB,
+        EqConfig::LUA_STUB,
+        EqPresetConfig::LUA_STUB,
+        EqBandConfig::LUA_STUB,
         CacheConfig::LUA_STUB,
         DownloadConfig::LUA_STUB,
+        DownloadTagsConfig::LUA_STUB,
+        LyricSourcesConfig::LUA_STUB,
         SourcesConfig::LUA_STUB,
         QueueConfig::LUA_STUB,
         QueueTransform::LUA_STUB,
+        RadioConfig::LUA_STUB,
         NeteaseSection::LUA_STUB,
         BilibiliSection::LUA_STUB,
+        LocalSection::LUA_STUB,
         MineralSection::LUA_STUB,
         BackfillSection::LUA_STUB,
+        SmartPlaylistConfig::LUA_STUB,
         DaemonConfig::LUA_STUB,
+        HttpConfig::LUA_STUB,
+        MpdConfig::LUA_STUB,
         ScriptConfig::LUA_STUB,
         StatsConfig::LUA_STUB,
         ReportConfig::LUA_STUB,
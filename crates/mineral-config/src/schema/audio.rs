@@ -1,13 +1,18 @@
-//! 音频段(音量 / 后端 / 播放音质 / 引擎内参)。
+//! 音频段(音量 / 后端 / 播放音质 / 引擎内参 / 响度归一化 / 交叉淡化 / 均衡器)。
 //!
 //! [`BackendKind`] 与音频层的后端模式语义对齐,但保持 config 与音频 crate 解耦——
 //! client 接线处做 `BackendKind → 音频后端模式` 映射,本枚举不依赖音频 crate。
 
-use mineral_config_macros::{config_section, lua_enum};
+use std::path::PathBuf;
+
+use mineral_config_macros::config_section;
 use mineral_model::BitRate;
 use serde::Deserialize;
 
+use super::crossfade::CrossfadeConfig;
 use super::envelope::EnvelopeConfig;
+use super::eq::EqConfig;
+use super::normalization::NormalizationConfig;
 
 /// 音频段。
 #[config_section]
@@ -18,6 +23,10 @@ pub struct AudioConfig {
     /// 后端选择;环境变量 `MINERAL_AUDIO_NULL` 优先于本字段。
     backend: BackendKind,
 
+    /// 钉住的输出设备名(`mineral audio devices` 列出);`None`(Lua `nil`)= 系统默认设备。
+    /// 钉住的设备不在(如 USB DAC 拔出)时回落默认,重新出现后自动切回。
+    device: Option<String>,
+
     /// 在线播放音质(独立于下载音质);高音质更耗流量,源没有对应档会回落。
     playback_quality: BitRate,
 
@@ -33,11 +42,22 @@ pub struct AudioConfig {
 
     /// 响度包络段(波形 seekbar 的离线包络计算参数)。
     envelope: EnvelopeConfig,
+
+    /// 响度归一化段(ReplayGain / R128 增益 + 峰值限幅)。
+    normalization: NormalizationConfig,
+
+    /// 交叉淡化段(曲间叠混;同专辑连播恒 gapless)。
+    crossfade: CrossfadeConfig,
+
+    /// 均衡器段(具名预设;运行期可按名切换)。
+    eq: EqConfig,
 }
 
 /// 音频后端选择。不依赖音频 crate;接线处映射到具体后端模式。
-#[lua_enum]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+///
+/// 单元变体写作字符串(`"auto"` / `"null"`),`File` 写作 `{ file = { path = "..." } }`;
+/// LuaCATS 别名手写在 `meta/aliases.lua`。
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum BackendKind {
@@ -46,4 +66,14 @@ pub enum BackendKind {
 
     /// 强制空跑(无声卡)。
     Null,
+
+    /// 不出声,把混音后的 PCM 流渲染进文件(无头 CI / 测试断言实际产出)。
+    File {
+        /// 目标文件;扩展名 `.flac` 写 24-bit FLAC,其余写 32-bit 浮点 WAV。已存在则覆盖。
+        path: PathBuf,
+
+        /// `true` = 按墙钟实时渲染;省略 / `false` = 尽快渲染(远快于实时)。
+        #[serde(default)]
+        realtime: bool,
+    },
 }
@@ -1,6 +1,6 @@
 //! TUI 交互手感段(挂在 `TuiConfig` 下,经 `cfg.tui().behavior()` 取)。
 //!
-//! const 审计补录的交互旋钮:音量/seek 步长、列表大步跳行、滚动步长与边距、
+//! const 审计补录的交互旋钮:音量/seek/歌词偏移步长、列表大步跳行、滚动步长与边距、
 //! 自拉起 daemon 的退出续命。
 //! 命令名 + 这些步长参数组装成可执行动作是 client 接线的事;本段只承载强类型值。
 
@@ -19,6 +19,9 @@ pub struct BehaviorConfig {
     /// 大步 seek 步长(秒)。
     seek_big_step_secs: u32,
 
+    /// 歌词时间偏移单次微调步长(毫秒),≥1。
+    lyric_offset_step_ms: u16,
+
     /// 列表大步跳行的行数,≥1。
     list_jump_rows: u16,
 
@@ -16,6 +16,7 @@ use super::daemon::DaemonConfig;
 use super::download::DownloadConfig;
 use super::keys::KeysConfig;
 use super::layout::LayoutConfig;
+use super::lyric_sources::LyricSourcesConfig;
 use super::lyrics::LyricsConfig;
 use super::prefetch::PrefetchConfig;
 use super::queue::QueueConfig;
@@ -44,13 +45,16 @@ pub struct Config {
     /// 下载段(音质 / 目录)。
     download: DownloadConfig,
 
+    /// 歌词来源段(本地歌词优先 + 用户歌词目录)。
+    lyrics: LyricSourcesConfig,
+
     /// 音乐源段(网易云等)。
     sources: SourcesConfig,
 
-    /// 队列段(脚本注册的具名队列变换)。
+    /// 队列段(脚本注册的具名队列变换 + 电台续播)。
     queue: QueueConfig,
 
-    /// daemon 段(gapless 预取 + 各间隔节拍)。
+    /// daemon 段(gapless 预取 + 各间隔节拍 + HTTP / MPD 遥控)。
     daemon: DaemonConfig,
 
     /// 脚本运行时段(watchdog 双阈值)。
@@ -0,0 +1,36 @@
+//! 交叉淡化段(挂在 `AudioConfig` 下):当前曲尾段与下一曲开头叠混。
+//!
+//! 复用 gapless 的预排:窗口按淡化时长提前打开,下一曲就绪后挂到当前曲尾段。同一专辑内的
+//! 连播(以及单曲循环)恒走 gapless,不打断专辑本身的无缝衔接。
+
+use mineral_config_macros::{config_section, lua_enum};
+use serde::Deserialize;
+
+/// 交叉淡化配置。
+#[config_section]
+pub struct CrossfadeConfig {
+    /// 是否启用;关闭时所有切换都是 gapless。
+    enabled: bool,
+
+    /// 淡化时长(毫秒);短曲按曲长一半封顶。
+    duration_ms: u32,
+
+    /// 淡化曲线。
+    curve: CrossfadeCurve,
+}
+
+/// 淡化曲线。
+#[lua_enum]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CrossfadeCurve {
+    /// 线性交换;中点略凹。
+    Linear,
+
+    /// 等功率(正弦 / 余弦);中点响度不塌。
+    EqualPower,
+
+    /// S 形;首尾缓、中段快。
+    SCurve,
+}
@@ -1,6 +1,7 @@
 //! daemon 段:gapless 预取 + 播放器/服务端各间隔节拍。
 //!
-//! 这些是领域/后端逻辑(非 TUI 交互手感):prev 分界、循环节拍、心跳、上报间隔等。
+//! 这些是领域/后端逻辑(非 TUI 交互手感):prev 分界、循环节拍、心跳、上报间隔等;
+//! `http` / `mpd` 子表是 unix socket 之外的可选遥控入口(HTTP + WebSocket / MPD 协议)。
 
 use mineral_config_macros::config_section;
 
@@ -34,4 +35,39 @@ pub struct DaemonConfig {
 
     /// 每个 channel 的任务 worker 数(user/bg 两级队列共享),≥1;大了抓取快但更容易撞源限流。
     channel_workers_per: usize,
+
+    /// HTTP / WebSocket 遥控子表(局域网手机 / web 面板)。
+    http: HttpConfig,
+
+    /// MPD 协议兼容子表(ncmpcpp / mpc / 状态栏模块 / 手机 MPD 遥控)。
+    mpd: MpdConfig,
+}
+
+/// HTTP / WebSocket 遥控:REST 控制播放 / 队列 / 搜索 / 歌单库,WebSocket 推属性变更与曲终,
+/// 另供当前曲封面与歌词。与 unix socket 并存,驱动的是同一个 daemon。
+#[config_section]
+pub struct HttpConfig {
+    /// 是否启用;关闭时 daemon 只听 unix socket。
+    enabled: bool,
+
+    /// 监听地址 `host:port`;默认只绑回环。非回环地址必须配 `token`,否则 daemon 拒绝启动。
+    bind: String,
+
+    /// 访问令牌:请求带 `Authorization: Bearer <token>` 或 `?token=<token>`;
+    /// `None`(Lua `nil`)= 不鉴权,仅允许回环地址。
+    token: Option<String>,
+}
+
+/// MPD 协议兼容层:现成的 MPD 客户端经它控制播放、看队列、搜歌加歌。只覆盖播放控制 /
+/// 队列 / 搜索 / idle 这一子集,没有 MPD 的本地曲库数据库。
+#[config_section]
+pub struct MpdConfig {
+    /// 是否启用;关闭时不听 MPD 端口。
+    enabled: bool,
+
+    /// 监听地址 `host:port`;默认只绑回环。非回环地址必须配 `password`,否则 daemon 拒绝启动。
+    bind: String,
+
+    /// 连接密码(客户端以 MPD `password` 命令出示);`None`(Lua `nil`)= 不设防,仅允许回环地址。
+    password: Option<String>,
 }
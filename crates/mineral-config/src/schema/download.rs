@@ -1,13 +1,16 @@
-//! 下载段(音质 / 目录)。
+//! 下载段(音质 / 目录 / 路径模板 / 写标签)。
 //!
 //! `quality` 直接复用 [`mineral_model::BitRate`](其 serde 已是小写名,契合 schema);
-//! `dir` 为 `Option`,Lua `nil`(字段缺省)→ `None`,接线处回落到默认导出目录。
+//! `dir` 为 `Option`,Lua `nil`(字段缺省)→ `None`,接线处回落到默认导出目录;
+//! `path_template` 落型即解析(见 [`PathTemplate`]),非法模板同其他坏值一样报配置告警。
 
 use mineral_config_macros::config_section;
 use std::path::PathBuf;
 
 use mineral_model::BitRate;
 
+use super::PathTemplate;
+
 /// 下载段。
 #[config_section]
 pub struct DownloadConfig {
@@ -16,4 +19,26 @@ pub struct DownloadConfig {
 
     /// 下载导出目录,绝对路径;`None`(Lua `nil`)→ 接线处回落平台默认导出目录(`~/Music/mineral`)。
     dir: Option<PathBuf>,
+
+    /// 导出路径模板(相对 `dir`,`/` 分目录):`{变量}` 按歌曲信息填充、各段做文件名安全规整,
+    /// 扩展名由实际格式决定(模板末尾的 `.{ext}` 可省)。
+    #[lua_type("string")]
+    path_template: PathTemplate,
+
+    /// 下载完成后往文件里写元信息(见 [`DownloadTagsConfig`])。
+    tags: DownloadTagsConfig,
+}
+
+/// 下载后写标签(挂在 `DownloadConfig` 下):CDN 流多半不带标签,导出库拿到别的播放器 /
+/// NAS 上要靠这些字段识别。
+#[config_section]
+pub struct DownloadTagsConfig {
+    /// 是否写标题 / 艺人 / 专辑等文本标签(总开关;关则封面、歌词也不写)。
+    enabled: bool,
+
+    /// 是否内嵌封面(按歌曲封面 URL 拉取)。
+    cover: bool,
+
+    /// 是否内嵌歌词(有时间轴写 LRC,否则写纯文本)。
+    lyrics: bool,
 }
@@ -0,0 +1,112 @@
+//! 均衡器段(挂在 `AudioConfig` 下):具名预设 + 启动时选中的预设。
+//!
+//! 预设是一组按序级联的 biquad 段加前级增益;运行期经 `SetEq` 请求 / `mineral.player.set_eq`
+//! 按名切换(不落盘,每次启动回到 `preset`)。预设表按键深合并:用户可只覆盖或新增某个预设。
+
+use mineral_config_macros::{config_section, lua_enum};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+
+/// 均衡器配置。
+#[config_section]
+#[lua_optional_by_serde]
+pub struct EqConfig {
+    /// 启动时启用的预设名;省略 = 旁路。须是 `presets` 里的键。
+    #[serde(default)]
+    preset: Option<String>,
+
+    /// 具名预设(名 → 参数)。
+    presets: FxHashMap<String, EqPresetConfig>,
+}
+
+/// 一个均衡预设。
+#[config_section]
+pub struct EqPresetConfig {
+    /// 前级增益(dB);有提升段时调低以留余量,避免削波。
+    preamp_db: f64,
+
+    /// 各段,按顺序级联(数组整体替换)。
+    bands: Vec<EqBandConfig>,
+}
+
+/// 一段均衡。
+#[config_section]
+#[lua_optional_by_serde]
+pub struct EqBandConfig {
+    /// 滤波类型。
+    kind: EqBandKind,
+
+    /// 中心 / 转折 / 截止频率(Hz)。
+    freq_hz: f64,
+
+    /// 增益(dB;高低通忽略)。省略 = 0。
+    #[serde(default)]
+    gain_db: f64,
+
+    /// 品质因数;省略 = 0.707(搁架 / 高低通的平坦过渡)。
+    #[serde(default = "default_q")]
+    q: f64,
+}
+
+/// 段的默认品质因数(Butterworth)。
+fn default_q() -> f64 {
+    std::f64::consts::FRAC_1_SQRT_2
+}
+
+/// 均衡段的滤波类型。
+#[lua_enum]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EqBandKind {
+    /// 峰值(钟形):中心频率处增减。
+    Peaking,
+
+    /// 低频搁架:转折频率以下整体增减。
+    LowShelf,
+
+    /// 高频搁架:转折频率以上整体增减。
+    HighShelf,
+
+    /// 高通:截止频率以下衰减。
+    HighPass,
+
+    /// 低通:截止频率以上衰减。
+    LowPass,
+}
+
+impl EqConfig {
+    /// 按名取预设。
+    ///
+    /// # Params:
+    ///   - `name`: 预设名
+    ///
+    /// # Return:
+    ///   预设参数;未定义时 `None`。
+    pub fn preset_named(&self, name: &str) -> Option<&EqPresetConfig> {
+        self.presets.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EqBandKind;
+
+    /// 默认预设均合法(非空段),起始为旁路;段的省略字段落到默认值。
+    #[test]
+    fn default_presets_parse() -> color_eyre::Result<()> {
+        let cfg = crate::Config::defaults()?;
+        let eq = cfg.audio().eq();
+        assert_eq!(eq.preset(), &None);
+        let bass = eq
+            .preset_named("bass_boost")
+            .ok_or_else(|| color_eyre::eyre::eyre!("缺 bass_boost 预设"))?;
+        assert!(!bass.bands().is_empty());
+        let band: super::EqBandConfig =
+            serde_json::from_value(serde_json::json!({ "kind": "high_pass", "freq_hz": 30.0 }))?;
+        assert_eq!(band.kind(), &EqBandKind::HighPass);
+        assert!((band.gain_db() - 0.0).abs() < f64::EPSILON);
+        assert!((band.q() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+        Ok(())
+    }
+}
@@ -44,6 +44,15 @@ pub struct KeysConfig {
     /// 循环歌词副语言(原文 → 翻译 → 罗马音)。
     cycle_lyric: KeyBinding,
 
+    /// 在播曲歌词推迟一步(步长见 `behavior.lyric_offset_step_ms`;歌词比歌声早时用),按曲持久。
+    lyric_later: KeyBinding,
+
+    /// 在播曲歌词提前一步(步长见 `behavior.lyric_offset_step_ms`),按曲持久。
+    lyric_earlier: KeyBinding,
+
+    /// 为在播曲打开歌词编辑器(贴入文本、边播边打轴,存为该曲的 `.lrc` 覆盖)。
+    edit_lyrics: KeyBinding,
+
     /// 进入搜索输入态(全屏态屏蔽)。
     enter_search: KeyBinding,
 
@@ -0,0 +1,19 @@
+//! 歌词来源段(daemon 侧):本地歌词优先于 channel 歌词的开关与用户歌词目录。
+//!
+//! `dir` 为 `Option`,Lua `nil`(字段缺省)→ `None`,接线处回落到默认歌词目录。
+
+use std::path::PathBuf;
+
+use mineral_config_macros::config_section;
+
+/// 歌词来源段。
+#[config_section]
+pub struct LyricSourcesConfig {
+    /// 先找本地歌词(同目录 `.lrc` / 内嵌标签 / 用户歌词目录),都没有才向 channel 拉取。
+    /// 歌词编辑器存下的覆盖不受它管,总是先查。
+    prefer_local: bool,
+
+    /// 用户歌词目录,按 `<来源>/<歌曲 id>.lrc` 存放;`None`(Lua `nil`)→ 接线处回落
+    /// 平台默认目录(`~/.local/share/mineral/lyrics`)。
+    dir: Option<PathBuf>,
+}
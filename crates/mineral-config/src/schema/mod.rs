@@ -8,13 +8,18 @@ mod cache;
 mod config;
 mod copy;
 mod cover;
+mod crossfade;
 mod daemon;
 mod de;
 mod download;
 mod envelope;
+mod eq;
 mod keys;
 mod layout;
+mod lyric_sources;
 mod lyrics;
+mod normalization;
+mod path_template;
 mod prefetch;
 mod queue;
 mod script;
@@ -44,19 +49,24 @@ pub use cover::{
     CoverCacheConfig, CoverConfig, CoverProtocolMode, CoverStorageMode, CoverTransitionConfig,
     CoverTransitionStyle, KittyTransmitConfig, KmeansConfig, ZoomConfig,
 };
-pub use daemon::DaemonConfig;
-pub use download::DownloadConfig;
+pub use crossfade::{CrossfadeConfig, CrossfadeCurve};
+pub use daemon::{DaemonConfig, HttpConfig, MpdConfig};
+pub use download::{DownloadConfig, DownloadTagsConfig};
 pub use envelope::{EnvelopeConfig, HighpassConfig, ShelfConfig};
+pub use eq::{EqBandConfig, EqBandKind, EqConfig, EqPresetConfig};
 pub use keys::{KeyBinding, KeysConfig};
 pub use layout::{FsSpectrumConfig, LayoutConfig, MenuAlign};
+pub use lyric_sources::LyricSourcesConfig;
 pub use lyrics::LyricsConfig;
+pub use normalization::{LimiterConfig, NormalizationConfig, NormalizationMode};
+pub use path_template::{PathTemplate, TemplatePiece, TemplateVar};
 pub use prefetch::PrefetchConfig;
-pub use queue::{QUEUE_TRANSFORM_FNS, QueueConfig, QueueTransform};
+pub use queue::{QUEUE_TRANSFORM_FNS, QueueConfig, QueueTransform, RadioConfig};
 pub use script::ScriptConfig;
 pub use search::{ChannelSearchConfig, DeepSearchConfig, DeepWeights, SearchConfig};
 pub use sources::{
     BackfillSection, BilibiliSection, CURATE_PLAYLISTS_MERGED_FN, CURATE_PLAYLISTS_SOURCE_FNS,
-    MineralSection, NeteaseSection, SourcesConfig,
+    LocalSection, MineralSection, NeteaseSection, SmartPlaylistConfig, SourcesConfig,
 };
 pub use spectrum::{
     BarsConfig, ScopeConfig, SpectrumConfig, SpectrumStyle, TerrainConfig, WaterfallConfig,
@@ -0,0 +1,56 @@
+//! 响度归一化段(挂在 `AudioConfig` 下):ReplayGain 标签 / R128 分析 → 逐曲增益 + 峰值限幅。
+//!
+//! 增益来源优先级:文件自带的 ReplayGain 标签(本地曲库 / 下载导出 / 缓存命中的本地文件)
+//! 优先,其次是 daemon 离线分析落库的 R128 积分响度;两者都没有(首次流播)的曲目保持原样,
+//! 等分析算完再平滑补上。
+
+use mineral_config_macros::{config_section, lua_enum};
+use serde::Deserialize;
+
+/// 响度归一化配置。
+#[config_section]
+pub struct NormalizationConfig {
+    /// 归一化模式;`off` 时增益与限幅器都不介入。
+    mode: NormalizationMode,
+
+    /// 目标积分响度(LUFS);ReplayGain 标签按其 −18 LUFS 参考电平折算到这里。
+    target_lufs: f64,
+
+    /// 额外前级增益(dB),叠加在算出的增益上。
+    preamp_db: f64,
+
+    /// 按峰值收住正增益,保证归一化后不超满幅(无峰值信息时只靠限幅器)。
+    prevent_clipping: bool,
+
+    /// 峰值限幅器(归一化链路的最后一道保险)。
+    limiter: LimiterConfig,
+}
+
+/// 峰值限幅器配置。
+#[config_section]
+pub struct LimiterConfig {
+    /// 是否启用(归一化 `off` 时无论此值都不介入)。
+    enabled: bool,
+
+    /// 限幅门限(dBFS,≤ 0;正值按 0 处理)。
+    threshold_db: f64,
+
+    /// 增益回升的时间常数(毫秒)。
+    release_ms: u32,
+}
+
+/// 归一化模式。
+#[lua_enum]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum NormalizationMode {
+    /// 不归一化。
+    Off,
+
+    /// 逐曲归一到目标响度(随机播放 / 混合歌单)。
+    Track,
+
+    /// 按专辑增益归一,保留专辑内曲目间的相对响度;无专辑增益时回落逐曲。
+    Album,
+}
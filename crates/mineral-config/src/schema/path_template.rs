@@ -0,0 +1,302 @@
+//! 下载导出路径模板(`download.path_template`)的语法:`{变量}` 占位 + `/` 分目录。
+//!
+//! 落型即解析:未知变量、花括号不配对、`{ext}` 不在末尾等按普通配置错误处理(带字段路径
+//! 告警,本段回落默认)。按歌曲取值与文件名规整在 daemon 导出侧做,本模块只管语法。
+
+use color_eyre::eyre::{bail, eyre};
+use serde::Deserialize;
+
+/// 模板末尾的扩展名占位(可省;解析时剥掉,渲染时按实际格式补)。
+const EXT_SUFFIX: &str = ".{ext}";
+
+/// 模板变量。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateVar {
+    /// 来源名(`netease` 等)。
+    Source,
+
+    /// 下载音质(`lossless` 等)。
+    Quality,
+
+    /// 歌名(空则退歌曲 id)。
+    Title,
+
+    /// 专辑名。
+    Album,
+
+    /// 专辑艺人:来源不给时退主艺人。
+    AlbumArtist,
+
+    /// 主艺人。
+    Artist,
+
+    /// 全部艺人(`, ` 连接)。
+    Artists,
+
+    /// 音轨号。
+    Track,
+
+    /// 发行年。
+    Year,
+
+    /// 来源内歌曲 id。
+    Id,
+}
+
+impl TemplateVar {
+    /// 按变量名查表。
+    ///
+    /// # Params:
+    ///   - `name`: 花括号内的变量名(不含宽度)
+    ///
+    /// # Return:
+    ///   对应变量;未知名为 `None`。
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "source" => Self::Source,
+            "quality" => Self::Quality,
+            "title" => Self::Title,
+            "album" => Self::Album,
+            "album_artist" => Self::AlbumArtist,
+            "artist" => Self::Artist,
+            "artists" => Self::Artists,
+            "track" => Self::Track,
+            "year" => Self::Year,
+            "id" => Self::Id,
+            _ => return None,
+        })
+    }
+
+    /// 是否数字变量(只有它接受补零宽度)。
+    fn numeric(self) -> bool {
+        matches!(self, Self::Track | Self::Year)
+    }
+}
+
+/// 模板段内的一个片段。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplatePiece {
+    /// 原样输出的字面文本。
+    Lit(String),
+
+    /// 变量占位。
+    Var {
+        /// 变量。
+        var: TemplateVar,
+
+        /// 补零宽度(`{track:02}` → 2);0 = 不补。
+        width: usize,
+    },
+}
+
+/// 解析好的导出路径模板。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathTemplate {
+    /// 目录段(相对导出根,可为空 = 直接落根下)。
+    dirs: Vec<Vec<TemplatePiece>>,
+
+    /// 文件名主干(不含扩展名)。
+    stem: Vec<TemplatePiece>,
+}
+
+impl Default for PathTemplate {
+    /// 默认模板 `{source}/{quality}/{album}/{title}.{ext}`(与缓存库布局一致)。
+    fn default() -> Self {
+        let var = |var| vec![TemplatePiece::Var { var, width: 0 }];
+        Self {
+            dirs: vec![
+                var(TemplateVar::Source),
+                var(TemplateVar::Quality),
+                var(TemplateVar::Album),
+            ],
+            stem: var(TemplateVar::Title),
+        }
+    }
+}
+
+impl PathTemplate {
+    /// 解析模板串。
+    ///
+    /// # Params:
+    ///   - `raw`: 配置里的模板(如 `{album_artist}/{album}/{track:02} - {title}.{ext}`)
+    ///
+    /// # Return:
+    ///   解析结果;空模板 / 未知变量 / 花括号不配对 / `{ext}` 不在末尾返回 `Err`。
+    pub fn parse(raw: &str) -> color_eyre::Result<Self> {
+        let trimmed = raw.trim();
+        let body = trimmed.strip_suffix(EXT_SUFFIX).unwrap_or(trimmed);
+        let mut segments = body
+            .split('/')
+            .filter(|s| !s.trim().is_empty())
+            .map(parse_segment)
+            .collect::<color_eyre::Result<Vec<Vec<TemplatePiece>>>>()?;
+        let stem = segments
+            .pop()
+            .ok_or_else(|| eyre!("路径模板为空: {raw:?}"))?;
+        Ok(Self {
+            dirs: segments,
+            stem,
+        })
+    }
+
+    /// 目录段。
+    ///
+    /// # Return:
+    ///   各目录段的片段序列(可为空 = 直接落根下)。
+    pub fn dirs(&self) -> &[Vec<TemplatePiece>] {
+        &self.dirs
+    }
+
+    /// 文件名主干。
+    ///
+    /// # Return:
+    ///   主干片段序列(不含扩展名)。
+    pub fn stem(&self) -> &[TemplatePiece] {
+        &self.stem
+    }
+}
+
+impl<'de> Deserialize<'de> for PathTemplate {
+    /// 解析模板串;语法错误返 `de::Error`(经 `serde_path_to_error` 带字段路径)。
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Self::parse(&raw).map_err(|e| serde::de::Error::custom(format!("{e}")))
+    }
+}
+
+/// 解析单段(两个 `/` 之间)。
+///
+/// # Params:
+///   - `seg`: 段文本
+///
+/// # Return:
+///   片段序列。
+fn parse_segment(seg: &str) -> color_eyre::Result<Vec<TemplatePiece>> {
+    let mut pieces = Vec::new();
+    let mut lit = String::new();
+    let mut chars = seg.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => bail!("路径模板花括号不配对: {seg:?}"),
+                        Some(c) => inner.push(c),
+                    }
+                }
+                if !lit.is_empty() {
+                    pieces.push(TemplatePiece::Lit(std::mem::take(&mut lit)));
+                }
+                pieces.push(parse_var(&inner)?);
+            }
+            '}' => bail!("路径模板花括号不配对: {seg:?}"),
+            c => lit.push(c),
+        }
+    }
+    if !lit.is_empty() {
+        pieces.push(TemplatePiece::Lit(lit));
+    }
+    Ok(pieces)
+}
+
+/// 解析花括号内的 `name` / `name:width`。
+///
+/// # Params:
+///   - `inner`: 花括号内文本
+///
+/// # Return:
+///   变量片段。
+fn parse_var(inner: &str) -> color_eyre::Result<TemplatePiece> {
+    let (name, width) = match inner.split_once(':') {
+        Some((name, w)) => {
+            let width = w
+                .parse::<usize>()
+                .map_err(|e| eyre!("路径模板宽度非法: {{{inner}}}: {e}"))?;
+            (name.trim(), width)
+        }
+        None => (inner.trim(), 0),
+    };
+    if name == "ext" {
+        bail!("{{ext}} 只能出现在路径模板末尾");
+    }
+    let var = TemplateVar::from_name(name).ok_or_else(|| eyre!("路径模板未知变量: {{{name}}}"))?;
+    if width > 0 && !var.numeric() {
+        bail!("只有 {{track}} / {{year}} 支持补零宽度: {{{inner}}}");
+    }
+    Ok(TemplatePiece::Var { var, width })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathTemplate, TemplatePiece, TemplateVar};
+
+    /// 默认模板串解析结果等于 `Default`(default.lua 与缓存库布局对齐)。
+    #[test]
+    fn default_string_parses_to_default() -> color_eyre::Result<()> {
+        assert_eq!(
+            PathTemplate::parse("{source}/{quality}/{album}/{title}.{ext}")?,
+            PathTemplate::default()
+        );
+        Ok(())
+    }
+
+    /// 需求原例:专辑艺人 / 年份 / 补零音轨号都认,末尾 `.{ext}` 剥掉。
+    #[test]
+    fn parses_request_example() -> color_eyre::Result<()> {
+        let t = PathTemplate::parse("{album_artist}/{album} ({year})/{track:02} - {title}.{ext}")?;
+        let var = |var, width| TemplatePiece::Var { var, width };
+        let lit = |s: &str| TemplatePiece::Lit(s.to_owned());
+        assert_eq!(
+            t.dirs(),
+            [
+                vec![var(TemplateVar::AlbumArtist, 0)],
+                vec![
+                    var(TemplateVar::Album, 0),
+                    lit(" ("),
+                    var(TemplateVar::Year, 0),
+                    lit(")"),
+                ],
+            ]
+        );
+        assert_eq!(
+            t.stem(),
+            [
+                var(TemplateVar::Track, 2),
+                lit(" - "),
+                var(TemplateVar::Title, 0)
+            ]
+        );
+        Ok(())
+    }
+
+    /// 非法模板报错:未知变量、花括号不配对、`{ext}` 不在末尾、字符串变量带宽度、空模板。
+    #[test]
+    fn rejects_malformed_templates() {
+        for bad in [
+            "{albm}/{title}",
+            "{album/{title}",
+            "{album}}/{title}",
+            "{ext}/{title}",
+            "{title:02}",
+            " / ",
+        ] {
+            assert!(PathTemplate::parse(bad).is_err(), "应拒绝 {bad:?}");
+        }
+    }
+
+    /// 落型即校验:非法模板是反序列化错误(经加载管线成为带路径的配置告警)。
+    #[test]
+    fn deserialize_validates() {
+        assert!(
+            serde_json::from_value::<PathTemplate>(serde_json::json!("{year}/{title}")).is_ok()
+        );
+        assert!(
+            serde_json::from_value::<PathTemplate>(serde_json::json!("{albm}/{title}")).is_err()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! queue 段(顶层):脚本注册的具名队列变换。
+//! queue 段(顶层):脚本注册的具名队列变换 + 电台续播。
 //!
 //! `transform` 字段是 Lua function,没法进 serde 落型——加载管线在落型前把它从表里
 //! 摘走、存进 VM named registry(键 [`QUEUE_TRANSFORM_FNS`]),这里只落 `key`/`label`
@@ -18,6 +18,21 @@ pub const QUEUE_TRANSFORM_FNS: &str = "mineral.queue_transform_fns";
 pub struct QueueConfig {
     /// 具名队列变换,出现在队列操作菜单的脚本段(数组整体替换)。
     transforms: Vec<QueueTransform>,
+
+    /// 电台续播段(顺序模式队列将尽时自动续歌)。
+    radio: RadioConfig,
+}
+
+/// 电台续播:顺序模式播到队尾前,在 gapless 预排窗口内自动追加一批歌,播放不断。
+/// 候选优先取当前曲所属源的相似歌曲 / 私人 FM,源不支持或取空时回落本地统计
+/// (常听曲 / 常听艺人 + 收藏)。
+#[config_section]
+pub struct RadioConfig {
+    /// 是否启用;关闭时顺序模式播到队尾即停。
+    enabled: bool,
+
+    /// 每次续上的曲数上限,≥1。
+    batch: usize,
 }
 
 /// 一个具名队列变换(的展示侧;变换函数本体留在 VM,见模块文档)。
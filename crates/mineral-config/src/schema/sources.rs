@@ -29,6 +29,9 @@ pub struct SourcesConfig {
     /// 哔哩哔哩源段。
     bilibili: BilibiliSection,
 
+    /// 本地曲库源段(扫描根下的音频文件)。
+    local: LocalSection,
+
     /// Mineral 聚合源段(全源收藏投影)。
     mineral: MineralSection,
 }
@@ -43,6 +46,7 @@ impl SourcesConfig {
         vec![
             ("netease", self.netease.color()),
             ("bilibili", self.bilibili.color()),
+            ("local", self.local.color()),
             ("mineral", self.mineral.color()),
         ]
     }
@@ -51,7 +55,7 @@ impl SourcesConfig {
 /// Mineral 聚合源段(全源收藏投影,source = `mineral`)。
 ///
 /// 非网络源:没有 timeout / proxy 等网络旋钮(故不走 `#[source_section]`),
-/// 可配徽标色 + 后台补 meta 的节流参数。
+/// 可配徽标色 + 后台补 meta 的节流参数 + 声明式智能歌单。
 #[config_section]
 #[lua_extra_field(
     "curate_playlists?",
@@ -64,6 +68,20 @@ pub struct MineralSection {
 
     /// 后台补 meta 的节流参数(聚合面如何逐步补全 sync 导入的、缺 meta 的收藏)。
     backfill: BackfillSection,
+
+    /// 声明式智能歌单:每项一张按表达式现算的歌单,排在用户自建的智能歌单之前,只读(数组整体替换)。
+    smart_playlists: Vec<SmartPlaylistConfig>,
+}
+
+/// 一张声明式智能歌单。表达式语法见 `docs/configuration.md` 的 `sources.mineral` 段。
+#[config_section]
+#[lua_optional_by_serde]
+pub struct SmartPlaylistConfig {
+    /// 歌单名(同时作稳定键:同名的用户自建智能歌单被遮蔽)。
+    name: String,
+
+    /// 筛选表达式,如 `"loved unplayed:90d"`、`"top:50 within:30d"`。
+    query: String,
 }
 
 /// 聚合收藏后台补 meta 的节流参数。
@@ -82,6 +100,25 @@ pub struct BackfillSection {
     max_concurrent: usize,
 }
 
+/// 本地曲库源段(source = `local`)。
+///
+/// 扫描根下的音频文件按标签入库;根的直接子目录与 `.m3u` / `.m3u8` 文件投影成歌单。
+/// Lua 里 `local` 是关键字,表键须写成 `["local"]`。
+#[config_section]
+#[lua_extra_field(
+    "curate_playlists?",
+    "mineral.CuratePlaylistsFn",
+    "该源歌单(= 文件夹 / m3u)列表的呈现策展(过滤/改名/重排)"
+)]
+pub struct LocalSection {
+    /// 来源徽标色:token 名(随主题联动)或 `"#rrggbb"`(固定色)。
+    color: ColorRef,
+
+    /// 扫描根目录列表:绝对路径,`~/` 开头按家目录展开。空表 = 不启用本地源。
+    #[serde(deserialize_with = "super::de::string_list")]
+    roots: Vec<String>,
+}
+
 /// 哔哩哔哩源段。
 ///
 /// B站取流 URL(baseUrl)与 API 请求都要带 `Referer`(见 header 通道)。
@@ -2,11 +2,15 @@
 
 use std::time::Duration;
 
+use crate::state::TrackRef;
+
 /// 系统媒体控件(MPRIS / 键盘媒体键 / 桌面播放小组件)发来的控制命令。
 ///
 /// 由后端把各平台的原生事件归一到本枚举,交给宿主(播放器)处理。只覆盖播放器
-/// 能直接响应的子集;音量回写、Raise/Quit/OpenUri 等暂不纳入。
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// 能直接响应的子集;音量回写、Raise/Quit 等暂不纳入。
+///
+/// 队列 / 歌单类命令(`GoTo` 起)目前只有 Linux 后端(MPRIS TrackList / Playlists)会发。
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MediaCommand {
     /// 从暂停恢复播放。
     Play,
@@ -40,6 +44,35 @@ pub enum MediaCommand {
 
     /// 设置循环模式(MPRIS `LoopStatus` 属性写入)。
     SetLoop(LoopMode),
+
+    /// 设置播放倍速(MPRIS `Rate` 属性写入),值为百分比(100 = 原速),已钳进
+    /// [`crate::MediaConfig`] 给的倍速范围。
+    SetRate(u16),
+
+    /// 跳到队列中的某首(MPRIS TrackList `GoTo`)。
+    GoTo(TrackRef),
+
+    /// 从队列移除某首(MPRIS TrackList `RemoveTrack`)。
+    RemoveTrack(TrackRef),
+
+    /// 按 URI 加一首进队列(MPRIS TrackList `AddTrack`)。
+    AddTrack {
+        /// 外部给的歌曲引用:分享链接 / `file://` / 限定 id,原样交宿主解析。
+        uri: String,
+
+        /// 插在哪首之后;`None` = 调用方给的是 `NoTrack`(插到最前)。
+        after: Option<TrackRef>,
+
+        /// 加入后是否立即播放。
+        play: bool,
+    },
+
+    /// 打开并立即播放一个 URI(MPRIS `OpenUri`),解析同 [`Self::AddTrack`] 的 `uri`。
+    OpenUri(String),
+
+    /// 把某张歌单整张替换进队列并播放(MPRIS Playlists `ActivatePlaylist`)。
+    /// 值为 [`crate::PlaylistEntry`] 上报时的 id。
+    ActivatePlaylist(String),
 }
 
 /// 循环模式,对应 MPRIS `LoopStatus` 的三态;平台无关。
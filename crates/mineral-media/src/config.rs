@@ -6,7 +6,7 @@ use typed_builder::TypedBuilder;
 ///
 /// 字段私有 + builder 构造,遵循「不暴露可被字面量直接构造的配置 struct」。
 ///
-/// 字段目前仅 Linux(MPRIS)后端消费(总线名 / identity);非 Linux 后端不需要它们,
+/// 字段目前仅 Linux(MPRIS)后端消费(总线名 / identity / 倍速范围);非 Linux 后端不需要它们,
 /// 故对非 Linux 平台放开 `dead_code`。
 #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
 #[derive(Clone, Debug, TypedBuilder)]
@@ -20,4 +20,12 @@ pub struct MediaConfig {
     /// 用户可见的播放器名(系统媒体控件里显示)。
     #[builder(setter(into))]
     pub(crate) display_name: String,
+
+    /// 最低播放倍速(MPRIS `MinimumRate`);缺省 1 即不支持变速。
+    #[builder(default = 1.0)]
+    pub(crate) min_rate: f64,
+
+    /// 最高播放倍速(MPRIS `MaximumRate`);缺省 1。
+    #[builder(default = 1.0)]
+    pub(crate) max_rate: f64,
 }
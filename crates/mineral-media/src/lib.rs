@@ -1,8 +1,8 @@
 //! 系统媒体服务集成的平台无关封装。
 //!
 //! 把「系统媒体控件 ↔ 播放器」的对接收敛成一组平台无关类型:上报曲目元数据
-//! ([`NowPlaying`])与播放状态([`PlaybackState`]),接收来自系统的控制命令
-//! ([`MediaCommand`])。
+//! ([`NowPlaying`])与播放状态([`PlaybackState`]),上报队列与歌单库供系统控件浏览、
+//! 跳转([`TrackRef`] / [`PlaylistEntry`]),接收来自系统的控制命令([`MediaCommand`])。
 //!
 //! 后端按平台选择:
 //! - **Linux**:MPRIS(`org.mpris.MediaPlayer2.*`)。
@@ -17,7 +17,7 @@ mod state;
 pub use command::{LoopMode, MediaCommand};
 pub use config::MediaConfig;
 pub use os::MediaService;
-pub use state::{NowPlaying, PlaybackState};
+pub use state::{NowPlaying, PlaybackState, PlaylistEntry, TrackRef};
 
 /// macOS 专属:主线程 NSApplication 句柄与 run loop 驱动入口。
 ///
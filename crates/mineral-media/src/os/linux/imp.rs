@@ -0,0 +1,586 @@
+//! MPRIS 四个接口(根 / Player / TrackList / Playlists)的实现体。
+//!
+//! 属性读取直接回 [`ImpState`] 里的最新值(由专属线程的更新循环写入);方法调用
+//! 归一成 [`MediaCommand`] 交 `on_command`,真实状态变化随后经更新回流,这里不自行改写。
+//! 整个实现体只活在专属线程的 `LocalSet` 上,故状态用 `RefCell` 而非锁。
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use mpris_server::zbus::{Result, fdo};
+use mpris_server::{
+    LocalPlayerInterface, LocalPlaylistsInterface, LocalRootInterface, LocalTrackListInterface,
+    LoopStatus, Metadata, PlaybackRate, PlaybackStatus, Playlist, PlaylistId, PlaylistOrdering,
+    Time, TrackId, Volume,
+};
+
+use super::micros_to_duration;
+use super::path::{parse_playlist_path, parse_track_id, playlist_path, track_id};
+use crate::command::{LoopMode, MediaCommand};
+use crate::state::{PlaylistEntry, TrackRef};
+
+/// 命令回调(在专属线程触发)。
+pub(super) type OnCommand = Arc<dyn Fn(MediaCommand) + Send + Sync>;
+
+/// 接口属性的当前值,由更新循环写入、接口读取。
+pub(super) struct ImpState {
+    /// 当前曲 metadata(已含 `mpris:trackid`)。
+    pub(super) metadata: Metadata,
+
+    /// 当前曲在队列中的位置;不在队列里为 `None`。
+    pub(super) current: Option<TrackRef>,
+
+    /// 播放状态。
+    pub(super) status: PlaybackStatus,
+
+    /// 循环模式。
+    pub(super) loop_status: LoopStatus,
+
+    /// 随机播放开关。
+    pub(super) shuffle: bool,
+
+    /// 播放倍速(1 = 原速)。
+    pub(super) rate: PlaybackRate,
+
+    /// 最近一次上报的进度(`Position` 属性不发变更信号,客户端按需读)。
+    pub(super) position: Time,
+
+    /// 当前队列版本号,与 `tracks` 配套。
+    pub(super) version: u64,
+
+    /// 队列各条目的 metadata,下标即队列下标。
+    pub(super) tracks: Vec<Metadata>,
+
+    /// 歌单库,上报序即 `UserDefined` 序。
+    pub(super) playlists: Vec<PlaylistEntry>,
+
+    /// 当前队列来自哪张歌单(歌单 id)。
+    pub(super) active: Option<String>,
+}
+
+impl ImpState {
+    /// 当前曲的 track id;不在队列里或与队列版本对不上时为 `NoTrack`。
+    pub(super) fn current_track_id(&self) -> TrackId {
+        match self.current {
+            Some(track) if track.version() == self.version => track_id(track),
+            _ => TrackId::NO_TRACK,
+        }
+    }
+
+    /// 队列各条目的 track id。
+    pub(super) fn track_ids(&self) -> Vec<TrackId> {
+        (0..self.tracks.len())
+            .map(|index| track_id(TrackRef::new(self.version, index)))
+            .collect()
+    }
+
+    /// 当前活动歌单;id 不在歌单库里(库尚未上报等)视同无。
+    pub(super) fn active_playlist(&self) -> Option<Playlist> {
+        let active = self.active.as_deref()?;
+        self.playlists
+            .iter()
+            .find(|entry| entry.id == active)
+            .map(to_playlist)
+    }
+}
+
+/// MPRIS 实现体:显示名、倍速范围、命令回调与属性状态。
+pub(super) struct MprisImp {
+    /// `Identity` 属性(显示名)。
+    identity: String,
+
+    /// `MinimumRate` 属性。
+    min_rate: PlaybackRate,
+
+    /// `MaximumRate` 属性。
+    max_rate: PlaybackRate,
+
+    /// 命令回调。
+    on_command: OnCommand,
+
+    /// 属性当前值。
+    pub(super) state: RefCell<ImpState>,
+}
+
+impl MprisImp {
+    /// 构造实现体;属性取「无曲目、已停止、原速」的初值,等首轮更新覆盖。
+    ///
+    /// # Params:
+    ///   - `identity`: 显示名
+    ///   - `rates`: 倍速范围(最低, 最高);最高低于最低时按最低算
+    ///   - `on_command`: 命令回调
+    pub(super) fn new(
+        identity: String,
+        (min_rate, max_rate): (PlaybackRate, PlaybackRate),
+        on_command: OnCommand,
+    ) -> Self {
+        Self {
+            identity,
+            min_rate,
+            max_rate: max_rate.max(min_rate),
+            on_command,
+            state: RefCell::new(ImpState {
+                metadata: Metadata::new(),
+                current: None,
+                status: PlaybackStatus::Stopped,
+                loop_status: LoopStatus::None,
+                shuffle: false,
+                rate: 1.0,
+                position: Time::ZERO,
+                version: 0,
+                tracks: Vec::new(),
+                playlists: Vec::new(),
+                active: None,
+            }),
+        }
+    }
+
+    /// 把一条命令交给宿主。
+    fn send(&self, cmd: MediaCommand) {
+        (self.on_command)(cmd);
+    }
+}
+
+/// 歌单条目 → MPRIS `Playlist`(无封面给空串,规范约定)。
+pub(super) fn to_playlist(entry: &PlaylistEntry) -> Playlist {
+    Playlist {
+        id: playlist_path(&entry.id),
+        name: entry.name.clone(),
+        icon: entry.icon.clone().unwrap_or_default(),
+    }
+}
+
+/// `GetPlaylists` 的排序与分页。
+///
+/// 只有上报序(`UserDefined`)与按名排序两种真实序;其余序(创建 / 修改 / 播放时间)
+/// 拿不到数据,按上报序给。
+///
+/// # Params:
+///   - `entries`: 歌单库(上报序)
+///   - `index`: 起始偏移
+///   - `max_count`: 最多返回几张
+///   - `order`: 排序方式
+///   - `reverse`: 是否倒序
+fn page_playlists(
+    entries: &[PlaylistEntry],
+    index: u32,
+    max_count: u32,
+    order: PlaylistOrdering,
+    reverse: bool,
+) -> Vec<&PlaylistEntry> {
+    let mut sorted = entries.iter().collect::<Vec<_>>();
+    if order == PlaylistOrdering::Alphabetical {
+        sorted.sort_by_cached_key(|entry| entry.name.to_lowercase());
+    }
+    if reverse {
+        sorted.reverse();
+    }
+    let skip = usize::try_from(index).unwrap_or(usize::MAX);
+    let take = usize::try_from(max_count).unwrap_or(usize::MAX);
+    sorted.into_iter().skip(skip).take(take).collect()
+}
+
+impl LocalRootInterface for MprisImp {
+    async fn raise(&self) -> fdo::Result<()> {
+        Ok(())
+    }
+
+    async fn quit(&self) -> fdo::Result<()> {
+        Ok(())
+    }
+
+    async fn can_quit(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn fullscreen(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn set_fullscreen(&self, _fullscreen: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn can_set_fullscreen(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn can_raise(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn has_track_list(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn identity(&self) -> fdo::Result<String> {
+        Ok(self.identity.clone())
+    }
+
+    async fn desktop_entry(&self) -> fdo::Result<String> {
+        Ok(String::new())
+    }
+
+    async fn supported_uri_schemes(&self) -> fdo::Result<Vec<String>> {
+        // 分享链接(http/https)与本地文件;限定 id 不是 URI,控件不会按 scheme 投递。
+        Ok(["file", "http", "https"].map(str::to_owned).to_vec())
+    }
+
+    async fn supported_mime_types(&self) -> fdo::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+impl LocalPlayerInterface for MprisImp {
+    async fn next(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Next);
+        Ok(())
+    }
+
+    async fn previous(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Previous);
+        Ok(())
+    }
+
+    async fn pause(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Pause);
+        Ok(())
+    }
+
+    async fn play_pause(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Toggle);
+        Ok(())
+    }
+
+    async fn stop(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Stop);
+        Ok(())
+    }
+
+    async fn play(&self) -> fdo::Result<()> {
+        self.send(MediaCommand::Play);
+        Ok(())
+    }
+
+    async fn seek(&self, offset: Time) -> fdo::Result<()> {
+        let micros = offset.as_micros();
+        if micros >= 0 {
+            self.send(MediaCommand::SeekForward(micros_to_duration(micros)));
+        } else {
+            self.send(MediaCommand::SeekBackward(micros_to_duration(
+                micros.saturating_neg(),
+            )));
+        }
+        Ok(())
+    }
+
+    async fn set_position(&self, track_id: TrackId, position: Time) -> fdo::Result<()> {
+        // 规范:track id 不是当前曲(控件拿着旧曲的进度条)时忽略。
+        let current = self.state.borrow().current;
+        if current.is_some() && parse_track_id(&track_id) != current {
+            return Ok(());
+        }
+        self.send(MediaCommand::SetPosition(micros_to_duration(
+            position.as_micros(),
+        )));
+        Ok(())
+    }
+
+    async fn open_uri(&self, uri: String) -> fdo::Result<()> {
+        self.send(MediaCommand::OpenUri(uri));
+        Ok(())
+    }
+
+    async fn playback_status(&self) -> fdo::Result<PlaybackStatus> {
+        Ok(self.state.borrow().status)
+    }
+
+    async fn loop_status(&self) -> fdo::Result<LoopStatus> {
+        Ok(self.state.borrow().loop_status)
+    }
+
+    async fn set_loop_status(&self, loop_status: LoopStatus) -> Result<()> {
+        self.send(MediaCommand::SetLoop(loop_status_to_mode(loop_status)));
+        Ok(())
+    }
+
+    async fn rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(self.state.borrow().rate)
+    }
+
+    async fn set_rate(&self, rate: PlaybackRate) -> Result<()> {
+        // 规范:写 0 视同 Pause;其余钳进 MinimumRate..=MaximumRate。
+        if rate == 0.0 {
+            self.send(MediaCommand::Pause);
+        } else if let Some(pct) = rate_to_pct(rate, self.min_rate, self.max_rate) {
+            self.send(MediaCommand::SetRate(pct));
+        }
+        Ok(())
+    }
+
+    async fn shuffle(&self) -> fdo::Result<bool> {
+        Ok(self.state.borrow().shuffle)
+    }
+
+    async fn set_shuffle(&self, shuffle: bool) -> Result<()> {
+        self.send(MediaCommand::SetShuffle(shuffle));
+        Ok(())
+    }
+
+    async fn metadata(&self) -> fdo::Result<Metadata> {
+        Ok(self.state.borrow().metadata.clone())
+    }
+
+    async fn volume(&self) -> fdo::Result<Volume> {
+        Ok(1.0)
+    }
+
+    async fn set_volume(&self, _volume: Volume) -> Result<()> {
+        Ok(())
+    }
+
+    async fn position(&self) -> fdo::Result<Time> {
+        Ok(self.state.borrow().position)
+    }
+
+    async fn minimum_rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(self.min_rate)
+    }
+
+    async fn maximum_rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(self.max_rate)
+    }
+
+    async fn can_go_next(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_go_previous(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_play(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_pause(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_seek(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_control(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+}
+
+impl LocalTrackListInterface for MprisImp {
+    async fn get_tracks_metadata(&self, track_ids: Vec<TrackId>) -> fdo::Result<Vec<Metadata>> {
+        let state = self.state.borrow();
+        // 过期 / 非本播放器的 id 直接略过(规范允许返回少于请求数)。
+        Ok(track_ids
+            .iter()
+            .filter_map(parse_track_id)
+            .filter(|track| track.version() == state.version)
+            .filter_map(|track| state.tracks.get(track.index()).cloned())
+            .collect())
+    }
+
+    async fn add_track(
+        &self,
+        uri: String,
+        after_track: TrackId,
+        set_as_current: bool,
+    ) -> fdo::Result<()> {
+        self.send(MediaCommand::AddTrack {
+            uri,
+            after: parse_track_id(&after_track),
+            play: set_as_current,
+        });
+        Ok(())
+    }
+
+    async fn remove_track(&self, track_id: TrackId) -> fdo::Result<()> {
+        if let Some(track) = parse_track_id(&track_id) {
+            self.send(MediaCommand::RemoveTrack(track));
+        }
+        Ok(())
+    }
+
+    async fn go_to(&self, track_id: TrackId) -> fdo::Result<()> {
+        if let Some(track) = parse_track_id(&track_id) {
+            self.send(MediaCommand::GoTo(track));
+        }
+        Ok(())
+    }
+
+    async fn tracks(&self) -> fdo::Result<Vec<TrackId>> {
+        Ok(self.state.borrow().track_ids())
+    }
+
+    async fn can_edit_tracks(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+}
+
+impl LocalPlaylistsInterface for MprisImp {
+    async fn activate_playlist(&self, playlist_id: PlaylistId) -> fdo::Result<()> {
+        if let Some(id) = parse_playlist_path(playlist_id.as_str()) {
+            self.send(MediaCommand::ActivatePlaylist(id));
+        }
+        Ok(())
+    }
+
+    async fn get_playlists(
+        &self,
+        index: u32,
+        max_count: u32,
+        order: PlaylistOrdering,
+        reverse_order: bool,
+    ) -> fdo::Result<Vec<Playlist>> {
+        let state = self.state.borrow();
+        Ok(
+            page_playlists(&state.playlists, index, max_count, order, reverse_order)
+                .into_iter()
+                .map(to_playlist)
+                .collect(),
+        )
+    }
+
+    async fn playlist_count(&self) -> fdo::Result<u32> {
+        Ok(u32::try_from(self.state.borrow().playlists.len()).unwrap_or(u32::MAX))
+    }
+
+    async fn orderings(&self) -> fdo::Result<Vec<PlaylistOrdering>> {
+        Ok(vec![
+            PlaylistOrdering::UserDefined,
+            PlaylistOrdering::Alphabetical,
+        ])
+    }
+
+    async fn active_playlist(&self) -> fdo::Result<Option<Playlist>> {
+        Ok(self.state.borrow().active_playlist())
+    }
+}
+
+/// MPRIS 倍速 → 钳进 `min..=max` 的百分比;非有限值(控件传坏值)给 `None`。
+///
+/// # Params:
+///   - `rate`: 控件写入的倍速
+///   - `min`: 最低倍速
+///   - `max`: 最高倍速(不低于 `min`)
+fn rate_to_pct(rate: PlaybackRate, min: PlaybackRate, max: PlaybackRate) -> Option<u16> {
+    if !rate.is_finite() {
+        return None;
+    }
+    #[allow(clippy::as_conversions)] // reason: 已钳进倍速范围(个位数倍),×100 后落在 u16 内
+    let pct = (rate.max(min).min(max) * 100.0).round() as u16;
+    Some(pct)
+}
+
+/// MPRIS `LoopStatus` → 平台无关 [`LoopMode`]。
+fn loop_status_to_mode(status: LoopStatus) -> LoopMode {
+    match status {
+        LoopStatus::None => LoopMode::None,
+        LoopStatus::Track => LoopMode::Track,
+        LoopStatus::Playlist => LoopMode::Playlist,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use color_eyre::eyre::eyre;
+    use mpris_server::{LocalPlayerInterface, PlaylistOrdering};
+
+    use super::{MprisImp, page_playlists, rate_to_pct};
+    use crate::command::MediaCommand;
+    use crate::state::PlaylistEntry;
+
+    fn entries() -> Vec<PlaylistEntry> {
+        ["b 歌单", "A 歌单", "c 歌单"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                PlaylistEntry::builder()
+                    .id(format!("netease:{i}"))
+                    .name(*name)
+                    .build()
+            })
+            .collect()
+    }
+
+    fn names(page: &[&PlaylistEntry]) -> Vec<String> {
+        page.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    #[test]
+    fn user_defined_keeps_reported_order() {
+        let all = entries();
+        let page = page_playlists(&all, 0, 10, PlaylistOrdering::UserDefined, false);
+        assert_eq!(names(&page), ["b 歌单", "A 歌单", "c 歌单"]);
+    }
+
+    #[test]
+    fn alphabetical_ignores_case_and_reverses() {
+        let all = entries();
+        let page = page_playlists(&all, 0, 10, PlaylistOrdering::Alphabetical, true);
+        assert_eq!(names(&page), ["c 歌单", "b 歌单", "A 歌单"]);
+    }
+
+    #[test]
+    fn paging_clamps_to_range() {
+        let all = entries();
+        let page = page_playlists(&all, 1, 1, PlaylistOrdering::UserDefined, false);
+        assert_eq!(names(&page), ["A 歌单"]);
+        assert!(page_playlists(&all, 5, 10, PlaylistOrdering::UserDefined, false).is_empty());
+    }
+
+    #[test]
+    fn rate_clamps_to_range() {
+        assert_eq!(rate_to_pct(1.25, 0.5, 3.0), Some(125));
+        assert_eq!(rate_to_pct(0.1, 0.5, 3.0), Some(50));
+        assert_eq!(rate_to_pct(8.0, 0.5, 3.0), Some(300));
+        assert_eq!(rate_to_pct(f64::NAN, 0.5, 3.0), None);
+        assert_eq!(rate_to_pct(f64::INFINITY, 0.5, 3.0), None);
+    }
+
+    /// `Rate` 回报更新循环写入的倍速;`SetRate` 钳进范围后发命令,写 0 视同暂停。
+    #[tokio::test]
+    async fn rate_reports_state_and_set_rate_sends_clamped() -> color_eyre::Result<()> {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&sent);
+        let imp = MprisImp::new(
+            "mineral".to_owned(),
+            (0.5, 3.0),
+            Arc::new(move |cmd| {
+                if let Ok(mut cmds) = sink.lock() {
+                    cmds.push(cmd);
+                }
+            }),
+        );
+        assert!((imp.minimum_rate().await? - 0.5).abs() < f64::EPSILON);
+        assert!((imp.maximum_rate().await? - 3.0).abs() < f64::EPSILON);
+        assert!((imp.rate().await? - 1.0).abs() < f64::EPSILON);
+        imp.state.borrow_mut().rate = 1.5;
+        assert!((imp.rate().await? - 1.5).abs() < f64::EPSILON);
+
+        imp.set_rate(2.0).await?;
+        imp.set_rate(10.0).await?;
+        imp.set_rate(0.0).await?;
+        imp.set_rate(f64::NAN).await?;
+        let cmds = sent.lock().map_err(|e| eyre!("poisoned: {e}"))?.clone();
+        assert_eq!(
+            cmds,
+            [
+                MediaCommand::SetRate(200),
+                MediaCommand::SetRate(300),
+                MediaCommand::Pause,
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! [`NowPlaying`] → MPRIS metadata:标准 xesam / mpris 字段 + 歌词扩展 key。
+
+use mineral_model::{LyricLine, to_lrc_string};
+use mpris_server::Metadata;
+use serde::Serialize;
+
+use super::duration_to_time;
+use super::path::track_id;
+use crate::state::{NowPlaying, TrackRef};
+
+/// [`NowPlaying`] → mpris-server `Metadata`。
+///
+/// 结构化歌词在这里(写 MPRIS 的最边界)才序列化:行级原文 / 翻译 / 罗马音走标准 LRC,
+/// 逐字原文走JSON([`serialize_words`])。某路为空就不 set 对应 key
+/// (key 不存在即代表该轨无数据,显示端按 逐字 → 行级 → 无 降级)。
+///
+/// # Params:
+///   - `now_playing`: 曲目信息
+///   - `track`: 写进 `mpris:trackid` 的队列位置;`None` 不写
+pub(super) fn build_metadata(now_playing: &NowPlaying, track: Option<TrackRef>) -> Metadata {
+    let mut builder = Metadata::builder();
+    if let Some(track) = track {
+        builder = builder.trackid(track_id(track));
+    }
+    if let Some(title) = &now_playing.title {
+        builder = builder.title(title.clone());
+    }
+    if let Some(artist) = &now_playing.artist {
+        builder = builder.artist([artist.clone()]);
+    }
+    if let Some(album) = &now_playing.album {
+        builder = builder.album(album.clone());
+    }
+    if let Some(cover) = &now_playing.cover_url {
+        builder = builder.art_url(cover.clone());
+    }
+    if let Some(duration) = now_playing.duration {
+        builder = builder.length(duration_to_time(duration));
+    }
+    let mut metadata = builder.build();
+    // 原文带时间戳行 → 标准 LRC(xesam:asText);其中逐字行 → JSON(mineral:words)。
+    let astext = to_lrc_string(&now_playing.original);
+    if !astext.is_empty() {
+        let _ = metadata.set("xesam:asText", Some(astext));
+    }
+    if let Some(json) = serialize_words(&now_playing.original) {
+        let _ = metadata.set("mineral:words", Some(json));
+    }
+    let translation = to_lrc_string(&now_playing.translation);
+    if !translation.is_empty() {
+        let _ = metadata.set("mineral:translation", Some(translation));
+    }
+    let romanization = to_lrc_string(&now_playing.romanization);
+    if !romanization.is_empty() {
+        let _ = metadata.set("mineral:romanization", Some(romanization));
+    }
+    metadata
+}
+
+/// `mineral:words` JSON 的一行(字段名即 quickshell 契约,勿改 / 勿缩写)。
+#[derive(Serialize)]
+struct WordsLineDto {
+    /// 行起始绝对毫秒。
+    start: u64,
+
+    /// 该行的字单元,按时间升序。
+    words: Vec<WordCellDto>,
+}
+
+/// `mineral:words` JSON 的一个字单元(字段名即 quickshell 契约,勿改 / 勿缩写)。
+#[derive(Serialize)]
+struct WordCellDto {
+    /// 字起始绝对毫秒。
+    start: u64,
+
+    /// 字持续毫秒(wipe 高亮要用,行末字 / 间隙无法靠下一字推算,必须显式给)。
+    duration: u64,
+
+    /// 字面文本,原样保留前后空格(显示端直接拼成行)。
+    text: String,
+}
+
+/// 原文里的逐字行 → `mineral:words` 的 JSON 字符串;无逐字行返回 `None`(不发该 key)。
+/// 纯文本行(行级 / credits / 无时间戳)不进逐字轨。
+fn serialize_words(lines: &[LyricLine]) -> Option<String> {
+    let dto = lines
+        .iter()
+        .filter(|l| !l.kind.words().is_empty())
+        .map(|line| WordsLineDto {
+            start: line.time_ms.unwrap_or(0),
+            words: line
+                .kind
+                .words()
+                .iter()
+                .map(|w| WordCellDto {
+                    start: w.start_ms,
+                    duration: w.dur_ms,
+                    text: w.text.clone(),
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    if dto.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&dto).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serialize_words;
+    use color_eyre::eyre::eyre;
+    use mineral_model::{LineKind, LyricLine, Word};
+    use serde_json::Value;
+
+    fn word(start_ms: u64, dur_ms: u64, text: &str) -> Word {
+        Word {
+            start_ms,
+            dur_ms,
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn non_word_lines_serialize_to_none() {
+        // 空 / 仅纯文本行 → None,build_metadata 据此不发 mineral:words key。
+        assert_eq!(serialize_words(&[]), None);
+        assert_eq!(serialize_words(&[LyricLine::timed(0, "纯文本")]), None);
+    }
+
+    #[test]
+    fn words_serialize_to_quickshell_schema() -> color_eyre::Result<()> {
+        // 字段名 / 层级 / 整数毫秒 / 保留空格 —— 严格对齐 quickshell 契约。
+        let lines = vec![LyricLine {
+            time_ms: Some(11_350),
+            kind: LineKind::Words {
+                dur_ms: 1020,
+                words: vec![word(11_350, 300, "How "), word(11_650, 720, "will")],
+            },
+            translation: None,
+            romanization: None,
+        }];
+        let json = serialize_words(&lines).ok_or_else(|| eyre!("expected Some json"))?;
+        let v: Value = serde_json::from_str(&json)?;
+
+        // 顶层数组,行有 start + words,无行级 duration 字段。
+        let line = v.get(0).ok_or_else(|| eyre!("missing line 0"))?;
+        assert_eq!(line.get("start").and_then(Value::as_u64), Some(11_350));
+        assert!(line.get("duration").is_none());
+        // 字单元字段为 start/duration/text,整数毫秒。
+        let w0 = line
+            .get("words")
+            .and_then(|w| w.get(0))
+            .ok_or_else(|| eyre!("missing word 0"))?;
+        assert_eq!(w0.get("start").and_then(Value::as_u64), Some(11_350));
+        assert_eq!(w0.get("duration").and_then(Value::as_u64), Some(300));
+        // text 原样保留尾随空格(显示端直接拼)。
+        assert_eq!(w0.get("text").and_then(Value::as_str), Some("How "));
+        Ok(())
+    }
+}
@@ -0,0 +1,405 @@
+//! Linux MediaService:基于 mpris-server(zbus 官方)的 MPRIS 实现。
+//!
+//! mpris-server 是 async,且其 server task 为 `!Send`(`LocalServerRunTask`),
+//! 不能直接丢进 daemon 的多线程 tokio runtime。这里起一个**专属线程**,在它的
+//! current-thread runtime + `LocalSet` 里起 server、`spawn_local` 它的 run
+//! task、并消费状态更新。对外仍是同步 API:状态更新经 channel 投递,命令经
+//! `on_command` 回调(在专属线程触发)回传。
+//!
+//! 选 mpris-server 而非 souvlaki 的原因:它能设任意 metadata 字段,我们要往
+//! `xesam:asText` 塞 LRC 歌词,souvlaki 的固定 5 字段做不到。除 Player 外还导出
+//! TrackList(队列浏览 / 跳转 / 增删)与 Playlists(歌单库)接口,故用底层的
+//! `LocalServer` 自己实现四个接口([`imp`]),而非只覆盖 Player 的高层 `Player`。
+
+mod imp;
+mod metadata;
+mod path;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::eyre;
+use mpris_server::{
+    LocalServer, LoopStatus, PlaybackStatus, PlaylistsProperty, PlaylistsSignal, Property, Signal,
+    Time, TrackListSignal,
+};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use self::imp::{MprisImp, OnCommand, to_playlist};
+use self::metadata::build_metadata;
+use crate::command::{LoopMode, MediaCommand};
+use crate::config::MediaConfig;
+use crate::state::{NowPlaying, PlaybackState, PlaylistEntry, TrackRef};
+
+/// 跑在专属线程上的 MPRIS server。
+type Server = LocalServer<MprisImp>;
+
+/// 主线程 → MPRIS 专属线程的状态更新消息。
+enum Update {
+    /// 重设当前曲目元数据(含 `xesam:asText` 歌词)。
+    Metadata(NowPlaying),
+
+    /// 重设播放状态与进度。
+    Playback {
+        /// 播放 / 暂停 / 停止。
+        status: PlaybackState,
+
+        /// 当前进度;`None` 表示不更新位置。
+        position: Option<Duration>,
+    },
+
+    /// 发生了非线性位置跳变(seek),需 emit MPRIS `Seeked` 信号让外推型客户端重置基准。
+    Seeked(Duration),
+
+    /// 更新随机播放开关(写 MPRIS `Shuffle` 属性,自动发 `PropertiesChanged`)。
+    Shuffle(bool),
+
+    /// 更新循环模式(写 MPRIS `LoopStatus` 属性,自动发 `PropertiesChanged`)。
+    Loop(LoopMode),
+
+    /// 更新播放倍速(写 MPRIS `Rate` 属性,自动发 `PropertiesChanged`)。
+    Rate(f64),
+
+    /// 整体替换队列(emit TrackList `TrackListReplaced`)。
+    Tracklist {
+        /// 队列版本号,编进各条目的 track id。
+        version: u64,
+
+        /// 队列各条目,下标即队列下标。
+        tracks: Vec<NowPlaying>,
+    },
+
+    /// 替换歌单库与当前活动歌单(Playlists 接口)。
+    Playlists {
+        /// 歌单库(上报序)。
+        entries: Vec<PlaylistEntry>,
+
+        /// 当前队列来自的歌单 id。
+        active: Option<String>,
+    },
+}
+
+/// 系统媒体服务句柄(Linux = MPRIS via mpris-server)。
+pub struct MediaService {
+    /// 向专属线程投递状态更新。
+    tx: UnboundedSender<Update>,
+}
+
+impl MediaService {
+    /// 起 MPRIS 专属线程,注册控件 + attach 命令回调,等到注册完成才返回。
+    ///
+    /// # Params:
+    ///   - `config`: D-Bus 名后缀(同时用作 identity)、显示名与倍速范围。
+    ///   - `on_command`: 收到系统媒体控件命令时回调,在专属线程触发。
+    ///
+    /// # Return:
+    ///   注册失败(无 D-Bus session 等)返回 `Err`。
+    pub fn spawn(
+        config: &MediaConfig,
+        on_command: Arc<dyn Fn(MediaCommand) + Send + Sync>,
+    ) -> color_eyre::Result<Self> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Update>();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel::<color_eyre::Result<()>>();
+        let config = config.clone();
+        std::thread::Builder::new()
+            .name("mineral-mpris".to_owned())
+            .spawn(move || run_thread(&config, &on_command, rx, &ready_tx))
+            .map_err(|e| eyre!("spawn mpris thread: {e}"))?;
+        match ready_rx.recv() {
+            Ok(result) => result.map(|()| Self { tx }),
+            Err(e) => Err(eyre!("mpris thread exited before ready: {e}")),
+        }
+    }
+
+    /// 上报当前曲目元数据(含歌词)。
+    pub fn set_now_playing(&self, now_playing: &NowPlaying) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Metadata(now_playing.clone()))
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报播放状态与进度。
+    pub fn set_playback(
+        &self,
+        state: PlaybackState,
+        position: Option<Duration>,
+    ) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Playback {
+                status: state,
+                position,
+            })
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 通知发生了非线性位置跳变(seek),emit MPRIS `Seeked` 信号。
+    ///
+    /// 正常线性播放**不要**调用(外推型客户端自行外推);只在 seek / `SetPosition`
+    /// 等跳变时调,让客户端把外推基准重置到 `position`。
+    pub fn notify_seek(&self, position: Duration) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Seeked(position))
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报随机播放开关(回写 MPRIS `Shuffle` 属性)。
+    pub fn set_shuffle(&self, shuffle: bool) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Shuffle(shuffle))
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报循环模式(回写 MPRIS `LoopStatus` 属性)。
+    pub fn set_loop(&self, mode: LoopMode) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Loop(mode))
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报播放倍速(回写 MPRIS `Rate` 属性;1 = 原速)。
+    pub fn set_rate(&self, rate: f64) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Rate(rate))
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报整个队列(MPRIS TrackList)。
+    ///
+    /// 只在队列变了时调;各条目的 track id 由版本号 + 下标编出,当前曲的
+    /// [`NowPlaying`] 须带同一版本的 [`TrackRef`] 才能被认作列表里的那首,故宜先
+    /// [`Self::set_now_playing`] 再调本方法。
+    ///
+    /// # Params:
+    ///   - `version`: 队列版本号
+    ///   - `tracks`: 队列各条目(不必带歌词)
+    pub fn set_tracklist(&self, version: u64, tracks: &[NowPlaying]) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Tracklist {
+                version,
+                tracks: tracks.to_vec(),
+            })
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+
+    /// 上报歌单库与当前活动歌单(MPRIS Playlists)。
+    ///
+    /// # Params:
+    ///   - `playlists`: 歌单库,按希望的展示序
+    ///   - `active`: 当前队列来自的歌单 id;不来自歌单给 `None`
+    pub fn set_playlists(
+        &self,
+        playlists: &[PlaylistEntry],
+        active: Option<&str>,
+    ) -> color_eyre::Result<()> {
+        self.tx
+            .send(Update::Playlists {
+                entries: playlists.to_vec(),
+                active: active.map(str::to_owned),
+            })
+            .map_err(|e| eyre!("mpris thread gone: {e}"))
+    }
+}
+
+/// 专属线程主体:current-thread runtime + `LocalSet`,起 server 后消费更新。
+fn run_thread(
+    config: &MediaConfig,
+    on_command: &OnCommand,
+    mut rx: UnboundedReceiver<Update>,
+    ready_tx: &std::sync::mpsc::Sender<color_eyre::Result<()>>,
+) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            let _ = ready_tx.send(Err(eyre!("build mpris runtime: {e}")));
+            return;
+        }
+    };
+    let local = tokio::task::LocalSet::new();
+    local.block_on(&runtime, async move {
+        let imp = MprisImp::new(
+            config.display_name.clone(),
+            (config.min_rate, config.max_rate),
+            Arc::clone(on_command),
+        );
+        let server = match Server::new_with_all(&config.dbus_name, imp).await {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = ready_tx.send(Err(eyre!("build mpris server: {e}")));
+                return;
+            }
+        };
+        tokio::task::spawn_local(server.run());
+        let _ = ready_tx.send(Ok(()));
+        while let Some(update) = rx.recv().await {
+            apply_update(&server, update).await;
+        }
+    });
+}
+
+/// emit 失败只 warn,不影响播放。
+fn warn_emit(result: mpris_server::zbus::Result<()>, what: &str) {
+    if let Err(e) = result {
+        mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "mpris {what}");
+    }
+}
+
+/// 应用一条状态更新:改写实现体的属性值,值确有变化时 emit 对应信号。
+async fn apply_update(server: &Server, update: Update) {
+    let state = &server.imp().state;
+    match update {
+        Update::Metadata(now_playing) => {
+            let metadata = build_metadata(&now_playing, now_playing.track);
+            {
+                let mut st = state.borrow_mut();
+                st.metadata = metadata.clone();
+                st.current = now_playing.track;
+            }
+            let changed = server
+                .properties_changed([Property::Metadata(metadata)])
+                .await;
+            warn_emit(changed, "metadata changed");
+        }
+        Update::Playback { status, position } => {
+            let status = to_status(status);
+            let changed = {
+                let mut st = state.borrow_mut();
+                // 只改 Position 内部值,不发 PropertiesChanged(MPRIS 规范)。正常播放靠
+                // 客户端外推;非线性跳变由 Update::Seeked 补信号。
+                if let Some(p) = position {
+                    st.position = duration_to_time(p);
+                }
+                std::mem::replace(&mut st.status, status) != status
+            };
+            if changed {
+                let emitted = server
+                    .properties_changed([Property::PlaybackStatus(status)])
+                    .await;
+                warn_emit(emitted, "playback status changed");
+            }
+        }
+        Update::Seeked(position) => {
+            let position = duration_to_time(position);
+            state.borrow_mut().position = position;
+            warn_emit(server.emit(Signal::Seeked { position }).await, "seeked");
+        }
+        Update::Shuffle(shuffle) => {
+            let changed = std::mem::replace(&mut state.borrow_mut().shuffle, shuffle) != shuffle;
+            if changed {
+                let emitted = server
+                    .properties_changed([Property::Shuffle(shuffle)])
+                    .await;
+                warn_emit(emitted, "shuffle changed");
+            }
+        }
+        Update::Loop(mode) => {
+            let status = mode_to_loop_status(mode);
+            let changed = std::mem::replace(&mut state.borrow_mut().loop_status, status) != status;
+            if changed {
+                let emitted = server
+                    .properties_changed([Property::LoopStatus(status)])
+                    .await;
+                warn_emit(emitted, "loop status changed");
+            }
+        }
+        Update::Rate(rate) => {
+            let changed = std::mem::replace(&mut state.borrow_mut().rate, rate) != rate;
+            if changed {
+                let emitted = server.properties_changed([Property::Rate(rate)]).await;
+                warn_emit(emitted, "rate changed");
+            }
+        }
+        Update::Tracklist { version, tracks } => {
+            let (tracks, current_track) = {
+                let mut st = state.borrow_mut();
+                st.version = version;
+                st.tracks = tracks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, np)| build_metadata(np, Some(TrackRef::new(version, index))))
+                    .collect();
+                (st.track_ids(), st.current_track_id())
+            };
+            let signal = TrackListSignal::TrackListReplaced {
+                tracks,
+                current_track,
+            };
+            warn_emit(server.track_list_emit(signal).await, "track list replaced");
+        }
+        Update::Playlists { entries, active } => {
+            apply_playlists(server, entries, active).await;
+        }
+    }
+}
+
+/// 替换歌单库:数量变了发 `PlaylistCount`,改名 / 换封面的发 `PlaylistChanged`,
+/// 活动歌单变了发 `ActivePlaylist`。
+async fn apply_playlists(server: &Server, entries: Vec<PlaylistEntry>, active: Option<String>) {
+    let (count, renamed, active) = {
+        let mut st = server.imp().state.borrow_mut();
+        let count = (st.playlists.len() != entries.len())
+            .then(|| u32::try_from(entries.len()).unwrap_or(u32::MAX));
+        let renamed = entries
+            .iter()
+            .filter(|entry| {
+                st.playlists
+                    .iter()
+                    .any(|old| old.id == entry.id && old != *entry)
+            })
+            .map(to_playlist)
+            .collect::<Vec<_>>();
+        let before = st.active_playlist();
+        st.playlists = entries;
+        st.active = active;
+        let after = st.active_playlist();
+        (count, renamed, (before != after).then_some(after))
+    };
+    for playlist in renamed {
+        let emitted = server
+            .playlists_emit(PlaylistsSignal::PlaylistChanged { playlist })
+            .await;
+        warn_emit(emitted, "playlist changed");
+    }
+    let mut changed = Vec::new();
+    if let Some(count) = count {
+        changed.push(PlaylistsProperty::PlaylistCount(count));
+    }
+    if let Some(active) = active {
+        changed.push(PlaylistsProperty::ActivePlaylist(active));
+    }
+    if !changed.is_empty() {
+        let emitted = server.playlists_properties_changed(changed).await;
+        warn_emit(emitted, "playlists changed");
+    }
+}
+
+/// 平台无关 [`LoopMode`] → MPRIS `LoopStatus`。
+fn mode_to_loop_status(mode: LoopMode) -> LoopStatus {
+    match mode {
+        LoopMode::None => LoopStatus::None,
+        LoopMode::Track => LoopStatus::Track,
+        LoopMode::Playlist => LoopStatus::Playlist,
+    }
+}
+
+/// [`PlaybackState`] → mpris-server `PlaybackStatus`。
+fn to_status(state: PlaybackState) -> PlaybackStatus {
+    match state {
+        PlaybackState::Playing => PlaybackStatus::Playing,
+        PlaybackState::Paused => PlaybackStatus::Paused,
+        PlaybackState::Stopped => PlaybackStatus::Stopped,
+    }
+}
+
+/// `Duration` → mpris-server `Time`(微秒),溢出饱和到 `i64::MAX`。
+fn duration_to_time(d: Duration) -> Time {
+    let micros = i64::try_from(d.as_micros()).unwrap_or(i64::MAX);
+    Time::from_micros(micros)
+}
+
+/// 微秒(i64,非负)→ `Duration`。
+fn micros_to_duration(micros: i64) -> Duration {
+    Duration::from_micros(u64::try_from(micros).unwrap_or(0))
+}
@@ -0,0 +1,101 @@
+//! 队列条目 / 歌单 ↔ D-Bus object path 的互转。
+//!
+//! MPRIS 用 object path 标识 track 与 playlist。队列条目编成 `…/track/<版本>/<下标>`,
+//! 控件拿着旧列表发来的 id 能按版本认出过期;歌单 id(`netease:123`)含 path 不允许的
+//! 字符,按字节 hex 编进单个 path 段,可逆解回。
+
+use std::fmt::Write as _;
+
+use mpris_server::{PlaylistId, TrackId};
+
+use crate::state::TrackRef;
+
+/// 队列条目 path 前缀(规范禁止自定义 id 落在 `/org/mpris` 下)。
+const TRACK_PREFIX: &str = "/org/mineral/track/";
+
+/// 歌单 path 前缀。
+const PLAYLIST_PREFIX: &str = "/org/mineral/playlist/";
+
+/// 歌单 path 段的引导字符:保证 hex 为空(空 id)时段也非空。
+const PLAYLIST_LEAD: char = 'p';
+
+/// 队列引用 → MPRIS track id。
+pub(super) fn track_id(track: TrackRef) -> TrackId {
+    let path = format!("{TRACK_PREFIX}{}/{}", track.version(), track.index());
+    // 前缀 + 两段十进制数必是合法 path;兜底给 NoTrack 而不是 panic。
+    TrackId::try_from(path).unwrap_or(TrackId::NO_TRACK)
+}
+
+/// MPRIS track id → 队列引用;非本播放器发出的 id(含 `NoTrack`)返回 `None`。
+pub(super) fn parse_track_id(id: &TrackId) -> Option<TrackRef> {
+    let rest = id.as_str().strip_prefix(TRACK_PREFIX)?;
+    let (version, index) = rest.split_once('/')?;
+    Some(TrackRef::new(version.parse().ok()?, index.parse().ok()?))
+}
+
+/// 歌单 id → MPRIS playlist id。
+pub(super) fn playlist_path(id: &str) -> PlaylistId {
+    let mut path = String::with_capacity(PLAYLIST_PREFIX.len() + 1 + id.len() * 2);
+    path.push_str(PLAYLIST_PREFIX);
+    path.push(PLAYLIST_LEAD);
+    for byte in id.bytes() {
+        let _ = write!(path, "{byte:02x}");
+    }
+    // 前缀 + p[0-9a-f]* 必是合法 path;兜底给根 path `/`。
+    PlaylistId::try_from(path).unwrap_or_default()
+}
+
+/// MPRIS playlist id → 歌单 id;非本播放器发出的 path 返回 `None`。
+pub(super) fn parse_playlist_path(path: &str) -> Option<String> {
+    let hex = path
+        .strip_prefix(PLAYLIST_PREFIX)?
+        .strip_prefix(PLAYLIST_LEAD)?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use mpris_server::TrackId;
+
+    use super::{parse_playlist_path, parse_track_id, playlist_path, track_id};
+    use crate::state::TrackRef;
+
+    #[test]
+    fn track_id_round_trips() {
+        let track = TrackRef::new(42, 7);
+        let id = track_id(track);
+        assert_eq!(id.as_str(), "/org/mineral/track/42/7");
+        assert_eq!(parse_track_id(&id), Some(track));
+    }
+
+    #[test]
+    fn foreign_track_ids_are_rejected() {
+        assert_eq!(parse_track_id(&TrackId::NO_TRACK), None);
+        let other = TrackId::try_from("/org/mineral/track/x/1").ok();
+        assert_eq!(other.as_ref().and_then(parse_track_id), None);
+    }
+
+    #[test]
+    fn playlist_path_round_trips_non_path_chars() {
+        // 冒号 / 非 ASCII 都不合 path 语法,hex 后须能原样解回。
+        for id in ["netease:123", "mineral:每日推荐", ""] {
+            let path = playlist_path(id);
+            assert!(path.as_str().starts_with("/org/mineral/playlist/p"));
+            assert_eq!(parse_playlist_path(path.as_str()).as_deref(), Some(id));
+        }
+    }
+
+    #[test]
+    fn malformed_playlist_paths_are_rejected() {
+        assert_eq!(parse_playlist_path("/org/mineral/playlist/p6"), None);
+        assert_eq!(parse_playlist_path("/org/mineral/playlist/pzz"), None);
+        assert_eq!(parse_playlist_path("/org/other/p00"), None);
+    }
+}
@@ -81,7 +81,7 @@ fn attach(
 fn wire_simple(command: &MPRemoteCommand, on_command: &OnCommand, cmd: MediaCommand) {
     let cb = Arc::clone(on_command);
     let handler = RcBlock::new(move |_event: NonNull<MPRemoteCommandEvent>| {
-        cb(cmd);
+        cb(cmd.clone());
         MPRemoteCommandHandlerStatus::Success
     });
     attach(command, &handler);
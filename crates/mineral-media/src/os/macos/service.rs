@@ -16,7 +16,7 @@ use super::convert::secs;
 use super::now_playing::MacNowPlaying;
 use crate::command::{LoopMode, MediaCommand};
 use crate::config::MediaConfig;
-use crate::state::{NowPlaying, PlaybackState};
+use crate::state::{NowPlaying, PlaybackState, PlaylistEntry};
 
 /// 主线程 / tokio → 专属线程的状态更新消息。
 enum Update {
@@ -99,6 +99,28 @@ impl MediaService {
         Ok(())
     }
 
+    /// 上报播放倍速。macOS 后端暂不上报倍速,本平台 no-op。
+    pub fn set_rate(&self, rate: f64) -> color_eyre::Result<()> {
+        let _ = rate;
+        Ok(())
+    }
+
+    /// 上报队列。macOS 系统媒体中心无队列浏览面,本平台 no-op。
+    pub fn set_tracklist(&self, version: u64, tracks: &[NowPlaying]) -> color_eyre::Result<()> {
+        let _ = (version, tracks);
+        Ok(())
+    }
+
+    /// 上报歌单库。macOS 系统媒体中心无歌单面,本平台 no-op。
+    pub fn set_playlists(
+        &self,
+        playlists: &[PlaylistEntry],
+        active: Option<&str>,
+    ) -> color_eyre::Result<()> {
+        let _ = (playlists, active);
+        Ok(())
+    }
+
     /// 设置当前曲目封面(已编码的图片字节,由上层拉取后传入)。
     pub fn set_artwork(&self, image_bytes: &[u8]) -> color_eyre::Result<()> {
         self.send(Update::Artwork(image_bytes.to_vec()))
@@ -5,7 +5,7 @@ use std::time::Duration;
 
 use crate::command::{LoopMode, MediaCommand};
 use crate::config::MediaConfig;
-use crate::state::{NowPlaying, PlaybackState};
+use crate::state::{NowPlaying, PlaybackState, PlaylistEntry};
 
 /// 系统媒体服务句柄(无集成平台的占位)。
 pub struct MediaService {
@@ -61,4 +61,26 @@ impl MediaService {
         let _ = mode;
         Ok(())
     }
+
+    /// 占位:no-op。
+    pub fn set_rate(&self, rate: f64) -> color_eyre::Result<()> {
+        let _ = rate;
+        Ok(())
+    }
+
+    /// 占位:no-op。
+    pub fn set_tracklist(&self, version: u64, tracks: &[NowPlaying]) -> color_eyre::Result<()> {
+        let _ = (version, tracks);
+        Ok(())
+    }
+
+    /// 占位:no-op。
+    pub fn set_playlists(
+        &self,
+        playlists: &[PlaylistEntry],
+        active: Option<&str>,
+    ) -> color_eyre::Result<()> {
+        let _ = (playlists, active);
+        Ok(())
+    }
 }
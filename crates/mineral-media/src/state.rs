@@ -65,4 +65,64 @@ pub struct NowPlaying {
     /// 行级罗马音。空 = 无。序列化成标准 LRC 透传到 `mineral:romanization`。
     #[builder(default)]
     pub(crate) romanization: Vec<LyricLine>,
+
+    /// 本曲在队列中的位置(MPRIS `mpris:trackid`)。`None` = 不在队列里(已被摘出但仍在出声)。
+    /// 只对当前曲有意义;[`crate::MediaService::set_tracklist`] 里的条目由后端按下标自行编号。
+    #[builder(default)]
+    pub(crate) track: Option<TrackRef>,
+}
+
+/// 队列中的一首:队列版本号 + 下标。
+///
+/// 队列一改下标即漂移,带上版本号让宿主认出控件拿着旧列表发来的过期引用(版本不符即忽略),
+/// 而不是误操作到同一下标上的另一首。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TrackRef {
+    /// 产生该引用时的队列版本号。
+    version: u64,
+
+    /// 队列下标。
+    index: usize,
+}
+
+impl TrackRef {
+    /// 构造队列引用。
+    ///
+    /// # Params:
+    ///   - `version`: 宿主的队列版本号(队列每次变更递增)
+    ///   - `index`: 队列下标
+    #[must_use]
+    pub fn new(version: u64, index: usize) -> Self {
+        Self { version, index }
+    }
+
+    /// 产生该引用时的队列版本号。
+    #[must_use]
+    pub fn version(self) -> u64 {
+        self.version
+    }
+
+    /// 队列下标。
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+/// 上报给系统媒体控件的一张歌单(MPRIS Playlists)。
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder)]
+#[non_exhaustive]
+pub struct PlaylistEntry {
+    /// 歌单标识(限定 id,如 `netease:123`);[`crate::MediaCommand::ActivatePlaylist`] 原样带回。
+    #[builder(setter(into))]
+    pub(crate) id: String,
+
+    /// 歌单名。
+    #[builder(setter(into))]
+    pub(crate) name: String,
+
+    /// 封面 URL(`http(s)://` 或 `file://`)。
+    #[builder(default)]
+    pub(crate) icon: Option<String>,
 }
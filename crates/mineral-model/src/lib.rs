@@ -25,6 +25,8 @@ pub mod envelope;
 pub mod format;
 /// 各类资源(歌、专辑、艺人、歌单、用户)的 ID newtype。
 pub mod ids;
+/// 一首歌的整曲响度分析结果(离线预计算,响度归一化用)。
+pub mod loudness;
 /// 一首歌的歌词集合(行级 LRC、逐字、翻译、罗马音)。
 pub mod lyrics;
 /// 一首歌的可播放 URL + 元信息。
@@ -48,6 +50,7 @@ pub use bitrate::BitRate;
 pub use envelope::Envelope;
 pub use format::AudioFormat;
 pub use ids::{AlbumId, ArtistId, PlaylistId, SongId, UserId};
+pub use loudness::Loudness;
 pub use lyrics::{
     LineKind, LyricLine, Lyrics, Word, current_line, has_timed, has_words, parse_lrc, to_lrc_string,
 };
@@ -55,6 +58,6 @@ pub use play_url::{PlayUrl, StreamLayout};
 pub use playlist::Playlist;
 pub use refs::{AlbumRef, ArtistRef};
 pub use search::SearchKind;
-pub use song::Song;
+pub use song::{CST_OFFSET_MS, Song, release_year};
 pub use source::SourceKind;
 pub use url::MediaUrl;
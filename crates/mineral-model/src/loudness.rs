@@ -0,0 +1,38 @@
+//! 一首歌的整曲响度分析结果(离线预计算,响度归一化的增益依据)。
+
+use serde::{Deserialize, Serialize};
+
+/// 一首歌的 EBU R128 整曲响度 + 采样峰值。
+///
+/// 与 [`crate::Envelope`] 同一遍离线解码产出、同行落库,但各自带算法版本:
+/// 包络算法变更不必让响度重算,反之亦然。
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Loudness {
+    /// 门限积分响度(LUFS,BS.1770 绝对 −70 / 相对 −10 LU 双门限);全曲静音时落在
+    /// 绝对门限 −70。
+    pub integrated_lufs: f64,
+
+    /// 全曲最大采样绝对值(线性,满幅 = 1.0;非 true peak)。
+    pub peak: f64,
+
+    /// 产出算法版本;读取方与当前版本不符时视同缺失、触发重算。
+    pub version: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loudness::Loudness;
+
+    /// 响度经 serde 往返不变(db / IPC 存取的共同前提)。
+    #[test]
+    fn loudness_survives_serde_roundtrip() -> color_eyre::Result<()> {
+        let loudness = Loudness {
+            integrated_lufs: -14.25,
+            peak: 0.98,
+            version: 1,
+        };
+        let back = serde_json::from_str::<Loudness>(&serde_json::to_string(&loudness)?)?;
+        assert_eq!(back, loudness);
+        Ok(())
+    }
+}
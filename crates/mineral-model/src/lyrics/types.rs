@@ -197,6 +197,34 @@ impl Lyrics {
     pub fn romanization_lines(&self) -> Vec<LyricLine> {
         rebuild_track(&self.lines, |l| l.romanization.as_ref())
     }
+
+    /// 整体平移时间轴(行级时间戳与逐字起点一并平移,早于 0 的钳到 0),给外部协议导出
+    /// 施加 per-song 歌词偏移用。
+    ///
+    /// # Params:
+    ///   - `offset_ms`: 偏移毫秒;正 = 歌词推迟,负 = 提前
+    ///
+    /// # Return:
+    ///   平移后的副本;`offset_ms == 0` 时与原值相等。
+    pub fn shifted(&self, offset_ms: i64) -> Self {
+        let shift = |t: u64| {
+            if offset_ms >= 0 {
+                t.saturating_add(offset_ms.unsigned_abs())
+            } else {
+                t.saturating_sub(offset_ms.unsigned_abs())
+            }
+        };
+        let mut lines = self.lines.clone();
+        for line in &mut lines {
+            line.time_ms = line.time_ms.map(shift);
+            if let LineKind::Words { words, .. } = &mut line.kind {
+                for w in words {
+                    w.start_ms = shift(w.start_ms);
+                }
+            }
+        }
+        Self { lines }
+    }
 }
 
 /// 把一条副轨按互最近邻配对到原文行上,配上的行经 `set` 写入文本。
@@ -259,7 +287,7 @@ fn rebuild_track(
 
 #[cfg(test)]
 mod tests {
-    use super::{LyricLine, Lyrics};
+    use super::{LineKind, LyricLine, Lyrics, Word};
 
     /// 《迷星叫》真实形状:原文开头 3 行 credits(0/1/2s,翻译轨无对应行),正文从
     /// 16.64s 起。互最近邻下 credits 行不得借走正文首句翻译(单向最近邻的错配 bug)。
@@ -384,4 +412,41 @@ mod tests {
             "时间戳为原文行的 10_000 而非副轨的 10_080;credits 行不出现"
         );
     }
+
+    /// 平移:行级时间戳与逐字起点同步推迟 / 提前,提前越过 0 钳到 0,无戳行不动。
+    #[test]
+    fn shifted_moves_lines_and_words() {
+        let l = Lyrics {
+            lines: vec![
+                LyricLine::untimed("credits"),
+                LyricLine::timed(100, "a"),
+                LyricLine {
+                    time_ms: Some(2_000),
+                    kind: LineKind::Words {
+                        dur_ms: 500,
+                        words: vec![Word {
+                            start_ms: 2_000,
+                            dur_ms: 500,
+                            text: "b".to_owned(),
+                        }],
+                    },
+                    translation: None,
+                    romanization: None,
+                },
+            ],
+        };
+        let later = l.shifted(250);
+        let times = |x: &Lyrics| x.lines.iter().map(|l| l.time_ms).collect::<Vec<_>>();
+        assert_eq!(times(&later), vec![None, Some(350), Some(2_250)]);
+        assert_eq!(
+            later
+                .lines
+                .get(2)
+                .and_then(|l| l.kind.words().first())
+                .map(|w| w.start_ms),
+            Some(2_250)
+        );
+        assert_eq!(times(&l.shifted(-300)), vec![None, Some(0), Some(1_700)]);
+        assert_eq!(l.shifted(0), l);
+    }
 }
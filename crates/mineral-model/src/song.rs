@@ -34,6 +34,23 @@ pub struct Song {
     #[builder(default)]
     pub album: Option<AlbumRef>,
 
+    /// 专辑内音轨号(从 1 起),来源没给为 `None`。写下载文件标签时用。
+    /// serde 容缺:旧缓存快照没有本字段,反序列化落 `None`。
+    #[builder(default)]
+    #[serde(default)]
+    pub track_no: Option<u32>,
+
+    /// 专辑艺人名(合辑常为「群星」,与曲目艺人不同);来源没给为 `None`。
+    /// 下载路径模板 `{album_artist}` 与写文件标签时用。serde 容缺同 `track_no`。
+    #[builder(default)]
+    #[serde(default)]
+    pub album_artist: Option<String>,
+
+    /// 发行年;来源没给为 `None`。下载路径模板 `{year}` 与写文件标签时用。serde 容缺同 `track_no`。
+    #[builder(default)]
+    #[serde(default)]
+    pub year: Option<u32>,
+
     /// 时长(ms);`None` = **未知**(来源接口没给 / 本地文件未探)——与「真的 0 ms」区分开,
     /// 展示层据此画占位而非 `0:00`,预排窗口等下游据此显式回落而非静默吃 0。
     #[builder(default)]
@@ -65,9 +82,52 @@ impl Song {
     }
 }
 
+/// 北京时间相对 UTC 的偏移(ms)。国内源的发行时间按北京时间零点给,按 UTC 取年
+/// 会把元旦发行的专辑算成上一年。
+pub const CST_OFFSET_MS: i64 = 8 * 3_600_000;
+
+/// 时间戳 → 公历年份(按所给偏移的本地日期取)。
+///
+/// # Params:
+///   - `unix_ms`: unix 毫秒时间戳;`<= 0` 视为来源没给
+///   - `offset_ms`: 时区偏移(ms),国内源传 [`CST_OFFSET_MS`]
+///
+/// # Return:
+///   年份;没给或早于公元元年为 `None`。
+pub fn release_year(unix_ms: i64, offset_ms: i64) -> Option<u32> {
+    if unix_ms <= 0 {
+        return None;
+    }
+    // 日数 → 公历年(Howard Hinnant 的 civil_from_days,按 400 年周期折算)。
+    let days = unix_ms.saturating_add(offset_ms).div_euclid(86_400_000);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    // mp 从三月起算:0..=9 是三月到十二月,10/11 是次年一二月。
+    let year = yoe + era * 400 + i64::from(mp >= 10);
+    u32::try_from(year).ok().filter(|y| *y > 0)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Song;
+    use super::{CST_OFFSET_MS, Song, release_year};
+
+    /// 年份按给定偏移的本地日期取;跨年边界、闰年末、无效时间戳。
+    #[test]
+    fn release_year_from_timestamp() {
+        // 2003-07-31T00:00:00+08:00
+        assert_eq!(release_year(1_059_580_800_000, CST_OFFSET_MS), Some(2003));
+        // 2020-01-01T00:00:00+08:00 = 2019-12-31T16:00Z:UTC 下还在上一年。
+        assert_eq!(release_year(1_577_808_000_000, 0), Some(2019));
+        assert_eq!(release_year(1_577_808_000_000, CST_OFFSET_MS), Some(2020));
+        // 2024-12-31T23:59:59Z(闰年最后一秒)
+        assert_eq!(release_year(1_735_689_599_000, 0), Some(2024));
+        assert_eq!(release_year(0, CST_OFFSET_MS), None);
+        assert_eq!(release_year(-1, 0), None);
+    }
 
     /// 旧缓存快照(无 `unavailable` 字段)反序列化落 `false`,不炸缓存。
     #[test]
@@ -64,6 +64,58 @@ pub fn music_export_dir() -> color_eyre::Result<PathBuf> {
     Ok(xdg::music_dir()?.join("mineral"))
 }
 
+/// 用户歌词目录的**平台默认**(`<data_dir>/lyrics`)。
+///
+/// 按 `<来源>/<歌曲 id>.lrc` 手放的歌词优先于来源拉取的歌词;用户改目录走
+/// `config.lua` 的 `lyrics.dir`。
+///
+/// # Return:
+///   解析得到的目录路径。本函数不创建目录。
+pub fn lyrics_dir() -> color_eyre::Result<PathBuf> {
+    Ok(data_dir()?.join("lyrics"))
+}
+
+/// 歌词覆盖目录(`<data_dir>/lyric-overrides`)。
+///
+/// TUI 歌词编辑器存下的逐曲打轴结果按 `<来源>/<歌曲 id>.lrc` 落这里;与用户歌词目录分开,
+/// 查找时先于 sidecar / 内嵌 / 用户目录。
+///
+/// # Return:
+///   解析得到的目录路径。本函数不创建目录。
+pub fn lyric_overrides_dir() -> color_eyre::Result<PathBuf> {
+    Ok(data_dir()?.join("lyric-overrides"))
+}
+
+/// 本地曲库内嵌封面的抽取目录(`<cache_dir>/local-cover`)。
+///
+/// 音频文件内嵌的封面图抽成独立文件落这里,供封面链路按 `MediaUrl::Local` 读取;
+/// 可被清理,下次扫描重新抽取。
+///
+/// # Return:
+///   解析得到的目录路径。本函数不创建目录。
+pub fn local_cover_dir() -> color_eyre::Result<PathBuf> {
+    Ok(cache_dir()?.join("local-cover"))
+}
+
+/// 展开用户配置里的路径:开头的 `~` / `~/` 换成 `$HOME`,其余原样。
+///
+/// 只认**开头**的 `~`(不支持 `~user` 形式);配置里写绝对路径时不依赖 `$HOME`。
+///
+/// # Params:
+///   - `raw`: 用户写的路径文本
+///
+/// # Return:
+///   展开后的路径;需要 `$HOME` 而其未设置时返回 `Err`。
+pub fn expand_home(raw: &str) -> color_eyre::Result<PathBuf> {
+    match raw.strip_prefix('~') {
+        Some("") => xdg::home_dir(),
+        Some(rest) if rest.starts_with('/') => {
+            Ok(xdg::home_dir()?.join(rest.trim_start_matches('/')))
+        }
+        _ => Ok(PathBuf::from(raw)),
+    }
+}
+
 /// 客户端(TUI)持久化数据库文件(`<data_dir>/tui.db`)。
 ///
 /// 与 server 的 `mineral.db` 同目录的另一个 sqlite 文件,存纯客户端态(当前:封面缓存索引)。
@@ -246,4 +298,27 @@ mod tests {
         assert_eq!(super::music_export_dir()?, tmp.path().join("Music/mineral"));
         Ok(())
     }
+
+    /// `~/` 开头按 `$HOME` 展开;绝对路径与 `~user` 形式原样保留。
+    #[test]
+    fn expand_home_only_leading_tilde() -> color_eyre::Result<()> {
+        let _lock = ENV_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let tmp = fake_home()?;
+        let _g = EnvGuard::set("HOME", tmp.path());
+
+        assert_eq!(super::expand_home("~/Music")?, tmp.path().join("Music"));
+        assert_eq!(super::expand_home("~")?, tmp.path().to_path_buf());
+        assert_eq!(
+            super::expand_home("/srv/music")?,
+            std::path::PathBuf::from("/srv/music")
+        );
+        assert_eq!(
+            super::expand_home("~bob/x")?,
+            std::path::PathBuf::from("~bob/x"),
+            "~user 形式不展开"
+        );
+        Ok(())
+    }
 }
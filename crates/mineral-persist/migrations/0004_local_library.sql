@@ -0,0 +1,38 @@
+-- 本地曲库索引:扫描根下每个音频文件一行,(mtime, size) 未变的文件重扫时直接复用标签,
+-- 不再 probe。path 为绝对路径,即 local 源的歌曲 id。
+CREATE TABLE local_tracks (
+    path TEXT PRIMARY KEY NOT NULL,
+    mtime_ms INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    album TEXT,
+    album_artist TEXT,
+    disc INTEGER,
+    track INTEGER,
+    duration_ms INTEGER,
+    format TEXT,
+    bitrate_kbps INTEGER,
+    bit_depth INTEGER,
+    sample_rate INTEGER,
+    -- 封面图文件绝对路径(抽出的内嵌封面或同目录图片);NULL = 无。
+    cover TEXT,
+    scanned_at INTEGER NOT NULL);
+
+-- 多值艺人按 position 保序(主艺人 position = 0),随曲目行级联删除。
+CREATE TABLE local_track_artists (
+    path TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (path, position),
+    FOREIGN KEY (path) REFERENCES local_tracks(path) ON DELETE CASCADE);
+
+-- 最近一次扫描的回执(单例行 id=0),供 `mineral library status` 展示。
+CREATE TABLE local_scan_state (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    finished_at INTEGER NOT NULL,
+    full_scan INTEGER NOT NULL,
+    tracks INTEGER NOT NULL,
+    added INTEGER NOT NULL,
+    updated INTEGER NOT NULL,
+    removed INTEGER NOT NULL,
+    elapsed_ms INTEGER NOT NULL);
@@ -0,0 +1,6 @@
+-- 整曲响度(EBU R128 积分响度 + 采样峰值)挂在包络行上:两者同一遍离线解码产出。
+-- 三列成组可空:老行 / 只写过包络的行为 NULL,读取视同缺失、由重算补齐。
+-- 独立版本列:响度算法与包络算法各自演进,互不牵连重算。
+ALTER TABLE song_envelope ADD COLUMN loudness_version INTEGER;
+ALTER TABLE song_envelope ADD COLUMN integrated_lufs REAL;
+ALTER TABLE song_envelope ADD COLUMN sample_peak REAL;
@@ -0,0 +1,8 @@
+-- 用户在 TUI 里新建的智能歌单(config.lua 里声明的不落库,随配置走)。
+-- key 取建单时的名字,即歌单 id 的裸值段:改名只动 name,id 不漂移。
+-- query 存筛选表达式原文,求值时现解析——表达式语法演进不需要迁移数据。
+CREATE TABLE smart_playlists (
+    key        TEXT PRIMARY KEY NOT NULL,
+    name       TEXT NOT NULL,
+    query      TEXT NOT NULL,
+    created_at INTEGER NOT NULL);
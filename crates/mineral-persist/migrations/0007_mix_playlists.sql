@@ -0,0 +1,19 @@
+-- 聚合源自有的混源歌单:曲目可跨 netease / bilibili / 本地并排。
+-- 曲目只存 (namespace, song_value) 引用,元数据 join song_meta 现取——与聚合收藏同口径,
+-- 缺 meta 的行不出,随后台补 meta 自然出现。
+CREATE TABLE mix_playlists (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    name        TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    created_at  INTEGER NOT NULL);
+
+-- position 在单张歌单内保持 0..n 连续(增删 / 挪动都在事务里重排),同一首歌一张单里至多一行。
+CREATE TABLE mix_playlist_songs (
+    playlist_id INTEGER NOT NULL,
+    position    INTEGER NOT NULL,
+    namespace   TEXT NOT NULL,
+    song_value  TEXT NOT NULL,
+    added_at    INTEGER NOT NULL,
+    PRIMARY KEY (playlist_id, namespace, song_value),
+    FOREIGN KEY (playlist_id) REFERENCES mix_playlists(id) ON DELETE CASCADE);
+CREATE INDEX idx_mix_playlist_songs_position ON mix_playlist_songs (playlist_id, position);
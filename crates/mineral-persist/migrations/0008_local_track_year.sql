@@ -0,0 +1,4 @@
+-- 发行年(标签 DATE / YEAR 取年份);老行为 NULL。
+-- 指纹清零:老行在下次增量扫描时按「变动」重读标签,补上年份,无需用户手动全量扫描。
+ALTER TABLE local_tracks ADD COLUMN year INTEGER;
+UPDATE local_tracks SET mtime_ms = -1;
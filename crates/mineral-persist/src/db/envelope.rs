@@ -1,11 +1,12 @@
-//! 每曲振幅包络缓存(`song_envelope` 表)。挂在 [`NamespaceStore`] 上的扩展方法。
+//! 每曲振幅包络 + 整曲响度缓存(`song_envelope` 表)。挂在 [`NamespaceStore`] 上的扩展方法。
 //!
 //! 包络与音质无关(振幅形状跨码率基本一致),按 `(namespace, song_value)` 每曲一行;
 //! 读取按算法版本过滤,版本不符视同缺失,由产出方重算覆盖,不让旧算法数据毒化渲染。
+//! 响度(R128 积分响度 + 峰值)与包络同遍产出,挂在同一行的可空列上,版本各自过滤。
 
 use color_eyre::eyre::WrapErr;
 use mineral_log::trace;
-use mineral_model::{Envelope, SongId};
+use mineral_model::{Envelope, Loudness, SongId};
 
 use crate::db::namespace::NamespaceStore;
 
@@ -68,11 +69,75 @@ impl NamespaceStore {
         .wrap_err_with(|| format!("读包络失败 song={}", id.value()))?;
         Ok(row.map(|(points,)| Envelope { points, version }))
     }
+
+    /// 把整曲响度写到该曲的包络行上(同遍产出,须先 [`Self::put_envelope`];行不存在
+    /// 时静默不写)。降级 no-op。
+    ///
+    /// # Params:
+    ///   - `id`: 歌曲 id
+    ///   - `loudness`: 响度分析结果(积分响度 + 峰值 + 算法版本)
+    ///
+    /// # Return:
+    ///   成功返回 `Ok(())`;降级时同样 `Ok(())`。
+    pub async fn put_loudness(&self, id: &SongId, loudness: &Loudness) -> color_eyre::Result<()> {
+        let Some(pool) = self.pool() else {
+            return Ok(());
+        };
+        trace!(target: "persist", song = %id.value(), version = loudness.version, "put_loudness");
+        sqlx::query(
+            "UPDATE song_envelope SET loudness_version=?, integrated_lufs=?, sample_peak=? \
+             WHERE namespace=? AND song_value=?",
+        )
+        .bind(i64::from(loudness.version))
+        .bind(loudness.integrated_lufs)
+        .bind(loudness.peak)
+        .bind(self.namespace())
+        .bind(id.value())
+        .execute(pool)
+        .await
+        .wrap_err_with(|| format!("写响度失败 song={}", id.value()))?;
+        Ok(())
+    }
+
+    /// 读一首歌的整曲响度,**按算法版本过滤**:列为空(老行 / 未分析)或版本不符
+    /// 视同缺失。降级 / 未命中返回 `Ok(None)`。
+    ///
+    /// # Params:
+    ///   - `id`: 歌曲 id
+    ///   - `version`: 期望的响度算法版本
+    ///
+    /// # Return:
+    ///   命中且版本相符返回 `Ok(Some(loudness))`,否则 `Ok(None)`。
+    pub async fn get_loudness(
+        &self,
+        id: &SongId,
+        version: u16,
+    ) -> color_eyre::Result<Option<Loudness>> {
+        let Some(pool) = self.pool() else {
+            return Ok(None);
+        };
+        let row: Option<(f64, f64)> = sqlx::query_as(
+            "SELECT integrated_lufs, sample_peak FROM song_envelope \
+             WHERE namespace=? AND song_value=? AND loudness_version=? \
+               AND integrated_lufs IS NOT NULL AND sample_peak IS NOT NULL",
+        )
+        .bind(self.namespace())
+        .bind(id.value())
+        .bind(i64::from(version))
+        .fetch_optional(pool)
+        .await
+        .wrap_err_with(|| format!("读响度失败 song={}", id.value()))?;
+        Ok(row.map(|(integrated_lufs, peak)| Loudness {
+            integrated_lufs,
+            peak,
+            version,
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use mineral_model::{Envelope, SongId, SourceKind};
+    use mineral_model::{Envelope, Loudness, SongId, SourceKind};
 
     use crate::ServerStore;
 
@@ -167,6 +232,43 @@ mod tests {
         Ok(())
     }
 
+    /// 响度挂在包络行上:put 后同版本读回;老行(只有包络)与版本不符都视同缺失;
+    /// 其后重写包络不清掉已写的响度。
+    #[tokio::test]
+    async fn loudness_rides_on_envelope_row() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let scope = store.scope(SourceKind::NETEASE);
+        let id = SongId::new(SourceKind::NETEASE, "s1");
+        let envelope = Envelope {
+            points: vec![1, 2],
+            version: 3,
+        };
+        scope.put_envelope(&id, &envelope).await?;
+        assert_eq!(
+            scope.get_loudness(&id, /*version*/ 1).await?,
+            None,
+            "只有包络的行视同未分析"
+        );
+        let loudness = Loudness {
+            integrated_lufs: -9.5,
+            peak: 1.0,
+            version: 1,
+        };
+        scope.put_loudness(&id, &loudness).await?;
+        assert_eq!(
+            scope.get_loudness(&id, /*version*/ 1).await?,
+            Some(loudness)
+        );
+        assert_eq!(scope.get_loudness(&id, /*version*/ 2).await?, None);
+        scope.put_envelope(&id, &envelope).await?;
+        assert_eq!(
+            scope.get_loudness(&id, /*version*/ 1).await?,
+            Some(loudness)
+        );
+        Ok(())
+    }
+
     /// 降级句柄:put 静默成功、get 恒 `None`,播放路径无需特判。
     #[tokio::test]
     async fn disabled_store_is_noop() -> color_eyre::Result<()> {
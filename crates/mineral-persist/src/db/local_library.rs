@@ -0,0 +1,458 @@
+//! 本地曲库索引(`local_tracks` / `local_track_artists` / `local_scan_state` 表)。
+//!
+//! 扫描器(local channel)的持久侧:每个文件一行标签快照 + `(mtime, size)` 指纹,重扫时
+//! 指纹未变的文件直接复用,只 probe 新增 / 变动的文件。路径即身份(= local 源歌曲 id 的值),
+//! 不挂 namespace——本表只服务 local 源。
+
+use color_eyre::eyre::WrapErr;
+use mineral_log::debug;
+use rustc_hash::FxHashMap;
+use sqlx::FromRow;
+
+use crate::ServerStore;
+use crate::db::time::now_ms;
+
+/// 一个已索引文件的标签快照。只读 / 写入 DTO,字段全 `pub`。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTrackRow {
+    /// 文件绝对路径(身份)。
+    pub path: String,
+
+    /// 文件修改时间(unix ms);与 `size` 一起构成重扫指纹。
+    pub mtime_ms: i64,
+
+    /// 文件大小(bytes)。
+    pub size: u64,
+
+    /// 标题。
+    pub title: String,
+
+    /// 艺人,主艺人在前。
+    pub artists: Vec<String>,
+
+    /// 专辑名。
+    pub album: Option<String>,
+
+    /// 专辑艺人。
+    pub album_artist: Option<String>,
+
+    /// 发行年。
+    pub year: Option<u32>,
+
+    /// 碟号。
+    pub disc: Option<u32>,
+
+    /// 曲序。
+    pub track: Option<u32>,
+
+    /// 时长(ms)。
+    pub duration_ms: Option<u64>,
+
+    /// 容器格式稳定名(`AudioFormat::as_str`)。
+    pub format: Option<String>,
+
+    /// 音频码率(kbps)。
+    pub bitrate_kbps: Option<u32>,
+
+    /// 位深。
+    pub bit_depth: Option<u8>,
+
+    /// 采样率(Hz)。
+    pub sample_rate: Option<u32>,
+
+    /// 封面图文件绝对路径。
+    pub cover: Option<String>,
+}
+
+/// 一次扫描的回执(单例,后写覆盖前写)。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalScanRecord {
+    /// 完成时刻(unix ms)。
+    pub finished_at_ms: i64,
+
+    /// 是否全量扫描(忽略指纹、全部重读标签)。
+    pub full: bool,
+
+    /// 扫描后曲库总曲数。
+    pub tracks: u64,
+
+    /// 新增文件数。
+    pub added: u64,
+
+    /// 指纹变动而重读的文件数。
+    pub updated: u64,
+
+    /// 已消失而移出索引的文件数。
+    pub removed: u64,
+
+    /// 扫描耗时(ms)。
+    pub elapsed_ms: u64,
+}
+
+/// 索引总览(`mineral library status` 用)。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalLibraryStats {
+    /// 已索引文件数。
+    pub tracks: u64,
+
+    /// 已索引文件总字节。
+    pub bytes: u64,
+
+    /// 最近一次扫描回执;从未扫描为 `None`。
+    pub last_scan: Option<LocalScanRecord>,
+}
+
+/// `local_tracks` 行(不含艺人)。
+#[derive(FromRow)]
+struct TrackDbRow {
+    /// 路径。
+    path: String,
+    /// 修改时间。
+    mtime_ms: i64,
+    /// 大小。
+    size: i64,
+    /// 标题。
+    title: String,
+    /// 专辑。
+    album: Option<String>,
+    /// 专辑艺人。
+    album_artist: Option<String>,
+    /// 发行年。
+    year: Option<i64>,
+    /// 碟号。
+    disc: Option<i64>,
+    /// 曲序。
+    track: Option<i64>,
+    /// 时长。
+    duration_ms: Option<i64>,
+    /// 格式。
+    format: Option<String>,
+    /// 码率。
+    bitrate_kbps: Option<i64>,
+    /// 位深。
+    bit_depth: Option<i64>,
+    /// 采样率。
+    sample_rate: Option<i64>,
+    /// 封面。
+    cover: Option<String>,
+}
+
+/// `local_scan_state` 单例行。
+#[derive(FromRow)]
+struct ScanDbRow {
+    /// 完成时刻。
+    finished_at: i64,
+    /// 是否全量。
+    full_scan: i64,
+    /// 总曲数。
+    tracks: i64,
+    /// 新增。
+    added: i64,
+    /// 重读。
+    updated: i64,
+    /// 移除。
+    removed: i64,
+    /// 耗时。
+    elapsed_ms: i64,
+}
+
+impl ScanDbRow {
+    /// 行 → 回执(负数 / 越界计数按 0 落,不因脏行拒读)。
+    fn into_record(self) -> LocalScanRecord {
+        let count = |v: i64| u64::try_from(v).unwrap_or(0);
+        LocalScanRecord {
+            finished_at_ms: self.finished_at,
+            full: self.full_scan != 0,
+            tracks: count(self.tracks),
+            added: count(self.added),
+            updated: count(self.updated),
+            removed: count(self.removed),
+            elapsed_ms: count(self.elapsed_ms),
+        }
+    }
+}
+
+/// 本地曲库索引存储。
+pub struct LocalLibraryStore {
+    /// 顶层句柄。
+    persist: ServerStore,
+}
+
+impl LocalLibraryStore {
+    /// 构造。
+    ///
+    /// # Params:
+    ///   - `persist`: 顶层句柄
+    pub(crate) fn new(persist: ServerStore) -> Self {
+        Self { persist }
+    }
+
+    /// 读出全部索引行(按路径排序,艺人按 position 保序)。降级返回空。
+    ///
+    /// # Return:
+    ///   全部已索引文件的标签快照。
+    pub async fn load_all(&self) -> color_eyre::Result<Vec<LocalTrackRow>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query_as::<_, TrackDbRow>(
+            "SELECT path, mtime_ms, size, title, album, album_artist, year, disc, track, duration_ms, \
+             format, bitrate_kbps, bit_depth, sample_rate, cover \
+             FROM local_tracks ORDER BY path",
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("读本地曲库索引失败")?;
+        let artist_rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT path, name FROM local_track_artists ORDER BY path, position")
+                .fetch_all(pool)
+                .await
+                .wrap_err("读本地曲库艺人失败")?;
+        let mut artists = FxHashMap::<String, Vec<String>>::default();
+        for (path, name) in artist_rows {
+            artists.entry(path).or_default().push(name);
+        }
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let artists = artists.remove(&r.path).unwrap_or_default();
+                LocalTrackRow {
+                    mtime_ms: r.mtime_ms,
+                    size: u64::try_from(r.size).unwrap_or(0),
+                    title: r.title,
+                    artists,
+                    album: r.album,
+                    album_artist: r.album_artist,
+                    year: r.year.and_then(|v| u32::try_from(v).ok()),
+                    disc: r.disc.and_then(|v| u32::try_from(v).ok()),
+                    track: r.track.and_then(|v| u32::try_from(v).ok()),
+                    duration_ms: r.duration_ms.and_then(|v| u64::try_from(v).ok()),
+                    format: r.format,
+                    bitrate_kbps: r.bitrate_kbps.and_then(|v| u32::try_from(v).ok()),
+                    bit_depth: r.bit_depth.and_then(|v| u8::try_from(v).ok()),
+                    sample_rate: r.sample_rate.and_then(|v| u32::try_from(v).ok()),
+                    cover: r.cover,
+                    path: r.path,
+                }
+            })
+            .collect())
+    }
+
+    /// 一次扫描的增量落库:`upserts` 覆盖写(含艺人重写),`removed` 删行(艺人级联)。
+    /// 全部在一个事务里,不留半套索引。降级静默成功。
+    ///
+    /// # Params:
+    ///   - `upserts`: 新增 / 重读的文件
+    ///   - `removed`: 已消失文件的路径
+    ///
+    /// # Return:
+    ///   成功返回 `Ok(())`。
+    pub async fn apply(
+        &self,
+        upserts: &[LocalTrackRow],
+        removed: &[String],
+    ) -> color_eyre::Result<()> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(());
+        };
+        debug!(target: "persist", upserts = upserts.len(), removed = removed.len(), "写本地曲库索引");
+        let now = now_ms();
+        let mut tx = pool.begin().await.wrap_err("开启本地曲库索引事务失败")?;
+        for path in removed {
+            sqlx::query("DELETE FROM local_tracks WHERE path=?")
+                .bind(path)
+                .execute(&mut *tx)
+                .await
+                .wrap_err_with(|| format!("删本地曲库行失败 path={path}"))?;
+        }
+        for row in upserts {
+            sqlx::query(
+                "INSERT INTO local_tracks(path,mtime_ms,size,title,album,album_artist,year,disc,\
+                 track,duration_ms,format,bitrate_kbps,bit_depth,sample_rate,cover,scanned_at) \
+                 VALUES(?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?) \
+                 ON CONFLICT(path) DO UPDATE SET \
+                   mtime_ms=excluded.mtime_ms, size=excluded.size, title=excluded.title, \
+                   album=excluded.album, album_artist=excluded.album_artist, year=excluded.year, \
+                   disc=excluded.disc, track=excluded.track, duration_ms=excluded.duration_ms, \
+                   format=excluded.format, bitrate_kbps=excluded.bitrate_kbps, \
+                   bit_depth=excluded.bit_depth, sample_rate=excluded.sample_rate, \
+                   cover=excluded.cover, scanned_at=excluded.scanned_at",
+            )
+            .bind(&row.path)
+            .bind(row.mtime_ms)
+            .bind(i64::try_from(row.size).unwrap_or(i64::MAX))
+            .bind(&row.title)
+            .bind(&row.album)
+            .bind(&row.album_artist)
+            .bind(row.year.map(i64::from))
+            .bind(row.disc.map(i64::from))
+            .bind(row.track.map(i64::from))
+            .bind(row.duration_ms.and_then(|v| i64::try_from(v).ok()))
+            .bind(&row.format)
+            .bind(row.bitrate_kbps.map(i64::from))
+            .bind(row.bit_depth.map(i64::from))
+            .bind(row.sample_rate.map(i64::from))
+            .bind(&row.cover)
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .wrap_err_with(|| format!("写本地曲库行失败 path={}", row.path))?;
+            sqlx::query("DELETE FROM local_track_artists WHERE path=?")
+                .bind(&row.path)
+                .execute(&mut *tx)
+                .await
+                .wrap_err("清本地曲库艺人失败")?;
+            for (pos, name) in row.artists.iter().enumerate() {
+                sqlx::query("INSERT INTO local_track_artists(path,position,name) VALUES(?,?,?)")
+                    .bind(&row.path)
+                    .bind(i64::try_from(pos)?)
+                    .bind(name)
+                    .execute(&mut *tx)
+                    .await
+                    .wrap_err("写本地曲库艺人失败")?;
+            }
+        }
+        tx.commit().await.wrap_err("提交本地曲库索引事务失败")?;
+        Ok(())
+    }
+
+    /// 记录一次扫描回执(覆盖单例行)。降级静默成功。
+    ///
+    /// # Params:
+    ///   - `record`: 扫描回执
+    ///
+    /// # Return:
+    ///   成功返回 `Ok(())`。
+    pub async fn record_scan(&self, record: &LocalScanRecord) -> color_eyre::Result<()> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(());
+        };
+        let count = |v: u64| i64::try_from(v).unwrap_or(i64::MAX);
+        sqlx::query(
+            "INSERT INTO local_scan_state(id,finished_at,full_scan,tracks,added,updated,removed,elapsed_ms) \
+             VALUES(0,?,?,?,?,?,?,?) \
+             ON CONFLICT(id) DO UPDATE SET \
+               finished_at=excluded.finished_at, full_scan=excluded.full_scan, \
+               tracks=excluded.tracks, added=excluded.added, updated=excluded.updated, \
+               removed=excluded.removed, elapsed_ms=excluded.elapsed_ms",
+        )
+        .bind(record.finished_at_ms)
+        .bind(i64::from(record.full))
+        .bind(count(record.tracks))
+        .bind(count(record.added))
+        .bind(count(record.updated))
+        .bind(count(record.removed))
+        .bind(count(record.elapsed_ms))
+        .execute(pool)
+        .await
+        .wrap_err("写本地曲库扫描回执失败")?;
+        Ok(())
+    }
+
+    /// 索引总览:文件数 / 总字节 / 最近扫描回执。降级返回全零。
+    ///
+    /// # Return:
+    ///   [`LocalLibraryStats`]。
+    pub async fn stats(&self) -> color_eyre::Result<LocalLibraryStats> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(LocalLibraryStats {
+                tracks: 0,
+                bytes: 0,
+                last_scan: None,
+            });
+        };
+        let (tracks, bytes): (i64, i64) =
+            sqlx::query_as("SELECT COUNT(*), COALESCE(SUM(size), 0) FROM local_tracks")
+                .fetch_one(pool)
+                .await
+                .wrap_err("统计本地曲库索引失败")?;
+        let last_scan = sqlx::query_as::<_, ScanDbRow>(
+            "SELECT finished_at, full_scan, tracks, added, updated, removed, elapsed_ms \
+             FROM local_scan_state WHERE id=0",
+        )
+        .fetch_optional(pool)
+        .await
+        .wrap_err("读本地曲库扫描回执失败")?
+        .map(ScanDbRow::into_record);
+        Ok(LocalLibraryStats {
+            tracks: u64::try_from(tracks).unwrap_or(0),
+            bytes: u64::try_from(bytes).unwrap_or(0),
+            last_scan,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalScanRecord, LocalTrackRow};
+    use crate::ServerStore;
+
+    /// 构造一条测试行。
+    fn row(path: &str, artists: &[&str]) -> LocalTrackRow {
+        LocalTrackRow {
+            path: path.to_owned(),
+            mtime_ms: 1_700_000_000_000,
+            size: 1024,
+            title: "t".to_owned(),
+            artists: artists.iter().map(|a| (*a).to_owned()).collect(),
+            album: Some("al".to_owned()),
+            album_artist: None,
+            year: Some(2003),
+            disc: Some(1),
+            track: Some(2),
+            duration_ms: Some(180_000),
+            format: Some("flac".to_owned()),
+            bitrate_kbps: Some(900),
+            bit_depth: Some(24),
+            sample_rate: Some(96_000),
+            cover: None,
+        }
+    }
+
+    /// 写读往返 + 覆盖写重写艺人 + 删除级联艺人。
+    #[tokio::test]
+    async fn apply_roundtrips_and_removes() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let lib = store.local_library();
+        let a = row("/m/a.flac", &["Ada", "Bo"]);
+        let b = row("/m/b.flac", &["Cy"]);
+        lib.apply(&[a.clone(), b.clone()], &[]).await?;
+        assert_eq!(lib.load_all().await?, vec![a.clone(), b]);
+
+        let a2 = LocalTrackRow {
+            mtime_ms: a.mtime_ms + 1,
+            artists: vec!["Bo".to_owned()],
+            ..a
+        };
+        lib.apply(std::slice::from_ref(&a2), &["/m/b.flac".to_owned()])
+            .await?;
+        assert_eq!(lib.load_all().await?, vec![a2], "艺人重写、删行级联");
+        Ok(())
+    }
+
+    /// 总览:计数 / 字节求和 / 扫描回执覆盖写。
+    #[tokio::test]
+    async fn stats_counts_and_last_scan() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let lib = store.local_library();
+        assert_eq!(lib.stats().await?.last_scan, None, "从未扫描");
+        lib.apply(&[row("/m/a.flac", &[]), row("/m/b.flac", &[])], &[])
+            .await?;
+        let record = LocalScanRecord {
+            finished_at_ms: 42,
+            full: true,
+            tracks: 2,
+            added: 2,
+            updated: 0,
+            removed: 0,
+            elapsed_ms: 7,
+        };
+        lib.record_scan(&record).await?;
+        let stats = lib.stats().await?;
+        assert_eq!((stats.tracks, stats.bytes), (2, 2048));
+        assert_eq!(stats.last_scan, Some(record));
+        Ok(())
+    }
+}
@@ -0,0 +1,461 @@
+//! 聚合源自有的混源歌单(`mix_playlists` / `mix_playlist_songs` 表)。
+//!
+//! 远端歌单只装得下本源的歌;这里的歌单由 Mineral 自己持有,曲目可跨源并排。
+//! 曲目只存 `(namespace, song_value)` 引用,读时 join `song_meta` 重建——与聚合收藏
+//! 同口径:缺 meta 的行不出、不计数,补 meta 后自然出现。
+
+use color_eyre::eyre::WrapErr;
+use mineral_log::debug;
+use mineral_model::{Song, SongId, SourceKind};
+use sqlx::{FromRow, Sqlite, Transaction};
+
+use crate::ServerStore;
+use crate::db::rows::{SongMetaRow, assemble_songs};
+use crate::db::time::now_ms;
+
+/// 一张混源歌单的元信息。只读返回 DTO,字段全 `pub`。
+#[derive(Clone, Debug, PartialEq, Eq, FromRow)]
+pub struct MixPlaylistRow {
+    /// 自增主键(歌单 id 的裸值段)。
+    pub id: i64,
+
+    /// 展示名。
+    pub name: String,
+
+    /// 描述(空串 = 无)。
+    pub description: String,
+
+    /// 创建时刻(unix ms)。
+    pub created_at: i64,
+
+    /// 有 meta 的曲目数(与 [`MixPlaylistStore::songs`] 同口径)。
+    pub track_count: i64,
+}
+
+/// 混源歌单存储。
+pub struct MixPlaylistStore {
+    /// 顶层句柄。
+    persist: ServerStore,
+}
+
+/// 元信息查询的公共前缀(曲目数只计 join 到 meta 的行)。
+const SELECT_ROW: &str = "SELECT p.id, p.name, p.description, p.created_at, \
+     (SELECT COUNT(*) FROM mix_playlist_songs s \
+      JOIN song_meta m ON m.namespace = s.namespace AND m.song_value = s.song_value \
+      WHERE s.playlist_id = p.id) AS track_count \
+     FROM mix_playlists p";
+
+impl MixPlaylistStore {
+    /// 构造。
+    ///
+    /// # Params:
+    ///   - `persist`: 顶层句柄
+    pub(crate) fn new(persist: ServerStore) -> Self {
+        Self { persist }
+    }
+
+    /// 全部歌单,按创建时刻升序(同毫秒按 id)。降级返回空。
+    ///
+    /// # Return:
+    ///   全部混源歌单的元信息。
+    pub async fn list(&self) -> color_eyre::Result<Vec<MixPlaylistRow>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(Vec::new());
+        };
+        sqlx::query_as::<_, MixPlaylistRow>(&format!("{SELECT_ROW} ORDER BY p.created_at, p.id"))
+            .fetch_all(pool)
+            .await
+            .wrap_err("读混源歌单列表失败")
+    }
+
+    /// 按 id 取一张歌单的元信息。降级 / 未命中返回 `None`。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///
+    /// # Return:
+    ///   命中的元信息。
+    pub async fn get(&self, id: i64) -> color_eyre::Result<Option<MixPlaylistRow>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(None);
+        };
+        sqlx::query_as::<_, MixPlaylistRow>(&format!("{SELECT_ROW} WHERE p.id = ?"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .wrap_err_with(|| format!("读混源歌单失败 id={id}"))
+    }
+
+    /// 新建一张空歌单(允许重名,与远端歌单一致)。
+    ///
+    /// # Params:
+    ///   - `name`: 歌单名
+    ///
+    /// # Return:
+    ///   新歌单的主键;降级无处落库返回 `None`。
+    pub async fn create(&self, name: &str) -> color_eyre::Result<Option<i64>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(None);
+        };
+        debug!(target: "persist", name, "新建混源歌单");
+        let done = sqlx::query(
+            "INSERT INTO mix_playlists(name, description, created_at) VALUES(?, '', ?)",
+        )
+        .bind(name)
+        .bind(now_ms())
+        .execute(pool)
+        .await
+        .wrap_err_with(|| format!("新建混源歌单失败 name={name}"))?;
+        Ok(Some(done.last_insert_rowid()))
+    }
+
+    /// 删除歌单连同其曲目行。降级 / 不存在静默成功。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    pub async fn delete(&self, id: i64) -> color_eyre::Result<()> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(());
+        };
+        let mut tx = pool.begin().await.wrap_err("开启删混源歌单事务失败")?;
+        sqlx::query("DELETE FROM mix_playlist_songs WHERE playlist_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .wrap_err_with(|| format!("删混源歌单曲目失败 id={id}"))?;
+        sqlx::query("DELETE FROM mix_playlists WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .wrap_err_with(|| format!("删混源歌单失败 id={id}"))?;
+        tx.commit()
+            .await
+            .wrap_err_with(|| format!("提交删混源歌单事务失败 id={id}"))
+    }
+
+    /// 改名。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `name`: 新名
+    ///
+    /// # Return:
+    ///   命中返回 `true`;不存在返回 `false`。
+    pub async fn rename(&self, id: i64, name: &str) -> color_eyre::Result<bool> {
+        self.update(id, "name", name).await
+    }
+
+    /// 改描述。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `description`: 新描述
+    ///
+    /// # Return:
+    ///   命中返回 `true`;不存在返回 `false`。
+    pub async fn set_description(&self, id: i64, description: &str) -> color_eyre::Result<bool> {
+        self.update(id, "description", description).await
+    }
+
+    /// 歌单曲目,按歌单内顺序;缺 meta 的行跳过。降级返回空。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///
+    /// # Return:
+    ///   跨 namespace 的曲目。
+    pub async fn songs(&self, id: i64) -> color_eyre::Result<Vec<Song>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(Vec::new());
+        };
+        let meta_rows = sqlx::query_as::<_, SongMetaRow>(
+            "SELECT m.namespace, m.song_value, m.name, m.alias, m.album_id, m.album_name, \
+             m.duration_ms, m.cover_url \
+             FROM mix_playlist_songs s \
+             JOIN song_meta m ON m.namespace = s.namespace AND m.song_value = s.song_value \
+             WHERE s.playlist_id = ? ORDER BY s.position",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .wrap_err_with(|| format!("查混源歌单曲目失败 id={id}"))?;
+        let artist_rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT a.namespace, a.song_value, a.artist_id, a.artist_name \
+             FROM mix_playlist_songs s \
+             JOIN song_artists a ON a.namespace = s.namespace AND a.song_value = s.song_value \
+             WHERE s.playlist_id = ? ORDER BY a.position",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .wrap_err_with(|| format!("查混源歌单艺人失败 id={id}"))?;
+        assemble_songs(meta_rows, artist_rows)
+    }
+
+    /// 追加歌曲到末尾;已在单里的跳过(不挪位置)。降级视同全部追加。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `songs`: 待追加歌曲(任意 namespace)
+    ///
+    /// # Return:
+    ///   实际新增的行数。
+    pub async fn add_songs(&self, id: i64, songs: &[SongId]) -> color_eyre::Result<u64> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(u64::try_from(songs.len()).unwrap_or(u64::MAX));
+        };
+        let mut tx = pool.begin().await.wrap_err("开启混源歌单加歌事务失败")?;
+        let (mut next,): (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM mix_playlist_songs WHERE playlist_id = ?",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await
+        .wrap_err_with(|| format!("查混源歌单末位失败 id={id}"))?;
+        let now = now_ms();
+        let mut added = 0_u64;
+        for song in songs {
+            let done = sqlx::query(
+                "INSERT INTO mix_playlist_songs \
+                 (playlist_id, position, namespace, song_value, added_at) VALUES(?, ?, ?, ?, ?) \
+                 ON CONFLICT(playlist_id, namespace, song_value) DO NOTHING",
+            )
+            .bind(id)
+            .bind(next)
+            .bind(song.namespace().name())
+            .bind(song.value())
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .wrap_err_with(|| format!("混源歌单加歌失败 id={id} song={}", song.qualified()))?;
+            if done.rows_affected() > 0 {
+                next = next.saturating_add(1);
+                added = added.saturating_add(1);
+            }
+        }
+        tx.commit()
+            .await
+            .wrap_err_with(|| format!("提交混源歌单加歌事务失败 id={id}"))?;
+        Ok(added)
+    }
+
+    /// 移除歌曲并把余下曲目重排为连续位置;不在单里的忽略。降级视同全部移除。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `songs`: 待移除歌曲
+    ///
+    /// # Return:
+    ///   实际移除的行数。
+    pub async fn remove_songs(&self, id: i64, songs: &[SongId]) -> color_eyre::Result<u64> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(u64::try_from(songs.len()).unwrap_or(u64::MAX));
+        };
+        let mut tx = pool.begin().await.wrap_err("开启混源歌单删歌事务失败")?;
+        let mut removed = 0_u64;
+        for song in songs {
+            let done = sqlx::query(
+                "DELETE FROM mix_playlist_songs \
+                 WHERE playlist_id = ? AND namespace = ? AND song_value = ?",
+            )
+            .bind(id)
+            .bind(song.namespace().name())
+            .bind(song.value())
+            .execute(&mut *tx)
+            .await
+            .wrap_err_with(|| format!("混源歌单删歌失败 id={id} song={}", song.qualified()))?;
+            removed = removed.saturating_add(done.rows_affected());
+        }
+        let order = ordered_keys(&mut tx, id).await?;
+        renumber(&mut tx, id, &order).await?;
+        tx.commit()
+            .await
+            .wrap_err_with(|| format!("提交混源歌单删歌事务失败 id={id}"))?;
+        Ok(removed)
+    }
+
+    /// 把一首歌挪到指定位置(越界即放末尾),其余曲目顺延。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `song`: 要挪的歌
+    ///   - `to`: 目标位置(0-based,按挪动后的列表计)
+    ///
+    /// # Return:
+    ///   挪动成功返回 `true`;歌不在单里返回 `false`。
+    pub async fn move_song(&self, id: i64, song: &SongId, to: usize) -> color_eyre::Result<bool> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(true);
+        };
+        let mut tx = pool.begin().await.wrap_err("开启混源歌单挪歌事务失败")?;
+        let mut order = ordered_keys(&mut tx, id).await?;
+        let key = (song.namespace().name().to_owned(), song.value().to_owned());
+        let Some(from) = order.iter().position(|k| *k == key) else {
+            return Ok(false);
+        };
+        let moved = order.remove(from);
+        order.insert(to.min(order.len()), moved);
+        renumber(&mut tx, id, &order).await?;
+        tx.commit()
+            .await
+            .wrap_err_with(|| format!("提交混源歌单挪歌事务失败 id={id}"))?;
+        Ok(true)
+    }
+
+    /// 全部混源歌单里**缺 meta** 的歌 id(去重)。供后台补 meta 任务回填。降级返回空。
+    ///
+    /// # Return:
+    ///   跨 namespace 的缺 meta 曲目 id。
+    pub async fn missing_meta_ids(&self) -> color_eyre::Result<Vec<SongId>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(Vec::new());
+        };
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT DISTINCT s.namespace, s.song_value FROM mix_playlist_songs s \
+             LEFT JOIN song_meta m ON m.namespace = s.namespace AND m.song_value = s.song_value \
+             WHERE m.song_value IS NULL",
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("查混源歌单缺 meta 的行失败")?;
+        Ok(rows
+            .into_iter()
+            .map(|(namespace, value)| SongId::new(SourceKind::from_name(&namespace), value))
+            .collect())
+    }
+
+    /// 单列更新。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单主键
+    ///   - `column`: 列名(我方静态选定,直接拼进 SQL;非外部输入,无注入)
+    ///   - `value`: 新值
+    ///
+    /// # Return:
+    ///   命中返回 `true`;不存在返回 `false`。
+    async fn update(&self, id: i64, column: &'static str, value: &str) -> color_eyre::Result<bool> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(true);
+        };
+        let done = sqlx::query(&format!("UPDATE mix_playlists SET {column}=? WHERE id=?"))
+            .bind(value)
+            .bind(id)
+            .execute(pool)
+            .await
+            .wrap_err_with(|| format!("更新混源歌单失败 id={id} column={column}"))?;
+        Ok(done.rows_affected() > 0)
+    }
+}
+
+/// 事务内读一张歌单的曲目键,按当前位置升序。
+async fn ordered_keys(
+    tx: &mut Transaction<'_, Sqlite>,
+    id: i64,
+) -> color_eyre::Result<Vec<(String, String)>> {
+    sqlx::query_as(
+        "SELECT namespace, song_value FROM mix_playlist_songs \
+         WHERE playlist_id = ? ORDER BY position",
+    )
+    .bind(id)
+    .fetch_all(&mut **tx)
+    .await
+    .wrap_err_with(|| format!("读混源歌单顺序失败 id={id}"))
+}
+
+/// 事务内按给定顺序把位置重写为 `0..n`(位置列无唯一约束,逐行改不会撞键)。
+async fn renumber(
+    tx: &mut Transaction<'_, Sqlite>,
+    id: i64,
+    order: &[(String, String)],
+) -> color_eyre::Result<()> {
+    for (position, (namespace, song_value)) in order.iter().enumerate() {
+        sqlx::query(
+            "UPDATE mix_playlist_songs SET position = ? \
+             WHERE playlist_id = ? AND namespace = ? AND song_value = ?",
+        )
+        .bind(i64::try_from(position)?)
+        .bind(id)
+        .bind(namespace)
+        .bind(song_value)
+        .execute(&mut **tx)
+        .await
+        .wrap_err_with(|| format!("重排混源歌单失败 id={id}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{SongId, SourceKind};
+    use mineral_test::{song, with_name};
+
+    use crate::ServerStore;
+
+    /// 加 / 挪 / 删的往返:跨源并排、重复加跳过、删后位置连续、缺 meta 的行不出但列入补全。
+    #[tokio::test]
+    async fn mixed_sources_roundtrip() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let n1 = with_name(song("n1"), "Palisade");
+        store.scope(SourceKind::NETEASE).upsert_meta(&n1).await?;
+        let mut b1 = with_name(song("b1"), "夜間飛行");
+        b1.id = SongId::new(SourceKind::BILIBILI, "b1");
+        store.scope(SourceKind::BILIBILI).upsert_meta(&b1).await?;
+        let ghost = SongId::new(SourceKind::LOCAL, "ghost");
+
+        let mix = store.mix_playlists();
+        let id = mix
+            .create("混听")
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("启用态应返回新 id"))?;
+        let ids = [n1.id.clone(), ghost.clone(), b1.id.clone()];
+        assert_eq!(mix.add_songs(id, &ids).await?, 3);
+        assert_eq!(
+            mix.add_songs(id, std::slice::from_ref(&n1.id)).await?,
+            0,
+            "重复加跳过"
+        );
+
+        let names =
+            |songs: Vec<mineral_model::Song>| songs.into_iter().map(|s| s.name).collect::<Vec<_>>();
+        assert_eq!(names(mix.songs(id).await?), vec!["Palisade", "夜間飛行"]);
+        assert_eq!(mix.missing_meta_ids().await?, vec![ghost.clone()]);
+
+        assert!(mix.move_song(id, &b1.id, 0).await?);
+        assert_eq!(names(mix.songs(id).await?), vec!["夜間飛行", "Palisade"]);
+        assert!(
+            !mix.move_song(id, &SongId::new(SourceKind::NETEASE, "x"), 0)
+                .await?
+        );
+
+        assert_eq!(mix.remove_songs(id, &[ghost]).await?, 1);
+        assert!(mix.move_song(id, &b1.id, 9).await?, "越界放末尾");
+        assert_eq!(names(mix.songs(id).await?), vec!["Palisade", "夜間飛行"]);
+
+        assert!(mix.rename(id, "通勤").await?);
+        assert!(mix.set_description(id, "早八").await?);
+        let row = mix
+            .get(id)
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("应能按 id 取回"))?;
+        assert_eq!(
+            (row.name.as_str(), row.description.as_str(), row.track_count),
+            ("通勤", "早八", 2)
+        );
+
+        mix.delete(id).await?;
+        assert!(mix.list().await?.is_empty());
+        assert!(mix.songs(id).await?.is_empty(), "曲目行随歌单删除");
+        Ok(())
+    }
+
+    /// 降级句柄:读空、新建无 id。
+    #[tokio::test]
+    async fn disabled_is_noop() -> color_eyre::Result<()> {
+        let mix = ServerStore::disabled().mix_playlists();
+        assert!(mix.create("x").await?.is_none());
+        assert!(mix.list().await?.is_empty());
+        assert!(mix.get(1).await?.is_none());
+        Ok(())
+    }
+}
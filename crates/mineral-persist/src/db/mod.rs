@@ -3,12 +3,18 @@
 pub(crate) mod schema;
 
 mod envelope;
+mod local_library;
+mod mix_playlist;
 mod namespace;
 pub(crate) mod rows;
 mod session;
+mod smart_playlist;
 mod song_kv;
 mod time;
 
+pub use local_library::{LocalLibraryStats, LocalLibraryStore, LocalScanRecord, LocalTrackRow};
+pub use mix_playlist::{MixPlaylistRow, MixPlaylistStore};
 pub use namespace::{HistoryEntry, NamespaceStore, PlaylistCacheEntry, SongStats};
 pub use session::{SessionSnapshot, SessionStore};
+pub use smart_playlist::{SmartPlaylistRow, SmartPlaylistStore};
 pub use song_kv::RESERVED_KEYS;
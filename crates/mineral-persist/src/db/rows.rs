@@ -3,6 +3,7 @@
 use std::str::FromStr;
 
 use mineral_model::{AlbumId, AlbumRef, ArtistId, ArtistRef, MediaUrl, Song, SongId, SourceKind};
+use rustc_hash::FxHashMap;
 use sqlx::FromRow;
 
 /// `song_meta` 行。
@@ -87,3 +88,34 @@ impl SongMetaRow {
             .build())
     }
 }
+
+/// 按 meta 行的顺序把跨源的 meta 行与艺人行拼回 [`Song`] 列表。
+///
+/// # Params:
+///   - `meta_rows`: meta 行(顺序即输出顺序)
+///   - `artist_rows`: `(namespace, song_value, artist_id, artist_name)`,调用方按 `position` 升序给
+///
+/// # Return:
+///   与 `meta_rows` 同序的歌曲;任一行重建失败即报错。
+pub(crate) fn assemble_songs(
+    meta_rows: Vec<SongMetaRow>,
+    artist_rows: Vec<(String, String, String, String)>,
+) -> color_eyre::Result<Vec<Song>> {
+    let mut artists_by_song = FxHashMap::<(String, String), Vec<SongArtistRow>>::default();
+    for (namespace, song_value, artist_id, artist_name) in artist_rows {
+        artists_by_song
+            .entry((namespace, song_value))
+            .or_default()
+            .push(SongArtistRow {
+                artist_id,
+                artist_name,
+            });
+    }
+    meta_rows
+        .into_iter()
+        .map(|row| {
+            let key = (row.namespace.clone(), row.song_value.clone());
+            row.into_song(artists_by_song.remove(&key).unwrap_or_default())
+        })
+        .collect()
+}
@@ -0,0 +1,214 @@
+//! 用户自建智能歌单的定义(`smart_playlists` 表)。
+//!
+//! 只存定义(名字 + 筛选表达式原文),不存结果:曲目由聚合 channel 每次按表达式对
+//! persist / stats 现算。config.lua 里声明的智能歌单不经本表。
+
+use color_eyre::eyre::WrapErr;
+use mineral_log::debug;
+use sqlx::FromRow;
+
+use crate::ServerStore;
+use crate::db::time::now_ms;
+
+/// 一条智能歌单定义。只读返回 DTO,字段全 `pub`。
+#[derive(Clone, Debug, PartialEq, Eq, FromRow)]
+pub struct SmartPlaylistRow {
+    /// 稳定键(建单时的名字;歌单 id 的裸值段,改名不变)。
+    pub key: String,
+
+    /// 展示名。
+    pub name: String,
+
+    /// 筛选表达式原文(空串 = 尚未设置)。
+    pub query: String,
+
+    /// 创建时刻(unix ms)。
+    pub created_at: i64,
+}
+
+/// 智能歌单定义存储。
+pub struct SmartPlaylistStore {
+    /// 顶层句柄。
+    persist: ServerStore,
+}
+
+impl SmartPlaylistStore {
+    /// 构造。
+    ///
+    /// # Params:
+    ///   - `persist`: 顶层句柄
+    pub(crate) fn new(persist: ServerStore) -> Self {
+        Self { persist }
+    }
+
+    /// 全部定义,按创建时刻升序(同毫秒按 key)。降级返回空。
+    ///
+    /// # Return:
+    ///   全部智能歌单定义。
+    pub async fn list(&self) -> color_eyre::Result<Vec<SmartPlaylistRow>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(Vec::new());
+        };
+        sqlx::query_as::<_, SmartPlaylistRow>(
+            "SELECT key, name, query, created_at FROM smart_playlists ORDER BY created_at, key",
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("读智能歌单定义失败")
+    }
+
+    /// 按键取一条定义。降级 / 未命中返回 `None`。
+    ///
+    /// # Params:
+    ///   - `key`: 稳定键
+    ///
+    /// # Return:
+    ///   命中的定义。
+    pub async fn get(&self, key: &str) -> color_eyre::Result<Option<SmartPlaylistRow>> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(None);
+        };
+        sqlx::query_as::<_, SmartPlaylistRow>(
+            "SELECT key, name, query, created_at FROM smart_playlists WHERE key=?",
+        )
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+        .wrap_err_with(|| format!("读智能歌单定义失败 key={key}"))
+    }
+
+    /// 新建一条定义(名字即键,表达式留空待设)。键已存在不覆盖。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `name`: 歌单名(同时作稳定键)
+    ///
+    /// # Return:
+    ///   新插入返回 `true`;同名已存在返回 `false`。
+    pub async fn create(&self, name: &str) -> color_eyre::Result<bool> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(true);
+        };
+        debug!(target: "persist", name, "新建智能歌单");
+        let done = sqlx::query(
+            "INSERT INTO smart_playlists(key, name, query, created_at) VALUES(?, ?, '', ?) \
+             ON CONFLICT(key) DO NOTHING",
+        )
+        .bind(name)
+        .bind(name)
+        .bind(now_ms())
+        .execute(pool)
+        .await
+        .wrap_err_with(|| format!("新建智能歌单失败 name={name}"))?;
+        Ok(done.rows_affected() > 0)
+    }
+
+    /// 删除一条定义。降级 / 不存在静默成功。
+    ///
+    /// # Params:
+    ///   - `key`: 稳定键
+    pub async fn delete(&self, key: &str) -> color_eyre::Result<()> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(());
+        };
+        sqlx::query("DELETE FROM smart_playlists WHERE key=?")
+            .bind(key)
+            .execute(pool)
+            .await
+            .wrap_err_with(|| format!("删智能歌单失败 key={key}"))?;
+        Ok(())
+    }
+
+    /// 改展示名(键不变)。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `key`: 稳定键
+    ///   - `name`: 新名
+    ///
+    /// # Return:
+    ///   命中返回 `true`;键不存在返回 `false`。
+    pub async fn rename(&self, key: &str, name: &str) -> color_eyre::Result<bool> {
+        self.update(key, "name", name).await
+    }
+
+    /// 改筛选表达式原文(合法性由调用方先行校验)。降级视同成功。
+    ///
+    /// # Params:
+    ///   - `key`: 稳定键
+    ///   - `query`: 表达式原文
+    ///
+    /// # Return:
+    ///   命中返回 `true`;键不存在返回 `false`。
+    pub async fn set_query(&self, key: &str, query: &str) -> color_eyre::Result<bool> {
+        self.update(key, "query", query).await
+    }
+
+    /// 单列更新。
+    ///
+    /// # Params:
+    ///   - `key`: 稳定键
+    ///   - `column`: 列名(我方静态选定,直接拼进 SQL;非外部输入,无注入)
+    ///   - `value`: 新值
+    ///
+    /// # Return:
+    ///   命中返回 `true`;键不存在返回 `false`。
+    async fn update(
+        &self,
+        key: &str,
+        column: &'static str,
+        value: &str,
+    ) -> color_eyre::Result<bool> {
+        let Some(pool) = self.persist.pool() else {
+            return Ok(true);
+        };
+        let done = sqlx::query(&format!(
+            "UPDATE smart_playlists SET {column}=? WHERE key=?"
+        ))
+        .bind(value)
+        .bind(key)
+        .execute(pool)
+        .await
+        .wrap_err_with(|| format!("更新智能歌单失败 key={key} column={column}"))?;
+        Ok(done.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ServerStore;
+
+    /// 建 / 改名 / 改表达式 / 删的往返;同名重建不覆盖,改名不动键。
+    #[tokio::test]
+    async fn create_update_delete_roundtrip() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = ServerStore::open(&dir.path().join("t.db")).await?;
+        let smart = store.smart_playlists();
+        assert!(smart.create("冷宫").await?);
+        assert!(!smart.create("冷宫").await?, "同名不覆盖");
+        assert!(smart.set_query("冷宫", "loved unplayed:90d").await?);
+        assert!(smart.rename("冷宫", "久未听的收藏").await?);
+        assert!(!smart.rename("nope", "x").await?, "键不存在报未命中");
+
+        let rows = smart.list().await?;
+        assert_eq!(rows.len(), 1);
+        let row = smart
+            .get("冷宫")
+            .await?
+            .ok_or_else(|| color_eyre::eyre::eyre!("应能按键取回"))?;
+        assert_eq!(row.name, "久未听的收藏", "改名只动 name");
+        assert_eq!(row.query, "loved unplayed:90d");
+
+        smart.delete("冷宫").await?;
+        assert!(smart.list().await?.is_empty());
+        Ok(())
+    }
+
+    /// 降级句柄:读空、写视同成功。
+    #[tokio::test]
+    async fn disabled_is_noop() -> color_eyre::Result<()> {
+        let smart = ServerStore::disabled().smart_playlists();
+        assert!(smart.create("x").await?);
+        assert!(smart.list().await?.is_empty());
+        assert!(smart.get("x").await?.is_none());
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 //! Mineral 本地持久化层:server / client 各自的 sqlite 库门面 + 通用文件缓存索引原语。
 //!
 //! - [`ServerStore`]:daemon 的库(`mineral.db`)——结构态(歌元数据 / 统计 / 历史 / 歌单缓存 /
-//!   会话)+ 它名下的音频缓存索引表(`audio_cache`)。下载导出**不走索引**:导出目录本身即真相
-//!   (见 server 的播放解析),持久层不再为它存表。
+//!   会话)+ 它名下的两张文件索引表:音频缓存(`audio_cache`,LRU)与下载导出
+//!   (`download_exports`,不驱逐;导出路径由用户模板决定,靠它按歌反查)。
 //! - [`ClientStore`]:TUI 客户端的库(`tui.db`)——封面缓存索引(`cover_cache`)、
 //!   UI 偏好(`ui_prefs`)与歌单内光标位置记忆(`track_pos`)。
 //! - [`CacheIndex`]:表级原语(内存镜像 sync 读 + 写穿透),由上面两个库门面取得,二者共用。
@@ -16,7 +16,8 @@ mod server_store;
 pub use cache_index::{CacheEntryStat, CacheIndex, CacheStats, Evicted};
 pub use client_store::{ClientStore, TrackPosRow};
 pub use db::{
-    HistoryEntry, NamespaceStore, PlaylistCacheEntry, RESERVED_KEYS, SessionSnapshot, SessionStore,
-    SongStats,
+    HistoryEntry, LocalLibraryStats, LocalLibraryStore, LocalScanRecord, LocalTrackRow,
+    MixPlaylistRow, MixPlaylistStore, NamespaceStore, PlaylistCacheEntry, RESERVED_KEYS,
+    SessionSnapshot, SessionStore, SmartPlaylistRow, SmartPlaylistStore, SongStats,
 };
 pub use server_store::{PlaylistCacheStats, ServerStore};
@@ -5,13 +5,14 @@ use std::sync::Arc;
 use color_eyre::eyre::WrapErr;
 use mineral_log::{info, warn};
 use mineral_model::{Song, SongId, SourceKind};
-use rustc_hash::FxHashMap;
 use sqlx::SqlitePool;
 
 use crate::CacheIndex;
-use crate::db::rows::{SongArtistRow, SongMetaRow};
+use crate::db::rows::{SongMetaRow, assemble_songs};
 use crate::db::schema::ensure_schema;
-use crate::db::{NamespaceStore, SessionStore};
+use crate::db::{
+    LocalLibraryStore, MixPlaylistStore, NamespaceStore, SessionStore, SmartPlaylistStore,
+};
 
 /// 持久化服务句柄。廉价 clone(内部 `Arc`)。
 ///
@@ -99,6 +100,30 @@ impl ServerStore {
         SessionStore::new(self.clone())
     }
 
+    /// 取本地曲库索引存储(local channel 增量扫描用)。
+    ///
+    /// # Return:
+    ///   [`LocalLibraryStore`]。
+    pub fn local_library(&self) -> LocalLibraryStore {
+        LocalLibraryStore::new(self.clone())
+    }
+
+    /// 取用户自建智能歌单的定义存储(聚合 channel 用)。
+    ///
+    /// # Return:
+    ///   [`SmartPlaylistStore`]。
+    pub fn smart_playlists(&self) -> SmartPlaylistStore {
+        SmartPlaylistStore::new(self.clone())
+    }
+
+    /// 取聚合源自有的混源歌单存储(聚合 channel 用)。
+    ///
+    /// # Return:
+    ///   [`MixPlaylistStore`]。
+    pub fn mix_playlists(&self) -> MixPlaylistStore {
+        MixPlaylistStore::new(self.clone())
+    }
+
     /// 音频本体缓存索引(`audio_cache` 表,LRU 驱逐)。播放命中本地副本走它。
     ///
     /// # Params:
@@ -118,6 +143,24 @@ impl ServerStore {
         }
     }
 
+    /// 下载导出索引(`download_exports` 表,不驱逐):歌曲 + 音质 → 导出文件。导出路径由用户模板
+    /// 决定、无法从歌曲反推,播放命中与「已下载」判断都查它。
+    ///
+    /// # Params:
+    ///   - `root`: 导出根目录(`relpath` 相对它)
+    ///
+    /// # Return:
+    ///   就绪索引;降级句柄返回 [`CacheIndex::disabled`];建表 / 载入失败返回 `Err`。
+    pub async fn download_exports(
+        &self,
+        root: std::path::PathBuf,
+    ) -> color_eyre::Result<CacheIndex> {
+        match self.pool() {
+            Some(pool) => CacheIndex::open(pool.clone(), "download_exports", root, None).await,
+            None => Ok(CacheIndex::disabled()),
+        }
+    }
+
     /// 全部源的 loved 歌曲(join meta 重建),按 `loved_at` 降序(最新收藏在顶),
     /// 同毫秒收藏以 `(namespace, song_value)` 破平局,顺序稳定不随库文件重排。
     ///
@@ -153,24 +196,7 @@ impl ServerStore {
         .fetch_all(pool)
         .await
         .wrap_err("查跨源 loved 艺人失败")?;
-        let mut artists_by_song = FxHashMap::<(String, String), Vec<SongArtistRow>>::default();
-        for (namespace, song_value, artist_id, artist_name) in artist_rows {
-            artists_by_song
-                .entry((namespace, song_value))
-                .or_default()
-                .push(SongArtistRow {
-                    artist_id,
-                    artist_name,
-                });
-        }
-
-        meta_rows
-            .into_iter()
-            .map(|row| {
-                let key = (row.namespace.clone(), row.song_value.clone());
-                row.into_song(artists_by_song.remove(&key).unwrap_or_default())
-            })
-            .collect()
+        assemble_songs(meta_rows, artist_rows)
     }
 
     /// 跨源 loved 歌曲计数,与 [`Self::loved_songs`] **严格同口径**(只计 join 到 meta 的
@@ -25,6 +25,7 @@ impl CancelFilter {
             }
             // 写操作不可批量取消:开跑后远端可能已执行,中途砍只会脱节
             (Self::ChannelFetchKinds(_), TaskKind::PlaylistWrite(_)) => false,
+            (Self::ChannelFetchKinds(_), TaskKind::LocalScan { .. }) => false,
         }
     }
 }
@@ -8,7 +8,7 @@ use futures_util::{SinkExt, StreamExt};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use tokio::io::{AsyncRead, AsyncWrite};
-use tokio_util::codec::{Decoder, Framed as TokioFramed, LengthDelimitedCodec};
+use tokio_util::codec::{Decoder, Framed as TokioFramed, FramedParts, LengthDelimitedCodec};
 
 /// 带 length-delimited framing 的双向流。包 [`tokio::net::UnixStream`] 或测试用的
 /// `tokio::io::DuplexStream` 都可以。
@@ -19,6 +19,18 @@ pub fn framed<T: AsyncRead + AsyncWrite>(stream: T) -> Framed<T> {
     LengthDelimitedCodec::new().framed(stream)
 }
 
+/// 同 [`framed`],但连接开头已被读走的字节先垫回读缓冲(server 读首字节选 codec
+/// 后用,见 [`crate::is_json_lead`])。
+///
+/// # Params:
+///   - `stream`: 已连接的双向流
+///   - `prefix`: 已从 `stream` 读走的开头字节
+pub fn framed_with_prefix<T: AsyncRead + AsyncWrite>(stream: T, prefix: &[u8]) -> Framed<T> {
+    let mut parts = FramedParts::new::<Bytes>(stream, LengthDelimitedCodec::new());
+    parts.read_buf.extend_from_slice(prefix);
+    TokioFramed::from_parts(parts)
+}
+
 /// 把消息编码成一帧负载字节(单遍 `serialize_into`,不含长度前缀——那是
 /// [`Framed`] 的事)。`Framed` 被 split 成 sink/stream 两半后无法走 [`send`],
 /// 两端的 writer/reader task 用本函数 + [`decode`] 手动过 codec。
@@ -0,0 +1,224 @@
+//! JSON-lines 传输:给非 Rust client(Python / shell 小部件)用的第二种 codec。
+//!
+//! 与 bincode 共用同一个 socket,server 按连接首字节分流:bincode 帧以 4 字节 BE
+//! 长度前缀开头(帧上限远小于 16 MiB,首字节恒为 0),JSON 行以 `{` 开头。
+//!
+//! 一行一个 JSON 值(UTF-8,`\n` 结尾,空行忽略):
+//! 1. client 先发 [`JsonHandshake`](schema 版本 + 订阅集);
+//! 2. server 回 [`JsonHello`],`accepted == false` 时随即关连接;
+//! 3. 之后每行一个 [`Frame`](只用 `Request` / `Response` / `Event`),形状即 serde
+//!    默认的 externally tagged 表示,与 bincode 走同一套 derive。
+//!
+//! 版本守门看 [`JSON_SCHEMA_VERSION`] 而非包版本:JSON 形状不随发版漂移,只在 wire
+//! 类型发生不兼容改动时手动 bump(形状钉子见 `tests/jsonl.rs`)。
+
+use color_eyre::eyre::WrapErr;
+use futures_util::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{Decoder, Framed as TokioFramed, FramedParts, LinesCodec};
+
+use crate::frame::{Frame, RequestId};
+use crate::handshake::{ClientInfo, PkgVersion, RejectReason, Subscription};
+use crate::message::Response;
+
+/// JSON-lines 协议的 schema 版本。两端相等才互通;wire 类型的 JSON 形状发生
+/// 不兼容改动(改名 / 删字段 / 加无默认值的必填字段)时 +1,纯新增变体不动。
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// 单行上限(与 bincode 侧 length-delimited 的默认帧上限同量级)。
+const MAX_LINE_BYTES: usize = 8 * 1024 * 1024;
+
+/// 按行切分的双向流(行内容为一个 JSON 值,不含换行)。
+pub type JsonFramed<T> = TokioFramed<T, LinesCodec>;
+
+/// client → server 握手首行(先于任何 [`Frame::Request`](crate::Frame::Request))。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonHandshake {
+    /// client 实现所依的 schema 版本(应为 [`JSON_SCHEMA_VERSION`])。
+    pub schema: u32,
+
+    /// client 实现的自报名(同 [`ClientInfo::name`],只作观测归属)。
+    pub name: String,
+
+    /// 期望接收的推送类别;缺省为空集(一次性脚本不收推送)。
+    #[serde(default)]
+    pub subscriptions: Vec<Subscription>,
+}
+
+impl JsonHandshake {
+    /// 以本端 schema 版本构造握手行。
+    ///
+    /// # Params:
+    ///   - `name`: client 实现的自报名
+    ///   - `subscriptions`: 期望接收的推送类别
+    #[must_use]
+    pub fn new(name: &str, subscriptions: Vec<Subscription>) -> Self {
+        Self {
+            schema: JSON_SCHEMA_VERSION,
+            name: name.to_owned(),
+            subscriptions,
+        }
+    }
+
+    /// 对端 schema 与本端是否互通(server 侧守门判定)。
+    #[must_use]
+    pub fn schema_matches(&self) -> bool {
+        self.schema == JSON_SCHEMA_VERSION
+    }
+
+    /// 折成连接注册表用的身份。JSON client 不报包版本,按本端补齐——守门
+    /// 已由 schema 完成,版本字段只剩观测意义。
+    #[must_use]
+    pub fn into_client_info(self) -> ClientInfo {
+        ClientInfo::new(&self.name, self.subscriptions)
+    }
+}
+
+/// server → client 握手应答行。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonHello {
+    /// 是否接受本连接。
+    pub accepted: bool,
+
+    /// 拒绝原因(schema 不符报 `VersionMismatch`);`accepted == true` 时恒 `None`。
+    pub reason: Option<RejectReason>,
+
+    /// server 的 schema 版本(错配时 client 据此提示)。
+    pub schema: u32,
+
+    /// server 包版本(`0.4.2` 形,仅供人读,不参与守门)。
+    pub server_version: String,
+}
+
+impl JsonHello {
+    /// 接受连接的应答。
+    #[must_use]
+    pub fn accept() -> Self {
+        Self {
+            accepted: true,
+            reason: None,
+            schema: JSON_SCHEMA_VERSION,
+            server_version: PkgVersion::current().to_string(),
+        }
+    }
+
+    /// 拒绝连接的应答。
+    ///
+    /// # Params:
+    ///   - `reason`: 拒绝原因
+    #[must_use]
+    pub fn reject(reason: RejectReason) -> Self {
+        Self {
+            accepted: false,
+            reason: Some(reason),
+            ..Self::accept()
+        }
+    }
+}
+
+/// 连接首字节是否为 JSON-lines 协议(`{` 或空白;bincode 帧首字节恒为 0)。
+///
+/// # Params:
+///   - `byte`: 连接上读到的第一个字节(随后经 `*_with_prefix` 垫回 codec)
+#[must_use]
+pub fn is_json_lead(byte: u8) -> bool {
+    byte == b'{' || byte.is_ascii_whitespace()
+}
+
+/// 用按行 codec 包一个 stream。
+pub fn json_framed<T: AsyncRead + AsyncWrite>(stream: T) -> JsonFramed<T> {
+    LinesCodec::new_with_max_length(MAX_LINE_BYTES).framed(stream)
+}
+
+/// 同 [`json_framed`],已读走的开头字节先垫回读缓冲(同 [`crate::framed_with_prefix`])。
+///
+/// # Params:
+///   - `stream`: 已连接的双向流
+///   - `prefix`: 已从 `stream` 读走的开头字节
+pub fn json_framed_with_prefix<T: AsyncRead + AsyncWrite>(
+    stream: T,
+    prefix: &[u8],
+) -> JsonFramed<T> {
+    let mut parts =
+        FramedParts::new::<String>(stream, LinesCodec::new_with_max_length(MAX_LINE_BYTES));
+    parts.read_buf.extend_from_slice(prefix);
+    TokioFramed::from_parts(parts)
+}
+
+/// 把消息编码成一行(不含换行——那是 [`JsonFramed`] 的事)。split 后的两半
+/// 用本函数 + [`decode_json`] 手动过 codec(同 bincode 侧 [`crate::encode`])。
+///
+/// # Errors
+/// JSON 序列化失败。
+pub fn encode_json<T: Serialize>(msg: &T) -> color_eyre::Result<String> {
+    serde_json::to_string(msg).wrap_err("json encode")
+}
+
+/// 从一行解码消息([`encode_json`] 的对偶)。
+///
+/// # Errors
+/// JSON 反序列化失败。
+pub fn decode_json<T: DeserializeOwned>(line: &str) -> color_eyre::Result<T> {
+    serde_json::from_str(line).wrap_err("json decode")
+}
+
+/// server 侧解码一行 [`Frame`];解不出时不断连,而是给出回给 client 的错误应答帧。
+///
+/// 手拼 JSON 的 client 写错一行是常态,断连会连带丢掉在途请求与订阅。应答 id 尽力
+/// 从行里的 `Request.id` 认出(行是合法 JSON 只是形状不对时),认不出用 `0`。
+///
+/// # Params:
+///   - `line`: 一行(不含换行,非空)
+///
+/// # Errors
+/// 解码失败:`Err` 里是 `Frame::Response { resp: Response::Error(..) }`,原样写回即可。
+pub fn decode_json_frame(line: &str) -> Result<Frame, Box<Frame>> {
+    decode_json::<Frame>(line).map_err(|e| {
+        let id = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|v| v.pointer("/Request/id")?.as_u64())
+            .unwrap_or(0);
+        Box::new(Frame::Response {
+            id: RequestId::new(id),
+            resp: Box::new(Response::Error(format!(
+                "malformed frame: {}",
+                e.root_cause()
+            ))),
+        })
+    })
+}
+
+/// 把一条消息编码成一行发出去。
+///
+/// # Errors
+/// JSON 序列化失败 / 写 stream 失败。
+pub async fn send_json<T, S>(stream: &mut JsonFramed<S>, msg: &T) -> color_eyre::Result<()>
+where
+    T: Serialize,
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream
+        .send(encode_json(msg)?)
+        .await
+        .wrap_err("json line send")
+}
+
+/// 收一条消息(跳过空行)。
+///
+/// # Errors
+/// stream 关闭返回 `Ok(None)`(EOF);其它 I/O 错误 / 超长行 / 解码错误返回 `Err`。
+pub async fn recv_json<T, S>(stream: &mut JsonFramed<S>) -> color_eyre::Result<Option<T>>
+where
+    T: DeserializeOwned,
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    while let Some(line) = stream.next().await {
+        let line = line.wrap_err("json line recv")?;
+        if !line.trim().is_empty() {
+            return Ok(Some(decode_json(&line)?));
+        }
+    }
+    Ok(None)
+}
@@ -12,12 +12,16 @@
 //! - **版本守门**: 无协商 —— 两端包版本([`PkgVersion`])相等才互通,
 //!   错配回 `Hello { accepted: false }`,client 提示重启 daemon。
 //! - **错误**: server 端处理异常用 [`Response::Error`] 兜底;不再额外的 Status code
+//! - **JSON-lines**: 同一 socket 上的第二种 codec(server 按首字节分流),给非 Rust
+//!   client 用;握手换成 [`JsonHandshake`] / [`JsonHello`],以 [`JSON_SCHEMA_VERSION`]
+//!   守门而非包版本,之后同样收发 [`Frame`](详见 `docs/ipc.md`)
 
 mod cancel;
 mod codec;
 mod event;
 mod frame;
 mod handshake;
+mod jsonl;
 mod key;
 mod message;
 mod oneshot;
@@ -26,7 +30,7 @@ mod queue_edit;
 mod store;
 
 pub use cancel::CancelFilter;
-pub use codec::{Framed, decode, encode, framed, recv, send};
+pub use codec::{Framed, decode, encode, framed, framed_with_prefix, recv, send};
 pub use event::{
     BusValue, Event, FinishReason, PropName, PropValue, SpanAlign, SpanFg, TextSpan, ToastKind,
 };
@@ -34,6 +38,10 @@ pub use frame::{Frame, RequestId};
 pub use handshake::{
     ClientInfo, PkgVersion, RejectReason, ServerHello, Subscription, client_handshake,
 };
+pub use jsonl::{
+    JSON_SCHEMA_VERSION, JsonFramed, JsonHandshake, JsonHello, decode_json, decode_json_frame,
+    encode_json, is_json_lead, json_framed, json_framed_with_prefix, recv_json, send_json,
+};
 pub use key::{KeyContext, PlaylistRef, ScriptBind, ViewKind};
 pub use message::{
     CopyTemplateCtx, DownloadProgress, DownloadTarget, QueueContextWire, Request, Response,
@@ -3,7 +3,7 @@
 //! 与 [`mineral_server::ClientHandle`] 的方法 1:1 对应;`Response` 的 variant 由
 //! 调用方根据自己发的 `Request` 决定预期。错误统一走 [`Response::Error`]。
 
-use mineral_audio::AudioSnapshot;
+use mineral_audio::{AudioSnapshot, OutputDevice, SpeedMode};
 use mineral_model::{AlbumId, ArtistId, MediaUrl, PlaylistId, Song, SongId};
 use mineral_task::{Priority, Snapshot, TaskId, TaskKind};
 use serde::{Deserialize, Serialize};
@@ -148,9 +148,28 @@ pub enum Request {
     /// 设置音量百分比(0..=100)。
     SetVolume(u8),
 
+    /// 按名切换均衡预设(`None` = 旁路);预设未定义时回 [`Response::Error`]。
+    SetEq(Option<String>),
+
+    /// 设播放倍速(越界钳进 0.5..=3),跨曲保持。快照位置 / 时长仍是曲内媒体时间。
+    SetSpeed {
+        /// 倍速(1 = 原速)。
+        speed: f32,
+
+        /// 变速方式(时间伸缩保持音高 / 变调变速)。
+        mode: SpeedMode,
+    },
+
     /// 拉一次音频快照。返回 [`Response::AudioSnapshot`]。
     AudioSnapshot,
 
+    /// 枚举可用输出设备(标注当前所用的一个)。返回 [`Response::AudioDevices`]。
+    AudioDevices,
+
+    /// 钉住输出设备并热切换,播放位置不变(`None` = 跟随默认设备);设备名不存在时回
+    /// [`Response::Error`]。
+    SetAudioDevice(Option<String>),
+
     // ---- 任务调度 ----
     /// 提交一个任务。返回 [`Response::TaskId`]。
     SubmitTask(TaskKind, Priority),
@@ -221,6 +240,21 @@ pub enum Request {
     /// `n` 键:按当前 mode 切下一首。返回 [`Response::Ok`]。
     NextSong,
 
+    /// 在播曲的歌词时间偏移增减 `delta` 毫秒(正 = 歌词推迟),按 per-song 持久;
+    /// 无在播曲时回 [`Response::Error`]。新偏移随 [`crate::CurrentSync`] 送达。
+    NudgeLyricOffset(i64),
+
+    /// 把一份 LRC 文本存为某曲的歌词覆盖(歌词覆盖目录 `<来源>/<歌曲 id>.lrc`),
+    /// 此后播该曲先于其他歌词;若正在播即刻换上。返回 [`Response::Ok`];
+    /// 写盘失败 / 没有覆盖目录回 [`Response::Error`]。
+    SaveLyrics {
+        /// 目标曲。
+        song_id: SongId,
+
+        /// LRC 文本(TUI 歌词编辑器打轴产出)。
+        lrc: String,
+    },
+
     /// 版本门控的播放状态同步:client 报自己已有的版本号(0 = 一无所有),
     /// server 仅在版本落后时附带对应重段。启动与每 tick 同一条路径。
     /// 返回 [`Response::PlayerSync`]。
@@ -354,6 +388,9 @@ pub enum Response {
     /// 对应 [`Request::AudioSnapshot`]。
     AudioSnapshot(AudioSnapshot),
 
+    /// 对应 [`Request::AudioDevices`]。
+    AudioDevices(Vec<OutputDevice>),
+
     /// 对应 [`Request::SubmitTask`]。
     TaskId(TaskId),
 
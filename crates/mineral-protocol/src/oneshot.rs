@@ -1,9 +1,12 @@
 //! 一次性 client:连接 + 握手 + 串行 request/response 配对的最小封装。
 //!
-//! 给 CLI 一次性命令(`mineral status` 等)用 —— 不订阅任何推送、不起后台
-//! worker,发一条等一条;间隙里交错下来的 [`Frame::Event`] 直接跳过。
-//! 长连接交互式 client(TUI)不用它,走自己的 worker(id 配对 + event 通道)。
+//! 给 CLI 一次性命令(`mineral status` 等)用 —— 不起后台 worker,发一条等一条。
+//! 默认订空集;要等推送结果的命令(`mineral search` 等任务型请求)握手时带订阅集,
+//! 等应答间隙里交错下来的 [`Frame::Event`] 暂存,由 [`OneshotClient::next_event`]
+//! 按到达顺序取走。长连接交互式 client(TUI)不用它,走自己的 worker
+//! (id 配对 + event 通道)。
 
+use std::collections::VecDeque;
 use std::path::Path;
 
 use color_eyre::eyre::{WrapErr, bail};
@@ -11,8 +14,9 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::UnixStream;
 
 use crate::codec::{Framed, framed, recv, send};
+use crate::event::Event;
 use crate::frame::{Frame, RequestId};
-use crate::handshake::ClientInfo;
+use crate::handshake::{ClientInfo, Subscription};
 use crate::message::{Request, Response};
 
 /// 本类型握手自报的 client 名:oneshot 即「CLI 一次性命令」的封装(见模块
@@ -27,6 +31,9 @@ pub struct OneshotClient<S = UnixStream> {
 
     /// 下一个请求 id(自增)。
     next_id: u64,
+
+    /// 等应答时交错到达、尚未取走的推送(订空集时恒空)。
+    pending: VecDeque<Event>,
 }
 
 impl OneshotClient<UnixStream> {
@@ -41,13 +48,31 @@ impl OneshotClient<UnixStream> {
     /// # Errors
     /// 连接失败 / 握手被拒(busy、版本不匹配 —— 错误信息已是人话提示)。
     pub async fn connect(socket_path: &Path) -> color_eyre::Result<Self> {
+        Self::connect_subscribed(socket_path, Vec::new()).await
+    }
+
+    /// 连接 daemon socket 并带订阅集完成握手(推送经 [`Self::next_event`] 取)。
+    ///
+    /// # Params:
+    ///   - `socket_path`: daemon 的 unix socket 路径
+    ///   - `subscriptions`: 期望接收的推送类别
+    ///
+    /// # Return:
+    ///   已可发请求的 client。
+    ///
+    /// # Errors
+    /// 连接失败 / 握手被拒(同 [`Self::connect`])。
+    pub async fn connect_subscribed(
+        socket_path: &Path,
+        subscriptions: Vec<Subscription>,
+    ) -> color_eyre::Result<Self> {
         let stream = UnixStream::connect(socket_path).await.wrap_err_with(|| {
             format!(
                 "connect daemon socket {} (run `mineral serve` first?)",
                 socket_path.display()
             )
         })?;
-        Self::from_stream(stream).await
+        Self::from_stream_subscribed(stream, subscriptions).await
     }
 }
 
@@ -60,13 +85,32 @@ impl<S: AsyncRead + AsyncWrite + Unpin> OneshotClient<S> {
     /// # Errors
     /// 握手被拒 / 对端没回 [`Frame::Hello`] / 连接被关。
     pub async fn from_stream(stream: S) -> color_eyre::Result<Self> {
+        Self::from_stream_subscribed(stream, Vec::new()).await
+    }
+
+    /// 在已建立的双向流上带订阅集完成握手。
+    ///
+    /// # Params:
+    ///   - `stream`: 已连接的双向流
+    ///   - `subscriptions`: 期望接收的推送类别
+    ///
+    /// # Errors
+    /// 握手被拒 / 对端没回 [`Frame::Hello`] / 连接被关。
+    pub async fn from_stream_subscribed(
+        stream: S,
+        subscriptions: Vec<Subscription>,
+    ) -> color_eyre::Result<Self> {
         let mut conn = framed(stream);
-        crate::handshake::client_handshake(&mut conn, ClientInfo::new(CLIENT_NAME, Vec::new()))
+        crate::handshake::client_handshake(&mut conn, ClientInfo::new(CLIENT_NAME, subscriptions))
             .await?;
-        Ok(Self { conn, next_id: 0 })
+        Ok(Self {
+            conn,
+            next_id: 0,
+            pending: VecDeque::new(),
+        })
     }
 
-    /// 发一条请求并等待**配对**的应答;间隙里交错的 [`Frame::Event`] 跳过。
+    /// 发一条请求并等待**配对**的应答;间隙里交错的 [`Frame::Event`] 暂存待取。
     ///
     /// # Params:
     ///   - `req`: 请求体
@@ -88,12 +132,32 @@ impl<S: AsyncRead + AsyncWrite + Unpin> OneshotClient<S> {
                 .wrap_err("等待应答")?
             {
                 Some(Frame::Response { id: got, resp }) if got == id => return Ok(*resp),
-                Some(Frame::Event(_)) => {
-                    // 一次性命令订空集,正常不会有 event;容忍并跳过。
-                }
+                // 订了推送的命令可能在应答前就收到结果(任务比应答先落地),暂存不丢。
+                Some(Frame::Event(event)) => self.pending.push_back(event),
                 Some(other) => bail!("收到无法配对的帧:{other:?}"),
                 None => bail!("daemon 在应答前关闭了连接"),
             }
         }
     }
+
+    /// 取下一条推送:先出暂存的,再等连接上新到的。
+    ///
+    /// # Return:
+    ///   下一条推送;daemon 关闭连接为 `None`。
+    ///
+    /// # Errors
+    /// 连接出错 / 收到请求 - 应答之外不该出现的帧。
+    pub async fn next_event(&mut self) -> color_eyre::Result<Option<Event>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(Some(event));
+        }
+        match recv::<Frame, _>(&mut self.conn)
+            .await
+            .wrap_err("等待推送")?
+        {
+            Some(Frame::Event(event)) => Ok(Some(event)),
+            Some(other) => bail!("收到无法配对的帧:{other:?}"),
+            None => Ok(None),
+        }
+    }
 }
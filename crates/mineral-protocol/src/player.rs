@@ -314,6 +314,10 @@ pub struct CurrentSync {
     /// 算完经一次 `current` 版本 bump 补发)。归属恒等于 `current_song`——server 端
     /// 组段时按当前曲过滤,client 直接采用无需再猜归属。
     pub current_envelope: Option<Envelope>,
+
+    /// 当前歌的歌词时间偏移(毫秒;正 = 歌词推迟,0 = 未校正)。per-song 持久,渲染端
+    /// 以「播放位置 − 偏移」定位歌词行。
+    pub lyric_offset_ms: i64,
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
-//! 端到端 codec 测试:在 in-memory `DuplexStream` 上 framed → send → recv → 反序列化。
+//! 端到端 codec 测试:在 in-memory `DuplexStream` 上 framed → send → recv → 反序列化;
+//! 每个用例同时过 bincode 帧与 JSON-lines 两种连接。
 
 use color_eyre::eyre::eyre;
 use mineral_audio::AudioSnapshot;
@@ -8,32 +9,39 @@ use mineral_model::{
 use mineral_protocol::{
     CancelFilter, ChannelFetchKindTag, CopyTemplateCtx, CurrentSync, DownloadProgress,
     DownloadTarget, KeyContext, PlayMode, PlayerSync, PlayerVersions, PlaylistRef, QueueSync,
-    Request, Response, ScriptBind, SongStatsWire, StoreValue, ViewKind, framed, recv, send,
+    Request, Response, ScriptBind, SongStatsWire, StoreValue, ViewKind, framed, json_framed, recv,
+    recv_json, send, send_json,
 };
 use mineral_task::{ChannelFetchKind, Priority, Snapshot, TaskId, TaskKind};
 use mineral_test::song;
 use pretty_assertions::assert_eq;
 use tokio::io::duplex;
 
-/// 同一值经 serde_json 往返,断言 Debug 保真。codec 可换的守卫(与 tests/frame.rs
-/// 的 `dual_codec_roundtrip` 同约定):wire 类型只许依赖 serde derive,不许绑死
-/// bincode。framed bincode 路径由调用方覆盖。
-fn json_round_trips<T>(value: &T) -> color_eyre::Result<()>
+/// 同一值经 JSON-lines 连接(`json_framed` → `send_json` → `recv_json`)往返,断言
+/// Debug 保真。既是 codec 可换的守卫(与 tests/frame.rs 的 `dual_codec_roundtrip`
+/// 同约定:wire 类型只许依赖 serde derive,不许绑死 bincode),也让非 Rust client
+/// 走的 JSON-lines 模式与 bincode 覆盖同一批用例。framed bincode 路径由调用方覆盖。
+async fn jsonl_round_trips<T>(value: &T) -> color_eyre::Result<()>
 where
     T: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug,
 {
+    let (a, b) = duplex(64 * 1024);
+    let mut sender = json_framed(a);
+    let mut receiver = json_framed(b);
     let want = format!("{value:?}");
-    let json = serde_json::to_string(value)?;
-    let back: T = serde_json::from_str(&json)?;
-    assert_eq!(format!("{back:?}"), want, "JSON 往返应保真");
+    send_json(&mut sender, value).await?;
+    let back: T = recv_json(&mut receiver)
+        .await?
+        .ok_or_else(|| eyre!("line missing"))?;
+    assert_eq!(format!("{back:?}"), want, "JSON-lines 往返应保真");
     Ok(())
 }
 
 /// 把一个 [`Request`] 走 framed round-trip,断言收回的与发出的 Debug 等价
 /// (`Request` 不实现 `PartialEq`,但成功反序列化的 Debug 必然逐字段相同);
-/// 同一值顺带过 [`json_round_trips`](双 codec 一次覆盖)。
+/// 同一值顺带过 [`jsonl_round_trips`](双 codec 一次覆盖)。
 async fn req_round_trips(req: Request) -> color_eyre::Result<()> {
-    json_round_trips(&req)?;
+    jsonl_round_trips(&req).await?;
     let (a, b) = duplex(64 * 1024);
     let mut sender = framed(a);
     let mut receiver = framed(b);
@@ -48,7 +56,7 @@ async fn req_round_trips(req: Request) -> color_eyre::Result<()> {
 
 /// 同 [`req_round_trips`],[`Response`] 版。
 async fn resp_round_trips(resp: Response) -> color_eyre::Result<()> {
-    json_round_trips(&resp)?;
+    jsonl_round_trips(&resp).await?;
     let (a, b) = duplex(64 * 1024);
     let mut sender = framed(a);
     let mut receiver = framed(b);
@@ -69,7 +77,7 @@ async fn round_trip_request_play() -> color_eyre::Result<()> {
 
     let url = MediaUrl::remote("https://example.com/song.mp3")?;
     let req = Request::Play(url.clone());
-    json_round_trips(&req)?;
+    jsonl_round_trips(&req).await?;
     send(&mut sender, &req).await?;
     let got: Request = recv(&mut receiver)
         .await?
@@ -93,7 +101,7 @@ async fn round_trip_request_submit_task() -> color_eyre::Result<()> {
         quality: BitRate::Higher,
     });
     let req = Request::SubmitTask(kind.clone(), Priority::User);
-    json_round_trips(&req)?;
+    jsonl_round_trips(&req).await?;
     send(&mut sender, &req).await?;
     let got: Request = recv(&mut receiver)
         .await?
@@ -118,7 +126,7 @@ async fn round_trip_request_cancel_tasks() -> color_eyre::Result<()> {
         ChannelFetchKindTag::Lyrics,
     ]);
     let req = Request::CancelTasks(filter.clone());
-    json_round_trips(&req)?;
+    jsonl_round_trips(&req).await?;
     send(&mut sender, &req).await?;
     let got: Request = recv(&mut receiver)
         .await?
@@ -142,6 +150,8 @@ async fn round_trip_response_audio_snapshot() -> color_eyre::Result<()> {
         position_ms: 12_345,
         duration_ms: Some(200_000),
         volume_pct: 77,
+        speed_pct: 100,
+        speed_mode: mineral_audio::SpeedMode::TimeStretch,
         track_finished_seq: 3,
         backend: mineral_audio::AudioBackend::Null,
         download_complete: false,
@@ -155,7 +165,7 @@ async fn round_trip_response_audio_snapshot() -> color_eyre::Result<()> {
         sample_rate_hz: 44_100,
     };
     let resp = Response::AudioSnapshot(snap);
-    json_round_trips(&resp)?;
+    jsonl_round_trips(&resp).await?;
     send(&mut sender, &resp).await?;
     let got: Response = recv(&mut receiver)
         .await?
@@ -176,7 +186,7 @@ async fn round_trip_response_error() -> color_eyre::Result<()> {
 
     let msg = "daemon busy: another client is connected";
     let resp = Response::Error(msg.to_owned());
-    json_round_trips(&resp)?;
+    jsonl_round_trips(&resp).await?;
     send(&mut sender, &resp).await?;
     let got: Response = recv(&mut receiver)
         .await?
@@ -221,9 +231,30 @@ async fn round_trip_simple_requests() -> color_eyre::Result<()> {
     req_round_trips(Request::Stop).await?;
     req_round_trips(Request::Seek(12_345)).await?;
     req_round_trips(Request::SetVolume(50)).await?;
+    req_round_trips(Request::SetEq(Some("bass_boost".to_owned()))).await?;
+    req_round_trips(Request::SetEq(None)).await?;
+    req_round_trips(Request::SetSpeed {
+        speed: 1.5,
+        mode: mineral_audio::SpeedMode::PitchShift,
+    })
+    .await?;
+    req_round_trips(Request::AudioDevices).await?;
+    req_round_trips(Request::SetAudioDevice(Some("USB DAC".to_owned()))).await?;
+    resp_round_trips(Response::AudioDevices(vec![mineral_audio::OutputDevice {
+        name: "USB DAC".to_owned(),
+        is_default: false,
+        active: true,
+    }]))
+    .await?;
     req_round_trips(Request::CyclePlayMode).await?;
     req_round_trips(Request::PrevOrRestart).await?;
     req_round_trips(Request::NextSong).await?;
+    req_round_trips(Request::NudgeLyricOffset(-50)).await?;
+    req_round_trips(Request::SaveLyrics {
+        song_id: SongId::new(SourceKind::BILIBILI, "BV1xx:1"),
+        lrc: "[00:01.25]第一句\n[00:04.20]第二句".to_owned(),
+    })
+    .await?;
     req_round_trips(Request::TaskSnapshot).await?;
     req_round_trips(Request::PlayerSync(PlayerVersions {
         queue: 3,
@@ -401,6 +432,7 @@ async fn round_trip_player_sync_rich() -> color_eyre::Result<()> {
                 points: vec![0, 128, 255],
                 version: 1,
             }),
+            lyric_offset_ms: -150,
         }),
     };
     resp_round_trips(Response::PlayerSync(Box::new(sync))).await?;
@@ -444,16 +476,32 @@ mod proptests {
             Just(Request::Resume),
             Just(Request::Stop),
             Just(Request::AudioSnapshot),
+            Just(Request::AudioDevices),
             Just(Request::TaskSnapshot),
             Just(Request::CyclePlayMode),
             Just(Request::PrevOrRestart),
             Just(Request::NextSong),
+            any::<i64>().prop_map(Request::NudgeLyricOffset),
+            (".{0,12}", ".{0,40}").prop_map(|(id, lrc)| Request::SaveLyrics {
+                song_id: SongId::new(SourceKind::NETEASE, id.as_str()),
+                lrc,
+            }),
             Just(Request::Shutdown),
             (any::<u64>(), any::<u64>()).prop_map(|(queue, current)| {
                 Request::PlayerSync(PlayerVersions { queue, current })
             }),
             any::<u64>().prop_map(Request::Seek),
             any::<u8>().prop_map(Request::SetVolume),
+            proptest::option::of(any::<String>()).prop_map(Request::SetEq),
+            (0.5f32..=3.0, any::<bool>()).prop_map(|(speed, pitch)| Request::SetSpeed {
+                speed,
+                mode: if pitch {
+                    mineral_audio::SpeedMode::PitchShift
+                } else {
+                    mineral_audio::SpeedMode::TimeStretch
+                },
+            }),
+            proptest::option::of(any::<String>()).prop_map(Request::SetAudioDevice),
             any::<usize>().prop_map(Request::PullPcm),
             arb_song().prop_map(|s| Request::PlaySong(Box::new(s))),
             (vec(arb_song(), 0..4), any::<String>()).prop_map(|(queue, target)| {
@@ -0,0 +1,207 @@
+//! JSON-lines 模式守卫:握手 / 帧的 JSON 形状钉子(改动即须 bump `JSON_SCHEMA_VERSION`)、
+//! 首字节分流判据、垫回首字节后的 codec 续读。逐变体的 round-trip 与 bincode 共用
+//! tests/codec.rs 的用例。
+
+use color_eyre::eyre::eyre;
+use mineral_protocol::{
+    ClientInfo, Event, Frame, JSON_SCHEMA_VERSION, JsonHandshake, JsonHello, PkgVersion,
+    RejectReason, Request, RequestId, Response, Subscription, TextSpan, ToastKind, decode_json,
+    decode_json_frame, encode_json, framed, framed_with_prefix, is_json_lead, json_framed,
+    json_framed_with_prefix, recv, recv_json, send, send_json,
+};
+use pretty_assertions::assert_eq;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, duplex};
+
+/// 握手 / 应答行的 JSON 形状:外部 client 照此手拼,字段名与值形态不许漂。
+#[test]
+fn handshake_lines_are_pinned() -> color_eyre::Result<()> {
+    assert_eq!(JSON_SCHEMA_VERSION, 1);
+    assert_eq!(
+        encode_json(&JsonHandshake::new("widget", vec![Subscription::Property]))?,
+        r#"{"schema":1,"name":"widget","subscriptions":["Property"]}"#
+    );
+    // 订阅集可省(一次性脚本)。
+    let bare: JsonHandshake = decode_json(r#"{"schema":1,"name":"sh"}"#)?;
+    assert_eq!(bare, JsonHandshake::new("sh", Vec::new()));
+
+    assert_eq!(
+        encode_json(&JsonHello::reject(RejectReason::VersionMismatch))?,
+        format!(
+            r#"{{"accepted":false,"reason":"VersionMismatch","schema":1,"server_version":"{}"}}"#,
+            PkgVersion::current()
+        )
+    );
+    Ok(())
+}
+
+/// 请求 / 应答帧的 JSON 形状:externally tagged,单元变体为裸字符串。
+#[test]
+fn frame_lines_are_pinned() -> color_eyre::Result<()> {
+    let cases = [
+        (
+            Frame::Request {
+                id: RequestId::new(1),
+                req: Request::Pause,
+            },
+            r#"{"Request":{"id":1,"req":"Pause"}}"#,
+        ),
+        (
+            Frame::Request {
+                id: RequestId::new(2),
+                req: Request::Seek(30_000),
+            },
+            r#"{"Request":{"id":2,"req":{"Seek":30000}}}"#,
+        ),
+        (
+            Frame::Response {
+                id: RequestId::new(1),
+                resp: Box::new(Response::Ok),
+            },
+            r#"{"Response":{"id":1,"resp":"Ok"}}"#,
+        ),
+        (
+            Frame::Response {
+                id: RequestId::new(3),
+                resp: Box::new(Response::Error("boom".to_owned())),
+            },
+            r#"{"Response":{"id":3,"resp":{"Error":"boom"}}}"#,
+        ),
+    ];
+    for (frame, want) in cases {
+        assert_eq!(encode_json(&frame)?, want);
+        let back: Frame = decode_json(want)?;
+        assert_eq!(format!("{back:?}"), format!("{frame:?}"));
+    }
+    Ok(())
+}
+
+/// schema 守门只认相等;转成注册表身份时订阅集原样保留。
+#[test]
+fn schema_gate_and_identity() {
+    let ok = JsonHandshake::new("widget", vec![Subscription::Task]);
+    assert!(ok.schema_matches());
+    let newer = JsonHandshake {
+        schema: JSON_SCHEMA_VERSION + 1,
+        ..ok.clone()
+    };
+    assert!(!newer.schema_matches());
+    let info: ClientInfo = ok.into_client_info();
+    assert_eq!(info.name, "widget");
+    assert_eq!(info.subscriptions, vec![Subscription::Task]);
+}
+
+/// 首字节分流:真实 bincode 握手帧的首字节不被认作 JSON,JSON 握手行则被认出。
+#[tokio::test]
+async fn lead_byte_tells_codecs_apart() -> color_eyre::Result<()> {
+    let (a, mut raw) = duplex(64 * 1024);
+    let mut sender = framed(a);
+    send(
+        &mut sender,
+        &Frame::Handshake(ClientInfo::new("tui", vec![Subscription::Property])),
+    )
+    .await?;
+    assert!(!is_json_lead(raw.read_u8().await?));
+
+    let (a, mut raw) = duplex(64 * 1024);
+    let mut sender = json_framed(a);
+    send_json(&mut sender, &JsonHandshake::new("widget", Vec::new())).await?;
+    assert!(is_json_lead(raw.read_u8().await?));
+    Ok(())
+}
+
+/// 读走首字节再垫回:两种 codec 都从帧头完整解出首帧。
+#[tokio::test]
+async fn prefixed_codecs_resume_from_lead() -> color_eyre::Result<()> {
+    let (a, mut b) = duplex(64 * 1024);
+    let mut sender = json_framed(a);
+    let hs = JsonHandshake::new("widget", vec![Subscription::Toast]);
+    send_json(&mut sender, &hs).await?;
+    let lead = [b.read_u8().await?];
+    let mut receiver = json_framed_with_prefix(b, &lead);
+    let got: JsonHandshake = recv_json(&mut receiver)
+        .await?
+        .ok_or_else(|| eyre!("line missing"))?;
+    assert_eq!(got, hs);
+
+    let (a, mut b) = duplex(64 * 1024);
+    let mut sender = framed(a);
+    send(&mut sender, &Request::NextSong).await?;
+    let lead = [b.read_u8().await?];
+    let mut receiver = framed_with_prefix(b, &lead);
+    let got: Request = recv(&mut receiver)
+        .await?
+        .ok_or_else(|| eyre!("frame missing"))?;
+    assert!(matches!(got, Request::NextSong));
+    Ok(())
+}
+
+/// 手写的多行输入(shell 拼接常见空行 / `\r\n`)逐帧解出,事件帧同样能过。
+#[tokio::test]
+async fn hand_written_lines_decode_in_order() -> color_eyre::Result<()> {
+    let (mut a, b) = duplex(64 * 1024);
+    a.write_all(b"{\"Request\":{\"id\":7,\"req\":\"NextSong\"}}\r\n\n   \n")
+        .await?;
+    let toast = Frame::Event(Event::Toast {
+        kind: ToastKind::Info,
+        content: vec![TextSpan::plain("hi")],
+        id: None,
+        ttl_secs: Some(3),
+    });
+    a.write_all(format!("{}\n", encode_json(&toast)?).as_bytes())
+        .await?;
+    drop(a);
+
+    let mut receiver = json_framed(b);
+    let first: Frame = recv_json(&mut receiver)
+        .await?
+        .ok_or_else(|| eyre!("first line missing"))?;
+    assert!(matches!(
+        first,
+        Frame::Request {
+            req: Request::NextSong,
+            ..
+        }
+    ));
+    let second: Frame = recv_json(&mut receiver)
+        .await?
+        .ok_or_else(|| eyre!("second line missing"))?;
+    assert_eq!(format!("{second:?}"), format!("{toast:?}"));
+    assert!(recv_json::<Frame, _>(&mut receiver).await?.is_none());
+    Ok(())
+}
+
+/// 解不出的行给出错误应答帧(能认出 `Request.id` 就沿用,否则 `0`),不影响后续行。
+#[test]
+fn malformed_lines_reply_with_error_frames() -> color_eyre::Result<()> {
+    let cases = [
+        ("not json at all", 0),
+        (r#"{"Request":{"id":5,"req":"NoSuchRequest"}}"#, 5),
+        (r#"{"Request":{"id":6,"req":{"Seek":"soon"}}}"#, 6),
+        (r#"{"Request":{"req":"Pause"}}"#, 0),
+    ];
+    for (line, want_id) in cases {
+        let reply = decode_json_frame(line)
+            .err()
+            .ok_or_else(|| eyre!("{line:?} should not decode"))?;
+        let Frame::Response { id, resp } = *reply else {
+            return Err(eyre!("{line:?} should yield an error response"));
+        };
+        assert_eq!(id, RequestId::new(want_id), "{line}");
+        let Response::Error(message) = *resp else {
+            return Err(eyre!("{line:?} should reply Response::Error"));
+        };
+        assert!(message.starts_with("malformed frame: "), "{message}");
+        // 应答帧本身能照常编码写回。
+        encode_json(&Frame::Response {
+            id,
+            resp: Box::new(Response::Error(message)),
+        })?;
+    }
+    let Ok(Frame::Request { id, req }) = decode_json_frame(r#"{"Request":{"id":7,"req":"Pause"}}"#)
+    else {
+        return Err(eyre!("a well-formed line should decode"));
+    };
+    assert_eq!(id, RequestId::new(7));
+    assert!(matches!(req, Request::Pause));
+    Ok(())
+}
@@ -2,11 +2,14 @@
 //! 一个文件对应一个 Lua 函数,与脚本侧 API 树一一对应。
 
 pub(crate) mod next;
+pub(crate) mod nudge_lyric_offset;
 pub(crate) mod play;
 pub(crate) mod prev;
 pub(crate) mod seek_rel;
 pub(crate) mod seek_to;
+pub(crate) mod set_eq;
 pub(crate) mod set_mode;
+pub(crate) mod set_speed;
 pub(crate) mod set_volume;
 pub(crate) mod stop;
 pub(crate) mod toggle;
@@ -33,7 +36,10 @@ pub(crate) fn install(lua: &Lua, mineral: &Table, host: &ScriptHost) -> mlua::Re
     seek_rel::install(lua, &player, host)?;
     seek_to::install(lua, &player, host)?;
     set_volume::install(lua, &player, host)?;
+    set_eq::install(lua, &player, host)?;
     set_mode::install(lua, &player, host)?;
+    set_speed::install(lua, &player, host)?;
+    nudge_lyric_offset::install(lua, &player, host)?;
     play::install(lua, &player, host)?;
     mineral.set("player", player)
 }
@@ -0,0 +1,24 @@
+//! `mineral.player.nudge_lyric_offset(ms)`:在播曲的歌词时间偏移增减 `ms` 毫秒
+//! (正 = 歌词推迟,负 = 提前),per-song 持久;越界由 daemon 钳到 ±10s。
+
+use mlua::{Lua, Table};
+
+use crate::host::ScriptHost;
+use crate::message::ScriptCmd;
+
+/// 把 `nudge_lyric_offset` 挂到 `player` 子表上。
+///
+/// # Params:
+///   - `lua`: 目标 VM
+///   - `player`: `mineral.player` 子表
+///   - `host`: 宿主句柄(闭包捕获其命令出口)
+pub(crate) fn install(lua: &Lua, player: &Table, host: &ScriptHost) -> mlua::Result<()> {
+    let commands = host.commands.clone();
+    player.set(
+        "nudge_lyric_offset",
+        lua.create_function(move |_lua, delta_ms: i64| {
+            let _ = commands.send(ScriptCmd::NudgeLyricOffset(delta_ms));
+            Ok(())
+        })?,
+    )
+}
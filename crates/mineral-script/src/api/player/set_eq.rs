@@ -0,0 +1,24 @@
+//! `mineral.player.set_eq(name)`:按名切换均衡预设(`nil` = 旁路;未定义的预设名由
+//! daemon 记 warn 并忽略)。
+
+use mlua::{Lua, Table};
+
+use crate::host::ScriptHost;
+use crate::message::ScriptCmd;
+
+/// 把 `set_eq` 挂到 `player` 子表上。
+///
+/// # Params:
+///   - `lua`: 目标 VM
+///   - `player`: `mineral.player` 子表
+///   - `host`: 宿主句柄(闭包捕获其命令出口)
+pub(crate) fn install(lua: &Lua, player: &Table, host: &ScriptHost) -> mlua::Result<()> {
+    let commands = host.commands.clone();
+    player.set(
+        "set_eq",
+        lua.create_function(move |_lua, preset: Option<String>| {
+            let _ = commands.send(ScriptCmd::SetEq(preset));
+            Ok(())
+        })?,
+    )
+}
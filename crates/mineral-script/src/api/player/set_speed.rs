@@ -0,0 +1,34 @@
+//! `mineral.player.set_speed(speed, mode?)`:设播放倍速(越界 clamp 到 0.5-3,不报错);
+//! `mode` 省略 = `"time_stretch"`(保持音高),`"pitch_shift"` = 变调变速,未知名报错。
+
+use mineral_audio::SpeedMode;
+use mlua::{Lua, Table};
+
+use crate::host::ScriptHost;
+use crate::message::ScriptCmd;
+
+/// 把 `set_speed` 挂到 `player` 子表上。
+///
+/// # Params:
+///   - `lua`: 目标 VM
+///   - `player`: `mineral.player` 子表
+///   - `host`: 宿主句柄(闭包捕获其命令出口)
+pub(crate) fn install(lua: &Lua, player: &Table, host: &ScriptHost) -> mlua::Result<()> {
+    let commands = host.commands.clone();
+    player.set(
+        "set_speed",
+        lua.create_function(move |_lua, (speed, mode): (f32, Option<String>)| {
+            let mode = match mode.as_deref() {
+                None | Some("time_stretch") => SpeedMode::TimeStretch,
+                Some("pitch_shift") => SpeedMode::PitchShift,
+                Some(other) => {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "unknown speed mode {other:?}, expected \"time_stretch\" | \"pitch_shift\""
+                    )));
+                }
+            };
+            let _ = commands.send(ScriptCmd::SetSpeed { speed, mode });
+            Ok(())
+        })?,
+    )
+}
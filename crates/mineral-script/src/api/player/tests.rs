@@ -1,6 +1,7 @@
 //! `mineral.player.*` 命令族的族级测试:Lua 调用 → 结构化 [`ScriptCmd`] 的
 //! 完整映射、入参 clamp 与校验。
 
+use mineral_audio::SpeedMode;
 use mineral_model::{SongId, SourceKind};
 use mineral_protocol::PlayMode;
 
@@ -64,6 +65,81 @@ fn volume_and_seek_clamp_out_of_range() -> color_eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn set_eq_sends_preset_or_bypass() -> color_eyre::Result<()> {
+    let (lua, mut cmd_rx) = vm_with_commands()?;
+    lua.load(
+        r#"
+        mineral.player.set_eq("vocal")
+        mineral.player.set_eq(nil)
+        "#,
+    )
+    .exec()?;
+    assert_eq!(
+        drain_cmds(&mut cmd_rx),
+        vec![
+            ScriptCmd::SetEq(Some("vocal".to_owned())),
+            ScriptCmd::SetEq(None),
+        ],
+        "预设名原样下发,nil 为旁路"
+    );
+    Ok(())
+}
+
+#[test]
+fn set_speed_defaults_to_time_stretch() -> color_eyre::Result<()> {
+    let (lua, mut cmd_rx) = vm_with_commands()?;
+    lua.load(
+        r#"
+        mineral.player.set_speed(1.5)
+        mineral.player.set_speed(0.75, "pitch_shift")
+        "#,
+    )
+    .exec()?;
+    assert_eq!(
+        drain_cmds(&mut cmd_rx),
+        vec![
+            ScriptCmd::SetSpeed {
+                speed: 1.5,
+                mode: SpeedMode::TimeStretch,
+            },
+            ScriptCmd::SetSpeed {
+                speed: 0.75,
+                mode: SpeedMode::PitchShift,
+            },
+        ],
+        "省略 mode 为时间伸缩"
+    );
+    assert!(
+        lua.load(r#"mineral.player.set_speed(2, "chipmunk")"#)
+            .exec()
+            .is_err(),
+        "未知变速方式必须报 Lua 错"
+    );
+    assert!(drain_cmds(&mut cmd_rx).is_empty(), "报错时不得发出命令");
+    Ok(())
+}
+
+#[test]
+fn nudge_lyric_offset_passes_signed_delta() -> color_eyre::Result<()> {
+    let (lua, mut cmd_rx) = vm_with_commands()?;
+    lua.load(
+        r#"
+        mineral.player.nudge_lyric_offset(50)
+        mineral.player.nudge_lyric_offset(-100)
+        "#,
+    )
+    .exec()?;
+    assert_eq!(
+        drain_cmds(&mut cmd_rx),
+        vec![
+            ScriptCmd::NudgeLyricOffset(50),
+            ScriptCmd::NudgeLyricOffset(-100),
+        ]
+    );
+    Ok(())
+}
+
 #[test]
 fn unknown_mode_and_bad_song_id_are_lua_errors() -> color_eyre::Result<()> {
     let (lua, mut cmd_rx) = vm_with_commands()?;
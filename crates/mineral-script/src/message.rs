@@ -4,6 +4,7 @@
 //! [`ScriptCmd`] 是脚本 → daemon(Lua API 发出的播放器命令)。两侧都是
 //! **结构化** Rust 类型,Lua 字符串只出现在 VM 边界的适配层(`api` 模块)。
 
+use mineral_audio::SpeedMode;
 use mineral_model::{Song, SongId};
 use mineral_protocol::PlayMode;
 
@@ -203,6 +204,21 @@ pub enum ScriptCmd {
     /// 设音量(0..=100)。
     SetVolume(u8),
 
+    /// 按名切换均衡预设(`None` = 旁路)。
+    SetEq(Option<String>),
+
+    /// 设播放倍速(越界由音频引擎钳进 0.5..=3)。
+    SetSpeed {
+        /// 倍速(1 = 原速)。
+        speed: f32,
+
+        /// 变速方式。
+        mode: SpeedMode,
+    },
+
+    /// 在播曲的歌词时间偏移增减若干毫秒(正 = 歌词推迟),per-song 持久。
+    NudgeLyricOffset(i64),
+
     /// 设播放模式。
     SetMode(PlayMode),
 
@@ -81,6 +81,14 @@ pub trait Client: Send + Sync {
     /// `n` 键:按 PlayMode 切下一首。
     fn next_song(&self);
 
+    /// 在播曲的歌词时间偏移增减 `delta_ms` 毫秒(正 = 歌词推迟),per-song 持久;
+    /// 无在播曲时无事。新偏移经 [`Self::player_sync`] 的 current 段送达。
+    fn nudge_lyric_offset(&self, delta_ms: i64);
+
+    /// 把 LRC 文本存为 `song_id` 的歌词覆盖(歌词覆盖目录),此后播该曲先于其他歌词;
+    /// 正在播即刻换上。结果经 toast 告知。
+    fn save_lyrics(&self, song_id: SongId, lrc: String);
+
     /// 版本门控的播放状态同步:`known` 是 client 已持有的版本号(0 = 一无所有),
     /// server 仅对落后部分附带重段。启动与每 tick 同一条路径(语义见 [`PlayerSync`])。
     fn player_sync(&self, known: PlayerVersions) -> PlayerSync;
@@ -196,6 +196,24 @@ impl ClientHandle {
         result
     }
 
+    /// 插到队列的指定下标(MPRIS `AddTrack` 用;wire 契约只有插播 / 追加两种插法)。
+    /// 行为域没有「任意位插入」一档,埋点按追加记。
+    ///
+    /// # Params:
+    ///   - `song`: 待插入的歌
+    ///   - `pos`: 插入下标(越界即队尾)
+    ///   - `context`: 该曲来源语境
+    pub(crate) fn queue_insert_at(&self, song: Song, pos: usize, context: QueueContextWire) {
+        let id = song.id.clone();
+        self.player
+            .queue_insert_at(song, pos, queue_context_from_wire(context));
+        self.record_behavior(mineral_stats::BehaviorEvent::QueueOp {
+            op: mineral_stats::QueueOp::Append,
+            song: Some(id),
+            count: 1,
+        });
+    }
+
     /// 队列编辑的完整入口(serve 层用)。
     ///
     /// [`QueueOp::ApplyTransform`] 要跨线程跑脚本、必须异步,其余操作同步落地。变换失败
@@ -290,6 +308,59 @@ impl ClientHandle {
         Ok(())
     }
 
+    /// 在播曲的歌词时间偏移增减 `delta_ms`(serve 层处理 `NudgeLyricOffset` 用)。
+    ///
+    /// # Return:
+    ///   调整后的偏移;无在播曲时报错。
+    pub(crate) fn adjust_lyric_offset(&self, delta_ms: i64) -> color_eyre::Result<i64> {
+        self.player.nudge_lyric_offset(delta_ms)
+    }
+
+    /// 存某曲的歌词覆盖(serve 层处理 `SaveLyrics` 用)。
+    ///
+    /// # Params:
+    ///   - `song_id`: 目标曲
+    ///   - `lrc`: LRC 文本
+    pub(crate) async fn save_lyrics_async(
+        &self,
+        song_id: &SongId,
+        lrc: String,
+    ) -> color_eyre::Result<()> {
+        self.player.save_lyrics(song_id, lrc).await
+    }
+
+    /// 设播放倍速(越界由引擎钳进 0.5..=3)。
+    pub(crate) fn set_speed(&self, speed: f32, mode: mineral_audio::SpeedMode) {
+        self.player.audio().set_speed(speed, mode);
+    }
+
+    /// 可用输出设备(标注当前所用的一个)。
+    pub(crate) fn audio_devices(&self) -> color_eyre::Result<Vec<mineral_audio::OutputDevice>> {
+        self.player.audio().output_devices()
+    }
+
+    /// 钉住输出设备并热切换(`None` = 跟随默认设备)。
+    ///
+    /// # Return:
+    ///   设备名不在当前可用列表里时报错(不切换)。
+    pub(crate) fn set_audio_device(&self, name: Option<String>) -> color_eyre::Result<()> {
+        if let Some(want) = name.as_deref()
+            && !self.audio_devices()?.iter().any(|d| d.name == want)
+        {
+            color_eyre::eyre::bail!("没有名为 `{want}` 的输出设备");
+        }
+        self.player.audio().set_output_device(name);
+        Ok(())
+    }
+
+    /// 按名切换均衡预设(`None` = 旁路)。
+    ///
+    /// # Return:
+    ///   预设名未定义时报错。
+    pub(crate) fn set_eq(&self, preset: Option<&str>) -> color_eyre::Result<()> {
+        self.player.set_eq_preset(preset)
+    }
+
     /// 当前有效配置(serve 层握手订阅 `Config` 时重放一帧)。
     pub(crate) fn effective_config(&self) -> mineral_protocol::BusValue {
         self.player.effective_config()
@@ -300,6 +371,67 @@ impl ClientHandle {
         self.player.window_title_override()
     }
 
+    /// 开始 / 恢复播放:在播不动;有当前曲(暂停中)恢复;否则从队列接续点起播。
+    ///
+    /// # Return:
+    ///   无当前曲且队列为空(无可播)时为 `false`。
+    pub(crate) fn play_or_resume(&self) -> bool {
+        if self.audio_snapshot().playing {
+            return true;
+        }
+        let sync = self.player_sync(PlayerVersions::default());
+        if sync.current.is_some_and(|c| c.current_song.is_some()) {
+            self.resume();
+            return true;
+        }
+        let queue = sync.queue.map(|q| q.queue).unwrap_or_default();
+        let start = queue.get(sync.cursor.anchor()).or_else(|| queue.first());
+        match start.cloned() {
+            Some(song) => {
+                self.play_song(song);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 直设播放模式(遥控入口给的是目标档位,不是「循环到下一档」)。
+    pub(crate) fn set_play_mode(&self, mode: mineral_protocol::PlayMode) {
+        self.player.set_play_mode(mode, mineral_stats::Actor::User);
+    }
+
+    /// 当前曲与其歌词(已按歌词偏移平移)快照(HTTP 遥控的封面 / 歌词端点用)。
+    pub(crate) fn current_media(&self) -> (Option<Song>, Option<mineral_model::Lyrics>) {
+        self.player.with_state(|st| {
+            let lyrics = st
+                .current_lyrics
+                .as_ref()
+                .map(|l| l.shifted(st.lyric_offset_ms()));
+            (st.current_song.clone(), lyrics)
+        })
+    }
+
+    /// 用户歌单库合并快照;还没有任何源结论时为 `None`。
+    pub(crate) fn library_snapshot(&self) -> Option<Vec<mineral_model::Playlist>> {
+        self.player.library().cached_snapshot()
+    }
+
+    /// 本地曲库的全部歌曲(MPD 的搜索与曲库浏览用);没注册本地源时为空。
+    ///
+    /// # Errors
+    /// 本地曲库扫描失败。
+    pub(crate) async fn local_songs_async(&self) -> color_eyre::Result<Vec<Song>> {
+        match self.player.channel_for(SourceKind::LOCAL) {
+            Some(channel) => Ok(channel.library_songs().await?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// daemon 共享的出站 HTTP client(远端封面代理用);构建失败时为 `None`。
+    pub(crate) fn http(&self) -> Option<reqwest::Client> {
+        self.player.http().cloned()
+    }
+
     /// 按握手订阅集组装重放帧:各订阅类别的当前状态快照,先于实时流下发,
     /// 新 client 无须等待下一次变更即拿到完整现状。
     ///
@@ -522,6 +654,18 @@ impl Client for ClientHandle {
     fn next_song(&self) {
         self.player.next_song(mineral_stats::Actor::User);
     }
+    fn nudge_lyric_offset(&self, delta_ms: i64) {
+        if let Err(e) = self.player.nudge_lyric_offset(delta_ms) {
+            mineral_log::debug!(target: "player", error = mineral_log::chain(&e), "nudge_lyric_offset ignored");
+        }
+    }
+    fn save_lyrics(&self, song_id: SongId, lrc: String) {
+        // 成败由 player 发 toast,这里不再另报。
+        let player = self.player.clone();
+        tokio::spawn(async move {
+            let _ = player.save_lyrics(&song_id, lrc).await;
+        });
+    }
     fn player_sync(&self, known: PlayerVersions) -> PlayerSync {
         self.player.sync(known)
     }
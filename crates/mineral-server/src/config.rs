@@ -3,20 +3,33 @@
 
 use std::num::{NonZeroU32, NonZeroUsize};
 
-use mineral_audio::{AudioMode, EngineParams, EnvelopeParams, HighpassParams, ShelfParams};
-use mineral_config::{BackendKind, DaemonConfig, DownloadConfig};
+use mineral_audio::{
+    AudioMode, CrossfadeCurve, CrossfadeParams, EngineParams, EnvelopeParams, EqBand, EqBandKind,
+    EqParams, HighpassParams, LimiterParams, ShelfParams,
+};
+use mineral_config::{
+    BackendKind, CrossfadeConfig, DaemonConfig, DownloadConfig, EqConfig, EqPresetConfig,
+    LyricSourcesConfig, NormalizationConfig, NormalizationMode, RadioConfig,
+};
 use mineral_model::BitRate;
 
 /// daemon 启动配置切片。私有字段 + getter 读取;
 #[non_exhaustive]
 #[derive(Clone, Debug, typed_builder::TypedBuilder, derive_getters::Getters)]
 pub struct ServerConfig {
-    /// 音频引擎启动参数(初始音量 / tick / prefetch / tap 容量)。
+    /// 音频引擎启动参数(初始音量 / tick / prefetch / tap 容量 / 限幅 / 交叉淡化 / 起始均衡 /
+    /// 输出设备)。
     engine: EngineParams,
 
+    /// 均衡器配置(`audio.eq`;运行期按名切换预设时查表)。
+    eq: EqConfig,
+
     /// 响度包络计算参数(配置 `audio.envelope`)。
     envelope: EnvelopeParams,
 
+    /// 响度归一化配置(`audio.normalization`;限幅器切片另进 `engine`)。
+    normalization: NormalizationConfig,
+
     /// 在线播放音质(独立于下载音质)。
     playback_quality: BitRate,
 
@@ -29,9 +42,15 @@ pub struct ServerConfig {
     /// 下载段(音质 + 目录)。
     download: DownloadConfig,
 
+    /// 歌词来源段(本地歌词优先 + 用户歌词目录)。
+    lyrics: LyricSourcesConfig,
+
     /// daemon 段(gapless 窗口 + 各间隔节拍)。
     daemon: DaemonConfig,
 
+    /// 电台续播段(配置 `queue.radio`)。
+    radio: RadioConfig,
+
     /// 同步拦截 hook 软超时(毫秒,配置 `script.hook_timeout_ms`)。
     hook_timeout_ms: u64,
 
@@ -62,14 +81,22 @@ impl ServerConfig {
                     .tick_ms(*audio.engine_tick_ms())
                     .prefetch_bytes(*audio.prefetch_bytes())
                     .tap_capacity(*audio.tap_capacity())
+                    .limiter(limiter_params_from(audio.normalization()))
+                    .crossfade(crossfade_params_from(audio.crossfade()))
+                    .eq(initial_eq_from(audio.eq()))
+                    .device(audio.device().clone())
                     .build(),
             )
+            .eq(audio.eq().clone())
             .envelope(envelope_params_from(audio.envelope()))
+            .normalization(audio.normalization().clone())
             .playback_quality(*audio.playback_quality())
             .audio_cache_capacity(*cfg.cache().audio_capacity())
             .channel_workers_per(*cfg.daemon().channel_workers_per())
             .download(cfg.download().clone())
+            .lyrics(cfg.lyrics().clone())
             .daemon(cfg.daemon().clone())
+            .radio(cfg.queue().radio().clone())
             .hook_timeout_ms(*cfg.script().hook_timeout_ms())
             .spawn_max_concurrent(*cfg.script().spawn_max_concurrent())
             .favorites_backfill_chunk_size(*cfg.sources().mineral().backfill().chunk_size())
@@ -108,6 +135,92 @@ fn envelope_params_from(cfg: &mineral_config::EnvelopeConfig) -> EnvelopeParams
         .build()
 }
 
+/// `audio.normalization` 配置 → 引擎限幅器参数。归一化 `off` 时限幅器一并关掉
+/// (不归一化就不该改动原始母带)。
+///
+/// # Params:
+///   - `cfg`: 归一化配置段
+///
+/// # Return:
+///   限幅器参数切片。
+fn limiter_params_from(cfg: &NormalizationConfig) -> LimiterParams {
+    LimiterParams::builder()
+        .enabled(*cfg.limiter().enabled() && *cfg.mode() != NormalizationMode::Off)
+        .threshold_db(*cfg.limiter().threshold_db())
+        .release_ms(*cfg.limiter().release_ms())
+        .build()
+}
+
+/// `audio.crossfade` 配置 → 引擎交叉淡化参数(曲线枚举在接线处映射,config 不依赖音频 crate)。
+///
+/// # Params:
+///   - `cfg`: 交叉淡化配置段
+///
+/// # Return:
+///   交叉淡化参数切片。
+fn crossfade_params_from(cfg: &CrossfadeConfig) -> CrossfadeParams {
+    let curve = match cfg.curve() {
+        mineral_config::CrossfadeCurve::Linear => CrossfadeCurve::Linear,
+        mineral_config::CrossfadeCurve::SCurve => CrossfadeCurve::SCurve,
+        mineral_config::CrossfadeCurve::EqualPower | _ => CrossfadeCurve::EqualPower,
+    };
+    CrossfadeParams::builder()
+        .enabled(*cfg.enabled() && *cfg.duration_ms() > 0)
+        .duration_ms(*cfg.duration_ms())
+        .curve(curve)
+        .build()
+}
+
+/// 一个均衡预设 → 引擎均衡参数(段类型枚举在接线处映射)。
+///
+/// # Params:
+///   - `preset`: 预设配置
+///
+/// # Return:
+///   均衡参数。
+pub(crate) fn eq_params_from(preset: &EqPresetConfig) -> EqParams {
+    let bands = preset
+        .bands()
+        .iter()
+        .map(|band| {
+            let kind = match band.kind() {
+                mineral_config::EqBandKind::LowShelf => EqBandKind::LowShelf,
+                mineral_config::EqBandKind::HighShelf => EqBandKind::HighShelf,
+                mineral_config::EqBandKind::HighPass => EqBandKind::HighPass,
+                mineral_config::EqBandKind::LowPass => EqBandKind::LowPass,
+                mineral_config::EqBandKind::Peaking | _ => EqBandKind::Peaking,
+            };
+            EqBand::builder()
+                .kind(kind)
+                .freq_hz(*band.freq_hz())
+                .gain_db(*band.gain_db())
+                .q(*band.q())
+                .build()
+        })
+        .collect();
+    EqParams::builder()
+        .preamp_db(*preset.preamp_db())
+        .bands(bands)
+        .build()
+}
+
+/// `audio.eq.preset` → 引擎起始均衡参数。预设名未定义只 warn 并旁路(坏配置不该让
+/// daemon 起不来)。
+///
+/// # Params:
+///   - `cfg`: 均衡器配置段
+///
+/// # Return:
+///   起始均衡参数;未选预设 / 预设未定义时 `None`。
+fn initial_eq_from(cfg: &EqConfig) -> Option<EqParams> {
+    let name = cfg.preset().as_deref()?;
+    let Some(preset) = cfg.preset_named(name) else {
+        mineral_log::warn!(target: "player", preset = name, "audio.eq.preset 未定义,均衡器旁路");
+        return None;
+    };
+    Some(eq_params_from(preset))
+}
+
 /// env > config 的音频后端 resolve:`MINERAL_AUDIO_NULL` 命中短路 config。
 /// env 在 binary 边缘读好后以 bool 传入,本函数保持纯(可单测)。
 ///
@@ -117,12 +230,16 @@ fn envelope_params_from(cfg: &mineral_config::EnvelopeConfig) -> EnvelopeParams
 ///
 /// # Return:
 ///   最终 [`AudioMode`]。
-pub fn resolve_audio_mode(env_null: bool, backend: BackendKind) -> AudioMode {
+pub fn resolve_audio_mode(env_null: bool, backend: &BackendKind) -> AudioMode {
     if env_null {
         return AudioMode::ForceNull;
     }
     match backend {
         BackendKind::Null => AudioMode::ForceNull,
+        BackendKind::File { path, realtime } => AudioMode::File {
+            path: path.clone(),
+            realtime: *realtime,
+        },
         // BackendKind 是 #[non_exhaustive]:未来新增后端在接线前一律按 Auto 兜底。
         BackendKind::Auto | _ => AudioMode::Auto,
     }
@@ -151,20 +268,35 @@ mod tests {
     #[test]
     fn resolve_audio_mode_matrix() {
         assert_eq!(
-            resolve_audio_mode(/*env_null*/ true, BackendKind::Auto),
+            resolve_audio_mode(/*env_null*/ true, &BackendKind::Auto),
             AudioMode::ForceNull
         );
         assert_eq!(
-            resolve_audio_mode(/*env_null*/ true, BackendKind::Null),
+            resolve_audio_mode(/*env_null*/ true, &BackendKind::Null),
             AudioMode::ForceNull
         );
         assert_eq!(
-            resolve_audio_mode(/*env_null*/ false, BackendKind::Null),
+            resolve_audio_mode(/*env_null*/ false, &BackendKind::Null),
             AudioMode::ForceNull
         );
         assert_eq!(
-            resolve_audio_mode(/*env_null*/ false, BackendKind::Auto),
+            resolve_audio_mode(/*env_null*/ false, &BackendKind::Auto),
             AudioMode::Auto
         );
+        let file = BackendKind::File {
+            path: "out.wav".into(),
+            realtime: true,
+        };
+        assert_eq!(
+            resolve_audio_mode(/*env_null*/ false, &file),
+            AudioMode::File {
+                path: "out.wav".into(),
+                realtime: true,
+            }
+        );
+        assert_eq!(
+            resolve_audio_mode(/*env_null*/ true, &file),
+            AudioMode::ForceNull
+        );
     }
 }
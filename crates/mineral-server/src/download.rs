@@ -1,8 +1,9 @@
 //! 不依赖播放的下载:给定 [`Song`] → `song_urls` 拿直链 → 整段 HTTP GET → **永久导出**。
 //!
 //! 这是可复用单元——键位下载单曲 / 歌单批量、将来 gapless 预下载都调 [`download_song`]:
-//! 导出落 `<music_dir>/<source>/<quality>/<album>/<title>.<ext>`(永久、不受缓存 LRU 驱逐);
-//! 播放解析(见 [`crate::resolve`])直接探测该目录命中,**无需再复制进缓存**。
+//! 导出按 `download.path_template` 落进导出目录(默认 `<source>/<quality>/<album>/<title>.<ext>`;
+//! 永久、不受缓存 LRU 驱逐)并登记导出索引;播放解析(见 [`crate::resolve`])查索引命中,
+//! **无需再复制进缓存**。
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -16,7 +17,7 @@ use mineral_protocol::{DownloadProgress, DownloadTarget};
 use parking_lot::Mutex;
 use tokio::io::AsyncWriteExt;
 
-use crate::media_cache::library_relpath;
+use crate::exports::Exports;
 use crate::player::PlayerCore;
 
 /// 一首下载的结局(`Err` 另表失败):区分「真正下载」与两类跳过(幂等 / 脚本否决),
@@ -54,48 +55,34 @@ pub(crate) enum SkipCause {
     HookVeto,
 }
 
-/// 解析下载环境:HTTP client(整段 GET 用)+ 永久导出根目录。
-/// 任一不可用时对应项为 `None`(下载整体降级为「不可用」,只 warn 不阻断启动)。
-///
-/// 导出目录优先级:config(`download.dir`)> 平台默认(`~/Music/mineral`)。
-/// config.lua 是唯一用户真相源,不设环境变量逃逸口。
-///
-/// # Params:
-///   - `config_dir`: 配置的下载目录(`download.dir`;`None` = 未配置)
+/// 构建下载用 HTTP client(整段 GET 用)。
+/// 不可用时为 `None`(下载整体降级为「不可用」,只 warn 不阻断启动)。
 ///
 /// # Return:
-///   `(http, music_dir)`,各自失败为 `None`。
-pub(crate) fn open_env(config_dir: Option<&Path>) -> (Option<reqwest::Client>, Option<PathBuf>) {
+///   HTTP client;构建失败为 `None`。
+pub(crate) fn open_http() -> Option<reqwest::Client> {
     let http = reqwest::Client::builder().build().ok();
     if http.is_none() {
         mineral_log::warn!(target: "download", "HTTP client 构建失败,下载不可用");
     }
-    let music_dir = if let Some(d) = config_dir {
-        Some(d.to_path_buf())
-    } else {
-        match mineral_paths::music_export_dir() {
-            Ok(d) => Some(d),
-            Err(e) => {
-                mineral_log::warn!(target: "download", error = mineral_log::chain(&e), "解析音乐导出目录失败,下载不可用");
-                None
-            }
-        }
-    };
-    (http, music_dir)
+    http
 }
 
-/// 下载环境:HTTP client + 导出根目录 + 脚本拦截门
+/// 下载环境:HTTP client + 导出库 + 脚本拦截门 + 写标签配置
 /// (`process_target` 从 [`PlayerCore`] 取齐,单测各自注入)。
 #[derive(Clone, Copy)]
 pub(crate) struct DownloadEnv<'a> {
     /// 复用的 HTTP client。
     pub(crate) http: &'a reqwest::Client,
 
-    /// 永久导出根目录(如 `~/Music/mineral`)。
-    pub(crate) music_dir: &'a Path,
+    /// 永久导出库(根目录 + 路径模板 + 导出索引)。
+    pub(crate) exports: &'a Exports,
 
     /// 脚本拦截门(`before_download`;无脚本恒放行)。
     pub(crate) hooks: &'a crate::hook_bridge::HookGate,
+
+    /// 下载后写标签配置(`download.tags`)。
+    pub(crate) tags: &'a mineral_config::DownloadTagsConfig,
 }
 
 /// 下载一首歌:**流式** GET(边下边写、边算速度写进度)→ 永久导出。
@@ -110,7 +97,7 @@ pub(crate) struct DownloadEnv<'a> {
 ///   - `music_dir`: 永久导出根目录(如 `~/Music/mineral`)
 ///   - `song`: 要下载的歌
 ///   - `quality`: 下载音质
-///   - `env`: 下载环境(HTTP client + 导出根目录 + 脚本拦截门)
+///   - `env`: 下载环境(HTTP client + 导出库 + 脚本拦截门 + 写标签配置)
 ///   - `progress`: 下载进度共享态(本函数实时写 `bytes_done`/`bytes_total`/`speed_bps`)
 ///   - `speed_tick`: 测速刷新节流间隔(配置 `daemon.download_speed_tick_ms`)
 ///
@@ -127,11 +114,13 @@ pub(crate) async fn download_song(
 ) -> color_eyre::Result<DownloadOutcome> {
     let DownloadEnv {
         http,
-        music_dir,
+        exports,
         hooks,
+        tags,
     } = *env;
-    // 1. 幂等:该歌该音质已在导出库 → 跳过(文件系统即真相,按 <album>/<title>.* 反查)。
-    if crate::resolve::probe_export(music_dir, song, quality).is_some() {
+    let music_dir = exports.root().ok_or_else(|| eyre!("下载导出目录不可用"))?;
+    // 1. 幂等:该歌该音质已在导出库 → 跳过(查导出索引,再按默认布局反查历史下载)。
+    if exports.find(song, quality).is_some() {
         mineral_log::debug!(target: "download", song_id = song.id.as_str(), "已下载,跳过");
         return Ok(DownloadOutcome::Skipped {
             cause: SkipCause::AlreadyExists,
@@ -183,10 +172,9 @@ pub(crate) async fn download_song(
             });
         }
     }
-    let (subdir, file_name) = library_relpath(song, quality, play_url.format.as_ref());
-    // 命名即身份:不做 ` (N)` 去重——同名直接落同一路径(本曲重下已被上面的幂等挡住;同源同专辑
-    // 同名的另一首歌会与之共用一个文件,概率极低,换来「文件系统即唯一真相」)。
-    let export = music_dir.join(&subdir).join(&file_name);
+    // 按模板算落点;撞名(别的歌已占用)留到落盘时追加 ` (N)`,本曲重下已被上面的幂等挡住。
+    let (subdir, file_name) = exports.relpath(song, quality, play_url.format.as_ref());
+    let planned = music_dir.join(&subdir).join(&file_name);
     let remote = match play_url.url {
         MediaUrl::Remote(u) => u,
         MediaUrl::Local(p) => {
@@ -194,13 +182,13 @@ pub(crate) async fn download_song(
         }
     };
 
-    // 3. 流式下载到 `<export>.part-dl`,边写边更新进度 / 速度。
-    if let Some(parent) = export.parent() {
+    // 3. 流式下载到 `<落点>.part-dl`,边写边更新进度 / 速度。
+    if let Some(parent) = planned.parent() {
         tokio::fs::create_dir_all(parent)
             .await
             .wrap_err_with(|| format!("创建导出目录失败 {}", parent.display()))?;
     }
-    let part = export.with_extension("part-dl");
+    let part = planned.with_extension("part-dl");
     stream_to_file(
         http,
         remote,
@@ -211,10 +199,12 @@ pub(crate) async fn download_song(
     )
     .await?;
 
-    // 4. 完成 → rename 为正式导出(永久)。
-    tokio::fs::rename(&part, &export)
-        .await
-        .wrap_err_with(|| format!("rename 导出失败 {}", export.display()))?;
+    // 4. 完成 → rename 为正式导出(永久)并登记导出索引。
+    let export = exports
+        .place(song, quality, &part, &subdir, &file_name)
+        .await?;
+    // 5. 写标签 / 封面 / 歌词(失败只 warn,不影响结局)。
+    crate::tagging::tag_export(channel, http, tags, song, &export).await;
     mineral_log::info!(target: "download", song_id = song.id.as_str(), path = %export.display(), "下载完成");
     Ok(DownloadOutcome::Downloaded {
         path: export,
@@ -326,7 +316,8 @@ pub(crate) struct Capturing {
 }
 
 /// 以远端 URL 起播,并(缓存可用时)把下载字节 capture 到临时文件、登记 [`Capturing`]
-/// 供下完 / 播完入缓存;缓存禁用时退回普通播放。
+/// 供下完 / 播完入缓存;缓存禁用时退回普通播放。channel 直接给出本地文件(本地曲库源)
+/// 时文件已在盘上,不 capture,直接播后补包络;远端流起播后查库施加归一化增益。
 ///
 /// # Params:
 ///   - `player`: 播放核心(取 audio / media_cache、登记 capturing)
@@ -334,6 +325,11 @@ pub(crate) struct Capturing {
 ///   - `pu`: 该歌的播放 URL(取 `url` 起播、`format` 定扩展名)
 ///   - `quality`: 入库音质(与请求一致)
 pub(crate) fn play_capturing(player: &PlayerCore, song: &Song, pu: &PlayUrl, quality: BitRate) {
+    if let MediaUrl::Local(path) = &pu.url {
+        player.audio().play(pu.url.clone(), Vec::new(), pu.layout);
+        player.ensure_envelope(song.id.clone(), path.clone());
+        return;
+    }
     match player.media_cache().capture_path(&song.id, quality) {
         Some(path) => {
             player.audio().play_capturing(
@@ -353,6 +349,7 @@ pub(crate) fn play_capturing(player: &PlayerCore, song: &Song, pu: &PlayUrl, qua
             .audio()
             .play(pu.url.clone(), pu.stream_headers.clone(), pu.layout),
     }
+    player.normalize_streamed(song.id.clone());
 }
 
 /// 把一首已下完的 capture 文件后台收编进缓存(spawn_blocking,不阻塞 loop)。
@@ -442,6 +439,8 @@ pub(crate) async fn worker(
         // 本批处理完。pending 归 0(无后续)→ 会话收尾:出完成提示 + 复位进度。
         if pending.fetch_sub(1, std::sync::atomic::Ordering::AcqRel) == 1 {
             finalize(&player);
+            // 下载记录变了:`downloaded:` 类智能歌单随之重算。
+            player.spawn_aggregate_refresh();
         }
     }
 }
@@ -452,7 +451,7 @@ pub(crate) async fn worker(
 ///   - `player`: 播放核心
 ///   - `target`: 下载目标
 async fn process_target(player: &PlayerCore, target: DownloadTarget) {
-    let (Some(http), Some(music_dir)) = (player.http(), player.music_dir()) else {
+    let (Some(http), Some(_)) = (player.http(), player.exports().root()) else {
         player.notify().toast(
             mineral_protocol::ToastKind::Warn,
             "下载不可用(无 HTTP client / 音乐目录)".to_owned(),
@@ -482,8 +481,9 @@ async fn process_target(player: &PlayerCore, target: DownloadTarget) {
     let hooks = player.hook_gate();
     let env = DownloadEnv {
         http,
-        music_dir,
+        exports: player.exports(),
         hooks: &hooks,
+        tags: player.download_tags(),
     };
     for song in &songs {
         {
@@ -515,6 +515,8 @@ async fn process_target(player: &PlayerCore, target: DownloadTarget) {
                 player
                     .notify()
                     .download_completed(song, &path, quality, format.as_ref());
+                // 维表先于事实行:没播过的下载也要有元数据,智能歌单才列得出它。
+                player.inner.stats.upsert_song(song);
                 let path_str = path.display().to_string();
                 record_download(
                     player,
@@ -623,8 +625,7 @@ async fn collect_songs(player: &PlayerCore, target: &DownloadTarget) -> Result<V
                 .channel_for(id.namespace())
                 .cloned()
                 .ok_or_else(|| "下载失败: 该来源无对应 channel".to_owned())?;
-            channel
-                .playlist_detail(id)
+            mineral_channel_core::discover::resolve_playlist(channel.as_ref(), id)
                 .await
                 .map(|pl| pl.songs)
                 .map_err(|e| {
@@ -678,6 +679,10 @@ mod tests {
         let url = serve_once(b"FAKEFLACDATA".to_vec()).await?;
         let channel = UrlChannel { url };
         let http = reqwest::Client::new();
+        let tags = mineral_config::Config::defaults()?
+            .download()
+            .tags()
+            .clone();
         let progress = Arc::new(Mutex::new(DownloadProgress::default()));
         let s = song();
 
@@ -685,8 +690,9 @@ mod tests {
             &channel,
             &super::DownloadEnv {
                 http: &http,
-                music_dir: &music_dir,
+                exports: &crate::exports::Exports::unindexed(Some(music_dir.clone())),
                 hooks: &crate::hook_bridge::HookGate::disabled(),
+                tags: &tags,
             },
             &s,
             BitRate::Lossless,
@@ -752,8 +758,12 @@ mod tests {
             &channel,
             &super::DownloadEnv {
                 http: &reqwest::Client::new(),
-                music_dir: &music_dir,
+                exports: &crate::exports::Exports::unindexed(Some(music_dir.clone())),
                 hooks: &gate,
+                tags: &mineral_config::Config::defaults()?
+                    .download()
+                    .tags()
+                    .clone(),
             },
             &song(),
             BitRate::Lossless,
@@ -795,8 +805,12 @@ mod tests {
             &channel,
             &super::DownloadEnv {
                 http: &reqwest::Client::new(),
-                music_dir: &music_dir,
+                exports: &crate::exports::Exports::unindexed(Some(music_dir.clone())),
                 hooks: &gate,
+                tags: &mineral_config::Config::defaults()?
+                    .download()
+                    .tags()
+                    .clone(),
             },
             &song(),
             BitRate::Lossless,
@@ -5,16 +5,22 @@
 //! **随 `CurrentSync` 与 `current_song` 原子送达 client**(而非独立事件),从根上免去
 //! 「包络先于 track 更新到达 → 归属对不上被丢弃」的到达乱序竞态。
 //! in-flight 守卫防同曲重复解码:开播 / gapless 预排 / 缓存收割多路都可能触发。
+//!
+//! 同一遍离线解码还产出整曲响度(R128),与包络同行落库;拿到本地文件这一刻也是读
+//! ReplayGain 标签的时机——归一化增益的编排见 [`crate::loudness`]。
 
 use std::path::PathBuf;
 
-use mineral_model::{Envelope, SongId};
+use mineral_model::{Envelope, Loudness, SongId};
 
 use crate::player::PlayerCore;
 
 impl PlayerCore {
     /// 确保一首歌的包络可用并推给 client:db 命中直推;缺失则离线解码、落库后推。
-    /// 任何失败只记日志(渲染侧自然回落普通进度条),不冒泡。
+    /// 顺带施加归一化增益:文件有 ReplayGain 标签即刻用标签,否则用(命中 / 算出的)
+    /// 响度分析。任何失败只记日志(渲染侧自然回落普通进度条、音量保持原样),不冒泡。
+    ///
+    /// 须在该曲 `play` / `append_next` **之后**调:增益推到引擎槽,起播会把槽复位。
     ///
     /// # Params:
     ///   - `song_id`: 目标歌曲
@@ -23,9 +29,22 @@ impl PlayerCore {
     pub(crate) fn ensure_envelope(&self, song_id: SongId, path: PathBuf) {
         let player = self.clone();
         tokio::spawn(async move {
-            match player.cached_envelope(&song_id).await {
-                Some(envelope) => player.deliver_envelope(song_id, envelope),
-                None => player.compute_envelope(song_id, path).await,
+            let tagged = player.apply_tag_gain(&song_id, &path).await;
+            let cached = match player.cached_envelope(&song_id).await {
+                Some(envelope) => player
+                    .cached_loudness(&song_id)
+                    .await
+                    .map(|loudness| (envelope, loudness)),
+                None => None,
+            };
+            match cached {
+                Some((envelope, loudness)) => {
+                    if !tagged {
+                        player.apply_analysis_gain(&song_id, &loudness);
+                    }
+                    player.deliver_envelope(song_id, envelope);
+                }
+                None => player.compute_envelope(song_id, path, tagged).await,
             }
         });
     }
@@ -60,8 +79,9 @@ impl PlayerCore {
         }
     }
 
-    /// 离线解码整曲算包络(in-flight 去重),成功后落库并推送。
-    async fn compute_envelope(&self, song_id: SongId, path: PathBuf) {
+    /// 离线解码整曲算包络 + 响度(in-flight 去重),成功后落库并推送;`tagged`(标签已给出
+    /// 增益)时不再用分析结果覆盖增益。
+    async fn compute_envelope(&self, song_id: SongId, path: PathBuf, tagged: bool) {
         if !self
             .inner
             .envelope_inflight
@@ -72,14 +92,13 @@ impl PlayerCore {
         }
         let params = self.inner.envelope_params.clone();
         let computed =
-            tokio::task::spawn_blocking(move || mineral_audio::envelope_from_file(&path, &params))
-                .await;
+            tokio::task::spawn_blocking(move || mineral_audio::analyze_file(&path, &params)).await;
         self.inner
             .envelope_inflight
             .lock()
             .remove(&song_id.qualified());
-        let envelope = match computed {
-            Ok(Ok(envelope)) => envelope,
+        let (envelope, loudness) = match computed {
+            Ok(Ok(analysis)) => analysis,
             Ok(Err(e)) => {
                 mineral_log::warn!(target: "player", song = song_id.as_str(), error = mineral_log::chain(&e), "包络计算失败");
                 return;
@@ -89,14 +108,28 @@ impl PlayerCore {
                 return;
             }
         };
-        let scope = self.persist().scope(song_id.namespace());
-        if let Err(e) = scope.put_envelope(&song_id, &envelope).await {
-            // 落库失败仍采纳:本次会话波形照常,只是重启后要重算。
+        if let Err(e) = self.store_analysis(&song_id, &envelope, &loudness).await {
+            // 落库失败仍采纳:本次会话波形 / 增益照常,只是重启后要重算。
             mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "包络落库失败");
         }
+        if !tagged {
+            self.apply_analysis_gain(&song_id, &loudness);
+        }
         self.deliver_envelope(song_id, envelope);
     }
 
+    /// 包络与响度同行落库(响度挂在包络行上,须先写包络)。
+    async fn store_analysis(
+        &self,
+        song_id: &SongId,
+        envelope: &Envelope,
+        loudness: &Loudness,
+    ) -> color_eyre::Result<()> {
+        let scope = self.persist().scope(song_id.namespace());
+        scope.put_envelope(song_id, envelope).await?;
+        scope.put_loudness(song_id, loudness).await
+    }
+
     /// 把包络落进当前曲 slot(见 [`crate::state::State::adopt_envelope`]):归属当前曲
     /// 才落并 bump `current` 版本,下次 `sync` 即随 `CurrentSync` 送达 client;
     /// 非当前曲(迟到 / 预排下一曲)静默忽略。
@@ -0,0 +1,35 @@
+//! 均衡器的运行期切换(配置 `audio.eq`):按预设名查表 → 推给音频引擎。
+//!
+//! 起始预设由 [`crate::config::ServerConfig`] 在引擎启动时带入;此后经 `SetEq` 请求 /
+//! `mineral.player.set_eq` 切换,只改内存不落盘,下次启动回到配置值。
+
+use color_eyre::eyre::eyre;
+
+use crate::config::eq_params_from;
+use crate::player::PlayerCore;
+
+impl PlayerCore {
+    /// 按名切换均衡预设,即时作用于正在播放的曲目。
+    ///
+    /// # Params:
+    ///   - `name`: 预设名;`None` = 旁路
+    ///
+    /// # Return:
+    ///   预设名未定义时报错(不改动当前均衡)。
+    pub(crate) fn set_eq_preset(&self, name: Option<&str>) -> color_eyre::Result<()> {
+        let params = match name {
+            Some(n) => {
+                let preset = self
+                    .inner
+                    .eq
+                    .preset_named(n)
+                    .ok_or_else(|| eyre!("未定义的均衡预设 `{n}`"))?;
+                Some(eq_params_from(preset))
+            }
+            None => None,
+        };
+        mineral_log::debug!(target: "player", preset = ?name, "切换均衡预设");
+        self.audio().set_eq(params);
+        Ok(())
+    }
+}
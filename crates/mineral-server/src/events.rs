@@ -3,9 +3,9 @@
 //! 每 tick 一次 drain:`PlayUrlReady` / `LyricsReady` 在 server 内部消化
 //! (进 PlayerSync 的 current 重段,不转发);`PlaylistsFetched` 进歌单库
 //! 聚合态(client 只见出口变换后的 LibrarySnapshot);`PlaylistWriteDone`
-//! 成功时先触发缓存收敛再转发;其余经 event hub 推送给订阅 client。
+//! 成功时先触发缓存收敛再转发;`LocalScanDone` 先重拉本地源歌单再转发;其余经 event hub 推送给订阅 client。
 
-use mineral_model::{PlayUrl, Song, SongId};
+use mineral_model::{PlayUrl, Song, SongId, SourceKind};
 use mineral_task::{ChannelFetchKind, PlaylistWriteOp, Priority, TaskEvent, TaskKind, WriteError};
 
 use crate::player::PlayerCore;
@@ -83,6 +83,16 @@ impl PlayerCore {
                     }
                     forward.push(TaskEvent::PlaylistWriteDone { op, error });
                 }
+                // 文件夹 / m3u 歌单是扫描结果的投影:重扫后走同一条读管线重拉。
+                TaskEvent::LocalScanDone { report } => {
+                    self.inner.scheduler.submit(
+                        TaskKind::ChannelFetch(ChannelFetchKind::MyPlaylists {
+                            source: SourceKind::LOCAL,
+                        }),
+                        Priority::Background,
+                    );
+                    forward.push(TaskEvent::LocalScanDone { report });
+                }
                 other => forward.push(other),
             }
         }
@@ -96,7 +106,18 @@ impl PlayerCore {
     /// (netease 写后 `trackUpdateTime` 必然变化,自动命中"版本变 → 全拉"分支。)
     fn refresh_after_write(&self, op: &PlaylistWriteOp) {
         match op {
-            PlaylistWriteOp::AddSongs { id, .. } | PlaylistWriteOp::RemoveSongs { id, .. } => {
+            // 聚合源的歌单全由本地 persist / stats 现算:列表 + 各张 detail 一并重推
+            // (智能歌单的描述即表达式,改了曲目随之变)。新加进混源歌单的歌可能还缺
+            // meta,顺带触发补全,补齐后渐进出现。
+            _ if op.target_source() == SourceKind::MINERAL => {
+                self.spawn_aggregate_refresh();
+                if matches!(op, PlaylistWriteOp::AddSongs { .. }) {
+                    self.spawn_meta_backfill();
+                }
+            }
+            PlaylistWriteOp::AddSongs { id, .. }
+            | PlaylistWriteOp::RemoveSongs { id, .. }
+            | PlaylistWriteOp::MoveSong { id, .. } => {
                 self.inner.scheduler.submit(
                     TaskKind::ChannelFetch(ChannelFetchKind::PlaylistDetail { id: id.clone() }),
                     Priority::User,
@@ -105,6 +126,7 @@ impl PlayerCore {
             PlaylistWriteOp::Create { source, .. } => {
                 self.submit_my_playlists(*source);
             }
+            PlaylistWriteOp::CreateSmart { .. } => self.submit_my_playlists(op.target_source()),
             PlaylistWriteOp::Delete { id }
             | PlaylistWriteOp::Rename { id, .. }
             | PlaylistWriteOp::SetDescription { id, .. } => {
@@ -151,6 +173,10 @@ impl PlayerCore {
             Route::Current(song) => {
                 mineral_log::debug!(target: "player", song_id = song_id.as_str(), action = "play", "play url ready");
                 if let Some(song) = song {
+                    // 本地曲库曲起播时路径未知、歌词推迟到此:按取回的文件就近查找。
+                    if song.source() == SourceKind::LOCAL {
+                        self.fetch_lyrics(&song, crate::lyrics::local_file(&play_url));
+                    }
                     // 拦截桥:无脚本同步直走,有脚本异步裁决(play_url 已在锁内写过,
                     // 桥内回填同值幂等;改写时回填改写值)。
                     crate::hook_bridge::before_stream(self, &song, play_url);
@@ -268,6 +294,7 @@ impl PlayerCore {
                 mineral_stats::FetchKind::ArtistAlbums
             }
             mineral_task::ChannelFetchKindTag::AlbumDetail => mineral_stats::FetchKind::AlbumDetail,
+            mineral_task::ChannelFetchKindTag::SongsDetail => mineral_stats::FetchKind::SongsDetail,
         };
         let (actor, trigger) = if from_user {
             (
@@ -361,6 +388,13 @@ impl PlayerCore {
                     name: name.clone(),
                 },
             ),
+            PlaylistWriteOp::CreateSmart { name, .. } => (
+                PlaylistOpKind::Create,
+                PlaylistRef::Creating {
+                    source: op.target_source(),
+                    name: name.clone(),
+                },
+            ),
             PlaylistWriteOp::Delete { id } => {
                 (PlaylistOpKind::Delete, PlaylistRef::Existing(id.clone()))
             }
@@ -370,6 +404,9 @@ impl PlayerCore {
             PlaylistWriteOp::RemoveSongs { id, .. } => {
                 (PlaylistOpKind::Remove, PlaylistRef::Existing(id.clone()))
             }
+            PlaylistWriteOp::MoveSong { id, .. } => {
+                (PlaylistOpKind::Move, PlaylistRef::Existing(id.clone()))
+            }
             PlaylistWriteOp::Rename { id, .. } => {
                 (PlaylistOpKind::Rename, PlaylistRef::Existing(id.clone()))
             }
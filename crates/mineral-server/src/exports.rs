@@ -0,0 +1,330 @@
+//! 下载导出库:导出根目录 + 路径模板 + 「歌曲 + 音质 → 文件」索引。
+//!
+//! 导出路径由用户模板决定、不能从歌曲反推,故每次下载落盘后登记进 `download_exports` 索引;
+//! 播放命中与下载幂等都先查索引。索引建立前的历史下载(以及手动拷进来的文件)按默认布局
+//! 反查兜底——但已被别的歌登记的路径不算,免得同名歌互相冒认。
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{WrapErr, eyre};
+use mineral_config::{DownloadConfig, PathTemplate};
+use mineral_model::{AudioFormat, BitRate, Song};
+use mineral_persist::{CacheIndex, ServerStore};
+use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
+
+use crate::media_cache::cache_key;
+use crate::path_template::render;
+
+/// 下载导出库。线程安全(索引自带锁,`claimed` 另一把)。
+pub(crate) struct Exports {
+    /// 导出根目录;`None` = 下载不可用(解析失败)。
+    root: Option<PathBuf>,
+
+    /// 导出路径模板。
+    template: PathTemplate,
+
+    /// 歌曲 + 音质 → 导出文件(键同音频缓存 [`cache_key`];relpath 相对 `root`)。
+    index: CacheIndex,
+
+    /// 已登记的相对路径(旧布局兜底反查时排除别人的文件)。
+    claimed: Mutex<FxHashSet<String>>,
+}
+
+impl Exports {
+    /// 按配置打开导出库:解析根目录、载入索引(模板已在配置落型时校验)。任一环节失败只
+    /// warn 并降级(根目录解析失败 → 下载不可用;索引打不开 → 只按旧布局反查)。
+    ///
+    /// # Params:
+    ///   - `persist`: 持久化句柄(索引落其 `mineral.db`)
+    ///   - `config`: 下载段配置(`dir` / `path_template`)
+    ///
+    /// # Return:
+    ///   就绪的导出库。
+    pub(crate) async fn open(persist: &ServerStore, config: &DownloadConfig) -> Self {
+        let template = config.path_template().clone();
+        let Some(root) = export_root(config.dir().as_deref()) else {
+            return Self::new(None, template, CacheIndex::disabled());
+        };
+        let index = persist
+            .download_exports(root.clone())
+            .await
+            .unwrap_or_else(|e| {
+                mineral_log::warn!(target: "download", error = mineral_log::chain(&e), "下载导出索引打开失败,只按默认布局反查");
+                CacheIndex::disabled()
+            });
+        Self::new(Some(root), template, index)
+    }
+
+    /// 组装导出库并从索引快照建 `claimed`。
+    ///
+    /// # Params:
+    ///   - `root`: 导出根目录
+    ///   - `template`: 路径模板
+    ///   - `index`: 导出索引
+    ///
+    /// # Return:
+    ///   导出库。
+    pub(crate) fn new(root: Option<PathBuf>, template: PathTemplate, index: CacheIndex) -> Self {
+        let claimed = index
+            .snapshot()
+            .entries
+            .into_iter()
+            .map(|e| e.relpath)
+            .collect::<FxHashSet<String>>();
+        Self {
+            root,
+            template,
+            index,
+            claimed: Mutex::new(claimed),
+        }
+    }
+
+    /// 无索引、默认模板的导出库(只按旧布局反查;单测用)。
+    ///
+    /// # Params:
+    ///   - `root`: 导出根目录;`None` = 下载不可用
+    ///
+    /// # Return:
+    ///   导出库。
+    #[cfg(test)]
+    pub(crate) fn unindexed(root: Option<PathBuf>) -> Self {
+        Self::new(root, PathTemplate::default(), CacheIndex::disabled())
+    }
+
+    /// 导出根目录;`None` = 下载不可用。
+    pub(crate) fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// 找 `song` 该音质的导出文件:先查索引,再按默认布局反查历史下载。
+    ///
+    /// # Params:
+    ///   - `song`: 歌曲
+    ///   - `quality`: 音质
+    ///
+    /// # Return:
+    ///   命中且文件存在返回绝对路径,否则 `None`。
+    pub(crate) fn find(&self, song: &Song, quality: BitRate) -> Option<PathBuf> {
+        let root = self.root.as_deref()?;
+        if let Some(path) = self.index.get(&cache_key(&song.id, quality)) {
+            return Some(path);
+        }
+        let legacy = crate::resolve::probe_export(root, song, quality)?;
+        let rel = legacy
+            .strip_prefix(root)
+            .ok()?
+            .to_string_lossy()
+            .into_owned();
+        (!self.claimed.lock().contains(&rel)).then_some(legacy)
+    }
+
+    /// 按模板算 `song` 的导出相对落点(未去重;真正落点以 [`Self::place`] 为准)。
+    ///
+    /// # Params:
+    ///   - `song`: 歌曲
+    ///   - `quality`: 下载音质
+    ///   - `format`: 实际格式
+    ///
+    /// # Return:
+    ///   `(subdir, file_name)`。
+    pub(crate) fn relpath(
+        &self,
+        song: &Song,
+        quality: BitRate,
+        format: Option<&AudioFormat>,
+    ) -> (String, String) {
+        render(&self.template, song, quality, format)
+    }
+
+    /// 把下完的临时文件移到正式落点(撞名追加 ` (N)`)并登记索引。登记失败只 warn:
+    /// 文件已就位,下次仍可能按默认布局反查到。
+    ///
+    /// # Params:
+    ///   - `song`: 歌曲
+    ///   - `quality`: 下载音质(索引键)
+    ///   - `part`: 已下完的临时文件
+    ///   - `subdir`: 模板渲染的目录段
+    ///   - `file_name`: 模板渲染的文件名
+    ///
+    /// # Return:
+    ///   正式导出文件的绝对路径。
+    pub(crate) async fn place(
+        &self,
+        song: &Song,
+        quality: BitRate,
+        part: &Path,
+        subdir: &str,
+        file_name: &str,
+    ) -> color_eyre::Result<PathBuf> {
+        let root = self
+            .root
+            .as_deref()
+            .ok_or_else(|| eyre!("下载导出目录不可用"))?;
+        let rel = free_relpath(root, subdir, file_name);
+        let dst = root.join(&rel);
+        tokio::fs::rename(part, &dst)
+            .await
+            .wrap_err_with(|| format!("rename 导出失败 {}", dst.display()))?;
+        let bytes = tokio::fs::metadata(&dst)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        if let Err(e) = self
+            .index
+            .record(&cache_key(&song.id, quality), &rel, bytes)
+            .await
+        {
+            mineral_log::warn!(target: "download", song_id = song.id.as_str(), error = mineral_log::chain(&e), "下载导出索引登记失败");
+        }
+        self.claimed.lock().insert(rel);
+        Ok(dst)
+    }
+}
+
+/// 导出根目录:config(`download.dir`)> 平台默认(`~/Music/mineral`)。
+/// config.lua 是唯一用户真相源,不设环境变量逃逸口。
+///
+/// # Params:
+///   - `config_dir`: 配置的下载目录(`download.dir`;`None` = 未配置)
+///
+/// # Return:
+///   根目录;平台默认解析失败 warn 后为 `None`(下载不可用)。
+fn export_root(config_dir: Option<&Path>) -> Option<PathBuf> {
+    if let Some(d) = config_dir {
+        return Some(d.to_path_buf());
+    }
+    match mineral_paths::music_export_dir() {
+        Ok(d) => Some(d),
+        Err(e) => {
+            mineral_log::warn!(target: "download", error = mineral_log::chain(&e), "解析音乐导出目录失败,下载不可用");
+            None
+        }
+    }
+}
+
+/// 选一个盘上未被占用的相对路径:`<subdir>/<file_name>`,撞名追加 ` (N)`。
+///
+/// # Params:
+///   - `root`: 导出根目录
+///   - `subdir`: 目录段(可为空 = 根下)
+///   - `file_name`: 期望文件名(含扩展名)
+///
+/// # Return:
+///   相对 `root` 的路径(`/` 分隔)。
+fn free_relpath(root: &Path, subdir: &str, file_name: &str) -> String {
+    let join = |name: &str| {
+        if subdir.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{subdir}/{name}")
+        }
+    };
+    let first = join(file_name);
+    if !root.join(&first).exists() {
+        return first;
+    }
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) => (stem, Some(ext)),
+        None => (file_name, None),
+    };
+    for n in 2_u32..=9999 {
+        let rel = match ext {
+            Some(e) => join(&format!("{stem} ({n}).{e}")),
+            None => join(&format!("{stem} ({n})")),
+        };
+        if !root.join(&rel).exists() {
+            return rel;
+        }
+    }
+    first // 极端兜底(几乎不可能):覆盖 first,绝不 panic
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{AlbumId, AlbumRef, BitRate, Song, SongId, SourceKind};
+    use mineral_persist::ServerStore;
+
+    use super::Exports;
+    use mineral_config::PathTemplate;
+
+    /// 同专辑同名的两首歌。
+    fn twin(id: &str) -> Song {
+        Song::builder()
+            .id(SongId::new(SourceKind::NETEASE, id))
+            .name("Intro".to_owned())
+            .album(Some(AlbumRef {
+                id: AlbumId::new(SourceKind::NETEASE, "1"),
+                name: "Live".to_owned(),
+            }))
+            .build()
+    }
+
+    /// 落盘 + 登记:自定义模板下按索引找回;同名第二首追加 ` (2)` 而不覆盖、不冒认;
+    /// 重开索引后仍找得到。
+    #[tokio::test]
+    async fn place_indexes_and_dedups_collisions() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path().join("music");
+        let db = dir.path().join("m.db");
+        let persist = ServerStore::open(&db).await?;
+        let open = |persist: &ServerStore| {
+            let persist = persist.clone();
+            let root = root.clone();
+            async move {
+                let index = persist.download_exports(root.clone()).await?;
+                let template = PathTemplate::parse("{album}/{title}")?;
+                color_eyre::Result::<Exports>::Ok(Exports::new(Some(root), template, index))
+            }
+        };
+        let exports = open(&persist).await?;
+        let (a, b) = (twin("1"), twin("2"));
+
+        let mut placed = Vec::new();
+        for s in [&a, &b] {
+            assert!(exports.find(s, BitRate::Lossless).is_none());
+            let (subdir, name) = exports.relpath(s, BitRate::Lossless, None);
+            std::fs::create_dir_all(root.join(&subdir))?;
+            let part = root.join(&subdir).join("x.part-dl");
+            std::fs::write(&part, s.id.as_str())?;
+            placed.push(
+                exports
+                    .place(s, BitRate::Lossless, &part, &subdir, &name)
+                    .await?,
+            );
+        }
+        assert_eq!(placed.first(), Some(&root.join("Live/Intro.flac")));
+        assert_eq!(placed.get(1), Some(&root.join("Live/Intro (2).flac")));
+
+        let reopened = open(&persist).await?;
+        assert_eq!(reopened.find(&b, BitRate::Lossless).as_ref(), placed.get(1));
+        assert!(reopened.find(&a, BitRate::Exhigh).is_none(), "音质独立");
+        Ok(())
+    }
+
+    /// 无索引时按默认布局反查历史下载;已被别的歌登记的路径不冒认。
+    #[tokio::test]
+    async fn legacy_layout_fallback_skips_claimed() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path().join("music");
+        let legacy = root.join("netease/lossless/Live/Intro.flac");
+        std::fs::create_dir_all(root.join("netease/lossless/Live"))?;
+        std::fs::write(&legacy, b"old")?;
+
+        let unindexed = Exports::unindexed(Some(root.clone()));
+        assert_eq!(unindexed.find(&twin("1"), BitRate::Lossless), Some(legacy));
+
+        let persist = ServerStore::open(&dir.path().join("m.db")).await?;
+        let index = persist.download_exports(root.clone()).await?;
+        index
+            .record("netease:1:lossless", "netease/lossless/Live/Intro.flac", 3)
+            .await?;
+        let exports = Exports::new(Some(root), PathTemplate::default(), index);
+        assert!(exports.find(&twin("1"), BitRate::Lossless).is_some());
+        assert!(
+            exports.find(&twin("2"), BitRate::Lossless).is_none(),
+            "同名别曲的文件不冒认"
+        );
+        Ok(())
+    }
+}
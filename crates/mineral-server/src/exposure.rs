@@ -0,0 +1,68 @@
+//! 网络遥控入口(`daemon.http` 等)的开放面守门:没有口令的入口不出本机;
+//! 口令比对不按应答时延泄露前缀。
+
+use std::net::SocketAddr;
+
+use color_eyre::eyre::bail;
+
+/// 未配口令(或为空串)时只许绑回环地址。daemon 入口在 bind 之前调用,配置不合法直接
+/// 拒绝启动。
+///
+/// # Params:
+///   - `addr`: 监听地址
+///   - `secret`: 配置的访问口令(如 HTTP 令牌)
+///   - `secret_key`: 口令的配置键名(报错时指给用户,如 `daemon.http.token`)
+///
+/// # Errors
+/// 非回环地址且无口令。
+pub fn check_exposure(
+    addr: &SocketAddr,
+    secret: Option<&str>,
+    secret_key: &str,
+) -> color_eyre::Result<()> {
+    if !addr.ip().is_loopback() && secret.is_none_or(str::is_empty) {
+        bail!("{addr} is reachable from the network; set {secret_key}");
+    }
+    Ok(())
+}
+
+/// 口令比较:长度相同时逐字节异或累积,不在首个不同字节处提前返回(防按应答时延
+/// 逐字节试出口令)。
+pub(crate) fn secret_eq(given: &str, want: &str) -> bool {
+    given.len() == want.len()
+        && given
+            .bytes()
+            .zip(want.bytes())
+            .fold(0_u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::{check_exposure, secret_eq};
+
+    /// 回环地址可以不设口令;对外地址没有口令(含空串)拒绝启动。
+    #[test]
+    fn exposure_requires_secret_off_loopback() -> color_eyre::Result<()> {
+        let local: SocketAddr = "127.0.0.1:6690".parse()?;
+        let local_v6: SocketAddr = "[::1]:6690".parse()?;
+        let lan: SocketAddr = "0.0.0.0:6690".parse()?;
+        let key = "daemon.http.token";
+        assert!(check_exposure(&local, None, key).is_ok());
+        assert!(check_exposure(&local_v6, None, key).is_ok());
+        assert!(check_exposure(&lan, None, key).is_err());
+        assert!(check_exposure(&lan, Some(""), key).is_err());
+        assert!(check_exposure(&lan, Some("s3cret"), key).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn secret_eq_is_exact() {
+        assert!(secret_eq("abc", "abc"));
+        assert!(!secret_eq("abd", "abc"));
+        assert!(!secret_eq("ab", "abc"));
+        assert!(!secret_eq("", "abc"));
+    }
+}
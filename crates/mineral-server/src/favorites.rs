@@ -275,9 +275,29 @@ impl PlayerCore {
         self.library_concluded(SourceKind::MINERAL, Some(playlists));
     }
 
-    /// 触发后台补 meta(单飞):sync 导入的红心先只有 id,后台扫全部缺 meta 的 loved 歌,逐源
-    /// 分块拉 `songs_detail` 回填 persist,渐进填满聚合面。已有 worker 在跑则只置 `pending`,由它
-    /// 收尾再扫一轮(coalesce sync 分源晚到的导入)。fire-and-forget,daemon 退出随 runtime 收。
+    /// 播放 / 下载记录变化后重推聚合歌单:智能歌单的曲目随 stats 现算,一次播放结算或一批
+    /// 下载收尾都可能让它变。先 [`flush`](crate::StatsRecorder::flush) 等埋点 actor 把刚投递
+    /// 的事件落库,再走 [`Self::refresh_aggregate_favorites`]。已有一轮在排队则合并(连切歌
+    /// 不叠加重算)。fire-and-forget。
+    pub(crate) fn spawn_aggregate_refresh(&self) {
+        if self.inner.aggregate_refresh.swap(true, Ordering::SeqCst) {
+            return; // 已有一轮排队;它 flush 后读到的是含本次的最新态
+        }
+        let player = self.clone();
+        tokio::spawn(async move {
+            player.inner.stats.flush().await;
+            player
+                .inner
+                .aggregate_refresh
+                .store(false, Ordering::SeqCst);
+            player.refresh_aggregate_favorites().await;
+        });
+    }
+
+    /// 触发后台补 meta(单飞):sync 导入的红心、加进混源歌单的歌都可能先只有 id,后台扫聚合面
+    /// 里全部缺 meta 的歌,逐源分块拉 `songs_detail` 回填 persist,渐进填满聚合面。已有 worker
+    /// 在跑则只置 `pending`,由它收尾再扫一轮(coalesce sync 分源晚到的导入)。fire-and-forget,
+    /// daemon 退出随 runtime 收。
     pub(crate) fn spawn_meta_backfill(&self) {
         self.inner.backfill.pending.store(true, Ordering::SeqCst);
         if self.inner.backfill.running.swap(true, Ordering::SeqCst) {
@@ -299,11 +319,11 @@ impl PlayerCore {
         });
     }
 
-    /// 扫一遍缺 meta 的 loved 歌,逐源分块拉 `songs_detail` 回填 persist,每块补完刷一次聚合面
+    /// 扫一遍缺 meta 的 loved 歌与混源歌单曲目,逐源分块拉 `songs_detail` 回填 persist,每块补完刷一次聚合面
     /// (渐进填充)。**source-neutral**:按各歌 namespace 走各自 channel、`buffer_unordered` 限并发,
     /// 不假设 `songs_detail` 是批量还是逐个。best-effort:无该源 channel / 不支持 / 失败都只 debug。
     async fn run_meta_backfill(&self) {
-        let missing = match self.persist().missing_meta_loved_ids().await {
+        let missing = match self.missing_meta_ids().await {
             Ok(ids) => ids,
             Err(e) => {
                 mineral_log::debug!(
@@ -376,6 +396,15 @@ impl PlayerCore {
         }
     }
 
+    /// 聚合面里全部缺 meta 的歌:loved 在前,混源歌单里的补在后(去重)。
+    async fn missing_meta_ids(&self) -> color_eyre::Result<Vec<SongId>> {
+        let mut ids = self.persist().missing_meta_loved_ids().await?;
+        let seen = ids.iter().cloned().collect::<FxHashSet<SongId>>();
+        let mixed = self.persist().mix_playlists().missing_meta_ids().await?;
+        ids.extend(mixed.into_iter().filter(|id| !seen.contains(id)));
+        Ok(ids)
+    }
+
     /// 远端镜像(best-effort,**锁外**):把新态同步到该源远端(如网易云红心)。无该源 channel /
     /// 不支持 / 未登录 / 网络失败都无害——本地已写。
     async fn mirror_remote_favorite(&self, id: &SongId, loved: bool) {
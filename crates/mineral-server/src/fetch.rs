@@ -0,0 +1,103 @@
+//! 请求-应答式入口(如 HTTP 遥控)的任务型读取:投一个 channel 取数任务,在 event
+//! hub 上等它的结果帧。
+//!
+//! 先订阅再投任务,结果帧不会早于订阅落地;按请求键配对,同 hub 上别的任务事件跳过。
+//! channel 失败不推结果帧,等满 [`FETCH_TIMEOUT`] 即放弃。
+
+use std::time::Duration;
+
+use mineral_channel_core::Page;
+use mineral_model::{SearchKind, Song, SourceKind};
+use mineral_protocol::Event;
+use mineral_task::{ChannelFetchKind, Priority, SearchPayload, TaskEvent, TaskKind};
+use tokio::sync::broadcast;
+
+use crate::client::{Client, ClientHandle};
+
+/// 等结果帧的上限(channel 取数失败时不会有结果帧)。
+pub(crate) const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 等不到结果的原因。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FetchFailure {
+    /// [`FETCH_TIMEOUT`] 内没有结果(源失败 / 未登录,或积压丢帧恰好丢了结果)。
+    TimedOut,
+
+    /// event hub 已关闭,daemon 正在退出。
+    ShuttingDown,
+}
+
+/// 投一个取数任务并等它的结果。
+///
+/// # Params:
+///   - `client`: 指令面
+///   - `events`: event hub(结果帧从这里来)
+///   - `fetch`: 取数任务
+///   - `pick`: 从任务事件里认出本次结果(别的任务事件返回 `None` 跳过)
+///
+/// # Errors
+/// 超时 / daemon 正在退出。
+pub(crate) async fn await_fetch<T>(
+    client: &ClientHandle,
+    events: &broadcast::Sender<Event>,
+    fetch: ChannelFetchKind,
+    mut pick: impl FnMut(TaskEvent) -> Option<T>,
+) -> Result<T, FetchFailure> {
+    let mut rx = events.subscribe();
+    client.submit_task(TaskKind::ChannelFetch(fetch), Priority::User);
+    let wait = async {
+        loop {
+            match rx.recv().await {
+                Ok(Event::Task(task)) => {
+                    if let Some(found) = pick(*task) {
+                        return Ok(found);
+                    }
+                }
+                // 积压丢帧若恰好丢了结果,按超时报。
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return Err(FetchFailure::ShuttingDown),
+            }
+        }
+    };
+    tokio::time::timeout(FETCH_TIMEOUT, wait)
+        .await
+        .unwrap_or(Err(FetchFailure::TimedOut))
+}
+
+/// 在某源上搜歌。
+///
+/// # Params:
+///   - `client`: 指令面
+///   - `events`: event hub
+///   - `source`: 已注册的音乐源
+///   - `query`: 关键词
+///   - `page`: 分页
+///
+/// # Errors
+/// 同 [`await_fetch`]。
+pub(crate) async fn search_songs(
+    client: &ClientHandle,
+    events: &broadcast::Sender<Event>,
+    source: SourceKind,
+    query: String,
+    page: Page,
+) -> Result<Vec<Song>, FetchFailure> {
+    let fetch = ChannelFetchKind::Search {
+        source,
+        kind: SearchKind::Song,
+        query: query.clone(),
+        page,
+    };
+    await_fetch(client, events, fetch, |event| match event {
+        TaskEvent::SearchResults {
+            source: got_source,
+            kind: SearchKind::Song,
+            query: got_query,
+            page: got_page,
+            payload: SearchPayload::Songs(songs),
+            ..
+        } if got_source == source && got_query == query && got_page == page => Some(songs),
+        _ => None,
+    })
+    .await
+}
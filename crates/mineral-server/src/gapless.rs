@@ -4,9 +4,13 @@
 //! 引擎([`mineral_audio`])在当前曲自然耗尽时已把下一曲零静音接上;服务端这边只需在
 //! 边界处把记账状态轮转过来(current=queued、queue_sel 推进、play_url/origin/capturing
 //! 轮转、歌词与预拉复位),**不**重新 `play_song`(音频没有中断)。
+//!
+//! 交叉淡化(配置 `audio.crossfade`)复用同一套预排:窗口按淡化时长提前打开,预排时逐曲
+//! 决定衔接方式([`transition_for`]),同专辑连播与单曲循环恒走 gapless。
 
 use std::path::PathBuf;
 
+use mineral_audio::Transition;
 use mineral_model::{BitRate, MediaUrl, PlayUrl, Song, SongId};
 use mineral_protocol::{PlayCursor, PlaybackOrigin};
 use mineral_task::{ChannelFetchKind, Priority, TaskKind};
@@ -145,8 +149,41 @@ fn prefetch_window_open(
     duration_ms.saturating_sub(position_ms) <= window_ms
 }
 
+/// 下一曲与当前曲的衔接方式:淡化未启用、同一首(单曲循环)、同一专辑的相邻曲走 gapless
+/// (保住专辑本身的无缝衔接),其余交叉淡化。
+///
+/// # Params:
+///   - `crossfade_ms`: 交叉淡化时长(未启用为 0)
+///   - `current`: 当前曲
+///   - `next`: 预排的下一曲
+///
+/// # Return:
+///   衔接方式。
+fn transition_for(crossfade_ms: u64, current: Option<&Song>, next: &Song) -> Transition {
+    let Some(current) = current else {
+        return Transition::Gapless;
+    };
+    let same_album = match (&current.album, &next.album) {
+        (Some(a), Some(b)) => a.id == b.id,
+        _ => false,
+    };
+    if crossfade_ms == 0 || current.id == next.id || same_album {
+        Transition::Gapless
+    } else {
+        Transition::Crossfade
+    }
+}
+
+/// 按当前状态算预排 `next` 的衔接方式(见 [`transition_for`])。
+fn next_transition(player: &PlayerCore, next: &Song) -> Transition {
+    let crossfade_ms = player.crossfade_ms();
+    player.with_state(|st| transition_for(crossfade_ms, st.current_song.as_ref(), next))
+}
+
 /// gapless 预排:进入曲终前窗口(配置 `daemon.gapless_prefetch_ms`)时,据下一曲来源预排 decoder 进引擎队列
 /// ——本地命中 / RepeatOne 直排,远端先取链 → [`on_prefetch_url_ready`] 再排。本曲只触发一次。
+/// 交叉淡化启用时窗口再提前一个淡化时长(下一曲须在淡化开始前就绪)。顺序模式已无下一曲时
+/// 先交给电台续播([`crate::radio`])补歌。
 pub(crate) fn check_prefetch(player: &PlayerCore) {
     let snap = player.audio_snapshot();
     let metadata_duration_ms =
@@ -155,10 +192,12 @@ pub(crate) fn check_prefetch(player: &PlayerCore) {
         snap.duration_ms,
         metadata_duration_ms,
         snap.position_ms,
-        player.gapless_prefetch_ms(),
+        player.gapless_prefetch_ms() + player.crossfade_ms(),
     ) {
         return;
     }
+    // 顺序模式队列到尾:电台续播(启用时)在窗口内补歌,落地后下个 tick 照常预排。
+    crate::radio::check_extend(player);
     let (cur_id, next) = player.with_state(|st| {
         let Some(cur_id) = st.current_song.as_ref().map(|s| s.id.clone()) else {
             return (None, None);
@@ -186,12 +225,10 @@ pub(crate) fn check_prefetch(player: &PlayerCore) {
         queue_repeatone(player, next);
     } else if let Some((path, quality, origin)) = crate::resolve::resolve_local(
         player.media_cache(),
-        player.music_dir(),
+        player.exports(),
         &next,
         player.playback_quality(),
     ) {
-        // 预排命中本地:提前把下一曲包络算好落库(adopt 边界经 replay 直取,零等待)。
-        player.ensure_envelope(next.id.clone(), path.clone());
         queue_local_next(player, next, path, quality, origin);
     } else {
         player.submit_task(
@@ -211,9 +248,16 @@ fn queue_repeatone(player: &PlayerCore, next: Song) {
     let Some(pu) = pu else {
         return; // 当前 url 尚未就绪(极少),本轮不排。
     };
-    player
-        .audio()
-        .append_next(pu.url.clone(), pu.stream_headers.clone(), pu.layout);
+    player.audio().append_next(
+        pu.url.clone(),
+        pu.stream_headers.clone(),
+        pu.layout,
+        Transition::Gapless,
+    );
+    let local = match &pu.url {
+        MediaUrl::Local(path) => Some(path.clone()),
+        MediaUrl::Remote(_) => None,
+    };
     let song_id = next.id.clone();
     player.with_state(|st| {
         st.queued = Some(Queued {
@@ -223,6 +267,11 @@ fn queue_repeatone(player: &PlayerCore, next: Song) {
             capturing: None,
         });
     });
+    // 同曲:当前曲的增益也推到预排槽(本地曲经包络编排读标签,远端查库)。
+    match local {
+        Some(path) => player.ensure_envelope(song_id.clone(), path),
+        None => player.normalize_streamed(song_id.clone()),
+    }
     record_prefetch(
         player,
         song_id,
@@ -242,9 +291,10 @@ fn queue_local_next(
     let pu = crate::resolve::local_play_url(&next, &path, quality);
     // 本地文件无需附加取流头;本地恒 seekable(Contiguous)。
     player.audio().append_next(
-        MediaUrl::Local(path),
+        MediaUrl::Local(path.clone()),
         Vec::new(),
         mineral_model::StreamLayout::Contiguous,
+        next_transition(player, &next),
     );
     let song_id = next.id.clone();
     player.with_state(|st| {
@@ -255,6 +305,9 @@ fn queue_local_next(
             capturing: None,
         });
     });
+    // 登记预排后再补包络 / 增益:增益按「已预排的下一曲」推到引擎 Next 槽;包络提前算好
+    // 落库,adopt 边界经 replay 直取零等待。
+    player.ensure_envelope(song_id.clone(), path);
     record_prefetch(
         player,
         song_id,
@@ -274,16 +327,26 @@ pub(crate) fn on_prefetch_url_ready(player: &PlayerCore, song_id: &SongId, play_
     let Some(next) = next else {
         return;
     };
-    match player
-        .media_cache()
-        .capture_path(&next.id, player.playback_quality())
-    {
+    // channel 直接给出本地文件(本地曲库源):不 capture,登记后补包络。
+    let local = match &play_url.url {
+        MediaUrl::Local(path) => Some(path.clone()),
+        MediaUrl::Remote(_) => None,
+    };
+    let capture = match &play_url.url {
+        MediaUrl::Local(_) => None,
+        MediaUrl::Remote(_) => player
+            .media_cache()
+            .capture_path(&next.id, player.playback_quality()),
+    };
+    let transition = next_transition(player, &next);
+    match capture {
         Some(path) => {
             player.audio().append_next_capturing(
                 play_url.url.clone(),
                 play_url.stream_headers.clone(),
                 path.clone(),
                 play_url.layout,
+                transition,
             );
             let cap = Capturing {
                 song: next.clone(),
@@ -305,6 +368,7 @@ pub(crate) fn on_prefetch_url_ready(player: &PlayerCore, song_id: &SongId, play_
                 play_url.url.clone(),
                 play_url.stream_headers.clone(),
                 play_url.layout,
+                transition,
             );
             player.with_state(|st| {
                 st.queued = Some(Queued {
@@ -316,6 +380,10 @@ pub(crate) fn on_prefetch_url_ready(player: &PlayerCore, song_id: &SongId, play_
             });
         }
     }
+    match local {
+        Some(path) => player.ensure_envelope(song_id.clone(), path),
+        None => player.normalize_streamed(song_id.clone()),
+    }
     record_prefetch(
         player,
         song_id.clone(),
@@ -340,6 +408,7 @@ pub(crate) fn on_prefetch_rewritten(player: &PlayerCore, song_id: &SongId, play_
         play_url.url.clone(),
         play_url.stream_headers.clone(),
         play_url.layout,
+        next_transition(player, &next),
     );
     player.with_state(|st| {
         st.queued = Some(Queued {
@@ -461,13 +530,11 @@ pub(crate) fn check_advance(player: &PlayerCore) {
                     player.inner.stats.play_started(pending);
                 }
                 // 富化音频快照:预排 URL 已轮转进 play_url。
-                if let Some(pu) = play_url {
-                    player.enrich_from_play_url(&pu);
+                if let Some(pu) = &play_url {
+                    player.enrich_from_play_url(pu);
                 }
-                player.submit_task(
-                    TaskKind::ChannelFetch(ChannelFetchKind::Lyrics { song_id: s.id }),
-                    Priority::User,
-                );
+                player.fetch_lyrics(&s, play_url.as_ref().and_then(crate::lyrics::local_file));
+                player.load_lyric_offset(&s.id);
             }
             // 无缝翻曲后补推新当前曲的 db 包络(预排时已算好;client 换曲后才认它)。
             player.replay_current_envelope();
@@ -499,9 +566,30 @@ mod tests {
     use mineral_protocol::{PlayCursor, PlaybackOrigin};
     use mineral_test::song;
 
-    use super::{Advance, Queued, adopt_queued, decide_advance, prefetch_window_open};
+    use mineral_audio::Transition;
+    use mineral_test::with_album;
+
+    use super::{
+        Advance, Queued, adopt_queued, decide_advance, prefetch_window_open, transition_for,
+    };
     use crate::state::State;
 
+    /// transition_for:跨专辑淡化;同专辑 / 同一首 / 未启用恒 gapless;缺专辑信息视为不同专辑。
+    #[test]
+    fn same_album_stays_gapless() {
+        let a1 = with_album(song("1"), "x");
+        let a2 = with_album(song("2"), "x");
+        let b1 = with_album(song("3"), "y");
+        assert_eq!(transition_for(6_000, Some(&a1), &b1), Transition::Crossfade);
+        assert_eq!(transition_for(6_000, Some(&a1), &a2), Transition::Gapless);
+        assert_eq!(transition_for(6_000, Some(&a1), &a1), Transition::Gapless);
+        assert_eq!(transition_for(0, Some(&a1), &b1), Transition::Gapless);
+        assert_eq!(
+            transition_for(6_000, Some(&song("4")), &a1),
+            Transition::Crossfade
+        );
+    }
+
     /// prefetch_window_open:decoder 实测优先;实测探不出(分片 fMP4 流式打开)回落元数据
     /// ——B站源曾因缺这层回落,预排窗口永远不开、gapless 从不触发;两口径都未知不开窗。
     #[test]
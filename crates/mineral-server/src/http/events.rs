@@ -0,0 +1,90 @@
+//! WebSocket 推送 `/api/events`:每条文本消息是一个 [`Event`] 的 JSON(形状同
+//! JSON-lines 模式的 `Event` 载荷),只推属性变更与曲终。
+//!
+//! 推送只读:client 发来的文本忽略,Close 即收尾;Ping 由 axum 自动回 Pong。积压
+//! (Lagged)丢帧只 warn——event 是 advisory,需要全量时再 `GET /api/player`。
+
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use mineral_protocol::{Event, encode_json};
+use tokio::sync::broadcast;
+
+use super::HttpState;
+
+/// `GET /api/events`:升级为 WebSocket 推送流。
+pub(super) async fn upgrade(State(state): State<HttpState>, ws: WebSocketUpgrade) -> Response {
+    // 升级前订阅:握手完成之后产生的事件零窗口不丢。
+    let rx = state.events.subscribe();
+    ws.on_upgrade(move |socket| push(socket, rx))
+}
+
+/// 推送循环:hub 事件过滤后逐条写出,直到对端关闭 / 写失败 / hub 关闭。
+async fn push(mut socket: WebSocket, mut rx: broadcast::Receiver<Event>) {
+    mineral_log::debug!(target: "http", "event stream opened");
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            event = rx.recv() => match event {
+                Ok(ev) => {
+                    if !pushed(&ev) {
+                        continue;
+                    }
+                    let text = match encode_json(&ev) {
+                        Ok(t) => t,
+                        Err(e) => {
+                            mineral_log::warn!(target: "http", error = mineral_log::chain(&e), "event 编码失败,跳过");
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    mineral_log::warn!(target: "http", skipped, "event 推送积压,丢弃滞后事件");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+    mineral_log::debug!(target: "http", "event stream closed");
+}
+
+/// 是否推给 WebSocket:只推属性变更与曲终(toast / 任务结果等留给 IPC client)。
+fn pushed(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::PropertyChanged { .. } | Event::TrackFinished { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{SongId, SourceKind};
+    use mineral_protocol::{Event, FinishReason, PropName, PropValue, TextSpan, ToastKind};
+
+    use super::pushed;
+
+    /// 只放行属性变更与曲终。
+    #[test]
+    fn only_property_and_finish_are_pushed() {
+        assert!(pushed(&Event::PropertyChanged {
+            prop: PropName::PLAYER_VOLUME,
+            value: PropValue::Int(42),
+        }));
+        assert!(pushed(&Event::TrackFinished {
+            song_id: SongId::new(SourceKind::NETEASE, "1"),
+            reason: FinishReason::Eof,
+        }));
+        assert!(!pushed(&Event::Toast {
+            kind: ToastKind::Info,
+            content: vec![TextSpan::plain("hi")],
+            id: None,
+            ttl_secs: None,
+        }));
+    }
+}
@@ -0,0 +1,298 @@
+//! HTTP / WebSocket 遥控入口(配置 `daemon.http`):局域网手机 / web 面板经它驱动与
+//! unix socket 同一个 daemon。
+//!
+//! - REST(JSON):`/api/player` 传输控制、`/api/queue` 队列、`/api/search` 搜索、
+//!   `/api/library` 歌单库、`/api/cover` / `/api/lyrics` 当前曲封面与歌词(见 [`rest`]);
+//! - WebSocket `/api/events`:推 `PropertyChanged` / `TrackFinished`(见 [`events`])。
+//!
+//! 端点直达 [`ClientHandle`] 上与 [`crate::serve`] dispatch 同一组方法,不经帧协议。
+//! 鉴权是单一共享令牌:`Authorization: Bearer` 头或 `?token=` 查询参数(浏览器
+//! WebSocket 无法自设头);未配令牌时只许绑回环地址,由 [`crate::check_exposure`] 在
+//! bind 前守门。配了令牌时 CORS 放开任意来源(令牌不走 cookie,web 面板可异源托管);
+//! 未配令牌时不发 CORS 头,且 `Host` / `Origin` 须指向回环——回环监听拦不住本机浏览器里
+//! 的网页经跨站请求 / DNS rebinding 打进来。
+
+mod events;
+mod rest;
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use color_eyre::eyre::WrapErr;
+use mineral_protocol::Event;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+use crate::client::ClientHandle;
+use crate::exposure::secret_eq;
+use crate::fetch::{FETCH_TIMEOUT, FetchFailure};
+
+/// 每请求共享的状态(clone 廉价,全 Arc 内部)。
+#[derive(Clone)]
+struct HttpState {
+    /// 指令面(与 unix socket 连接同一个 player)。
+    client: ClientHandle,
+
+    /// Event 推送 hub(WebSocket 推送 + 任务型端点配对结果帧)。
+    events: broadcast::Sender<Event>,
+
+    /// 访问令牌;`None` = 不鉴权(只可能绑在回环地址上)。
+    token: Option<Arc<str>>,
+}
+
+/// HTTP accept loop:路由 + 鉴权 / CORS 中间件,跑到 listener 出错为止。
+///
+/// # Params:
+///   - `listener`: 已 bind 的 TCP listener(调用方先过 [`crate::check_exposure`])
+///   - `client`: 指令面
+///   - `events`: Event 推送 hub
+///   - `token`: 访问令牌(空串视同未配)
+pub(crate) async fn run(
+    listener: TcpListener,
+    client: ClientHandle,
+    events: broadcast::Sender<Event>,
+    token: Option<String>,
+) -> color_eyre::Result<()> {
+    let state = HttpState {
+        client,
+        events,
+        token: token.filter(|t| !t.is_empty()).map(Arc::from),
+    };
+    axum::serve(listener, router(state))
+        .await
+        .wrap_err("http serve")
+}
+
+/// 全部路由。
+fn router(state: HttpState) -> Router {
+    Router::new()
+        .route("/api/player", get(rest::player_status))
+        .route("/api/player/position", put(rest::seek))
+        .route("/api/player/volume", put(rest::volume))
+        .route("/api/player/mode", put(rest::mode))
+        .route("/api/player/love", post(rest::love))
+        .route("/api/player/{action}", post(rest::transport))
+        .route(
+            "/api/queue",
+            get(rest::queue)
+                .post(rest::queue_add)
+                .put(rest::queue_replace),
+        )
+        .route("/api/queue/edit", post(rest::queue_edit))
+        .route("/api/search", get(rest::search))
+        .route("/api/library/playlists", get(rest::playlists))
+        .route("/api/library/playlists/{id}", get(rest::playlist_detail))
+        .route("/api/cover", get(rest::cover))
+        .route("/api/lyrics", get(rest::lyrics))
+        .route("/api/events", get(events::upgrade))
+        .layer(middleware::from_fn_with_state(state.clone(), gate))
+        .with_state(state)
+}
+
+/// 中间件:未配令牌只放行回环来源、不发 CORS 头;配了令牌则 CORS 预检直接放行,
+/// 其余请求验令牌,应答一律补 CORS 头。
+async fn gate(State(state): State<HttpState>, req: Request, next: Next) -> Response {
+    let Some(token) = &state.token else {
+        if !loopback_request(req.headers()) {
+            mineral_log::debug!(target: "http", path = %req.uri().path(), "non-loopback request without token");
+            return ApiError::new(
+                StatusCode::FORBIDDEN,
+                "non-loopback Host / Origin refused (no token configured)",
+            )
+            .into_response();
+        }
+        return next.run(req).await;
+    };
+    let mut resp = if req.method() == Method::OPTIONS {
+        StatusCode::NO_CONTENT.into_response()
+    } else if !authorized(req.headers(), req.uri().query(), token) {
+        mineral_log::debug!(target: "http", path = %req.uri().path(), "unauthorized request");
+        ApiError::new(StatusCode::UNAUTHORIZED, "missing or wrong token").into_response()
+    } else {
+        next.run(req).await
+    };
+    let headers = resp.headers_mut();
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("authorization, content-type"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("GET, POST, PUT, OPTIONS"),
+    );
+    resp
+}
+
+/// 请求是否带对了令牌(`Authorization: Bearer <token>` 或 `?token=<token>` 任一)。
+///
+/// # Params:
+///   - `headers`: 请求头
+///   - `query`: 原始查询串(不含 `?`)
+///   - `token`: 期望的令牌
+fn authorized(headers: &HeaderMap, query: Option<&str>, token: &str) -> bool {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if bearer.is_some_and(|given| secret_eq(given, token)) {
+        return true;
+    }
+    query.is_some_and(|q| {
+        url::form_urlencoded::parse(q.as_bytes())
+            .any(|(key, given)| key == "token" && secret_eq(&given, token))
+    })
+}
+
+/// 未配令牌时的来源守门:`Host` 与 `Origin`(带了才查)都须指向回环地址。
+///
+/// 浏览器必带 `Host`,跨站页面还带 `Origin`;两者都缺的只会是本机工具,放行。
+///
+/// # Params:
+///   - `headers`: 请求头
+fn loopback_request(headers: &HeaderMap) -> bool {
+    let points_home = |name: header::HeaderName, to_url: fn(&str) -> String| {
+        headers.get(name).is_none_or(|v| {
+            v.to_str()
+                .ok()
+                .and_then(|raw| url::Url::parse(&to_url(raw)).ok())
+                .is_some_and(|u| match u.host() {
+                    Some(url::Host::Domain(d)) => d.eq_ignore_ascii_case("localhost"),
+                    Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+                    Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+                    None => false,
+                })
+        })
+    };
+    points_home(header::HOST, |raw| format!("http://{raw}"))
+        && points_home(header::ORIGIN, str::to_owned)
+}
+
+/// REST 错误应答:状态码 + `{"error": "..."}`。
+struct ApiError {
+    /// HTTP 状态码。
+    status: StatusCode,
+
+    /// 人读的原因。
+    message: String,
+}
+
+impl ApiError {
+    /// 构造错误应答。
+    ///
+    /// # Params:
+    ///   - `status`: HTTP 状态码
+    ///   - `message`: 人读的原因
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response()
+    }
+}
+
+impl From<FetchFailure> for ApiError {
+    /// 任务型读取等不到结果:超时 `504`,daemon 退出中 `503`。
+    fn from(failure: FetchFailure) -> Self {
+        match failure {
+            FetchFailure::TimedOut => Self::new(
+                StatusCode::GATEWAY_TIMEOUT,
+                format!(
+                    "no result in {}s (source failed or not logged in?)",
+                    FETCH_TIMEOUT.as_secs()
+                ),
+            ),
+            FetchFailure::ShuttingDown => {
+                Self::new(StatusCode::SERVICE_UNAVAILABLE, "daemon is shutting down")
+            }
+        }
+    }
+}
+
+impl From<color_eyre::Report> for ApiError {
+    /// 内部失败(远端打点 / persist 等)收敛成 500,原因展开 context 链。
+    fn from(e: color_eyre::Report) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, mineral_log::chain(&e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, HeaderValue, header};
+
+    use super::{authorized, loopback_request};
+
+    /// 令牌可走 Bearer 头或查询参数(含百分号转义);错的 / 缺的都不放行。
+    #[test]
+    fn token_from_header_or_query() {
+        let mut headers = HeaderMap::new();
+        assert!(!authorized(&headers, None, "s3cret"));
+        assert!(authorized(&headers, Some("a=1&token=s3cret"), "s3cret"));
+        assert!(authorized(&headers, Some("token=a%2Bb"), "a+b"));
+        assert!(!authorized(&headers, Some("token=s3cre"), "s3cret"));
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer s3cret"),
+        );
+        assert!(authorized(&headers, None, "s3cret"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic s3cret"),
+        );
+        assert!(!authorized(&headers, None, "s3cret"));
+    }
+
+    /// 无令牌的来源守门:回环 Host / Origin 放行,外来的(含 rebinding 域名与 `null` 来源)拒绝。
+    #[test]
+    fn loopback_only_without_token() {
+        let with = |pairs: &[(header::HeaderName, &'static str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in pairs {
+                headers.insert(name.clone(), HeaderValue::from_static(value));
+            }
+            loopback_request(&headers)
+        };
+        assert!(with(&[]), "无 Host / Origin 的本机工具放行");
+        assert!(with(&[(header::HOST, "127.0.0.1:8080")]));
+        assert!(with(&[(header::HOST, "[::1]:8080")]));
+        assert!(with(&[
+            (header::HOST, "localhost:8080"),
+            (header::ORIGIN, "http://localhost:8080"),
+        ]));
+        assert!(
+            !with(&[(header::HOST, "evil.example:8080")]),
+            "rebinding 域名拒绝"
+        );
+        assert!(
+            !with(&[
+                (header::HOST, "127.0.0.1:8080"),
+                (header::ORIGIN, "https://evil.example"),
+            ]),
+            "跨站 Origin 拒绝"
+        );
+        assert!(
+            !with(&[(header::HOST, "127.0.0.1:8080"), (header::ORIGIN, "null")]),
+            "不透明来源拒绝"
+        );
+    }
+}
@@ -0,0 +1,732 @@
+//! REST 端点:传输控制 / 队列 / 搜索 / 歌单库 / 当前曲封面与歌词。
+//!
+//! 写操作与 dispatch 同样 fire-and-forget:成功回 `204`,新状态经 WebSocket 推送或
+//! 再 `GET` 取。任务型读取(搜索 / 歌单详情)经 [`crate::fetch`] 等结果帧;channel 失败
+//! 不推结果,等满 [`crate::fetch::FETCH_TIMEOUT`] 回 `504`。
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use mineral_channel_core::Page;
+use mineral_model::{Playlist, PlaylistId, Song, SourceKind, to_lrc_string};
+use mineral_protocol::{PlayMode, PlayerVersions, QueueContextWire, QueueEditOutcome, QueueOp};
+use mineral_task::{ChannelFetchKind, TaskEvent};
+use serde::{Deserialize, Serialize};
+
+use super::{ApiError, HttpState};
+use crate::client::{Client, ClientHandle};
+use crate::fetch;
+use crate::uri::split_qualified;
+
+/// 搜索缺省源。
+const DEFAULT_SEARCH_SOURCE: &str = "netease";
+
+/// 搜索缺省条数。
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+
+/// 播放现状(字段与 `mineral status --json` 一致)。
+#[derive(Serialize)]
+pub(super) struct PlayerStatus {
+    /// 是否在播。
+    playing: bool,
+
+    /// 播放位置(毫秒)。
+    position_ms: u64,
+
+    /// 当前曲总时长(毫秒);未知为 `None`。
+    duration_ms: Option<u64>,
+
+    /// 音量百分比。
+    volume: u8,
+
+    /// 播放模式(脚本名:`sequential` / `shuffle` / `repeat_all` / `repeat_one`)。
+    mode: &'static str,
+
+    /// 当前曲在队列中的下标;不在队列里为 `None`。
+    index: Option<usize>,
+
+    /// 当前曲;未播为 `None`。
+    song: Option<Song>,
+}
+
+/// `GET /api/player`:播放现状。
+pub(super) async fn player_status(State(state): State<HttpState>) -> Json<PlayerStatus> {
+    let snap = state.client.audio_snapshot();
+    let sync = state.client.player_sync(PlayerVersions::default());
+    Json(PlayerStatus {
+        playing: snap.playing,
+        position_ms: snap.position_ms,
+        duration_ms: snap.duration_ms,
+        volume: snap.volume_pct,
+        mode: sync.play_mode.script_name(),
+        index: sync.cursor.queue_index(),
+        song: sync.current.and_then(|c| c.current_song),
+    })
+}
+
+/// `POST /api/player/{action}` 的动作名。
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Transport {
+    /// 开始 / 恢复播放。
+    Play,
+
+    /// 暂停。
+    Pause,
+
+    /// 播放 / 暂停切换。
+    Toggle,
+
+    /// 下一首。
+    Next,
+
+    /// 上一首(进度过分界则回曲首)。
+    Prev,
+
+    /// 停止。
+    Stop,
+}
+
+/// `POST /api/player/{action}`:传输控制。
+pub(super) async fn transport(
+    State(state): State<HttpState>,
+    Path(action): Path<Transport>,
+) -> Result<StatusCode, ApiError> {
+    let client = &state.client;
+    match action {
+        Transport::Play => play(client)?,
+        Transport::Pause => client.pause(),
+        Transport::Toggle => {
+            if client.audio_snapshot().playing {
+                client.pause();
+            } else {
+                play(client)?;
+            }
+        }
+        Transport::Next => client.next_song(),
+        Transport::Prev => client.prev_or_restart(),
+        Transport::Stop => client.stop(),
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 开始 / 恢复播放(见 [`ClientHandle::play_or_resume`])。
+///
+/// # Errors
+/// 无当前曲且队列为空(`409`)。
+fn play(client: &ClientHandle) -> Result<(), ApiError> {
+    if client.play_or_resume() {
+        Ok(())
+    } else {
+        Err(ApiError::new(StatusCode::CONFLICT, "queue is empty"))
+    }
+}
+
+/// `PUT /api/player/position` 请求体。
+#[derive(Deserialize)]
+pub(super) struct SeekBody {
+    /// 目标位置(毫秒);超出时长由引擎钳住。
+    position_ms: u64,
+}
+
+/// `PUT /api/player/position`:跳到绝对位置。
+pub(super) async fn seek(
+    State(state): State<HttpState>,
+    Json(body): Json<SeekBody>,
+) -> Result<StatusCode, ApiError> {
+    if state.client.audio_snapshot().duration_ms.is_none() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "nothing playing"));
+    }
+    state.client.seek(body.position_ms);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `PUT /api/player/volume` 请求体。
+#[derive(Deserialize)]
+pub(super) struct VolumeBody {
+    /// 音量百分比;超出截到 100。
+    volume: u8,
+}
+
+/// `PUT /api/player/volume`:设音量。
+pub(super) async fn volume(
+    State(state): State<HttpState>,
+    Json(body): Json<VolumeBody>,
+) -> StatusCode {
+    state.client.set_volume(body.volume.min(100));
+    StatusCode::NO_CONTENT
+}
+
+/// `PUT /api/player/mode` 请求体。
+#[derive(Deserialize)]
+pub(super) struct ModeBody {
+    /// 目标模式(脚本名,同 [`PlayerStatus::mode`])。
+    mode: String,
+}
+
+/// `PUT /api/player/mode`:切到目标播放模式。
+pub(super) async fn mode(
+    State(state): State<HttpState>,
+    Json(body): Json<ModeBody>,
+) -> Result<StatusCode, ApiError> {
+    let target = PlayMode::from_script_name(&body.mode).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "unknown mode {:?}, expected sequential / shuffle / repeat_all / repeat_one",
+                body.mode
+            ),
+        )
+    })?;
+    state.client.set_play_mode(target);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /api/player/love`:翻转当前曲的收藏状态,回 `{"loved": bool}`。
+pub(super) async fn love(
+    State(state): State<HttpState>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (song, _) = state.client.current_media();
+    let song = song.ok_or_else(|| ApiError::new(StatusCode::CONFLICT, "nothing playing"))?;
+    let loved = state.client.toggle_love_async(&song).await?;
+    Ok(Json(serde_json::json!({ "loved": loved })))
+}
+
+/// 队列视图。
+#[derive(Serialize)]
+pub(super) struct QueueView {
+    /// 当前曲下标;不在队列里为 `None`。
+    index: Option<usize>,
+
+    /// 队列(播放序)。
+    songs: Vec<Song>,
+}
+
+/// `GET /api/queue`:当前队列。
+pub(super) async fn queue(State(state): State<HttpState>) -> Json<QueueView> {
+    let sync = state.client.player_sync(PlayerVersions::default());
+    Json(QueueView {
+        index: sync.cursor.queue_index(),
+        songs: sync.queue.map(|q| q.queue).unwrap_or_default(),
+    })
+}
+
+/// `POST /api/queue` 请求体。
+#[derive(Deserialize)]
+pub(super) struct QueueAdd {
+    /// 待加入的歌(按给定顺序;通常取自搜索 / 歌单详情的应答)。
+    songs: Vec<Song>,
+
+    /// 插到当前曲之后(而非队尾)。
+    #[serde(default)]
+    next: bool,
+}
+
+/// `POST /api/queue`:追加到队尾或插播到当前曲之后,保持给定顺序。
+pub(super) async fn queue_add(
+    State(state): State<HttpState>,
+    Json(body): Json<QueueAdd>,
+) -> Result<StatusCode, ApiError> {
+    if body.songs.is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "no songs given"));
+    }
+    if body.next {
+        // 逐首插到当前曲之后会倒序,故反向插。
+        for song in body.songs.into_iter().rev() {
+            state
+                .client
+                .queue_insert_next(song, QueueContextWire::Manual);
+        }
+    } else {
+        for song in body.songs {
+            state.client.queue_append(song, QueueContextWire::Manual);
+        }
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `PUT /api/queue` 请求体。
+#[derive(Deserialize)]
+pub(super) struct QueueReplace {
+    /// 新队列。
+    songs: Vec<Song>,
+
+    /// 起播下标(缺省 0)。
+    #[serde(default)]
+    start: usize,
+}
+
+/// `PUT /api/queue`:替换队列并从 `start` 起播。
+pub(super) async fn queue_replace(
+    State(state): State<HttpState>,
+    Json(body): Json<QueueReplace>,
+) -> Result<StatusCode, ApiError> {
+    let first = body.songs.get(body.start).cloned().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("start {} is out of {} songs", body.start, body.songs.len()),
+        )
+    })?;
+    state
+        .client
+        .set_queue(body.songs, first.id.clone(), QueueContextWire::Manual);
+    state.client.play_song(first);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /api/queue/edit`:结构化队列编辑(请求体即协议的 `QueueOp` JSON 形态)。
+/// 定位对不上(视图已过期)回 `409`,客户端应重取队列再试。
+pub(super) async fn queue_edit(
+    State(state): State<HttpState>,
+    Json(op): Json<QueueOp>,
+) -> Result<Json<QueueEditOutcome>, ApiError> {
+    match state.client.queue_edit_async(op).await {
+        QueueEditOutcome::Stale => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "queue changed since it was fetched; refetch and retry",
+        )),
+        outcome => Ok(Json(outcome)),
+    }
+}
+
+/// `GET /api/search` 查询参数。
+#[derive(Deserialize)]
+pub(super) struct SearchParams {
+    /// 关键词。
+    q: String,
+
+    /// 音乐源名(缺省 [`DEFAULT_SEARCH_SOURCE`])。
+    source: Option<String>,
+
+    /// 条数上限(缺省 [`DEFAULT_SEARCH_LIMIT`])。
+    limit: Option<u32>,
+}
+
+/// `GET /api/search?q=…`:搜歌,回歌曲数组。
+pub(super) async fn search(
+    State(state): State<HttpState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<Song>>, ApiError> {
+    let query = params.q.trim().to_owned();
+    if query.is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "empty query"));
+    }
+    let source = known_source(
+        &state.client,
+        params.source.as_deref().unwrap_or(DEFAULT_SEARCH_SOURCE),
+    )?;
+    let page = Page::new(0, params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+    let songs = fetch::search_songs(&state.client, &state.events, source, query, page).await?;
+    Ok(Json(songs))
+}
+
+/// `GET /api/library/playlists`:用户歌单库(各源合并,展示序;不含曲目)。
+pub(super) async fn playlists(
+    State(state): State<HttpState>,
+) -> Result<Json<Vec<Playlist>>, ApiError> {
+    state.client.library_snapshot().map(Json).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "library is still loading, retry shortly",
+        )
+    })
+}
+
+/// `GET /api/library/playlists/{id}`:歌单详情(含曲目);`id` 形如 `netease:123`。
+pub(super) async fn playlist_detail(
+    State(state): State<HttpState>,
+    Path(raw): Path<String>,
+) -> Result<Json<Playlist>, ApiError> {
+    let (namespace, value) = split_qualified(&raw).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("invalid playlist id {raw:?}, expected \"<source>:<id>\""),
+        )
+    })?;
+    let id = PlaylistId::new(known_source(&state.client, namespace)?, value);
+    let request = ChannelFetchKind::PlaylistDetail { id: id.clone() };
+    let playlist = fetch::await_fetch(&state.client, &state.events, request, |event| match event {
+        TaskEvent::PlaylistDetailFetched {
+            id: got_id,
+            playlist,
+        } if got_id == id => Some(*playlist),
+        _ => None,
+    })
+    .await?;
+    Ok(Json(playlist))
+}
+
+/// `GET /api/cover`:当前曲封面图片字节(远端封面由 daemon 代取,本地封面直读)。
+pub(super) async fn cover(State(state): State<HttpState>) -> Result<Response, ApiError> {
+    let (song, _) = state.client.current_media();
+    let url = song
+        .and_then(|s| s.cover_url)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "no cover for the current song"))?;
+    let bytes = if let Some(remote) = url.as_remote() {
+        let http = state.client.http().ok_or_else(|| {
+            ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "http client unavailable")
+        })?;
+        let fetched = async {
+            http.get(remote.as_str())
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await
+        };
+        fetched
+            .await
+            .map_err(|e| ApiError::new(StatusCode::BAD_GATEWAY, format!("fetch cover: {e}")))?
+            .to_vec()
+    } else if let Some(path) = url.as_local() {
+        tokio::fs::read(path).await.map_err(|e| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                format!("read cover {}: {e}", path.display()),
+            )
+        })?
+    } else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "unsupported cover url",
+        ));
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, image_mime(&bytes)),
+            // 同一路径随切歌换图,不许缓存。
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// `GET /api/lyrics` 查询参数。
+#[derive(Deserialize)]
+pub(super) struct LyricsParams {
+    /// 应答形态。
+    #[serde(default)]
+    format: LyricsFormat,
+}
+
+/// 歌词应答形态。
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum LyricsFormat {
+    /// 结构化行(逐字 / 翻译 / 罗马音都在行上)。
+    #[default]
+    Json,
+
+    /// 纯 LRC 文本(行级)。
+    Lrc,
+}
+
+/// `GET /api/lyrics[?format=lrc]`:当前曲歌词(已按歌词偏移平移)。
+pub(super) async fn lyrics(
+    State(state): State<HttpState>,
+    Query(params): Query<LyricsParams>,
+) -> Result<Response, ApiError> {
+    let (song, lyrics) = state.client.current_media();
+    let song = song.ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "nothing playing"))?;
+    let lyrics = lyrics
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "no lyrics for the current song"))?;
+    Ok(match params.format {
+        LyricsFormat::Json => Json(serde_json::json!({
+            "song_id": song.id.qualified(),
+            "lines": lyrics.lines,
+        }))
+        .into_response(),
+        LyricsFormat::Lrc => (
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            to_lrc_string(&lyrics.lines),
+        )
+            .into_response(),
+    })
+}
+
+/// 按名认出已注册的音乐源。
+///
+/// # Errors
+/// 未注册的源名(`400`)。
+fn known_source(client: &ClientHandle, name: &str) -> Result<SourceKind, ApiError> {
+    let source = SourceKind::from_name(name);
+    if client.channel_caps().iter().any(|(s, _)| *s == source) {
+        Ok(source)
+    } else {
+        Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("unknown source {name:?}"),
+        ))
+    }
+}
+
+/// 按魔数认图片 MIME(封面源不可靠地带扩展名);认不出按二进制流。
+fn image_mime(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if bytes.starts_with(b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP".as_slice()) {
+        "image/webp"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode, header};
+    use axum::response::Response;
+    use mineral_model::{LyricLine, Lyrics};
+    use tower::ServiceExt;
+
+    use super::{LyricsFormat, Transport, image_mime};
+    use crate::http::{HttpState, router};
+
+    /// 测试用令牌。
+    const TOKEN: &str = "s3cret";
+
+    /// 起进程内 Server(ForceNull 音频 + 禁用 persist,无音乐源)并配好 HTTP 状态。
+    ///
+    /// # Params:
+    ///   - `token`: 访问令牌;`None` = 不鉴权
+    ///
+    /// # Return:
+    ///   (Server 本体 —— 须活过测试,HTTP 状态)。
+    async fn app(token: Option<&str>) -> color_eyre::Result<(crate::Server, HttpState)> {
+        let cfg = mineral_config::Config::defaults()?;
+        let server = crate::Server::spawn(
+            Vec::new(),
+            mineral_audio::AudioMode::ForceNull,
+            mineral_persist::ServerStore::disabled(),
+            crate::ServerConfig::from_config(&cfg),
+            mineral_config::default_tree()?,
+            /*script*/ None,
+            crate::StatsRecorder::disabled(),
+        )
+        .await?;
+        let state = HttpState {
+            client: server.client(),
+            events: server.event_sink(),
+            token: token.map(Arc::from),
+        };
+        Ok((server, state))
+    }
+
+    /// 带令牌发一个请求(`body` 为 JSON 请求体)。
+    ///
+    /// # Params:
+    ///   - `state`: HTTP 状态
+    ///   - `method`: 方法
+    ///   - `uri`: 路径(含查询串)
+    ///   - `body`: JSON 请求体;`None` = 空体
+    async fn call(
+        state: &HttpState,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> color_eyre::Result<Response> {
+        let mut req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {TOKEN}"));
+        let body = match body {
+            Some(json) => {
+                req = req.header(header::CONTENT_TYPE, "application/json");
+                Body::from(serde_json::to_vec(&json)?)
+            }
+            None => Body::empty(),
+        };
+        Ok(router(state.clone()).oneshot(req.body(body)?).await?)
+    }
+
+    /// 读出应答体的 JSON。
+    async fn json(resp: Response) -> color_eyre::Result<serde_json::Value> {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// 缺令牌 / 令牌错回 401,令牌对放行;未配令牌时外来 Host 回 403,回环 Host 放行。
+    #[tokio::test]
+    async fn gate_rejects_bad_token_and_foreign_host() -> color_eyre::Result<()> {
+        let (_server, state) = app(Some(TOKEN)).await?;
+        let bare = Request::get("/api/player").body(Body::empty())?;
+        let resp = router(state.clone()).oneshot(bare).await?;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(
+            resp.headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+        let wrong = Request::get("/api/player")
+            .header(header::AUTHORIZATION, "Bearer nope")
+            .body(Body::empty())?;
+        let resp = router(state.clone()).oneshot(wrong).await?;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let query = Request::get(format!("/api/player?token={TOKEN}")).body(Body::empty())?;
+        assert_eq!(
+            router(state.clone()).oneshot(query).await?.status(),
+            StatusCode::OK
+        );
+
+        let (_open_server, open) = app(None).await?;
+        let foreign = Request::get("/api/player")
+            .header(header::HOST, "evil.example:8080")
+            .body(Body::empty())?;
+        assert_eq!(
+            router(open.clone()).oneshot(foreign).await?.status(),
+            StatusCode::FORBIDDEN
+        );
+        let home = Request::get("/api/player")
+            .header(header::HOST, "127.0.0.1:8080")
+            .body(Body::empty())?;
+        assert_eq!(router(open).oneshot(home).await?.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    /// 传输控制:空队列 play / toggle 回 409,其余动作 204,未知动作与坏模式回 4xx。
+    #[tokio::test]
+    async fn playback_actions() -> color_eyre::Result<()> {
+        let (_server, state) = app(Some(TOKEN)).await?;
+        for action in ["play", "toggle"] {
+            let resp = call(&state, Method::POST, &format!("/api/player/{action}"), None).await?;
+            assert_eq!(resp.status(), StatusCode::CONFLICT, "{action}");
+        }
+        for action in ["pause", "next", "prev", "stop"] {
+            let resp = call(&state, Method::POST, &format!("/api/player/{action}"), None).await?;
+            assert_eq!(resp.status(), StatusCode::NO_CONTENT, "{action}");
+        }
+        let resp = call(&state, Method::POST, "/api/player/rewind", None).await?;
+        assert!(resp.status().is_client_error());
+
+        let seek = serde_json::json!({ "position_ms": 1000 });
+        let resp = call(&state, Method::PUT, "/api/player/position", Some(seek)).await?;
+        assert_eq!(resp.status(), StatusCode::CONFLICT, "无在播曲不许 seek");
+
+        let volume = serde_json::json!({ "volume": 250 });
+        let resp = call(&state, Method::PUT, "/api/player/volume", Some(volume)).await?;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let mode = serde_json::json!({ "mode": "shuffle" });
+        let resp = call(&state, Method::PUT, "/api/player/mode", Some(mode)).await?;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let status = json(call(&state, Method::GET, "/api/player", None).await?).await?;
+        assert_eq!(status.pointer("/mode"), Some(&"shuffle".into()));
+        let bad = serde_json::json!({ "mode": "random" });
+        let resp = call(&state, Method::PUT, "/api/player/mode", Some(bad)).await?;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    /// 队列:追加保序、插播到当前曲之后、替换越界回 400、空追加回 400。
+    #[tokio::test]
+    async fn queue_endpoints() -> color_eyre::Result<()> {
+        let (_server, state) = app(Some(TOKEN)).await?;
+        let ids = |view: &serde_json::Value| -> Vec<String> {
+            view.pointer("/songs")
+                .and_then(serde_json::Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|s| s.pointer("/name")?.as_str().map(str::to_owned))
+                .collect()
+        };
+
+        let empty = serde_json::json!({ "songs": [] });
+        let resp = call(&state, Method::POST, "/api/queue", Some(empty)).await?;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let add = serde_json::json!({
+            "songs": [mineral_test::song("a"), mineral_test::song("b")],
+        });
+        let resp = call(&state, Method::POST, "/api/queue", Some(add)).await?;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let view = json(call(&state, Method::GET, "/api/queue", None).await?).await?;
+        assert_eq!(ids(&view), ["a", "b"]);
+
+        let replace = serde_json::json!({
+            "songs": [mineral_test::song("x"), mineral_test::song("y")],
+            "start": 2,
+        });
+        let resp = call(&state, Method::PUT, "/api/queue", Some(replace)).await?;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let view = json(call(&state, Method::GET, "/api/queue", None).await?).await?;
+        assert_eq!(ids(&view), ["a", "b"], "越界替换不动队列");
+
+        let replace = serde_json::json!({
+            "songs": [mineral_test::song("x"), mineral_test::song("y")],
+            "start": 0,
+        });
+        let resp = call(&state, Method::PUT, "/api/queue", Some(replace)).await?;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let next = serde_json::json!({
+            "songs": [mineral_test::song("n1"), mineral_test::song("n2")],
+            "next": true,
+        });
+        let resp = call(&state, Method::POST, "/api/queue", Some(next)).await?;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let view = json(call(&state, Method::GET, "/api/queue", None).await?).await?;
+        assert_eq!(ids(&view), ["x", "n1", "n2", "y"]);
+        assert_eq!(view.pointer("/index"), Some(&0.into()));
+        Ok(())
+    }
+
+    /// 现状带在播曲;歌词按偏移平移,LRC 形态同样平移;无在播曲回 404。
+    #[tokio::test]
+    async fn now_playing_and_lyric_offset() -> color_eyre::Result<()> {
+        let (server, state) = app(Some(TOKEN)).await?;
+        let resp = call(&state, Method::GET, "/api/lyrics", None).await?;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        server.player().with_state(|st| {
+            st.current_song = Some(mineral_test::song("now"));
+            st.current_lyrics = Some(Lyrics {
+                lines: vec![LyricLine::timed(1000, "hello")],
+            });
+        });
+        let status = json(call(&state, Method::GET, "/api/player", None).await?).await?;
+        assert_eq!(status.pointer("/song/name"), Some(&"now".into()));
+
+        state.client.adjust_lyric_offset(500)?;
+        let lyrics = json(call(&state, Method::GET, "/api/lyrics", None).await?).await?;
+        assert_eq!(lyrics.pointer("/song_id"), Some(&"netease:now".into()));
+        assert_eq!(lyrics.pointer("/lines/0/time_ms"), Some(&1500.into()));
+
+        let resp = call(&state, Method::GET, "/api/lyrics?format=lrc", None).await?;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+        let lrc = String::from_utf8(bytes.to_vec())?;
+        assert!(lrc.contains("[00:01.50]hello"), "{lrc}");
+        Ok(())
+    }
+
+    #[test]
+    fn image_mime_sniffs_magic() {
+        assert_eq!(image_mime(b"\x89PNG\r\n\x1a\n...."), "image/png");
+        assert_eq!(image_mime(b"\xFF\xD8\xFF\xE0...."), "image/jpeg");
+        assert_eq!(image_mime(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(image_mime(b"RIFF\0\0\0\0WAVE"), "application/octet-stream");
+        assert_eq!(image_mime(b""), "application/octet-stream");
+    }
+
+    /// 路径段 / 查询参数里的枚举名按小写认。
+    #[test]
+    fn lowercase_action_names() -> color_eyre::Result<()> {
+        assert!(matches!(
+            serde_json::from_str::<Transport>("\"toggle\"")?,
+            Transport::Toggle
+        ));
+        assert!(serde_json::from_str::<Transport>("\"Toggle\"").is_err());
+        assert!(matches!(
+            serde_json::from_str::<LyricsFormat>("\"lrc\"")?,
+            LyricsFormat::Lrc
+        ));
+        Ok(())
+    }
+}
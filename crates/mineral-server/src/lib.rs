@@ -21,18 +21,29 @@ mod config;
 mod config_host;
 mod download;
 mod envelope;
+mod eq;
 mod events;
+mod exports;
+mod exposure;
 mod favorites;
+mod fetch;
 mod gapless;
 mod hook_bridge;
+mod http;
 mod library;
+mod loudness;
+mod lyric_offset;
+mod lyrics;
 mod media;
 mod media_cache;
+mod mpd;
 mod notify;
+mod path_template;
 mod pcm;
 mod player;
 mod props;
 mod queue;
+mod radio;
 mod resolve;
 mod script_bridge;
 mod script_reload;
@@ -41,9 +52,12 @@ mod server;
 mod session;
 mod state;
 mod stats;
+mod tagging;
+mod uri;
 
 pub use client::{Client, ClientHandle};
 pub use config::{ServerConfig, resolve_audio_mode};
+pub use exposure::check_exposure;
 pub use mineral_audio::AudioMode;
 pub use mineral_protocol::{CancelFilter, ChannelFetchKindTag};
 pub use script_bridge::{ScriptParts, ScriptPumps, ScriptReloadParts};
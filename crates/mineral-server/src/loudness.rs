@@ -0,0 +1,301 @@
+//! 响度归一化的编排(配置 `audio.normalization`):ReplayGain 标签 / R128 分析 → 逐曲增益
+//! → 推给音频引擎对应槽。
+//!
+//! 来源优先级:文件自带的 ReplayGain 标签 > 离线分析落库的积分响度。标签在拿到本地文件时
+//! 即读(与包络编排同一入口,见 [`crate::envelope`]);分析随包络一并算出 / 命中。远端流播
+//! 没有文件,只查库里已有的分析(缓存被驱逐 / 曾经下载过的曲目)。
+//!
+//! 增益按「该曲此刻是当前曲还是已预排的下一曲」推到引擎对应槽;两者都不是(迟到结果)
+//! 静默丢弃。同曲循环(RepeatOne)时两槽同时推。
+
+use std::path::Path;
+
+use color_eyre::eyre::{WrapErr, eyre};
+use lofty::file::TaggedFileExt;
+use lofty::prelude::ItemKey;
+use mineral_audio::GainSlot;
+use mineral_config::{NormalizationConfig, NormalizationMode};
+use mineral_model::{Loudness, SongId};
+
+use crate::player::PlayerCore;
+
+/// ReplayGain 的参考电平(LUFS):标签增益都是「把该曲拉到 −18 LUFS」的量。
+const REPLAYGAIN_REFERENCE_LUFS: f64 = -18.0;
+
+/// 文件自带的 ReplayGain 标签(各项独立可缺)。
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct ReplayGain {
+    /// 逐曲增益(dB)。
+    track_gain_db: Option<f64>,
+
+    /// 逐曲峰值(线性)。
+    track_peak: Option<f64>,
+
+    /// 专辑增益(dB)。
+    album_gain_db: Option<f64>,
+
+    /// 专辑峰值(线性)。
+    album_peak: Option<f64>,
+}
+
+/// 解析 ReplayGain 标签值:`"-6.48 dB"` / `"+2.1dB"` / `"0.988"` 均可,非有限值视同缺失。
+///
+/// # Params:
+///   - `raw`: 标签原文
+///
+/// # Return:
+///   数值;不可解析时 `None`。
+fn parse_replay_gain(raw: &str) -> Option<f64> {
+    let t = raw.trim();
+    let t = t
+        .strip_suffix("dB")
+        .or_else(|| t.strip_suffix("db"))
+        .or_else(|| t.strip_suffix("DB"))
+        .unwrap_or(t);
+    t.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// 读文件的 ReplayGain 标签(主标签优先,其余标签补缺)。阻塞,调用方放 `spawn_blocking`。
+///
+/// # Params:
+///   - `path`: 本地音频文件
+///
+/// # Return:
+///   至少有一项增益时 `Some`;无增益标签为 `None`。
+fn read_replay_gain(path: &Path) -> color_eyre::Result<Option<ReplayGain>> {
+    let tagged =
+        lofty::read_from_path(path).wrap_err_with(|| format!("读标签失败 {}", path.display()))?;
+    let lookup = |key: ItemKey| {
+        tagged
+            .primary_tag()
+            .into_iter()
+            .chain(tagged.tags())
+            .find_map(|tag| tag.get_string(&key).and_then(parse_replay_gain))
+    };
+    let rg = ReplayGain {
+        track_gain_db: lookup(ItemKey::ReplayGainTrackGain),
+        track_peak: lookup(ItemKey::ReplayGainTrackPeak),
+        album_gain_db: lookup(ItemKey::ReplayGainAlbumGain),
+        album_peak: lookup(ItemKey::ReplayGainAlbumPeak),
+    };
+    Ok((rg.track_gain_db.is_some() || rg.album_gain_db.is_some()).then_some(rg))
+}
+
+/// 按配置把标签 / 分析折算成要施加的增益。
+///
+/// 标签按模式取逐曲或专辑增益(缺哪个回落另一个),从 ReplayGain 参考电平折算到
+/// `target_lufs`;分析直接 `target − 积分响度`。再叠前级,`prevent_clipping` 时按峰值
+/// 收住(峰值未知不收,交给限幅器)。
+///
+/// # Params:
+///   - `cfg`: 归一化配置
+///   - `tags`: 文件的 ReplayGain 标签(优先)
+///   - `analysis`: 离线分析的积分响度
+///
+/// # Return:
+///   增益(dB);模式 `off` 或两种来源都没有时 `None`。
+pub(crate) fn gain_db(
+    cfg: &NormalizationConfig,
+    tags: Option<&ReplayGain>,
+    analysis: Option<&Loudness>,
+) -> Option<f64> {
+    let album = match cfg.mode() {
+        NormalizationMode::Off => return None,
+        NormalizationMode::Album => true,
+        NormalizationMode::Track | _ => false,
+    };
+    let (base, peak) = match (tags, analysis) {
+        (Some(rg), _) => {
+            let (gain, peak) = if album {
+                (
+                    rg.album_gain_db.or(rg.track_gain_db),
+                    rg.album_peak.or(rg.track_peak),
+                )
+            } else {
+                (
+                    rg.track_gain_db.or(rg.album_gain_db),
+                    rg.track_peak.or(rg.album_peak),
+                )
+            };
+            (
+                gain? + (cfg.target_lufs() - REPLAYGAIN_REFERENCE_LUFS),
+                peak,
+            )
+        }
+        (None, Some(l)) => (cfg.target_lufs() - l.integrated_lufs, Some(l.peak)),
+        (None, None) => return None,
+    };
+    let gain = base + cfg.preamp_db();
+    Some(match peak.filter(|p| *cfg.prevent_clipping() && *p > 0.0) {
+        Some(p) => gain.min(-20.0 * p.log10()),
+        None => gain,
+    })
+}
+
+impl PlayerCore {
+    /// 归一化开启时读本地文件的 ReplayGain 标签并施加;读失败只 debug。
+    ///
+    /// # Params:
+    ///   - `song_id`: 目标歌曲
+    ///   - `path`: 该曲的本地文件
+    ///
+    /// # Return:
+    ///   标签给出了增益(已施加)时 `true`——调用方据此不再用分析结果覆盖。
+    pub(crate) async fn apply_tag_gain(&self, song_id: &SongId, path: &Path) -> bool {
+        if *self.normalization().mode() == NormalizationMode::Off {
+            return false;
+        }
+        let owned = path.to_path_buf();
+        let read = tokio::task::spawn_blocking(move || read_replay_gain(&owned))
+            .await
+            .map_err(|e| eyre!("读标签线程异常: {e}"))
+            .and_then(|r| r);
+        let tags = match read {
+            Ok(tags) => tags,
+            Err(e) => {
+                mineral_log::debug!(target: "player", song = song_id.as_str(), error = mineral_log::chain(e), "读 ReplayGain 标签失败");
+                None
+            }
+        };
+        let Some(gain) = gain_db(self.normalization(), tags.as_ref(), None) else {
+            return false;
+        };
+        self.push_gain(song_id, gain);
+        true
+    }
+
+    /// 按离线分析结果施加增益(模式 `off` 时无事)。
+    pub(crate) fn apply_analysis_gain(&self, song_id: &SongId, loudness: &Loudness) {
+        if let Some(gain) = gain_db(self.normalization(), None, Some(loudness)) {
+            self.push_gain(song_id, gain);
+        }
+    }
+
+    /// 远端流播(无本地文件)的归一化:只查库里已有的分析,有则施加。
+    pub(crate) fn normalize_streamed(&self, song_id: SongId) {
+        if *self.normalization().mode() == NormalizationMode::Off {
+            return;
+        }
+        let player = self.clone();
+        tokio::spawn(async move {
+            if let Some(loudness) = player.cached_loudness(&song_id).await {
+                player.apply_analysis_gain(&song_id, &loudness);
+            }
+        });
+    }
+
+    /// 读 db 缓存的当前版本响度;读失败 warn 后按缺失处理。
+    pub(crate) async fn cached_loudness(&self, song_id: &SongId) -> Option<Loudness> {
+        let scope = self.persist().scope(song_id.namespace());
+        match scope
+            .get_loudness(song_id, mineral_audio::LOUDNESS_VERSION)
+            .await
+        {
+            Ok(hit) => hit,
+            Err(e) => {
+                mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "读响度缓存失败");
+                None
+            }
+        }
+    }
+
+    /// 把增益推到该曲所在的引擎槽(当前曲 / 已预排下一曲,同曲循环两槽都推)。
+    fn push_gain(&self, song_id: &SongId, gain: f64) {
+        let (current, next) = self.with_state(|st| {
+            (
+                st.current_song.as_ref().is_some_and(|s| s.id == *song_id),
+                st.queued.as_ref().is_some_and(|q| q.song.id == *song_id),
+            )
+        });
+        mineral_log::debug!(target: "player", song = song_id.as_str(), gain_db = gain, current, next, "归一化增益");
+        if current {
+            self.audio().set_track_gain(GainSlot::Current, gain);
+        }
+        if next {
+            self.audio().set_track_gain(GainSlot::Next, gain);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::Loudness;
+
+    use super::{ReplayGain, gain_db, parse_replay_gain};
+
+    /// 默认配置(track / −18 LUFS / 0dB 前级 / 防削波)。
+    fn config() -> color_eyre::Result<mineral_config::NormalizationConfig> {
+        Ok(mineral_config::Config::defaults()?
+            .audio()
+            .normalization()
+            .clone())
+    }
+
+    /// 标签值的常见写法都能解析;垃圾值视同缺失。
+    #[test]
+    fn parses_replay_gain_values() {
+        assert_eq!(parse_replay_gain("-6.48 dB"), Some(-6.48));
+        assert_eq!(parse_replay_gain("+2.1dB"), Some(2.1));
+        assert_eq!(parse_replay_gain(" 0.988 "), Some(0.988));
+        assert_eq!(parse_replay_gain("loud"), None);
+        assert_eq!(parse_replay_gain("NaN"), None);
+    }
+
+    /// 标签优先于分析;分析按 `target − 积分响度`;两者皆无为 `None`。
+    #[test]
+    fn tags_win_over_analysis() -> color_eyre::Result<()> {
+        let cfg = config()?;
+        let tags = ReplayGain {
+            track_gain_db: Some(-4.0),
+            ..ReplayGain::default()
+        };
+        let loud = Loudness {
+            integrated_lufs: -8.0,
+            peak: 1.0,
+            version: 1,
+        };
+        assert_eq!(gain_db(&cfg, Some(&tags), Some(&loud)), Some(-4.0));
+        assert_eq!(gain_db(&cfg, None, Some(&loud)), Some(-10.0));
+        assert_eq!(gain_db(&cfg, None, None), None);
+        Ok(())
+    }
+
+    /// 防削波:安静曲的正增益被峰值收住(峰值 0.5 → 至多 +6.02dB)。
+    #[test]
+    fn prevent_clipping_caps_boost_by_peak() -> color_eyre::Result<()> {
+        let cfg = config()?;
+        let quiet = Loudness {
+            integrated_lufs: -30.0,
+            peak: 0.5,
+            version: 1,
+        };
+        let gain = gain_db(&cfg, None, Some(&quiet)).unwrap_or_default();
+        assert!((gain - 6.0206).abs() < 1e-3, "应按峰值收住:{gain}");
+        Ok(())
+    }
+
+    /// album 模式取专辑增益,缺失回落逐曲;track 模式反之。
+    #[test]
+    fn album_mode_prefers_album_gain() -> color_eyre::Result<()> {
+        let track_cfg = config()?;
+        let album_cfg: mineral_config::NormalizationConfig =
+            serde_json::from_value(serde_json::json!({
+                "mode": "album", "target_lufs": -18.0, "preamp_db": 0.0,
+                "prevent_clipping": false,
+                "limiter": { "enabled": true, "threshold_db": -1.0, "release_ms": 100 }
+            }))?;
+        let both = ReplayGain {
+            track_gain_db: Some(-3.0),
+            album_gain_db: Some(-5.0),
+            ..ReplayGain::default()
+        };
+        let track_only = ReplayGain {
+            track_gain_db: Some(-3.0),
+            ..ReplayGain::default()
+        };
+        assert_eq!(gain_db(&album_cfg, Some(&both), None), Some(-5.0));
+        assert_eq!(gain_db(&album_cfg, Some(&track_only), None), Some(-3.0));
+        assert_eq!(gain_db(&track_cfg, Some(&both), None), Some(-3.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! per-song 歌词时间偏移:网易 LRC 与顶换流(`synced ~` 降级档)常整体漂几百毫秒,
+//! 用户按键 / 脚本按步微调,校正值落 `song_kv` 开放键 [`OFFSET_KEY`],下次播同一首自动沿用。
+//!
+//! 偏移只是一个数,歌词本体不改:TUI 以「播放位置 − 偏移」定位行,MPRIS 导出
+//! `xesam:asText` 时整体平移时间戳([`mineral_model::Lyrics::shifted`])。
+
+use color_eyre::eyre::eyre;
+use mineral_model::SongId;
+use mineral_protocol::{StoreValue, ToastKind};
+
+use crate::player::PlayerCore;
+
+/// `song_kv` 里存偏移的开放键(毫秒,`Int`;0 = 删除该键)。脚本可经 `mineral.store.get` 读。
+pub(crate) const OFFSET_KEY: &str = "lyric_offset_ms";
+
+/// 偏移绝对值上限(毫秒):再大已不是「漂移」,多半是配错了歌词。
+const MAX_OFFSET_MS: i64 = 10_000;
+
+impl PlayerCore {
+    /// 开播时载入该曲持久的歌词偏移(异步读 `song_kv`;未命中 / 读失败按 0)。
+    ///
+    /// 只在该曲仍在播、且 slot 还没被本曲占用时落 slot——读库期间用户已按键微调的,以按键为准。
+    ///
+    /// # Params:
+    ///   - `song_id`: 刚成为在播曲的歌
+    pub(crate) fn load_lyric_offset(&self, song_id: &SongId) {
+        let player = self.clone();
+        let song_id = song_id.clone();
+        tokio::spawn(async move {
+            let scope = player.persist().scope(song_id.namespace());
+            let stored = match scope.kv_get(&song_id, OFFSET_KEY).await {
+                Ok(StoreValue::Int(ms)) => ms.clamp(-MAX_OFFSET_MS, MAX_OFFSET_MS),
+                Ok(_) => 0,
+                Err(e) => {
+                    mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "读歌词偏移失败");
+                    0
+                }
+            };
+            player.with_state(|st| {
+                let current = st.current_song.as_ref().is_some_and(|s| s.id == song_id);
+                let taken = st
+                    .current_lyric_offset
+                    .as_ref()
+                    .is_some_and(|(id, _)| *id == song_id);
+                if current && !taken {
+                    st.current_lyric_offset = Some((song_id, stored));
+                    if stored != 0 {
+                        st.bump_current();
+                    }
+                }
+            });
+        });
+    }
+
+    /// 在播曲的歌词偏移增减 `delta_ms`(钳到 ±10s),即刻生效并异步落库。
+    ///
+    /// # Params:
+    ///   - `delta_ms`: 增量毫秒;正 = 歌词推迟(歌词比歌声早时用),负 = 提前
+    ///
+    /// # Return:
+    ///   调整后的偏移;无在播曲时报错。
+    pub(crate) fn nudge_lyric_offset(&self, delta_ms: i64) -> color_eyre::Result<i64> {
+        let (song_id, offset) = self
+            .with_state(|st| {
+                let song_id = st.current_song.as_ref()?.id.clone();
+                let offset = st
+                    .lyric_offset_ms()
+                    .saturating_add(delta_ms)
+                    .clamp(-MAX_OFFSET_MS, MAX_OFFSET_MS);
+                st.current_lyric_offset = Some((song_id.clone(), offset));
+                st.bump_current();
+                Some((song_id, offset))
+            })
+            .ok_or_else(|| eyre!("没有在播曲目"))?;
+        self.notify()
+            .toast(ToastKind::Info, format!("歌词偏移 {offset:+}ms"));
+        let player = self.clone();
+        tokio::spawn(async move {
+            let value = if offset == 0 {
+                StoreValue::Nil
+            } else {
+                StoreValue::Int(offset)
+            };
+            let scope = player.persist().scope(song_id.namespace());
+            match scope.kv_set(&song_id, OFFSET_KEY, &value).await {
+                Ok(()) => player.notify().store_changed(&song_id, OFFSET_KEY),
+                Err(e) => {
+                    mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "歌词偏移落库失败");
+                }
+            }
+        });
+        Ok(offset)
+    }
+}
@@ -0,0 +1,465 @@
+//! 歌词解析层(配置 `lyrics`):本地歌词优先,本地全无才向 channel 拉取。
+//!
+//! 最先查歌词覆盖 `<覆盖目录>/<来源>/<歌曲 id>.lrc`:TUI 歌词编辑器存下的打轴结果(见
+//! [`PlayerCore::save_lyrics`])。它是用户对这首歌的明确指定,所以先于下面的常规链,
+//! 也不受 `prefer_local` 管。
+//!
+//! 没有覆盖时,`prefer_local` 开启则按序查三处,先命中先用:
+//! 1. sidecar:音频文件同目录、同 stem 的 `.lrc`,其次 `<歌名>.lrc`(下载导出按歌名落盘);
+//! 2. 内嵌标签:ID3v2 `SYLT`(逐行时间轴)优先,其次主标签的歌词项(ID3v2 `USLT` /
+//!    Vorbis `LYRICS` / MP4 `©lyr`);
+//! 3. 用户歌词目录:`<dir>/<来源>/<歌曲 id>.lrc`。
+//!
+//! 文本一律经 [`parse_lrc`] 解析(宽进:标准 LRC / JSON 富文本行 / 裸文本);解析后为空的
+//! 文件视作未命中,继续往下找。查找是阻塞 IO,放在 `spawn_blocking` 里跑;结果与 channel
+//! 拉取的一样经 [`PlayerCore::handle_lyrics_ready`] 落进当前曲。
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{WrapErr, eyre};
+use lofty::config::ParseOptions;
+use lofty::file::{AudioFile, FileType, TaggedFileExt};
+use lofty::id3::v2::{
+    Frame, FrameId, Id3v2Tag, SyncTextContentType, SynchronizedTextFrame, TimestampFormat,
+};
+use lofty::prelude::ItemKey;
+use lofty::probe::Probe;
+use mineral_config::LyricSourcesConfig;
+use mineral_model::{Lyrics, MediaUrl, PlayUrl, Song, SongId, parse_lrc};
+use mineral_protocol::ToastKind;
+use mineral_task::{ChannelFetchKind, Priority, TaskKind};
+
+use crate::media_cache::sanitize_segment;
+use crate::player::PlayerCore;
+
+/// 歌词来源旋钮(配置 `lyrics` 落地,用户目录缺省已解析成平台默认)。
+#[derive(Clone)]
+pub(crate) struct LyricSources {
+    /// 是否先查本地(sidecar / 内嵌标签 / 用户目录;覆盖不受它管)。
+    prefer_local: bool,
+
+    /// 用户歌词目录;平台默认也解析不出时为 `None`(不查)。
+    dir: Option<PathBuf>,
+
+    /// 歌词覆盖目录(编辑器存盘处);平台目录解析不出时为 `None`(不查,也存不了)。
+    overrides: Option<PathBuf>,
+}
+
+impl LyricSources {
+    /// 从配置段构造。
+    ///
+    /// # Params:
+    ///   - `cfg`: 配置 `lyrics` 段
+    pub(crate) fn from_config(cfg: &LyricSourcesConfig) -> Self {
+        Self {
+            prefer_local: *cfg.prefer_local(),
+            dir: cfg
+                .dir()
+                .clone()
+                .or_else(|| mineral_paths::lyrics_dir().ok()),
+            overrides: mineral_paths::lyric_overrides_dir().ok(),
+        }
+    }
+
+    /// 测试用:直接给定旋钮(不碰平台目录)。
+    #[cfg(test)]
+    pub(crate) fn new(
+        prefer_local: bool,
+        dir: Option<PathBuf>,
+        overrides: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            prefer_local,
+            dir,
+            overrides,
+        }
+    }
+
+    /// 本地有没有可查之处(没有就直接走 channel,不白起一个阻塞任务)。
+    ///
+    /// # Params:
+    ///   - `has_audio`: 是否知道该曲的本地音频文件
+    fn any_local(&self, has_audio: bool) -> bool {
+        self.overrides.is_some() || (self.prefer_local && (has_audio || self.dir.is_some()))
+    }
+
+    /// 查本地歌词:先查覆盖(总查),再在 `prefer_local` 时走 [`resolve_local`] 常规链。
+    ///
+    /// # Params:
+    ///   - `song`: 歌曲
+    ///   - `audio`: 该曲的本地音频文件(已知时)
+    ///
+    /// # Return:
+    ///   第一处命中的歌词;全无返回 `None`。
+    fn resolve(&self, song: &Song, audio: Option<&Path>) -> Option<Lyrics> {
+        if let Some(hit) = self
+            .overrides
+            .as_deref()
+            .and_then(|d| read_lrc(&user_file(d, &song.id)))
+        {
+            return Some(hit);
+        }
+        if !self.prefer_local {
+            return None;
+        }
+        resolve_local(song, audio, self.dir.as_deref())
+    }
+}
+
+/// 播放链接指向的本地文件(本地曲库 / 预排的本地副本);远端链接为 `None`。
+pub(crate) fn local_file(play_url: &PlayUrl) -> Option<PathBuf> {
+    match &play_url.url {
+        MediaUrl::Local(p) => Some(p.clone()),
+        MediaUrl::Remote(_) => None,
+    }
+}
+
+impl PlayerCore {
+    /// 为新的在播曲取歌词:先按 [`LyricSources::resolve`] 查覆盖与本地,全无再提交 channel
+    /// 拉取任务。
+    ///
+    /// # Params:
+    ///   - `song`: 在播曲
+    ///   - `audio`: 该曲的本地音频文件(已知时);sidecar / 内嵌标签都靠它定位
+    pub(crate) fn fetch_lyrics(&self, song: &Song, audio: Option<PathBuf>) {
+        let sources = self.inner.lyric_sources.clone();
+        if !sources.any_local(audio.is_some()) {
+            self.submit_lyrics_task(&song.id);
+            return;
+        }
+        let player = self.clone();
+        let song = song.clone();
+        tokio::spawn(async move {
+            let owned = song.clone();
+            let found =
+                tokio::task::spawn_blocking(move || sources.resolve(&owned, audio.as_deref()))
+                    .await
+                    .ok()
+                    .flatten();
+            match found {
+                Some(lyrics) => player.handle_lyrics_ready(&song.id, lyrics),
+                // 查找期间已切歌:旧曲不再拉(切歌时其 Lyrics 任务本就会被砍)。
+                None if player.is_current(&song.id) => player.submit_lyrics_task(&song.id),
+                None => {}
+            }
+        });
+    }
+
+    /// 提交 channel 歌词拉取任务(结果经 `LyricsReady` 事件回到 [`Self::handle_lyrics_ready`])。
+    fn submit_lyrics_task(&self, song_id: &SongId) {
+        mineral_log::debug!(target: "player", song_id = song_id.as_str(), source = ?song_id.namespace(), "submit Lyrics task");
+        self.submit_task(
+            TaskKind::ChannelFetch(ChannelFetchKind::Lyrics {
+                song_id: song_id.clone(),
+            }),
+            Priority::User,
+        );
+    }
+
+    /// 把一份 LRC 文本存为该曲的歌词覆盖(`<覆盖目录>/<来源>/<歌曲 id>.lrc`),正在播即刻换上。
+    ///
+    /// 成败都发 toast:TUI 经 IPC 调用时回执被丢弃,提示是用户唯一的反馈。
+    ///
+    /// # Params:
+    ///   - `song_id`: 目标曲
+    ///   - `lrc`: LRC 文本
+    ///
+    /// # Return:
+    ///   写盘结果;没有覆盖目录 / 文本解析后为空 / IO 失败时 `Err`。
+    pub(crate) async fn save_lyrics(
+        &self,
+        song_id: &SongId,
+        lrc: String,
+    ) -> color_eyre::Result<()> {
+        let result = self.write_lyrics(song_id, lrc).await;
+        match &result {
+            Ok(lyrics) => {
+                self.notify()
+                    .toast(ToastKind::Info, "歌词已保存".to_owned());
+                if self.is_current(song_id) {
+                    self.handle_lyrics_ready(song_id, lyrics.clone());
+                }
+            }
+            Err(e) => {
+                mineral_log::warn!(target: "lyrics", song_id = song_id.as_str(), error = mineral_log::chain(e), "保存歌词失败");
+                self.notify()
+                    .toast(ToastKind::Error, format!("保存歌词失败: {e}"));
+            }
+        }
+        result.map(|_| ())
+    }
+
+    /// 解析 + 落盘(阻塞 IO 放 `spawn_blocking`);返回解析好的歌词供即刻换上。
+    async fn write_lyrics(&self, song_id: &SongId, lrc: String) -> color_eyre::Result<Lyrics> {
+        let dir = self
+            .inner
+            .lyric_sources
+            .overrides
+            .clone()
+            .ok_or_else(|| eyre!("没有可用的歌词覆盖目录"))?;
+        let lyrics = parse_text(&lrc).ok_or_else(|| eyre!("歌词为空"))?;
+        let path = user_file(&dir, song_id);
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .wrap_err_with(|| format!("创建 {}", parent.display()))?;
+            }
+            std::fs::write(&path, lrc).wrap_err_with(|| format!("写 {}", path.display()))
+        })
+        .await
+        .wrap_err("写歌词任务中断")??;
+        Ok(lyrics)
+    }
+
+    /// `song_id` 是否仍是在播曲。
+    fn is_current(&self, song_id: &SongId) -> bool {
+        self.with_state(|st| st.current_song.as_ref().is_some_and(|s| &s.id == song_id))
+    }
+}
+
+/// 按序查本地歌词。
+///
+/// # Params:
+///   - `song`: 歌曲(sidecar 按歌名、用户目录按 id 定位)
+///   - `audio`: 该曲的本地音频文件(下载导出 / 本地曲库 / 缓存);未知时 `None`,只查用户目录
+///   - `dir`: 用户歌词目录;`None` 不查
+///
+/// # Return:
+///   第一处命中的歌词;全无返回 `None`。
+pub(crate) fn resolve_local(
+    song: &Song,
+    audio: Option<&Path>,
+    dir: Option<&Path>,
+) -> Option<Lyrics> {
+    audio
+        .and_then(|path| sidecar(song, path).or_else(|| embedded(path)))
+        .or_else(|| dir.and_then(|d| read_lrc(&user_file(d, &song.id))))
+}
+
+/// 同目录 sidecar:同 stem 的 `.lrc`,其次 `<歌名>.lrc`。
+fn sidecar(song: &Song, audio: &Path) -> Option<Lyrics> {
+    let same_stem = audio.with_extension("lrc");
+    let by_title = audio
+        .parent()
+        .map(|d| d.join(format!("{}.lrc", sanitize_segment(&song.name, "_untitled"))));
+    std::iter::once(same_stem)
+        .chain(by_title)
+        .find_map(|p| read_lrc(&p))
+}
+
+/// 用户歌词目录 / 覆盖目录里该曲的文件路径:`<dir>/<来源>/<歌曲 id>.lrc`。
+fn user_file(dir: &Path, song_id: &SongId) -> PathBuf {
+    dir.join(song_id.namespace().name())
+        .join(format!("{}.lrc", sanitize_segment(song_id.as_str(), "_")))
+}
+
+/// 读一个 `.lrc` 文件;不存在 / 读不了 / 解析后为空都算未命中。
+fn read_lrc(path: &Path) -> Option<Lyrics> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let lyrics = parse_text(&text)?;
+    mineral_log::debug!(target: "lyrics", path = %path.display(), "sidecar lyrics hit");
+    Some(lyrics)
+}
+
+/// 歌词文本 → 歌词;空文本为 `None`。
+fn parse_text(text: &str) -> Option<Lyrics> {
+    let lines = parse_lrc(text);
+    (!lines.is_empty()).then_some(Lyrics { lines })
+}
+
+/// 内嵌歌词:`SYLT` 优先,其次主标签歌词项。
+fn embedded(audio: &Path) -> Option<Lyrics> {
+    let hit = synced_frame(audio).or_else(|| {
+        let tagged = lofty::read_from_path(audio).ok()?;
+        let text = tagged
+            .primary_tag()?
+            .get_string(&ItemKey::Lyrics)?
+            .to_owned();
+        parse_text(&text)
+    });
+    if hit.is_some() {
+        mineral_log::debug!(target: "lyrics", path = %audio.display(), "embedded lyrics hit");
+    }
+    hit
+}
+
+/// 取文件 ID3v2 标签里的 `SYLT`(只认毫秒时间戳的歌词帧)并转成歌词。
+///
+/// lofty 的通用标签不承载 `SYLT`,只能按容器读出原生 ID3v2 标签再解帧;只有 MP3 / WAV /
+/// AIFF 会带 ID3v2。
+fn synced_frame(audio: &Path) -> Option<Lyrics> {
+    let tag = id3v2_tag(audio)?;
+    let id = FrameId::Valid("SYLT".into());
+    let frame = (&tag).into_iter().find_map(|f| match f {
+        Frame::Binary(b) if f.id() == &id => SynchronizedTextFrame::parse(&b.data, f.flags()).ok(),
+        _ => None,
+    })?;
+    if frame.timestamp_format != TimestampFormat::MS
+        || frame.content_type != SyncTextContentType::Lyrics
+    {
+        return None;
+    }
+    let lrc = frame
+        .content
+        .iter()
+        .map(|(ms, text)| lrc_line(*ms, text))
+        .collect::<Vec<String>>()
+        .join("\n");
+    parse_text(&lrc)
+}
+
+/// 按容器读出原生 ID3v2 标签;容器不带 ID3v2 / 读失败为 `None`。
+fn id3v2_tag(audio: &Path) -> Option<Id3v2Tag> {
+    let file_type = Probe::open(audio)
+        .ok()?
+        .guess_file_type()
+        .ok()?
+        .file_type()?;
+    let mut reader = File::open(audio).ok()?;
+    let options = ParseOptions::new();
+    match file_type {
+        FileType::Mpeg => lofty::mpeg::MpegFile::read_from(&mut reader, options)
+            .ok()?
+            .id3v2()
+            .cloned(),
+        FileType::Wav => lofty::iff::wav::WavFile::read_from(&mut reader, options)
+            .ok()?
+            .id3v2()
+            .cloned(),
+        FileType::Aiff => lofty::iff::aiff::AiffFile::read_from(&mut reader, options)
+            .ok()?
+            .id3v2()
+            .cloned(),
+        _ => None,
+    }
+}
+
+/// 毫秒 + 文本 → 一行 `[mm:ss.xxx]text`(交给 [`parse_lrc`] 统一解析)。
+fn lrc_line(ms: u32, text: &str) -> String {
+    let (min, sec, milli) = (ms / 60_000, ms / 1_000 % 60, ms % 1_000);
+    format!("[{min:02}:{sec:02}.{milli:03}]{}", text.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::TextEncoding;
+    use lofty::config::WriteOptions;
+    use lofty::id3::v2::{
+        BinaryFrame, Frame, FrameId, Id3v2Tag, SyncTextContentType, SynchronizedTextFrame,
+        TimestampFormat,
+    };
+    use lofty::prelude::{ItemKey, TagExt};
+    use mineral_model::Song;
+
+    use super::{LyricSources, resolve_local};
+
+    /// 测试曲:netease 源 id `186016`,歌名「晴天」。
+    fn song() -> Song {
+        mineral_test::with_name(mineral_test::song("186016"), "晴天")
+    }
+
+    /// 首行文本。
+    fn first_text(lyrics: &mineral_model::Lyrics) -> Option<String> {
+        lyrics.lines.first().map(|l| l.kind.text().into_owned())
+    }
+
+    /// 三处都有时 sidecar 赢;删掉 sidecar 退到内嵌;再无内嵌退到用户目录;全无为 None。
+    #[test]
+    fn resolution_order() -> color_eyre::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let audio = tmp.path().join("晴天.wav");
+        mineral_test::write_wav(&audio, &[0_i16; 800], 1, 8_000)?;
+        let mut tag = lofty::tag::Tag::new(lofty::tag::TagType::Id3v2);
+        tag.insert_text(ItemKey::Lyrics, "[00:01.00]内嵌".to_owned());
+        tag.save_to_path(&audio, WriteOptions::default())?;
+        std::fs::write(audio.with_extension("lrc"), "[00:01.00]旁挂")?;
+        let dir = tmp.path().join("lyrics");
+        std::fs::create_dir_all(dir.join("netease"))?;
+        std::fs::write(dir.join("netease/186016.lrc"), "[00:01.00]目录")?;
+        let s = song();
+
+        let hit = resolve_local(&s, Some(&audio), Some(&dir));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("旁挂"));
+        std::fs::remove_file(audio.with_extension("lrc"))?;
+        let hit = resolve_local(&s, Some(&audio), Some(&dir));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("内嵌"));
+        let hit = resolve_local(&s, None, Some(&dir));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("目录"));
+        assert!(resolve_local(&s, None, None).is_none());
+        Ok(())
+    }
+
+    /// 编辑器存下的覆盖先于常规链(压过 sidecar),且关掉 `prefer_local` 照样生效;
+    /// 没有覆盖时 `prefer_local` 关掉就不查本地。
+    #[test]
+    fn override_precedes_chain() -> color_eyre::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let audio = tmp.path().join("晴天.wav");
+        mineral_test::write_wav(&audio, &[0_i16; 800], 1, 8_000)?;
+        std::fs::write(audio.with_extension("lrc"), "[00:01.00]旁挂")?;
+        let overrides = tmp.path().join("overrides");
+        std::fs::create_dir_all(overrides.join("netease"))?;
+        std::fs::write(overrides.join("netease/186016.lrc"), "[00:01.00]覆盖")?;
+        let s = song();
+
+        let local = LyricSources::new(/*prefer_local*/ true, None, Some(overrides.clone()));
+        let hit = local.resolve(&s, Some(&audio));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("覆盖"));
+        let remote_only =
+            LyricSources::new(/*prefer_local*/ false, None, Some(overrides.clone()));
+        let hit = remote_only.resolve(&s, Some(&audio));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("覆盖"));
+
+        std::fs::remove_file(overrides.join("netease/186016.lrc"))?;
+        let hit = local.resolve(&s, Some(&audio));
+        assert_eq!(hit.as_ref().and_then(first_text).as_deref(), Some("旁挂"));
+        assert!(
+            remote_only.resolve(&s, Some(&audio)).is_none(),
+            "sidecar 不查"
+        );
+        Ok(())
+    }
+
+    /// 下载导出按歌名落盘:stem 不同时按 `<歌名>.lrc` 找到。
+    #[test]
+    fn sidecar_by_title() -> color_eyre::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let audio = tmp.path().join("03 - track.wav");
+        mineral_test::write_wav(&audio, &[0_i16; 800], 1, 8_000)?;
+        std::fs::write(tmp.path().join("晴天.lrc"), "[00:02.50]按歌名")?;
+        let hit = resolve_local(&song(), Some(&audio), None);
+        let line = hit.as_ref().and_then(|l| l.lines.first());
+        assert_eq!(line.and_then(|l| l.time_ms), Some(2_500));
+        Ok(())
+    }
+
+    /// ID3v2 `SYLT` 帧按毫秒时间戳逐行还原。
+    #[test]
+    fn embedded_sylt() -> color_eyre::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let audio = tmp.path().join("a.wav");
+        mineral_test::write_wav(&audio, &[0_i16; 800], 1, 8_000)?;
+        let sylt = SynchronizedTextFrame::new(
+            TextEncoding::UTF8,
+            *b"chi",
+            TimestampFormat::MS,
+            SyncTextContentType::Lyrics,
+            None,
+            vec![(1_000, "第一句".to_owned()), (62_345, "第二句".to_owned())],
+        );
+        let mut tag = Id3v2Tag::new();
+        tag.insert(Frame::Binary(BinaryFrame::new(
+            FrameId::Valid("SYLT".into()),
+            sylt.as_bytes()?,
+        )));
+        tag.save_to_path(&audio, WriteOptions::default())?;
+
+        let hit = resolve_local(&song(), Some(&audio), None);
+        let times: Vec<Option<u64>> = hit
+            .map(|l| l.lines.iter().map(|line| line.time_ms).collect())
+            .unwrap_or_default();
+        assert_eq!(times, vec![Some(1_000), Some(62_345)]);
+        Ok(())
+    }
+}
@@ -1,36 +1,53 @@
 //! 系统媒体服务(MPRIS)接入:把 [`PlayerCore`] 的播放状态上报给系统媒体控件,
-//! 把控件发来的命令转成播放控制。
+//! 把控件发来的命令转成播放控制;队列与歌单库另经 [`queue`] 上报给 MPRIS
+//! TrackList / Playlists,控件可浏览、跳转、增删与激活歌单。
 //!
 //! 只在 daemon(常驻 server)里启用 —— 系统媒体控件控制的是常驻播放,关掉 TUI
 //! 仍然有效。in-proc 模式(TUI 自起 server)不调用本模块,避免多实例抢同一条
 //! D-Bus 总线名。
 
+mod queue;
+
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use mineral_audio::AudioSnapshot;
-use mineral_media::{LoopMode, MediaCommand, MediaConfig, MediaService, NowPlaying, PlaybackState};
+use mineral_media::{
+    LoopMode, MediaCommand, MediaConfig, MediaService, NowPlaying, PlaybackState, TrackRef,
+};
 use mineral_model::{Lyrics, MediaUrl, Song, SongId};
-use mineral_protocol::{PlayMode, Repeat};
+use mineral_protocol::{Event, PlayMode, Repeat};
+use tokio::sync::broadcast;
 
+use self::queue::{ListReporter, QueueControl};
+use crate::client::ClientHandle;
 use crate::player::PlayerCore;
 
 /// 起系统媒体服务:注册控件、attach 命令回调、spawn 状态上报 task。
 ///
 /// # Params:
 ///   - `player`: 服务端播放核心(命令回调与上报都打到它)。
+///   - `client`: 指令面(队列 / 歌单类命令走它)。
+///   - `events`: event hub(激活歌单时等取数结果)。
 ///
 /// # Return:
 ///   注册失败(如无 D-Bus session)返回 `Err`;调用方可据此降级(daemon 照常跑)。
-pub(crate) fn start(player: PlayerCore) -> color_eyre::Result<()> {
+pub(crate) fn start(
+    player: PlayerCore,
+    client: ClientHandle,
+    events: broadcast::Sender<Event>,
+) -> color_eyre::Result<()> {
     let cmd_player = player.clone();
+    let queue = QueueControl::new(player.clone(), client, events);
     let on_command: Arc<dyn Fn(MediaCommand) + Send + Sync> =
-        Arc::new(move |cmd| handle_command(&cmd_player, cmd));
+        Arc::new(move |cmd| handle_command(&cmd_player, &queue, cmd));
     // identity 与 bus 后缀一致(都 "mineral"):显示端常用 `playerctl -p <identity>`
     // 拉数据,而 playerctl 的 -p 匹配的是 bus 后缀,大小写需一致。
     let config = MediaConfig::builder()
         .dbus_name("mineral")
         .display_name("mineral")
+        .min_rate(f64::from(mineral_audio::MIN_SPEED))
+        .max_rate(f64::from(mineral_audio::MAX_SPEED))
         .build();
     let service = MediaService::spawn(&config, on_command)?;
     tokio::spawn(report_loop(player, service));
@@ -38,8 +55,8 @@ pub(crate) fn start(player: PlayerCore) -> color_eyre::Result<()> {
 }
 
 /// 系统媒体控件命令 → 播放控制。走 PlayerCore 的 transport 方法(执行 + 埋点同点):
-/// 媒体键是用户按的,actor=User,与界面按键同样入库。
-fn handle_command(player: &PlayerCore, cmd: MediaCommand) {
+/// 媒体键是用户按的,actor=User,与界面按键同样入库。队列 / 歌单类命令交 [`QueueControl`]。
+fn handle_command(player: &PlayerCore, queue: &QueueControl, cmd: MediaCommand) {
     use mineral_stats::Actor;
     let audio = player.audio();
     match cmd {
@@ -67,6 +84,16 @@ fn handle_command(player: &PlayerCore, cmd: MediaCommand) {
             let mode = player.with_state(|st| st.play_mode);
             player.set_play_mode(mode.with_repeat(loop_to_repeat(loop_mode)), Actor::User);
         }
+        // 控件只给倍速;变速方式沿用当前的(report_loop 随即回报真实倍速)。
+        MediaCommand::SetRate(pct) => {
+            let mode = audio.snapshot().speed_mode;
+            audio.set_speed(f32::from(pct) / 100.0, mode);
+        }
+        MediaCommand::GoTo(track) => queue.go_to(track),
+        MediaCommand::RemoveTrack(track) => queue.remove(track),
+        MediaCommand::AddTrack { uri, after, play } => queue.add(&uri, after, play),
+        MediaCommand::OpenUri(uri) => queue.open_uri(&uri),
+        MediaCommand::ActivatePlaylist(id) => queue.activate_playlist(&id),
     }
 }
 
@@ -148,29 +175,39 @@ fn looks_like_seek(
 
 /// 周期把 metadata + playback 上报给系统媒体控件。
 ///
-/// metadata 在换歌、或任一路歌词从无到有时重报;playback(状态 + 进度)每 tick 上报;
-/// 检测到非线性位置跳变(seek)时补发 `Seeked` 信号(外推型客户端靠它重置基准)。
+/// metadata 在换歌、当前曲的队列位置变化、或任一路歌词从无到有时重报;playback(状态 +
+/// 进度)每 tick 上报;检测到非线性位置跳变(seek)时补发 `Seeked` 信号(外推型客户端靠它
+/// 重置基准);队列与歌单库变化时经 [`ListReporter`] 重报。
 async fn report_loop(player: PlayerCore, service: MediaService) {
     let mut tick = tokio::time::interval(Duration::from_millis(player.media_report_interval_ms()));
     let seek_threshold_ms = player.media_seek_threshold_ms();
     let mut last_song_id = Option::<SongId>::None;
     let mut last_presence = LyricsPresence::default();
+    let mut last_offset = 0_i64;
     let mut last_pos = Option::<u64>::None;
     let mut last_tick = Instant::now();
     let mut last_playing = false;
     let mut last_play_mode = Option::<PlayMode>::None;
+    let mut last_speed_pct = Option::<u16>::None;
+    let mut last_track = Option::<TrackRef>::None;
+    let mut lists = ListReporter::default();
     loop {
         tick.tick().await;
         let now = Instant::now();
-        // in-process 直读 State 需要的三个字段(歌 + 歌词 + 模式),不再拉含整个
-        // queue 的全量快照(queue 这里用不上,clone 它纯浪费)。
-        let (current_song, current_lyrics, play_mode) = player.with_state(|st| {
-            (
-                st.current_song.clone(),
-                st.current_lyrics.clone(),
-                st.play_mode,
-            )
-        });
+        // in-process 直读 State 需要的字段(歌 + 歌词 + 歌词偏移 + 模式 + 队列位置),不拉含
+        // 整个 queue 的全量快照(queue 只在版本变化时由 ListReporter 单独 clone)。
+        let (current_song, current_lyrics, lyric_offset, play_mode, track) =
+            player.with_state(|st| {
+                (
+                    st.current_song.clone(),
+                    st.current_lyrics.clone(),
+                    st.lyric_offset_ms(),
+                    st.play_mode,
+                    st.cursor
+                        .queue_index()
+                        .map(|index| TrackRef::new(st.queue_version, index)),
+                )
+            });
         let audio = player.audio().snapshot();
 
         // 歌词在 channel 层已结构化清洗,这里只在确实要重发 metadata 时才序列化:
@@ -178,9 +215,16 @@ async fn report_loop(player: PlayerCore, service: MediaService) {
         let cur_id = current_song.as_ref().map(|s| s.id.clone());
         let presence = LyricsPresence::of(current_lyrics.as_ref());
         let song_changed = cur_id != last_song_id;
-        if song_changed || presence != last_presence {
+        // 歌词偏移微调也要重发:asText 的时间戳已按偏移平移,显示端才跟得上校正。
+        // 队列位置变了(队列被改 / 重复曲间跳转)也要重发:trackid 得跟上 TrackList。
+        if song_changed
+            || presence != last_presence
+            || lyric_offset != last_offset
+            || track != last_track
+        {
             if let Some(song) = &current_song {
-                let now_playing = build_now_playing(song, current_lyrics.as_ref());
+                let shifted = current_lyrics.as_ref().map(|l| l.shifted(lyric_offset));
+                let now_playing = build_now_playing(song, shifted.as_ref(), track);
                 if let Err(e) = service.set_now_playing(&now_playing) {
                     mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "set_now_playing failed");
                 }
@@ -192,7 +236,10 @@ async fn report_loop(player: PlayerCore, service: MediaService) {
             }
             last_song_id = cur_id;
             last_presence = presence;
+            last_offset = lyric_offset;
+            last_track = track;
         }
+        lists.report(&player, &service);
 
         // 检测 seek:report_loop 是 snapshot 轮询拿不到事件,靠线性外推对比判定跳变,
         // 跳变时补发 Seeked(只在有当前歌时;首 tick last_pos=None 不判定)。
@@ -228,6 +275,14 @@ async fn report_loop(player: PlayerCore, service: MediaService) {
             last_play_mode = Some(play_mode);
         }
 
+        // 倍速变化时回写 MPRIS Rate;首 tick 必报一次(同上)。
+        if last_speed_pct != Some(audio.speed_pct) {
+            if let Err(e) = service.set_rate(f64::from(audio.speed_pct) / 100.0) {
+                mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "set_rate failed");
+            }
+            last_speed_pct = Some(audio.speed_pct);
+        }
+
         let (state, position) = playback_of(current_song.as_ref(), &audio);
         if let Err(e) = service.set_playback(state, position) {
             mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "set_playback failed");
@@ -254,8 +309,9 @@ fn playback_of(
 /// [`Song`] + 结构化歌词 → 上报用的 [`NowPlaying`]。
 ///
 /// 歌词原样以结构化形式塞进 [`NowPlaying`],序列化(LRC / JSON)推迟到 MPRIS 适配层
-/// (mineral-media)写 metadata 的最边界做。无歌词时各路为空。
-fn build_now_playing(song: &Song, lyrics: Option<&Lyrics>) -> NowPlaying {
+/// (mineral-media)写 metadata 的最边界做。无歌词时各路为空。`track` 是该曲的队列位置
+/// (写进 `mpris:trackid`),不在队列里给 `None`。
+fn build_now_playing(song: &Song, lyrics: Option<&Lyrics>, track: Option<TrackRef>) -> NowPlaying {
     let artist = if song.artists.is_empty() {
         None
     } else {
@@ -271,7 +327,8 @@ fn build_now_playing(song: &Song, lyrics: Option<&Lyrics>) -> NowPlaying {
         .artist(artist)
         .album(song.album.as_ref().map(|a| a.name.clone()))
         .cover_url(song.cover_url.as_ref().map(cover_to_url))
-        .duration(song.duration_ms.map(Duration::from_millis));
+        .duration(song.duration_ms.map(Duration::from_millis))
+        .track(track);
     match lyrics {
         None => builder.build(),
         // 翻译 / 罗马音轨从合并行重建:时间戳取原文行的,与 asText 严格对齐。
@@ -344,7 +401,11 @@ async fn load_cover_bytes(cover: &MediaUrl) -> color_eyre::Result<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::looks_like_seek;
+    use mineral_audio::SpeedMode;
+    use mineral_media::MediaCommand;
+
+    use super::queue::QueueControl;
+    use super::{handle_command, looks_like_seek};
 
     #[test]
     fn normal_playback_not_seek() {
@@ -385,4 +446,29 @@ mod tests {
             10_000, 10_000, 5_000, false, /*threshold_ms*/ 1000
         ));
     }
+
+    /// `SetRate` 按百分比改倍速,变速方式沿用当前的。
+    #[tokio::test]
+    async fn set_rate_keeps_speed_mode() -> color_eyre::Result<()> {
+        let cfg = mineral_config::Config::defaults()?;
+        let server = crate::Server::spawn(
+            Vec::new(),
+            mineral_audio::AudioMode::ForceNull,
+            mineral_persist::ServerStore::disabled(),
+            crate::ServerConfig::from_config(&cfg),
+            mineral_config::default_tree()?,
+            /*script*/ None,
+            crate::StatsRecorder::disabled(),
+        )
+        .await?;
+        let player = server.player();
+        let queue = QueueControl::new(player.clone(), server.client(), server.event_sink());
+        player.audio().set_speed(1.0, SpeedMode::PitchShift);
+
+        handle_command(&player, &queue, MediaCommand::SetRate(150));
+        let snap = player.audio().snapshot();
+        assert_eq!(snap.speed_pct, 150);
+        assert_eq!(snap.speed_mode, SpeedMode::PitchShift);
+        Ok(())
+    }
 }
@@ -0,0 +1,486 @@
+//! MPRIS TrackList / Playlists 的服务端一侧:把控件的队列跳转 / 增删、`OpenUri`、
+//! 激活歌单落到指令面,并备好上报用的队列条目与歌单库。
+//!
+//! 控件拿到的队列条目以「队列版本 + 下标」编号([`TrackRef`]),版本对不上的命令
+//! 一律视为过期丢弃——队列已被别处改过,同一下标可能已是另一首。
+
+use std::time::{Duration, Instant};
+
+use mineral_media::{MediaService, NowPlaying, PlaylistEntry, TrackRef};
+use mineral_model::{Playlist, Song};
+use mineral_protocol::{Event, QueueAnchor, QueueContextWire, QueueOp};
+use mineral_task::{ChannelFetchKind, TaskEvent};
+use tokio::sync::broadcast;
+
+use super::{build_now_playing, cover_to_url};
+use crate::client::{Client, ClientHandle};
+use crate::player::PlayerCore;
+
+/// 队列 / 歌单类命令的执行上下文。
+///
+/// 命令回调在 MPRIS 专属线程上触发,要等取数结果的命令(激活歌单)经 `runtime`
+/// 投回 daemon 的 tokio runtime 执行。
+pub(super) struct QueueControl {
+    /// 服务端播放核心(读队列与版本号)。
+    player: PlayerCore,
+
+    /// 指令面(改队列走它,与其它入口同样埋点)。
+    client: ClientHandle,
+
+    /// event hub(取数结果帧从这里来)。
+    events: broadcast::Sender<Event>,
+
+    /// daemon 的 tokio runtime。
+    runtime: tokio::runtime::Handle,
+}
+
+impl QueueControl {
+    /// 构造执行上下文;须在 daemon 的 tokio runtime 内调用。
+    pub(super) fn new(
+        player: PlayerCore,
+        client: ClientHandle,
+        events: broadcast::Sender<Event>,
+    ) -> Self {
+        Self {
+            player,
+            client,
+            events,
+            runtime: tokio::runtime::Handle::current(),
+        }
+    }
+
+    /// 跳到队列中的某首(TrackList `GoTo`)。
+    pub(super) fn go_to(&self, track: TrackRef) {
+        if let Some(song) = self.song_at(track) {
+            self.client.play_song(song);
+        }
+    }
+
+    /// 从队列移除某首(TrackList `RemoveTrack`)。
+    pub(super) fn remove(&self, track: TrackRef) {
+        if let Some(song) = self.song_at(track) {
+            let anchor = QueueAnchor::new(track.index(), song.id);
+            self.client.queue_edit(QueueOp::Remove(anchor));
+        }
+    }
+
+    /// 打开并立即播放一个 URI(`OpenUri`):插到当前曲之后再起播,留在队列里可被跳回。
+    pub(super) fn open_uri(&self, uri: &str) {
+        let Some(song) = self.resolve_uri(uri) else {
+            return;
+        };
+        self.client
+            .queue_insert_next(song.clone(), QueueContextWire::Manual);
+        self.client.play_song(song);
+    }
+
+    /// 取引用指向的那首;版本过期或越界返回 `None`(debug 记一笔)。
+    fn song_at(&self, track: TrackRef) -> Option<Song> {
+        let (version, song) = self
+            .player
+            .with_state(|st| (st.queue_version, st.queue.get(track.index()).cloned()));
+        if version != track.version() {
+            mineral_log::debug!(target: "media", ?track, version, "stale track id, ignored");
+            return None;
+        }
+        song
+    }
+
+    /// 解析 URI;认不出记 warn 返回 `None`。
+    fn resolve_uri(&self, uri: &str) -> Option<Song> {
+        crate::uri::song_from_uri(uri, &self.client.channel_caps())
+            .inspect_err(|e| {
+                mineral_log::warn!(target: "media", uri, error = mineral_log::chain(e), "mpris add track: unrecognized uri");
+            })
+            .ok()
+    }
+
+    /// 解析 URI 并插进队列:紧跟 `after` 之后;`after` 为 `None`(`NoTrack`)插到最前。
+    /// `after` 版本过期则整条丢弃(同 [`Self::go_to`])。
+    ///
+    /// # Params:
+    ///   - `uri`: 分享链接 / `file://` / 限定 id(见 [`crate::uri::song_from_uri`])
+    ///   - `after`: 插在哪首之后
+    ///   - `play`: 加入后是否立即播放
+    pub(super) fn add(&self, uri: &str, after: Option<TrackRef>, play: bool) {
+        let pos = match after {
+            Some(track) => match self.song_at(track) {
+                Some(_) => track.index() + 1,
+                None => return,
+            },
+            None => 0,
+        };
+        let Some(song) = self.resolve_uri(uri) else {
+            return;
+        };
+        self.client
+            .queue_insert_at(song.clone(), pos, QueueContextWire::Manual);
+        if play {
+            self.client.play_song(song);
+        }
+    }
+
+    /// 取歌单曲目,整张替换队列并从第一首播起(在 daemon runtime 上异步执行)。
+    ///
+    /// # Params:
+    ///   - `id`: 歌单的限定 id(上报 [`PlaylistEntry`] 时给的)
+    pub(super) fn activate_playlist(&self, id: &str) {
+        let Some(playlist) = self
+            .client
+            .library_snapshot()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|p| p.id.qualified() == id)
+        else {
+            mineral_log::warn!(target: "media", id, "mpris activate playlist: not in library");
+            return;
+        };
+        let client = self.client.clone();
+        let events = self.events.clone();
+        self.runtime.spawn(async move {
+            let wanted = playlist.id.clone();
+            let fetch = ChannelFetchKind::PlaylistDetail { id: wanted.clone() };
+            let fetched = crate::fetch::await_fetch(&client, &events, fetch, |event| match event {
+                TaskEvent::PlaylistDetailFetched { id, playlist } if id == wanted => {
+                    Some(playlist.songs)
+                }
+                _ => None,
+            })
+            .await;
+            let songs = match fetched {
+                Ok(songs) => songs,
+                Err(e) => {
+                    mineral_log::warn!(target: "media", id = wanted.qualified(), failure = ?e, "mpris activate playlist: fetch failed");
+                    return;
+                }
+            };
+            let Some(first) = songs.first().cloned() else {
+                mineral_log::debug!(target: "media", id = wanted.qualified(), "mpris activate playlist: empty");
+                return;
+            };
+            let context = QueueContextWire::Playlist {
+                id: playlist.id,
+                name: Some(playlist.name),
+            };
+            client.set_queue(songs, first.id.clone(), context);
+            client.play_song(first);
+        });
+    }
+}
+
+/// 歌单库的轮询间隔:库只在登录 / 刷新时变,不必每个上报 tick 都拷一遍。
+const LIBRARY_POLL: Duration = Duration::from_secs(5);
+
+/// 队列与歌单库的上报状态:只在变化时重报。
+#[derive(Default)]
+pub(super) struct ListReporter {
+    /// 上次上报的队列版本号;`None` = 还没报过。
+    queue_version: Option<u64>,
+
+    /// 上次上报的活动歌单 id。
+    active: Option<String>,
+
+    /// 上次上报的歌单库;`None` = 库还没有任何源结论。
+    library: Option<Vec<PlaylistEntry>>,
+
+    /// 上次轮询歌单库的时刻。
+    last_poll: Option<Instant>,
+}
+
+impl ListReporter {
+    /// 队列版本变了重报整个队列;歌单库(节流轮询)或活动歌单变了重报歌单。
+    ///
+    /// 当前曲的 metadata 须已按新队列版本报过(调用方先报 metadata 再调本方法),
+    /// 控件才能在新列表里认出当前曲。
+    pub(super) fn report(&mut self, player: &PlayerCore, service: &MediaService) {
+        let known = self.queue_version;
+        let changed = player.with_state(|st| {
+            (known != Some(st.queue_version)).then(|| {
+                (
+                    st.queue_version,
+                    st.queue.clone(),
+                    active_playlist(&st.queue_context),
+                )
+            })
+        });
+        let mut playlists_dirty = false;
+        if let Some((version, queue, active)) = changed {
+            if let Err(e) = service.set_tracklist(version, &tracklist_entries(&queue)) {
+                mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "set_tracklist failed");
+            }
+            self.queue_version = Some(version);
+            playlists_dirty |= active != self.active;
+            self.active = active;
+        }
+        let now = Instant::now();
+        let due = match self.last_poll {
+            Some(at) => now.duration_since(at) >= LIBRARY_POLL,
+            None => true,
+        };
+        if due {
+            self.last_poll = Some(now);
+            let library = player
+                .library()
+                .cached_snapshot()
+                .map(|l| playlist_entries(&l));
+            if library.is_some() && library != self.library {
+                self.library = library;
+                playlists_dirty = true;
+            }
+        }
+        if playlists_dirty {
+            let library = self.library.as_deref().unwrap_or_default();
+            if let Err(e) = service.set_playlists(library, self.active.as_deref()) {
+                mineral_log::warn!(target: "media", error = mineral_log::chain(&e), "set_playlists failed");
+            }
+        }
+    }
+}
+
+/// 队列语境 → 活动歌单 id;队列不来自歌单为 `None`。
+fn active_playlist(context: &mineral_stats::QueueContext) -> Option<String> {
+    match context {
+        mineral_stats::QueueContext::Playlist { id, .. } => Some(id.qualified()),
+        _ => None,
+    }
+}
+
+/// 队列 → 上报用的条目(只带展示字段,不带歌词;编号由后端按下标给)。
+fn tracklist_entries(queue: &[Song]) -> Vec<NowPlaying> {
+    queue
+        .iter()
+        .map(|song| build_now_playing(song, None, None))
+        .collect()
+}
+
+/// 歌单库 → 上报用的歌单条目,保持库的展示序。
+fn playlist_entries(library: &[Playlist]) -> Vec<PlaylistEntry> {
+    library
+        .iter()
+        .map(|p| {
+            PlaylistEntry::builder()
+                .id(p.id.qualified())
+                .name(p.name.clone())
+                .icon(p.cover_url.as_ref().map(cover_to_url))
+                .build()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use mineral_channel_core::{
+        ArtistSections, ChannelCaps, Error, MusicChannel, Page, Result as ChannelResult, SearchHits,
+    };
+    use mineral_media::TrackRef;
+    use mineral_model::{BitRate, PlayUrl, Playlist, PlaylistId, Song, SongId, SourceKind};
+    use mineral_protocol::{PlayCursor, QueueContextWire};
+    use mineral_test::song;
+    use pretty_assertions::assert_eq;
+
+    use super::QueueControl;
+    use crate::client::Client;
+    use crate::player::PlayerCore;
+
+    /// 歌单 mock:`my_playlists` 报一张歌单(只有表头),`playlist_detail` 给全曲目。
+    struct PlaylistChannel {
+        /// 带曲目的完整歌单。
+        playlist: Playlist,
+    }
+
+    #[async_trait]
+    impl MusicChannel for PlaylistChannel {
+        fn source(&self) -> SourceKind {
+            SourceKind::NETEASE
+        }
+
+        fn caps(&self) -> ChannelCaps {
+            ChannelCaps::builder()
+                .searchable(Vec::new())
+                .playlist_edit(false)
+                .artist_sections(ArtistSections::new(Vec::new()))
+                .build()
+        }
+
+        async fn search_songs(&self, _q: &str, _p: Page) -> ChannelResult<SearchHits<Song>> {
+            Err(Error::NotSupported)
+        }
+
+        async fn songs_detail(&self, _ids: &[SongId]) -> ChannelResult<Vec<Song>> {
+            Err(Error::NotSupported)
+        }
+
+        async fn song_urls(&self, _ids: &[SongId], _q: BitRate) -> ChannelResult<Vec<PlayUrl>> {
+            Err(Error::NotSupported)
+        }
+
+        async fn my_playlists(&self) -> ChannelResult<Vec<Playlist>> {
+            let mut header = self.playlist.clone();
+            header.songs.clear();
+            Ok(vec![header])
+        }
+
+        async fn playlist_detail(&self, id: &PlaylistId) -> ChannelResult<Playlist> {
+            if *id == self.playlist.id {
+                Ok(self.playlist.clone())
+            } else {
+                Err(Error::NotSupported)
+            }
+        }
+    }
+
+    /// 起进程内 Server(ForceNull 音频 + 禁用 persist + 歌单 mock),队列预置 a/b/c、游标在 a(未起播)。
+    ///
+    /// # Return:
+    ///   (执行上下文, 播放核心, Server)。Server 须由调用方持有(drop 即停)。
+    async fn control() -> color_eyre::Result<(QueueControl, PlayerCore, crate::Server)> {
+        let channel = PlaylistChannel {
+            playlist: Playlist::builder()
+                .id(PlaylistId::new(SourceKind::NETEASE, "p1"))
+                .name(String::from("日常"))
+                .songs(vec![song("p-a"), song("p-b")])
+                .build(),
+        };
+        let cfg = mineral_config::Config::defaults()?;
+        let server = crate::Server::spawn(
+            vec![Arc::new(channel)],
+            mineral_audio::AudioMode::ForceNull,
+            mineral_persist::ServerStore::disabled(),
+            crate::ServerConfig::from_config(&cfg),
+            mineral_config::default_tree()?,
+            /*script*/ None,
+            crate::StatsRecorder::disabled(),
+        )
+        .await?;
+        let player = server.player();
+        let client = server.client();
+        client.set_queue(
+            vec![song("a"), song("b"), song("c")],
+            song("a").id,
+            QueueContextWire::Manual,
+        );
+        let control = QueueControl::new(player.clone(), client, server.event_sink());
+        Ok((control, player, server))
+    }
+
+    /// 当前队列版本下第 `index` 首的引用。
+    fn track(player: &PlayerCore, index: usize) -> TrackRef {
+        TrackRef::new(player.with_state(|st| st.queue_version), index)
+    }
+
+    /// 队列的 id 串(逗号连接)。
+    fn queue_ids(player: &PlayerCore) -> String {
+        player.with_state(|st| {
+            st.queue
+                .iter()
+                .map(|s| s.id.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
+    /// 在播曲的 id。
+    fn current_id(player: &PlayerCore) -> Option<String> {
+        player.with_state(|st| st.current_song.as_ref().map(|s| s.id.as_str().to_owned()))
+    }
+
+    /// 轮询直到条件成立或超时(异步任务落地用)。
+    async fn wait_until(mut pred: impl FnMut() -> bool) -> bool {
+        for _ in 0..200 {
+            if pred() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        pred()
+    }
+
+    /// GoTo / RemoveTrack 按「版本 + 下标」落到对应那首;队列改过之后旧引用一律丢弃。
+    #[tokio::test]
+    async fn go_to_and_remove_reject_stale_refs() -> color_eyre::Result<()> {
+        let (control, player, _server) = control().await?;
+        let before = track(&player, 0);
+        control.go_to(track(&player, 2));
+        assert_eq!(current_id(&player).as_deref(), Some("c"));
+
+        control.remove(track(&player, 0));
+        assert_eq!(queue_ids(&player), "b,c");
+
+        control.remove(before);
+        control.go_to(before);
+        assert_eq!(queue_ids(&player), "b,c", "过期引用不删");
+        assert_eq!(current_id(&player).as_deref(), Some("c"), "过期引用不跳");
+
+        control.go_to(track(&player, 5));
+        assert_eq!(current_id(&player).as_deref(), Some("c"), "越界不跳");
+        Ok(())
+    }
+
+    /// AddTrack 插在给定那首之后;`NoTrack` 插到最前;过期引用 / 认不出的 URI 不动队列。
+    #[tokio::test]
+    async fn add_inserts_after_track() -> color_eyre::Result<()> {
+        let (control, player, _server) = control().await?;
+        let stale = track(&player, 0);
+        control.add("netease:x", Some(track(&player, 1)), false);
+        assert_eq!(queue_ids(&player), "a,b,x,c");
+        control.add("netease:y", None, false);
+        assert_eq!(queue_ids(&player), "y,a,b,x,c");
+        assert_eq!(
+            player.with_state(|st| (st.cursor, st.current_song.is_none())),
+            (PlayCursor::InQueue(1), true),
+            "不起播,游标跟着 a 后移"
+        );
+
+        control.add("netease:z", Some(stale), false);
+        control.add("nowhere:z", None, false);
+        assert_eq!(queue_ids(&player), "y,a,b,x,c");
+
+        control.add("netease:w", Some(track(&player, 4)), true);
+        assert_eq!(queue_ids(&player), "y,a,b,x,c,w");
+        assert_eq!(
+            current_id(&player).as_deref(),
+            Some("w"),
+            "play=true 立即起播"
+        );
+        Ok(())
+    }
+
+    /// OpenUri 插到当前曲之后并起播。
+    #[tokio::test]
+    async fn open_uri_plays_next_to_current() -> color_eyre::Result<()> {
+        let (control, player, _server) = control().await?;
+        control.open_uri("netease:x");
+        assert_eq!(queue_ids(&player), "a,x,b,c");
+        assert_eq!(current_id(&player).as_deref(), Some("x"));
+        Ok(())
+    }
+
+    /// 激活歌单:拉全曲目整张替换队列并从第一首播起;不在库里的 id 不动队列。
+    #[tokio::test]
+    async fn activate_playlist_replaces_queue() -> color_eyre::Result<()> {
+        let (control, player, _server) = control().await?;
+        let library = || {
+            player
+                .library()
+                .cached_snapshot()
+                .is_some_and(|l| !l.is_empty())
+        };
+        assert!(wait_until(library).await, "库应载入 mock 歌单");
+
+        control.activate_playlist("netease:unknown");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(queue_ids(&player), "a,b,c");
+
+        control.activate_playlist("netease:p1");
+        assert!(
+            wait_until(|| queue_ids(&player) == "p-a,p-b").await,
+            "队列应换成歌单曲目"
+        );
+        assert_eq!(current_id(&player).as_deref(), Some("p-a"));
+        Ok(())
+    }
+}
@@ -185,7 +185,7 @@ fn library_subdir(source: &str, album: Option<&str>, quality: BitRate) -> String
 ///
 /// # Return:
 ///   扩展名(不含点)。
-fn ext_for(format: Option<&AudioFormat>, quality: BitRate) -> String {
+pub(crate) fn ext_for(format: Option<&AudioFormat>, quality: BitRate) -> String {
     if let Some(f) = format {
         return f.as_str().to_owned();
     }
@@ -204,7 +204,7 @@ fn ext_for(format: Option<&AudioFormat>, quality: BitRate) -> String {
 ///
 /// # Return:
 ///   合法的单段名(非空)。
-fn sanitize_segment(raw: &str, fallback: &str) -> String {
+pub(crate) fn sanitize_segment(raw: &str, fallback: &str) -> String {
     let mut out = String::with_capacity(raw.len());
     for ch in raw.chars() {
         if matches!(ch, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || ch.is_control() {
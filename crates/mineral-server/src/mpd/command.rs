@@ -0,0 +1,781 @@
+//! MPD 命令 → [`ClientHandle`] 调用。每条命令产出应答体(`key: value` 行)或一个
+//! [`Ack`];`OK` / `list_OK` 收尾与命令列表由 [`super::session`] 负责。
+//!
+//! 写命令与 dispatch 同样 fire-and-forget,新状态由客户端再 `status` 取。队列条目的
+//! `Pos` 与 `Id` 同为下标。
+
+use std::fmt::{Display, Write};
+
+use mineral_model::{MediaUrl, Song, SourceKind};
+use mineral_protocol::{
+    Event, PlayMode, PlayerVersions, QueueAnchor, QueueContextWire, QueueEditOutcome, QueueOp,
+    Repeat,
+};
+use tokio::sync::broadcast;
+
+use super::library;
+use crate::client::{Client, ClientHandle};
+use crate::uri::song_from_uri;
+
+/// 支持的全部命令(`commands` 应答;含会话层处理的几条)。
+pub(super) const COMMANDS: &[&str] = &[
+    "add",
+    "addid",
+    "clear",
+    "close",
+    "command_list_begin",
+    "command_list_end",
+    "command_list_ok_begin",
+    "commands",
+    "consume",
+    "currentsong",
+    "delete",
+    "deleteid",
+    "find",
+    "findadd",
+    "getvol",
+    "idle",
+    "list",
+    "lsinfo",
+    "next",
+    "noidle",
+    "notcommands",
+    "outputs",
+    "password",
+    "pause",
+    "ping",
+    "play",
+    "playid",
+    "playlistid",
+    "playlistinfo",
+    "plchanges",
+    "previous",
+    "random",
+    "repeat",
+    "search",
+    "searchadd",
+    "seek",
+    "seekcur",
+    "seekid",
+    "setvol",
+    "single",
+    "status",
+    "stop",
+    "tagtypes",
+];
+
+/// 命令执行上下文(每连接一份,clone 廉价)。
+#[derive(Clone)]
+pub(super) struct Ctx {
+    /// 指令面(与 unix socket 连接同一个 player)。
+    pub(super) client: ClientHandle,
+
+    /// Event 推送 hub(idle 订阅 + 搜索配对结果帧)。
+    pub(super) events: broadcast::Sender<Event>,
+}
+
+/// MPD 协议错误码(`ACK [<code>@<index>]`)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum AckCode {
+    /// 参数缺失 / 格式不对 / 越界。
+    Arg,
+
+    /// 密码不对。
+    Password,
+
+    /// 未出示密码就执行受限命令。
+    Permission,
+
+    /// 未知命令。
+    Unknown,
+
+    /// 引用的东西不存在(歌曲 / 下标)。
+    NoExist,
+
+    /// 内部失败(搜索超时、队列并发变更等)。
+    System,
+}
+
+impl AckCode {
+    /// 协议数值。
+    fn code(self) -> u8 {
+        match self {
+            Self::Arg => 2,
+            Self::Password => 3,
+            Self::Permission => 4,
+            Self::Unknown => 5,
+            Self::NoExist => 50,
+            Self::System => 52,
+        }
+    }
+}
+
+/// 命令失败应答。
+#[derive(Debug)]
+pub(super) struct Ack {
+    /// 错误码。
+    code: AckCode,
+
+    /// 人读的原因。
+    message: String,
+}
+
+impl Ack {
+    /// 构造失败应答。
+    ///
+    /// # Params:
+    ///   - `code`: 错误码
+    ///   - `message`: 人读的原因
+    pub(super) fn new(code: AckCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// 渲染成协议行(不含换行)。
+    ///
+    /// # Params:
+    ///   - `index`: 命令在命令列表中的下标(单条命令为 0)
+    ///   - `command`: 出错的命令名
+    pub(super) fn render(&self, index: usize, command: &str) -> String {
+        format!(
+            "ACK [{}@{index}] {{{command}}} {}",
+            self.code.code(),
+            self.message
+        )
+    }
+}
+
+/// 应答体累积器:逐行 `key: value`。
+#[derive(Default)]
+pub(super) struct Reply(String);
+
+impl Reply {
+    /// 追加一行;值里的换行折成空格(协议按行切分)。
+    pub(super) fn pair(&mut self, key: &str, value: impl Display) {
+        let value = value.to_string().replace(['\r', '\n'], " ");
+        let _ = writeln!(self.0, "{key}: {value}");
+    }
+
+    /// 取出应答体。
+    pub(super) fn into_body(self) -> String {
+        self.0
+    }
+}
+
+/// 毫秒按 MPD 的秒数写法渲染(`12.345`)。
+struct Seconds(u64);
+
+impl Display for Seconds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:03}", self.0 / 1000, self.0 % 1000)
+    }
+}
+
+/// 执行一条命令(会话层命令已在 [`super::session`] 截走)。
+///
+/// # Params:
+///   - `ctx`: 执行上下文
+///   - `name`: 命令名
+///   - `args`: 命令参数(不含命令名)
+///
+/// # Return:
+///   应答体;失败为 [`Ack`]。
+pub(super) async fn execute(ctx: &Ctx, name: &str, args: &[String]) -> Result<Reply, Ack> {
+    let client = &ctx.client;
+    let mut reply = Reply::default();
+    match name {
+        "ping" | "notcommands" => {}
+        "commands" => COMMANDS.iter().for_each(|c| reply.pair("command", c)),
+        "tagtypes" => {
+            // `tagtypes clear` / `enable` 等子命令照单全收:输出的标签本就只有这几个。
+            if args.is_empty() {
+                for tag in ["Artist", "Album", "Title", "Track"] {
+                    reply.pair("tagtype", tag);
+                }
+            }
+        }
+        "outputs" => {
+            reply.pair("outputid", 0);
+            reply.pair("outputname", "mineral");
+            reply.pair("plugin", "mineral");
+            reply.pair("outputenabled", 1);
+        }
+        "status" => status(client, &mut reply),
+        "currentsong" => current_song(client, &mut reply),
+        "getvol" => reply.pair("volume", client.audio_snapshot().volume_pct),
+        "play" | "playid" => play(client, args.first())?,
+        "pause" => pause(client, args.first())?,
+        "stop" => client.stop(),
+        "next" => client.next_song(),
+        "previous" => client.prev_or_restart(),
+        "seekcur" => seek_current(client, arg(args, 0)?)?,
+        "seek" | "seekid" => seek_at(client, arg(args, 0)?, arg(args, 1)?)?,
+        "setvol" => client.set_volume(parse_volume(arg(args, 0)?)?),
+        "random" | "repeat" | "single" | "consume" => {
+            set_option(client, name, parse_bool(arg(args, 0)?)?)?;
+        }
+        "playlistinfo" | "playlistid" => playlist_info(client, args.first(), &mut reply)?,
+        "plchanges" => plchanges(client, arg(args, 0)?, &mut reply)?,
+        "add" => {
+            add(client, arg(args, 0)?)?;
+        }
+        "addid" => {
+            if args.len() > 1 {
+                return Err(Ack::new(AckCode::Arg, "insert position is not supported"));
+            }
+            reply.pair("Id", add(client, arg(args, 0)?)?);
+        }
+        "delete" | "deleteid" => delete(client, arg(args, 0)?).await?,
+        "clear" => clear(client).await?,
+        "search" | "find" => {
+            for song in library::search(ctx, args, name == "find").await? {
+                song_entry(&mut reply, &song, None);
+            }
+        }
+        "searchadd" | "findadd" => {
+            for song in library::search(ctx, args, name == "findadd").await? {
+                client.queue_append(song, QueueContextWire::Manual);
+            }
+        }
+        "lsinfo" => library::lsinfo(ctx, args.first(), &mut reply).await?,
+        "list" => library::list(ctx, args, &mut reply).await?,
+        _ => {
+            return Err(Ack::new(
+                AckCode::Unknown,
+                format!("unknown command \"{name}\""),
+            ));
+        }
+    }
+    Ok(reply)
+}
+
+/// `status`:播放态 / 音量 / 模式开关 / 队列版本与长度 / 当前位置与进度。
+fn status(client: &ClientHandle, reply: &mut Reply) {
+    let snap = client.audio_snapshot();
+    let sync = client.player_sync(PlayerVersions::default());
+    let has_song = sync.current.is_some_and(|c| c.current_song.is_some());
+    let state = if snap.playing {
+        "play"
+    } else if has_song {
+        "pause"
+    } else {
+        "stop"
+    };
+    let (random, repeat, single) = mode_flags(sync.play_mode);
+    reply.pair("volume", snap.volume_pct);
+    reply.pair("repeat", u8::from(repeat));
+    reply.pair("random", u8::from(random));
+    reply.pair("single", u8::from(single));
+    reply.pair("consume", 0);
+    reply.pair("playlist", sync.versions.queue);
+    reply.pair("playlistlength", sync.queue.map_or(0, |q| q.queue.len()));
+    reply.pair("state", state);
+    if state == "stop" {
+        return;
+    }
+    if let Some(index) = sync.cursor.queue_index() {
+        reply.pair("song", index);
+        reply.pair("songid", index);
+    }
+    let elapsed = snap.position_ms;
+    match snap.duration_ms {
+        Some(total) => {
+            reply.pair(
+                "time",
+                format!("{}:{}", elapsed / 1000, total.div_ceil(1000)),
+            );
+            reply.pair("elapsed", Seconds(elapsed));
+            reply.pair("duration", Seconds(total));
+        }
+        None => reply.pair("elapsed", Seconds(elapsed)),
+    }
+}
+
+/// 播放模式 → MPD 的 (random, repeat, single) 三个开关。
+fn mode_flags(mode: PlayMode) -> (bool, bool, bool) {
+    let repeat = mode.repeat();
+    (mode.shuffle(), repeat != Repeat::Off, repeat == Repeat::One)
+}
+
+/// `random` / `repeat` / `single` / `consume` 开关落到四档模式上。
+///
+/// mineral 的单曲模式恒循环,`single 1` 即单曲循环;关掉时留在整列循环。
+///
+/// # Return:
+///   目标模式;`consume 0` 恒成立返回 `None`(无需改动)。
+///
+/// # Errors
+/// `consume 1`(mineral 没有「播完即删」)。
+fn apply_option(mode: PlayMode, option: &str, on: bool) -> Result<Option<PlayMode>, Ack> {
+    let repeat = mode.repeat();
+    let target = match (option, on) {
+        ("random", _) => mode.with_shuffle(on),
+        ("repeat", false) => mode.with_repeat(Repeat::Off),
+        ("repeat", true) if repeat == Repeat::Off => mode.with_repeat(Repeat::All),
+        ("single", true) => mode.with_repeat(Repeat::One),
+        ("single", false) if repeat == Repeat::One => mode.with_repeat(Repeat::All),
+        ("consume", true) => {
+            return Err(Ack::new(AckCode::Arg, "consume mode is not supported"));
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(target))
+}
+
+/// 落一个模式开关。
+///
+/// # Errors
+/// 见 [`apply_option`]。
+fn set_option(client: &ClientHandle, option: &str, on: bool) -> Result<(), Ack> {
+    let mode = client.player_sync(PlayerVersions::default()).play_mode;
+    if let Some(target) = apply_option(mode, option, on)?
+        && target != mode
+    {
+        client.set_play_mode(target);
+    }
+    Ok(())
+}
+
+/// `currentsong`:当前曲条目(未播时空应答)。
+fn current_song(client: &ClientHandle, reply: &mut Reply) {
+    let sync = client.player_sync(PlayerVersions::default());
+    if let Some(song) = sync.current.and_then(|c| c.current_song) {
+        song_entry(reply, &song, sync.cursor.queue_index());
+    }
+}
+
+/// 一首歌的条目:`file` + 标签 + 时长;队列内另带 `Pos` / `Id`。
+pub(super) fn song_entry(reply: &mut Reply, song: &Song, pos: Option<usize>) {
+    reply.pair("file", song_file(song));
+    reply.pair("Title", &song.name);
+    for artist in &song.artists {
+        reply.pair("Artist", &artist.name);
+    }
+    if let Some(album) = &song.album {
+        reply.pair("Album", &album.name);
+    }
+    if let Some(track) = song.track_no {
+        reply.pair("Track", track);
+    }
+    if let Some(ms) = song.duration_ms {
+        reply.pair("Time", ms.div_ceil(1000));
+        reply.pair("duration", Seconds(ms));
+    }
+    if let Some(pos) = pos {
+        reply.pair("Pos", pos);
+        reply.pair("Id", pos);
+    }
+}
+
+/// 歌的 `file`:本地文件给路径,其余给限定 id;两者都能原样 `add` 回来。
+pub(super) fn song_file(song: &Song) -> String {
+    match &song.source_url {
+        Some(MediaUrl::Local(path)) if song.source() == SourceKind::LOCAL => {
+            path.display().to_string()
+        }
+        _ => song.id.qualified(),
+    }
+}
+
+/// 当前队列快照。
+fn queue(client: &ClientHandle) -> Vec<Song> {
+    client
+        .player_sync(PlayerVersions::default())
+        .queue
+        .map(|q| q.queue)
+        .unwrap_or_default()
+}
+
+/// `play [POS]` / `playid [ID]`:有下标播那一首,否则开始 / 恢复。
+///
+/// # Errors
+/// 下标越界 / 无可播。
+fn play(client: &ClientHandle, pos: Option<&String>) -> Result<(), Ack> {
+    // 部分客户端以 `-1` 表示「当前」。
+    match pos.map(String::as_str).filter(|p| *p != "-1") {
+        Some(raw) => {
+            let index = parse_index(raw)?;
+            let song = queue(client)
+                .into_iter()
+                .nth(index)
+                .ok_or_else(|| Ack::new(AckCode::Arg, "Bad song index"))?;
+            client.play_song(song);
+        }
+        None => {
+            if !client.play_or_resume() {
+                return Err(Ack::new(AckCode::NoExist, "queue is empty"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `pause [0|1]`:无参数切换。
+///
+/// # Errors
+/// 参数不是 0 / 1。
+fn pause(client: &ClientHandle, state: Option<&String>) -> Result<(), Ack> {
+    let pause = match state {
+        Some(raw) => parse_bool(raw)?,
+        None => client.audio_snapshot().playing,
+    };
+    if pause {
+        client.pause();
+    } else if !client.play_or_resume() {
+        return Err(Ack::new(AckCode::NoExist, "queue is empty"));
+    }
+    Ok(())
+}
+
+/// `seekcur TIME`:绝对秒数,或 `+` / `-` 前缀的相对秒数。
+///
+/// # Errors
+/// 时间格式不对 / 没有在播曲。
+fn seek_current(client: &ClientHandle, raw: &str) -> Result<(), Ack> {
+    let snap = client.audio_snapshot();
+    if snap.duration_ms.is_none() {
+        return Err(Ack::new(AckCode::NoExist, "Not playing"));
+    }
+    let target = if let Some(delta) = raw.strip_prefix('+') {
+        snap.position_ms.saturating_add(parse_seconds(delta)?)
+    } else if let Some(delta) = raw.strip_prefix('-') {
+        snap.position_ms.saturating_sub(parse_seconds(delta)?)
+    } else {
+        parse_seconds(raw)?
+    };
+    client.seek(target);
+    Ok(())
+}
+
+/// `seek POS TIME` / `seekid ID TIME`:只支持当前曲(跳到别的曲再定位会与取流竞态)。
+///
+/// # Errors
+/// 下标不是当前曲 / 时间格式不对。
+fn seek_at(client: &ClientHandle, pos: &str, time: &str) -> Result<(), Ack> {
+    let index = parse_index(pos)?;
+    let current = client
+        .player_sync(PlayerVersions::default())
+        .cursor
+        .queue_index();
+    if current != Some(index) {
+        return Err(Ack::new(
+            AckCode::Arg,
+            "can only seek within the current song",
+        ));
+    }
+    seek_current(client, time)
+}
+
+/// `playlistinfo [POS|START:END]` / `playlistid [ID]`。
+///
+/// # Errors
+/// 下标 / 区间格式不对或越界。
+fn playlist_info(
+    client: &ClientHandle,
+    range: Option<&String>,
+    reply: &mut Reply,
+) -> Result<(), Ack> {
+    let songs = queue(client);
+    let range = match range {
+        Some(raw) => parse_range(raw, songs.len())?,
+        None => 0..songs.len(),
+    };
+    for (pos, song) in songs.iter().enumerate().skip(range.start).take(range.len()) {
+        song_entry(reply, song, Some(pos));
+    }
+    Ok(())
+}
+
+/// `plchanges VERSION`:版本未变回空,否则回整个队列(不做逐条 diff)。
+///
+/// # Errors
+/// 版本号不是数字。
+fn plchanges(client: &ClientHandle, version: &str, reply: &mut Reply) -> Result<(), Ack> {
+    let known: u64 = version
+        .parse()
+        .map_err(|e| Ack::new(AckCode::Arg, format!("need an integer version: {e}")))?;
+    let sync = client.player_sync(PlayerVersions::default());
+    if known == sync.versions.queue {
+        return Ok(());
+    }
+    for (pos, song) in sync
+        .queue
+        .map(|q| q.queue)
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        song_entry(reply, song, Some(pos));
+    }
+    Ok(())
+}
+
+/// `add URI`:追加到队尾(URI 认限定 id / 分享链接 / 本机文件)。
+///
+/// # Return:
+///   新条目的下标(即 `Id`)。
+///
+/// # Errors
+/// URI 认不出。
+fn add(client: &ClientHandle, uri: &str) -> Result<usize, Ack> {
+    let song = song_from_uri(uri, &client.channel_caps())
+        .map_err(|e| Ack::new(AckCode::NoExist, mineral_log::chain(&e)))?;
+    let index = queue(client).len();
+    client.queue_append(song, QueueContextWire::Manual);
+    Ok(index)
+}
+
+/// `delete POS` / `deleteid ID`。
+///
+/// # Errors
+/// 下标越界 / 队列并发变更。
+async fn delete(client: &ClientHandle, pos: &str) -> Result<(), Ack> {
+    let index = parse_index(pos)?;
+    let song = queue(client)
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| Ack::new(AckCode::Arg, "Bad song index"))?;
+    edit(client, QueueOp::Remove(QueueAnchor::new(index, song.id))).await
+}
+
+/// `clear`:停止并清空整个队列。
+///
+/// # Errors
+/// 队列并发变更。
+async fn clear(client: &ClientHandle) -> Result<(), Ack> {
+    client.stop();
+    let songs = queue(client);
+    // 「清掉首条之下」再删首条:两步编辑清空,不随队列长度增长。
+    if let Some(first) = songs.first() {
+        let at = QueueAnchor::new(0, first.id.clone());
+        edit(client, QueueOp::ClearBelow(at.clone())).await?;
+        edit(client, QueueOp::Remove(at)).await?;
+    }
+    Ok(())
+}
+
+/// 发一次队列编辑;锚点对不上(期间被别的 client 改过)报错。
+///
+/// # Errors
+/// 队列并发变更。
+async fn edit(client: &ClientHandle, op: QueueOp) -> Result<(), Ack> {
+    match client.queue_edit_async(op).await {
+        QueueEditOutcome::Stale => Err(Ack::new(
+            AckCode::System,
+            "queue changed concurrently, retry",
+        )),
+        QueueEditOutcome::Applied | QueueEditOutcome::NoOp => Ok(()),
+    }
+}
+
+/// 第 `i` 个参数。
+///
+/// # Errors
+/// 参数缺失。
+fn arg(args: &[String], i: usize) -> Result<&str, Ack> {
+    args.get(i)
+        .map(String::as_str)
+        .ok_or_else(|| Ack::new(AckCode::Arg, "missing argument"))
+}
+
+/// 非负下标。
+///
+/// # Errors
+/// 不是非负整数。
+fn parse_index(raw: &str) -> Result<usize, Ack> {
+    raw.parse()
+        .map_err(|e| Ack::new(AckCode::Arg, format!("need a positive integer: {e}")))
+}
+
+/// `0` / `1` 开关。
+///
+/// # Errors
+/// 不是 0 / 1。
+fn parse_bool(raw: &str) -> Result<bool, Ack> {
+    match raw {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(Ack::new(
+            AckCode::Arg,
+            format!("need 0 or 1, got \"{raw}\""),
+        )),
+    }
+}
+
+/// 音量 0..=100。
+///
+/// # Errors
+/// 不是整数或超出范围。
+fn parse_volume(raw: &str) -> Result<u8, Ack> {
+    raw.parse::<u8>()
+        .ok()
+        .filter(|v| *v <= 100)
+        .ok_or_else(|| Ack::new(AckCode::Arg, "Invalid volume value"))
+}
+
+/// 秒数(可带小数,精度到毫秒)→ 毫秒;不走浮点。
+///
+/// # Errors
+/// 不是非负十进制数。
+fn parse_seconds(raw: &str) -> Result<u64, Ack> {
+    let bad = || {
+        Ack::new(
+            AckCode::Arg,
+            format!("need a number of seconds, got \"{raw}\""),
+        )
+    };
+    let (whole, frac) = raw.split_once('.').unwrap_or((raw, ""));
+    if whole.is_empty() && frac.is_empty() {
+        return Err(bad());
+    }
+    if !whole
+        .bytes()
+        .chain(frac.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(bad());
+    }
+    let secs: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|e| {
+            Ack::new(
+                AckCode::Arg,
+                format!("need a number of seconds, got \"{raw}\": {e}"),
+            )
+        })?
+    };
+    // 小数位补齐 / 截断到 3 位即毫秒。
+    let millis = frac
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(3)
+        .fold(0_u64, |acc, b| acc * 10 + u64::from(b - b'0'));
+    Ok(secs.saturating_mul(1000).saturating_add(millis))
+}
+
+/// `POS` 或 `START:END`(`END` 可缺省 = 到队尾)→ 下标区间。
+///
+/// # Errors
+/// 格式不对或越界。
+fn parse_range(raw: &str, len: usize) -> Result<std::ops::Range<usize>, Ack> {
+    let range = match raw.split_once(':') {
+        Some((start, "")) => parse_index(start)?..len,
+        Some((start, end)) => parse_index(start)?..parse_index(end)?.min(len),
+        None => {
+            let pos = parse_index(raw)?;
+            pos..pos.saturating_add(1)
+        }
+    };
+    if range.start >= len || range.start > range.end {
+        return Err(Ack::new(AckCode::Arg, "Bad song index"));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{AlbumId, AlbumRef, ArtistId, ArtistRef, Song, SongId, SourceKind};
+    use mineral_protocol::PlayMode;
+
+    use super::{
+        Ack, AckCode, Reply, apply_option, mode_flags, parse_range, parse_seconds, song_entry,
+    };
+
+    /// 秒数解析:整数 / 小数补齐 / 截断到毫秒;负号与杂字符不认。
+    #[test]
+    fn seconds_parse_to_millis() {
+        assert_eq!(parse_seconds("12").ok(), Some(12_000));
+        assert_eq!(parse_seconds("12.5").ok(), Some(12_500));
+        assert_eq!(parse_seconds("0.0429").ok(), Some(42));
+        assert_eq!(parse_seconds(".25").ok(), Some(250));
+        assert!(parse_seconds("").is_err());
+        assert!(parse_seconds(".").is_err());
+        assert!(parse_seconds("-1").is_err());
+        assert!(parse_seconds("1e3").is_err());
+    }
+
+    /// 区间:单下标、开尾、终点截到队尾;起点越界报错。
+    #[test]
+    fn ranges_clamp_to_queue() {
+        assert_eq!(parse_range("2", 5).ok(), Some(2..3));
+        assert_eq!(parse_range("1:", 5).ok(), Some(1..5));
+        assert_eq!(parse_range("1:99", 5).ok(), Some(1..5));
+        assert!(parse_range("5", 5).is_err());
+        assert!(parse_range("3:1", 5).is_err());
+    }
+
+    /// 三个开关与四档模式互转。
+    #[test]
+    fn options_map_onto_play_modes() -> Result<(), Ack> {
+        assert_eq!(mode_flags(PlayMode::Sequential), (false, false, false));
+        assert_eq!(mode_flags(PlayMode::Shuffle), (true, true, false));
+        assert_eq!(mode_flags(PlayMode::RepeatOne), (false, true, true));
+
+        let seq = PlayMode::Sequential;
+        assert_eq!(apply_option(seq, "random", true)?, Some(PlayMode::Shuffle));
+        assert_eq!(
+            apply_option(seq, "repeat", true)?,
+            Some(PlayMode::RepeatAll)
+        );
+        assert_eq!(
+            apply_option(seq, "single", true)?,
+            Some(PlayMode::RepeatOne)
+        );
+        assert_eq!(
+            apply_option(PlayMode::RepeatOne, "repeat", true)?,
+            None,
+            "已在单曲循环,repeat 1 不降级"
+        );
+        assert_eq!(
+            apply_option(PlayMode::RepeatOne, "single", false)?,
+            Some(PlayMode::RepeatAll)
+        );
+        assert_eq!(apply_option(seq, "consume", false)?, None);
+        assert!(apply_option(seq, "consume", true).is_err());
+        Ok(())
+    }
+
+    /// 条目:限定 id 作 file,多艺人逐行,时长秒向上取整,队列内带 Pos / Id。
+    #[test]
+    fn song_entry_lines() {
+        let song = Song::builder()
+            .id(SongId::new(SourceKind::NETEASE, "186016"))
+            .name("晴天".to_owned())
+            .artists(vec![
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "6452"),
+                    name: "周杰伦".to_owned(),
+                },
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "1"),
+                    name: "客串\n歌手".to_owned(),
+                },
+            ])
+            .album(Some(AlbumRef {
+                id: AlbumId::new(SourceKind::NETEASE, "18905"),
+                name: "叶惠美".to_owned(),
+            }))
+            .duration_ms(Some(269_500))
+            .build();
+        let mut reply = Reply::default();
+        song_entry(&mut reply, &song, Some(3));
+        assert_eq!(
+            reply.into_body(),
+            "file: netease:186016\nTitle: 晴天\nArtist: 周杰伦\nArtist: 客串 歌手\n\
+             Album: 叶惠美\nTime: 270\nduration: 269.500\nPos: 3\nId: 3\n"
+        );
+    }
+
+    /// ACK 行格式。
+    #[test]
+    fn ack_renders_protocol_line() {
+        let ack = Ack::new(AckCode::Unknown, "unknown command \"foo\"");
+        assert_eq!(
+            ack.render(2, "foo"),
+            "ACK [5@2] {foo} unknown command \"foo\""
+        );
+    }
+}
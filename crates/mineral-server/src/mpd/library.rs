@@ -0,0 +1,550 @@
+//! 曲库类命令:`search` / `find` 与 `lsinfo` / `list`。
+//!
+//! 本地曲库(local 源)全量在手,按过滤条件逐首比对;远端源只能关键词搜歌,取过滤值拼成
+//! 关键词搜回前 [`SEARCH_LIMIT`] 条,再按同样的条件过一遍。`lsinfo` / `list` 只看本地
+//! 曲库:目录即文件所在目录(绝对路径,与 `file` 同一写法)。
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use futures_util::future::join_all;
+use mineral_channel_core::Page;
+use mineral_model::{MediaUrl, SearchKind, Song, SourceKind};
+use rustc_hash::FxHashSet;
+
+use super::command::{Ack, AckCode, Ctx, Reply, song_entry, song_file};
+use super::parse::filter_clauses;
+use crate::client::Client;
+use crate::fetch::{self, FetchFailure};
+
+/// 每个远端源一次搜索取回的条数(MPD 没有分页概念,客户端拿到即全部)。
+const SEARCH_LIMIT: u32 = 30;
+
+/// 认得的标签(小写);`any` 为标题 / 艺人 / 专辑合在一起。
+const TAGS: &[&str] = &[
+    "title",
+    "artist",
+    "albumartist",
+    "album",
+    "track",
+    "file",
+    "any",
+];
+
+/// 过滤运算。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    /// 整值相等(`find` 的旧式写法 / `==`)。
+    Eq,
+
+    /// 整值不等(`!=`)。
+    NotEq,
+
+    /// 子串(`search` 的旧式写法 / `contains`)。
+    Contains,
+
+    /// 前缀(`starts_with`)。
+    StartsWith,
+}
+
+/// 一条过滤条件;多条之间是「且」。
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Filter {
+    /// 标签名(小写)。
+    tag: String,
+
+    /// 运算。
+    op: Op,
+
+    /// 比对值。
+    value: String,
+}
+
+impl Filter {
+    /// 这首歌是否满足条件。标签取不到值(如没有专辑)时,只有 `!=` 成立。
+    ///
+    /// # Params:
+    ///   - `song`: 待比对的歌
+    ///   - `fold_case`: 忽略大小写(`search` 为真,`find` 为假)
+    fn matches(&self, song: &Song, fold_case: bool) -> bool {
+        let norm = |s: &str| {
+            if fold_case {
+                s.to_lowercase()
+            } else {
+                s.to_owned()
+            }
+        };
+        let want = norm(&self.value);
+        let mut values = tag_values(song, &self.tag).unwrap_or_default().into_iter();
+        let hit = |v: String| {
+            let v = norm(&v);
+            match self.op {
+                Op::Eq | Op::NotEq => v == want,
+                Op::Contains => v.contains(&want),
+                Op::StartsWith => v.starts_with(&want),
+            }
+        };
+        if self.op == Op::NotEq {
+            !values.any(hit)
+        } else {
+            values.any(hit)
+        }
+    }
+}
+
+/// 一首歌在某标签下的全部值(多艺人逐个给)。
+///
+/// # Params:
+///   - `song`: 歌
+///   - `tag`: 小写标签名;`any` 为标题 / 艺人 / 专辑合在一起
+///
+/// # Return:
+///   标签值;不认识的标签为 `None`。
+fn tag_values(song: &Song, tag: &str) -> Option<Vec<String>> {
+    let artists = || song.artists.iter().map(|a| a.name.clone());
+    let album = || song.album.iter().map(|a| a.name.clone());
+    let values = match tag {
+        "title" => vec![song.name.clone()],
+        "artist" | "albumartist" => artists().collect(),
+        "album" => album().collect(),
+        "track" => song.track_no.iter().map(ToString::to_string).collect(),
+        "file" => vec![song_file(song)],
+        "any" => std::iter::once(song.name.clone())
+            .chain(artists())
+            .chain(album())
+            .collect(),
+        _ => return None,
+    };
+    Some(values)
+}
+
+/// `list` 应答里标签的规范写法。
+fn tag_key(tag: &str) -> Option<&'static str> {
+    Some(match tag {
+        "title" => "Title",
+        "artist" => "Artist",
+        "albumartist" => "AlbumArtist",
+        "album" => "Album",
+        "track" => "Track",
+        "file" => "file",
+        _ => return None,
+    })
+}
+
+/// 搜索参数 → 过滤条件:过滤表达式按子句的运算符,旧式 `TAG VALUE` 对按命令(`find`
+/// 整值相等,`search` 子串);`sort` / `window` / `position` 这类修饰对跳过。
+///
+/// # Params:
+///   - `args`: 命令参数
+///   - `exact`: 旧式写法是否整值相等
+///
+/// # Errors
+/// 标签不认识 / 运算符不支持(正则)。
+fn parse_filters(args: &[String], exact: bool) -> Result<Vec<Filter>, Ack> {
+    let mut filters = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg.starts_with('(') {
+            for clause in filter_clauses(arg) {
+                let op = match clause.op.as_str() {
+                    "==" => Op::Eq,
+                    "!=" => Op::NotEq,
+                    "contains" => Op::Contains,
+                    "starts_with" => Op::StartsWith,
+                    other => {
+                        return Err(Ack::new(
+                            AckCode::Arg,
+                            format!("unsupported filter operator \"{other}\""),
+                        ));
+                    }
+                };
+                filters.push(filter(&clause.tag, op, clause.value)?);
+            }
+            continue;
+        }
+        let value = rest.next();
+        if matches!(
+            arg.to_ascii_lowercase().as_str(),
+            "sort" | "window" | "position" | "group"
+        ) {
+            continue;
+        }
+        let Some(value) = value else {
+            return Err(Ack::new(AckCode::Arg, "incorrect number of arguments"));
+        };
+        let op = if exact { Op::Eq } else { Op::Contains };
+        filters.push(filter(arg, op, value.clone())?);
+    }
+    Ok(filters)
+}
+
+/// 构造一条过滤条件并校验标签。
+///
+/// # Errors
+/// 标签不认识。
+fn filter(tag: &str, op: Op, value: String) -> Result<Filter, Ack> {
+    let tag = tag.to_ascii_lowercase();
+    if !TAGS.contains(&tag.as_str()) {
+        return Err(Ack::new(AckCode::Arg, format!("Unknown tag type: {tag}")));
+    }
+    Ok(Filter { tag, op, value })
+}
+
+/// 远端源的搜索关键词:肯定条件里除 `file` 外的值(路径对远端没意义)。
+fn keywords(filters: &[Filter]) -> String {
+    filters
+        .iter()
+        .filter(|f| f.op != Op::NotEq && f.tag != "file")
+        .map(|f| f.value.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// 全部条件都满足。
+fn keep(filters: &[Filter], song: &Song, fold_case: bool) -> bool {
+    filters.iter().all(|f| f.matches(song, fold_case))
+}
+
+/// `search` / `find`(及其 `…add`):本地曲库逐首比对 + 各远端能搜歌的源关键词搜索后同样
+/// 过滤;本地在前、各源按注册序,同一首歌只出一次。
+///
+/// # Params:
+///   - `ctx`: 执行上下文
+///   - `args`: 命令参数
+///   - `exact`: `find` 语义(旧式写法整值相等、区分大小写)
+///
+/// # Return:
+///   命中的歌。
+///
+/// # Errors
+/// 没有过滤条件 / 条件不合法 / 没有可搜的地方;全部来源都失败且一首没命中时报首个失败。
+pub(super) async fn search(ctx: &Ctx, args: &[String], exact: bool) -> Result<Vec<Song>, Ack> {
+    let filters = parse_filters(args, exact)?;
+    if filters.is_empty() {
+        return Err(Ack::new(AckCode::Arg, "nothing to search for"));
+    }
+    let fold_case = !exact;
+    let caps = ctx.client.channel_caps();
+    let has_local = caps.iter().any(|(s, _)| *s == SourceKind::LOCAL);
+    let query = keywords(&filters);
+    let remote = caps
+        .iter()
+        .filter(|(s, c)| *s != SourceKind::LOCAL && c.searchable().contains(&SearchKind::Song))
+        .map(|(s, _)| *s)
+        .filter(|_| !query.trim().is_empty())
+        .collect::<Vec<SourceKind>>();
+    if !has_local && remote.is_empty() {
+        return Err(Ack::new(AckCode::System, "no source can search songs"));
+    }
+
+    let mut failure = None;
+    let mut found = Vec::new();
+    match ctx.client.local_songs_async().await {
+        Ok(songs) => found.extend(songs),
+        Err(e) => {
+            failure = Some(Ack::new(AckCode::System, mineral_log::chain(&e)));
+        }
+    }
+    let results = join_all(remote.iter().map(|source| {
+        fetch::search_songs(
+            &ctx.client,
+            &ctx.events,
+            *source,
+            query.clone(),
+            Page::new(0, SEARCH_LIMIT),
+        )
+    }))
+    .await;
+    for result in results {
+        match result {
+            Ok(songs) => found.extend(songs),
+            Err(e) => {
+                failure.get_or_insert_with(|| fetch_ack(e));
+            }
+        }
+    }
+
+    let mut seen = FxHashSet::default();
+    let hits = found
+        .into_iter()
+        .filter(|song| keep(&filters, song, fold_case))
+        .filter(|song| seen.insert(song.id.clone()))
+        .collect::<Vec<Song>>();
+    match failure {
+        Some(ack) if hits.is_empty() => Err(ack),
+        _ => Ok(hits),
+    }
+}
+
+/// 远端搜索失败 → 应答。
+fn fetch_ack(failure: FetchFailure) -> Ack {
+    match failure {
+        FetchFailure::TimedOut => Ack::new(
+            AckCode::System,
+            "search timed out (source failed or not logged in?)",
+        ),
+        FetchFailure::ShuttingDown => Ack::new(AckCode::System, "daemon is shutting down"),
+    }
+}
+
+/// 本地曲库全部歌曲。
+///
+/// # Errors
+/// 本地曲库扫描失败。
+async fn local_songs(ctx: &Ctx) -> Result<Vec<Song>, Ack> {
+    ctx.client
+        .local_songs_async()
+        .await
+        .map_err(|e| Ack::new(AckCode::System, mineral_log::chain(&e)))
+}
+
+/// 本地歌的文件路径;非本地文件为 `None`。
+fn local_path(song: &Song) -> Option<&Path> {
+    match &song.source_url {
+        Some(MediaUrl::Local(path)) => Some(path.as_path()),
+        _ => None,
+    }
+}
+
+/// 全部歌曲所在目录的最深公共祖先(`lsinfo` 不带参数时列出的那一层)。
+fn library_root<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Option<PathBuf> {
+    paths
+        .into_iter()
+        .filter_map(Path::parent)
+        .fold(None, |root: Option<PathBuf>, dir| match root {
+            None => Some(dir.to_path_buf()),
+            Some(root) => Some(
+                root.ancestors()
+                    .find(|a| dir.starts_with(a))
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+            ),
+        })
+}
+
+/// 某目录下一层的内容:直接子目录(有歌的)与直接放在这里的歌。
+///
+/// # Return:
+///   `(子目录, 歌)`,各按路径序。
+fn dir_entries<'a>(songs: &'a [Song], dir: &Path) -> (BTreeSet<PathBuf>, Vec<&'a Song>) {
+    let mut dirs = BTreeSet::new();
+    let mut files = Vec::new();
+    for song in songs {
+        let Some(rel) = local_path(song).and_then(|p| p.strip_prefix(dir).ok()) else {
+            continue;
+        };
+        let mut comps = rel.components();
+        match (comps.next(), comps.next()) {
+            (Some(first), Some(_)) => {
+                dirs.insert(dir.join(first));
+            }
+            (Some(_), None) => files.push(song),
+            _ => {}
+        }
+    }
+    (dirs, files)
+}
+
+/// `lsinfo [URI]`:不带参数(或 `/`)列本地曲库最顶层;目录列其下一层;歌曲文件给该曲条目。
+///
+/// # Errors
+/// 曲库读取失败 / 路径不在曲库里。
+pub(super) async fn lsinfo(ctx: &Ctx, uri: Option<&String>, reply: &mut Reply) -> Result<(), Ack> {
+    let songs = local_songs(ctx).await?;
+    let uri = uri
+        .map(|u| u.trim_end_matches('/'))
+        .filter(|u| !u.is_empty());
+    let dir = match uri {
+        Some(uri) => {
+            let path = Path::new(uri);
+            if let Some(song) = songs.iter().find(|s| local_path(s) == Some(path)) {
+                song_entry(reply, song, None);
+                return Ok(());
+            }
+            path.to_path_buf()
+        }
+        None => match library_root(songs.iter().filter_map(local_path)) {
+            Some(root) => root,
+            None => return Ok(()),
+        },
+    };
+    let (dirs, files) = dir_entries(&songs, &dir);
+    if uri.is_some() && dirs.is_empty() && files.is_empty() {
+        return Err(Ack::new(AckCode::NoExist, "No such directory"));
+    }
+    for sub in dirs {
+        reply.pair("directory", sub.display());
+    }
+    for song in files {
+        song_entry(reply, song, None);
+    }
+    Ok(())
+}
+
+/// `list TAG [FILTER…] [group TAG]`:本地曲库里某标签的全部取值(去重排序),可带
+/// `find` 语义的过滤;旧式 `list album ARTIST` 视作按艺人过滤。`group` 忽略。
+///
+/// # Errors
+/// 标签不支持 / 过滤条件不合法 / 曲库读取失败。
+pub(super) async fn list(ctx: &Ctx, args: &[String], reply: &mut Reply) -> Result<(), Ack> {
+    let (tag, rest) = args
+        .split_first()
+        .ok_or_else(|| Ack::new(AckCode::Arg, "missing argument"))?;
+    let tag = tag.to_ascii_lowercase();
+    let key =
+        tag_key(&tag).ok_or_else(|| Ack::new(AckCode::Arg, format!("Unknown tag type: {tag}")))?;
+    let filters = match rest {
+        [artist] if tag == "album" && !artist.starts_with('(') => {
+            vec![filter("artist", Op::Eq, artist.clone())?]
+        }
+        _ => parse_filters(rest, /*exact*/ true)?,
+    };
+    let values = local_songs(ctx)
+        .await?
+        .iter()
+        .filter(|song| keep(&filters, song, /*fold_case*/ false))
+        .flat_map(|song| tag_values(song, &tag).unwrap_or_default())
+        .collect::<BTreeSet<String>>();
+    for value in values {
+        reply.pair(key, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use mineral_model::{
+        AlbumId, AlbumRef, ArtistId, ArtistRef, MediaUrl, Song, SongId, SourceKind,
+    };
+
+    use super::{Filter, Op, dir_entries, keep, keywords, library_root, parse_filters};
+
+    /// 本地歌:路径即 id,带艺人 / 专辑。
+    fn local(path: &str, title: &str, artist: &str, album: &str) -> Song {
+        Song::builder()
+            .id(SongId::new(SourceKind::LOCAL, path))
+            .name(title.to_owned())
+            .artists(vec![ArtistRef {
+                id: ArtistId::new(SourceKind::LOCAL, artist),
+                name: artist.to_owned(),
+            }])
+            .album(Some(AlbumRef {
+                id: AlbumId::new(SourceKind::LOCAL, album),
+                name: album.to_owned(),
+            }))
+            .source_url(Some(MediaUrl::local(Path::new(path))))
+            .build()
+    }
+
+    /// 参数表。
+    fn args(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| (*s).to_owned()).collect()
+    }
+
+    /// 旧式对按命令定运算,表达式按子句运算符;修饰对跳过;不认识的标签 / 正则报错。
+    #[test]
+    fn filters_from_both_syntaxes() -> Result<(), super::Ack> {
+        let find = parse_filters(&args(&["Artist", "周杰伦", "window", "0:10"]), true)?;
+        assert_eq!(
+            find,
+            vec![Filter {
+                tag: "artist".to_owned(),
+                op: Op::Eq,
+                value: "周杰伦".to_owned(),
+            }]
+        );
+        let search = parse_filters(&args(&["title", "晴"]), false)?;
+        assert_eq!(search.first().map(|f| f.op), Some(Op::Contains));
+        let expr = parse_filters(
+            &args(&[
+                "((any contains '晴天') AND (album != \"x\"))",
+                "sort",
+                "Title",
+            ]),
+            true,
+        )?;
+        assert_eq!(
+            expr.iter().map(|f| f.op).collect::<Vec<_>>(),
+            vec![Op::Contains, Op::NotEq]
+        );
+        assert_eq!(keywords(&expr), "晴天");
+        assert!(parse_filters(&args(&["genre", "rock"]), true).is_err());
+        assert!(parse_filters(&args(&["(title =~ \"a.*\")"]), true).is_err());
+        assert!(parse_filters(&args(&["artist"]), true).is_err());
+        Ok(())
+    }
+
+    /// find 整值且区分大小写;search 子串且忽略大小写;`!=` 对缺标签的歌成立。
+    #[test]
+    fn find_is_exact_search_is_fuzzy() -> Result<(), super::Ack> {
+        let song = local("/m/a/1.flac", "晴天", "周杰伦", "叶惠美");
+        let find = parse_filters(&args(&["title", "晴天"]), true)?;
+        assert!(keep(&find, &song, false));
+        let partial = parse_filters(&args(&["title", "晴"]), true)?;
+        assert!(!keep(&partial, &song, false), "find 不做子串");
+        let search = parse_filters(&args(&["title", "晴"]), false)?;
+        assert!(keep(&search, &song, true));
+
+        let latin = local("/m/b/2.flac", "Palisade", "Shore", "Tide");
+        assert!(!keep(
+            &parse_filters(&args(&["artist", "shore"]), true)?,
+            &latin,
+            false
+        ));
+        assert!(keep(
+            &parse_filters(&args(&["artist", "shore"]), false)?,
+            &latin,
+            true
+        ));
+        assert!(keep(
+            &parse_filters(&args(&["file", "/m/b/2.flac"]), true)?,
+            &latin,
+            false
+        ));
+
+        let bare = Song::builder()
+            .id(SongId::new(SourceKind::NETEASE, "1"))
+            .name("x".to_owned())
+            .build();
+        assert!(keep(
+            &parse_filters(&args(&["(album != \"Tide\")"]), true)?,
+            &bare,
+            false
+        ));
+        assert!(!keep(
+            &parse_filters(&args(&["album", "Tide"]), true)?,
+            &bare,
+            false
+        ));
+        Ok(())
+    }
+
+    /// 顶层取公共祖先目录;每层列直接子目录与直接放着的歌。
+    #[test]
+    fn directories_follow_file_paths() {
+        let songs = vec![
+            local("/m/a/1.flac", "1", "x", "y"),
+            local("/m/a/cd2/2.flac", "2", "x", "y"),
+            local("/m/b/3.flac", "3", "x", "y"),
+            local("/m/4.flac", "4", "x", "y"),
+        ];
+        let paths = songs.iter().filter_map(super::local_path);
+        assert_eq!(library_root(paths), Some(PathBuf::from("/m")));
+
+        let (dirs, files) = dir_entries(&songs, Path::new("/m"));
+        assert_eq!(
+            dirs.into_iter().collect::<Vec<_>>(),
+            vec![PathBuf::from("/m/a"), PathBuf::from("/m/b")]
+        );
+        assert_eq!(
+            files.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["4"]
+        );
+        let (dirs, files) = dir_entries(&songs, Path::new("/m/a"));
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(files.len(), 1);
+        assert!(library_root(std::iter::empty()).is_none());
+    }
+}
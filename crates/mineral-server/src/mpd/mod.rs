@@ -0,0 +1,58 @@
+//! MPD 协议兼容入口(配置 `daemon.mpd`):ncmpcpp / mpc / 状态栏模块 / 手机 MPD 遥控
+//! 经它驱动与 unix socket 同一个 daemon。
+//!
+//! 只实现遥控用得到的子集:传输控制、音量与模式开关、`status` / `currentsong`、队列
+//! 查看与增删、搜索(本地曲库 + 各远端源)、本地曲库浏览(`lsinfo` / `list`)、`idle`
+//! 推送、命令列表与密码;存储歌单与 `update` 不支持。
+//!
+//! - 歌曲的 `file` 是限定 id(`netease:186016`)或本地文件路径,两者都能原样 `add`
+//!   回来;`add` 另认各源分享链接(见 [`crate::uri`]);
+//! - 队列条目的 `Id` 即下标,不跨编辑稳定;
+//! - 未配密码时只许绑回环地址,由 [`crate::check_exposure`] 在 bind 前守门。
+
+mod command;
+mod library;
+mod parse;
+mod session;
+
+use std::sync::Arc;
+
+use color_eyre::eyre::WrapErr;
+use mineral_protocol::Event;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+use crate::client::ClientHandle;
+
+/// MPD accept loop:每条连接起一个 task,跑到 listener 出错为止。
+///
+/// # Params:
+///   - `listener`: 已 bind 的 TCP listener(调用方先过 [`crate::check_exposure`])
+///   - `client`: 指令面
+///   - `events`: Event 推送 hub
+///   - `password`: 连接密码(空串视同未配)
+///
+/// # Errors
+/// accept 失败。
+pub(crate) async fn run(
+    listener: TcpListener,
+    client: ClientHandle,
+    events: broadcast::Sender<Event>,
+    password: Option<String>,
+) -> color_eyre::Result<()> {
+    let password: Option<Arc<str>> = password.filter(|p| !p.is_empty()).map(Arc::from);
+    loop {
+        let (stream, peer) = listener.accept().await.wrap_err("mpd accept")?;
+        let ctx = command::Ctx {
+            client: client.clone(),
+            events: events.clone(),
+        };
+        let password = password.clone();
+        tokio::spawn(async move {
+            mineral_log::debug!(target: "mpd", %peer, "client connected");
+            if let Err(e) = session::run(stream, ctx, password).await {
+                mineral_log::debug!(target: "mpd", %peer, error = mineral_log::chain(&e), "connection ended with error");
+            }
+        });
+    }
+}
@@ -0,0 +1,150 @@
+//! MPD 命令行的分词:空白分隔,双引号包裹的参数内 `\"` / `\\` 转义;以及 `search` /
+//! `find` / `list` 的过滤表达式(`(artist == "x")`)拆成 `标签 运算符 值` 子句。
+
+/// 把一行命令拆成参数(首个即命令名)。
+///
+/// # Params:
+///   - `line`: 一行命令(不含换行)
+///
+/// # Return:
+///   参数表;引号没闭合时为错误说明。
+pub(super) fn split_args(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            return Ok(args);
+        };
+        if first == '"' {
+            chars.next();
+            args.push(quoted(&mut chars, '"').ok_or("Missing closing '\"'")?);
+        } else {
+            let mut arg = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                arg.push(c);
+            }
+            args.push(arg);
+        }
+    }
+}
+
+/// 过滤表达式里的一个子句(`(TAG OP "VALUE")`)。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Clause {
+    /// 标签名(原样,大小写由调用方归一)。
+    pub(super) tag: String,
+
+    /// 运算符(`==` / `!=` / `contains` 等);括号里只有标签与值时为 `==`。
+    pub(super) op: String,
+
+    /// 被引用的值(已解转义)。
+    pub(super) value: String,
+}
+
+/// 把过滤表达式拆成子句:每个被引用的值(单 / 双引号均可,同样的转义规则)与它所在括号里
+/// 前面的标签名、运算符配成一条。`AND` 只是子句的并列;`!` 取反不支持,按原子句处理。
+///
+/// # Params:
+///   - `expr`: 过滤表达式,如 `((artist == "周杰伦") AND (title contains '晴'))`
+///
+/// # Return:
+///   按出现顺序的子句;引号没闭合的尾段丢弃,没有标签名的值略过。
+pub(super) fn filter_clauses(expr: &str) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    // 当前括号内、值之前的词(标签名、运算符)。
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut chars = expr.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '"' || c == '\'' {
+            let Some(value) = quoted(&mut chars, c) else {
+                break;
+            };
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            let mut parts = words.drain(..);
+            if let Some(tag) = parts.next() {
+                clauses.push(Clause {
+                    tag,
+                    op: parts.next().unwrap_or_else(|| "==".to_owned()),
+                    value,
+                });
+            }
+        } else if c == '(' || c == ')' || c.is_whitespace() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            if c == '(' {
+                words.clear();
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    clauses
+}
+
+/// 读到与 `close` 匹配的引号为止(开引号已消费),处理反斜杠转义。
+///
+/// # Return:
+///   引号内的值;到行尾仍未闭合为 `None`。
+fn quoted(chars: &mut std::iter::Peekable<std::str::Chars<'_>>, close: char) -> Option<String> {
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '\\' => value.push(chars.next()?),
+            c if c == close => return Some(value),
+            c => value.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clause, filter_clauses, split_args};
+
+    /// 裸参数按空白拆;引号参数保留内部空白并解转义。
+    #[test]
+    fn splits_plain_and_quoted_args() {
+        assert_eq!(split_args("  status "), Ok(vec!["status".to_owned()]));
+        assert_eq!(
+            split_args(r#"add "netease:1""#),
+            Ok(vec!["add".to_owned(), "netease:1".to_owned()])
+        );
+        assert_eq!(
+            split_args(r#"search any "say \"hi\" \\ now""#),
+            Ok(vec![
+                "search".to_owned(),
+                "any".to_owned(),
+                r#"say "hi" \ now"#.to_owned(),
+            ])
+        );
+        assert_eq!(split_args(""), Ok(vec![]));
+        assert!(split_args(r#"add "unterminated"#).is_err());
+    }
+
+    /// 过滤表达式拆成 标签 / 运算符 / 值,单双引号都认;没有值的子句不出。
+    #[test]
+    fn filter_clauses_pair_tags_with_values() {
+        let clause = |tag: &str, op: &str, value: &str| Clause {
+            tag: tag.to_owned(),
+            op: op.to_owned(),
+            value: value.to_owned(),
+        };
+        assert_eq!(
+            filter_clauses(r#"((artist == "周杰伦") AND (title contains '晴\'天'))"#),
+            vec![
+                clause("artist", "==", "周杰伦"),
+                clause("title", "contains", "晴'天")
+            ]
+        );
+        assert_eq!(
+            filter_clauses(r#"(Album "叶惠美")"#),
+            vec![clause("Album", "==", "叶惠美")]
+        );
+        assert!(filter_clauses("(base music)").is_empty());
+        assert!(filter_clauses(r#"(artist == "unterminated)"#).is_empty());
+    }
+}
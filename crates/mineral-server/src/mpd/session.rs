@@ -0,0 +1,782 @@
+//! 单条 MPD 连接:问候行、命令 / 命令列表、密码、`idle` 等待。
+//!
+//! 读端按行分帧([`LinesCodec`] 由 [`FramedRead`] 驱动,`select!` 取消安全);写端
+//! 缓冲、每个应答后 flush。`idle` 期间的变更来源有二:event hub 上的属性变更,与
+//! 定时轮询的队列版本(队列重排 / 编辑不一定改长度,属性树上看不出来)。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::eyre::WrapErr;
+use futures_util::StreamExt;
+use mineral_protocol::{Event, PlayerVersions, PropName};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::broadcast;
+use tokio_util::codec::{FramedRead, LinesCodec};
+
+use super::command::{self, Ack, AckCode, Ctx, Reply};
+use super::parse::split_args;
+use crate::client::Client;
+use crate::exposure::secret_eq;
+
+/// 连接建立时的问候行(协议版本取 mineral 覆盖子集对得上的版本)。
+const GREETING: &str = "OK MPD 0.23.5\n";
+
+/// 单行命令上限(防对端喂一条无换行的长流撑爆内存)。
+const MAX_LINE: usize = 64 * 1024;
+
+/// `idle` 期间轮询队列版本的间隔。
+const IDLE_POLL: Duration = Duration::from_millis(500);
+
+/// 未出示密码也能执行的命令。
+const OPEN_COMMANDS: [&str; 5] = ["close", "commands", "notcommands", "password", "ping"];
+
+/// MPD 全部 idle 子系统名;mineral 只产出 [`Subsystem`] 里的四个,其余照收不触发。
+const IDLE_NAMES: [&str; 14] = [
+    "database",
+    "update",
+    "stored_playlist",
+    "playlist",
+    "player",
+    "mixer",
+    "output",
+    "options",
+    "partition",
+    "sticker",
+    "subscription",
+    "message",
+    "neighbor",
+    "mount",
+];
+
+/// 会触发的 idle 子系统。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Subsystem {
+    /// 播放态 / 在播曲。
+    Player,
+
+    /// 音量。
+    Mixer,
+
+    /// 随机 / 循环开关。
+    Options,
+
+    /// 队列。
+    Playlist,
+}
+
+impl Subsystem {
+    /// 全部(`idle` 无参数时等待的集合)。
+    const ALL: [Self; 4] = [Self::Player, Self::Mixer, Self::Options, Self::Playlist];
+
+    /// 协议名。
+    fn name(self) -> &'static str {
+        match self {
+            Self::Player => "player",
+            Self::Mixer => "mixer",
+            Self::Options => "options",
+            Self::Playlist => "playlist",
+        }
+    }
+
+    /// 在变更位图里的位。
+    fn bit(self) -> u8 {
+        match self {
+            Self::Player => 0b0001,
+            Self::Mixer => 0b0010,
+            Self::Options => 0b0100,
+            Self::Playlist => 0b1000,
+        }
+    }
+}
+
+/// 全部子系统的位图。
+const ALL_BITS: u8 = 0b1111;
+
+/// 属性变更 → idle 子系统。播放进度每秒一跳,MPD 客户端自己按 `elapsed` 外推,不映射。
+const PROP_SUBSYSTEMS: [(PropName, Subsystem); 5] = [
+    (PropName::PLAYER_SONG, Subsystem::Player),
+    (PropName::PLAYER_STATE, Subsystem::Player),
+    (PropName::PLAYER_VOLUME, Subsystem::Mixer),
+    (PropName::PLAYER_MODE, Subsystem::Options),
+    (PropName::QUEUE_LENGTH, Subsystem::Playlist),
+];
+
+/// 一条命令处理完之后连接怎么走。
+enum Flow {
+    /// 继续读下一条。
+    Continue,
+
+    /// 关闭连接(`close` / 对端在 idle 中发了非 `noidle`)。
+    Close,
+}
+
+/// 进行中的命令列表。
+struct CommandList {
+    /// `command_list_ok_begin` 开的:每条成功命令后回 `list_OK`。
+    ok_mode: bool,
+
+    /// 已收的命令(各自分好词,非空)。
+    commands: Vec<Vec<String>>,
+}
+
+/// 连接状态。
+struct Session {
+    /// 命令执行上下文。
+    ctx: Ctx,
+
+    /// 按行分帧的读端。
+    lines: FramedRead<OwnedReadHalf, LinesCodec>,
+
+    /// 缓冲写端。
+    out: BufWriter<OwnedWriteHalf>,
+
+    /// event hub 订阅(连接建立即订阅,idle 之间的变更也记账)。
+    rx: broadcast::Receiver<Event>,
+
+    /// 上次 idle 应答以来变更过的子系统位图。
+    changed: u8,
+
+    /// 上次轮询看到的版本号(队列版本变 = 队列变更)。
+    versions: PlayerVersions,
+
+    /// 配置的密码;`None` = 不设防。
+    password: Option<Arc<str>>,
+
+    /// 是否已出示正确密码(未设密码时恒真)。
+    authed: bool,
+
+    /// 进行中的命令列表。
+    list: Option<CommandList>,
+}
+
+/// 服务一条连接直到对端断开 / `close`。
+///
+/// # Params:
+///   - `stream`: 已 accept 的 TCP 连接
+///   - `ctx`: 命令执行上下文
+///   - `password`: 配置的密码(已滤掉空串)
+///
+/// # Errors
+/// 读写失败 / 行超长。
+pub(super) async fn run(
+    stream: TcpStream,
+    ctx: Ctx,
+    password: Option<Arc<str>>,
+) -> color_eyre::Result<()> {
+    let (read, write) = stream.into_split();
+    let rx = ctx.events.subscribe();
+    let versions = ctx.client.player_sync(PlayerVersions::default()).versions;
+    let mut session = Session {
+        ctx,
+        lines: FramedRead::new(read, LinesCodec::new_with_max_length(MAX_LINE)),
+        out: BufWriter::new(write),
+        rx,
+        changed: 0,
+        versions,
+        authed: password.is_none(),
+        password,
+        list: None,
+    };
+    session.write(GREETING).await?;
+    while let Some(line) = session.lines.next().await {
+        let line = line.wrap_err("mpd read")?;
+        if let Flow::Close = session.handle(&line).await? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+impl Session {
+    /// 处理一行命令。
+    ///
+    /// # Errors
+    /// 写失败。
+    async fn handle(&mut self, line: &str) -> color_eyre::Result<Flow> {
+        let args = match split_args(line) {
+            Ok(args) => args,
+            Err(msg) => {
+                let ack = Ack::new(AckCode::Arg, msg);
+                self.write(&format!("{}\n", ack.render(0, ""))).await?;
+                return Ok(Flow::Continue);
+            }
+        };
+        let Some(name) = args.first() else {
+            let ack = Ack::new(AckCode::Unknown, "No command given");
+            self.write(&format!("{}\n", ack.render(0, ""))).await?;
+            return Ok(Flow::Continue);
+        };
+        if let Some(list) = &mut self.list {
+            if name != "command_list_end" {
+                list.commands.push(args);
+                return Ok(Flow::Continue);
+            }
+            return self.run_list().await.map(|()| Flow::Continue);
+        }
+        match name.as_str() {
+            "close" => return Ok(Flow::Close),
+            "command_list_begin" | "command_list_ok_begin" => {
+                self.list = Some(CommandList {
+                    ok_mode: name == "command_list_ok_begin",
+                    commands: Vec::new(),
+                });
+            }
+            // idle 之外的 noidle 不回应(协议规定)。
+            "noidle" => {}
+            "idle" => return self.idle(&args).await,
+            _ => {
+                let text = match self.run_one(&args).await {
+                    Ok(body) => format!("{body}OK\n"),
+                    Err(ack) => format!("{}\n", ack.render(0, name)),
+                };
+                self.write(&text).await?;
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// 执行一条命令(含密码与权限检查)。
+    ///
+    /// # Return:
+    ///   应答体;失败为 [`Ack`]。
+    async fn run_one(&mut self, args: &[String]) -> Result<String, Ack> {
+        let Some((name, rest)) = args.split_first() else {
+            return Err(Ack::new(AckCode::Unknown, "No command given"));
+        };
+        if name == "password" {
+            let given = rest
+                .first()
+                .ok_or_else(|| Ack::new(AckCode::Arg, "missing argument"))?;
+            return match &self.password {
+                Some(want) if !secret_eq(given, want) => {
+                    Err(Ack::new(AckCode::Password, "incorrect password"))
+                }
+                _ => {
+                    self.authed = true;
+                    Ok(String::new())
+                }
+            };
+        }
+        if !self.authed && !OPEN_COMMANDS.contains(&name.as_str()) {
+            return Err(Ack::new(
+                AckCode::Permission,
+                format!("you don't have permission for \"{name}\""),
+            ));
+        }
+        command::execute(&self.ctx, name, rest)
+            .await
+            .map(Reply::into_body)
+    }
+
+    /// `command_list_end`:依次执行,首个失败即停并回 ACK(带其下标)。
+    ///
+    /// # Errors
+    /// 写失败。
+    async fn run_list(&mut self) -> color_eyre::Result<()> {
+        let Some(list) = self.list.take() else {
+            return Ok(());
+        };
+        let mut text = String::new();
+        for (index, args) in list.commands.iter().enumerate() {
+            match self.run_one(args).await {
+                Ok(body) => {
+                    text.push_str(&body);
+                    if list.ok_mode {
+                        text.push_str("list_OK\n");
+                    }
+                }
+                Err(ack) => {
+                    let name = args.first().map_or("", String::as_str);
+                    text.push_str(&ack.render(index, name));
+                    text.push('\n');
+                    return self.write(&text).await;
+                }
+            }
+        }
+        text.push_str("OK\n");
+        self.write(&text).await
+    }
+
+    /// `idle [SUBSYSTEM...]`:等到所关心的子系统有变更再回 `changed:` 行;期间对端
+    /// 只能发 `noidle`(立即以空应答收尾),发别的按协议断开。
+    ///
+    /// # Errors
+    /// 写失败 / 读失败。
+    async fn idle(&mut self, args: &[String]) -> color_eyre::Result<Flow> {
+        if !self.authed {
+            let ack = Ack::new(
+                AckCode::Permission,
+                "you don't have permission for \"idle\"",
+            );
+            self.write(&format!("{}\n", ack.render(0, "idle"))).await?;
+            return Ok(Flow::Continue);
+        }
+        let wanted = match wanted_bits(args.get(1..).unwrap_or_default()) {
+            Ok(bits) => bits,
+            Err(ack) => {
+                self.write(&format!("{}\n", ack.render(0, "idle"))).await?;
+                return Ok(Flow::Continue);
+            }
+        };
+        let mut poll = tokio::time::interval(IDLE_POLL);
+        loop {
+            self.drain_events();
+            self.poll_versions();
+            let hit = self.changed & wanted;
+            if hit != 0 {
+                self.changed &= !hit;
+                let mut text = String::new();
+                for sub in Subsystem::ALL.into_iter().filter(|s| hit & s.bit() != 0) {
+                    text.push_str("changed: ");
+                    text.push_str(sub.name());
+                    text.push('\n');
+                }
+                text.push_str("OK\n");
+                self.write(&text).await?;
+                return Ok(Flow::Continue);
+            }
+            tokio::select! {
+                event = self.rx.recv() => match event {
+                    Ok(ev) => self.note(&ev),
+                    Err(broadcast::error::RecvError::Lagged(_)) => self.changed = ALL_BITS,
+                    Err(broadcast::error::RecvError::Closed) => return Ok(Flow::Close),
+                },
+                line = self.lines.next() => {
+                    return match line {
+                        Some(Ok(l)) if l.trim() == "noidle" => {
+                            self.write("OK\n").await?;
+                            Ok(Flow::Continue)
+                        }
+                        Some(Ok(_)) | None => Ok(Flow::Close),
+                        Some(Err(e)) => Err(e).wrap_err("mpd read"),
+                    };
+                }
+                _ = poll.tick() => {}
+            }
+        }
+    }
+
+    /// 把 hub 上积下的事件记进变更位图(不阻塞)。
+    fn drain_events(&mut self) {
+        loop {
+            match self.rx.try_recv() {
+                Ok(ev) => self.note(&ev),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => self.changed = ALL_BITS,
+                Err(
+                    broadcast::error::TryRecvError::Empty | broadcast::error::TryRecvError::Closed,
+                ) => {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 版本号轮询:队列版本变了记 playlist,在播版本变了记 player。
+    fn poll_versions(&mut self) {
+        let now = self.ctx.client.player_sync(self.versions).versions;
+        if now.queue != self.versions.queue {
+            self.changed |= Subsystem::Playlist.bit();
+        }
+        if now.current != self.versions.current {
+            self.changed |= Subsystem::Player.bit();
+        }
+        self.versions = now;
+    }
+
+    /// 记一条事件。
+    fn note(&mut self, event: &Event) {
+        if let Some(sub) = subsystem_of(event) {
+            self.changed |= sub.bit();
+        }
+    }
+
+    /// 写出并 flush。
+    ///
+    /// # Errors
+    /// 写失败(对端已断)。
+    async fn write(&mut self, text: &str) -> color_eyre::Result<()> {
+        self.out
+            .write_all(text.as_bytes())
+            .await
+            .wrap_err("mpd write")?;
+        self.out.flush().await.wrap_err("mpd write")
+    }
+}
+
+/// `idle` 参数 → 等待的子系统位图;无参数 = 全部。
+///
+/// # Errors
+/// 不认识的子系统名。
+fn wanted_bits(names: &[String]) -> Result<u8, Ack> {
+    if names.is_empty() {
+        return Ok(ALL_BITS);
+    }
+    names.iter().try_fold(0, |bits, name| {
+        if let Some(sub) = Subsystem::ALL.into_iter().find(|s| s.name() == name) {
+            Ok(bits | sub.bit())
+        } else if IDLE_NAMES.contains(&name.as_str()) {
+            Ok(bits)
+        } else {
+            Err(Ack::new(
+                AckCode::Arg,
+                format!("Unrecognized idle event: {name}"),
+            ))
+        }
+    })
+}
+
+/// 事件落到哪个 idle 子系统(不映射的为 `None`)。
+fn subsystem_of(event: &Event) -> Option<Subsystem> {
+    let Event::PropertyChanged { prop, .. } = event else {
+        return None;
+    };
+    PROP_SUBSYSTEMS
+        .iter()
+        .find(|(p, _)| p == prop)
+        .map(|(_, sub)| *sub)
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_channel_core::{
+        ArtistSections, ChannelCaps, Error as ChannelError, MusicChannel, Result as ChannelResult,
+    };
+    use mineral_model::{
+        AlbumId, AlbumRef, ArtistId, ArtistRef, BitRate, MediaUrl, PlayUrl, SearchKind, Song,
+        SongId, SourceKind,
+    };
+    use mineral_protocol::{Event, FinishReason, PropName, PropValue};
+
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use color_eyre::eyre::{WrapErr, eyre};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+    use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::broadcast;
+
+    use super::{ALL_BITS, GREETING, Subsystem, run, subsystem_of, wanted_bits};
+    use crate::mpd::command::Ctx;
+
+    /// 单条应答的等待上限(防会话卡住把测试挂死)。
+    const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// 测试对端:按行读应答、整段写命令。
+    struct Peer {
+        /// 按行读端。
+        lines: Lines<BufReader<OwnedReadHalf>>,
+
+        /// 写端。
+        write: OwnedWriteHalf,
+    }
+
+    impl Peer {
+        /// 发一段命令(可多行)。
+        async fn send(&mut self, text: &str) -> color_eyre::Result<()> {
+            self.write
+                .write_all(text.as_bytes())
+                .await
+                .wrap_err("write")
+        }
+
+        /// 读一条应答:直到 `OK` / `ACK` 收尾行为止,各行以 `\n` 连回。
+        async fn reply(&mut self) -> color_eyre::Result<String> {
+            let mut text = String::new();
+            loop {
+                let line = tokio::time::timeout(REPLY_TIMEOUT, self.lines.next_line())
+                    .await
+                    .wrap_err("reply timeout")??
+                    .ok_or_else(|| eyre!("connection closed"))?;
+                let done = line.starts_with("OK") || line.starts_with("ACK");
+                text.push_str(&line);
+                text.push('\n');
+                if done {
+                    return Ok(text);
+                }
+            }
+        }
+
+        /// 发一段命令并读一条应答。
+        async fn ask(&mut self, text: &str) -> color_eyre::Result<String> {
+            self.send(text).await?;
+            self.reply().await
+        }
+    }
+
+    /// 起进程内 Server(ForceNull 音频 + 禁用 persist),在回环 TCP 上跑一条带密码的
+    /// 会话并接上对端;问候行已读掉。
+    ///
+    /// # Return:
+    ///   (对端, event hub 发送端)。
+    async fn connect(password: &str) -> color_eyre::Result<(Peer, broadcast::Sender<Event>)> {
+        connect_with(password, Vec::new()).await
+    }
+
+    /// 同 [`connect`],另注入音乐源。
+    ///
+    /// # Params:
+    ///   - `password`: 连接密码
+    ///   - `channels`: 注入的音乐源
+    async fn connect_with(
+        password: &str,
+        channels: Vec<Arc<dyn MusicChannel>>,
+    ) -> color_eyre::Result<(Peer, broadcast::Sender<Event>)> {
+        let cfg = mineral_config::Config::defaults()?;
+        let server = crate::Server::spawn(
+            channels,
+            mineral_audio::AudioMode::ForceNull,
+            mineral_persist::ServerStore::disabled(),
+            crate::ServerConfig::from_config(&cfg),
+            mineral_config::default_tree()?,
+            /*script*/ None,
+            crate::StatsRecorder::disabled(),
+        )
+        .await?;
+        let events = server.event_sink();
+        let ctx = Ctx {
+            client: server.client(),
+            events: events.clone(),
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let password = Some(password.into());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            let _server = server;
+            run(stream, ctx, password).await
+        });
+        let (read, write) = TcpStream::connect(addr).await?.into_split();
+        let mut peer = Peer {
+            lines: BufReader::new(read).lines(),
+            write,
+        };
+        assert_eq!(peer.reply().await?, GREETING);
+        Ok((peer, events))
+    }
+
+    /// 本地曲库桩:只给出固定的两首歌。
+    struct LocalLibrary;
+
+    #[async_trait]
+    impl MusicChannel for LocalLibrary {
+        fn source(&self) -> SourceKind {
+            SourceKind::LOCAL
+        }
+
+        fn caps(&self) -> ChannelCaps {
+            ChannelCaps::builder()
+                .searchable(vec![SearchKind::Song])
+                .playlist_edit(false)
+                .artist_sections(ArtistSections::new(Vec::new()))
+                .build()
+        }
+
+        async fn songs_detail(&self, _ids: &[SongId]) -> ChannelResult<Vec<Song>> {
+            Err(ChannelError::NotSupported)
+        }
+
+        async fn song_urls(&self, _ids: &[SongId], _q: BitRate) -> ChannelResult<Vec<PlayUrl>> {
+            Err(ChannelError::NotSupported)
+        }
+
+        async fn library_songs(&self) -> ChannelResult<Vec<Song>> {
+            let song = |path: &str, title: &str, artist: &str, album: &str| {
+                Song::builder()
+                    .id(SongId::new(SourceKind::LOCAL, path))
+                    .name(title.to_owned())
+                    .artists(vec![ArtistRef {
+                        id: ArtistId::new(SourceKind::LOCAL, artist),
+                        name: artist.to_owned(),
+                    }])
+                    .album(Some(AlbumRef {
+                        id: AlbumId::new(SourceKind::LOCAL, album),
+                        name: album.to_owned(),
+                    }))
+                    .source_url(Some(MediaUrl::local(path)))
+                    .build()
+            };
+            Ok(vec![
+                song("/m/a/1.flac", "晴天", "周杰伦", "叶惠美"),
+                song("/m/b/2.flac", "Palisade", "Shore", "Tide"),
+            ])
+        }
+    }
+
+    /// 本地曲库:`lsinfo` 按目录浏览,`list` 列标签值,`find` 整值匹配,`search` 子串忽略大小写。
+    #[tokio::test]
+    async fn browses_and_searches_local_library() -> color_eyre::Result<()> {
+        let (mut peer, _events) = connect_with("pw", vec![Arc::new(LocalLibrary)]).await?;
+        assert_eq!(peer.ask("password pw\n").await?, "OK\n");
+        assert_eq!(
+            peer.ask("lsinfo\n").await?,
+            "directory: /m/a\ndirectory: /m/b\nOK\n"
+        );
+        let dir = peer.ask("lsinfo \"/m/a\"\n").await?;
+        assert!(
+            dir.starts_with("file: /m/a/1.flac\nTitle: 晴天\n") && dir.ends_with("OK\n"),
+            "{dir}"
+        );
+        assert!(peer.ask("lsinfo /nope\n").await?.starts_with("ACK [50@0]"));
+        assert_eq!(
+            peer.ask("list artist\n").await?,
+            "Artist: Shore\nArtist: 周杰伦\nOK\n"
+        );
+        assert_eq!(
+            peer.ask("list album 周杰伦\n").await?,
+            "Album: 叶惠美\nOK\n"
+        );
+        assert_eq!(
+            peer.ask("list title \"(album == 'Tide')\"\n").await?,
+            "Title: Palisade\nOK\n"
+        );
+        assert_eq!(peer.ask("find title 晴\n").await?, "OK\n", "find 不做子串");
+        assert!(
+            peer.ask("find title 晴天\n")
+                .await?
+                .starts_with("file: /m/a/1.flac\n")
+        );
+        assert!(
+            peer.ask("search artist shore\n")
+                .await?
+                .starts_with("file: /m/b/2.flac\n")
+        );
+        Ok(())
+    }
+
+    /// 密码门:未出示前只放行开放命令;错密码回 ACK 且仍受限,对了才放行。
+    #[tokio::test]
+    async fn password_gates_commands() -> color_eyre::Result<()> {
+        let (mut peer, _events) = connect("pw").await?;
+        assert_eq!(peer.ask("ping\n").await?, "OK\n");
+        assert_eq!(
+            peer.ask("status\n").await?,
+            "ACK [4@0] {status} you don't have permission for \"status\"\n"
+        );
+        assert_eq!(
+            peer.ask("idle\n").await?,
+            "ACK [4@0] {idle} you don't have permission for \"idle\"\n"
+        );
+        assert_eq!(
+            peer.ask("password nope\n").await?,
+            "ACK [3@0] {password} incorrect password\n"
+        );
+        assert!(peer.ask("status\n").await?.starts_with("ACK [4@0]"));
+        assert_eq!(peer.ask("password pw\n").await?, "OK\n");
+        let status = peer.ask("status\n").await?;
+        assert!(
+            status.contains("volume: ") && status.ends_with("OK\n"),
+            "{status}"
+        );
+        Ok(())
+    }
+
+    /// 命令列表:ok 模式逐条回 `list_OK`;首个失败即停,ACK 带其下标,后续不执行;
+    /// 列表里的受限命令同样过密码门。
+    #[tokio::test]
+    async fn command_lists_run_in_order() -> color_eyre::Result<()> {
+        let (mut peer, _events) = connect("pw").await?;
+        assert_eq!(
+            peer.ask("command_list_begin\nping\nstatus\ncommand_list_end\n")
+                .await?,
+            "ACK [4@1] {status} you don't have permission for \"status\"\n"
+        );
+        assert_eq!(peer.ask("password pw\n").await?, "OK\n");
+        assert_eq!(
+            peer.ask("command_list_ok_begin\nping\nping\ncommand_list_end\n")
+                .await?,
+            "list_OK\nlist_OK\nOK\n"
+        );
+        assert_eq!(
+            peer.ask("command_list_begin\nping\nping\ncommand_list_end\n")
+                .await?,
+            "OK\n"
+        );
+        assert_eq!(
+            peer.ask("command_list_ok_begin\nping\nbogus\nsetvol 50\ncommand_list_end\n")
+                .await?,
+            "list_OK\nACK [5@1] {bogus} unknown command \"bogus\"\n"
+        );
+        assert_eq!(peer.ask("ping\n").await?, "OK\n", "列表收尾后回到普通模式");
+        Ok(())
+    }
+
+    /// idle:`noidle` 立即以空应答收尾;所关心子系统有变更才回 `changed:` 行;
+    /// 不关心的变更留账,下次 idle 到它时立即回。
+    #[tokio::test]
+    async fn idle_waits_for_changes() -> color_eyre::Result<()> {
+        let (mut peer, events) = connect("pw").await?;
+        assert_eq!(peer.ask("password pw\n").await?, "OK\n");
+        // 先等一个不产出的子系统:启动期的音量等事件可能晚到,等 mixer 会被它提前叫醒。
+        assert_eq!(peer.ask("idle database\nnoidle\n").await?, "OK\n");
+        assert_eq!(
+            peer.ask("noidle\nping\n").await?,
+            "OK\n",
+            "idle 外的 noidle 不回应"
+        );
+
+        let changed = |prop| Event::PropertyChanged {
+            prop,
+            value: PropValue::Int(1),
+        };
+        peer.send("idle mixer\n").await?;
+        // 等会话进入 idle 再推(确认确实在等,而非进 idle 前就已记账)。
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        events
+            .send(changed(PropName::PLAYER_MODE))
+            .map_err(|e| eyre!("event send: {e}"))?;
+        events
+            .send(changed(PropName::PLAYER_VOLUME))
+            .map_err(|e| eyre!("event send: {e}"))?;
+        assert_eq!(peer.reply().await?, "changed: mixer\nOK\n");
+        assert_eq!(peer.ask("idle options\n").await?, "changed: options\nOK\n");
+        Ok(())
+    }
+
+    /// 属性变更按表映射;进度与非属性事件不触发。
+    #[test]
+    fn events_map_to_subsystems() {
+        let changed = |prop| Event::PropertyChanged {
+            prop,
+            value: PropValue::Int(1),
+        };
+        assert_eq!(
+            subsystem_of(&changed(PropName::PLAYER_STATE)),
+            Some(Subsystem::Player)
+        );
+        assert_eq!(
+            subsystem_of(&changed(PropName::PLAYER_VOLUME)),
+            Some(Subsystem::Mixer)
+        );
+        assert_eq!(
+            subsystem_of(&changed(PropName::QUEUE_LENGTH)),
+            Some(Subsystem::Playlist)
+        );
+        assert_eq!(subsystem_of(&changed(PropName::PLAYER_POSITION)), None);
+        assert_eq!(
+            subsystem_of(&Event::TrackFinished {
+                song_id: SongId::new(SourceKind::NETEASE, "1"),
+                reason: FinishReason::Eof,
+            }),
+            None
+        );
+    }
+
+    /// 无参数等全部;认得但不产出的子系统照收;不认识的报错。
+    #[test]
+    fn idle_names_select_subsystems() {
+        let names = |v: &[&str]| v.iter().map(|s| (*s).to_owned()).collect::<Vec<_>>();
+        assert_eq!(wanted_bits(&[]).ok(), Some(ALL_BITS));
+        assert_eq!(
+            wanted_bits(&names(&["player", "mixer"])).ok(),
+            Some(Subsystem::Player.bit() | Subsystem::Mixer.bit())
+        );
+        assert_eq!(wanted_bits(&names(&["database"])).ok(), Some(0));
+        assert!(wanted_bits(&names(&["bogus"])).is_err());
+    }
+}
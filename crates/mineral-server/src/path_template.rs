@@ -0,0 +1,270 @@
+//! 下载导出路径模板(配置 `download.path_template`)的渲染。
+//!
+//! 模板语法在配置落型时已解析校验(见 [`PathTemplate`]);这里逐段填值后做文件名安全规整(同缓存库命名的
+//! [`sanitize_segment`]),扩展名恒按实际格式补在末尾。默认模板与缓存库布局
+//! `<source>/<quality>/<album>/<title>.<ext>` 逐字节一致——索引建立前的历史下载据此仍可反查。
+
+use mineral_config::{PathTemplate, TemplatePiece, TemplateVar};
+use mineral_model::{AudioFormat, BitRate, Song};
+
+use crate::media_cache::{ext_for, sanitize_segment};
+
+/// 目录段渲染为空时的兜底名。
+const EMPTY_DIR: &str = "_unknown";
+
+/// 文件名主干渲染为空时的兜底名。
+const EMPTY_STEM: &str = "_untitled";
+
+/// 按模板渲染一首歌的导出相对落点。
+///
+/// # Params:
+///   - `template`: 路径模板(配置落型时已解析)
+///   - `song`: 歌曲
+///   - `quality`: 下载音质
+///   - `format`: 实际格式(定扩展名;未知按音质兜底)
+///
+/// # Return:
+///   `(subdir, file_name)`:目录段以 `/` 连接(可为空串),各段已规整。
+pub(crate) fn render(
+    template: &PathTemplate,
+    song: &Song,
+    quality: BitRate,
+    format: Option<&AudioFormat>,
+) -> (String, String) {
+    let subdir = template
+        .dirs()
+        .iter()
+        .map(|seg| render_segment(seg, song, quality, EMPTY_DIR))
+        .collect::<Vec<String>>()
+        .join("/");
+    let stem = render_segment(template.stem(), song, quality, EMPTY_STEM);
+    let ext = sanitize_segment(&ext_for(format, quality), "bin");
+    (subdir, format!("{stem}.{ext}"))
+}
+
+/// 渲染一段并规整;有变量取空时先清掉残留的空括号与首尾分隔符。
+///
+/// # Params:
+///   - `pieces`: 段片段
+///   - `song`: 歌曲
+///   - `quality`: 下载音质
+///   - `fallback`: 规整后为空的兜底名
+///
+/// # Return:
+///   合法的单段名。
+fn render_segment(
+    pieces: &[TemplatePiece],
+    song: &Song,
+    quality: BitRate,
+    fallback: &str,
+) -> String {
+    let mut out = String::new();
+    let mut hollow = false;
+    for piece in pieces {
+        match piece {
+            TemplatePiece::Lit(s) => out.push_str(s),
+            TemplatePiece::Var { var, width } => {
+                let value = var_value(*var, *width, song, quality);
+                hollow |= value.is_empty();
+                out.push_str(&value);
+            }
+        }
+    }
+    if hollow {
+        out = tidy(&out);
+    }
+    sanitize_segment(&out, fallback)
+}
+
+/// 变量取值(未规整;取不到为空串)。
+///
+/// # Params:
+///   - `var`: 变量
+///   - `width`: 补零宽度
+///   - `song`: 歌曲
+///   - `quality`: 下载音质
+///
+/// # Return:
+///   变量值。
+fn var_value(var: TemplateVar, width: usize, song: &Song, quality: BitRate) -> String {
+    let first_artist = || {
+        song.artists
+            .first()
+            .map(|a| a.name.clone())
+            .unwrap_or_default()
+    };
+    match var {
+        TemplateVar::Source => song.source().name().to_owned(),
+        TemplateVar::Quality => quality.as_str().to_owned(),
+        TemplateVar::Title if song.name.is_empty() => song.id.as_str().to_owned(),
+        TemplateVar::Title => song.name.clone(),
+        TemplateVar::Album => song
+            .album
+            .as_ref()
+            .map(|a| a.name.clone())
+            .unwrap_or_default(),
+        TemplateVar::AlbumArtist => song
+            .album_artist
+            .clone()
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(first_artist),
+        TemplateVar::Artist => first_artist(),
+        TemplateVar::Artists => song
+            .artists
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        TemplateVar::Track => song
+            .track_no
+            .map(|n| format!("{n:0width$}"))
+            .unwrap_or_default(),
+        TemplateVar::Year => song
+            .year
+            .map(|y| format!("{y:0width$}"))
+            .unwrap_or_default(),
+        TemplateVar::Id => song.id.as_str().to_owned(),
+    }
+}
+
+/// 清理空变量留下的痕迹:空括号、连续空白、首尾的 ` - ` / `_` / `,`。
+///
+/// # Params:
+///   - `s`: 渲染后的段
+///
+/// # Return:
+///   清理后的段。
+fn tidy(s: &str) -> String {
+    let mut out = s.to_owned();
+    loop {
+        let next = out.replace("()", "").replace("[]", "");
+        if next == out {
+            break;
+        }
+        out = next;
+    }
+    out.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '_' | ','))
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{
+        AlbumId, AlbumRef, ArtistId, ArtistRef, AudioFormat, BitRate, Song, SongId, SourceKind,
+    };
+
+    use mineral_config::PathTemplate;
+
+    use super::render;
+    use crate::media_cache::library_relpath;
+
+    /// 默认模板串(同 default.lua 的 `download.path_template`)。
+    const DEFAULT_TEMPLATE: &str = "{source}/{quality}/{album}/{title}.{ext}";
+
+    /// 测试用歌:两个艺人、带专辑与音轨号。
+    fn song(track_no: Option<u32>) -> Song {
+        Song::builder()
+            .id(SongId::new(SourceKind::NETEASE, "42"))
+            .name("晴天".to_owned())
+            .artists(vec![
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "1"),
+                    name: "周杰伦".to_owned(),
+                },
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "2"),
+                    name: "A/B".to_owned(),
+                },
+            ])
+            .album(Some(AlbumRef {
+                id: AlbumId::new(SourceKind::NETEASE, "7"),
+                name: "叶惠美".to_owned(),
+            }))
+            .track_no(track_no)
+            .build()
+    }
+
+    /// 默认模板与缓存库布局逐字节一致(历史下载靠它反查)。
+    #[test]
+    fn default_matches_library_layout() -> color_eyre::Result<()> {
+        let parsed = PathTemplate::parse(DEFAULT_TEMPLATE)?;
+        assert_eq!(parsed, PathTemplate::default());
+        let s = song(None);
+        for format in [Some(AudioFormat::Flac), None] {
+            assert_eq!(
+                render(&parsed, &s, BitRate::Lossless, format.as_ref()),
+                library_relpath(&s, BitRate::Lossless, format.as_ref())
+            );
+        }
+        Ok(())
+    }
+
+    /// 自定义模板:补零、多艺人、值里的 `/` 规整成 `_`;缺值时空括号与悬空分隔符去掉。
+    #[test]
+    fn renders_custom_template_and_tidies_missing() -> color_eyre::Result<()> {
+        let t = PathTemplate::parse("{album_artist}/{album}/{track:02} - {title}.{ext}")?;
+        assert_eq!(
+            render(
+                &t,
+                &song(Some(3)),
+                BitRate::Lossless,
+                Some(&AudioFormat::Flac)
+            ),
+            ("周杰伦/叶惠美".to_owned(), "03 - 晴天.flac".to_owned())
+        );
+        assert_eq!(
+            render(&t, &song(None), BitRate::Exhigh, None),
+            ("周杰伦/叶惠美".to_owned(), "晴天.mp3".to_owned()),
+            "无音轨号:前导 ` - ` 去掉;未知格式按音质兜底扩展名"
+        );
+
+        let flat = PathTemplate::parse("{artists} - {title}")?;
+        assert_eq!(
+            render(
+                &flat,
+                &song(None),
+                BitRate::Lossless,
+                Some(&AudioFormat::Flac)
+            ),
+            (String::new(), "周杰伦, A_B - 晴天.flac".to_owned()),
+            "无目录段落根下;省略 .{{ext}} 自动补"
+        );
+
+        let bracketed = PathTemplate::parse("{album} [{track}]/{title}")?;
+        assert_eq!(
+            render(
+                &bracketed,
+                &song(None),
+                BitRate::Lossless,
+                Some(&AudioFormat::Flac)
+            ),
+            ("叶惠美".to_owned(), "晴天.flac".to_owned()),
+            "取空的变量留下的空括号去掉"
+        );
+        Ok(())
+    }
+
+    /// 需求原例:专辑艺人取来源给的值(而非主艺人),年份照常渲染;缺年份时空括号去掉。
+    #[test]
+    fn renders_album_artist_and_year() -> color_eyre::Result<()> {
+        let t = PathTemplate::parse("{album_artist}/{album} ({year})/{track:02} - {title}.{ext}")?;
+        let mut s = song(Some(3));
+        s.album_artist = Some("群星".to_owned());
+        s.year = Some(2003);
+        assert_eq!(
+            render(&t, &s, BitRate::Lossless, Some(&AudioFormat::Flac)),
+            ("群星/叶惠美 (2003)".to_owned(), "03 - 晴天.flac".to_owned())
+        );
+        s.album_artist = None;
+        s.year = None;
+        assert_eq!(
+            render(&t, &s, BitRate::Lossless, Some(&AudioFormat::Flac)),
+            ("周杰伦/叶惠美".to_owned(), "03 - 晴天.flac".to_owned()),
+            "无专辑艺人退主艺人;无年份时空括号去掉"
+        );
+        Ok(())
+    }
+}
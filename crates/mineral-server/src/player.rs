@@ -5,13 +5,12 @@
 //! 其余推 client)、auto-next(监听 `track_finished_seq`)、prefetch 下一曲 URL、harvest
 //! 下完的 capture。下载走独立单 worker 串行消费队列(见 [`crate::download`])。
 
-use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use mineral_audio::AudioHandle;
-use mineral_channel_core::MusicChannel;
+use mineral_channel_core::{MusicChannel, discover};
 use mineral_model::{BitRate, MediaUrl, PlayUrl, Song, SongId, SourceKind};
 use mineral_persist::ServerStore;
 use mineral_protocol::{
@@ -52,9 +51,9 @@ pub(crate) struct Inner {
     /// 下载用的 HTTP client(整段 GET);构建失败为 `None`(下载不可用)。
     http: Option<reqwest::Client>,
 
-    /// 永久下载导出根目录(`~/Music/mineral`);播放解析据此**直接探盘**命中已下载副本、
-    /// 跳过网络;解析失败为 `None`(下载不可用)。
-    music_dir: Option<PathBuf>,
+    /// 永久下载导出库(根目录 + 路径模板 + 导出索引);播放解析据此命中已下载副本、
+    /// 跳过网络;根目录解析失败时下载不可用。
+    exports: crate::exports::Exports,
 
     /// 下载进度共享态:下载任务实时写,client(TUI 弹窗 / CLI status)轮询读。
     download_progress: Arc<Mutex<DownloadProgress>>,
@@ -109,9 +108,21 @@ pub(crate) struct Inner {
     /// 响度包络计算参数(配置 `audio.envelope`)。
     pub(crate) envelope_params: mineral_audio::EnvelopeParams,
 
+    /// 响度归一化配置(`audio.normalization`)。
+    normalization: mineral_config::NormalizationConfig,
+
     /// gapless 预排触发距曲终的剩余时间(ms,配置 `daemon.gapless_prefetch_ms`)。
     gapless_prefetch_ms: u64,
 
+    /// 交叉淡化时长(ms,配置 `audio.crossfade`;未启用为 0)。
+    crossfade_ms: u64,
+
+    /// 电台续播配置(`queue.radio`)。
+    radio: mineral_config::RadioConfig,
+
+    /// 均衡器配置(`audio.eq`;按名切换预设时查表)。
+    pub(crate) eq: mineral_config::EqConfig,
+
     /// `p` 键的「回开头 vs 上一首」分界(ms,配置 `daemon.prev_restart_threshold_ms`)。
     prev_restart_threshold_ms: u64,
 
@@ -127,6 +138,12 @@ pub(crate) struct Inner {
     /// 下载测速刷新节流间隔(配置 `daemon.download_speed_tick_ms`)。
     download_speed_tick: Duration,
 
+    /// 下载后写标签配置(`download.tags`)。
+    download_tags: mineral_config::DownloadTagsConfig,
+
+    /// 歌词来源(配置 `lyrics`,默认目录已解析)。
+    pub(crate) lyric_sources: crate::lyrics::LyricSources,
+
     /// 系统媒体服务的播放进度上报间隔(ms,配置 `daemon.report_interval_ms`)。
     media_report_interval_ms: u64,
 
@@ -141,6 +158,9 @@ pub(crate) struct Inner {
 
     /// 聚合收藏补 meta 后台任务的状态 + 节流旋钮(单飞闸 / 待办标志 / 并发参数,见 [`crate::favorites`])。
     pub(crate) backfill: crate::favorites::Backfill,
+
+    /// 聚合歌单「统计面刷新」合并闸:`true` = 已有一轮在排队(见 `spawn_aggregate_refresh`)。
+    pub(crate) aggregate_refresh: std::sync::atomic::AtomicBool,
 }
 
 /// [`PlayerCore::spawn`] 的配置侧参数包:daemon 切片与有效配置底树是
@@ -153,6 +173,16 @@ pub(crate) struct SpawnConfig<'a> {
     pub(crate) tree: serde_json::Value,
 }
 
+/// [`PlayerCore::spawn`] 的两处本地副本:音频本体缓存 + 下载导出库。
+/// 合成一参避免 spawn 超 clippy 参数上限。
+pub(crate) struct LocalCopies {
+    /// 音频本体缓存;无音频缓存环境传 [`MediaCache::disabled`]。
+    pub(crate) media_cache: MediaCache,
+
+    /// 下载导出库(见 [`crate::exports`])。
+    pub(crate) exports: crate::exports::Exports,
+}
+
 /// [`PlayerCore::spawn`] 的两个 fire-and-forget 出口:事件通知 + 埋点 recorder。
 /// 合成一参避免 spawn 超 clippy 参数上限。
 pub(crate) struct Sinks {
@@ -171,7 +201,7 @@ impl PlayerCore {
     ///   - `scheduler`: 任务调度器。
     ///   - `channels`: 已注入的全部音乐源 handle。
     ///   - `persist`: 持久化句柄,存入 [`Inner`] 供 B-T7 起使用。
-    ///   - `media_cache`: 音频本体缓存;无音频缓存环境传 [`MediaCache::disabled`]。
+    ///   - `local`: 本地副本两处(音频缓存 + 下载导出库)。
     ///   - `spawn_config`: 配置侧参数包(切片 + 有效配置底树)。
     ///   - `sinks`: 事件通知 + 埋点 recorder 两个 fire-and-forget 出口。
     pub(crate) fn spawn(
@@ -179,7 +209,7 @@ impl PlayerCore {
         scheduler: Scheduler,
         channels: Vec<Arc<dyn MusicChannel>>,
         persist: ServerStore,
-        media_cache: MediaCache,
+        local: LocalCopies,
         spawn_config: SpawnConfig<'_>,
         sinks: Sinks,
     ) -> Self {
@@ -188,7 +218,11 @@ impl PlayerCore {
             tree: config_tree,
         } = spawn_config;
         let Sinks { notify, stats } = sinks;
-        let (http, music_dir) = crate::download::open_env(config.download().dir().as_deref());
+        let LocalCopies {
+            media_cache,
+            exports,
+        } = local;
+        let http = crate::download::open_http();
         let (download_tx, download_rx) = tokio::sync::mpsc::unbounded_channel();
         let library = crate::library::Library::new(
             channels
@@ -203,7 +237,7 @@ impl PlayerCore {
             persist,
             media_cache: Arc::new(media_cache),
             http,
-            music_dir,
+            exports,
             download_progress: Arc::new(Mutex::new(DownloadProgress::default())),
             download_tx,
             download_pending: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
@@ -220,12 +254,22 @@ impl PlayerCore {
             last_session_save: Mutex::new(Instant::now()),
             playback_quality: *config.playback_quality(),
             envelope_params: config.envelope().clone(),
+            normalization: config.normalization().clone(),
             gapless_prefetch_ms: *config.daemon().gapless_prefetch_ms(),
+            crossfade_ms: if *config.engine().crossfade().enabled() {
+                u64::from(*config.engine().crossfade().duration_ms())
+            } else {
+                0
+            },
+            radio: config.radio().clone(),
+            eq: config.eq().clone(),
             prev_restart_threshold_ms: *config.daemon().prev_restart_threshold_ms(),
             player_tick_ms: *config.daemon().player_tick_ms(),
             session_save: Duration::from_secs(*config.daemon().session_save_secs()),
             download_quality: *config.download().quality(),
             download_speed_tick: Duration::from_millis(*config.daemon().download_speed_tick_ms()),
+            download_tags: config.download().tags().clone(),
+            lyric_sources: crate::lyrics::LyricSources::from_config(config.lyrics()),
             media_report_interval_ms: *config.daemon().report_interval_ms(),
             media_seek_threshold_ms: *config.daemon().seek_threshold_ms(),
             hook_timeout: Duration::from_millis(*config.hook_timeout_ms()),
@@ -234,6 +278,7 @@ impl PlayerCore {
                 *config.favorites_backfill_chunk_size(),
                 *config.favorites_backfill_max_concurrent(),
             ),
+            aggregate_refresh: std::sync::atomic::AtomicBool::new(false),
         });
         let me = Self { inner };
         let bg = me.clone();
@@ -293,9 +338,9 @@ impl PlayerCore {
         self.inner.http.as_ref()
     }
 
-    /// 永久下载导出根目录;解析失败为 `None`(下载不可用)。
-    pub(crate) fn music_dir(&self) -> Option<&std::path::Path> {
-        self.inner.music_dir.as_deref()
+    /// 永久下载导出库(根目录解析失败时 [`Exports::root`](crate::exports::Exports::root) 为 `None`,下载不可用)。
+    pub(crate) fn exports(&self) -> &crate::exports::Exports {
+        &self.inner.exports
     }
 
     /// 下载进度共享态句柄(下载任务实时写入)。
@@ -313,11 +358,31 @@ impl PlayerCore {
         self.inner.gapless_prefetch_ms
     }
 
+    /// 交叉淡化时长(ms,配置 `audio.crossfade`;未启用为 0)。
+    pub(crate) fn crossfade_ms(&self) -> u64 {
+        self.inner.crossfade_ms
+    }
+
+    /// 电台续播配置(`queue.radio`)。
+    pub(crate) fn radio(&self) -> &mineral_config::RadioConfig {
+        &self.inner.radio
+    }
+
     /// 下载音质(配置 `download.quality`)。
     pub(crate) fn download_quality(&self) -> BitRate {
         self.inner.download_quality
     }
 
+    /// 下载后写标签配置(`download.tags`)。
+    pub(crate) fn download_tags(&self) -> &mineral_config::DownloadTagsConfig {
+        &self.inner.download_tags
+    }
+
+    /// 响度归一化配置(`audio.normalization`)。
+    pub(crate) fn normalization(&self) -> &mineral_config::NormalizationConfig {
+        &self.inner.normalization
+    }
+
     /// 同步拦截 hook 软超时(配置 `script.hook_timeout_ms`)。
     pub(crate) fn hook_timeout(&self) -> Duration {
         self.inner.hook_timeout
@@ -429,7 +494,25 @@ impl PlayerCore {
     }
 
     /// 直通:client submit 任务(playlists/tracks 类 prefetch)。
+    ///
+    /// 「相似歌曲 / 相似艺人」虚拟歌单的详情在此绑上当前播放曲作种子(client 只认得不带种子的
+    /// 歌单库条目);没在播或在播曲不同源则原样提交,lane 给空歌单。
     pub fn submit_task(&self, kind: TaskKind, priority: Priority) -> TaskId {
+        let kind = match kind {
+            TaskKind::ChannelFetch(ChannelFetchKind::PlaylistDetail { id }) => {
+                let seeded = self
+                    .inner
+                    .state
+                    .lock()
+                    .current_song
+                    .as_ref()
+                    .and_then(|song| discover::seed_similar(&id, &song.id));
+                TaskKind::ChannelFetch(ChannelFetchKind::PlaylistDetail {
+                    id: seeded.unwrap_or(id),
+                })
+            }
+            other => other,
+        };
         self.inner.scheduler.submit(kind, priority).id
     }
 
@@ -482,7 +565,7 @@ impl PlayerCore {
         // → 直接本地播,跳过整条 SongUrl 网络路径。
         let local_hit = crate::resolve::resolve_local(
             &self.inner.media_cache,
-            self.inner.music_dir.as_deref(),
+            &self.inner.exports,
             song,
             self.inner.playback_quality,
         );
@@ -513,6 +596,7 @@ impl PlayerCore {
             st.current_lyrics = None;
             st.current_lyrics_song_id = None;
             st.prefetch_fired_for = None;
+            st.radio_fired_for = None;
             // 手动切歌 = 本预取窗口结束,窗口内的否决一并作废(queued 在下面按命中与否消费)。
             st.prefetch_vetoed.clear();
             st.bump_current();
@@ -561,18 +645,26 @@ impl PlayerCore {
             .last_seen_finished_seq
             .store(seq, Ordering::Relaxed);
 
+        // 歌词就近查找用的本地文件:本地副本,或预排好的本地曲库链接。
+        let audio_path = local_hit
+            .as_ref()
+            .map(|(p, _, _)| p.clone())
+            .or_else(|| cached_url.as_ref().and_then(crate::lyrics::local_file));
         if let Some((path, quality, _)) = local_hit {
             mineral_log::debug!(target: "player", song_id = song.id.as_str(), action = "local_hit", quality = quality.as_str(), origin = ?origin, "本地命中,跳过网络");
-            // 本地可完整读取:确保包络可用并推给 client(db 命中直推,缺失离线补算)。
-            self.ensure_envelope(song.id.clone(), path.clone());
             // 本地播也填 play_url(format / bitrate 按文件内容经 lofty 读出,见 resolve),transport 才显 fmt。
             let pu = crate::resolve::local_play_url(song, &path, quality);
             self.inner
                 .audio
-                .play(MediaUrl::Local(path), Vec::new(), pu.layout);
-            let mut st = self.inner.state.lock();
-            st.play_url = Some(pu);
-            st.bump_current();
+                .play(MediaUrl::Local(path.clone()), Vec::new(), pu.layout);
+            {
+                let mut st = self.inner.state.lock();
+                st.play_url = Some(pu);
+                st.bump_current();
+            }
+            // 本地可完整读取:确保包络可用并推给 client(db 命中直推,缺失离线补算),
+            // 顺带施加归一化增益(须在起播之后,起播会复位引擎增益槽)。
+            self.ensure_envelope(song.id.clone(), path);
         } else if let Some(pu) = cached_url {
             mineral_log::debug!(target: "player", song_id = song.id.as_str(), "using queued url");
             // 拦截桥:无脚本同步直走(play_capturing + 回填 play_url),有脚本异步裁决。
@@ -590,13 +682,11 @@ impl PlayerCore {
                 Priority::User,
             );
         }
-        mineral_log::debug!(target: "player", song_id = song.id.as_str(), source = ?song.source(), "submit Lyrics task");
-        self.inner.scheduler.submit(
-            TaskKind::ChannelFetch(ChannelFetchKind::Lyrics {
-                song_id: song.id.clone(),
-            }),
-            Priority::User,
-        );
+        // 本地曲库曲的文件路径要等取链回来才知道,歌词推迟到 PlayUrlReady 再找。
+        if audio_path.is_some() || song.source() != SourceKind::LOCAL {
+            self.fetch_lyrics(song, audio_path);
+        }
+        self.load_lyric_offset(&song.id);
         self.spawn_save_session();
     }
 
@@ -672,6 +762,8 @@ impl PlayerCore {
         self.inner
             .stats
             .play_ended(reason, i64::try_from(listen_ms).unwrap_or(i64::MAX));
+        // 播放计数变了:智能歌单(top / unplayed …)随之重算。
+        self.spawn_aggregate_refresh();
         let Some(channel) = self.channel_for(id.namespace()) else {
             return;
         };
@@ -97,6 +97,23 @@ impl PlayerCore {
         self.spawn_save_session();
     }
 
+    /// 插到队列的指定下标(越界即队尾),不动队列级 context 与当前曲。
+    ///
+    /// # Params:
+    ///   - `song`: 待插入的歌
+    ///   - `pos`: 插入下标
+    ///   - `context`: 该曲来源语境(落 per-song 覆盖:同插播)
+    pub fn queue_insert_at(&self, song: Song, pos: usize, context: mineral_stats::QueueContext) {
+        {
+            let mut st = self.inner.state.lock();
+            st.context_overrides.insert(song.id.qualified(), context);
+            crate::queue::insert_at(&mut st, pos, song);
+            st.invalidate_prefetch();
+        }
+        self.inner.audio.clear_next();
+        self.spawn_save_session();
+    }
+
     /// 队列结构编辑:删除 / 重排 / 批量清理 / 撤销。
     ///
     /// [`QueueOp::ApplyTransform`] 不走这里——它要跨线程跑脚本,由调用方拿到新序后走
@@ -300,7 +300,7 @@ fn core_with_events_stats(
         persist,
         media_cache: Arc::new(media_cache),
         http: None,
-        music_dir,
+        exports: crate::exports::Exports::unindexed(music_dir),
         download_progress: Arc::new(Mutex::new(DownloadProgress::default())),
         download_tx: tokio::sync::mpsc::unbounded_channel().0,
         download_pending: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
@@ -319,12 +319,22 @@ fn core_with_events_stats(
         last_session_save: Mutex::new(std::time::Instant::now()),
         playback_quality: *cfg.playback_quality(),
         envelope_params: cfg.envelope().clone(),
+        normalization: cfg.normalization().clone(),
         gapless_prefetch_ms: *cfg.daemon().gapless_prefetch_ms(),
+        // 默认未启用交叉淡化。
+        crossfade_ms: 0,
+        radio: cfg.radio().clone(),
+        eq: cfg.eq().clone(),
         prev_restart_threshold_ms: *cfg.daemon().prev_restart_threshold_ms(),
         player_tick_ms: *cfg.daemon().player_tick_ms(),
         session_save: Duration::from_secs(*cfg.daemon().session_save_secs()),
         download_quality: *cfg.download().quality(),
         download_speed_tick: Duration::from_millis(*cfg.daemon().download_speed_tick_ms()),
+        download_tags: cfg.download().tags().clone(),
+        // 不给用户目录与覆盖目录:只有本地副本旁的歌词参与查找,不读写开发机上的真实目录。
+        lyric_sources: crate::lyrics::LyricSources::new(
+            /*prefer_local*/ true, /*dir*/ None, /*overrides*/ None,
+        ),
         media_report_interval_ms: *cfg.daemon().report_interval_ms(),
         media_seek_threshold_ms: *cfg.daemon().seek_threshold_ms(),
         hook_timeout: Duration::from_millis(*cfg.hook_timeout_ms()),
@@ -333,6 +343,7 @@ fn core_with_events_stats(
             *cfg.favorites_backfill_chunk_size(),
             *cfg.favorites_backfill_max_concurrent(),
         ),
+        aggregate_refresh: std::sync::atomic::AtomicBool::new(false),
     });
     Ok(PlayerCore { inner })
 }
@@ -414,9 +425,12 @@ async fn wait_until(mut pred: impl FnMut() -> bool) -> bool {
 }
 
 mod envelope;
+mod eq;
 mod hooks;
 mod library;
+mod lyrics;
 mod play;
 mod queue;
+mod radio;
 mod session;
 mod ui;
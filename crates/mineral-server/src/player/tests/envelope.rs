@@ -74,10 +74,18 @@ async fn local_hit_computes_and_pushes_envelope() -> color_eyre::Result<()> {
         Some(envelope),
         "包络应落库,重启后可直取"
     );
+    assert!(
+        persist
+            .scope(SourceKind::NETEASE)
+            .get_loudness(&s.id, mineral_audio::LOUDNESS_VERSION)
+            .await?
+            .is_some(),
+        "同遍解码的响度应随包络落库"
+    );
     Ok(())
 }
 
-/// db 已有当前版本包络:开播直推缓存数据(以点数指纹区分),不重复解码。
+/// db 已有当前版本包络与响度:开播直推缓存数据(以点数指纹区分),不重复解码。
 #[tokio::test(flavor = "multi_thread")]
 async fn db_hit_pushes_cached_envelope_without_recompute() -> color_eyre::Result<()> {
     let d = tempfile::tempdir()?;
@@ -90,9 +98,17 @@ async fn db_hit_pushes_cached_envelope_without_recompute() -> color_eyre::Result
         points: vec![1, 2, 3],
         version: mineral_audio::ENVELOPE_VERSION,
     };
-    persist
-        .scope(SourceKind::NETEASE)
-        .put_envelope(&s.id, &fingerprint)
+    let scope = persist.scope(SourceKind::NETEASE);
+    scope.put_envelope(&s.id, &fingerprint).await?;
+    scope
+        .put_loudness(
+            &s.id,
+            &mineral_model::Loudness {
+                integrated_lufs: -14.0,
+                peak: 0.9,
+                version: mineral_audio::LOUDNESS_VERSION,
+            },
+        )
         .await?;
     let core = core_with_channels(
         vec![Arc::new(RecordingChannel::default())],
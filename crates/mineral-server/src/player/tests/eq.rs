@@ -0,0 +1,18 @@
+//! 均衡预设切换:已定义的预设与 `None` 旁路均可切换;未定义的预设名报错。
+
+use super::*;
+
+/// 内置预设与旁路可切换;未定义名报错。
+#[tokio::test]
+async fn set_eq_preset_rejects_unknown() -> color_eyre::Result<()> {
+    let core = core_with(Arc::default())?;
+    core.set_eq_preset(Some("bass_boost"))?;
+    core.set_eq_preset(None)?;
+    let err = core
+        .set_eq_preset(Some("no_such_preset"))
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("no_such_preset"), "应指明未定义的预设名:{err}");
+    Ok(())
+}
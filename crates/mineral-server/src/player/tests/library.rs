@@ -623,3 +623,72 @@ async fn sync_favorites_imports_remote_add_only_and_emits() -> color_eyre::Resul
     );
     Ok(())
 }
+
+/// 相似歌曲桩:`similar_songs` 的种子记进 `seeds`,其余走默认。
+#[derive(Default)]
+struct SimilarChannel {
+    /// 收到的种子曲。
+    seeds: Arc<Mutex<Vec<SongId>>>,
+}
+
+#[async_trait]
+impl MusicChannel for SimilarChannel {
+    fn source(&self) -> SourceKind {
+        SourceKind::NETEASE
+    }
+
+    fn caps(&self) -> ChannelCaps {
+        ChannelCaps::builder()
+            .searchable(Vec::new())
+            .playlist_edit(false)
+            .artist_sections(mineral_channel_core::ArtistSections::new(Vec::new()))
+            .build()
+    }
+
+    async fn songs_detail(&self, _ids: &[SongId]) -> ChannelResult<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn song_urls(&self, _ids: &[SongId], _q: BitRate) -> ChannelResult<Vec<PlayUrl>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn similar_songs(&self, id: &SongId) -> ChannelResult<Vec<Song>> {
+        self.seeds.lock().push(id.clone());
+        Ok(Vec::new())
+    }
+}
+
+/// client 提交不带种子的「相似歌曲」详情:server 按当前播放曲绑种子再下发给 channel。
+#[tokio::test]
+async fn similar_playlist_detail_is_seeded_with_current_song() -> color_eyre::Result<()> {
+    use mineral_channel_core::discover::DiscoverKind;
+    use mineral_task::{ChannelFetchKind, Priority, TaskKind};
+
+    let channel = SimilarChannel::default();
+    let seeds = Arc::clone(&channel.seeds);
+    let ch: Arc<dyn MusicChannel> = Arc::new(channel);
+    let core = core_with_channels(
+        vec![ch],
+        ServerStore::disabled(),
+        /*music_dir*/ None,
+        MediaCache::disabled(),
+    )?;
+    core.with_state(|st| st.current_song = Some(song("186016")));
+    let id = DiscoverKind::SimilarSongs
+        .playlist_id(SourceKind::NETEASE)
+        .ok_or_else(|| color_eyre::eyre::eyre!("相似歌曲应有虚拟歌单"))?;
+    core.submit_task(
+        TaskKind::ChannelFetch(ChannelFetchKind::PlaylistDetail { id }),
+        Priority::User,
+    );
+    assert!(
+        wait_until(|| !seeds.lock().is_empty()).await,
+        "channel 应收到相似歌曲请求"
+    );
+    assert_eq!(
+        seeds.lock().clone(),
+        vec![SongId::new(SourceKind::NETEASE, "186016")]
+    );
+    Ok(())
+}
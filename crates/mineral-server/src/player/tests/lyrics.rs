@@ -0,0 +1,123 @@
+//! 歌词解析层编排:本地副本旁有 sidecar 时直接用(mock channel 不供歌词,命中只能来自本地);
+//! per-song 歌词偏移的微调、落库与回放载入。
+
+use mineral_model::AudioFormat;
+
+use super::*;
+use crate::media_cache::library_relpath;
+
+/// 命中下载导出且同目录有同名 `.lrc` → 当前曲歌词取自 sidecar。
+#[tokio::test(flavor = "multi_thread")]
+async fn local_hit_uses_sidecar_lyrics() -> color_eyre::Result<()> {
+    let d = tempfile::tempdir()?;
+    let root = d.path().join("music");
+    let s = song("1");
+    let (subdir, file_name) = library_relpath(&s, BitRate::Lossless, Some(&AudioFormat::Wav));
+    let abs = root.join(&subdir).join(&file_name);
+    std::fs::create_dir_all(root.join(&subdir))?;
+    mineral_test::write_wav(&abs, &[0_i16; 8_000], 1, 8_000)?;
+    std::fs::write(abs.with_extension("lrc"), "[00:01.00]旁挂歌词")?;
+    let core = core_with_channels(
+        vec![Arc::new(RecordingChannel::default())],
+        ServerStore::disabled(),
+        Some(root),
+        MediaCache::disabled(),
+    )?;
+
+    core.play_song(
+        &s,
+        mineral_stats::PlayOrigin::Explicit,
+        mineral_stats::Actor::User,
+    );
+    let mut lyrics = None;
+    for _ in 0..200 {
+        lyrics = core
+            .sync(PlayerVersions::default())
+            .current
+            .and_then(|c| c.current_lyrics);
+        if lyrics.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let first = lyrics.and_then(|l| l.lines.first().map(|line| line.kind.text().into_owned()));
+    assert_eq!(first.as_deref(), Some("旁挂歌词"));
+    Ok(())
+}
+
+/// 轮询同步,直到当前歌的歌词偏移变成 `want`(载入 / 落库是异步的)。
+async fn await_offset(core: &PlayerCore, want: i64) -> i64 {
+    let mut got = 0;
+    for _ in 0..200 {
+        got = core
+            .sync(PlayerVersions::default())
+            .current
+            .map_or(0, |c| c.lyric_offset_ms);
+        if got == want {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    got
+}
+
+/// 歌词偏移按曲持久:微调即刻进同步段并落库;换歌归零,播回原曲自动载入。
+#[tokio::test(flavor = "multi_thread")]
+async fn lyric_offset_persists_per_song() -> color_eyre::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let persist = ServerStore::open(&dir.path().join("t.db")).await?;
+    let core = core_with_persist(Arc::new(Mutex::new(Vec::new())), persist.clone())?;
+    assert!(core.nudge_lyric_offset(50).is_err(), "无在播曲不可调");
+
+    let a = song("a");
+    core.play_song(
+        &a,
+        mineral_stats::PlayOrigin::Explicit,
+        mineral_stats::Actor::User,
+    );
+    core.nudge_lyric_offset(50)?;
+    assert_eq!(core.nudge_lyric_offset(100)?, 150);
+    assert_eq!(await_offset(&core, 150).await, 150);
+    let scope = persist.scope(a.id.namespace());
+    let mut stored = mineral_protocol::StoreValue::Nil;
+    for _ in 0..200 {
+        stored = scope.kv_get(&a.id, crate::lyric_offset::OFFSET_KEY).await?;
+        if stored == mineral_protocol::StoreValue::Int(150) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(stored, mineral_protocol::StoreValue::Int(150));
+
+    core.play_song(
+        &song("b"),
+        mineral_stats::PlayOrigin::Explicit,
+        mineral_stats::Actor::User,
+    );
+    assert_eq!(await_offset(&core, 0).await, 0, "别的歌不继承偏移");
+    core.play_song(
+        &a,
+        mineral_stats::PlayOrigin::Explicit,
+        mineral_stats::Actor::User,
+    );
+    assert_eq!(await_offset(&core, 150).await, 150, "播回原曲自动载入");
+    Ok(())
+}
+
+/// 没有歌词覆盖目录时存歌词覆盖报错,不往别处落盘。
+#[tokio::test(flavor = "multi_thread")]
+async fn save_lyrics_requires_dir() -> color_eyre::Result<()> {
+    let core = core_with_channels(
+        vec![Arc::new(RecordingChannel::default())],
+        ServerStore::disabled(),
+        None,
+        MediaCache::disabled(),
+    )?;
+    let s = song("1");
+    assert!(
+        core.save_lyrics(&s.id, "[00:01.00]一句".to_owned())
+            .await
+            .is_err()
+    );
+    Ok(())
+}
@@ -479,8 +479,12 @@ async fn downloads_then_plays_from_download() -> color_eyre::Result<()> {
         &dl_channel,
         &crate::download::DownloadEnv {
             http: &http,
-            music_dir: &music_dir,
+            exports: &crate::exports::Exports::unindexed(Some(music_dir.clone())),
             hooks: &crate::hook_bridge::HookGate::disabled(),
+            tags: &mineral_config::Config::defaults()?
+                .download()
+                .tags()
+                .clone(),
         },
         &s,
         BitRate::Lossless,
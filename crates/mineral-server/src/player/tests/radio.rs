@@ -0,0 +1,133 @@
+//! 电台续播:预排窗口内对队尾曲追加一批、每首队尾曲只续一次。
+
+use super::*;
+use mineral_channel_core::DiscoverKind;
+use mineral_test::with_duration;
+use pretty_assertions::assert_eq;
+
+/// 相似歌曲 mock:声明 [`DiscoverKind::SimilarSongs`],每次调用记一笔种子、返回固定候选。
+struct SimilarChannel {
+    /// 已收到的 `similar_songs` 种子(按调用序)。
+    seeds: Arc<Mutex<Vec<SongId>>>,
+
+    /// `similar_songs` 的候选。
+    candidates: Vec<Song>,
+}
+
+#[async_trait]
+impl MusicChannel for SimilarChannel {
+    fn source(&self) -> SourceKind {
+        SourceKind::NETEASE
+    }
+
+    fn caps(&self) -> ChannelCaps {
+        ChannelCaps::builder()
+            .searchable(Vec::new())
+            .playlist_edit(false)
+            .artist_sections(mineral_channel_core::ArtistSections::new(Vec::new()))
+            .discover(vec![DiscoverKind::SimilarSongs])
+            .build()
+    }
+
+    async fn search_songs(&self, _query: &str, _page: Page) -> ChannelResult<SearchHits<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn songs_detail(&self, _ids: &[SongId]) -> ChannelResult<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn song_urls(&self, _ids: &[SongId], _quality: BitRate) -> ChannelResult<Vec<PlayUrl>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn similar_songs(&self, id: &SongId) -> ChannelResult<Vec<Song>> {
+        self.seeds.lock().push(id.clone());
+        Ok(self.candidates.clone())
+    }
+}
+
+/// 一秒长的歌:元数据时长远小于默认预排窗口,在 0 位置即处于窗口内。
+fn short(id: &str) -> Song {
+    with_duration(song(id), 1_000)
+}
+
+/// 组一个启用电台(每批 2 首)的 [`PlayerCore`],注入相似歌曲 mock。
+fn core_with_radio(seeds: Arc<Mutex<Vec<SongId>>>) -> color_eyre::Result<PlayerCore> {
+    let channel = SimilarChannel {
+        seeds,
+        candidates: ["r1", "r2", "r3", "r4"].map(short).to_vec(),
+    };
+    let mut core = core_with_channels(
+        vec![Arc::new(channel)],
+        ServerStore::disabled(),
+        /*music_dir*/ None,
+        MediaCache::disabled(),
+    )?;
+    let inner = Arc::get_mut(&mut core.inner)
+        .ok_or_else(|| color_eyre::eyre::eyre!("刚组好的 core 不应有别的持有者"))?;
+    inner.radio = serde_json::from_value(serde_json::json!({ "enabled": true, "batch": 2 }))?;
+    Ok(core)
+}
+
+/// 把游标放到队尾那首(在播 = 队尾,顺序模式)。
+fn park_at_tail(core: &PlayerCore) {
+    core.with_state(|st| {
+        let last = st.queue.len().saturating_sub(1);
+        st.cursor = PlayCursor::InQueue(last);
+        st.current_song = st.queue.get(last).cloned();
+        st.play_mode = PlayMode::Sequential;
+    });
+}
+
+/// 预排窗口内、顺序模式播到队尾:追加一批;同一首队尾曲反复 tick 只续一次,播到新的队尾再续下一批。
+#[tokio::test]
+async fn extends_inside_window_once_per_tail_song() -> color_eyre::Result<()> {
+    let seeds = Arc::new(Mutex::new(Vec::new()));
+    let core = core_with_radio(Arc::clone(&seeds))?;
+    core.with_state(|st| st.queue = vec![short("a"), short("b")]);
+    park_at_tail(&core);
+
+    crate::gapless::check_prefetch(&core);
+    crate::gapless::check_prefetch(&core);
+    let queue_len = || core.with_state(|st| st.queue.len());
+    assert!(wait_until(|| queue_len() == 4).await, "应追加一批 2 首");
+    crate::gapless::check_prefetch(&core);
+    drain_spawned().await;
+    assert_eq!(
+        seeds.lock().clone(),
+        vec![song("b").id],
+        "同一首队尾曲只续一次"
+    );
+    assert_eq!(core.with_state(|st| ids(&st.queue).join(",")), "a,b,r1,r2");
+
+    park_at_tail(&core);
+    crate::gapless::check_prefetch(&core);
+    assert!(wait_until(|| queue_len() == 6).await, "新的队尾曲再续一批");
+    assert_eq!(
+        seeds.lock().clone(),
+        vec![song("b").id, song("r2").id],
+        "每首队尾曲各续一次"
+    );
+    assert_eq!(
+        core.with_state(|st| ids(&st.queue).join(",")),
+        "a,b,r1,r2,r3,r4",
+        "已在队列的候选不重复追加"
+    );
+    Ok(())
+}
+
+/// 窗口未开(离曲尾还远)不续播。
+#[tokio::test]
+async fn no_extend_outside_window() -> color_eyre::Result<()> {
+    let seeds = Arc::new(Mutex::new(Vec::new()));
+    let core = core_with_radio(Arc::clone(&seeds))?;
+    core.with_state(|st| st.queue = vec![with_duration(song("long"), 3_600_000)]);
+    park_at_tail(&core);
+
+    crate::gapless::check_prefetch(&core);
+    drain_spawned().await;
+    assert!(seeds.lock().is_empty(), "窗口外不应发起续播");
+    assert_eq!(core.with_state(|st| st.queue.len()), 1);
+    Ok(())
+}
@@ -5,8 +5,8 @@ mod nav;
 
 pub(crate) use edit::{apply, apply_order};
 pub(crate) use nav::{
-    QUEUE_CAP, advance_next, advance_prev, append, apply_play_mode, insert_next, next_in_queue,
-    next_index,
+    QUEUE_CAP, advance_next, advance_prev, append, apply_play_mode, insert_at, insert_next,
+    next_in_queue, next_index,
 };
 // shuffle 边界与 prev 预测只被 apply_play_mode / advance_prev 内部调用,导出仅供测试直接驱动。
 #[cfg(test)]
@@ -10,7 +10,7 @@ use crate::state::State;
 
 /// 队列硬上限:任何入队路径都不得让 `queue` 长度超过此值。
 ///
-/// 满时 [`append`] / [`insert_next`] 拒绝入队,[`super::PlayerCore::set_queue`] 截断到此长度。
+/// 满时 [`append`] / [`insert_next`] / [`insert_at`] 拒绝入队,[`super::PlayerCore::set_queue`] 截断到此长度。
 /// 取 9999 与序号显示上限一致(0-based 下标故最大 9998,四位数封顶)。
 pub(crate) const QUEUE_CAP: usize = 9999;
 
@@ -220,6 +220,46 @@ pub(crate) fn insert_next(st: &mut State, song: Song) {
     st.bump_queue();
 }
 
+/// 插到队列的指定下标(越界即队尾),游标跟着当前曲走。
+///
+/// shuffle 时原序里同样插在「前一条目」之后(插到最前则原序也插最前),退出 shuffle 时
+/// 这首仍挨着它在洗牌视图里的前一首。
+///
+/// # Params:
+///   - `st`: 播放状态
+///   - `pos`: 插入下标(插入后新歌所在的位置)
+///   - `song`: 待插入的歌
+pub(crate) fn insert_at(st: &mut State, pos: usize, song: Song) {
+    if at_capacity(st) {
+        mineral_log::debug!(target: "player", cap = QUEUE_CAP, "queue at capacity, insert dropped");
+        return;
+    }
+    let pos = pos.min(st.queue.len());
+    let prev = pos
+        .checked_sub(1)
+        .and_then(|i| st.queue.get(i))
+        .map(|s| s.id.clone());
+    if let Some(orig) = st.original_queue.as_mut() {
+        let orig_at = match prev {
+            Some(id) => orig
+                .iter()
+                .position(|s| s.id == id)
+                .map_or(orig.len(), |i| i + 1),
+            None => 0,
+        };
+        orig.insert(orig_at, song.clone());
+    }
+    st.queue.insert(pos, song);
+    st.cursor = match st.cursor {
+        PlayCursor::InQueue(cur) if pos <= cur => PlayCursor::InQueue(cur + 1),
+        PlayCursor::Detached { resume_at } if pos < resume_at => PlayCursor::Detached {
+            resume_at: resume_at + 1,
+        },
+        cursor => cursor,
+    };
+    st.bump_queue();
+}
+
 /// 「当前曲之后」在 `queue` 中的插入下标。
 ///
 /// 悬空时当前曲已不占下标,接续点本身就是「紧随当前曲之后」的位置。
@@ -270,7 +310,7 @@ mod tests {
     use mineral_protocol::{PlayCursor, PlayMode};
     use mineral_test::song;
 
-    use super::{QUEUE_CAP, advance_next, append, insert_next, next_index};
+    use super::{QUEUE_CAP, advance_next, append, insert_at, insert_next, next_index};
     use crate::state::State;
 
     /// 造一个 3 曲队列(a/b/c),当前在 a,指定模式。
@@ -385,6 +425,48 @@ mod tests {
         assert_eq!(st.queue.len(), 2, "未满时正常入队");
     }
 
+    /// 指定位插入:插在当前曲之前游标跟着后移,之后不动;悬空接续点同理;越界落队尾。
+    #[test]
+    fn insert_at_keeps_cursor_on_current() {
+        let mut st = state_with_mode(PlayMode::Sequential);
+        st.cursor = PlayCursor::InQueue(1);
+        insert_at(&mut st, 0, song("x"));
+        assert_eq!(st.cursor, PlayCursor::InQueue(2), "插在当前曲之前,游标后移");
+        insert_at(&mut st, 3, song("y"));
+        assert_eq!(st.cursor, PlayCursor::InQueue(2), "插在当前曲之后,游标不动");
+        insert_at(&mut st, 99, song("z"));
+        let ids = st.queue.iter().map(|s| s.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["x", "a", "b", "y", "c", "z"]);
+
+        let mut st = detached_state(PlayMode::Sequential);
+        st.cursor = PlayCursor::Detached { resume_at: 1 };
+        insert_at(&mut st, 1, song("x"));
+        assert_eq!(
+            st.cursor,
+            PlayCursor::Detached { resume_at: 1 },
+            "插在接续点上,新歌即下一首"
+        );
+        insert_at(&mut st, 0, song("y"));
+        assert_eq!(st.cursor, PlayCursor::Detached { resume_at: 2 });
+    }
+
+    /// shuffle 时原序里挨着前一条目插入;插到最前则原序也最前。
+    #[test]
+    fn insert_at_tracks_original_order() {
+        let mut st = state_with_mode(PlayMode::Shuffle);
+        st.queue = vec![song("c"), song("a"), song("b")];
+        st.original_queue = Some(vec![song("a"), song("b"), song("c")]);
+        insert_at(&mut st, 2, song("x"));
+        insert_at(&mut st, 0, song("y"));
+        let orig = st
+            .original_queue
+            .iter()
+            .flatten()
+            .map(|s| s.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(orig, ["y", "a", "x", "b", "c"]);
+    }
+
     /// 无否决时行为与既有语义一致(回归保护)。
     #[test]
     fn no_veto_keeps_existing_semantics() {
@@ -0,0 +1,310 @@
+//! 电台续播(配置 `queue.radio`):顺序模式播到队尾前,在 gapless 预排窗口内追加一批歌。
+//!
+//! 触发挂在 [`crate::gapless::check_prefetch`] 上:窗口已开、顺序模式下「下一首」为空,就对
+//! 当前曲发起一次续播(每曲一次)。候选先问当前曲所属源——相似歌曲优先、私人 FM 次之;源不
+//! 支持 / 取空 / 报错时回落本地:收藏与听过的歌为池,常听曲(stats)排最前,常听艺人与当前曲
+//! 艺人的歌次之,其余收藏垫底。
+//! 追加落地后下一 tick 的预排照常取链,衔接仍是 gapless,播放不断档。
+
+use mineral_channel_core::DiscoverKind;
+use mineral_model::{ArtistId, Song, SongId};
+use mineral_protocol::PlayMode;
+use rand::seq::SliceRandom;
+use rustc_hash::FxHashSet;
+
+use crate::player::PlayerCore;
+use crate::queue::{advance_next, next_index};
+
+/// 本地回落取常听艺人的榜长。
+const TOP_ARTISTS: i64 = 20;
+
+/// 本地回落取常听曲的榜长。
+const TOP_SONGS: i64 = 50;
+
+/// 常听曲 / 常听艺人的有效播放阈值(ms):听不足此值的播放不算「常听」。
+const MIN_LISTEN_MS: i64 = 30_000;
+
+/// 预排窗口已开时调用:顺序模式队列已到尾则为当前曲发起一次续播。
+///
+/// 未启用 / 非顺序模式 / 还有下一首 / 本曲已续过 → 无事发生。拉候选与追加在后台 task 里
+/// 做,不阻塞 player loop。
+pub(crate) fn check_extend(player: &PlayerCore) {
+    if !*player.radio().enabled() {
+        return;
+    }
+    let seed = player.with_state(|st| {
+        if st.play_mode != PlayMode::Sequential || next_index(st).is_some() {
+            return None;
+        }
+        let cur = st.current_song.clone()?;
+        if st.radio_fired_for.as_ref() == Some(&cur.id) {
+            return None;
+        }
+        st.radio_fired_for = Some(cur.id.clone());
+        Some(cur)
+    });
+    let Some(seed) = seed else {
+        return;
+    };
+    let batch = (*player.radio().batch()).max(1);
+    let finished_seq = player.last_seen_finished_seq();
+    let this = player.clone();
+    tokio::spawn(async move {
+        let exclude = this.with_state(|st| {
+            st.queue
+                .iter()
+                .map(|s| s.id.clone())
+                .chain(st.current_song.as_ref().map(|s| s.id.clone()))
+                .collect::<FxHashSet<SongId>>()
+        });
+        let mut songs = pick(remote_candidates(&this, &seed).await, &exclude, batch);
+        if songs.is_empty() {
+            songs = pick(local_candidates(&this, &seed).await, &exclude, batch);
+        }
+        mineral_log::info!(
+            target: "player",
+            seed = seed.id.as_str(),
+            count = songs.len(),
+            "radio extend"
+        );
+        if !songs.is_empty() {
+            land(&this, &seed.id, finished_seq, songs);
+        }
+    });
+}
+
+/// 续播曲落地:追加到队尾;期间已切歌(种子不再是在播曲)则整批作废。
+///
+/// 续播曲不属于任何实体语境,按曲落 `Unknown` 覆盖,不继承队列级 context(不污染歌单
+/// 归属)。触发时下一首为空、没有已排的预排,追加不改变既有预排,无需作废;下个 tick 的
+/// [`crate::gapless::check_prefetch`] 自会预排首曲。候选回来得太晚、种子曲已经播完停下
+/// (边界计数已前进)时,直接接播首曲。
+///
+/// # Params:
+///   - `player`: 播放核心
+///   - `seed`: 发起续播时的在播曲
+///   - `finished_seq`: 发起时的曲终计数
+///   - `songs`: 待追加的歌
+fn land(player: &PlayerCore, seed: &SongId, finished_seq: u64, songs: Vec<Song>) {
+    let landed = player.with_state(|st| {
+        if st.radio_fired_for.as_ref() != Some(seed) {
+            return false;
+        }
+        for song in songs {
+            st.context_overrides
+                .insert(song.id.qualified(), mineral_stats::QueueContext::Unknown);
+            crate::queue::append(st, song);
+        }
+        true
+    });
+    if !landed {
+        return;
+    }
+    player.spawn_save_session();
+    if player.last_seen_finished_seq() > finished_seq
+        && !player.audio_snapshot().playing
+        && let Some(next) = player.with_state(advance_next)
+    {
+        player.play_song(
+            &next,
+            mineral_stats::PlayOrigin::AutoAdvance,
+            mineral_stats::Actor::System,
+        );
+    }
+}
+
+/// 远端候选:当前曲所属源的相似歌曲,取不到再试私人 FM;源未声明则空。
+async fn remote_candidates(player: &PlayerCore, seed: &Song) -> Vec<Song> {
+    let Some(channel) = player.channel_for(seed.source()) else {
+        return Vec::new();
+    };
+    let caps = channel.caps();
+    let discover = caps.discover();
+    if discover.contains(&DiscoverKind::SimilarSongs) {
+        match channel.similar_songs(&seed.id).await {
+            Ok(songs) if !songs.is_empty() => return songs,
+            Ok(_) => {}
+            Err(e) => mineral_log::warn!(
+                target: "player",
+                seed = seed.id.as_str(),
+                error = mineral_log::chain(&e),
+                "电台取相似歌曲失败"
+            ),
+        }
+    }
+    if discover.contains(&DiscoverKind::PersonalFm) {
+        match channel.personal_fm().await {
+            Ok(songs) => return songs,
+            Err(e) => mineral_log::warn!(
+                target: "player",
+                error = mineral_log::chain(&e),
+                "电台取私人 FM 失败"
+            ),
+        }
+    }
+    Vec::new()
+}
+
+/// 本地候选:收藏 + stats 听过的歌为池,按 [`tier`] 分三组,组内打乱后依次拼接。
+///
+/// 各路查询失败只记 warn 当空处理,不拖垮其余来源。
+async fn local_candidates(player: &PlayerCore, seed: &Song) -> Vec<Song> {
+    let loved = player.persist().loved_songs().await.unwrap_or_else(|e| {
+        mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "电台读收藏失败");
+        Vec::new()
+    });
+    let store = player.inner.stats.store();
+    let known = store.known_songs().await.unwrap_or_else(|e| {
+        mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "电台读听过的歌失败");
+        Vec::new()
+    });
+    let song_options = mineral_stats::ReportOptions::builder()
+        .min_listen_ms(MIN_LISTEN_MS)
+        .top_limit(TOP_SONGS)
+        .build();
+    let top_songs = store
+        .top_songs(0..i64::MAX, mineral_stats::TopBy::Plays, &song_options)
+        .await
+        .unwrap_or_else(|e| {
+            mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "电台查常听曲失败");
+            Vec::new()
+        })
+        .into_iter()
+        .map(|t| t.song)
+        .collect::<FxHashSet<SongId>>();
+    let artist_options = mineral_stats::ReportOptions::builder()
+        .min_listen_ms(MIN_LISTEN_MS)
+        .top_limit(TOP_ARTISTS)
+        .build();
+    let top_artists = store
+        .top_artists(0..i64::MAX, mineral_stats::TopBy::Plays, &artist_options)
+        .await
+        .unwrap_or_else(|e| {
+            mineral_log::warn!(target: "player", error = mineral_log::chain(&e), "电台查常听艺人失败");
+            Vec::new()
+        });
+    let artists = top_artists
+        .into_iter()
+        .map(|a| a.artist)
+        .chain(seed.artists.iter().map(|a| a.id.clone()))
+        .collect::<FxHashSet<ArtistId>>();
+    let mut rng = rand::rng();
+    tier(loved, known, &top_songs, &artists)
+        .into_iter()
+        .flat_map(|mut group| {
+            group.shuffle(&mut rng);
+            group
+        })
+        .collect()
+}
+
+/// 本地候选池分三组(各自保持原序):常听曲、命中艺人的歌、其余收藏。
+///
+/// 池为收藏 ∪ 听过的歌,同 id 只留首个(收藏在前,其元数据更全)。听过但不常听、不命中
+/// 艺人、也没收藏的歌不入选。
+///
+/// # Params:
+///   - `loved`: 收藏曲
+///   - `known`: stats 维表里听过的歌
+///   - `top_songs`: 常听曲 id
+///   - `artists`: 常听艺人 + 当前曲艺人
+///
+/// # Return:
+///   `[常听曲, 命中艺人, 其余收藏]`。
+fn tier(
+    loved: Vec<Song>,
+    known: Vec<Song>,
+    top_songs: &FxHashSet<SongId>,
+    artists: &FxHashSet<ArtistId>,
+) -> [Vec<Song>; 3] {
+    let loved_ids = loved
+        .iter()
+        .map(|s| s.id.clone())
+        .collect::<FxHashSet<SongId>>();
+    let mut seen = FxHashSet::default();
+    let [mut heavy, mut favoured, mut rest] = [Vec::new(), Vec::new(), Vec::new()];
+    for song in loved.into_iter().chain(known) {
+        if !seen.insert(song.id.clone()) {
+            continue;
+        }
+        if top_songs.contains(&song.id) {
+            heavy.push(song);
+        } else if song.artists.iter().any(|a| artists.contains(&a.id)) {
+            favoured.push(song);
+        } else if loved_ids.contains(&song.id) {
+            rest.push(song);
+        }
+    }
+    [heavy, favoured, rest]
+}
+
+/// 候选 → 本次追加:去掉已在队列 / 在播的、不可播的、候选内重复的,截到 `batch` 首。
+///
+/// # Params:
+///   - `candidates`: 候选曲(按优先序)
+///   - `exclude`: 已在队列或在播的曲 id
+///   - `batch`: 追加上限
+///
+/// # Return:
+///   待追加的歌(保持候选序)。
+fn pick(candidates: Vec<Song>, exclude: &FxHashSet<SongId>, batch: usize) -> Vec<Song> {
+    let mut seen = FxHashSet::default();
+    candidates
+        .into_iter()
+        .filter(|s| !s.unavailable && !exclude.contains(&s.id) && seen.insert(s.id.clone()))
+        .take(batch)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{ArtistId, SourceKind};
+    use mineral_test::{song, with_artist};
+    use rustc_hash::FxHashSet;
+
+    use super::{pick, tier};
+
+    /// 已在队列的、不可播的、重复的一律剔除,按候选序截到 batch。
+    #[test]
+    fn pick_skips_queued_unavailable_and_duplicates() {
+        let mut gone = song("gone");
+        gone.unavailable = true;
+        let candidates = vec![song("a"), song("q"), gone, song("a"), song("b"), song("c")];
+        let exclude = [song("q").id].into_iter().collect::<FxHashSet<_>>();
+        let picked = pick(candidates, &exclude, 2);
+        let ids = picked.iter().map(|s| s.id.value()).collect::<Vec<_>>();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    /// 常听曲进首组、命中艺人的进次组、其余收藏垫底;只听过的冷门曲不入选,收藏与听过重复只留一份。
+    #[test]
+    fn tier_orders_top_songs_then_artists_then_loved() {
+        let loved = vec![
+            with_artist(song("miss"), "other"),
+            with_artist(song("hit"), "fav"),
+            song("heavy"),
+        ];
+        let known = vec![
+            song("heavy"),
+            with_artist(song("heard_hit"), "fav"),
+            song("heard_top"),
+            song("heard_cold"),
+        ];
+        let top_songs = [song("heavy").id, song("heard_top").id]
+            .into_iter()
+            .collect::<FxHashSet<_>>();
+        let artists = [ArtistId::new(SourceKind::NETEASE, "fav")]
+            .into_iter()
+            .collect::<FxHashSet<_>>();
+        let ids = |songs: &[mineral_model::Song]| {
+            songs
+                .iter()
+                .map(|s| s.id.value().to_owned())
+                .collect::<Vec<_>>()
+        };
+        let [heavy, favoured, rest] = tier(loved, known, &top_songs, &artists);
+        assert_eq!(ids(&heavy), ["heavy", "heard_top"]);
+        assert_eq!(ids(&favoured), ["hit", "heard_hit"]);
+        assert_eq!(ids(&rest), ["miss"]);
+    }
+}
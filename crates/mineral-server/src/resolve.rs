@@ -5,10 +5,9 @@
 //! 再探下载导出库,第一个命中即「本地最高可用音质」(故 lossless 也能喂给较低请求播放,同音质时
 //! 优先缓存)。
 //!
-//! 下载导出**不过索引**:导出目录(`~/Music/mineral`)就是权威——按
-//! `<source>/<quality>/<album>/<title>.<ext>` 重算专辑目录并 stat,文件在即可播。历史下载、换机
-//! 拷库、手动放进去的文件一律可见,不受任何索引漂移影响。代价是命名即身份:同源同专辑同名的两首歌
-//! 落到同一路径(下载侧按文件存在幂等,不产生 ` (N)` 副本),概率极低。
+//! 下载导出按用户路径模板落盘,无法从歌曲反推,故先查导出索引(见 [`crate::exports`]);
+//! 索引之外再按默认布局 `<source>/<quality>/<album>/<title>.<ext>` 重算专辑目录并 stat
+//! ([`probe_export`]),让索引建立前的历史下载、换机拷库、手动放进去的文件仍然可见。
 
 use std::path::{Path, PathBuf};
 
@@ -17,13 +16,14 @@ use lofty::probe::Probe;
 use mineral_model::{AudioFormat, BitRate, MediaUrl, PlayUrl, Song};
 use mineral_protocol::PlaybackOrigin;
 
+use crate::exports::Exports;
 use crate::media_cache::{MediaCache, library_dir_and_stem};
 
 /// 把 `song` 解析到本地音频文件(音质 `>= want` 的最高可用副本)。
 ///
 /// # Params:
 ///   - `media_cache`: 音频本体缓存(LRU,id 索引)
-///   - `download_root`: 下载导出根目录(如 `~/Music/mineral`);`None` = 下载不可用
+///   - `exports`: 下载导出库(根目录不可用时只看缓存)
 ///   - `song`: 待播歌曲
 ///   - `want`: 期望的最低音质
 ///
@@ -33,7 +33,7 @@ use crate::media_cache::{MediaCache, library_dir_and_stem};
 ///   `None`(走远端)。
 pub(crate) fn resolve_local(
     media_cache: &MediaCache,
-    download_root: Option<&Path>,
+    exports: &Exports,
     song: &Song,
     want: BitRate,
 ) -> Option<(PathBuf, BitRate, PlaybackOrigin)> {
@@ -45,20 +45,17 @@ pub(crate) fn resolve_local(
         if let Some(path) = media_cache.get(&song.id, q) {
             return Some((path, q, PlaybackOrigin::Cache));
         }
-        if let Some(root) = download_root
-            && let Some(path) = probe_export(root, song, q)
-        {
+        if let Some(path) = exports.find(song, q) {
             return Some((path, q, PlaybackOrigin::Download));
         }
     }
     None
 }
 
-/// 在下载导出库里找 `song` 该音质的文件(文件系统即真相)。
+/// 按默认布局在下载导出库里找 `song` 该音质的文件(索引之外的兜底反查,见 [`Exports::find`])。
 ///
 /// 按 `<source>/<quality>/<album>` 定位专辑目录,在其中找「去扩展名后与本曲标题(已 sanitize)
 /// 相等、且扩展名是已知音频格式」的文件,命中返回绝对路径。目录不存在 / 无匹配返回 `None`。
-/// 同时供下载侧做「已下载」幂等判断(见 [`crate::download`])。
 ///
 /// # Params:
 ///   - `root`: 下载导出根目录
@@ -248,6 +245,7 @@ mod tests {
     use mineral_protocol::PlaybackOrigin;
 
     use super::{local_play_url, probe_export, resolve_local};
+    use crate::exports::Exports;
     use crate::media_cache::{MediaCache, library_relpath};
 
     fn song(id: &str, name: &str, album: Option<&str>) -> Song {
@@ -318,8 +316,12 @@ mod tests {
         )
         .await?;
 
-        let Some((path, q, origin)) = resolve_local(&cache, Some(&root), &s, BitRate::Exhigh)
-        else {
+        let Some((path, q, origin)) = resolve_local(
+            &cache,
+            &Exports::unindexed(Some(root.clone())),
+            &s,
+            BitRate::Exhigh,
+        ) else {
             return Err(color_eyre::eyre::eyre!("应命中 cache"));
         };
         assert_eq!(q, BitRate::Exhigh);
@@ -337,8 +339,12 @@ mod tests {
         let s = song("1", "晴天", Some("叶惠美"));
         put_download(&root, &s, BitRate::Lossless, &AudioFormat::Flac, b"FLAC")?;
 
-        let Some((path, q, origin)) = resolve_local(&cache, Some(&root), &s, BitRate::Exhigh)
-        else {
+        let Some((path, q, origin)) = resolve_local(
+            &cache,
+            &Exports::unindexed(Some(root.clone())),
+            &s,
+            BitRate::Exhigh,
+        ) else {
             return Err(color_eyre::eyre::eyre!("应命中下载导出文件"));
         };
         assert_eq!(q, BitRate::Lossless, "下载是 Lossless,>= Exhigh 应命中");
@@ -367,8 +373,12 @@ mod tests {
         .await?;
         put_download(&root, &s, BitRate::Exhigh, &AudioFormat::Mp3, b"FROM_DL")?;
 
-        let Some((path, _, origin)) = resolve_local(&cache, Some(&root), &s, BitRate::Exhigh)
-        else {
+        let Some((path, _, origin)) = resolve_local(
+            &cache,
+            &Exports::unindexed(Some(root.clone())),
+            &s,
+            BitRate::Exhigh,
+        ) else {
             return Err(color_eyre::eyre::eyre!("应命中"));
         };
         assert_eq!(origin, PlaybackOrigin::Cache, "同音质应取 cache");
@@ -395,8 +405,12 @@ mod tests {
         .await?;
         put_download(&root, &s, BitRate::Lossless, &AudioFormat::Flac, b"FROM_DL")?;
 
-        let Some((path, q, origin)) = resolve_local(&cache, Some(&root), &s, BitRate::Exhigh)
-        else {
+        let Some((path, q, origin)) = resolve_local(
+            &cache,
+            &Exports::unindexed(Some(root.clone())),
+            &s,
+            BitRate::Exhigh,
+        ) else {
             return Err(color_eyre::eyre::eyre!("应命中"));
         };
         assert_eq!(q, BitRate::Lossless);
@@ -416,7 +430,13 @@ mod tests {
         put_download(&root, &s, BitRate::Standard, &AudioFormat::Mp3, b"LOW")?;
 
         assert!(
-            resolve_local(&cache, Some(&root), &s, BitRate::Exhigh).is_none(),
+            resolve_local(
+                &cache,
+                &Exports::unindexed(Some(root.clone())),
+                &s,
+                BitRate::Exhigh
+            )
+            .is_none(),
             "低于 want 的本地副本不应命中"
         );
         Ok(())
@@ -433,7 +453,13 @@ mod tests {
         std::fs::remove_file(&abs)?;
 
         assert!(
-            resolve_local(&cache, Some(&root), &s, BitRate::Exhigh).is_none(),
+            resolve_local(
+                &cache,
+                &Exports::unindexed(Some(root.clone())),
+                &s,
+                BitRate::Exhigh
+            )
+            .is_none(),
             "文件没了应当 miss"
         );
         Ok(())
@@ -451,18 +477,24 @@ mod tests {
         std::fs::write(dir.join("晴天.part-dl"), b"HALF")?;
 
         assert!(
-            resolve_local(&cache, Some(&root), &s, BitRate::Exhigh).is_none(),
+            resolve_local(
+                &cache,
+                &Exports::unindexed(Some(root.clone())),
+                &s,
+                BitRate::Exhigh
+            )
+            .is_none(),
             ".part-dl 残件不应命中"
         );
         Ok(())
     }
 
-    /// `download_root` 为 `None`(下载不可用)→ 只看 cache。
+    /// 导出根目录不可用(下载不可用)→ 只看 cache。
     #[tokio::test]
     async fn no_download_root_uses_cache_only() -> color_eyre::Result<()> {
         let cache = MediaCache::disabled();
         let s = song("1", "晴天", Some("叶惠美"));
-        assert!(resolve_local(&cache, /*download_root*/ None, &s, BitRate::Exhigh).is_none());
+        assert!(resolve_local(&cache, &Exports::unindexed(None), &s, BitRate::Exhigh).is_none());
         Ok(())
     }
 
@@ -484,6 +484,17 @@ fn apply_cmd(player: &PlayerCore, cmd: ScriptCmd, spawns: &SpawnTable) {
             player.seek_playback(target_ms, mineral_stats::Actor::Script);
         }
         ScriptCmd::SetVolume(pct) => player.set_playback_volume(pct, mineral_stats::Actor::Script),
+        ScriptCmd::SetEq(preset) => {
+            if let Err(e) = player.set_eq_preset(preset.as_deref()) {
+                mineral_log::warn!(target: "script", error = mineral_log::chain(&e), "set_eq 失败");
+            }
+        }
+        ScriptCmd::SetSpeed { speed, mode } => player.audio().set_speed(speed, mode),
+        ScriptCmd::NudgeLyricOffset(delta_ms) => {
+            if let Err(e) = player.nudge_lyric_offset(delta_ms) {
+                mineral_log::warn!(target: "script", error = mineral_log::chain(&e), "nudge_lyric_offset 失败");
+            }
+        }
         ScriptCmd::SetMode(mode) => player.set_play_mode(mode, mineral_stats::Actor::Script),
         ScriptCmd::Play(id) => {
             let song = player.with_state(|st| st.queue.iter().find(|s| s.id == id).cloned());
@@ -630,7 +641,9 @@ fn apply_cmd(player: &PlayerCore, cmd: ScriptCmd, spawns: &SpawnTable) {
                     resolve_err(&player, query, &e);
                     return;
                 };
-                match channel.playlist_detail(&playlist).await {
+                match mineral_channel_core::discover::resolve_playlist(channel.as_ref(), &playlist)
+                    .await
+                {
                     Ok(pl) => resolve_ok(&player, query, ResolveValue::Songs(pl.songs)),
                     Err(e) => {
                         resolve_err(&player, query, &color_eyre::eyre::eyre!("{e}"));
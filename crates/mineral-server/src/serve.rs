@@ -1,6 +1,6 @@
-//! IPC accept loop + 单 connection 的 [`Frame`] 管线:握手守门 → 读循环并发
-//! dispatch → **唯一 writer** 串行下发(Response 与订阅过滤后的 Event 汇同一条
-//! mpsc,杜绝并发写 sink)。
+//! IPC accept loop + 单 connection 的 [`Frame`] 管线:按首字节选 codec(bincode 帧 /
+//! JSON 行)→ 握手守门 → 读循环并发 dispatch → **唯一 writer** 串行下发(Response 与
+//! 订阅过滤后的 Event 汇同一条 mpsc,杜绝并发写 sink)。
 //!
 //! 多 client:每条 connection 独立 task / writer / event 订阅,连接间无共享
 //! 可变通道;在线身份经 [`ConnRegistry`] 登记,断开(含 panic unwind)即移除。
@@ -9,12 +9,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use color_eyre::eyre::WrapErr;
-use futures_util::stream::{SplitSink, SplitStream};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{Sink, SinkExt, Stream, StreamExt, future};
 use mineral_protocol::{
-    ClientInfo, Event, Frame, Framed, RejectReason, Request, Response, ServerHello, Subscription,
-    decode, encode, framed, recv, send,
+    ClientInfo, Event, Frame, Framed, JsonFramed, JsonHandshake, JsonHello, RejectReason, Request,
+    Response, ServerHello, Subscription, decode, decode_json_frame, encode, encode_json,
+    framed_with_prefix, is_json_lead, json_framed_with_prefix, recv, recv_json, send, send_json,
 };
+use tokio::io::AsyncReadExt;
 use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::{Notify, broadcast, mpsc};
 
@@ -168,40 +169,115 @@ impl Drop for ConnGuard {
     }
 }
 
-/// 接管已 accept 的 connection:握手守门 → split 出读写两半 → 起唯一 writer 与
-/// 推送泵 → 读循环到 client EOF / 出错,随后收尾(泵停、writer 排空退出)。
+/// 单连接会话管线共用的上下文(两种 codec 共享)。
+struct ConnCtx<'a> {
+    /// per-conn handle。
+    client: &'a ClientHandle,
+
+    /// 连接注册表(握手后补登身份)。
+    registry: &'a ConnRegistry,
+
+    /// 本连接 id。
+    conn_id: u64,
+
+    /// daemon 级关停通知。
+    shutdown: &'a Arc<Notify>,
+}
+
+/// 接管已 accept 的 connection:窥探首字节选 codec(bincode 帧 / JSON 行)→ 握手守门
+/// → 进同一条会话管线。
 async fn handle_connection(
-    stream: UnixStream,
+    mut stream: UnixStream,
     client: &ClientHandle,
     registry: &ConnRegistry,
     conn_id: u64,
     events: &broadcast::Sender<Event>,
     shutdown: &Arc<Notify>,
 ) -> color_eyre::Result<()> {
-    let mut conn = framed(stream);
+    // 读走的首字节经 `*_with_prefix` 垫回,选定的 codec 仍从头解析。
+    let mut lead = [0_u8; 1];
+    if stream.read(&mut lead).await.wrap_err("等待首字节")? == 0 {
+        return Ok(()); // 连上没说话就走(探活类),不算错。
+    }
     // 在回 Hello 之前就订阅 hub:保证「client 收到 Hello」之后产生的事件零窗口
     // 不丢(握手期间的事件缓冲在 receiver 里,由 pump 按订阅集过滤)。
     let events_rx = events.subscribe();
-    let Some(info) = handshake(&mut conn, client).await? else {
-        return Ok(());
+    let ctx = ConnCtx {
+        client,
+        registry,
+        conn_id,
+        shutdown,
     };
+    let [first] = lead;
+    if is_json_lead(first) {
+        let mut conn = json_framed_with_prefix(stream, &lead);
+        let Some(info) = json_handshake(&mut conn, client).await? else {
+            return Ok(());
+        };
+        let (sink, lines) = conn.split();
+        // 空行跳过(shell `echo` 拼请求时常见),其余每行一个 Frame;解不出的行回
+        // 错误应答、会话照常(手拼 JSON 写错一行不该断连)。
+        let frames = lines
+            .filter(|line| future::ready(!matches!(line, Ok(l) if l.trim().is_empty())))
+            .map(|line| {
+                line.wrap_err("json line recv")
+                    .map(|l| decode_json_frame(&l))
+            });
+        run_session(info, sink, encode_json::<Frame>, frames, events_rx, &ctx).await
+    } else {
+        let mut conn = framed_with_prefix(stream, &lead);
+        let Some(info) = handshake(&mut conn, client).await? else {
+            return Ok(());
+        };
+        let (sink, bytes) = conn.split();
+        let frames = bytes.map(|b| {
+            b.wrap_err("framed recv")
+                .and_then(|b| decode::<Frame>(&b))
+                .map(Ok)
+        });
+        run_session(info, sink, encode::<Frame>, frames, events_rx, &ctx).await
+    }
+}
+
+/// 握手后的会话管线:起唯一 writer 与推送泵 → 读循环到 client EOF / 出错,随后收尾
+/// (泵停、writer 排空退出)。codec 只体现在 `sink` / `encode` / `frames` 三处。
+///
+/// # Params:
+///   - `info`: 握手通过的 client 身份
+///   - `sink`: 连接写半边
+///   - `encode`: [`Frame`] → 写半边的单帧载荷
+///   - `frames`: 连接读半边(已解码成 [`Frame`];`Err` 为解不出的帧该回的错误应答)
+///   - `events_rx`: 握手前订阅好的事件 hub
+///   - `ctx`: 连接上下文
+async fn run_session<Si, M>(
+    info: ClientInfo,
+    sink: Si,
+    encode: fn(&Frame) -> color_eyre::Result<M>,
+    frames: impl Stream<Item = color_eyre::Result<Result<Frame, Box<Frame>>>>,
+    events_rx: broadcast::Receiver<Event>,
+    ctx: &ConnCtx<'_>,
+) -> color_eyre::Result<()>
+where
+    Si: Sink<M> + Unpin + Send + 'static,
+    Si::Error: std::fmt::Display,
+    M: Send + 'static,
+{
     let subscriptions = info.subscriptions.clone();
-    registry.set_identity(conn_id, info);
-    let (sink, stream) = conn.split();
+    ctx.registry.set_identity(ctx.conn_id, info);
     // Response 与 Event 汇同一条 mpsc → 唯一 writer 串行写 sink,杜绝并发写。
     let (out_tx, out_rx) = mpsc::unbounded_channel::<Frame>();
-    tokio::spawn(write_loop(sink, out_rx));
+    tokio::spawn(write_loop(sink, out_rx, encode));
     // 订阅类别的当前状态先重放,再进实时流(pump 还没起,重放帧必然先入队;
     // events_rx 在握手前已订阅,期间的变更不丢——快照与缓冲间可能重复一帧,
     // client 侧 last-wins 幂等)。重放内容按类别见 [`ClientHandle::replay_frames`]。
-    for ev in client.replay_frames(&subscriptions).await {
+    for ev in ctx.client.replay_frames(&subscriptions).await {
         let _ = out_tx.send(Frame::Event(ev));
     }
     let pump = tokio::spawn(event_pump(events_rx, subscriptions, out_tx.clone()));
-    let result = read_loop(stream, client, &out_tx, shutdown).await;
+    let result = read_loop(frames, ctx.client, &out_tx, ctx.shutdown).await;
     // client 断开:清本连接的终端上报与 PCM 游标(全部离线时 `terminal`
     // 属性回 None,脚本可感知离线)。
-    client.connection_closed();
+    ctx.client.connection_closed();
     // 收尾:推送泵立停、放掉本端 out_tx。writer **不 await**——飞行中的 dispatch
     // task(如 love 的远端打点,可达数秒)还持着 out_tx clone,等它们结束才轮到
     // writer 退出;把 busy 的释放拖到慢 dispatch 之后,会让紧接着重连的 client
@@ -249,21 +325,54 @@ async fn handshake(
     Ok(Some(info))
 }
 
-/// 唯一 writer:把汇聚的 [`Frame`] 串行写进 sink。写失败即退出(连接已断,
-/// 读循环也会随之退出);编码失败跳过该帧(单帧损坏不拖垮连接)。
-async fn write_loop(
-    mut sink: SplitSink<Framed<UnixStream>, bytes::Bytes>,
+/// JSON 行连接的握手守门:期待首行 [`JsonHandshake`],schema 不符回拒绝;通过回 accept。
+///
+/// # Return:
+///   同 [`handshake`]。
+async fn json_handshake(
+    conn: &mut JsonFramed<UnixStream>,
+    client: &ClientHandle,
+) -> color_eyre::Result<Option<ClientInfo>> {
+    let Some(info) = recv_json::<JsonHandshake, _>(conn)
+        .await
+        .wrap_err("等待 JSON 握手行")?
+    else {
+        return Ok(None);
+    };
+    if !info.schema_matches() {
+        mineral_log::warn!(
+            target: "ipc",
+            client_schema = info.schema,
+            "JSON client schema 不匹配,拒绝连接"
+        );
+        client.record_connection_reject(mineral_stats::RejectReason::VersionMismatch);
+        send_json(conn, &JsonHello::reject(RejectReason::VersionMismatch)).await?;
+        return Ok(None);
+    }
+    send_json(conn, &JsonHello::accept()).await?;
+    mineral_log::debug!(target: "ipc", client = %info.name, subscriptions = ?info.subscriptions, "json handshake accepted");
+    Ok(Some(info.into_client_info()))
+}
+
+/// 唯一 writer:把汇聚的 [`Frame`] 经 `encode` 串行写进 sink。写失败即退出(连接
+/// 已断,读循环也会随之退出);编码失败跳过该帧(单帧损坏不拖垮连接)。
+async fn write_loop<Si, M>(
+    mut sink: Si,
     mut rx: mpsc::UnboundedReceiver<Frame>,
-) {
+    encode: fn(&Frame) -> color_eyre::Result<M>,
+) where
+    Si: Sink<M> + Unpin,
+    Si::Error: std::fmt::Display,
+{
     while let Some(frame) = rx.recv().await {
-        let bytes = match encode(&frame) {
-            Ok(b) => b,
+        let payload = match encode(&frame) {
+            Ok(p) => p,
             Err(e) => {
                 mineral_log::warn!(target: "ipc", error = mineral_log::chain(&e), "frame 编码失败,跳过");
                 continue;
             }
         };
-        if let Err(e) = sink.send(bytes).await {
+        if let Err(e) = sink.send(payload).await {
             mineral_log::warn!(target: "ipc", error = mineral_log::chain(&e), "写连接失败,writer 退出");
             return;
         }
@@ -297,17 +406,23 @@ async fn event_pump(
 }
 
 /// 读循环:每条 [`Frame::Request`] spawn 并发 dispatch,应答带原 id 汇入 writer;
-/// 其余帧(重复握手等)warn 后忽略。client EOF 返回 `Ok`。
+/// 解不出的帧把错误应答交 writer 后继续读;其余帧(重复握手等)warn 后忽略。
+/// client EOF 返回 `Ok`。
 async fn read_loop(
-    mut stream: SplitStream<Framed<UnixStream>>,
+    frames: impl Stream<Item = color_eyre::Result<Result<Frame, Box<Frame>>>>,
     client: &ClientHandle,
     out: &mpsc::UnboundedSender<Frame>,
     shutdown: &Arc<Notify>,
 ) -> color_eyre::Result<()> {
-    while let Some(frame) = stream.next().await {
-        let bytes = frame.wrap_err("framed recv")?;
-        match decode::<Frame>(&bytes)? {
-            Frame::Request { id, req } => {
+    let mut frames = std::pin::pin!(frames);
+    while let Some(frame) = frames.next().await {
+        match frame? {
+            Err(reply) => {
+                mineral_log::debug!(target: "ipc", reply = ?reply, "帧解码失败,回错误应答");
+                // send 失败 = 连接已收尾,应答丢弃即可。
+                let _ = out.send(*reply);
+            }
+            Ok(Frame::Request { id, req }) => {
                 let client = client.clone();
                 let out = out.clone();
                 let shutdown = Arc::clone(shutdown);
@@ -327,7 +442,7 @@ async fn read_loop(
                     }
                 });
             }
-            other => {
+            Ok(other) => {
                 mineral_log::warn!(target: "ipc", frame = ?other, "忽略非 Request 帧");
             }
         }
@@ -370,7 +485,23 @@ async fn dispatch(req: Request, client: &ClientHandle) -> Response {
             client.set_volume(pct);
             Response::Ok
         }
+        Request::SetSpeed { speed, mode } => {
+            client.set_speed(speed, mode);
+            Response::Ok
+        }
+        Request::SetEq(preset) => match client.set_eq(preset.as_deref()) {
+            Ok(()) => Response::Ok,
+            Err(e) => Response::Error(mineral_log::chain(&e)),
+        },
         Request::AudioSnapshot => Response::AudioSnapshot(client.audio_snapshot()),
+        Request::AudioDevices => match client.audio_devices() {
+            Ok(devices) => Response::AudioDevices(devices),
+            Err(e) => Response::Error(mineral_log::chain(&e)),
+        },
+        Request::SetAudioDevice(name) => match client.set_audio_device(name) {
+            Ok(()) => Response::Ok,
+            Err(e) => Response::Error(mineral_log::chain(&e)),
+        },
         Request::SubmitTask(kind, priority) => Response::TaskId(client.submit_task(kind, priority)),
         Request::CancelTasks(filter) => {
             client.cancel_tasks(filter);
@@ -411,6 +542,16 @@ async fn dispatch(req: Request, client: &ClientHandle) -> Response {
             client.next_song();
             Response::Ok
         }
+        Request::NudgeLyricOffset(delta) => match client.adjust_lyric_offset(delta) {
+            Ok(_) => Response::Ok,
+            Err(e) => Response::Error(mineral_log::chain(&e)),
+        },
+        Request::SaveLyrics { song_id, lrc } => {
+            match client.save_lyrics_async(&song_id, lrc).await {
+                Ok(()) => Response::Ok,
+                Err(e) => Response::Error(mineral_log::chain(&e)),
+            }
+        }
         Request::PlayerSync(known) => Response::PlayerSync(Box::new(client.player_sync(known))),
         Request::PullPcm(n) => {
             let (samples, sample_rate) = client.pull_pcm(n);
@@ -496,6 +637,10 @@ fn req_log_name(req: &Request) -> Option<&'static str> {
         Request::Stop => Some("Stop"),
         Request::Seek(_) => Some("Seek"),
         Request::SetVolume(_) => Some("SetVolume"),
+        Request::SetEq(_) => Some("SetEq"),
+        Request::SetSpeed { .. } => Some("SetSpeed"),
+        Request::AudioDevices => Some("AudioDevices"),
+        Request::SetAudioDevice(_) => Some("SetAudioDevice"),
         Request::SubmitTask(..) => Some("SubmitTask"),
         Request::CancelTasks(_) => Some("CancelTasks"),
         Request::PlaySong(_) => Some("PlaySong"),
@@ -507,6 +652,8 @@ fn req_log_name(req: &Request) -> Option<&'static str> {
         Request::CyclePlayMode => Some("CyclePlayMode"),
         Request::PrevOrRestart => Some("PrevOrRestart"),
         Request::NextSong => Some("NextSong"),
+        Request::NudgeLyricOffset(_) => Some("NudgeLyricOffset"),
+        Request::SaveLyrics { .. } => Some("SaveLyrics"),
         Request::DaemonInfo => Some("DaemonInfo"),
         Request::InvokeAction { .. } => Some("InvokeAction"),
         Request::RenderCopyTemplate { .. } => Some("RenderCopyTemplate"),
@@ -8,7 +8,7 @@ use mineral_channel_core::MusicChannel;
 use mineral_persist::ServerStore;
 use mineral_protocol::{Event, PlayMode};
 use mineral_task::Scheduler;
-use tokio::net::UnixListener;
+use tokio::net::{TcpListener, UnixListener};
 use tokio::sync::{Notify, broadcast};
 
 use crate::client::ClientHandle;
@@ -67,6 +67,7 @@ impl Server {
         let (audio, spectrum_tap) = AudioHandle::spawn(audio_mode, config.engine().clone())?;
         mineral_log::debug!(target: "server", "audio engine ready");
         let media_cache = open_media_cache(&persist, *config.audio_cache_capacity()).await;
+        let exports = crate::exports::Exports::open(&persist, config.download()).await;
         // 容量按「单 client + advisory 语义」取小;积压时 event_pump 收 Lagged 丢弃。
         let (events, _) = broadcast::channel::<Event>(/*capacity*/ 256);
         let notify = crate::notify::Notifier::new(events.clone(), script);
@@ -75,7 +76,10 @@ impl Server {
             scheduler,
             channels,
             persist,
-            media_cache,
+            crate::player::LocalCopies {
+                media_cache,
+                exports,
+            },
             crate::player::SpawnConfig {
                 slices: &config,
                 tree: config_tree,
@@ -89,7 +93,10 @@ impl Server {
         // in-proc TUI 传 disabled recorder → 此调用静默 no-op,不污染。
         let audio_backend = match player.audio_snapshot().backend {
             mineral_audio::AudioBackend::Device => mineral_stats::AudioBackend::Device,
-            mineral_audio::AudioBackend::Null => mineral_stats::AudioBackend::Null,
+            // 文件出口同样不出声,按空跑记。
+            mineral_audio::AudioBackend::Null | mineral_audio::AudioBackend::File => {
+                mineral_stats::AudioBackend::Null
+            }
         };
         player
             .inner
@@ -139,12 +146,18 @@ impl Server {
         )
     }
 
-    /// 接入系统媒体服务(Linux MPRIS):上报当前播放、响应媒体键 / 桌面控件。
+    /// 播放核心(测试直接断言服务端状态用)。
+    #[cfg(test)]
+    pub(crate) fn player(&self) -> PlayerCore {
+        self.player.clone()
+    }
+
+    /// 接入系统媒体服务(Linux MPRIS):上报当前播放、队列与歌单库,响应媒体键 / 桌面控件。
     ///
     /// 仅 daemon 模式调用 —— 控制的是常驻播放。注册失败(无 D-Bus session 等)
     /// 返回 `Err`,调用方应降级而非中止 daemon。
     pub fn start_media_service(&self) -> color_eyre::Result<()> {
-        crate::media::start(self.player.clone())
+        crate::media::start(self.player.clone(), self.client(), self.events.clone())
     }
 
     /// 显式 shutdown。drop 自身,利用 PlayerCore / AudioHandle / Scheduler 现有 Drop 链。
@@ -179,6 +192,34 @@ impl Server {
         )
         .await
     }
+
+    /// HTTP / WebSocket 遥控 accept loop(配置 `daemon.http`):与 [`Self::serve`] 并行,
+    /// 驱动同一个 player;REST 端点与推送见 `crate::http`。
+    ///
+    /// # Params:
+    ///   - `listener`: 已 bind 的 TCP listener(daemon 入口先过 [`crate::check_exposure`])
+    ///   - `token`: 访问令牌;`None` / 空串 = 不鉴权
+    pub async fn serve_http(
+        &self,
+        listener: TcpListener,
+        token: Option<String>,
+    ) -> color_eyre::Result<()> {
+        crate::http::run(listener, self.client(), self.events.clone(), token).await
+    }
+
+    /// MPD 协议兼容 accept loop(配置 `daemon.mpd`):与 [`Self::serve`] 并行,驱动同一个
+    /// player;支持的命令子集见 `crate::mpd`。
+    ///
+    /// # Params:
+    ///   - `listener`: 已 bind 的 TCP listener(daemon 入口先过 [`crate::check_exposure`])
+    ///   - `password`: 连接密码;`None` / 空串 = 不设防
+    pub async fn serve_mpd(
+        &self,
+        listener: TcpListener,
+        password: Option<String>,
+    ) -> color_eyre::Result<()> {
+        crate::mpd::run(listener, self.client(), self.events.clone(), password).await
+    }
 }
 
 /// 打开音频本体缓存(`audio_cache` 表落 `persist` 的 `mineral.db`);目录解析 / open 失败时
@@ -68,10 +68,18 @@ pub(crate) struct State {
     /// `current` 版本;`sync` 组段时按当前曲过滤,故串曲的迟到包络天然不外发。
     pub(crate) current_envelope: Option<(SongId, Envelope)>,
 
+    /// 当前歌的歌词时间偏移(id + 毫秒):开播时从 per-song 持久值载入、按键 / 脚本微调时
+    /// 改写;`sync` 组段时按当前曲过滤,与包络同理。
+    pub(crate) current_lyric_offset: Option<(SongId, i64)>,
+
     /// 正在预拉(已发起 SongUrl 任务、URL 尚未回来)的下一曲 id;URL 到达时据此认领。
     /// 切歌 / 采纳后复位,避免对同一 next 重复预拉。
     pub(crate) prefetch_fired_for: Option<SongId>,
 
+    /// 已为其发起过电台续播的当前曲 id(每曲只续一次;取空也不在同一曲尾反复重试)。
+    /// 切歌时复位。
+    pub(crate) radio_fired_for: Option<SongId>,
+
     /// 当前正在 capture(边播边落盘)的曲;自然播完 → 入缓存,中途打断 → 删残件。
     /// 命中缓存直接本地播时为 `None`(无需 capture)。
     pub(crate) capturing: Option<Capturing>,
@@ -110,7 +118,9 @@ impl State {
             current_lyrics: None,
             current_lyrics_song_id: None,
             current_envelope: None,
+            current_lyric_offset: None,
             prefetch_fired_for: None,
+            radio_fired_for: None,
             capturing: None,
             queued: None,
             prefetch_vetoed: Vec::new(),
@@ -180,6 +190,7 @@ impl State {
                 .as_ref()
                 .filter(|(id, _)| self.current_song.as_ref().is_some_and(|s| s.id == *id))
                 .map(|(_, envelope)| envelope.clone()),
+            lyric_offset_ms: self.lyric_offset_ms(),
         });
         PlayerSync {
             versions: PlayerVersions {
@@ -213,6 +224,14 @@ impl State {
             self.bump_current();
         }
     }
+
+    /// 当前曲的歌词时间偏移;slot 空或归属别的曲(切歌后尚未载入)时为 0。
+    pub(crate) fn lyric_offset_ms(&self) -> i64 {
+        self.current_lyric_offset
+            .as_ref()
+            .filter(|(id, _)| self.current_song.as_ref().is_some_and(|s| s.id == *id))
+            .map_or(0, |(_, ms)| *ms)
+    }
 }
 
 #[cfg(test)]
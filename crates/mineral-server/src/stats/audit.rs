@@ -41,7 +41,11 @@ fn audit_request(req: &Request) -> TrackingDecision {
         Request::Stop => Recorded("plays"),
         Request::Seek(..) => Recorded("seeks"),
         Request::SetVolume(..) => Recorded("volume_changes"),
+        Request::SetEq(..) => NotAnEvent("音色偏好切换,不是听歌行为"),
+        Request::SetSpeed { .. } => NotAnEvent("倍速切换;listen_ms 记曲内进度而非墙钟时长"),
         Request::AudioSnapshot => NotAnEvent("轮询读:音频状态快照"),
+        Request::AudioDevices => NotAnEvent("读:输出设备列表"),
+        Request::SetAudioDevice(..) => NotAnEvent("输出设备切换,不是听歌行为"),
         Request::SubmitTask(..) => {
             NotAnEvent("任务提交;取数事件在 task 终态记,见 audit_fetch_kind")
         }
@@ -57,6 +61,8 @@ fn audit_request(req: &Request) -> TrackingDecision {
         // 切上首=skip 记 plays;回曲首(超阈值)分支另记 seeks,主归属取 plays。
         Request::PrevOrRestart => Recorded("plays"),
         Request::NextSong => Recorded("plays"),
+        Request::NudgeLyricOffset(..) => NotAnEvent("歌词时间校正,不是听歌行为"),
+        Request::SaveLyrics { .. } => NotAnEvent("歌词编辑,不是听歌行为"),
         Request::PlayerSync(..) => NotAnEvent("读:播放器版本同步"),
         Request::PullPcm(..) => NotAnEvent("读:拉 PCM 数据"),
         Request::DaemonInfo => NotAnEvent("读:daemon 信息"),
@@ -87,6 +93,9 @@ fn audit_script_cmd(cmd: &ScriptCmd) -> TrackingDecision {
         ScriptCmd::SeekRel(..) => Recorded("seeks"),
         ScriptCmd::SeekTo(..) => Recorded("seeks"),
         ScriptCmd::SetVolume(..) => Recorded("volume_changes"),
+        ScriptCmd::SetEq(..) => NotAnEvent("音色偏好切换,不是听歌行为"),
+        ScriptCmd::SetSpeed { .. } => NotAnEvent("倍速切换;listen_ms 记曲内进度而非墙钟时长"),
+        ScriptCmd::NudgeLyricOffset(..) => NotAnEvent("歌词时间校正,不是听歌行为"),
         ScriptCmd::SetMode(..) => Recorded("mode_changes"),
         ScriptCmd::Play(..) => Recorded("plays"),
         ScriptCmd::Download(..) => Recorded("downloads"),
@@ -126,6 +135,7 @@ fn audit_fetch_kind(kind: &ChannelFetchKind) -> TrackingDecision {
         ChannelFetchKind::ArtistDetail { .. } => Recorded("fetches"),
         ChannelFetchKind::ArtistAlbums { .. } => Recorded("fetches"),
         ChannelFetchKind::AlbumDetail { .. } => Recorded("fetches"),
+        ChannelFetchKind::SongsDetail { .. } => Recorded("fetches"),
     }
 }
 
@@ -134,9 +144,11 @@ fn audit_playlist_op(op: &PlaylistWriteOp) -> TrackingDecision {
     use TrackingDecision::Recorded;
     match op {
         PlaylistWriteOp::Create { .. } => Recorded("playlist_ops"),
+        PlaylistWriteOp::CreateSmart { .. } => Recorded("playlist_ops"),
         PlaylistWriteOp::Delete { .. } => Recorded("playlist_ops"),
         PlaylistWriteOp::AddSongs { .. } => Recorded("playlist_ops"),
         PlaylistWriteOp::RemoveSongs { .. } => Recorded("playlist_ops"),
+        PlaylistWriteOp::MoveSong { .. } => Recorded("playlist_ops"),
         PlaylistWriteOp::Rename { .. } => Recorded("playlist_ops"),
         PlaylistWriteOp::SetDescription { .. } => Recorded("playlist_ops"),
     }
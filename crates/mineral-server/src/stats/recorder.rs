@@ -104,6 +104,9 @@ enum StatsCommand {
     /// 随快照带 true)。pending 缺席(起播被 gate / 已结算)则丢弃。
     EnrichAudio(PlayAudioSnapshot),
 
+    /// 补写 songs 维表行(起播以外的入口,如下载完成)。
+    UpsertSong(Box<Song>),
+
     /// 排空栅栏:FIFO 保证之前的命令均已落库,回 ack。停机前 flush 用。
     Flush {
         /// 排空完成的应答端。
@@ -419,6 +422,22 @@ impl StatsRecorder {
         });
     }
 
+    /// 补写一首歌的 songs 维表行:下载过却没播过的歌也要有元数据,跨库聚合(智能歌单)
+    /// 才能把它列进全集。来源被排除时不写。
+    ///
+    /// # Params:
+    ///   - `song`: 待补写的歌
+    pub fn upsert_song(&self, song: &Song) {
+        if self
+            .params
+            .load()
+            .excludes_source(song.id.namespace().name())
+        {
+            return;
+        }
+        self.send(StatsCommand::UpsertSong(Box::new(song.clone())));
+    }
+
     /// play_url 就绪后富化在播行的音频快照(整组覆盖);pending 缺席则 actor 侧丢弃。
     /// 起播已带的 playback_origin 不在此改。
     ///
@@ -560,6 +579,11 @@ async fn process_command(
                 snapshot.audio = audio;
             }
         }
+        StatsCommand::UpsertSong(song) => {
+            if let Err(e) = store.upsert_song(&song).await {
+                mineral_log::warn!(target: "stats", error = chain(&e), "upsert_song 失败");
+            }
+        }
         // FIFO:走到这条时上面的命令都已 await 完成落库,应答即代表「已排空」。
         StatsCommand::Flush { ack } => {
             let _ = ack.send(());
@@ -0,0 +1,302 @@
+//! 下载后写标签(配置 `download.tags`):标题 / 艺人 / 专辑 / 音轨号,内嵌封面与歌词。
+//!
+//! CDN 流多半不带标签,导出库拿到别的播放器 / NAS 上全靠这些字段识别。只在导出文件
+//! rename 落盘后跑;任一环节失败只 warn——文件本身已完整可播,不因标签改判下载失败。
+
+use std::path::Path;
+
+use color_eyre::eyre::{WrapErr, eyre};
+use lofty::config::WriteOptions;
+use lofty::file::TaggedFileExt;
+use lofty::picture::{Picture, PictureType};
+use lofty::prelude::{Accessor, ItemKey, TagExt};
+use lofty::tag::Tag;
+use mineral_channel_core::MusicChannel;
+use mineral_config::DownloadTagsConfig;
+use mineral_model::{LyricLine, MediaUrl, Song, has_timed, to_lrc_string};
+
+/// 多艺人写进单个艺人字段时的分隔符(本地源读回按 `;` 拆分,见 channel-local tags)。
+const ARTIST_SEPARATOR: &str = "; ";
+
+/// 按配置给已导出的文件写标签;封面 / 歌词拉取失败各自跳过,写盘失败只 warn。
+///
+/// # Params:
+///   - `channel`: 该曲来源的 channel(取歌词)
+///   - `http`: 复用的 HTTP client(拉远端封面)
+///   - `tags`: 写标签配置(`download.tags`)
+///   - `song`: 刚下载的歌
+///   - `path`: 导出文件路径
+pub(crate) async fn tag_export(
+    channel: &dyn MusicChannel,
+    http: &reqwest::Client,
+    tags: &DownloadTagsConfig,
+    song: &Song,
+    path: &Path,
+) {
+    if !*tags.enabled() {
+        return;
+    }
+    let cover = match (*tags.cover(), &song.cover_url) {
+        (true, Some(url)) => fetch_cover(http, url)
+            .await
+            .inspect_err(|e| {
+                mineral_log::warn!(
+                    target: "download",
+                    song_id = song.id.as_str(),
+                    error = mineral_log::chain(e),
+                    "拉取封面失败,不内嵌"
+                );
+            })
+            .ok(),
+        _ => None,
+    };
+    let lyrics = if *tags.lyrics() {
+        fetch_lyrics(channel, song).await
+    } else {
+        None
+    };
+    let owned_song = song.clone();
+    let owned_path = path.to_path_buf();
+    let written =
+        tokio::task::spawn_blocking(move || write_tags(&owned_path, &owned_song, cover, lyrics))
+            .await
+            .map_err(|e| eyre!("写标签线程异常: {e}"))
+            .and_then(|r| r);
+    if let Err(e) = written {
+        mineral_log::warn!(
+            target: "download",
+            song_id = song.id.as_str(),
+            path = %path.display(),
+            error = mineral_log::chain(e),
+            "写标签失败,保留无标签文件"
+        );
+    }
+}
+
+/// 取封面原始字节:远端走 HTTP GET,本地直接读文件。
+///
+/// # Params:
+///   - `http`: HTTP client
+///   - `url`: 歌曲封面地址
+///
+/// # Return:
+///   图片字节。
+async fn fetch_cover(http: &reqwest::Client, url: &MediaUrl) -> color_eyre::Result<Vec<u8>> {
+    match url {
+        MediaUrl::Remote(u) => {
+            let bytes = http
+                .get(u.clone())
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+            Ok(bytes.to_vec())
+        }
+        MediaUrl::Local(p) => tokio::fs::read(p)
+            .await
+            .wrap_err_with(|| format!("读本地封面失败 {}", p.display())),
+    }
+}
+
+/// 取歌词文本;来源不支持 / 无歌词 / 拉取失败都返回 `None`(后两者 debug 记录)。
+///
+/// # Params:
+///   - `channel`: 该曲来源的 channel
+///   - `song`: 歌曲
+///
+/// # Return:
+///   待写入的歌词文本。
+async fn fetch_lyrics(channel: &dyn MusicChannel, song: &Song) -> Option<String> {
+    match channel.lyrics(&song.id).await {
+        Ok(lyrics) => lyrics_text(&lyrics.lines),
+        Err(mineral_channel_core::Error::NotSupported) => None,
+        Err(e) => {
+            mineral_log::debug!(
+                target: "download",
+                song_id = song.id.as_str(),
+                error = %e,
+                "取歌词失败,不内嵌"
+            );
+            None
+        }
+    }
+}
+
+/// 歌词行 → 写入标签的文本:有时间轴写标准 LRC(只含带时间戳的行),否则逐行纯文本。
+///
+/// # Params:
+///   - `lines`: 歌词行序列
+///
+/// # Return:
+///   歌词文本;空歌词为 `None`。
+fn lyrics_text(lines: &[LyricLine]) -> Option<String> {
+    let text = if has_timed(lines) {
+        to_lrc_string(lines)
+    } else {
+        lines
+            .iter()
+            .map(|l| l.kind.text().into_owned())
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// 把元信息写进文件的主标签(mp3/wav → ID3v2,flac/ogg → Vorbis comment,m4a → ilst);
+/// 文件原有主标签保留其余字段,只覆盖本函数管的几项。
+///
+/// 歌词走 [`ItemKey::Lyrics`]:ID3v2 落 `USLT`,Vorbis comment 落 `LYRICS`。
+///
+/// # Params:
+///   - `path`: 导出文件路径
+///   - `song`: 歌曲元信息
+///   - `cover`: 封面字节(格式不认识则跳过封面)
+///   - `lyrics`: 歌词文本
+///
+/// # Return:
+///   写盘成功返回 `Ok(())`。
+fn write_tags(
+    path: &Path,
+    song: &Song,
+    cover: Option<Vec<u8>>,
+    lyrics: Option<String>,
+) -> color_eyre::Result<()> {
+    let mut tagged = lofty::read_from_path(path)
+        .wrap_err_with(|| format!("解析导出文件失败 {}", path.display()))?;
+    let tag_type = tagged.primary_tag_type();
+    if tagged.tag(tag_type).is_none() {
+        tagged.insert_tag(Tag::new(tag_type));
+    }
+    let tag = tagged
+        .tag_mut(tag_type)
+        .ok_or_else(|| eyre!("无法为 {} 建立标签", path.display()))?;
+    tag.set_title(song.name.clone());
+    if !song.artists.is_empty() {
+        tag.set_artist(
+            song.artists
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(ARTIST_SEPARATOR),
+        );
+    }
+    if let Some(album) = &song.album {
+        tag.set_album(album.name.clone());
+    }
+    if let Some(artist) = &song.album_artist {
+        tag.insert_text(ItemKey::AlbumArtist, artist.clone());
+    }
+    if let Some(n) = song.track_no {
+        tag.set_track(n);
+    }
+    if let Some(y) = song.year {
+        tag.set_year(y);
+    }
+    if let Some(bytes) = cover {
+        match Picture::from_reader(&mut bytes.as_slice()) {
+            Ok(mut pic) => {
+                pic.set_pic_type(PictureType::CoverFront);
+                tag.remove_picture_type(PictureType::CoverFront);
+                tag.push_picture(pic);
+            }
+            Err(e) => {
+                mineral_log::debug!(target: "download", error = %e, "封面格式不识别,不内嵌");
+            }
+        }
+    }
+    if let Some(text) = lyrics {
+        tag.insert_text(ItemKey::Lyrics, text);
+    }
+    tag.save_to_path(path, WriteOptions::default())
+        .wrap_err_with(|| format!("写标签失败 {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::file::TaggedFileExt;
+    use lofty::picture::PictureType;
+    use lofty::prelude::{Accessor, ItemKey};
+    use mineral_model::{
+        AlbumId, AlbumRef, ArtistId, ArtistRef, LineKind, LyricLine, Song, SongId, SourceKind,
+    };
+
+    use super::{lyrics_text, write_tags};
+
+    /// 最小合法 PNG 头(`Picture::from_reader` 只按魔数判 MIME)。
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    /// 写入后读回:标题 / 多艺人 / 专辑 / 音轨号 / 前封面 / 歌词都在主标签里。
+    #[test]
+    fn writes_text_cover_and_lyrics() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("a.wav");
+        mineral_test::write_wav(&path, &[0_i16; 800], 1, 8_000)?;
+        let song = Song::builder()
+            .id(SongId::new(SourceKind::NETEASE, "1"))
+            .name("Palisade".to_owned())
+            .artists(vec![
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "1"),
+                    name: "Ada".to_owned(),
+                },
+                ArtistRef {
+                    id: ArtistId::new(SourceKind::NETEASE, "2"),
+                    name: "Bo".to_owned(),
+                },
+            ])
+            .album(Some(AlbumRef {
+                id: AlbumId::new(SourceKind::NETEASE, "9"),
+                name: "Shore".to_owned(),
+            }))
+            .track_no(Some(3))
+            .album_artist(Some("Ada".to_owned()))
+            .year(Some(2003))
+            .build();
+        write_tags(
+            &path,
+            &song,
+            Some(PNG.to_vec()),
+            Some("[00:01.00]hi".to_owned()),
+        )?;
+
+        let tagged = lofty::read_from_path(&path)?;
+        let tag = tagged
+            .primary_tag()
+            .ok_or_else(|| color_eyre::eyre::eyre!("主标签缺失"))?;
+        assert_eq!(tag.title().as_deref(), Some("Palisade"));
+        assert_eq!(tag.artist().as_deref(), Some("Ada; Bo"));
+        assert_eq!(tag.album().as_deref(), Some("Shore"));
+        assert_eq!(tag.track(), Some(3));
+        assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Ada"));
+        assert_eq!(tag.year(), Some(2003));
+        assert_eq!(tag.get_string(&ItemKey::Lyrics), Some("[00:01.00]hi"));
+        assert!(
+            tag.pictures()
+                .iter()
+                .any(|p| p.pic_type() == PictureType::CoverFront),
+            "封面按 front cover 内嵌"
+        );
+        Ok(())
+    }
+
+    /// 有时间轴写 LRC(丢无时间戳行);全无时间戳写纯文本;空歌词不写。
+    #[test]
+    fn lyrics_text_prefers_lrc() {
+        let line = |t: Option<u64>, s: &str| LyricLine {
+            time_ms: t,
+            kind: LineKind::Plain(s.to_owned()),
+            translation: None,
+            romanization: None,
+        };
+        assert_eq!(
+            lyrics_text(&[line(None, "作词"), line(Some(1_000), "hi")]).as_deref(),
+            Some("[00:01.00]hi")
+        );
+        assert_eq!(
+            lyrics_text(&[line(None, "a"), line(None, "b")]).as_deref(),
+            Some("a\nb")
+        );
+        assert_eq!(lyrics_text(&[]), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! 外部入口(MPD `add`、系统媒体控件的打开链接)给的歌曲引用 → [`Song`]。
+//!
+//! 认三种写法:本机音频文件(绝对路径或 `file://` URI,按 daemon 所在机器解释)、
+//! 各源分享链接(按源声明的网页模板反解)、`namespace:value` 形式的限定 id。远端 id
+//! 只带身份不带元数据,歌名先以裸 id 占位;播放取流只认 id,不受影响。
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, eyre};
+use mineral_channel_core::{ChannelCaps, match_web_url};
+use mineral_model::{MediaUrl, Song, SongId, SourceKind};
+
+/// 解析一条歌曲引用。
+///
+/// # Params:
+///   - `raw`: 外部输入
+///   - `caps`: 已注册 channel 的能力表(认分享链接 / 校验 namespace)
+///
+/// # Errors
+/// 文件不存在、没有源认得该链接、id 形状不对或源未注册。
+pub(crate) fn song_from_uri(
+    raw: &str,
+    caps: &[(SourceKind, ChannelCaps)],
+) -> color_eyre::Result<Song> {
+    let raw = raw.trim();
+    if let Some(path) = local_path(raw) {
+        return local_song(&path);
+    }
+    let id = if raw.starts_with("http://") || raw.starts_with("https://") {
+        caps.iter()
+            .find_map(|(source, caps)| {
+                let template = caps.song_web_url().as_deref()?;
+                Some(SongId::new(*source, match_web_url(template, raw)?))
+            })
+            .ok_or_else(|| eyre!("no source recognizes the link {raw:?}"))?
+    } else {
+        let (namespace, value) = split_qualified(raw).ok_or_else(|| {
+            eyre!("invalid song {raw:?}, expected an id like \"netease:123\", a link or a file")
+        })?;
+        let source = SourceKind::from_name(namespace);
+        if !caps.iter().any(|(s, _)| *s == source) {
+            bail!("unknown source {namespace:?}");
+        }
+        SongId::new(source, value)
+    };
+    Ok(Song::builder().name(id.value().to_owned()).id(id).build())
+}
+
+/// 拆 `<source>:<id>` 形的限定 id(裸值里可再含 `:`);任一半为空返回 `None`。
+pub(crate) fn split_qualified(raw: &str) -> Option<(&str, &str)> {
+    raw.split_once(':')
+        .filter(|(ns, value)| !ns.is_empty() && !value.is_empty())
+}
+
+/// 认本机文件写法:`file://` URI 或绝对路径(相对路径没有可信的基准目录,不认)。
+fn local_path(raw: &str) -> Option<PathBuf> {
+    if raw.starts_with("file://") {
+        return url::Url::parse(raw).ok()?.to_file_path().ok();
+    }
+    let path = Path::new(raw);
+    path.is_absolute().then(|| path.to_path_buf())
+}
+
+/// 本机音频文件 → 本地源的歌(歌名取文件名)。
+///
+/// # Errors
+/// 路径不是已存在的文件。
+fn local_song(path: &Path) -> color_eyre::Result<Song> {
+    if !path.is_file() {
+        bail!("no such file {}", path.display());
+    }
+    let name = path.file_stem().map_or_else(
+        || path.to_string_lossy().into_owned(),
+        |s| s.to_string_lossy().into_owned(),
+    );
+    Ok(Song::builder()
+        .id(SongId::new(SourceKind::LOCAL, path.to_string_lossy()))
+        .name(name)
+        .source_url(Some(MediaUrl::local(path)))
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_channel_core::{ArtistSectionKind, ArtistSections, ChannelCaps};
+    use mineral_model::{SearchKind, SourceKind};
+
+    use super::{song_from_uri, split_qualified};
+
+    /// 只注册网易云、带歌曲网页模板的能力表。
+    fn caps() -> Vec<(SourceKind, ChannelCaps)> {
+        vec![(
+            SourceKind::NETEASE,
+            ChannelCaps::builder()
+                .searchable(vec![SearchKind::Song])
+                .playlist_edit(false)
+                .artist_sections(ArtistSections::new(vec![ArtistSectionKind::Albums]))
+                .song_web_url(Some("https://music.163.com/song?id={id}".to_owned()))
+                .build(),
+        )]
+    }
+
+    /// 限定 id 只在首个 `:` 处拆(裸值可含 `:`),缺任一半不认。
+    #[test]
+    fn qualified_ids_split_once() {
+        assert_eq!(split_qualified("netease:123"), Some(("netease", "123")));
+        assert_eq!(split_qualified("local:a:b"), Some(("local", "a:b")));
+        assert_eq!(split_qualified(":123"), None);
+        assert_eq!(split_qualified("netease:"), None);
+        assert_eq!(split_qualified("netease"), None);
+    }
+
+    /// 限定 id 与分享链接落到同一首;未注册的源 / 认不出的链接报错。
+    #[test]
+    fn ids_and_links_resolve() -> color_eyre::Result<()> {
+        let caps = caps();
+        let by_id = song_from_uri("netease:123", &caps)?;
+        let by_link = song_from_uri("https://music.163.com/song?id=123&uct=x", &caps)?;
+        assert_eq!(by_id.id, by_link.id);
+        assert_eq!(by_id.source(), SourceKind::NETEASE);
+        assert!(song_from_uri("bilibili:BV1", &caps).is_err());
+        assert!(song_from_uri("https://example.com/song?id=1", &caps).is_err());
+        assert!(song_from_uri("relative/file.flac", &caps).is_err());
+        Ok(())
+    }
+
+    /// 本机文件:绝对路径与 `file://` URI 等价;不存在的文件报错。
+    #[test]
+    fn local_files_resolve() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("晴天.flac");
+        std::fs::write(&path, b"")?;
+        let by_path = song_from_uri(&path.to_string_lossy(), &[])?;
+        let uri = url::Url::from_file_path(&path)
+            .map_err(|()| color_eyre::eyre::eyre!("not an absolute path"))?;
+        let by_uri = song_from_uri(uri.as_str(), &[])?;
+        assert_eq!(by_path.id, by_uri.id);
+        assert_eq!(by_path.source(), SourceKind::LOCAL);
+        assert_eq!(by_path.name, "晴天");
+        assert!(song_from_uri(&dir.path().join("missing.flac").to_string_lossy(), &[]).is_err());
+        Ok(())
+    }
+}
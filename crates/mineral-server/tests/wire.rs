@@ -27,6 +27,8 @@ fn audio_snapshot_round_trip() -> color_eyre::Result<()> {
         position_ms: 12_345,
         duration_ms: Some(200_000),
         volume_pct: 77,
+        speed_pct: 150,
+        speed_mode: mineral_audio::SpeedMode::PitchShift,
         track_finished_seq: 3,
         backend: mineral_audio::AudioBackend::Null,
         download_complete: true,
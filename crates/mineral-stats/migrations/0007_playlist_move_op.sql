@@ -0,0 +1,28 @@
+-- 歌单写操作的 op 判别扩容:move(混源歌单内挪动曲目)。
+--
+-- SQLite 改不了 CHECK 约束,只能重建表再搬数据。除 op 的取值集合外,列定义与
+-- 0001_baseline 的 playlist_ops 完全一致。
+CREATE TABLE playlist_ops_new (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts           INTEGER NOT NULL,
+    session_id   INTEGER REFERENCES sessions(id),
+    actor        TEXT NOT NULL CHECK (actor IN ('user', 'script', 'system', 'cli')),
+    op           TEXT NOT NULL CHECK (op IN (
+        'create', 'delete', 'add', 'remove', 'move', 'rename', 'set_description'
+    )),
+    playlist_ref TEXT NOT NULL,
+    ns           TEXT,
+    song_value   TEXT,
+    song_count   INTEGER NOT NULL,
+    outcome      TEXT NOT NULL CHECK (outcome IN ('ok', 'failed')),
+    error_kind   TEXT CHECK (error_kind IN ('auth_required', 'rate_limited', 'not_supported', 'api', 'other'))
+);
+
+INSERT INTO playlist_ops_new
+    (id, ts, session_id, actor, op, playlist_ref, ns, song_value, song_count, outcome, error_kind)
+SELECT id, ts, session_id, actor, op, playlist_ref, ns, song_value, song_count, outcome, error_kind
+FROM playlist_ops;
+
+DROP TABLE playlist_ops;
+ALTER TABLE playlist_ops_new RENAME TO playlist_ops;
+CREATE INDEX idx_playlist_ops_ts ON playlist_ops (ts);
@@ -0,0 +1,28 @@
+-- 取数种类扩容:songs_detail(按 id 批量补歌曲元数据,CLI `queue add` 用)。
+--
+-- SQLite 改不了 CHECK 约束,只能重建表再搬数据。除 fetch_kind 的取值集合外,列定义与
+-- 0001_baseline 的 fetches 完全一致。
+CREATE TABLE fetches_new (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts         INTEGER NOT NULL,
+    session_id INTEGER REFERENCES sessions(id),
+    actor      TEXT NOT NULL CHECK (actor IN ('user', 'script', 'system', 'cli')),
+    fetch_kind TEXT NOT NULL CHECK (fetch_kind IN (
+        'my_playlists', 'playlist_detail', 'song_url', 'lyrics', 'remote_play_count', 'search',
+        'artist_detail', 'artist_albums', 'album_detail', 'songs_detail'
+    )),
+    source     TEXT NOT NULL,
+    target_ref TEXT,
+    trigger    TEXT NOT NULL CHECK (trigger IN ('user', 'system')),
+    outcome    TEXT NOT NULL CHECK (outcome IN ('ok', 'failed', 'cancelled')),
+    latency_ms INTEGER NOT NULL
+);
+
+INSERT INTO fetches_new
+    (id, ts, session_id, actor, fetch_kind, source, target_ref, trigger, outcome, latency_ms)
+SELECT id, ts, session_id, actor, fetch_kind, source, target_ref, trigger, outcome, latency_ms
+FROM fetches;
+
+DROP TABLE fetches;
+ALTER TABLE fetches_new RENAME TO fetches;
+CREATE INDEX idx_fetches_ts ON fetches (ts);
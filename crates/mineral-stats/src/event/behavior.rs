@@ -92,6 +92,9 @@ pub enum FetchKind {
 
     /// 专辑详情。
     AlbumDetail,
+
+    /// 歌曲元数据(批量)。
+    SongsDetail,
 }
 
 /// 歌单写操作类型(playlist_ops.op)。
@@ -110,6 +113,9 @@ pub enum PlaylistOpKind {
     /// 移除歌曲。
     Remove,
 
+    /// 挪动歌曲位置。
+    Move,
+
     /// 重命名。
     Rename,
 
@@ -28,8 +28,8 @@ pub use params::{Level, Retention, SearchQueryMode, StatsParams};
 pub use play::{PlayAudioSnapshot, PlayRecord};
 pub use report::{
     Bucket, BucketBy, ContextSlice, Discoveries, Distributions, Endurance, EventCount,
-    EventSummary, NamedEntry, PlayTail, RawReport, ReportOptions, Slice, SongSummary, StatsReport,
-    StatusReport, Tally, TopAlbum, TopArtist, TopBy, TopSong, Totals, combine,
+    EventSummary, NamedEntry, PlayTail, RawReport, ReportOptions, Slice, SongActivity, SongSummary,
+    StatsReport, StatusReport, Tally, TopAlbum, TopArtist, TopBy, TopSong, Totals, combine,
 };
 pub use session::{SessionDecision, SessionTracker};
 pub use store::{StatsStore, is_event_kind};
@@ -332,6 +332,25 @@ pub struct SongSummary {
     pub last_played_at: Option<i64>,
 }
 
+/// 单曲在某时间窗内的活跃度(智能歌单逐曲筛选用;窗内毫无痕迹的歌不出行)。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SongActivity {
+    /// 歌曲 id。
+    pub song: SongId,
+
+    /// 有效播放次数(`listen_ms` 达阈值的行)。
+    pub plays: i64,
+
+    /// 跳歌次数(`finish_reason = skip`,不论听了多久)。
+    pub skips: i64,
+
+    /// 窗内最后一次有效播放的起播时刻;窗内无有效播放为 `None`。
+    pub last_played_at: Option<i64>,
+
+    /// 窗内最后一次完成下载的时刻;窗内没下载过为 `None`。
+    pub downloaded_at: Option<i64>,
+}
+
 /// 一条带展示名的榜项(top songs / albums / artists 装配后通用)。
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct NamedEntry {
@@ -1091,4 +1091,28 @@ mod tests {
         assert_eq!(row.latency_ms, 123);
         Ok(())
     }
+
+    /// songs_detail(0008 迁移扩进 CHECK)可落库;批量取数无 target_ref。
+    #[tokio::test]
+    async fn record_fetch_songs_detail() -> color_eyre::Result<()> {
+        use crate::event::{FetchOutcome, FetchTrigger};
+        let (_dir, store, sid) = open_temp().await?;
+        let event = StatsEvent::Behavior {
+            actor: Actor::Cli,
+            event: BehaviorEvent::Fetch {
+                fetch_kind: crate::FetchKind::SongsDetail,
+                source: SourceKind::NETEASE,
+                target_ref: None,
+                trigger: FetchTrigger::User,
+                outcome: FetchOutcome::Ok,
+                latency_ms: 40,
+            },
+        };
+        store.record_event(11_000, Some(sid), &event).await?;
+        let kind: String = sqlx::query_scalar("SELECT fetch_kind FROM fetches")
+            .fetch_one(live(&store)?)
+            .await?;
+        assert_eq!(kind, "songs_detail");
+        Ok(())
+    }
 }
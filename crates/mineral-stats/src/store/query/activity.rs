@@ -0,0 +1,146 @@
+//! 逐曲活跃度:时间窗内每首歌的有效播放 / 跳过 / 最近播放 / 最近下载。
+
+use std::ops::Range;
+
+use color_eyre::eyre::WrapErr as _;
+
+use crate::report::{ReportOptions, SongActivity};
+use crate::store::StatsStore;
+
+use super::shared::song_id;
+
+impl StatsStore {
+    /// 时间窗内逐曲活跃度(plays 与 downloads 两张事实表按歌合并)。
+    ///
+    /// 有效播放 / 最近播放吃 `options.min_listen_ms` 阈值;跳过按 `finish_reason` 计、不吃
+    /// 阈值(秒切正是跳过的常态);下载只认 `downloaded` 结局(跳过 / 失败不算「下过」)。
+    /// 窗内两表都没有痕迹的歌不出行,调用方按缺行即全零处理。
+    ///
+    /// # Params:
+    ///   - `range`: 时间窗口 `[start_ms, end_ms)`(全量传 `0..i64::MAX`)
+    ///   - `options`: 查询口径(只用有效播放阈值)
+    ///
+    /// # Return:
+    ///   逐曲活跃度,按 `(ns, song_value)` 升序
+    pub async fn song_activity(
+        &self,
+        range: Range<i64>,
+        options: &ReportOptions,
+    ) -> color_eyre::Result<Vec<SongActivity>> {
+        let Some(pool) = self.pool() else {
+            return Ok(Vec::new());
+        };
+        let min = options.min_listen_ms();
+        let rows = sqlx::query_as::<_, (String, String, i64, i64, Option<i64>, Option<i64>)>(
+            "SELECT ns, song_value, SUM(plays), SUM(skips), MAX(last_played_at), MAX(downloaded_at) \
+             FROM ( \
+               SELECT ns, song_value, SUM(CASE WHEN listen_ms >= ? THEN 1 ELSE 0 END) AS plays, \
+                 SUM(CASE WHEN finish_reason = 'skip' THEN 1 ELSE 0 END) AS skips, \
+                 MAX(CASE WHEN listen_ms >= ? THEN started_at END) AS last_played_at, \
+                 NULL AS downloaded_at \
+               FROM plays WHERE started_at >= ? AND started_at < ? GROUP BY ns, song_value \
+               UNION ALL \
+               SELECT ns, song_value, 0, 0, NULL, MAX(ts) \
+               FROM downloads WHERE outcome = 'downloaded' AND ts >= ? AND ts < ? \
+               GROUP BY ns, song_value \
+             ) GROUP BY ns, song_value ORDER BY ns, song_value",
+        )
+        .bind(min)
+        .bind(min)
+        .bind(range.start)
+        .bind(range.end)
+        .bind(range.start)
+        .bind(range.end)
+        .fetch_all(pool)
+        .await
+        .wrap_err("song_activity 查询失败")?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(ns, value, plays, skips, last_played_at, downloaded_at)| SongActivity {
+                    song: song_id(&ns, &value),
+                    plays,
+                    skips,
+                    last_played_at,
+                    downloaded_at,
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::{BehaviorEvent, DownloadHook, DownloadOutcome, StatsEvent};
+    use crate::vocab::Actor;
+
+    use super::super::shared::song_id;
+    use super::super::test_support::{DAY, T0, full_range, open_temp, options, seed};
+
+    /// 落一条下载事件。
+    async fn download(
+        store: &crate::store::StatsStore,
+        ts: i64,
+        value: &str,
+        outcome: DownloadOutcome,
+    ) -> color_eyre::Result<()> {
+        let event = StatsEvent::Behavior {
+            actor: Actor::User,
+            event: BehaviorEvent::Download {
+                song: song_id("netease", value),
+                quality: "lossless".to_owned(),
+                format: None,
+                outcome,
+                hooked: DownloadHook::None,
+                path: None,
+            },
+        };
+        store.record_event(ts, None, &event).await
+    }
+
+    /// 播放与下载按歌合并:有效播放吃阈值、跳过不吃;失败的下载不算下过。
+    #[tokio::test]
+    async fn merges_plays_and_downloads_per_song() -> color_eyre::Result<()> {
+        let (_d, store) = open_temp().await?;
+        seed(&store).await?;
+        download(&store, T0 + DAY, "1", DownloadOutcome::Downloaded).await?;
+        download(&store, T0 + DAY, "9", DownloadOutcome::Downloaded).await?;
+        download(&store, T0 + DAY, "8", DownloadOutcome::Failed).await?;
+        let rows = store.song_activity(full_range(), &options(30_000)).await?;
+        let got = rows
+            .iter()
+            .map(|a| {
+                (
+                    a.song.qualified(),
+                    a.plays,
+                    a.skips,
+                    a.last_played_at.is_some(),
+                    a.downloaded_at,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            got,
+            vec![
+                ("bilibili:3".to_owned(), 1, 0, true, None),
+                ("netease:1".to_owned(), 2, 0, true, Some(T0 + DAY)),
+                ("netease:2".to_owned(), 0, 1, false, None),
+                ("netease:9".to_owned(), 0, 0, false, Some(T0 + DAY)),
+            ]
+        );
+        Ok(())
+    }
+
+    /// 降级句柄返回空。
+    #[tokio::test]
+    async fn disabled_is_empty() -> color_eyre::Result<()> {
+        let store = crate::store::StatsStore::disabled();
+        assert!(
+            store
+                .song_activity(full_range(), &options(0))
+                .await?
+                .is_empty()
+        );
+        Ok(())
+    }
+}
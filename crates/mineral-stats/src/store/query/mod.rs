@@ -5,8 +5,10 @@
 //! 接 [`crate::ReportOptions`] 的有效播放阈值——落库不过滤,口径在 SQL WHERE 生效。
 //!
 //! 按查询族拆分子模块([`overview`] 总量/流水、[`top`] 排行榜、[`distributions`] 分布、
-//! [`discoveries`] 新发现、[`endurance`] 续航),`shared` 收口跨族共用的 id 重建 helper。
+//! [`discoveries`] 新发现、[`endurance`] 续航、[`activity`] 逐曲活跃度),`shared` 收口
+//! 跨族共用的 id 重建 helper。
 
+mod activity;
 mod discoveries;
 mod distributions;
 mod endurance;
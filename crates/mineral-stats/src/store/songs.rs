@@ -1,10 +1,36 @@
-//! songs 维表维护:播放路径 write-through 的歌曲展示元数据。
+//! songs 维表维护:播放路径 write-through 的歌曲展示元数据(及其只读还原)。
 
 use color_eyre::eyre::WrapErr as _;
-use mineral_model::Song;
+use mineral_model::{AlbumId, AlbumRef, ArtistId, ArtistRef, Song, SongId, SourceKind};
+use rustc_hash::FxHashMap;
 
 use crate::store::StatsStore;
 
+/// [`StatsStore::known_songs`] 的维表行。
+#[derive(sqlx::FromRow)]
+struct KnownSongRow {
+    /// 来源 name。
+    ns: String,
+
+    /// 裸歌曲 id。
+    song_value: String,
+
+    /// 歌名。
+    name: String,
+
+    /// 别名。
+    alias: Option<String>,
+
+    /// 专辑裸 id。
+    album_id: Option<String>,
+
+    /// 专辑名。
+    album_name: Option<String>,
+
+    /// 时长 ms。
+    duration_ms: Option<i64>,
+}
+
 impl StatsStore {
     /// 落 / 富化一行歌曲维表(报表 JOIN 出名的唯一数据源)。降级时静默 no-op。
     ///
@@ -80,6 +106,67 @@ impl StatsStore {
             .wrap_err_with(|| format!("upsert_song 提交事务失败 song={song_value}"))?;
         Ok(())
     }
+
+    /// 维表里全部歌曲还原成 [`Song`](艺人按 position 排),按 `(ns, song_value)` 升序。
+    ///
+    /// 维表只存展示列,还原出的歌无封面 / 源链接等富字段;凡播过 / 下载过的歌必有行,供跨库
+    /// 聚合(智能歌单)拿「听过 / 下过的歌」的元数据。降级时返回空。
+    ///
+    /// # Return:
+    ///   维表里的全部歌曲
+    pub async fn known_songs(&self) -> color_eyre::Result<Vec<Song>> {
+        let Some(pool) = self.pool() else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query_as::<_, KnownSongRow>(
+            "SELECT ns, song_value, name, alias, album_id, album_name, duration_ms \
+             FROM songs ORDER BY ns, song_value",
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("known_songs 查维表失败")?;
+        let artist_rows = sqlx::query_as::<_, (String, String, String, String)>(
+            "SELECT ns, song_value, artist_value, artist_name FROM song_artists \
+             ORDER BY ns, song_value, position",
+        )
+        .fetch_all(pool)
+        .await
+        .wrap_err("known_songs 查艺人维表失败")?;
+        let mut artists = FxHashMap::<(String, String), Vec<ArtistRef>>::default();
+        for (ns, song_value, artist_value, artist_name) in artist_rows {
+            let id = ArtistId::new(SourceKind::from_name(&ns), artist_value);
+            artists
+                .entry((ns, song_value))
+                .or_default()
+                .push(ArtistRef {
+                    id,
+                    name: artist_name,
+                });
+        }
+        rows.into_iter()
+            .map(|row| {
+                let source = SourceKind::from_name(&row.ns);
+                let album = match (row.album_id, row.album_name) {
+                    (Some(id), Some(name)) => Some(AlbumRef {
+                        id: AlbumId::new(source, id),
+                        name,
+                    }),
+                    _ => None,
+                };
+                let artists = artists
+                    .remove(&(row.ns, row.song_value.clone()))
+                    .unwrap_or_default();
+                Ok(Song::builder()
+                    .id(SongId::new(source, row.song_value))
+                    .name(row.name)
+                    .alias(row.alias)
+                    .artists(artists)
+                    .album(album)
+                    .duration_ms(row.duration_ms.map(u64::try_from).transpose()?)
+                    .build())
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -244,4 +331,23 @@ mod tests {
         assert_eq!(only.artist_name, "已知艺人");
         Ok(())
     }
+
+    /// known_songs 按维表还原:名 / 专辑 / 时长 / 艺人顺序都回来,按 id 升序。
+    #[tokio::test]
+    async fn known_songs_restores_dimension_rows() -> color_eyre::Result<()> {
+        let (_dir, store) = open_temp().await?;
+        let full = with_duration(
+            with_album(
+                with_artists(with_name(song("2"), "Palisade"), &["甲", "乙"]),
+                "碟",
+            ),
+            200_000,
+        );
+        store.upsert_song(&full).await?;
+        store.upsert_song(&with_name(song("1"), "Intro")).await?;
+        let songs = store.known_songs().await?;
+        assert_eq!(songs, vec![with_name(song("1"), "Intro"), full]);
+        assert!(StatsStore::disabled().known_songs().await?.is_empty());
+        Ok(())
+    }
 }
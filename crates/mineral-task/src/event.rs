@@ -1,6 +1,6 @@
 //! 任务推到 client 的事件载荷。
 
-use mineral_channel_core::Page;
+use mineral_channel_core::{LibraryScanReport, Page};
 use mineral_model::{
     Album, Artist, ArtistId, Lyrics, PlayUrl, Playlist, PlaylistId, SearchKind, Song, SongId,
     SourceKind,
@@ -149,6 +149,18 @@ pub enum TaskEvent {
         album: Box<Album>,
     },
 
+    /// `SongsDetail` 任务成功:一批歌曲的元数据已到。
+    SongsDetailFetched {
+        /// 目标 channel(client 配对用)。
+        source: SourceKind,
+
+        /// 请求的 id(client 配对用,保持请求顺序)。
+        ids: Vec<SongId>,
+
+        /// 查得到的歌曲(源不认识的 id 略过)。
+        songs: Vec<Song>,
+    },
+
     /// `PlaylistWrite` 任务完结(**成功失败都发**,见模块文档)。
     PlaylistWriteDone {
         /// 原操作回带(client 据此定位 pending 项与 toast 文案)。
@@ -158,6 +170,13 @@ pub enum TaskEvent {
         error: Option<WriteError>,
     },
 
+    /// `LocalScan` 任务成功:本地曲库已重扫、索引已落库。server 据此重拉本地源歌单
+    /// (文件夹 / m3u 投影可能随之变化)。
+    LocalScanDone {
+        /// 扫描计数回执。
+        report: LibraryScanReport,
+    },
+
     /// 任意 channel 取数收束(成功 / 失败 / 取消都发)。纯埋点信号(fetches),与具体
     /// 结果事件(`PlayUrlReady` / `SearchResults` 等)并行——server 记录后不转发 client。
     FetchDone {
@@ -15,7 +15,13 @@ pub enum TaskKind {
 
     /// 歌单写操作(per-source 串行执行,见 `Lane::PlaylistWrite`)。
     PlaylistWrite(PlaylistWriteOp),
-    // 后续:PlayPrep / AuthRefresh / PrePreload / LocalScan
+
+    /// 本地曲库重扫(单 worker,见 `Lane::LocalScan`)。
+    LocalScan {
+        /// `true` 忽略索引指纹、全部重读标签。
+        full: bool,
+    },
+    // 后续:PlayPrep / AuthRefresh / PrePreload
 }
 
 impl TaskKind {
@@ -24,6 +30,7 @@ impl TaskKind {
         match self {
             Self::ChannelFetch(_) => Lane::ChannelFetch,
             Self::PlaylistWrite(_) => Lane::PlaylistWrite,
+            Self::LocalScan { .. } => Lane::LocalScan,
         }
     }
 
@@ -32,6 +39,9 @@ impl TaskKind {
         match self {
             Self::ChannelFetch(k) => DedupKey(format!("ChannelFetch:{}", k.dedup_part())),
             Self::PlaylistWrite(op) => DedupKey(format!("PlaylistWrite:{}", op.dedup_part())),
+            // 同模式再提交只是搭车;全量不能搭增量的车(增量跳过指纹未变的文件,
+            // 全量要的重读标签会被吞掉),分 key 让它在单 worker 上排在增量之后。
+            Self::LocalScan { full } => DedupKey(format!("LocalScan:full={full}")),
         }
     }
 }
@@ -107,6 +117,15 @@ pub enum ChannelFetchKind {
         /// 专辑 id(自带 namespace)。
         id: AlbumId,
     },
+
+    /// 按 id 批量拉歌曲元数据(歌名 / 艺人 / 专辑 / 时长)。
+    SongsDetail {
+        /// 目标 channel。
+        source: SourceKind,
+
+        /// 歌曲 id(须都属于 `source`)。
+        ids: Vec<SongId>,
+    },
 }
 
 impl ChannelFetchKind {
@@ -136,6 +155,13 @@ impl ChannelFetchKind {
                 format!("artist_albums:{}:{}", id.qualified(), page.offset)
             }
             Self::AlbumDetail { id } => format!("album_detail:{}", id.qualified()),
+            Self::SongsDetail { source, ids } => format!(
+                "{source:?}:songs_detail:{}",
+                ids.iter()
+                    .map(SongId::qualified)
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
         }
     }
 
@@ -144,7 +170,9 @@ impl ChannelFetchKind {
     /// 带 id 的形态从 id 的 namespace 派生;只有 source 的形态直接返回。
     pub fn source(&self) -> SourceKind {
         match self {
-            Self::MyPlaylists { source } | Self::Search { source, .. } => *source,
+            Self::MyPlaylists { source }
+            | Self::Search { source, .. }
+            | Self::SongsDetail { source, .. } => *source,
             Self::PlaylistDetail { id } => id.namespace(),
             Self::SongUrl { song_id, .. }
             | Self::Lyrics { song_id }
@@ -154,10 +182,11 @@ impl ChannelFetchKind {
         }
     }
 
-    /// 取数目标的 qualified 引用(埋点 fetches.target_ref 用);只有 source 的形态无目标。
+    /// 取数目标的 qualified 引用(埋点 fetches.target_ref 用);只有 source 的形态与批量
+    /// 形态无目标。
     pub fn target_ref(&self) -> Option<String> {
         match self {
-            Self::MyPlaylists { .. } | Self::Search { .. } => None,
+            Self::MyPlaylists { .. } | Self::Search { .. } | Self::SongsDetail { .. } => None,
             Self::PlaylistDetail { id } => Some(id.qualified()),
             Self::SongUrl { song_id, .. }
             | Self::Lyrics { song_id }
@@ -192,6 +221,8 @@ pub enum ChannelFetchKindTag {
     ArtistAlbums,
     /// 对应 [`ChannelFetchKind::AlbumDetail`]。
     AlbumDetail,
+    /// 对应 [`ChannelFetchKind::SongsDetail`]。
+    SongsDetail,
 }
 
 impl ChannelFetchKindTag {
@@ -208,6 +239,7 @@ impl ChannelFetchKindTag {
             ChannelFetchKind::ArtistDetail { .. } => Self::ArtistDetail,
             ChannelFetchKind::ArtistAlbums { .. } => Self::ArtistAlbums,
             ChannelFetchKind::AlbumDetail { .. } => Self::AlbumDetail,
+            ChannelFetchKind::SongsDetail { .. } => Self::SongsDetail,
         }
     }
 
@@ -224,6 +256,7 @@ impl ChannelFetchKindTag {
             Self::ArtistDetail => "artist_detail",
             Self::ArtistAlbums => "artist_albums",
             Self::AlbumDetail => "album_detail",
+            Self::SongsDetail => "songs_detail",
         }
     }
 }
@@ -291,4 +324,16 @@ mod tests {
             prop_assert_ne!(song_url(&r1, q).dedup_key(), song_url(&r2, q).dedup_key());
         }
     }
+
+    /// 同模式的重扫共用 key;全量与增量分 key,全量不会被进行中的增量吞掉。
+    #[test]
+    fn local_scan_keyed_by_mode() {
+        let full = TaskKind::LocalScan { full: true };
+        let incremental = TaskKind::LocalScan { full: false };
+        assert_eq!(
+            full.dedup_key(),
+            TaskKind::LocalScan { full: true }.dedup_key()
+        );
+        assert_ne!(full.dedup_key(), incremental.dedup_key());
+    }
 }
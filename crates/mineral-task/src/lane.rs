@@ -12,4 +12,8 @@ pub enum Lane {
     /// 歌单写操作:**per-source 单 worker 串行**——同源两个写乱序到达远端
     /// 会丢更新,串行是顺序保证,不是性能取舍。
     PlaylistWrite,
+
+    /// 本地曲库重扫:**全局单 worker**——两次扫描并发只会重复 probe 同一批文件、
+    /// 争抢索引写入。
+    LocalScan,
 }
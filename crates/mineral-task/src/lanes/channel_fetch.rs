@@ -2,8 +2,8 @@
 
 use std::sync::Arc;
 
-use mineral_channel_core::MusicChannel;
-use mineral_model::SourceKind;
+use mineral_channel_core::{DiscoverKind, Error, MusicChannel, discover};
+use mineral_model::{Playlist, SourceKind};
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use tokio::sync::{mpsc, oneshot};
@@ -198,6 +198,37 @@ async fn run_job(channel: &Arc<dyn MusicChannel>, job: Job, event_tx: &Arc<Mutex
     let _ = done_tx.send(outcome);
 }
 
+/// 「我的歌单」+ 发现面条目:caps 声明的虚拟歌单(每日推荐等)与排行榜榜单接在后面。
+///
+/// 无「我的歌单」的源(未登录 / 不支持)只要声明了发现面,照样贡献这些条目;排行榜拉取
+/// 失败只丢榜单(warn),不连累歌单列表。
+async fn my_playlists_with_discover(
+    channel: &Arc<dyn MusicChannel>,
+) -> mineral_channel_core::Result<Vec<Playlist>> {
+    let caps = channel.caps();
+    let mut extras = discover::virtual_playlists(channel.source(), &caps);
+    if caps.discover().contains(&DiscoverKind::Toplists) {
+        match channel.toplists().await {
+            Ok(lists) => extras.extend(lists),
+            Err(e) => mineral_log::warn!(
+                target: "channel_fetch",
+                source = ?channel.source(),
+                op = "toplists",
+                error = mineral_log::chain(&e),
+                "channel fetch failed"
+            ),
+        }
+    }
+    match channel.my_playlists().await {
+        Ok(mut playlists) => {
+            playlists.extend(extras);
+            Ok(playlists)
+        }
+        Err(Error::NotSupported) if !extras.is_empty() => Ok(extras),
+        Err(e) => Err(e),
+    }
+}
+
 /// 真正调 channel 的实现:按 kind 分派,把结果包成 [`TaskEvent`] 写进事件 buffer,失败统一变 `Failed`。
 async fn execute(
     channel: &Arc<dyn MusicChannel>,
@@ -205,7 +236,8 @@ async fn execute(
     event_tx: &Arc<Mutex<Vec<TaskEvent>>>,
 ) -> TaskOutcome {
     match kind {
-        ChannelFetchKind::MyPlaylists { source } => match channel.my_playlists().await {
+        ChannelFetchKind::MyPlaylists { source } => match my_playlists_with_discover(channel).await
+        {
             Ok(playlists) => {
                 event_tx.lock().push(TaskEvent::PlaylistsFetched {
                     source: *source,
@@ -224,26 +256,34 @@ async fn execute(
                 TaskOutcome::Failed
             }
         },
-        ChannelFetchKind::PlaylistDetail { id } => match channel.playlist_detail(id).await {
-            Ok(playlist) => {
-                event_tx.lock().push(TaskEvent::PlaylistDetailFetched {
-                    id: id.clone(),
-                    playlist: Box::new(playlist),
-                });
-                TaskOutcome::Ok
-            }
-            Err(e) => {
-                mineral_log::warn!(
-                    target: "channel_fetch",
-                    source = ?id.namespace(),
-                    op = "playlist_detail",
-                    playlist_id = id.as_str(),
-                    error = mineral_log::chain(&e),
-                    "channel fetch failed"
-                );
-                TaskOutcome::Failed
+        ChannelFetchKind::PlaylistDetail { id } => {
+            match discover::resolve_playlist(&**channel, id).await {
+                Ok(playlist) => {
+                    // 虚拟歌单按解析结果的 id 回报(已去掉 server 绑的种子,与歌单库条目对得上)。
+                    let id = if discover::is_virtual(id) {
+                        playlist.id.clone()
+                    } else {
+                        id.clone()
+                    };
+                    event_tx.lock().push(TaskEvent::PlaylistDetailFetched {
+                        id,
+                        playlist: Box::new(playlist),
+                    });
+                    TaskOutcome::Ok
+                }
+                Err(e) => {
+                    mineral_log::warn!(
+                        target: "channel_fetch",
+                        source = ?id.namespace(),
+                        op = "playlist_detail",
+                        playlist_id = id.as_str(),
+                        error = mineral_log::chain(&e),
+                        "channel fetch failed"
+                    );
+                    TaskOutcome::Failed
+                }
             }
-        },
+        }
         ChannelFetchKind::SongUrl { song_id, quality } => {
             let ids = [song_id.clone()];
             match channel.song_urls(&ids, *quality).await {
@@ -461,5 +501,26 @@ async fn execute(
                 TaskOutcome::Failed
             }
         },
+        ChannelFetchKind::SongsDetail { source, ids } => match channel.songs_detail(ids).await {
+            Ok(songs) => {
+                event_tx.lock().push(TaskEvent::SongsDetailFetched {
+                    source: *source,
+                    ids: ids.clone(),
+                    songs,
+                });
+                TaskOutcome::Ok
+            }
+            Err(e) => {
+                mineral_log::warn!(
+                    target: "channel_fetch",
+                    source = ?source,
+                    op = "songs_detail",
+                    count = ids.len(),
+                    error = mineral_log::chain(&e),
+                    "channel fetch failed"
+                );
+                TaskOutcome::Failed
+            }
+        },
     }
 }
@@ -0,0 +1,117 @@
+//! LocalScan lane:**全局单 worker**,把重扫请求交给本地源 channel。
+//!
+//! 与 PlaylistWrite 一样只在排队期响应取消:扫描一旦开跑,索引写入是整批事务,
+//! 半途丢弃只会白扫一遍。没注册本地源(配置里无扫描根)时任务直接 `Failed`。
+
+use std::sync::Arc;
+
+use mineral_channel_core::MusicChannel;
+use mineral_model::SourceKind;
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+
+use crate::event::TaskEvent;
+use crate::id::TaskId;
+use crate::ongoing::Ongoing;
+use crate::outcome::TaskOutcome;
+
+/// 投递给 worker 的一次重扫。
+pub(crate) struct Job {
+    /// 任务 id,worker 完成后用于从 [`Ongoing`] 移除。
+    pub id: TaskId,
+
+    /// 是否全量重扫。
+    pub full: bool,
+
+    /// 取消令牌(只在开跑前生效,见模块文档)。
+    pub cancel: CancellationToken,
+
+    /// 终态通知通道(写一次)。
+    pub done_tx: oneshot::Sender<TaskOutcome>,
+}
+
+/// LocalScan lane:对外只暴露 [`LocalScanLane::dispatch`]。
+pub(crate) struct LocalScanLane {
+    /// 本地源 worker 的发送端;没注册本地源为 `None`。
+    sender: Option<mpsc::UnboundedSender<Job>>,
+}
+
+impl LocalScanLane {
+    /// 启动 lane:channels 里有 [`SourceKind::LOCAL`] 才 spawn 唯一的 worker。
+    pub fn spawn(
+        channels: &[Arc<dyn MusicChannel>],
+        ongoing: &Arc<Ongoing>,
+        event_tx: &Arc<Mutex<Vec<TaskEvent>>>,
+    ) -> Self {
+        let Some(ch) = channels.iter().find(|c| c.source() == SourceKind::LOCAL) else {
+            return Self { sender: None };
+        };
+        let (tx, rx) = mpsc::unbounded_channel::<Job>();
+        let channel = Arc::clone(ch);
+        let ongoing = Arc::clone(ongoing);
+        let event_tx = Arc::clone(event_tx);
+        tokio::spawn(async move {
+            worker_loop(channel, rx, ongoing, event_tx).await;
+        });
+        Self { sender: Some(tx) }
+    }
+
+    /// 把一次重扫投递给 worker;没有本地源时直接以 `Failed` 结束。
+    pub fn dispatch(&self, job: Job, ongoing: &Ongoing) {
+        let Some(tx) = &self.sender else {
+            mineral_log::warn!(target: "local_scan", "no local channel registered");
+            ongoing.remove(job.id);
+            let _ = job.done_tx.send(TaskOutcome::Failed);
+            return;
+        };
+        let _ = tx.send(job);
+    }
+}
+
+/// 单 worker 主循环:逐个跑完排队的重扫,完成后从 ongoing 摘掉。
+async fn worker_loop(
+    channel: Arc<dyn MusicChannel>,
+    mut rx: mpsc::UnboundedReceiver<Job>,
+    ongoing: Arc<Ongoing>,
+    event_tx: Arc<Mutex<Vec<TaskEvent>>>,
+) {
+    while let Some(job) = rx.recv().await {
+        let Job {
+            id,
+            full,
+            cancel,
+            done_tx,
+        } = job;
+        let outcome = if cancel.is_cancelled() {
+            TaskOutcome::Cancelled
+        } else {
+            execute(&channel, full, &event_tx).await
+        };
+        ongoing.remove(id);
+        let _ = done_tx.send(outcome);
+    }
+}
+
+/// 调 channel 重扫;成功推 [`TaskEvent::LocalScanDone`],失败只留日志。
+async fn execute(
+    channel: &Arc<dyn MusicChannel>,
+    full: bool,
+    event_tx: &Arc<Mutex<Vec<TaskEvent>>>,
+) -> TaskOutcome {
+    match channel.rescan_library(full).await {
+        Ok(report) => {
+            event_tx.lock().push(TaskEvent::LocalScanDone { report });
+            TaskOutcome::Ok
+        }
+        Err(e) => {
+            mineral_log::warn!(
+                target: "local_scan",
+                full,
+                error = mineral_log::chain(&e),
+                "local library scan failed"
+            );
+            TaskOutcome::Failed
+        }
+    }
+}
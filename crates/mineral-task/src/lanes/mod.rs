@@ -1,4 +1,5 @@
 //! 各 lane 的具体执行体。
 
 pub mod channel_fetch;
+pub mod local_scan;
 pub mod playlist_write;
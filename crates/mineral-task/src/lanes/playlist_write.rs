@@ -8,7 +8,7 @@
 
 use std::sync::Arc;
 
-use mineral_channel_core::MusicChannel;
+use mineral_channel_core::{Error, MusicChannel, discover};
 use mineral_model::SourceKind;
 use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
@@ -122,16 +122,25 @@ async fn execute(
     event_tx: &Arc<Mutex<Vec<TaskEvent>>>,
 ) -> TaskOutcome {
     let result = match &op {
+        // 发现面虚拟歌单(每日推荐等)没有远端实体可写,不下发给 channel。
+        _ if op.target_playlist().is_some_and(discover::is_virtual) => Err(Error::NotSupported),
         // create 的返回值(新歌单)刻意丢弃:数据收敛统一走"写成功 → 重拉
         // my_playlists"单一路径,不在这里旁路塞数据
         PlaylistWriteOp::Create { name, .. } => {
             channel.create_playlist(name).await.map(|_created| ())
         }
+        PlaylistWriteOp::CreateSmart { name, query } => channel
+            .create_smart_playlist(name, query)
+            .await
+            .map(|_created| ()),
         PlaylistWriteOp::Delete { id } => channel.delete_playlist(id).await,
         PlaylistWriteOp::AddSongs { id, songs } => channel.playlist_add_songs(id, songs).await,
         PlaylistWriteOp::RemoveSongs { id, songs } => {
             channel.playlist_remove_songs(id, songs).await
         }
+        PlaylistWriteOp::MoveSong { id, song, to } => {
+            channel.playlist_move_song(id, song, *to).await
+        }
         PlaylistWriteOp::Rename { id, name } => channel.rename_playlist(id, name).await,
         PlaylistWriteOp::SetDescription { id, desc } => {
             channel.set_playlist_description(id, desc).await
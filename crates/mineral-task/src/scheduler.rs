@@ -14,6 +14,7 @@ use crate::kind::ChannelFetchKindTag;
 use crate::kind::TaskKind;
 use crate::lane::Lane;
 use crate::lanes::channel_fetch::{ChannelFetchLane, Job as ChannelFetchJob};
+use crate::lanes::local_scan::{Job as LocalScanJob, LocalScanLane};
 use crate::lanes::playlist_write::{Job as PlaylistWriteJob, PlaylistWriteLane};
 use crate::ongoing::{Bind, Ongoing};
 
@@ -37,6 +38,9 @@ struct Inner {
 
     /// PlaylistWrite lane(per-source 单 worker 串行)。
     playlist_write: PlaylistWriteLane,
+
+    /// LocalScan lane(全局单 worker;无本地源时任务直接失败)。
+    local_scan: LocalScanLane,
 }
 
 /// `Scheduler::snapshot` 的返回:当前 running 数与按 lane / kind 的拆分。
@@ -65,12 +69,14 @@ impl Scheduler {
         let channel_fetch =
             ChannelFetchLane::spawn(channels, &ongoing, &events, workers_per_channel);
         let playlist_write = PlaylistWriteLane::spawn(channels, &ongoing, &events);
+        let local_scan = LocalScanLane::spawn(channels, &ongoing, &events);
         Self {
             inner: Arc::new(Inner {
                 ongoing,
                 events,
                 channel_fetch,
                 playlist_write,
+                local_scan,
             }),
         }
     }
@@ -99,7 +105,7 @@ impl Scheduler {
         }
     }
 
-    /// 把新建任务路由到对应 lane(目前只有 ChannelFetch)。
+    /// 把新建任务路由到对应 lane。
     fn dispatch(
         &self,
         id: TaskId,
@@ -136,6 +142,17 @@ impl Scheduler {
                     &self.inner.events,
                 );
             }
+            TaskKind::LocalScan { full } => {
+                self.inner.local_scan.dispatch(
+                    LocalScanJob {
+                        id,
+                        full,
+                        cancel: handle.cancel.clone(),
+                        done_tx,
+                    },
+                    &self.inner.ongoing,
+                );
+            }
         }
     }
 
@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 
 /// 一次歌单写操作。server 边界解开成对应 `MusicChannel` 方法调用。
 ///
-/// `Create` 需要显式 `source`(还没有歌单 id 可派生 namespace);其余操作的
-/// 目标 channel 一律从 `id` 的 namespace 派生,不另带字段(单一事实源)。
+/// `Create` 需要显式 `source`(还没有歌单 id 可派生 namespace),`CreateSmart` 只有聚合源
+/// 认;其余操作的目标 channel 一律从 `id` 的 namespace 派生,不另带字段(单一事实源)。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlaylistWriteOp {
     /// 创建歌单。
@@ -18,6 +18,15 @@ pub enum PlaylistWriteOp {
         name: String,
     },
 
+    /// 创建智能歌单(聚合源):名字即稳定键,表达式一并落定。同名已存在报冲突,不覆盖。
+    CreateSmart {
+        /// 歌单名。
+        name: String,
+
+        /// 筛选表达式原文(空串 = 空歌单)。
+        query: String,
+    },
+
     /// 删除自己创建的歌单。
     Delete {
         /// 歌单 id(自带 namespace)。
@@ -42,6 +51,18 @@ pub enum PlaylistWriteOp {
         songs: Vec<SongId>,
     },
 
+    /// 把歌单内一首歌挪到指定位置。
+    MoveSong {
+        /// 歌单 id。
+        id: PlaylistId,
+
+        /// 要挪的歌(须已在歌单中)。
+        song: SongId,
+
+        /// 目标位置(0-based,按挪动后的列表计)。
+        to: usize,
+    },
+
     /// 歌单改名。
     Rename {
         /// 歌单 id。
@@ -66,19 +87,36 @@ impl PlaylistWriteOp {
     pub fn target_source(&self) -> SourceKind {
         match self {
             Self::Create { source, .. } => *source,
+            Self::CreateSmart { .. } => SourceKind::MINERAL,
             Self::Delete { id }
             | Self::AddSongs { id, .. }
             | Self::RemoveSongs { id, .. }
+            | Self::MoveSong { id, .. }
             | Self::Rename { id, .. }
             | Self::SetDescription { id, .. } => id.namespace(),
         }
     }
 
+    /// 目标歌单(建单还没有歌单 → `None`)。
+    pub fn target_playlist(&self) -> Option<&PlaylistId> {
+        match self {
+            Self::Create { .. } | Self::CreateSmart { .. } => None,
+            Self::Delete { id }
+            | Self::AddSongs { id, .. }
+            | Self::RemoveSongs { id, .. }
+            | Self::MoveSong { id, .. }
+            | Self::Rename { id, .. }
+            | Self::SetDescription { id, .. } => Some(id),
+        }
+    }
+
     /// 涉及的歌曲列表(同源校验用;无歌曲的操作返回空)。
     pub fn songs(&self) -> &[SongId] {
         match self {
             Self::AddSongs { songs, .. } | Self::RemoveSongs { songs, .. } => songs,
+            Self::MoveSong { song, .. } => std::slice::from_ref(song),
             Self::Create { .. }
+            | Self::CreateSmart { .. }
             | Self::Delete { .. }
             | Self::Rename { .. }
             | Self::SetDescription { .. } => &[],
@@ -90,6 +128,7 @@ impl PlaylistWriteOp {
     pub(crate) fn dedup_part(&self) -> String {
         match self {
             Self::Create { source, name } => format!("create:{source:?}:{name}"),
+            Self::CreateSmart { name, query } => format!("create-smart:{name}:{query}"),
             Self::Delete { id } => format!("delete:{}", id.qualified()),
             Self::AddSongs { id, songs } => {
                 format!("add:{}:{}", id.qualified(), join_qualified(songs))
@@ -97,6 +136,9 @@ impl PlaylistWriteOp {
             Self::RemoveSongs { id, songs } => {
                 format!("remove:{}:{}", id.qualified(), join_qualified(songs))
             }
+            Self::MoveSong { id, song, to } => {
+                format!("move:{}:{}:{to}", id.qualified(), song.qualified())
+            }
             Self::Rename { id, name } => format!("rename:{}:{name}", id.qualified()),
             Self::SetDescription { id, desc } => format!("desc:{}:{desc}", id.qualified()),
         }
@@ -170,6 +212,13 @@ mod tests {
         };
         assert_eq!(create.target_source(), SourceKind::NETEASE);
 
+        let smart = PlaylistWriteOp::CreateSmart {
+            name: String::from("x"),
+            query: String::from("loved"),
+        };
+        assert_eq!(smart.target_source(), SourceKind::MINERAL);
+        assert_eq!(smart.target_playlist(), None);
+
         let delete = PlaylistWriteOp::Delete {
             id: PlaylistId::new(SourceKind::LOCAL, "p1"),
         };
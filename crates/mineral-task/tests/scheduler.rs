@@ -401,3 +401,97 @@ async fn playlist_write_failure_emits_error_event() -> color_eyre::Result<()> {
     );
     Ok(())
 }
+
+// ---------------- LocalScan lane ----------------
+
+/// 本地源桩:rescan_library 回固定回执,记录收到的 `full` 参数。
+struct LocalRecorder {
+    /// 每次重扫收到的 `full`。
+    calls: Arc<parking_lot::Mutex<Vec<bool>>>,
+}
+
+#[async_trait]
+impl MusicChannel for LocalRecorder {
+    fn source(&self) -> SourceKind {
+        SourceKind::LOCAL
+    }
+
+    fn caps(&self) -> ChannelCaps {
+        ChannelCaps::builder()
+            .searchable(Vec::new())
+            .playlist_edit(false)
+            .artist_sections(mineral_channel_core::ArtistSections::new(Vec::new()))
+            .build()
+    }
+
+    async fn songs_detail(&self, _ids: &[SongId]) -> Result<Vec<Song>> {
+        Err(Error::NotSupported)
+    }
+    async fn song_urls(&self, _ids: &[SongId], _q: BitRate) -> Result<Vec<PlayUrl>> {
+        Err(Error::NotSupported)
+    }
+
+    async fn rescan_library(&self, full: bool) -> Result<mineral_channel_core::LibraryScanReport> {
+        self.calls.lock().push(full);
+        Ok(mineral_channel_core::LibraryScanReport {
+            tracks: 3,
+            added: 1,
+            ..Default::default()
+        })
+    }
+}
+
+/// LocalScan 路由到本地源:透传 `full`,成功推 `LocalScanDone`,且不计入 ChannelFetch。
+#[tokio::test]
+async fn local_scan_routes_to_local_channel() -> color_eyre::Result<()> {
+    let calls = Arc::new(parking_lot::Mutex::new(Vec::<bool>::new()));
+    let local: Arc<dyn MusicChannel> = Arc::new(LocalRecorder {
+        calls: Arc::clone(&calls),
+    });
+    let mut chs = channels(None);
+    chs.push(local);
+    let sched = Scheduler::new(&chs, /*workers_per_channel*/ 8);
+
+    let h = sched.submit(TaskKind::LocalScan { full: true }, Priority::User);
+    assert_eq!(h.done().await, TaskOutcome::Ok);
+    assert_eq!(*calls.lock(), vec![true]);
+    let evs = sched.drain_events();
+    assert!(
+        evs.iter().any(|e| matches!(
+            e,
+            TaskEvent::LocalScanDone { report } if report.tracks == 3
+        )),
+        "expected LocalScanDone, got {evs:?}"
+    );
+    assert_eq!(sched.snapshot().running, 0, "完成后摘出 ongoing");
+    Ok(())
+}
+
+/// 增量扫描进行中再提交全量:不搭增量的车,排在其后照跑一遍全量。
+#[tokio::test]
+async fn local_scan_full_is_not_deduped_into_incremental() -> color_eyre::Result<()> {
+    let calls = Arc::new(parking_lot::Mutex::new(Vec::<bool>::new()));
+    let local: Arc<dyn MusicChannel> = Arc::new(LocalRecorder {
+        calls: Arc::clone(&calls),
+    });
+    let mut chs = channels(None);
+    chs.push(local);
+    let sched = Scheduler::new(&chs, /*workers_per_channel*/ 8);
+
+    let incremental = sched.submit(TaskKind::LocalScan { full: false }, Priority::User);
+    let full = sched.submit(TaskKind::LocalScan { full: true }, Priority::User);
+    assert_eq!(incremental.done().await, TaskOutcome::Ok);
+    assert_eq!(full.done().await, TaskOutcome::Ok);
+    assert_eq!(*calls.lock(), vec![false, true]);
+    Ok(())
+}
+
+/// 没注册本地源:LocalScan 直接 Failed 且不残留在 ongoing。
+#[tokio::test]
+async fn local_scan_without_local_channel_fails() -> color_eyre::Result<()> {
+    let sched = Scheduler::new(&channels(None), /*workers_per_channel*/ 8);
+    let h = sched.submit(TaskKind::LocalScan { full: false }, Priority::User);
+    assert_eq!(h.done().await, TaskOutcome::Failed);
+    assert_eq!(sched.snapshot().running, 0);
+    Ok(())
+}
@@ -30,4 +30,4 @@ pub use builders::{
 pub use fixtures::{aliased_song, chinese_football, endserenading};
 pub use lyrics::{feiyu_lyrics, feiyu_song, qianzai_lyrics, qianzai_song};
 pub use strategies::arb_song;
-pub use wav::write_wav;
+pub use wav::{read_wav, write_wav};
@@ -2,6 +2,7 @@
 //!
 //! 给需要「真实可解码音频文件」的测试用(包络离线解码、本地播放路径等);
 //! 波形内容由调用方给交错样本,便于构造已知形状(渐强 / 恒幅 / 静音)。
+//! [`read_wav`] 反向读回(断言音频引擎文件出口的实际产出)。
 
 /// 把 i16 交错样本写成标准 16-bit PCM WAV 文件。
 ///
@@ -44,3 +45,72 @@ pub fn write_wav(
     std::fs::write(path, out)?;
     Ok(())
 }
+
+/// 读 WAV 文件的交错样本,归一到 `-1.0..=1.0`。
+///
+/// 只认本仓库会写的两种格式:16-bit PCM(format 1)与 32-bit 浮点(format 3);
+/// 未知块跳过。
+///
+/// # Params:
+///   - `path`: WAV 文件路径
+///
+/// # Return:
+///   `(交错样本, 声道数, 采样率)`;不是 RIFF/WAVE、缺 fmt / data 块或格式不支持返回 `Err`。
+pub fn read_wav(path: &std::path::Path) -> color_eyre::Result<(Vec<f32>, u16, u32)> {
+    let bytes = std::fs::read(path)?;
+    if bytes.get(0..4) != Some(b"RIFF".as_slice()) || bytes.get(8..12) != Some(b"WAVE".as_slice()) {
+        color_eyre::eyre::bail!("not a RIFF/WAVE file: {}", path.display());
+    }
+    let mut fmt: Option<(u16, u16, u32, u16)> = None;
+    let mut pos = 12usize;
+    while let (Some(id), Some(len)) = (bytes.get(pos..pos + 4), le_u32(&bytes, pos + 4)) {
+        let body_start = pos + 8;
+        let body_end = body_start
+            .saturating_add(usize::try_from(len)?)
+            .min(bytes.len());
+        let body = bytes.get(body_start..body_end).unwrap_or_default();
+        match id {
+            b"fmt " => {
+                fmt = Some((
+                    le_u16(body, 0).unwrap_or_default(),
+                    le_u16(body, 2).unwrap_or_default(),
+                    le_u32(body, 4).unwrap_or_default(),
+                    le_u16(body, 14).unwrap_or_default(),
+                ));
+            }
+            b"data" => {
+                let Some((format, channels, rate, bits)) = fmt else {
+                    color_eyre::eyre::bail!("data chunk before fmt chunk");
+                };
+                let samples = match (format, bits) {
+                    (1, 16) => body
+                        .chunks_exact(2)
+                        .filter_map(|b| b.try_into().ok())
+                        .map(|b| f32::from(i16::from_le_bytes(b)) / 32_768.0)
+                        .collect(),
+                    (3, 32) => body
+                        .chunks_exact(4)
+                        .filter_map(|b| b.try_into().ok())
+                        .map(f32::from_le_bytes)
+                        .collect(),
+                    _ => color_eyre::eyre::bail!("unsupported WAV format {format} / {bits} bit"),
+                };
+                return Ok((samples, channels, rate));
+            }
+            _ => {}
+        }
+        // 块长奇数时补一个 pad 字节。
+        pos = body_start + usize::try_from(len)? + usize::try_from(len % 2)?;
+    }
+    color_eyre::eyre::bail!("no data chunk in {}", path.display())
+}
+
+/// 小端 u16。
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+/// 小端 u32。
+fn le_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
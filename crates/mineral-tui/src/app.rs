@@ -20,11 +20,11 @@ use crate::components::popup::{OverlayAction, OverlayKind, OverlayResponse, Over
 use crate::components::toast::download_toast::DownloadNotifier;
 use rustc_hash::FxHashMap;
 
-use crate::components::toast::notifications::Notifications;
+use crate::components::toast::notifications::{Notifications, TextTint, tinted_text_item};
 use crate::player_actions::PlayMode;
 use crate::render::anim::{Transition, ticks16_from_ms};
 use crate::render::theme::Theme;
-use crate::runtime::action::{Action, SeekDelta, VolumeDelta};
+use crate::runtime::action::{Action, LyricOffsetDelta, SeekDelta, VolumeDelta};
 use crate::runtime::cover::encode::CoverEncoder;
 use crate::runtime::cover::fetch::CoverFetcher;
 use crate::runtime::keymap::{Keymap, chord_from_event};
@@ -493,6 +493,7 @@ impl App {
             self.state.player.current = c.current_song.clone();
             self.state.playback.track = c.current_song;
             self.state.playback.play_url = c.play_url;
+            self.state.browse.lyric_view.offset_ms = c.lyric_offset_ms;
             // lyrics 已在 channel 层结构化清洗,按 current_lyrics_song_id 直接整份收下。
             if let (Some(song_id), Some(lyrics)) = (c.current_lyrics_song_id, c.current_lyrics)
                 && !self.state.library.lyrics.contains_key(&song_id)
@@ -511,6 +512,7 @@ impl App {
             match ev {
                 mineral_protocol::Event::Task(te) => {
                     self.state.apply(&te);
+                    self.report_playlist_write(&te);
                     // apply 落数据后,容器播放意图在此兑现(入队走 client,state.apply 够不着)。
                     self.fulfill_pending_container(&te);
                 }
@@ -539,10 +541,27 @@ impl App {
             }
             Event::FocusGained => self.set_focus(/*focused*/ true),
             Event::FocusLost => self.set_focus(/*focused*/ false),
+            Event::Paste(text) => self.handle_paste(text),
             _ => {}
         }
     }
 
+    /// 括号粘贴落地:活跃浮层收下就整段交给它(歌词编辑器按行拆入草稿)。不收时只在文本
+    /// 输入态按字符重放(输入框都是单行,换行折成空格);非输入态丢弃,免得粘贴内容被当成
+    /// 一串快捷键执行。
+    fn handle_paste(&mut self, text: &str) {
+        if self.overlays.dispatch_paste(text, &self.state) {
+            return;
+        }
+        if !self.state.in_text_input() && !self.overlays.in_text_input() {
+            return;
+        }
+        for c in text.chars().filter(|&c| c != '\r') {
+            let c = if c == '\n' { ' ' } else { c };
+            self.handle_key(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+    }
+
     /// focus 事件落地:起顶栏变灰淡入/淡出(`dim` 开 = 未聚焦)、上报 daemon。
     fn set_focus(&mut self, focused: bool) {
         self.state.dim.set(!focused);
@@ -593,9 +612,12 @@ impl App {
 
         // Shift+Q 硬编码逃生口:退出 + 停掉 daemon。不进 keymap(不可重映射、压过
         // 用户绑定)、压过浮层(确认 / queue 开着也直接退);唯独让位文本输入——
-        // 文本输入态的大写 Q 是字符,不是退出意图(含 channel-search 搜索框)。只看
-        // `Char('Q')` 不看 modifier:部分终端报大写字符时不附带 SHIFT。
-        if !self.state.in_text_input() && key.code == KeyCode::Char('Q') {
+        // 文本输入态的大写 Q 是字符,不是退出意图(含 channel-search 搜索框与歌词编辑器
+        // 文本态)。只看 `Char('Q')` 不看 modifier:部分终端报大写字符时不附带 SHIFT。
+        if !self.state.in_text_input()
+            && !self.overlays.in_text_input()
+            && key.code == KeyCode::Char('Q')
+        {
             self.stop_daemon_on_quit = true;
             // 还停在 Library 内就退出:位置没经过「返回」记录,这里补记。放在转场
             // 起点而非收尾——fire-and-forget 落盘借收缩动画的时长完成,收尾才写
@@ -647,6 +669,7 @@ impl App {
             Action::CyclePlayMode => self.client.cycle_play_mode(),
             Action::NudgeVolume(VolumeDelta(delta)) => self.nudge_volume(delta),
             Action::SeekRelative(SeekDelta(secs)) => self.seek_relative(secs),
+            Action::NudgeLyricOffset(LyricOffsetDelta(ms)) => self.client.nudge_lyric_offset(ms),
             Action::PrevOrRestart => self.client.prev_or_restart(),
             Action::NextSong => self.client.next_song(),
             Action::ToggleLoveSelection => self.toggle_love_selection(),
@@ -656,6 +679,7 @@ impl App {
             Action::OpenCopyMenu => self.open_menu(menus::MenuKind::Copy),
             Action::InvokeScript(slot) => self.invoke_script_action(slot),
             Action::OpenHelp => self.open_help(),
+            Action::OpenLyricEditor => self.open_lyric_editor(),
             // 仅 search 面板内有意义(由 handle_search_panel_key 拦截消费);其它布局态落此 = no-op。
             Action::DrillIntoSelection | Action::CycleDetailSection => {}
             // 仅 queue 浮层内有意义(由其 on_action 消费);其它布局态落此 = no-op。
@@ -736,6 +760,14 @@ impl App {
                 self.overlays.close_top();
                 self.run_menu_action(action);
             }
+            OverlayAction::SaveLyrics { song_id, lrc } => {
+                self.client.save_lyrics(song_id, lrc);
+                self.overlays.close_top();
+            }
+            OverlayAction::PlaylistWrites(ops) => {
+                self.submit_playlist_writes(ops);
+                self.overlays.close_top();
+            }
         }
     }
 
@@ -749,12 +781,13 @@ impl App {
         }
     }
 
-    /// 半穿透白名单:歌词切换 + 播放控制族 + 通知卡关闭 + cheatsheet(任何半穿透
+    /// 半穿透白名单:歌词切换 / 偏移 + 播放控制族 + 通知卡关闭 + cheatsheet(任何半穿透
     /// 浮层上都能叠开);列表 / 视图 / 其余浮层动作不穿透。
     fn passes_overlay(action: Action) -> bool {
         matches!(
             action,
             Action::CycleLyricExtra
+                | Action::NudgeLyricOffset(_)
                 | Action::TogglePlayPause
                 | Action::CyclePlayMode
                 | Action::NudgeVolume(_)
@@ -790,6 +823,19 @@ impl App {
         self.overlays
             .push(OverlayKind::help(self.keymap.help().to_vec(), close_hint));
     }
+
+    /// 为在播曲打开歌词编辑器(以已缓存歌词为底稿);无在播曲时闪一条提示。
+    fn open_lyric_editor(&mut self) {
+        let Some(song) = self.state.playback.track.clone() else {
+            self.notifications.flash(tinted_text_item(
+                "没有在播的歌,无法编辑歌词".to_owned(),
+                TextTint::Error,
+            ));
+            return;
+        };
+        self.overlays
+            .push(OverlayKind::lyric_editor(song, &self.state));
+    }
 }
 
 #[cfg(test)]
@@ -972,6 +1018,37 @@ mod tests {
         Ok(())
     }
 
+    /// 集成:e 为在播曲开歌词编辑器(现有歌词作底稿直接进打轴态)→ 回车打轴 → ^s 把整份
+    /// LRC 交 client 存为该曲的覆盖,编辑器随之关闭;编辑器文本态下 Shift+Q 是字符不退出。
+    #[test]
+    fn lyric_editor_stamps_and_saves() -> color_eyre::Result<()> {
+        let (mut app, saved) = crate::test_support::app_in_fullscreen_lyrics_probe()?;
+        press(&mut app, KeyCode::Char('e'));
+        assert_eq!(app.overlays.len(), 1, "e 开歌词编辑器");
+        assert!(!app.overlays.in_text_input(), "有底稿直接进打轴态");
+
+        press(&mut app, KeyCode::Tab);
+        assert!(app.overlays.in_text_input(), "Tab 切到文本态");
+        press(&mut app, KeyCode::Char('Q'));
+        assert!(!app.stop_daemon_on_quit, "文本态 Shift+Q 让位给字符");
+        press(&mut app, KeyCode::Tab);
+
+        press(&mut app, KeyCode::Enter);
+        press_ctrl(&mut app, KeyCode::Char('s'));
+
+        let sent = saved
+            .lock()
+            .map_err(|_poisoned| color_eyre::eyre::eyre!("存盘记录被毒化"))?;
+        let Some((song_id, lrc)) = sent.first() else {
+            color_eyre::eyre::bail!("^s 应送出一次 save_lyrics");
+        };
+        let current = app.state.playback.track.as_ref().map(|s| s.id.qualified());
+        assert_eq!(Some(song_id), current.as_ref(), "目标是在播曲");
+        assert!(lrc.starts_with('['), "首行已打轴: {lrc}");
+        assert!(lrc.ends_with('Q'), "文本态敲入的字符并入末行");
+        Ok(())
+    }
+
     /// 集成回归:Tab 开队列 → 按键经 dispatch 路由到 queue 浮层移动光标,且**不被
     /// server sync tick 弹回**。此前 apply 每帧用 server 的
     /// 「在播锚点」覆盖 UI 光标,导致按键看似无效;现在光标归 overlay 私有、只 clamp。
@@ -1238,6 +1315,34 @@ mod tests {
         Ok(())
     }
 
+    /// 括号粘贴:歌词编辑器整段收下按行拆入(不当回车逐行提交);搜索框按字符重放、换行折空格;
+    /// 非输入态丢弃,不触发快捷键。
+    #[test]
+    fn paste_routes_to_editor_or_text_input() -> color_eyre::Result<()> {
+        let mut app = app_with_queue(3, /*current_idx*/ 0)?;
+        app.handle_event(&Event::Paste("Q?".to_owned()));
+        assert!(app.transition.is_none(), "非输入态粘贴不触发 Shift+Q");
+        assert!(!app.overlays.has_help(), "非输入态粘贴不当快捷键");
+
+        app.open_lyric_editor();
+        app.handle_event(&Event::Paste("第一句\n第二句\n".to_owned()));
+        assert_eq!(
+            app.overlays.lyric_draft(),
+            Some(vec!["第一句".to_owned(), "第二句".to_owned()]),
+            "整段粘贴按行拆入草稿"
+        );
+        press(&mut app, KeyCode::Esc);
+
+        app.state
+            .browse
+            .view
+            .switch_to(crate::runtime::state::View::Library);
+        press(&mut app, KeyCode::Char('/'));
+        app.handle_event(&Event::Paste("a\r\nb".to_owned()));
+        assert_eq!(app.state.browse.search.query(), "a b");
+        Ok(())
+    }
+
     /// 搜索输入态的大写 'Q' 是搜索词,不触发 Shift+Q 退出(硬编码键让位文本输入)。
     #[test]
     fn shift_q_in_search_mode_types_into_query() -> color_eyre::Result<()> {
@@ -4,10 +4,11 @@
 //! 锚点 = 选中行的屏幕矩形,由上一帧面积([`AppState::frame_area`])重算布局 +
 //! 列表滚动态的只读 offset 还原;菜单贴行下方弹出(`Placement::Below`)。
 
+use mineral_channel_mineral::{favorites_playlist_id, is_mix_playlist, smart_playlist_id};
 use mineral_config::{CopyContext, CopyTemplate};
-use mineral_model::{Album, Artist, ArtistRef, Song};
+use mineral_model::{Album, Artist, ArtistRef, Playlist, Song, SourceKind};
 use mineral_protocol::{CopyTemplateCtx, QueueAnchor, QueueOp, QueuePos};
-use mineral_task::SearchPayload;
+use mineral_task::{PlaylistWriteOp, SearchPayload};
 use ratatui::layout::Rect;
 
 use crate::components::layout::search::detail::detail_list_area;
@@ -15,6 +16,7 @@ use crate::components::layout::shared::compute::{compute, compute_search};
 use crate::components::popup::{
     ContainerRef, MenuAction, MenuItem, OverlayKind, Placement, PopMenu,
 };
+use crate::components::toast::notifications::{TextTint, tinted_text_item};
 use crate::runtime::scroll::list::{ScrollList, ScrollMotion};
 use crate::runtime::scroll::viewport::pin_cursor;
 use crate::runtime::state::{DetailFrame, EntityRef, SearchFocus, View};
@@ -131,6 +133,46 @@ impl App {
         )));
     }
 
+    /// 「加入歌单」选择器:列出歌单库里的混源歌单,选中即追加该歌;贴当前 list 选中行下方
+    /// 弹出(操作菜单已关,锚点不变)。一张混源歌单都没有时 toast 提示先新建。
+    ///
+    /// # Params:
+    ///   - `song`: 要加入的歌
+    pub(crate) fn open_add_to_playlist_menu(&mut self, song: &Song) {
+        let Some(sel) = self.current_list_selection() else {
+            return;
+        };
+        let items = self
+            .state
+            .library
+            .playlists
+            .iter()
+            .filter(|p| is_mix_playlist(&p.data.id))
+            .map(|p| {
+                MenuItem::labeled(
+                    p.data.name.clone(),
+                    MenuAction::PlaylistWrite(PlaylistWriteOp::AddSongs {
+                        id: p.data.id.clone(),
+                        songs: vec![song.id.clone()],
+                    }),
+                )
+            })
+            .collect::<Vec<MenuItem>>();
+        if items.is_empty() {
+            self.notifications.flash(tinted_text_item(
+                "no playlists yet, create one from the playlists view".to_owned(),
+                TextTint::Error,
+            ));
+            return;
+        }
+        self.overlays.push(OverlayKind::menu(PopMenu::new(
+            "add to playlist",
+            items,
+            sel.anchor,
+            Placement::Below,
+        )));
+    }
+
     /// queue 浮层 `y` 的落地:为队列第 `idx` 项构造复制菜单,贴 `anchor`(队列行下方)弹在
     /// queue 浮层**之上**(不关 queue)。空队列下标 / 空项静默。复用 [`Self::copy_items`]——
     /// 与全站复制同一套;queue 是 [`Self::current_list_selection`] resolver 之外的唯一接缝
@@ -232,26 +274,46 @@ impl App {
     }
 
     /// 选中实体的 `o` 操作项(按实体类型 + 面种类)。歌曲给队列动作(`p` 替换队列起播取所在
-    /// 列表整列作上下文);容器(专辑/歌单/artist)给播放全部 / 加入队列(见 [`container_action_items`])。
+    /// 列表整列作上下文)与 `t` 加入歌单,混源歌单内另带挪位 / 移出(见
+    /// [`Self::mix_song_items`]);容器(专辑/歌单/artist)给播放全部 / 加入队列(见
+    /// [`container_action_items`]),聚合源歌单另带混源 / 智能歌单管理项(见
+    /// [`Self::mix_playlist_items`]、[`Self::smart_playlist_items`]),危险项统一置底。
     fn action_items(&self, entity: &EntityRef, surface: SurfaceKind) -> Vec<MenuItem> {
         match entity {
-            EntityRef::Song(song) => vec![
-                MenuItem::keyed(
-                    'p',
-                    "Play",
-                    MenuAction::Play {
-                        song: song.clone(),
-                        queue: self.surface_song_queue(surface),
-                        context: self.surface_play_context(surface),
-                    },
-                ),
-                MenuItem::keyed('n', "Play next", MenuAction::PlayNext(song.clone())),
-                MenuItem::keyed('a', "Append to queue", MenuAction::Append(song.clone())),
-                MenuItem::keyed('d', "Download", MenuAction::Download(song.clone())),
-            ],
+            EntityRef::Song(song) => {
+                let mut items = vec![
+                    MenuItem::keyed(
+                        'p',
+                        "Play",
+                        MenuAction::Play {
+                            song: song.clone(),
+                            queue: self.surface_song_queue(surface),
+                            context: self.surface_play_context(surface),
+                        },
+                    ),
+                    MenuItem::keyed('n', "Play next", MenuAction::PlayNext(song.clone())),
+                    MenuItem::keyed('a', "Append to queue", MenuAction::Append(song.clone())),
+                    MenuItem::keyed('d', "Download", MenuAction::Download(song.clone())),
+                    MenuItem::keyed(
+                        't',
+                        "Add to playlist",
+                        MenuAction::AddToPlaylist(song.clone()),
+                    ),
+                ];
+                if surface == SurfaceKind::BrowseLibrary {
+                    items.extend(self.mix_song_items(song));
+                }
+                items
+            }
             EntityRef::Album(album) => container_action_items(ContainerRef::Album(album.clone())),
             EntityRef::Playlist(playlist) => {
-                container_action_items(ContainerRef::Playlist(playlist.clone()))
+                let mut items = container_action_items(ContainerRef::Playlist(playlist.clone()));
+                if surface == SurfaceKind::BrowsePlaylists {
+                    items.extend(mix_playlist_items(playlist));
+                    items.extend(self.smart_playlist_items(playlist));
+                    items.sort_by_key(|it| it.destructive);
+                }
+                items
             }
             EntityRef::Artist(artist) => {
                 container_action_items(ContainerRef::Artist(artist.clone()))
@@ -259,6 +321,89 @@ impl App {
         }
     }
 
+    /// 混源歌单内歌曲的管理项(浏览态 Library 面,当前歌单是混源歌单时):`u` / `v` 在整列
+    /// (非过滤投影)里上 / 下挪一位(到头的方向不出),`r` 移出歌单。
+    fn mix_song_items(&self, song: &Song) -> Vec<MenuItem> {
+        let Some(playlist) = self.state.selected_playlist() else {
+            return Vec::new();
+        };
+        let id = &playlist.data.id;
+        if !is_mix_playlist(id) {
+            return Vec::new();
+        }
+        let tracks = self.state.current_tracks_slot();
+        let Some(idx) = tracks.and_then(|v| v.iter().position(|sv| sv.data.id == song.id)) else {
+            return Vec::new();
+        };
+        let len = tracks.map_or(0, Vec::len);
+        let move_to = |to: usize| {
+            MenuAction::PlaylistWrite(PlaylistWriteOp::MoveSong {
+                id: id.clone(),
+                song: song.id.clone(),
+                to,
+            })
+        };
+        let mut items = Vec::new();
+        if let Some(up) = idx.checked_sub(1) {
+            items.push(MenuItem::keyed('u', "Move up", move_to(up)));
+        }
+        let down = idx.saturating_add(1);
+        if down < len {
+            items.push(MenuItem::keyed('v', "Move down", move_to(down)));
+        }
+        items.push(
+            MenuItem::keyed(
+                'r',
+                "Remove from playlist",
+                MenuAction::PlaylistWrite(PlaylistWriteOp::RemoveSongs {
+                    id: id.clone(),
+                    songs: vec![song.id.clone()],
+                }),
+            )
+            .destructive(),
+        );
+        items
+    }
+
+    /// 聚合源歌单的智能歌单管理项(浏览态 Playlists 面):任一张上都可新建;用户自建的
+    /// 还可编辑 / 删除。聚合收藏、混源歌单与 config 声明的智能歌单不出这两项。
+    fn smart_playlist_items(&self, playlist: &Playlist) -> Vec<MenuItem> {
+        if playlist.id.namespace() != SourceKind::MINERAL {
+            return Vec::new();
+        }
+        let mut items = vec![MenuItem::keyed(
+            's',
+            "New smart playlist",
+            MenuAction::NewSmartPlaylist,
+        )];
+        let declared = self
+            .state
+            .cfg
+            .sources()
+            .mineral()
+            .smart_playlists()
+            .iter()
+            .any(|p| smart_playlist_id(p.name()) == playlist.id);
+        if playlist.id != favorites_playlist_id() && !is_mix_playlist(&playlist.id) && !declared {
+            items.push(MenuItem::keyed(
+                'e',
+                "Edit smart playlist",
+                MenuAction::EditSmartPlaylist(Box::new(playlist.clone())),
+            ));
+            items.push(
+                MenuItem::keyed(
+                    'x',
+                    "Delete smart playlist",
+                    MenuAction::PlaylistWrite(PlaylistWriteOp::Delete {
+                        id: playlist.id.clone(),
+                    }),
+                )
+                .destructive(),
+            );
+        }
+        items
+    }
+
     /// 某 list 面的「整列歌曲」(`Play` 的队列上下文,语义同该面 activate 起播):
     /// Library 取当前全列曲目(非过滤投影,与 Enter 一致)、search 结果列取整列结果(仅歌曲
     /// kind),其余面无歌曲列表给空(落地时退化为单曲队列)。
@@ -741,6 +886,37 @@ fn container_action_items(container: ContainerRef) -> Vec<MenuItem> {
     items
 }
 
+/// 聚合源歌单的混源歌单管理项(浏览态 Playlists 面):任一张上都可 `c` 新建;混源歌单
+/// 本身还可 `r` 改名 / `x` 删除。
+fn mix_playlist_items(playlist: &Playlist) -> Vec<MenuItem> {
+    if playlist.id.namespace() != SourceKind::MINERAL {
+        return Vec::new();
+    }
+    let mut items = vec![MenuItem::keyed(
+        'c',
+        "New playlist",
+        MenuAction::NewPlaylist,
+    )];
+    if is_mix_playlist(&playlist.id) {
+        items.push(MenuItem::keyed(
+            'r',
+            "Rename playlist",
+            MenuAction::RenamePlaylist(Box::new(playlist.clone())),
+        ));
+        items.push(
+            MenuItem::keyed(
+                'x',
+                "Delete playlist",
+                MenuAction::PlaylistWrite(PlaylistWriteOp::Delete {
+                    id: playlist.id.clone(),
+                }),
+            )
+            .destructive(),
+        );
+    }
+    items
+}
+
 /// 结果实体的来源(由各自 id 的 namespace 派生);供查 caps 取网页模板。
 fn entity_source(entity: &EntityRef) -> mineral_model::SourceKind {
     match entity {
@@ -765,7 +941,7 @@ mod tests {
 
     use super::{
         album_copy_items, append_template_items, artist_copy_items, container_action_items,
-        playlist_copy_items, row_anchor, song_copy_items,
+        mix_playlist_items, playlist_copy_items, row_anchor, song_copy_items,
     };
     use crate::app::App;
     use crate::components::layout::shared::compute::compute_search;
@@ -932,6 +1108,105 @@ mod tests {
         Ok(())
     }
 
+    /// 智能歌单管理项只挂聚合源:任一张可新建;自建的另可编辑 / 删除,聚合收藏不可。
+    #[test]
+    fn smart_playlist_items_only_on_mineral() -> color_eyre::Result<()> {
+        let app = app_with_library(/*len*/ 1, /*sel_track*/ 0)?;
+        let playlist = |id: PlaylistId| Playlist::builder().id(id).name("P".to_owned()).build();
+        let hotkeys = |p: &Playlist| {
+            app.smart_playlist_items(p)
+                .iter()
+                .map(|it| it.hotkey)
+                .collect::<Vec<_>>()
+        };
+        assert!(hotkeys(&playlist(PlaylistId::new(SourceKind::NETEASE, "p1"))).is_empty());
+        assert_eq!(
+            hotkeys(&playlist(mineral_channel_mineral::favorites_playlist_id())),
+            vec![Some('s')]
+        );
+        let mine = playlist(mineral_channel_mineral::smart_playlist_id("mine"));
+        let items = app.smart_playlist_items(&mine);
+        assert_eq!(
+            items.iter().map(|it| it.hotkey).collect::<Vec<_>>(),
+            vec![Some('s'), Some('e'), Some('x')]
+        );
+        assert_eq!(
+            items.last().and_then(|it| it.action.clone()),
+            Some(MenuAction::PlaylistWrite(
+                mineral_task::PlaylistWriteOp::Delete { id: mine.id }
+            ))
+        );
+        Ok(())
+    }
+
+    /// 混源歌单管理项:歌单面上任一聚合源歌单可新建,混源歌单本身另可改名 / 删除,
+    /// 且不出智能歌单的编辑 / 删除。
+    #[test]
+    fn mix_playlist_items_on_mineral_playlists() -> color_eyre::Result<()> {
+        let app = app_with_library(/*len*/ 1, /*sel_track*/ 0)?;
+        let playlist = |id: PlaylistId| Playlist::builder().id(id).name("P".to_owned()).build();
+        assert!(
+            mix_playlist_items(&playlist(PlaylistId::new(SourceKind::NETEASE, "p1"))).is_empty()
+        );
+        let hotkeys = |items: Vec<crate::components::popup::MenuItem>| {
+            items.iter().map(|it| it.hotkey).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            hotkeys(mix_playlist_items(&playlist(
+                mineral_channel_mineral::favorites_playlist_id()
+            ))),
+            vec![Some('c')]
+        );
+        let mix = playlist(mineral_channel_mineral::mix_playlist_id(1));
+        assert_eq!(
+            hotkeys(mix_playlist_items(&mix)),
+            vec![Some('c'), Some('r'), Some('x')]
+        );
+        assert_eq!(hotkeys(app.smart_playlist_items(&mix)), vec![Some('s')]);
+        Ok(())
+    }
+
+    /// 混源歌单内的歌:按整列位置出上 / 下挪(到头的方向不出),移出置底;别的歌单不出。
+    #[test]
+    fn mix_song_items_follow_position() -> color_eyre::Result<()> {
+        let mut app = app_with_library(/*len*/ 3, /*sel_track*/ 0)?;
+        let songs = endserenading(3);
+        let [first, middle, _] = songs.as_slice() else {
+            return Err(color_eyre::eyre::eyre!("应有三首"));
+        };
+        assert!(app.mix_song_items(first).is_empty(), "远端歌单不出");
+
+        let pid = PlaylistId::new(SourceKind::NETEASE, "p1");
+        let mix = mineral_channel_mineral::mix_playlist_id(1);
+        let tracks = app.state.library.tracks.remove(&pid).unwrap_or_default();
+        app.state.library.tracks.insert(mix.clone(), tracks);
+        for p in &mut app.state.library.playlists {
+            p.data.id = mix.clone();
+        }
+
+        let hotkeys = |items: &[crate::components::popup::MenuItem]| {
+            items.iter().map(|it| it.hotkey).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            hotkeys(&app.mix_song_items(first)),
+            vec![Some('v'), Some('r')]
+        );
+        let items = app.mix_song_items(middle);
+        assert_eq!(hotkeys(&items), vec![Some('u'), Some('v'), Some('r')]);
+        assert_eq!(
+            items.first().and_then(|it| it.action.clone()),
+            Some(MenuAction::PlaylistWrite(
+                mineral_task::PlaylistWriteOp::MoveSong {
+                    id: mix,
+                    song: middle.id.clone(),
+                    to: 0,
+                }
+            ))
+        );
+        assert!(items.last().is_some_and(|it| it.destructive));
+        Ok(())
+    }
+
     /// Playlists 面 `o`→`n`:歌单曲目未缓存 → 登记「整单按序插播」意图(待详情到货兑现)。
     #[test]
     fn o_menu_play_next_on_playlists_registers_intent() -> color_eyre::Result<()> {
@@ -1500,7 +1775,7 @@ mod tests {
         }
         terminal.draw(|f| crate::view::draw(f, &app))?;
         crate::test_support::assert_snap!(
-            "Library 视图 o 操作菜单稳态(贴选中行下方,p/a/d 三项)",
+            "Library 视图 o 操作菜单稳态(贴选中行下方,p/n/a/d/t 五项)",
             terminal.backend()
         );
         Ok(())
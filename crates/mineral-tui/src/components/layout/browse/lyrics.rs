@@ -43,6 +43,7 @@ pub fn draw(frame: &mut Frame<'_>, area: Rect, state: &AppState, theme: &Theme,
             lines.is_some_and(mineral_model::has_words),
             lines.is_some_and(mineral_model::has_timed),
             trust,
+            state.browse.lyric_view.offset_ms,
             theme,
             ink,
         )))
@@ -67,7 +68,8 @@ pub fn draw(frame: &mut Frame<'_>, area: Rect, state: &AppState, theme: &Theme,
         draw_fallback(frame, inner, ink);
         return;
     };
-    let position_ms = state.playback.position_ms;
+    // 歌词时钟:播放位置已折算 per-song 偏移,定位与逐字 wipe 同源。
+    let position_ms = state.lyric_position_ms();
     // 时间轴失真(顶换流时长对不上)→ 不认当前行:无高亮、无自动跟随,退成静态整篇
     //(手动滚动照常),与「无时间戳歌词」走同一条渲染路径。
     let cur = match trust {
@@ -105,12 +107,14 @@ pub fn draw(frame: &mut Frame<'_>, area: Rect, state: &AppState, theme: &Theme,
 /// 不同高亮区分——行级 `synced` 用 accent_2(sapphire),逐字 `synced ✦` 用 accent
 /// (mauve);`lyrics · ` 前缀恒弱化(strong 档)。顶换流:[`SyncTrust::Borrowed`] 在
 /// synced 后缀 `~`(yellow,「可能漂移」);[`SyncTrust::Broken`] 整档换成
-/// `unsynced`(yellow,同步已放弃)。
+/// `unsynced`(yellow,同步已放弃)。同步档下歌词偏移非零时再缀 `+150ms`(muted),
+/// 提示当前曲的时间轴已被手动校正。
 ///
 /// # Params:
 ///   - `has_words`: 是否有逐字歌词
 ///   - `has_lrc`: 是否有行级 LRC
 ///   - `trust`: 时间轴信任档(无 LRC 时无同步可言,不参与)
+///   - `offset_ms`: 当前歌的歌词时间偏移(0 = 未校正,不显示)
 ///   - `theme`: 取色
 ///   - `ink`: 对实际背景现算的弱化色阶(前缀 / 空隙用 strong 档)
 ///
@@ -120,6 +124,7 @@ fn title_left_spans(
     has_words: bool,
     has_lrc: bool,
     trust: SyncTrust,
+    offset_ms: i64,
     theme: &Theme,
     ink: Ink,
 ) -> Vec<Span<'static>> {
@@ -150,6 +155,12 @@ fn title_left_spans(
     if trust == SyncTrust::Borrowed {
         spans.push(Span::styled(" ~", mark(theme.yellow)));
     }
+    if offset_ms != 0 {
+        spans.push(Span::styled(
+            format!(" {offset_ms:+}ms"),
+            Style::new().fg(ink.muted),
+        ));
+    }
     spans.push(Span::styled(" ", base));
     spans
 }
@@ -993,15 +1004,36 @@ mod tests {
         let th = Theme::default();
         let ink = th.ink_over(ratatui::style::Color::Reset);
         assert_eq!(
-            text_of(&title_left_spans(false, false, SyncTrust::Native, &th, ink)),
+            text_of(&title_left_spans(
+                false,
+                false,
+                SyncTrust::Native,
+                0,
+                &th,
+                ink
+            )),
             " lyrics "
         );
         assert_eq!(
-            text_of(&title_left_spans(false, true, SyncTrust::Native, &th, ink)),
+            text_of(&title_left_spans(
+                false,
+                true,
+                SyncTrust::Native,
+                0,
+                &th,
+                ink
+            )),
             " lyrics · synced "
         );
         assert_eq!(
-            text_of(&title_left_spans(true, true, SyncTrust::Native, &th, ink)),
+            text_of(&title_left_spans(
+                true,
+                true,
+                SyncTrust::Native,
+                0,
+                &th,
+                ink
+            )),
             " lyrics · synced ✦ "
         );
         // 顶换流:Borrowed 后缀 ~;Broken 整档换 unsynced;无 LRC 时信任档不参与。
@@ -1010,23 +1042,68 @@ mod tests {
                 false,
                 true,
                 SyncTrust::Borrowed,
+                0,
                 &th,
                 ink
             )),
             " lyrics · synced ~ "
         );
         assert_eq!(
-            text_of(&title_left_spans(true, true, SyncTrust::Borrowed, &th, ink)),
+            text_of(&title_left_spans(
+                true,
+                true,
+                SyncTrust::Borrowed,
+                0,
+                &th,
+                ink
+            )),
             " lyrics · synced ✦ ~ "
         );
         assert_eq!(
-            text_of(&title_left_spans(true, true, SyncTrust::Broken, &th, ink)),
+            text_of(&title_left_spans(
+                true,
+                true,
+                SyncTrust::Broken,
+                0,
+                &th,
+                ink
+            )),
             " lyrics · unsynced "
         );
         assert_eq!(
-            text_of(&title_left_spans(false, false, SyncTrust::Broken, &th, ink)),
+            text_of(&title_left_spans(
+                false,
+                false,
+                SyncTrust::Broken,
+                0,
+                &th,
+                ink
+            )),
             " lyrics "
         );
+        // 歌词偏移:同步档缀带符号毫秒;unsynced 档不显示(无同步可校)。
+        assert_eq!(
+            text_of(&title_left_spans(
+                false,
+                true,
+                SyncTrust::Borrowed,
+                -150,
+                &th,
+                ink
+            )),
+            " lyrics · synced ~ -150ms "
+        );
+        assert_eq!(
+            text_of(&title_left_spans(
+                true,
+                true,
+                SyncTrust::Broken,
+                50,
+                &th,
+                ink
+            )),
+            " lyrics · unsynced "
+        );
     }
 
     /// 顶换流时长差超阈(Broken):放弃逐行同步——窗口锚回篇首、无当前行高亮,
@@ -762,11 +762,8 @@ mod tests {
             Some(bilibili),
             "bilibili 行序号染 bilibili 色"
         );
-        assert_eq!(
-            fg_of(4, "2"),
-            Some(theme.subtext),
-            "local 未配置色,退中立兜底"
-        );
+        let local = resolve_source_color(&theme, state.cfg.sources(), SourceKind::LOCAL);
+        assert_eq!(fg_of(4, "2"), Some(local), "local 行序号染 local 色");
         Ok(())
     }
 
@@ -139,6 +139,18 @@ pub(crate) enum OverlayAction {
 
     /// PopMenu 确认了一项:关闭菜单并执行该动作。
     Menu(super::menu::MenuAction),
+
+    /// 歌词编辑器存盘:把 LRC 交 server 落为该曲的歌词覆盖,并关闭编辑器。
+    SaveLyrics {
+        /// 目标曲。
+        song_id: mineral_model::SongId,
+
+        /// 整份 LRC 文本。
+        lrc: String,
+    },
+
+    /// 智能歌单编辑确认:按序提交这串歌单写操作(建单 / 改名 / 改表达式),并关闭浮层。
+    PlaylistWrites(Vec<mineral_task::PlaylistWriteOp>),
 }
 
 /// 浮层抽象:实现方只声明四件事,chrome 自动包办居中 layout + 弹出动画。
@@ -175,6 +187,12 @@ pub(crate) trait Overlay {
     fn on_action(&mut self, _action: Action, _ctx: &AppState) -> Option<OverlayResponse> {
         None
     }
+
+    /// 处理一次括号粘贴(整段文本一次到达)。返回 `false` 表示本浮层不收粘贴,
+    /// App 回落为按字符重放;默认不收。
+    fn on_paste(&mut self, _text: &str, _ctx: &AppState) -> bool {
+        false
+    }
 }
 
 /// 统一外框底 Block:圆角边框 + mantle 背景。各 overlay 在此之上加 title / 边框色,
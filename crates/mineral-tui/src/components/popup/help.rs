@@ -230,7 +230,7 @@ impl Overlay for HelpOverlay {
                 self.scroll_by(scroll::viewport::step_delta(step, ctx.cfg.tui().behavior()));
                 Some(OverlayResponse::Consumed)
             }
-            // 播放控制族 + 歌词切换 + 关通知:不认 → 回落裸键 Pass(半穿透,边看边试)。
+            // 播放控制族 + 歌词切换 / 偏移 + 关通知:不认 → 回落裸键 Pass(半穿透,边看边试)。
             Action::TogglePlayPause
             | Action::CyclePlayMode
             | Action::NudgeVolume(_)
@@ -238,6 +238,7 @@ impl Overlay for HelpOverlay {
             | Action::PrevOrRestart
             | Action::NextSong
             | Action::CycleLyricExtra
+            | Action::NudgeLyricOffset(_)
             | Action::DismissNotice => None,
             // 其余(列表激活 / 下载 / 菜单 / 布局切换…)显式吞掉:cheatsheet 盖住
             // 主视图,不能让动作打在看不见的列表上。
@@ -252,6 +253,7 @@ impl Overlay for HelpOverlay {
             | Action::DownloadSelection
             | Action::OpenActionMenu
             | Action::OpenCopyMenu
+            | Action::OpenLyricEditor
             | Action::ReorderSelection(_)
             | Action::JumpToCurrent
             | Action::InvokeScript(_) => Some(OverlayResponse::Consumed),
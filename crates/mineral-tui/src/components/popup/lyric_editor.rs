@@ -0,0 +1,586 @@
+//! 歌词编辑器浮层(LRC 打轴):居中 modal,两态切换。
+//!
+//! - **文本态**:逐行敲入 / 整段粘贴歌词(括号粘贴整段一次到达,按行拆入),或 `^o` 输入
+//!   路径载入纯文本文件(`.txt`);打开时以该曲现有歌词为底稿。
+//! - **打轴态**:边播边按回车,给光标行盖上当前歌词时间、光标下移;退格撤回上一行。
+//!   其余键半穿透给全局播放控制(空格暂停 / 方向键 seek),方便对着歌声反复校。
+//!
+//! 存盘经 server 落为该曲的 `.lrc` 覆盖(歌词覆盖目录),此后播放先于其他歌词。
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mineral_model::Song;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+use crate::components::popup::component::{
+    Chrome, Overlay, OverlayAction, OverlayResponse, base_block,
+};
+use crate::render::cursor::cursor_spans;
+use crate::render::theme::Theme;
+use crate::runtime::line_input::{InputRequest, LineInput};
+use crate::runtime::lrc_draft::LrcDraft;
+use crate::runtime::state::AppState;
+
+/// 时间戳列宽(`[mm:ss.cc] `)。
+const STAMP_W: usize = 11;
+
+/// 编辑器当前态。
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// 敲入 / 粘贴文本。
+    Text,
+
+    /// 边播边打轴。
+    Stamp,
+
+    /// 输入要载入的文本文件路径(文本态 `^o` 进入,回车载入 / esc 返回文本态)。
+    Open,
+}
+
+/// 歌词编辑器浮层。目标曲在打开瞬间定格,中途切歌不会把时间打到别的歌上。
+pub(crate) struct LyricEditorOverlay {
+    /// 目标曲。
+    song: Song,
+
+    /// 行 + 时间戳草稿。
+    draft: LrcDraft,
+
+    /// 文本态正在敲的一行(回车并入草稿)。
+    input: LineInput,
+
+    /// 载入态正在敲的文件路径。
+    path: LineInput,
+
+    /// 载入失败的提示(状态行红字;载入成功或离开载入态清掉)。
+    notice: Option<String>,
+
+    /// 当前态。
+    mode: Mode,
+}
+
+impl LyricEditorOverlay {
+    /// 为 `song` 打开编辑器,以其现有歌词为底稿;底稿为空从文本态起,否则直接进打轴态。
+    ///
+    /// # Params:
+    ///   - `song`: 目标曲(通常是在播曲)
+    ///   - `ctx`: 只读后端态(取该曲已缓存的歌词)
+    pub(crate) fn new(song: Song, ctx: &AppState) -> Self {
+        let draft = LrcDraft::from_lyrics(ctx.library.lyrics.get(&song.id));
+        let mode = if draft.lines().is_empty() {
+            Mode::Text
+        } else {
+            Mode::Stamp
+        };
+        Self {
+            song,
+            draft,
+            input: LineInput::new(),
+            path: LineInput::new(),
+            notice: None,
+            mode,
+        }
+    }
+
+    /// 是否处于文本输入态(文本态 / 载入态;Shift+Q 逃生口据此让位给字符输入)。
+    pub(crate) fn is_typing(&self) -> bool {
+        matches!(self.mode, Mode::Text | Mode::Open)
+    }
+
+    /// 测试用:草稿各行文本。
+    #[cfg(test)]
+    pub(crate) fn draft_texts(&self) -> Vec<String> {
+        self.draft.lines().iter().map(|l| l.text.clone()).collect()
+    }
+
+    /// 把正在敲的一行并入草稿。
+    fn commit_input(&mut self) {
+        if !self.input.is_empty() {
+            self.draft.push_text(self.input.text());
+            self.input.clear();
+        }
+    }
+
+    /// 载入路径输入框里的文件:成功回文本态,失败留在载入态并给出提示。
+    fn load_path(&mut self) {
+        let loaded = mineral_paths::expand_home(self.path.text().trim())
+            .and_then(|path| self.draft.load_file(&path));
+        match loaded {
+            Ok(_) => {
+                self.path.clear();
+                self.notice = None;
+                self.mode = Mode::Text;
+            }
+            Err(e) => self.notice = Some(format!("{e}")),
+        }
+    }
+
+    /// 目标曲是否正是在播曲(否则打轴无意义:时钟属于别的歌)。
+    fn is_playing_target(&self, ctx: &AppState) -> bool {
+        ctx.playback
+            .track
+            .as_ref()
+            .is_some_and(|t| t.id == self.song.id)
+    }
+
+    /// 存盘意图:先并入未回车的一行;草稿全空无事可存。
+    fn save(&mut self) -> OverlayResponse {
+        self.commit_input();
+        match self.draft.to_lrc() {
+            Some(lrc) => OverlayResponse::Do(OverlayAction::SaveLyrics {
+                song_id: self.song.id.clone(),
+                lrc,
+            }),
+            None => OverlayResponse::Consumed,
+        }
+    }
+
+    /// 文本态按键:模态吞键(含空格),不半穿透。`^o` 进载入态。
+    fn on_text_key(&mut self, key: &KeyEvent) -> OverlayResponse {
+        match key.code {
+            KeyCode::Enter => self.commit_input(),
+            KeyCode::Backspace => {
+                if self.input.is_empty() {
+                    // 空行退格:退回上一行末尾续改。
+                    if let Some(prev) = self.draft.pop_line() {
+                        self.input.set_text(prev);
+                    }
+                } else {
+                    self.input.apply(InputRequest::DeletePrev);
+                }
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.commit_input();
+                self.mode = Mode::Open;
+            }
+            _ => edit_line(&mut self.input, key),
+        }
+        OverlayResponse::Consumed
+    }
+
+    /// 载入态按键:回车载入、其余编辑路径;模态吞键。
+    fn on_open_key(&mut self, key: &KeyEvent) -> OverlayResponse {
+        match key.code {
+            KeyCode::Enter => self.load_path(),
+            KeyCode::Backspace => {
+                self.path.apply(InputRequest::DeletePrev);
+            }
+            _ => edit_line(&mut self.path, key),
+        }
+        OverlayResponse::Consumed
+    }
+
+    /// 打轴态按键:回车打轴、退格撤回、上下移光标;其余半穿透给全局(播放控制族)。
+    fn on_stamp_key(&mut self, key: &KeyEvent, ctx: &AppState) -> OverlayResponse {
+        match key.code {
+            KeyCode::Enter => {
+                if self.is_playing_target(ctx) {
+                    self.draft.stamp(ctx.lyric_position_ms());
+                }
+                OverlayResponse::Consumed
+            }
+            KeyCode::Backspace => {
+                self.draft.unstamp_prev();
+                OverlayResponse::Consumed
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.draft.move_cursor(/*down*/ true);
+                OverlayResponse::Consumed
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.draft.move_cursor(/*down*/ false);
+                OverlayResponse::Consumed
+            }
+            _ => OverlayResponse::Pass,
+        }
+    }
+
+    /// 底栏按键提示(随态切换)。
+    fn hint(&self) -> &'static str {
+        match self.mode {
+            Mode::Text => " ⏎ new line · ⌫ back · ^o open · tab stamp · ^s save · esc cancel ",
+            Mode::Stamp => " ⏎ stamp · ⌫ undo · j/k move · tab text · ^s save · esc cancel ",
+            Mode::Open => " ⏎ load · esc back ",
+        }
+    }
+
+    /// 顶部状态行:态 + 已打轴进度;打轴态且目标曲不在播时给出警示。
+    fn status_line(&self, ctx: &AppState, theme: &Theme) -> Line<'static> {
+        let progress = format!(
+            "{}/{} stamped",
+            self.draft.stamped(),
+            self.draft.lines().len()
+        );
+        let mut spans = match self.mode {
+            Mode::Text => vec![Span::styled("TEXT", Style::new().fg(theme.peach))],
+            Mode::Stamp => vec![Span::styled("STAMP", Style::new().fg(theme.accent))],
+            Mode::Open => {
+                // 载入态:状态行即路径输入框,失败提示跟在后面。
+                let (before, after) = self.path.split();
+                let mut spans = vec![
+                    Span::styled("OPEN", Style::new().fg(theme.peach)),
+                    Span::styled("  file: ", Style::new().fg(theme.subtext)),
+                ];
+                spans.extend(cursor_spans(
+                    before.to_owned(),
+                    after,
+                    Style::new().fg(theme.text),
+                ));
+                if let Some(notice) = &self.notice {
+                    spans.push(Span::styled(
+                        format!("  {notice}"),
+                        Style::new().fg(theme.red),
+                    ));
+                }
+                return Line::from(spans);
+            }
+        };
+        spans.push(Span::styled(
+            format!("  {progress}"),
+            Style::new().fg(theme.subtext),
+        ));
+        if self.mode == Mode::Stamp {
+            if self.is_playing_target(ctx) {
+                spans.push(Span::styled(
+                    format!("  ♪ {}", stamp_label(ctx.lyric_position_ms())),
+                    Style::new().fg(theme.text),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    "  play this song to stamp",
+                    Style::new().fg(theme.red),
+                ));
+            }
+        }
+        Line::from(spans)
+    }
+
+    /// 一行草稿的渲染:时间戳列 + 文本;打轴光标行高亮。
+    fn draft_row(&self, idx: usize, theme: &Theme) -> Option<Line<'static>> {
+        let line = self.draft.lines().get(idx)?;
+        let stamp = line
+            .time_ms
+            .map_or_else(|| "[--:--.--]".to_owned(), stamp_label);
+        let focused = self.mode == Mode::Stamp && idx == self.draft.cursor();
+        let stamp_style = if line.time_ms.is_some() {
+            Style::new().fg(theme.subtext)
+        } else {
+            Style::new().fg(theme.overlay)
+        };
+        let text_style = if focused {
+            Style::new().fg(theme.accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::new().fg(theme.text)
+        };
+        let marker = if focused { "▶" } else { " " };
+        Some(Line::from(vec![
+            Span::styled(marker, Style::new().fg(theme.accent)),
+            Span::styled(format!("{stamp:<STAMP_W$}"), stamp_style),
+            Span::styled(line.text.clone(), text_style),
+        ]))
+    }
+}
+
+/// 单行输入框的通用编辑键:字符插入与光标移动。带 CONTROL 的字符键不进文本。
+///
+/// # Params:
+///   - `input`: 目标输入框
+///   - `key`: 按键
+fn edit_line(input: &mut LineInput, key: &KeyEvent) {
+    let req = match key.code {
+        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => return,
+        KeyCode::Char(c) => InputRequest::Insert(c),
+        KeyCode::Left => InputRequest::Left,
+        KeyCode::Right => InputRequest::Right,
+        KeyCode::Home => InputRequest::Home,
+        KeyCode::End => InputRequest::End,
+        _ => return,
+    };
+    input.apply(req);
+}
+
+/// 歌词时间 → `[mm:ss.cc]`(与落盘的 LRC 同精度)。
+fn stamp_label(ms: u64) -> String {
+    let (min, sec, cs) = (ms / 60_000, ms / 1_000 % 60, ms % 1_000 / 10);
+    format!("[{min:02}:{sec:02}.{cs:02}]")
+}
+
+impl Overlay for LyricEditorOverlay {
+    fn chrome(&self) -> Chrome {
+        Chrome {
+            pct_w: 60,
+            pct_h: 75,
+            min_w: 44,
+            min_h: 12,
+            max_w: 96,
+            max_h: 40,
+            animated: true,
+            dock: false,
+            anchor: None,
+            align: None,
+        }
+    }
+
+    fn block(&self, _ctx: &AppState, theme: &Theme, focused: bool) -> Block<'static> {
+        let border_color = if focused {
+            theme.accent
+        } else {
+            theme.surface1
+        };
+        base_block(theme)
+            .border_style(Style::new().fg(border_color))
+            .title(
+                Line::from(format!(" edit lyrics · {} ", self.song.name))
+                    .style(Style::new().fg(theme.subtext)),
+            )
+            .title_bottom(
+                Line::from(self.hint())
+                    .right_aligned()
+                    .style(Style::new().fg(theme.overlay)),
+            )
+    }
+
+    fn render_content(&self, buf: &mut Buffer, inner: Rect, ctx: &AppState, theme: &Theme) {
+        if inner.height < 3 || inner.width < 16 {
+            return;
+        }
+        let x = inner.x.saturating_add(1);
+        let w = inner.width.saturating_sub(2);
+        Paragraph::new(self.status_line(ctx, theme)).render(Rect::new(x, inner.y, w, 1), buf);
+
+        // 列表区:状态行下空一行起。文本态末尾多一行输入行,视口贴底跟随;打轴态视口让光标居中。
+        let top = inner.y.saturating_add(2);
+        let rows = usize::from(inner.height.saturating_sub(2));
+        let total = self.draft.lines().len();
+        let (focus, len) = match self.mode {
+            Mode::Text => (total, total.saturating_add(1)),
+            Mode::Stamp | Mode::Open => (self.draft.cursor(), total),
+        };
+        let first = focus.saturating_sub(rows / 2).min(len.saturating_sub(rows));
+        for (offset, idx) in (first..len).take(rows).enumerate() {
+            let Ok(dy) = u16::try_from(offset) else {
+                break;
+            };
+            let row = if idx == total {
+                let (before, after) = self.input.split();
+                let mut spans = vec![Span::raw(format!(" {:<STAMP_W$}", ""))];
+                spans.extend(cursor_spans(
+                    before.to_owned(),
+                    after,
+                    Style::new().fg(theme.text),
+                ));
+                Some(Line::from(spans))
+            } else {
+                self.draft_row(idx, theme)
+            };
+            if let Some(line) = row {
+                Paragraph::new(line).render(Rect::new(x, top.saturating_add(dy), w, 1), buf);
+            }
+        }
+    }
+
+    fn on_key(&mut self, key: &KeyEvent, ctx: &AppState) -> OverlayResponse {
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Esc) if self.mode == Mode::Open => {
+                self.notice = None;
+                self.mode = Mode::Text;
+                OverlayResponse::Consumed
+            }
+            (_, KeyCode::Esc) => OverlayResponse::Do(OverlayAction::CloseTop),
+            (KeyModifiers::CONTROL, KeyCode::Char('s')) => self.save(),
+            (_, KeyCode::Tab) => {
+                self.commit_input();
+                self.mode = match self.mode {
+                    Mode::Text => Mode::Stamp,
+                    Mode::Stamp | Mode::Open => Mode::Text,
+                };
+                OverlayResponse::Consumed
+            }
+            _ => match self.mode {
+                Mode::Text => self.on_text_key(key),
+                Mode::Stamp => self.on_stamp_key(key, ctx),
+                Mode::Open => self.on_open_key(key),
+            },
+        }
+    }
+
+    fn on_paste(&mut self, text: &str, _ctx: &AppState) -> bool {
+        match self.mode {
+            // 粘贴接在正在敲的一行后面,整段按行拆入草稿。
+            Mode::Text => {
+                let joined = format!("{}{text}", self.input.text());
+                self.input.clear();
+                self.draft.push_text(&joined);
+            }
+            // 打轴态粘贴:追加到草稿末尾,不动光标。
+            Mode::Stamp => self.draft.push_text(text),
+            // 路径是单行:只取首行。
+            Mode::Open => {
+                for c in text.lines().next().unwrap_or_default().chars() {
+                    self.path.apply(InputRequest::Insert(c));
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::LyricEditorOverlay;
+    use crate::components::popup::component::{
+        Overlay, OverlayAction, OverlayResponse, render_overlay,
+    };
+    use crate::render::theme::Theme;
+    use crate::runtime::state::AppState;
+
+    /// 喂一个无修饰按键。
+    fn press(o: &mut LyricEditorOverlay, code: KeyCode, ctx: &AppState) -> OverlayResponse {
+        o.on_key(&KeyEvent::new(code, KeyModifiers::empty()), ctx)
+    }
+
+    /// 无歌词的在播曲:文本态敲两行 → tab 进打轴 → 按播放位置打两轴 → ^s 产出 LRC。
+    /// 打轴态空格半穿透(留给暂停),文本态空格是字符。
+    #[test]
+    fn type_stamp_and_save() -> color_eyre::Result<()> {
+        let mut ctx = AppState::test_default()?;
+        let song = mineral_test::qianzai_song();
+        ctx.playback.track = Some(song.clone());
+        let mut o = LyricEditorOverlay::new(song, &ctx);
+        assert!(o.is_typing(), "无底稿从文本态起");
+        for c in "a b".chars() {
+            press(&mut o, KeyCode::Char(c), &ctx);
+        }
+        press(&mut o, KeyCode::Enter, &ctx);
+        press(&mut o, KeyCode::Char('c'), &ctx);
+        press(&mut o, KeyCode::Tab, &ctx);
+        assert!(!o.is_typing());
+        assert!(matches!(
+            press(&mut o, KeyCode::Char(' '), &ctx),
+            OverlayResponse::Pass
+        ));
+
+        ctx.playback.position_ms = 1_250;
+        press(&mut o, KeyCode::Enter, &ctx);
+        ctx.playback.position_ms = 4_200;
+        press(&mut o, KeyCode::Enter, &ctx);
+        let resp = o.on_key(
+            &KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            &ctx,
+        );
+        let OverlayResponse::Do(OverlayAction::SaveLyrics { lrc, .. }) = resp else {
+            return Err(color_eyre::eyre::eyre!("^s 应产出存盘意图"));
+        };
+        assert_eq!(lrc, "[00:01.25]a b\n[00:04.20]c");
+        Ok(())
+    }
+
+    /// 目标曲不在播:回车不打轴(时钟属于别的歌)。
+    #[test]
+    fn no_stamp_when_target_not_playing() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let mut o = LyricEditorOverlay::new(mineral_test::qianzai_song(), &ctx);
+        press(&mut o, KeyCode::Char('x'), &ctx);
+        press(&mut o, KeyCode::Tab, &ctx);
+        press(&mut o, KeyCode::Enter, &ctx);
+        assert_eq!(o.draft.stamped(), 0);
+        Ok(())
+    }
+
+    /// 打轴态渲染:现有逐字歌词作底稿(时间戳沿用),光标行带 `▶`、状态行带当前歌词时间。
+    #[test]
+    fn stamp_mode_renders_cursor_and_clock() -> color_eyre::Result<()> {
+        let mut terminal = Terminal::new(TestBackend::new(72, 16))?;
+        let mut ctx = AppState::test_default()?;
+        let song = mineral_test::qianzai_song();
+        let lyrics = mineral_test::qianzai_lyrics();
+        let third = lyrics
+            .lines
+            .iter()
+            .map(|l| l.kind.text().trim().to_owned())
+            .filter(|t| !t.is_empty())
+            .nth(2)
+            .unwrap_or_default();
+        ctx.library.lyrics.insert(song.id.clone(), lyrics);
+        ctx.playback.track = Some(song.clone());
+        ctx.playback.position_ms = 62_000;
+        let mut o = LyricEditorOverlay::new(song, &ctx);
+        press(&mut o, KeyCode::Down, &ctx);
+        press(&mut o, KeyCode::Down, &ctx);
+        terminal.draw(|f| {
+            render_overlay(
+                f,
+                f.area(),
+                &o,
+                /*scale*/ 1000,
+                /*focused*/ true,
+                &ctx,
+                &Theme::default(),
+            );
+        })?;
+        let buf = terminal.backend().buffer();
+        let rows = (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .filter_map(|x| buf.cell((x, y)).map(|c| c.symbol().to_owned()))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        assert!(
+            rows.iter()
+                .any(|r| r.contains("STAMP") && r.contains("[01:02.00]")),
+            "状态行:打轴态 + 当前歌词时间"
+        );
+        // 宽字符的后半格是空白,比对前把空白都剥掉。
+        let head = third
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .take(4)
+            .collect::<String>();
+        assert!(
+            rows.iter()
+                .map(|r| r.replace(' ', ""))
+                .any(|r| r.contains('▶') && r.contains(head.as_str())),
+            "光标落在第 3 行: {head}"
+        );
+        Ok(())
+    }
+
+    /// 括号粘贴接在正在敲的一行后面按行拆入;`^o` 载入 `.txt`,读不到时留在载入态给提示。
+    #[test]
+    fn paste_and_load_text_file() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let mut o = LyricEditorOverlay::new(mineral_test::qianzai_song(), &ctx);
+        press(&mut o, KeyCode::Char('a'), &ctx);
+        assert!(o.on_paste("b\nc", &ctx));
+        assert_eq!(o.draft_texts(), ["ab", "c"]);
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("lyrics.txt");
+        std::fs::write(&path, "d\n\ne\n")?;
+        o.on_key(
+            &KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+            &ctx,
+        );
+        assert!(o.is_typing(), "载入态也是文本输入");
+        for c in "missing.txt".chars() {
+            press(&mut o, KeyCode::Char(c), &ctx);
+        }
+        press(&mut o, KeyCode::Enter, &ctx);
+        assert!(o.notice.is_some(), "读不到的文件给提示");
+        assert!(o.mode == super::Mode::Open, "失败留在载入态");
+
+        o.path.clear();
+        assert!(o.on_paste(&format!("{}\n", path.display()), &ctx));
+        press(&mut o, KeyCode::Enter, &ctx);
+        assert!(o.mode == super::Mode::Text, "载入成功回文本态");
+        assert_eq!(o.draft_texts(), ["ab", "c", "d", "e"]);
+        Ok(())
+    }
+}
@@ -75,6 +75,24 @@ pub(crate) enum MenuAction {
         /// 模板作用的实体(构造菜单时捕获)。
         ctx: mineral_protocol::CopyTemplateCtx,
     },
+
+    /// 打开智能歌单编辑浮层(新建)。
+    NewSmartPlaylist,
+
+    /// 打开智能歌单编辑浮层(编辑该张,名字 / 表达式预填)。
+    EditSmartPlaylist(Box<Playlist>),
+
+    /// 打开歌单命名浮层(新建混源歌单)。
+    NewPlaylist,
+
+    /// 打开歌单命名浮层(给该张混源歌单改名,原名预填)。
+    RenamePlaylist(Box<Playlist>),
+
+    /// 弹出「加入歌单」选择器(列出可写入的混源歌单)。
+    AddToPlaylist(Box<Song>),
+
+    /// 提交一次歌单写操作(如删除智能歌单)。
+    PlaylistWrite(mineral_task::PlaylistWriteOp),
 }
 
 /// 容器实体(整体携带,供容器播放动作登记意图 / 取拉取目标)。区别于 `EntityRef`:只含有
@@ -10,9 +10,12 @@ mod component;
 mod confirm;
 mod disconnect;
 mod help;
+mod lyric_editor;
 mod menu;
 mod placement;
+mod playlist_name;
 mod queue;
+mod smart_playlist;
 mod stack;
 
 pub(crate) use component::{OverlayAction, OverlayResponse, render_overlay};
@@ -0,0 +1,233 @@
+//! 歌单命名浮层:居中 modal,单栏输入歌单名。
+//!
+//! 新建混源歌单与给它改名共用:确认时折成一次歌单写操作交 App 提交。空名就地忽略,
+//! 改名时名字没变直接关闭、不发写请求。
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mineral_model::{Playlist, PlaylistId, SourceKind};
+use mineral_task::PlaylistWriteOp;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget};
+
+use crate::components::popup::component::{
+    Chrome, Overlay, OverlayAction, OverlayResponse, base_block,
+};
+use crate::render::cursor::cursor_spans;
+use crate::render::theme::Theme;
+use crate::runtime::line_input::{InputRequest, LineInput};
+use crate::runtime::state::AppState;
+
+/// 歌单命名浮层。
+pub(crate) struct PlaylistNameOverlay {
+    /// 改名目标;`None` = 新建。
+    target: Option<PlaylistId>,
+
+    /// 打开时的名字(改名态据此判断是否真改了)。
+    original: String,
+
+    /// 名字栏。
+    input: LineInput,
+}
+
+impl PlaylistNameOverlay {
+    /// 新建混源歌单:名字栏为空。
+    pub(crate) fn create() -> Self {
+        Self {
+            target: None,
+            original: String::new(),
+            input: LineInput::new(),
+        }
+    }
+
+    /// 给已有歌单改名:原名预填。
+    ///
+    /// # Params:
+    ///   - `playlist`: 目标歌单
+    pub(crate) fn rename(playlist: &Playlist) -> Self {
+        let mut input = LineInput::new();
+        input.set_text(playlist.name.clone());
+        Self {
+            target: Some(playlist.id.clone()),
+            original: playlist.name.clone(),
+            input,
+        }
+    }
+
+    /// 确认:空名吞键留在浮层;改名未变直接关闭。
+    fn submit(&self) -> OverlayResponse {
+        let name = self.input.text().trim().to_owned();
+        if name.is_empty() {
+            return OverlayResponse::Consumed;
+        }
+        let op = match &self.target {
+            None => PlaylistWriteOp::Create {
+                source: SourceKind::MINERAL,
+                name,
+            },
+            Some(_) if name == self.original => {
+                return OverlayResponse::Do(OverlayAction::CloseTop);
+            }
+            Some(id) => PlaylistWriteOp::Rename {
+                id: id.clone(),
+                name,
+            },
+        };
+        OverlayResponse::Do(OverlayAction::PlaylistWrites(vec![op]))
+    }
+}
+
+impl Overlay for PlaylistNameOverlay {
+    fn chrome(&self) -> Chrome {
+        Chrome {
+            pct_w: 40,
+            pct_h: 20,
+            min_w: 36,
+            min_h: 5,
+            max_w: 64,
+            max_h: 5,
+            animated: true,
+            dock: false,
+            anchor: None,
+            align: None,
+        }
+    }
+
+    fn block(&self, _ctx: &AppState, theme: &Theme, focused: bool) -> Block<'static> {
+        let border_color = if focused {
+            theme.accent
+        } else {
+            theme.surface1
+        };
+        let title = if self.target.is_some() {
+            " rename playlist "
+        } else {
+            " new playlist "
+        };
+        base_block(theme)
+            .border_style(Style::new().fg(border_color))
+            .title(Line::from(title).style(Style::new().fg(theme.subtext)))
+            .title_bottom(
+                Line::from(" ⏎ save · esc cancel ")
+                    .right_aligned()
+                    .style(Style::new().fg(theme.overlay)),
+            )
+    }
+
+    fn render_content(&self, buf: &mut Buffer, inner: Rect, _ctx: &AppState, theme: &Theme) {
+        if inner.height < 1 || inner.width < 8 {
+            return;
+        }
+        let (before, after) = self.input.split();
+        let mut spans = vec![Span::styled("name  ", Style::new().fg(theme.accent))];
+        spans.extend(cursor_spans(
+            before.to_owned(),
+            after,
+            Style::new().fg(theme.text),
+        ));
+        let y = inner.y.saturating_add(inner.height.saturating_sub(1) / 2);
+        Paragraph::new(Line::from(spans)).render(
+            Rect::new(
+                inner.x.saturating_add(1),
+                y,
+                inner.width.saturating_sub(2),
+                1,
+            ),
+            buf,
+        );
+    }
+
+    fn on_key(&mut self, key: &KeyEvent, _ctx: &AppState) -> OverlayResponse {
+        let request = match key.code {
+            KeyCode::Esc => return OverlayResponse::Do(OverlayAction::CloseTop),
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => None,
+            KeyCode::Char(c) => Some(InputRequest::Insert(c)),
+            KeyCode::Backspace => Some(InputRequest::DeletePrev),
+            KeyCode::Left => Some(InputRequest::Left),
+            KeyCode::Right => Some(InputRequest::Right),
+            KeyCode::Home => Some(InputRequest::Home),
+            KeyCode::End => Some(InputRequest::End),
+            _ => None,
+        };
+        if let Some(request) = request {
+            self.input.apply(request);
+        }
+        OverlayResponse::Consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use mineral_model::{Playlist, PlaylistId, SourceKind};
+    use mineral_task::PlaylistWriteOp;
+
+    use super::PlaylistNameOverlay;
+    use crate::components::popup::component::{Overlay, OverlayAction, OverlayResponse};
+    use crate::runtime::state::AppState;
+
+    /// 按一个键。
+    fn press(o: &mut PlaylistNameOverlay, code: KeyCode, ctx: &AppState) -> OverlayResponse {
+        o.on_key(&KeyEvent::new(code, KeyModifiers::empty()), ctx)
+    }
+
+    /// 新建:空名回车不提交;敲入名字后产出聚合源建单。
+    #[test]
+    fn create_emits_mineral_create() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let mut o = PlaylistNameOverlay::create();
+        assert!(matches!(
+            press(&mut o, KeyCode::Enter, &ctx),
+            OverlayResponse::Consumed
+        ));
+        for c in "通勤".chars() {
+            press(&mut o, KeyCode::Char(c), &ctx);
+        }
+        let OverlayResponse::Do(OverlayAction::PlaylistWrites(ops)) =
+            press(&mut o, KeyCode::Enter, &ctx)
+        else {
+            return Err(color_eyre::eyre::eyre!("有名字应产出写操作"));
+        };
+        assert_eq!(
+            ops,
+            vec![PlaylistWriteOp::Create {
+                source: SourceKind::MINERAL,
+                name: "通勤".to_owned(),
+            }]
+        );
+        Ok(())
+    }
+
+    /// 改名:名字没变直接关闭,改了才发改名。
+    #[test]
+    fn rename_skips_unchanged_name() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let id = PlaylistId::new(SourceKind::MINERAL, "mix:1");
+        let playlist = Playlist::builder()
+            .id(id.clone())
+            .name("混听".to_owned())
+            .build();
+        let mut o = PlaylistNameOverlay::rename(&playlist);
+        assert!(matches!(
+            press(&mut o, KeyCode::Enter, &ctx),
+            OverlayResponse::Do(OverlayAction::CloseTop)
+        ));
+        press(&mut o, KeyCode::Char('!'), &ctx);
+        let OverlayResponse::Do(OverlayAction::PlaylistWrites(ops)) =
+            press(&mut o, KeyCode::Enter, &ctx)
+        else {
+            return Err(color_eyre::eyre::eyre!("改了名应产出写操作"));
+        };
+        assert_eq!(
+            ops,
+            vec![PlaylistWriteOp::Rename {
+                id,
+                name: "混听!".to_owned(),
+            }]
+        );
+        Ok(())
+    }
+}
@@ -427,7 +427,8 @@ mod tests {
         assert_eq!(fg_of("0"), Some(theme.accent), "光标行序号被高亮前景覆盖");
         let bilibili = resolve_source_color(&theme, ctx.cfg.sources(), SourceKind::BILIBILI);
         assert_eq!(fg_of("1"), Some(bilibili), "bilibili 行序号染 bilibili 色");
-        assert_eq!(fg_of("2"), Some(theme.subtext), "local 未配置色,退中立兜底");
+        let local = resolve_source_color(&theme, ctx.cfg.sources(), SourceKind::LOCAL);
+        assert_eq!(fg_of("2"), Some(local), "local 行序号染 local 色");
         Ok(())
     }
 
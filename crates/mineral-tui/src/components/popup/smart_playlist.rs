@@ -0,0 +1,357 @@
+//! 智能歌单编辑浮层:居中 modal,名字 + 表达式两栏。
+//!
+//! 新建与编辑共用:新建发一条显式的智能歌单建单,编辑按「改名 / 改表达式」折成一串歌单
+//! 写操作交 App 提交(聚合源的描述即表达式)。表达式先在本地校验,不合法或新名字已在列表里
+//! 就地提示、不发写请求;重名与存在性的最终裁决在 server(列表可能过期),失败经 toast 回来。
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mineral_model::{Playlist, PlaylistId};
+use mineral_task::PlaylistWriteOp;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Widget, Wrap};
+
+use crate::components::popup::component::{
+    Chrome, Overlay, OverlayAction, OverlayResponse, base_block,
+};
+use crate::render::cursor::cursor_spans;
+use crate::render::theme::Theme;
+use crate::runtime::line_input::{InputRequest, LineInput};
+use crate::runtime::state::AppState;
+
+/// 输入栏。
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// 歌单名。
+    Name,
+
+    /// 筛选表达式。
+    Query,
+}
+
+/// 智能歌单编辑浮层。
+pub(crate) struct SmartPlaylistOverlay {
+    /// 编辑目标;`None` = 新建。
+    target: Option<PlaylistId>,
+
+    /// 打开时的名字(编辑态据此判断要不要改名)。
+    original_name: String,
+
+    /// 名字栏。
+    name: LineInput,
+
+    /// 表达式栏。
+    query: LineInput,
+
+    /// 焦点栏。
+    field: Field,
+
+    /// 上次确认被拒的原因(改动任一栏即清)。
+    error: Option<String>,
+}
+
+impl SmartPlaylistOverlay {
+    /// 新建智能歌单:两栏皆空,焦点在名字栏。
+    pub(crate) fn create() -> Self {
+        Self {
+            target: None,
+            original_name: String::new(),
+            name: LineInput::new(),
+            query: LineInput::new(),
+            field: Field::Name,
+            error: None,
+        }
+    }
+
+    /// 编辑已有智能歌单:名字与表达式(= 歌单描述)预填,焦点在表达式栏。
+    ///
+    /// # Params:
+    ///   - `playlist`: 目标歌单
+    pub(crate) fn edit(playlist: &Playlist) -> Self {
+        let mut name = LineInput::new();
+        name.set_text(playlist.name.clone());
+        let mut query = LineInput::new();
+        query.set_text(playlist.description.clone());
+        Self {
+            target: Some(playlist.id.clone()),
+            original_name: playlist.name.clone(),
+            name,
+            query,
+            field: Field::Query,
+            error: None,
+        }
+    }
+
+    /// 焦点栏的输入。
+    fn input_mut(&mut self) -> &mut LineInput {
+        match self.field {
+            Field::Name => &mut self.name,
+            Field::Query => &mut self.query,
+        }
+    }
+
+    /// 确认:校验后折成写操作序列;不合法记下原因留在浮层里。
+    ///
+    /// # Params:
+    ///   - `ctx`: 全局状态(新建时据歌单库查重名)
+    fn submit(&mut self, ctx: &AppState) -> OverlayResponse {
+        let name = self.name.text().trim().to_owned();
+        let query = self.query.text().trim().to_owned();
+        if name.is_empty() {
+            self.error = Some("name is required".to_owned());
+            self.field = Field::Name;
+            return OverlayResponse::Consumed;
+        }
+        if let Err(e) = mineral_channel_mineral::check_smart_query(&query) {
+            self.error = Some(e.to_string());
+            self.field = Field::Query;
+            return OverlayResponse::Consumed;
+        }
+        let ops = match &self.target {
+            None => {
+                let id = mineral_channel_mineral::smart_playlist_id(&name);
+                if ctx.library.playlists.iter().any(|p| p.data.id == id) {
+                    self.error = Some(format!("smart playlist \"{name}\" already exists"));
+                    self.field = Field::Name;
+                    return OverlayResponse::Consumed;
+                }
+                vec![PlaylistWriteOp::CreateSmart { name, query }]
+            }
+            Some(id) => {
+                let mut ops = Vec::new();
+                if name != self.original_name {
+                    ops.push(PlaylistWriteOp::Rename {
+                        id: id.clone(),
+                        name,
+                    });
+                }
+                ops.push(PlaylistWriteOp::SetDescription {
+                    id: id.clone(),
+                    desc: query,
+                });
+                ops
+            }
+        };
+        OverlayResponse::Do(OverlayAction::PlaylistWrites(ops))
+    }
+
+    /// 一栏的渲染:标签 + 文本,焦点栏带光标。
+    fn field_line(&self, field: Field, theme: &Theme) -> Line<'static> {
+        let (label, input) = match field {
+            Field::Name => ("name  ", &self.name),
+            Field::Query => ("query ", &self.query),
+        };
+        let focused = self.field == field;
+        let label_style = if focused {
+            Style::new().fg(theme.accent)
+        } else {
+            Style::new().fg(theme.subtext)
+        };
+        let mut spans = vec![Span::styled(label, label_style)];
+        if focused {
+            let (before, after) = input.split();
+            spans.extend(cursor_spans(
+                before.to_owned(),
+                after,
+                Style::new().fg(theme.text),
+            ));
+        } else {
+            spans.push(Span::styled(
+                input.text().to_owned(),
+                Style::new().fg(theme.text),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Overlay for SmartPlaylistOverlay {
+    fn chrome(&self) -> Chrome {
+        Chrome {
+            pct_w: 60,
+            pct_h: 30,
+            min_w: 44,
+            min_h: 9,
+            max_w: 96,
+            max_h: 12,
+            animated: true,
+            dock: false,
+            anchor: None,
+            align: None,
+        }
+    }
+
+    fn block(&self, _ctx: &AppState, theme: &Theme, focused: bool) -> Block<'static> {
+        let border_color = if focused {
+            theme.accent
+        } else {
+            theme.surface1
+        };
+        let title = if self.target.is_some() {
+            " edit smart playlist "
+        } else {
+            " new smart playlist "
+        };
+        base_block(theme)
+            .border_style(Style::new().fg(border_color))
+            .title(Line::from(title).style(Style::new().fg(theme.subtext)))
+            .title_bottom(
+                Line::from(" tab switch · ⏎ next / save · esc cancel ")
+                    .right_aligned()
+                    .style(Style::new().fg(theme.overlay)),
+            )
+    }
+
+    fn render_content(&self, buf: &mut Buffer, inner: Rect, _ctx: &AppState, theme: &Theme) {
+        if inner.height < 5 || inner.width < 16 {
+            return;
+        }
+        let x = inner.x.saturating_add(1);
+        let w = inner.width.saturating_sub(2);
+        Paragraph::new(self.field_line(Field::Name, theme))
+            .render(Rect::new(x, inner.y, w, 1), buf);
+        Paragraph::new(self.field_line(Field::Query, theme))
+            .render(Rect::new(x, inner.y.saturating_add(1), w, 1), buf);
+        let (text, style) = match &self.error {
+            Some(e) => (e.clone(), Style::new().fg(theme.red)),
+            None => (
+                "e.g. loved unplayed:90d · top:50 within:30d · downloaded:7d".to_owned(),
+                Style::new().fg(theme.overlay),
+            ),
+        };
+        let rest = inner.height.saturating_sub(3);
+        Paragraph::new(Line::from(Span::styled(text, style)))
+            .wrap(Wrap { trim: true })
+            .render(Rect::new(x, inner.y.saturating_add(3), w, rest), buf);
+    }
+
+    fn on_key(&mut self, key: &KeyEvent, ctx: &AppState) -> OverlayResponse {
+        let request = match key.code {
+            KeyCode::Esc => return OverlayResponse::Do(OverlayAction::CloseTop),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.field = match self.field {
+                    Field::Name => Field::Query,
+                    Field::Query => Field::Name,
+                };
+                return OverlayResponse::Consumed;
+            }
+            KeyCode::Enter => {
+                return match self.field {
+                    Field::Name => {
+                        self.field = Field::Query;
+                        OverlayResponse::Consumed
+                    }
+                    Field::Query => self.submit(ctx),
+                };
+            }
+            KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => None,
+            KeyCode::Char(c) => Some(InputRequest::Insert(c)),
+            KeyCode::Backspace => Some(InputRequest::DeletePrev),
+            KeyCode::Left => Some(InputRequest::Left),
+            KeyCode::Right => Some(InputRequest::Right),
+            KeyCode::Home => Some(InputRequest::Home),
+            KeyCode::End => Some(InputRequest::End),
+            _ => None,
+        };
+        if let Some(request) = request
+            && self.input_mut().apply(request)
+        {
+            self.error = None;
+        }
+        OverlayResponse::Consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use mineral_model::{Playlist, PlaylistId, SourceKind};
+    use mineral_task::PlaylistWriteOp;
+
+    use super::SmartPlaylistOverlay;
+    use crate::components::popup::component::{Overlay, OverlayAction, OverlayResponse};
+    use crate::runtime::state::AppState;
+    use crate::runtime::view_model::PlaylistView;
+
+    /// 逐字敲入。
+    fn type_text(o: &mut SmartPlaylistOverlay, text: &str, ctx: &AppState) {
+        for c in text.chars() {
+            o.on_key(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()), ctx);
+        }
+    }
+
+    /// 回车。
+    fn enter(o: &mut SmartPlaylistOverlay, ctx: &AppState) -> OverlayResponse {
+        o.on_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()), ctx)
+    }
+
+    /// 新建:名字栏回车进表达式栏;非法表达式就地拒绝,改对后产出一条显式的智能歌单建单。
+    #[test]
+    fn create_validates_then_emits_create_smart() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let mut o = SmartPlaylistOverlay::create();
+        type_text(&mut o, "cold", &ctx);
+        enter(&mut o, &ctx);
+        type_text(&mut o, "loved unplayed:90", &ctx);
+        assert!(matches!(enter(&mut o, &ctx), OverlayResponse::Consumed));
+        assert!(o.error.is_some(), "缺单位的时长被拒");
+        type_text(&mut o, "d", &ctx);
+        assert!(o.error.is_none(), "改动即清错误");
+        let OverlayResponse::Do(OverlayAction::PlaylistWrites(ops)) = enter(&mut o, &ctx) else {
+            return Err(color_eyre::eyre::eyre!("合法表达式应产出写操作"));
+        };
+        assert_eq!(
+            ops,
+            vec![PlaylistWriteOp::CreateSmart {
+                name: "cold".to_owned(),
+                query: "loved unplayed:90d".to_owned(),
+            }]
+        );
+        Ok(())
+    }
+
+    /// 新建撞上已有的同键歌单:就地拒绝,不覆盖其表达式。
+    #[test]
+    fn create_rejects_taken_name() -> color_eyre::Result<()> {
+        let mut ctx = AppState::test_default()?;
+        let taken = Playlist::builder()
+            .id(PlaylistId::new(SourceKind::MINERAL, "smart:top"))
+            .name("renamed".to_owned())
+            .build();
+        ctx.library.playlists.push(PlaylistView { data: taken });
+        let mut o = SmartPlaylistOverlay::create();
+        type_text(&mut o, "top", &ctx);
+        enter(&mut o, &ctx);
+        type_text(&mut o, "top:50", &ctx);
+        assert!(matches!(enter(&mut o, &ctx), OverlayResponse::Consumed));
+        assert!(o.error.is_some(), "同键已存在被拒");
+        Ok(())
+    }
+
+    /// 编辑:预填名字与表达式;只改表达式不发改名。
+    #[test]
+    fn edit_only_sets_query_when_name_unchanged() -> color_eyre::Result<()> {
+        let ctx = AppState::test_default()?;
+        let id = PlaylistId::new(SourceKind::MINERAL, "smart:top");
+        let playlist = Playlist::builder()
+            .id(id.clone())
+            .name("top".to_owned())
+            .description("top:50".to_owned())
+            .build();
+        let mut o = SmartPlaylistOverlay::edit(&playlist);
+        type_text(&mut o, " within:30d", &ctx);
+        let OverlayResponse::Do(OverlayAction::PlaylistWrites(ops)) = enter(&mut o, &ctx) else {
+            return Err(color_eyre::eyre::eyre!("应产出写操作"));
+        };
+        assert_eq!(
+            ops,
+            vec![PlaylistWriteOp::SetDescription {
+                id,
+                desc: "top:50 within:30d".to_owned(),
+            }]
+        );
+        Ok(())
+    }
+}
@@ -12,8 +12,11 @@ use crate::components::popup::component::{Chrome, Overlay, OverlayResponse, rend
 use crate::components::popup::confirm::ConfirmOverlay;
 use crate::components::popup::disconnect::DisconnectOverlay;
 use crate::components::popup::help::HelpOverlay;
+use crate::components::popup::lyric_editor::LyricEditorOverlay;
 use crate::components::popup::menu::PopMenu;
+use crate::components::popup::playlist_name::PlaylistNameOverlay;
 use crate::components::popup::queue::QueueOverlay;
+use crate::components::popup::smart_playlist::SmartPlaylistOverlay;
 use crate::render::anim::Transition;
 use crate::render::theme::Theme;
 use crate::runtime::action::Action;
@@ -38,6 +41,15 @@ pub(crate) enum OverlayKind {
 
     /// 键位 cheatsheet。
     Help(HelpOverlay),
+
+    /// 歌词编辑器(LRC 打轴)。装箱:编辑缓冲远大于其余浮层,免得整个枚举被它撑大。
+    LyricEditor(Box<LyricEditorOverlay>),
+
+    /// 智能歌单编辑(名字 + 表达式)。
+    SmartPlaylist(SmartPlaylistOverlay),
+
+    /// 混源歌单命名(新建 / 改名)。
+    PlaylistName(PlaylistNameOverlay),
 }
 
 impl OverlayKind {
@@ -68,6 +80,31 @@ impl OverlayKind {
     ) -> Self {
         Self::Help(HelpOverlay::new(entries, close_hint))
     }
+
+    /// 歌词编辑器,以 `song` 已缓存的歌词为底稿。
+    pub(crate) fn lyric_editor(song: mineral_model::Song, ctx: &AppState) -> Self {
+        Self::LyricEditor(Box::new(LyricEditorOverlay::new(song, ctx)))
+    }
+
+    /// 新建智能歌单。
+    pub(crate) fn smart_playlist_create() -> Self {
+        Self::SmartPlaylist(SmartPlaylistOverlay::create())
+    }
+
+    /// 编辑已有智能歌单(名字 / 表达式预填)。
+    pub(crate) fn smart_playlist_edit(playlist: &mineral_model::Playlist) -> Self {
+        Self::SmartPlaylist(SmartPlaylistOverlay::edit(playlist))
+    }
+
+    /// 新建混源歌单。
+    pub(crate) fn playlist_name_create() -> Self {
+        Self::PlaylistName(PlaylistNameOverlay::create())
+    }
+
+    /// 给混源歌单改名(原名预填)。
+    pub(crate) fn playlist_name_rename(playlist: &mineral_model::Playlist) -> Self {
+        Self::PlaylistName(PlaylistNameOverlay::rename(playlist))
+    }
 }
 
 impl Overlay for OverlayKind {
@@ -78,6 +115,9 @@ impl Overlay for OverlayKind {
             Self::Disconnect(o) => o.chrome(),
             Self::Menu(o) => o.chrome(),
             Self::Help(o) => o.chrome(),
+            Self::LyricEditor(o) => o.chrome(),
+            Self::SmartPlaylist(o) => o.chrome(),
+            Self::PlaylistName(o) => o.chrome(),
         }
     }
 
@@ -88,6 +128,9 @@ impl Overlay for OverlayKind {
             Self::Disconnect(o) => o.block(ctx, theme, focused),
             Self::Menu(o) => o.block(ctx, theme, focused),
             Self::Help(o) => o.block(ctx, theme, focused),
+            Self::LyricEditor(o) => o.block(ctx, theme, focused),
+            Self::SmartPlaylist(o) => o.block(ctx, theme, focused),
+            Self::PlaylistName(o) => o.block(ctx, theme, focused),
         }
     }
 
@@ -98,6 +141,9 @@ impl Overlay for OverlayKind {
             Self::Disconnect(o) => o.render_content(buf, inner, ctx, theme),
             Self::Menu(o) => o.render_content(buf, inner, ctx, theme),
             Self::Help(o) => o.render_content(buf, inner, ctx, theme),
+            Self::LyricEditor(o) => o.render_content(buf, inner, ctx, theme),
+            Self::SmartPlaylist(o) => o.render_content(buf, inner, ctx, theme),
+            Self::PlaylistName(o) => o.render_content(buf, inner, ctx, theme),
         }
     }
 
@@ -108,6 +154,9 @@ impl Overlay for OverlayKind {
             Self::Disconnect(o) => o.on_key(key, ctx),
             Self::Menu(o) => o.on_key(key, ctx),
             Self::Help(o) => o.on_key(key, ctx),
+            Self::LyricEditor(o) => o.on_key(key, ctx),
+            Self::SmartPlaylist(o) => o.on_key(key, ctx),
+            Self::PlaylistName(o) => o.on_key(key, ctx),
         }
     }
 
@@ -118,6 +167,22 @@ impl Overlay for OverlayKind {
             Self::Disconnect(o) => o.on_action(action, ctx),
             Self::Menu(o) => o.on_action(action, ctx),
             Self::Help(o) => o.on_action(action, ctx),
+            Self::LyricEditor(o) => o.on_action(action, ctx),
+            Self::SmartPlaylist(o) => o.on_action(action, ctx),
+            Self::PlaylistName(o) => o.on_action(action, ctx),
+        }
+    }
+
+    fn on_paste(&mut self, text: &str, ctx: &AppState) -> bool {
+        match self {
+            Self::Queue(o) => o.on_paste(text, ctx),
+            Self::Confirm(o) => o.on_paste(text, ctx),
+            Self::Disconnect(o) => o.on_paste(text, ctx),
+            Self::Menu(o) => o.on_paste(text, ctx),
+            Self::Help(o) => o.on_paste(text, ctx),
+            Self::LyricEditor(o) => o.on_paste(text, ctx),
+            Self::SmartPlaylist(o) => o.on_paste(text, ctx),
+            Self::PlaylistName(o) => o.on_paste(text, ctx),
         }
     }
 }
@@ -228,6 +293,15 @@ impl OverlayStack {
         Some(top.kind.on_key(key, ctx))
     }
 
+    /// 把一次括号粘贴交给活跃栈顶浮层。
+    ///
+    /// # Return:
+    ///   栈顶浮层收下返回 `true`;无活跃浮层或其不收粘贴返回 `false`。
+    pub(crate) fn dispatch_paste(&mut self, text: &str, ctx: &AppState) -> bool {
+        self.active_top_mut()
+            .is_some_and(|top| top.kind.on_paste(text, ctx))
+    }
+
     /// 自底向上渲染所有浮层;活跃栈顶标记为 `focused`(影响边框色)。
     pub(crate) fn render(&self, frame: &mut Frame<'_>, area: Rect, ctx: &AppState, theme: &Theme) {
         let top = self.active_top_index();
@@ -280,6 +354,18 @@ impl OverlayStack {
             .any(|m| matches!(m.kind, OverlayKind::Disconnect(_)))
     }
 
+    /// 活跃栈顶是否在收文本输入(歌词编辑器文本态 / 智能歌单编辑 / 歌单命名);为真时
+    /// Shift+Q 逃生口让位给字符。
+    pub(crate) fn in_text_input(&self) -> bool {
+        self.active_top_index()
+            .and_then(|i| self.stack.get(i))
+            .is_some_and(|m| match &m.kind {
+                OverlayKind::LyricEditor(e) => e.is_typing(),
+                OverlayKind::SmartPlaylist(_) | OverlayKind::PlaylistName(_) => true,
+                _ => false,
+            })
+    }
+
     /// 把栈内 queue 浮层的光标钳到 `[0, len-1]`(队列变短后防越界)。
     pub(crate) fn clamp_queue(&mut self, len: usize) {
         for m in &mut self.stack {
@@ -315,4 +401,13 @@ impl OverlayStack {
             _ => None,
         })
     }
+
+    /// 测试用:栈内歌词编辑器草稿的行文本(无编辑器时 `None`)。
+    #[cfg(test)]
+    pub(crate) fn lyric_draft(&self) -> Option<Vec<String>> {
+        self.stack.iter().find_map(|m| match &m.kind {
+            OverlayKind::LyricEditor(e) => Some(e.draft_texts()),
+            _ => None,
+        })
+    }
 }
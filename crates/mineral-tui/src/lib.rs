@@ -108,7 +108,7 @@ pub async fn run(
             // 视作 binary 边缘);有声卡真出声,没有则降级 null。
             let audio_mode = mineral_server::resolve_audio_mode(
                 std::env::var_os("MINERAL_AUDIO_NULL").is_some(),
-                *cfg.audio().backend(),
+                cfg.audio().backend(),
             );
             let server = Server::spawn(
                 channels,
@@ -5,10 +5,10 @@
 
 use mineral_model::Song;
 use mineral_protocol::DownloadTarget;
-use mineral_task::TaskEvent;
+use mineral_task::{PlaylistWriteOp, Priority, TaskEvent, TaskKind, WriteError};
 
 use crate::app::App;
-use crate::components::popup::{ContainerRef, MenuAction};
+use crate::components::popup::{ContainerRef, MenuAction, OverlayKind};
 use crate::components::toast::notifications::{TextTint, tinted_text_item};
 use crate::runtime::action::ScriptSlot;
 use crate::runtime::state::{ActiveLayer, DetailFetch, View};
@@ -212,9 +212,56 @@ impl App {
                     }
                 }
             }
+            MenuAction::NewSmartPlaylist => {
+                self.overlays.push(OverlayKind::smart_playlist_create());
+            }
+            MenuAction::EditSmartPlaylist(playlist) => {
+                self.overlays
+                    .push(OverlayKind::smart_playlist_edit(&playlist));
+            }
+            MenuAction::NewPlaylist => {
+                self.overlays.push(OverlayKind::playlist_name_create());
+            }
+            MenuAction::RenamePlaylist(playlist) => {
+                self.overlays
+                    .push(OverlayKind::playlist_name_rename(&playlist));
+            }
+            MenuAction::AddToPlaylist(song) => self.open_add_to_playlist_menu(&song),
+            MenuAction::PlaylistWrite(op) => self.submit_playlist_writes(vec![op]),
+        }
+    }
+
+    /// 按序提交一串歌单写操作(同源写走串行 lane,改名先于设表达式落地)。结果经
+    /// [`TaskEvent::PlaylistWriteDone`] 回来,失败由 [`Self::report_playlist_write`] 出 toast;
+    /// 成功后 server 重拉该源歌单,列表随 `LibrarySnapshot` 刷新。
+    pub(crate) fn submit_playlist_writes(&self, ops: Vec<PlaylistWriteOp>) {
+        for op in ops {
+            self.client
+                .submit_task(TaskKind::PlaylistWrite(op), Priority::User);
         }
     }
 
+    /// 歌单写操作失败:翻译成用户语言 toast(失败不能只留在日志里)。成功无提示——列表
+    /// 刷新本身就是回执。
+    pub(crate) fn report_playlist_write(&mut self, event: &TaskEvent) {
+        let TaskEvent::PlaylistWriteDone {
+            error: Some(error), ..
+        } = event
+        else {
+            return;
+        };
+        let reason = match error {
+            WriteError::AuthRequired => "login required".to_owned(),
+            WriteError::RateLimited => "rate limited, try again later".to_owned(),
+            WriteError::NotSupported => "not supported by this source".to_owned(),
+            WriteError::Api { message, .. } | WriteError::Other(message) => message.clone(),
+        };
+        self.notifications.flash(tinted_text_item(
+            format!("playlist edit failed: {reason}"),
+            TextTint::Error,
+        ));
+    }
+
     /// 把文本写进系统剪贴板:成功 flash `Copied: …`(超长截断),失败 error toast。
     /// 句柄懒初始化、终身持有(理由见字段文档)。
     fn copy_to_clipboard(&mut self, text: &str) {
@@ -434,7 +434,7 @@ mod tests {
     }
 
     /// 来源徽标色解析逻辑:已配置来源(bilibili 在 sources 段配了固定品牌色)解析成配置色、
-    /// 不落中立兜底;未配置来源(LOCAL)落中立兜底(= subtext)。
+    /// 不落中立兜底;未配置来源(插件源)落中立兜底(= subtext)。
     ///
     /// 只钉逻辑不钉具体色值——default.lua 里 bilibili 的实际品牌色由 `defaults_snapshot` 快照钉,
     /// 改色只需 review 快照,不必动本测试。
@@ -449,9 +449,9 @@ mod tests {
             "已配置来源(bilibili)解析成其配置色,不走中立兜底"
         );
         assert_eq!(
-            resolve_source_color(&theme, sources, SourceKind::LOCAL),
+            resolve_source_color(&theme, sources, SourceKind::from_static("plugin", "plugin")),
             theme.subtext,
-            "未配色来源(LOCAL)走中立兜底(subtext)"
+            "未配色来源(插件源)走中立兜底(subtext)"
         );
         Ok(())
     }
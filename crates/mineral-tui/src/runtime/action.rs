@@ -32,6 +32,9 @@ pub enum Action {
     /// 打开键位 cheatsheet 浮层(已开时再按 = 关闭)。
     OpenHelp,
 
+    /// 为在播曲打开歌词编辑器浮层(LRC 打轴)。
+    OpenLyricEditor,
+
     /// 循环歌词副语言(原文 → 翻译 → 罗马音)。
     CycleLyricExtra,
 
@@ -71,6 +74,9 @@ pub enum Action {
     /// 相对 seek,秒数可负(含 Shift 大跨)。
     SeekRelative(SeekDelta),
 
+    /// 在播曲歌词时间偏移增减(`.` / `,`;步长见 `behavior.lyric_offset_step_ms`)。
+    NudgeLyricOffset(LyricOffsetDelta),
+
     /// 上一首 / 回开头(`p`)。
     PrevOrRestart,
 
@@ -147,3 +153,7 @@ pub struct VolumeDelta(pub i16);
 /// seek 增量(秒;可负)。
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SeekDelta(pub i64);
+
+/// 歌词时间偏移增量(毫秒;正 = 歌词推迟)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LyricOffsetDelta(pub i64);
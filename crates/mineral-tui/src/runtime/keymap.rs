@@ -9,7 +9,9 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use mineral_config::keys::{Key, KeyChord};
 use rustc_hash::FxHashMap;
 
-use super::action::{Action, ScriptSlot, ScrollStep, SeekDelta, SelectionMove, VolumeDelta};
+use super::action::{
+    Action, LyricOffsetDelta, ScriptSlot, ScrollStep, SeekDelta, SelectionMove, VolumeDelta,
+};
 use help::{CatalogBuilder, HelpEntry, HelpGroup};
 
 pub mod help;
@@ -76,6 +78,7 @@ impl Keymap {
         let vol = i16::from(*behavior.volume_step());
         let seek = i64::from(*behavior.seek_step_secs());
         let seek_big = i64::from(*behavior.seek_big_step_secs());
+        let lyric_step = i64::from(*behavior.lyric_offset_step_ms());
         let jump = usize::from(*behavior.list_jump_rows());
         // 脚本动作绑定:开放映射按名排序保证槽位确定性。
         let mut script_bindings = keys.script().iter().collect::<Vec<_>>();
@@ -145,6 +148,9 @@ impl Keymap {
                 open_queue => OpenQueue, "Queue";
                 enter_search => EnterSearch, "Search input";
                 cycle_lyric => CycleLyricExtra, "Lyric language";
+                lyric_later => NudgeLyricOffset(LyricOffsetDelta(lyric_step)), format!("Lyric offset ±{lyric_step}ms");
+                lyric_earlier => NudgeLyricOffset(LyricOffsetDelta(-lyric_step)), format!("Lyric offset ±{lyric_step}ms");
+                edit_lyrics => OpenLyricEditor, "Edit lyrics (LRC)";
                 quit => OpenQuitConfirm, "Quit";
                 open_help => OpenHelp, "This help";
             }
@@ -276,7 +282,9 @@ mod tests {
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use mineral_config::keys::KeyChord;
 
-    use super::super::action::{Action, ScrollStep, SeekDelta, SelectionMove, VolumeDelta};
+    use super::super::action::{
+        Action, LyricOffsetDelta, ScrollStep, SeekDelta, SelectionMove, VolumeDelta,
+    };
     use super::{Keymap, chord_from_event};
 
     /// 默认表的全部预期绑定(键字符串 → 动作),与重构前 `app.rs` 散落 match 逐键对齐。
@@ -289,6 +297,9 @@ mod tests {
             ("<Tab>", Action::OpenQueue),
             ("q", Action::OpenQuitConfirm),
             ("t", Action::CycleLyricExtra),
+            (".", Action::NudgeLyricOffset(LyricOffsetDelta(50))),
+            (",", Action::NudgeLyricOffset(LyricOffsetDelta(-50))),
+            ("e", Action::OpenLyricEditor),
             ("/", Action::EnterSearch),
             ("?", Action::OpenHelp),
             // ---- 播放控制(handle_playback_key) ----
@@ -361,7 +372,7 @@ mod tests {
     fn unbound_key_returns_none() -> color_eyre::Result<()> {
         let km = default_keymap()?;
         assert_eq!(km.lookup(KeyChord::parse("!")?), None);
-        assert_eq!(km.lookup(KeyChord::parse("e")?), None);
+        assert_eq!(km.lookup(KeyChord::parse("i")?), None);
         Ok(())
     }
 
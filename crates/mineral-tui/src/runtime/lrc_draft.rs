@@ -0,0 +1,237 @@
+//! 歌词编辑器(LRC 打轴)的纯编辑态:逐行文本 + 各行时间戳 + 打轴光标(零 ratatui 依赖)。
+//!
+//! 文本先整段贴入 / 逐行敲入,再边播边按键给光标行盖上当前歌词时间、光标随之下移——
+//! 与 [`LineInput`](super::line_input::LineInput) 同样只做纯态更新,按键解码在浮层边缘。
+
+use std::path::Path;
+
+use color_eyre::eyre::eyre;
+use mineral_model::{LyricLine, Lyrics, to_lrc_string};
+
+/// 草稿里的一行。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DraftLine {
+    /// 行文本。
+    pub(crate) text: String,
+
+    /// 已打的时间戳(歌词时间,毫秒);`None` = 尚未打轴。
+    pub(crate) time_ms: Option<u64>,
+}
+
+/// LRC 草稿:行序列 + 打轴光标。
+pub(crate) struct LrcDraft {
+    /// 行序列(空行不收)。
+    lines: Vec<DraftLine>,
+
+    /// 打轴光标:下一次打轴落在这一行(`0..=行数`;等于行数 = 已打到底)。
+    cursor: usize,
+}
+
+impl LrcDraft {
+    /// 以现有歌词为底稿:沿用各行文本与时间戳(已同步的歌也能局部重打),无歌词为空稿。
+    ///
+    /// # Params:
+    ///   - `lyrics`: 该曲当前歌词(未取到为 `None`)
+    pub(crate) fn from_lyrics(lyrics: Option<&Lyrics>) -> Self {
+        let lines = lyrics
+            .map(|l| {
+                l.lines
+                    .iter()
+                    .filter_map(|line| {
+                        let text = line.kind.text().trim().to_owned();
+                        (!text.is_empty()).then_some(DraftLine {
+                            text,
+                            time_ms: line.time_ms,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { lines, cursor: 0 }
+    }
+
+    /// 追加一段文本:按换行拆行、去首尾空白、丢空行(整段粘贴与逐行回车同一入口)。
+    ///
+    /// # Params:
+    ///   - `text`: 待追加文本
+    pub(crate) fn push_text(&mut self, text: &str) {
+        self.lines.extend(text.lines().filter_map(|raw| {
+            let text = raw.trim();
+            (!text.is_empty()).then(|| DraftLine {
+                text: text.to_owned(),
+                time_ms: None,
+            })
+        }));
+    }
+
+    /// 从纯文本文件(`.txt`)追加歌词:读全文后按 [`Self::push_text`] 拆行。
+    ///
+    /// # Params:
+    ///   - `path`: 文件路径
+    ///
+    /// # Return:
+    ///   新增的行数。
+    ///
+    /// # Errors
+    ///   文件读不到或非 UTF-8 时返回 `Err`;草稿不变。
+    pub(crate) fn load_file(&mut self, path: &Path) -> color_eyre::Result<usize> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("读取歌词文件 {} 失败: {e}", path.display()))?;
+        let before = self.lines.len();
+        self.push_text(&text);
+        Ok(self.lines.len().saturating_sub(before))
+    }
+
+    /// 弹出末行文本(文本态退格退回上一行继续改用);该行的时间戳随之丢弃。
+    pub(crate) fn pop_line(&mut self) -> Option<String> {
+        let line = self.lines.pop()?;
+        self.cursor = self.cursor.min(self.lines.len());
+        Some(line.text)
+    }
+
+    /// 给光标行打上时间戳并下移光标;已打到底时无事。
+    ///
+    /// # Params:
+    ///   - `time_ms`: 当前歌词时间(毫秒)
+    ///
+    /// # Return:
+    ///   是否真的打上了。
+    pub(crate) fn stamp(&mut self, time_ms: u64) -> bool {
+        let Some(line) = self.lines.get_mut(self.cursor) else {
+            return false;
+        };
+        line.time_ms = Some(time_ms);
+        self.cursor = self.cursor.saturating_add(1);
+        true
+    }
+
+    /// 撤回上一次打轴:光标上移一行并清掉该行时间戳(打早 / 打晚了重来)。
+    pub(crate) fn unstamp_prev(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+        if let Some(line) = self.lines.get_mut(self.cursor) {
+            line.time_ms = None;
+        }
+    }
+
+    /// 光标上下移一行(钳到 `0..=行数`),不改时间戳。
+    ///
+    /// # Params:
+    ///   - `down`: 下移为真
+    pub(crate) fn move_cursor(&mut self, down: bool) {
+        self.cursor = if down {
+            self.cursor.saturating_add(1).min(self.lines.len())
+        } else {
+            self.cursor.saturating_sub(1)
+        };
+    }
+
+    /// 行序列。
+    pub(crate) fn lines(&self) -> &[DraftLine] {
+        &self.lines
+    }
+
+    /// 打轴光标(`0..=行数`)。
+    pub(crate) fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 已打轴的行数。
+    pub(crate) fn stamped(&self) -> usize {
+        self.lines.iter().filter(|l| l.time_ms.is_some()).count()
+    }
+
+    /// 序列化成 LRC:已打轴行出 `[mm:ss.xx]text`,未打轴行保留为裸文本(解析时随前一行时间
+    /// 排序,不丢字);全空为 `None`。
+    pub(crate) fn to_lrc(&self) -> Option<String> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let text = self
+            .lines
+            .iter()
+            .map(|l| match l.time_ms {
+                Some(t) => to_lrc_string(&[LyricLine::timed(t, l.text.as_str())]),
+                None => l.text.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{LyricLine, Lyrics, parse_lrc};
+
+    use super::LrcDraft;
+
+    /// 贴入多行文本 → 逐行打轴 → 撤回一行重打;序列化后能被 LRC 解析器原样读回。
+    #[test]
+    fn paste_stamp_and_round_trip() {
+        let mut d = LrcDraft::from_lyrics(None);
+        d.push_text("第一句\n\n  第二句  \r\n第三句");
+        assert_eq!(d.lines().len(), 3, "空行丢弃、首尾空白去掉");
+        assert!(d.stamp(1_250));
+        assert!(d.stamp(9_999));
+        d.unstamp_prev();
+        assert_eq!(d.cursor(), 1, "撤回:光标回到上一行");
+        assert!(d.stamp(4_200));
+        assert_eq!(d.stamped(), 2);
+
+        let lrc = d.to_lrc().unwrap_or_default();
+        assert_eq!(lrc, "[00:01.25]第一句\n[00:04.20]第二句\n第三句");
+        let parsed = parse_lrc(&lrc);
+        let times = parsed.iter().map(|l| l.time_ms).collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            vec![Some(1_250), Some(4_200), None],
+            "未打轴行保留为裸文本"
+        );
+    }
+
+    /// 现有歌词作底稿:沿用时间戳、丢空行;打到底后再打无事;退格弹末行、光标随之钳回。
+    #[test]
+    fn seeds_from_lyrics_and_clamps() {
+        let lyrics = Lyrics {
+            lines: vec![
+                LyricLine::timed(500, "a"),
+                LyricLine::untimed(" "),
+                LyricLine::untimed("b"),
+            ],
+        };
+        let mut d = LrcDraft::from_lyrics(Some(&lyrics));
+        assert_eq!(d.stamped(), 1);
+        d.move_cursor(/*down*/ true);
+        d.move_cursor(/*down*/ true);
+        d.move_cursor(/*down*/ true);
+        assert_eq!(d.cursor(), 2, "光标钳到行数");
+        assert!(!d.stamp(1_000), "打到底后无行可打");
+        assert_eq!(d.pop_line().as_deref(), Some("b"));
+        assert_eq!(d.cursor(), 1, "弹行后光标钳回");
+        assert!(
+            LrcDraft::from_lyrics(None).to_lrc().is_none(),
+            "空稿不出 LRC"
+        );
+    }
+
+    /// 从 `.txt` 载入:按行追加、返回新增行数;读不到的文件报错且草稿不动。
+    #[test]
+    fn loads_plain_text_file() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("lyrics.txt");
+        std::fs::write(&path, "第一句\n\n第二句\n")?;
+        let mut d = LrcDraft::from_lyrics(None);
+        d.push_text("已有");
+        assert_eq!(d.load_file(&path)?, 2);
+        assert_eq!(
+            d.lines()
+                .iter()
+                .map(|l| l.text.as_str())
+                .collect::<Vec<&str>>(),
+            ["已有", "第一句", "第二句"]
+        );
+        assert!(d.load_file(&dir.path().join("missing.txt")).is_err());
+        assert_eq!(d.lines().len(), 3);
+        Ok(())
+    }
+}
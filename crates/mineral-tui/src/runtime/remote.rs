@@ -324,6 +324,12 @@ impl Client for RemoteClient {
     fn next_song(&self) {
         let _ = self.send_recv(Request::NextSong);
     }
+    fn nudge_lyric_offset(&self, delta_ms: i64) {
+        let _ = self.send_recv(Request::NudgeLyricOffset(delta_ms));
+    }
+    fn save_lyrics(&self, song_id: SongId, lrc: String) {
+        let _ = self.send_recv(Request::SaveLyrics { song_id, lrc });
+    }
     fn player_sync(&self, known: PlayerVersions) -> PlayerSync {
         match self.send_recv(Request::PlayerSync(known)) {
             Response::PlayerSync(s) => *s,
@@ -502,8 +502,13 @@ impl AppState {
                 self.apply_artist_albums(id, albums);
             }
             TaskEvent::AlbumDetailFetched { id, album } => self.apply_album_detail(id, album),
-            // 歌单写操作完结由后续里程碑(歌单管理)消费;先吞掉保持 match 穷尽。
+            // 歌单写操作完结:失败 toast 在 App 层出(通知层不在 state 里),成功随 server 重拉的
+            // LibrarySnapshot 体现,回执本身无需落状态。
             TaskEvent::PlaylistWriteDone { .. } => {}
+            // 本地歌单变化随 server 重拉后的 LibrarySnapshot 到达;回执本身无需落状态。
+            TaskEvent::LocalScanDone { .. } => {}
+            // 按 id 补元数据只给一次性调用方(CLI `queue add`)用,TUI 不发也不接。
+            TaskEvent::SongsDetailFetched { .. } => {}
         }
     }
 
@@ -584,7 +589,7 @@ impl AppState {
             return None;
         }
         let lines = self.current_lines()?;
-        let idx = mineral_model::current_line(lines, self.playback.position_ms)?;
+        let idx = mineral_model::current_line(lines, self.lyric_position_ms())?;
         lines.get(idx).map(|line| line.kind.text().into_owned())
     }
 
@@ -176,7 +176,7 @@ impl AppState {
                 .current_lines()
                 .and_then(|lines| usize::try_from(line).ok().and_then(|i| lines.get(i)))
                 .and_then(|line| line.time_ms)
-                .is_some_and(|t| self.playback.position_ms.saturating_sub(t) >= scroll_ms);
+                .is_some_and(|t| self.lyric_position_ms().saturating_sub(t) >= scroll_ms);
             let settled = cur_line == line && past_fade;
             let Some(g) = self.browse.lyric_view.scroll.as_mut() else {
                 return;
@@ -234,7 +234,7 @@ impl AppState {
     /// 当前播放位置对应的原文行索引(无时间戳 / 未进首句时落 `0`),作整数锚定行用。
     fn current_line_anchor(&self) -> i64 {
         self.current_lines()
-            .and_then(|lines| mineral_model::current_line(lines, self.playback.position_ms))
+            .and_then(|lines| mineral_model::current_line(lines, self.lyric_position_ms()))
             .and_then(|i| i64::try_from(i).ok())
             .unwrap_or(0)
     }
@@ -266,11 +266,12 @@ impl AppState {
             .and_then(|g| usize::try_from(g.target_line()).ok())
     }
 
-    /// 脱离态焦点行的起始时间(ms),给 Enter「跳到此行」的绝对 seek 用;附着态 / 焦点行
-    /// 无时间戳(无同步歌)均返回 `None`(无从跳)。
+    /// 脱离态焦点行起始处的播放位置(ms,已折算歌词偏移),给 Enter「跳到此行」的绝对
+    /// seek 用;附着态 / 焦点行无时间戳(无同步歌)均返回 `None`(无从跳)。
     pub(crate) fn lyric_focus_seek_target(&self) -> Option<u64> {
         let line = self.manual_lyric_focus_line()?;
-        self.current_lines()?.get(line)?.time_ms
+        let time_ms = self.current_lines()?.get(line)?.time_ms?;
+        Some(self.lyric_time_to_position(time_ms))
     }
 
     /// Enter 跳到焦点行后钉住锚点等 seek 落地:把脱离态换成 [`GlidePhase::AwaitSeek`] 定在
@@ -1,4 +1,6 @@
-//! 歌词显示态:面板显示档与脱离态(view)、全屏手动滚动的缓动平移(glide)。
+//! 歌词显示态:面板显示档与脱离态(view)、全屏手动滚动的缓动平移(glide)、
+//! per-song 时间偏移下的歌词时钟(offset)。
 
 pub mod glide;
+pub mod offset;
 pub mod view;
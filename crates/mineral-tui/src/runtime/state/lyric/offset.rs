@@ -0,0 +1,65 @@
+//! per-song 歌词时间偏移下的歌词时钟:歌词本体不改,定位 / wipe 改读「播放位置 − 偏移」。
+//!
+//! 偏移值由 server 持有并随 current 段同步进 [`LyricView`](super::view::LyricView);
+//! 按键只转发增量,不在本地乐观改写——新值一拍后随同步到达。
+
+use super::super::AppState;
+
+impl AppState {
+    /// 歌词时间轴上的当前位置:播放位置 − 偏移(钳到 0)。当前行定位 / 逐字 wipe /
+    /// 回锚判定一律读它,而非裸 `playback.position_ms`。
+    pub(crate) fn lyric_position_ms(&self) -> u64 {
+        shift(self.playback.position_ms, -self.browse.lyric_view.offset_ms)
+    }
+
+    /// 歌词时间 → 播放位置([`Self::lyric_position_ms`] 的逆):「跳到此行」的 seek 目标用。
+    ///
+    /// # Params:
+    ///   - `time_ms`: 歌词行时间戳
+    pub(crate) fn lyric_time_to_position(&self, time_ms: u64) -> u64 {
+        shift(time_ms, self.browse.lyric_view.offset_ms)
+    }
+}
+
+/// `t + delta`,两端饱和(不越过 0 / `u64::MAX`)。
+fn shift(t: u64, delta: i64) -> u64 {
+    if delta >= 0 {
+        t.saturating_add(delta.unsigned_abs())
+    } else {
+        t.saturating_sub(delta.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mineral_model::{LyricLine, Lyrics};
+    use mineral_test::qianzai_song;
+
+    use crate::runtime::state::AppState;
+
+    /// 歌词推迟 300ms:同一播放位置落在前一行;跳行 seek 目标随之后移,往返自洽。
+    #[test]
+    fn offset_shifts_lyric_clock_both_ways() -> color_eyre::Result<()> {
+        let mut s = AppState::test_default()?;
+        let song = qianzai_song();
+        s.library.lyrics.insert(
+            song.id.clone(),
+            Lyrics {
+                lines: vec![LyricLine::timed(1_000, "a"), LyricLine::timed(2_000, "b")],
+            },
+        );
+        s.playback.track = Some(song);
+        s.playback.position_ms = 2_100;
+        assert_eq!(s.active_title_lyric().as_deref(), Some("b"));
+
+        s.browse.lyric_view.offset_ms = 300;
+        assert_eq!(s.lyric_position_ms(), 1_800);
+        assert_eq!(s.active_title_lyric().as_deref(), Some("a"));
+        assert_eq!(s.lyric_time_to_position(2_000), 2_300);
+
+        s.browse.lyric_view.offset_ms = -5_000;
+        assert_eq!(s.lyric_position_ms(), 7_100);
+        assert_eq!(s.lyric_time_to_position(1_000), 0, "提前越过开头钳到 0");
+        Ok(())
+    }
+}
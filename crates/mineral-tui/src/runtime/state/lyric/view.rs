@@ -1,4 +1,4 @@
-//! 歌词面板的显示态:副歌词档(原文 / 翻译 / 罗马音)+ 全屏手动滚动的脱离态。
+//! 歌词面板的显示态:副歌词档(原文 / 翻译 / 罗马音)+ 全屏手动滚动的脱离态 + 时间偏移。
 //!
 //! 只持状态;操作它的方法(切档、滚动、回锚)因要跨读 playback/fullscreen/配置,
 //! 留在组合根([`AppState`](crate::runtime::state::AppState))。
@@ -18,6 +18,10 @@ pub struct LyricView {
 
     /// 手动滚动绑定的歌;换歌即清滚动偏移。
     pub(crate) scroll_song: Option<SongId>,
+
+    /// 当前歌的歌词时间偏移(毫秒;正 = 歌词推迟)。随 server 同步的 current 段整体更替,
+    /// 归属恒为当前歌(server 组段时已按当前曲过滤)。
+    pub(crate) offset_ms: i64,
 }
 
 impl LyricView {
@@ -27,6 +31,7 @@ impl LyricView {
             extra: LyricExtra::None,
             scroll: None,
             scroll_song: None,
+            offset_ms: 0,
         }
     }
 }
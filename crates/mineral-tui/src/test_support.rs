@@ -243,8 +243,14 @@ pub(crate) struct TestClient {
 
     /// `seek` 收到的目标位置(ms)序列(全屏歌词 Enter 跳到焦点行的绝对 seek 路径断言用)。
     pub(crate) seeks: Arc<Mutex<Vec<u64>>>,
+
+    /// `save_lyrics` 收到的 `(歌 id 全限定串, LRC)` 序列(歌词编辑器存盘路径断言用)。
+    pub(crate) saved_lyrics: SavedLyricsLog,
 }
 
+/// [`TestClient::saved_lyrics`] 的记录容器:`(歌 id 全限定串, LRC)` 序列。
+pub(crate) type SavedLyricsLog = Arc<Mutex<Vec<(String, String)>>>;
+
 /// [`TestClient::queue_ops`] 的记录容器:`(操作名, 歌 id 全限定串)` 序列。
 pub(crate) type QueueOpsLog = Arc<Mutex<Vec<(&'static str, String)>>>;
 
@@ -319,6 +325,12 @@ impl Client for TestClient {
     fn cycle_play_mode(&self) {}
     fn prev_or_restart(&self) {}
     fn next_song(&self) {}
+    fn nudge_lyric_offset(&self, _delta_ms: i64) {}
+    fn save_lyrics(&self, song_id: SongId, lrc: String) {
+        if let Ok(mut v) = self.saved_lyrics.lock() {
+            v.push((song_id.qualified(), lrc));
+        }
+    }
     fn player_sync(&self, _known: PlayerVersions) -> PlayerSync {
         PlayerSync::default()
     }
@@ -634,6 +646,17 @@ pub(crate) fn app_in_fullscreen_seek_probe() -> color_eyre::Result<(App, Arc<Mut
     Ok((seed_fullscreen(test_app_with(Arc::new(client))?), seeks))
 }
 
+/// 同 [`app_in_fullscreen`],但接一个记录 `save_lyrics` 的 [`TestClient`];额外返回该记录
+/// (歌词编辑器存盘路径断言用)。
+pub(crate) fn app_in_fullscreen_lyrics_probe() -> color_eyre::Result<(App, SavedLyricsLog)> {
+    let saved = SavedLyricsLog::default();
+    let client = TestClient {
+        saved_lyrics: Arc::clone(&saved),
+        ..TestClient::default()
+    };
+    Ok((seed_fullscreen(test_app_with(Arc::new(client))?), saved))
+}
+
 /// 把一个空 [`App`] 布置成稳态全屏在播态:缓存《潜在表明》逐字歌词、position 62s 落中段、
 /// queue 填 3 首、fullscreen 推到满值。[`app_in_fullscreen`] 系列共用。
 fn seed_fullscreen(mut app: App) -> App {
@@ -10,8 +10,9 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use crossterm::Command;
 use crossterm::event::{
-    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
-    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
 use crossterm::terminal::{
@@ -59,11 +60,13 @@ impl Tui {
             .map(|(x, y)| Position { x, y });
         // focus 事件(mode 1004):FocusGained/FocusLost 驱动顶栏失焦变灰。
         // 不支持的终端忽略该序列、永不发事件,UI 恒按聚焦渲染。
+        // 括号粘贴(mode 2004):整段粘贴作一个 Paste 事件到达,不再拆成连串按键。
         execute!(
             io::stdout(),
             EnterAlternateScreen,
             EnableMouseCapture,
-            EnableFocusChange
+            EnableFocusChange,
+            EnableBracketedPaste
         )?;
         // kitty keyboard protocol:让 Shift+arrow / Ctrl+组合键 都带显式 modifier 上来。
         // 不开的话 kitty 默认把 Shift+Left 当裸 Left 报,丢了 SHIFT modifier
@@ -155,7 +158,8 @@ fn restore_terminal(title_pushed: &Arc<AtomicBool>) -> io::Result<()> {
             io::stdout(),
             LeaveAlternateScreen,
             DisableMouseCapture,
-            DisableFocusChange
+            DisableFocusChange,
+            DisableBracketedPaste
         )?;
     }
     Ok(())
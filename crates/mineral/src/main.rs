@@ -89,8 +89,19 @@ pub(crate) fn serve_blocking() -> color_eyre::Result<()> {
         } = loaded;
         let script = mineral_server::ScriptParts::new(vm, host, cmd_tx, cmd_rx, push_tx, push_rx);
         let persist = open_persist().await;
-        let channels = build_channels(persist.clone(), config.sources())?;
-        mineral_cli::serve_run(channels, persist, config, script, config_tree, config_path).await
+        // stats.db 单连接池:聚合 channel(智能歌单读)与埋点 recorder(写)共用同一句柄。
+        let stats = mineral_cli::open_stats().await;
+        let channels = build_channels(persist.clone(), stats.clone(), &config)?;
+        mineral_cli::serve_run(
+            channels,
+            persist,
+            stats,
+            config,
+            script,
+            config_tree,
+            config_path,
+        )
+        .await
     });
     if let Err(e) = &result {
         mineral_log::error!(target: "daemon", error = mineral_log::chain(e), "daemon 启动失败");
@@ -155,7 +166,7 @@ async fn run_tui(connect: bool, in_proc: bool) -> color_eyre::Result<()> {
     let (channels, persist) = match launch {
         Launch::InProc => {
             let p = mineral_persist::ServerStore::disabled();
-            let ch = build_channels(p.clone(), config.sources())?;
+            let ch = build_channels(p.clone(), mineral_stats::StatsStore::disabled(), &config)?;
             (ch, p)
         }
         Launch::Auto | Launch::Connect => (Vec::new(), mineral_persist::ServerStore::disabled()),
@@ -178,25 +189,63 @@ fn log_config_warnings(warnings: &[mineral_config::ConfigWarning]) {
     }
 }
 
-/// 按可用凭证 / 编译 feature 收集所有 channel(目前是 mineral 聚合 + netease + bilibili
-/// + 可选 mock)。
+/// 智能歌单的 stats 查询口径:有效播放阈值同 `stats report`(榜长不参与求值)。
+///
+/// # Params:
+///   - `report`: `stats.report` 段
+///
+/// # Return:
+///   查询期口径;阈值溢出 i64 时报错。
+fn smart_report_options(
+    report: &mineral_config::ReportConfig,
+) -> color_eyre::Result<mineral_stats::ReportOptions> {
+    let min_listen_ms = i64::try_from(*report.min_listen_secs())
+        .wrap_err("stats.report.min_listen_secs 溢出 i64")?
+        .saturating_mul(1000);
+    let top_limit =
+        i64::try_from(*report.top_limit()).wrap_err("stats.report.top_limit 溢出 i64")?;
+    Ok(mineral_stats::ReportOptions::builder()
+        .min_listen_ms(min_listen_ms)
+        .top_limit(top_limit)
+        .build())
+}
+
+/// 按可用凭证 / 配置 / 编译 feature 收集所有 channel(目前是 mineral 聚合 + netease +
+/// bilibili + local + 可选 mock)。
 ///
 /// **单个 channel 失败不阻塞**:某源构建失败(如凭证损坏)只 warn + 跳过,不拖垮其他源
 /// 或 daemon;空 channels 也是合法状态(没登录任何源),由 TUI 空状态提示兜。
 ///
 /// # Params:
 ///   - `persist`: 持久化句柄,注入各 channel 供登录状态/统计落盘使用。
-///   - `sources`: 音乐源段配置(netease 的 timeout / proxy / 并发)。
+///   - `stats`: stats.db 句柄(聚合源的智能歌单求值用)。
+///   - `config`: 全局配置(取音乐源段与 `stats.report` 的有效播放阈值)。
 fn build_channels(
     persist: mineral_persist::ServerStore,
-    sources: &mineral_config::SourcesConfig,
+    stats: mineral_stats::StatsStore,
+    config: &mineral_config::Config,
 ) -> color_eyre::Result<Vec<Arc<dyn MusicChannel>>> {
+    let sources = config.sources();
     let mut channels = Vec::<Arc<dyn MusicChannel>>::new();
-    // 聚合源(全源收藏投影):纯 persist 投影、无凭证依赖,恒注册。放列表首位,
+    // 聚合源(全源收藏 + 智能歌单):纯 persist / stats 投影、无凭证依赖,恒注册。放列表首位,
     // 其歌单列表(本地 SQL)最先就绪,聚合收藏歌单自然排 sidebar 顶部。
-    channels.push(Arc::new(mineral_channel_mineral::MineralChannel::new(
-        persist.clone(),
-    )));
+    channels.push(Arc::new(
+        mineral_channel_mineral::MineralChannel::new(persist.clone()).with_smart(
+            stats,
+            smart_report_options(config.stats().report())?,
+            sources
+                .mineral()
+                .smart_playlists()
+                .iter()
+                .map(|p| mineral_channel_mineral::SmartPlaylistDef {
+                    name: p.name().clone(),
+                    query: p.query().clone(),
+                })
+                .collect(),
+        ),
+    ));
+    // 本地源先构造(借 persist 取索引),注册顺序不变:仍排在远端源之后。
+    let local = build_local(&persist, sources.local());
     match build_netease(persist, sources.netease()) {
         Ok(Some(c)) => channels.push(c),
         Ok(None) => mineral_log::info!(target: "channel", "netease 未登录,跳过"),
@@ -215,6 +264,16 @@ fn build_channels(
             "bilibili channel 构建失败,跳过(不影响其他源 / daemon)"
         ),
     }
+    // 本地源只在配了扫描根时注册:没配根的用户不该在源列表里看到一个空源。
+    match local {
+        Ok(Some(c)) => channels.push(c),
+        Ok(None) => mineral_log::info!(target: "channel", "local 未配置扫描根,跳过"),
+        Err(e) => mineral_log::warn!(
+            target: "channel",
+            error = mineral_log::chain(&e),
+            "local channel 构建失败,跳过(不影响其他源 / daemon)"
+        ),
+    }
     #[cfg(feature = "mock")]
     channels.push(build_mock());
     Ok(channels)
@@ -262,6 +321,26 @@ fn build_netease(
     Ok(Some(arc))
 }
 
+/// 构造本地曲库 channel;`roots` 为空返回 `Ok(None)`(未启用本地源,正常)。
+///
+/// 构造不扫描(曲库首次被查询时才扫),故这里只做配置映射,不碰磁盘。
+///
+/// # Params:
+///   - `persist`: 持久化句柄(本地曲库索引,增量重扫用)。
+///   - `local`: 本地源段配置(扫描根)。
+fn build_local(
+    persist: &mineral_persist::ServerStore,
+    local: &mineral_config::LocalSection,
+) -> color_eyre::Result<Option<Arc<dyn MusicChannel>>> {
+    if local.roots().is_empty() {
+        return Ok(None);
+    }
+    let lc = mineral_cli::local_config_from(local).wrap_err("解析本地源扫描根失败")?;
+    let arc: Arc<dyn MusicChannel> =
+        Arc::new(mineral_channel_local::LocalChannel::new(lc, persist));
+    Ok(Some(arc))
+}
+
 /// 构造一个永远在线的假数据 channel,离线开发用(`--features mock`)。
 #[cfg(feature = "mock")]
 fn build_mock() -> Arc<dyn MusicChannel> {